@@ -0,0 +1,6 @@
+fn main() {
+    // Models and service traits of the gRPC contract, included by `src/grpc`
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/gateway.proto")
+        .expect("proto/gateway.proto could not be compiled");
+}
@@ -0,0 +1,50 @@
+/// Where the upstream resource of a fixture is served from
+pub enum Source {
+    ConfigService,
+    /// The transaction service of the chain, as returned by the config service
+    TransactionService {
+        chain_id: &'static str,
+    },
+}
+
+/// A file in `src/tests/json` that mirrors a single upstream response.
+/// Fixtures that were edited by hand to cover edge cases are not listed, as recording them
+/// would drop the edits.
+pub struct Fixture {
+    pub file: &'static str,
+    pub source: Source,
+    pub path: &'static str,
+}
+
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        file: "chains/polygon.json",
+        source: Source::ConfigService,
+        path: "/api/v1/chains/137/",
+    },
+    Fixture {
+        file: "safe_apps/polygon_safe_apps.json",
+        source: Source::ConfigService,
+        path: "/api/v1/safe-apps/?chainId=137",
+    },
+    Fixture {
+        file: "master_copies/polygon_master_copies.json",
+        source: Source::TransactionService { chain_id: "137" },
+        path: "/api/v1/about/master-copies/",
+    },
+    Fixture {
+        file: "safes/with_guard_safe_v130.json",
+        source: Source::TransactionService { chain_id: "4" },
+        path: "/api/v1/safes/0x4cb09344de5bCCD45F045c5Defa0E0452869FF0f/",
+    },
+    Fixture {
+        file: "tokens/dai.json",
+        source: Source::TransactionService { chain_id: "4" },
+        path: "/api/v1/tokens/0x5592EC0cfb4dbc12D3aB100b257153436a1f0FEa/",
+    },
+    Fixture {
+        file: "tokens/usdt.json",
+        source: Source::TransactionService { chain_id: "4" },
+        path: "/api/v1/tokens/0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02/",
+    },
+];
@@ -0,0 +1,139 @@
+//! Records the fixtures in `src/tests/json` from the live services configured in `.env`
+//! (`CONFIG_SERVICE_URI`, transaction services are taken from the chain configurations), so that
+//! they don't drift from the upstream schemas. Only meant for development:
+//!
+//! `cargo run --bin record-fixtures` rewrites the fixtures and prints a schema diff report,
+//! `cargo run --bin record-fixtures -- --check` only prints the report and fails if any schema
+//! changed.
+mod fixtures;
+mod sanitize;
+mod schema;
+
+#[cfg(test)]
+mod tests;
+
+use crate::fixtures::{Fixture, Source, FIXTURES};
+use crate::sanitize::sanitize;
+use crate::schema::{diff, schema_of};
+use dotenv::dotenv;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+const FIXTURES_DIR: &str = "src/tests/json";
+
+#[rocket::main]
+async fn main() {
+    dotenv().ok();
+    let check_only = env::args().any(|arg| arg == "--check");
+    let config_service_uri =
+        env::var("CONFIG_SERVICE_URI").expect("CONFIG_SERVICE_URI must be set to record fixtures");
+    let fixtures_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURES_DIR);
+    let client = reqwest::Client::new();
+
+    let mut transaction_services: HashMap<&'static str, String> = HashMap::new();
+    let mut failed = false;
+    let mut changed = false;
+    for fixture in FIXTURES {
+        let base_uri = match base_uri(
+            &client,
+            &config_service_uri,
+            &fixture.source,
+            &mut transaction_services,
+        )
+        .await
+        {
+            Ok(base_uri) => base_uri,
+            Err(error) => {
+                eprintln!("{}: {}", fixture.file, error);
+                failed = true;
+                continue;
+            }
+        };
+        match record(&client, &base_uri, fixture, &fixtures_dir, check_only).await {
+            Ok(has_changes) => changed |= has_changes,
+            Err(error) => {
+                eprintln!("{}: {}", fixture.file, error);
+                failed = true;
+            }
+        }
+    }
+
+    if failed || (check_only && changed) {
+        std::process::exit(1);
+    }
+}
+
+async fn base_uri(
+    client: &reqwest::Client,
+    config_service_uri: &str,
+    source: &Source,
+    transaction_services: &mut HashMap<&'static str, String>,
+) -> Result<String, String> {
+    match source {
+        Source::ConfigService => Ok(config_service_uri.to_string()),
+        Source::TransactionService { chain_id } => {
+            if let Some(transaction_service) = transaction_services.get(chain_id) {
+                return Ok(transaction_service.to_string());
+            }
+            let url = format!("{}/api/v1/chains/{}/", config_service_uri, chain_id);
+            let chain_info = fetch(client, &url).await?;
+            let transaction_service = chain_info
+                .get("transactionService")
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("No transaction service configured for chain {}", chain_id))?
+                .trim_end_matches('/')
+                .to_string();
+            transaction_services.insert(*chain_id, transaction_service.to_string());
+            Ok(transaction_service)
+        }
+    }
+}
+
+/// Returns whether the schema of the fixture changed
+async fn record(
+    client: &reqwest::Client,
+    base_uri: &str,
+    fixture: &Fixture,
+    fixtures_dir: &Path,
+    check_only: bool,
+) -> Result<bool, String> {
+    let mut recorded = fetch(client, &format!("{}{}", base_uri, fixture.path)).await?;
+    sanitize(&mut recorded);
+
+    let file: PathBuf = fixtures_dir.join(fixture.file);
+    let previous: Value = fs::read_to_string(&file)
+        .map_err(|error| error.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|error| error.to_string()))
+        .unwrap_or(Value::Null);
+
+    let changes = diff(&schema_of(&previous), &schema_of(&recorded));
+    if changes.is_empty() {
+        println!("{}: schema unchanged", fixture.file);
+    } else {
+        println!("{}: {} schema changes", fixture.file, changes.len());
+        changes.iter().for_each(|change| println!("    {}", change));
+    }
+
+    if !check_only {
+        let json = serde_json::to_string_pretty(&recorded).map_err(|error| error.to_string())?;
+        fs::write(&file, format!("{}\n", json)).map_err(|error| error.to_string())?;
+    }
+    Ok(!changes.is_empty())
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<Value, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|error| error.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("{} responded with {}", url, response.status()));
+    }
+    response
+        .json::<Value>()
+        .await
+        .map_err(|error| error.to_string())
+}
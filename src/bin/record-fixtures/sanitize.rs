@@ -0,0 +1,48 @@
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+pub const REDACTED: &str = "REDACTED";
+
+/// Keys whose values are credentials, regardless of their content
+const SECRET_KEYS: &[&str] = &["apiKey", "api_key", "authorization", "token", "password"];
+
+lazy_static! {
+    // Path segments that look like api keys (e.g. `/v3/<key>`)
+    static ref URL_KEY_SEGMENT: Regex = Regex::new(r"/([0-9A-Za-z_\-]{32,})(/|$)").unwrap();
+    static ref URL_QUERY: Regex = Regex::new(r"\?.*$").unwrap();
+}
+
+/// Removes credentials from recorded responses, so that they can be committed as fixtures
+pub fn sanitize(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if value.is_string() && SECRET_KEYS.contains(&key.as_str()) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    sanitize(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(sanitize),
+        Value::String(string) if string.starts_with("http") => {
+            *string = sanitize_url(string);
+        }
+        _ => {}
+    }
+}
+
+/// Query strings are dropped, path segments are kept if they are addresses or hashes
+pub fn sanitize_url(url: &str) -> String {
+    let url = URL_QUERY.replace(url, "");
+    URL_KEY_SEGMENT
+        .replace_all(&url, |captures: &Captures| {
+            if captures[1].starts_with("0x") {
+                captures[0].to_string()
+            } else {
+                format!("/{}{}", REDACTED, &captures[2])
+            }
+        })
+        .to_string()
+}
@@ -0,0 +1,84 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Json paths of a document mapped to the type found there. Array elements share the `[]` path.
+pub type Schema = BTreeMap<String, &'static str>;
+
+pub fn schema_of(value: &Value) -> Schema {
+    let mut schema = Schema::new();
+    collect("$", value, &mut schema);
+    schema
+}
+
+fn collect(path: &str, value: &Value, schema: &mut Schema) {
+    let value_type = match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(values) => {
+            let element_path = format!("{}[]", path);
+            values
+                .iter()
+                .for_each(|value| collect(&element_path, value, schema));
+            "array"
+        }
+        Value::Object(map) => {
+            map.iter()
+                .for_each(|(key, value)| collect(&format!("{}.{}", path, key), value, schema));
+            "object"
+        }
+    };
+    // Elements of different types (e.g. nullable fields within a list) are all reported
+    schema
+        .entry(path.to_string())
+        .and_modify(|current| {
+            if *current != value_type && *current != "mixed" {
+                *current = "mixed"
+            }
+        })
+        .or_insert(value_type);
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SchemaChange {
+    Added(String, &'static str),
+    Removed(String, &'static str),
+    Changed(String, &'static str, &'static str),
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::Added(path, value_type) => write!(f, "+ {}: {}", path, value_type),
+            SchemaChange::Removed(path, value_type) => write!(f, "- {}: {}", path, value_type),
+            SchemaChange::Changed(path, previous, current) => {
+                write!(f, "~ {}: {} -> {}", path, previous, current)
+            }
+        }
+    }
+}
+
+/// Changes from the schema of the committed fixture to the schema of the recorded response
+pub fn diff(previous: &Schema, current: &Schema) -> Vec<SchemaChange> {
+    let mut changes: Vec<SchemaChange> = previous
+        .iter()
+        .filter_map(|(path, previous_type)| match current.get(path) {
+            None => Some(SchemaChange::Removed(path.to_string(), previous_type)),
+            Some(current_type) if current_type != previous_type => Some(SchemaChange::Changed(
+                path.to_string(),
+                previous_type,
+                current_type,
+            )),
+            _ => None,
+        })
+        .collect();
+    changes.extend(
+        current
+            .iter()
+            .filter(|(path, _)| !previous.contains_key(*path))
+            .map(|(path, current_type)| SchemaChange::Added(path.to_string(), current_type)),
+    );
+    changes
+}
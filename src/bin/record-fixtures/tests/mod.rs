@@ -0,0 +1,95 @@
+use crate::sanitize::{sanitize, sanitize_url};
+use crate::schema::{diff, schema_of, SchemaChange};
+use serde_json::json;
+
+#[test]
+fn sanitize_url_redacts_api_keys() {
+    assert_eq!(
+        sanitize_url("https://mainnet.infura.io/v3/9aa3d95b3bc440fa88ea12eaa4456161"),
+        "https://mainnet.infura.io/v3/REDACTED"
+    );
+    assert_eq!(
+        sanitize_url("https://rpc.example.com/?apikey=secret"),
+        "https://rpc.example.com/"
+    );
+}
+
+#[test]
+fn sanitize_url_keeps_addresses() {
+    let logo_uri = "https://gnosis-safe-token-logos.s3.amazonaws.com/0x5592EC0cfb4dbc12D3aB100b257153436a1f0FEa.png";
+    let safe_uri = "https://example.com/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/";
+
+    assert_eq!(sanitize_url(logo_uri), logo_uri);
+    assert_eq!(sanitize_url(safe_uri), safe_uri);
+}
+
+#[test]
+fn sanitize_redacts_secret_keys() {
+    let mut value = json!({
+        "rpcUri": {
+            "authentication": "API_KEY_PATH",
+            "value": "https://rinkeby.infura.io/v3/9aa3d95b3bc440fa88ea12eaa4456161"
+        },
+        "apiKey": "secret",
+        "token": { "name": "Dai" },
+        "owners": ["0x1230B3d59858296A31053C1b8562Ecf89A2f888b"]
+    });
+
+    sanitize(&mut value);
+
+    assert_eq!(
+        value,
+        json!({
+            "rpcUri": {
+                "authentication": "API_KEY_PATH",
+                "value": "https://rinkeby.infura.io/v3/REDACTED"
+            },
+            "apiKey": "REDACTED",
+            "token": { "name": "Dai" },
+            "owners": ["0x1230B3d59858296A31053C1b8562Ecf89A2f888b"]
+        })
+    );
+}
+
+#[test]
+fn schema_merges_array_elements() {
+    let value = json!({
+        "results": [
+            { "nonce": 1, "executor": null },
+            { "nonce": 2, "executor": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b" }
+        ]
+    });
+
+    let schema = schema_of(&value);
+
+    assert_eq!(schema.get("$"), Some(&"object"));
+    assert_eq!(schema.get("$.results"), Some(&"array"));
+    assert_eq!(schema.get("$.results[].nonce"), Some(&"number"));
+    assert_eq!(schema.get("$.results[].executor"), Some(&"mixed"));
+}
+
+#[test]
+fn schema_diff_reports_added_removed_and_changed_paths() {
+    let previous = schema_of(&json!({ "nonce": 1, "guard": null, "version": "1.3.0" }));
+    let current = schema_of(&json!({ "nonce": "1", "version": "1.3.0", "modules": [] }));
+
+    let changes = diff(&previous, &current);
+
+    assert_eq!(
+        changes,
+        vec![
+            SchemaChange::Removed(String::from("$.guard"), "null"),
+            SchemaChange::Changed(String::from("$.nonce"), "number", "string"),
+            SchemaChange::Added(String::from("$.modules"), "array"),
+        ]
+    );
+    assert_eq!(changes[1].to_string(), "~ $.nonce: number -> string");
+}
+
+#[test]
+fn schema_diff_empty_for_same_shape() {
+    let previous = schema_of(&json!({ "nonce": 1, "owners": ["0x1"] }));
+    let current = schema_of(&json!({ "nonce": 5, "owners": ["0x2", "0x3"] }));
+
+    assert!(diff(&previous, &current).is_empty());
+}
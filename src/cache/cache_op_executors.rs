@@ -1,15 +1,26 @@
 use crate::cache::cache_operations::{CacheResponse, InvalidationPattern, RequestCached};
 use crate::cache::inner_cache::CachedWithCode;
-use crate::cache::{Cache, CACHE_REQS_PREFIX, CACHE_RESP_PREFIX};
-use crate::utils::errors::ApiResult;
+use crate::cache::{
+    namespaced, Cache, CACHE_LAST_KNOWN_GOOD_PREFIX, CACHE_REQS_PREFIX, CACHE_RESP_PREFIX,
+};
+use crate::config::response_hex_data_limit;
+use crate::providers::failover;
+use crate::utils::cache_control::CacheLookup;
+use crate::utils::call_budget;
+use crate::utils::errors::{ApiError, ApiResult};
 use crate::utils::http_client::Request;
+use crate::utils::response_budget;
+use crate::utils::upstream_queue;
 use rocket::response::content;
 use serde::Serialize;
 use std::sync::Arc;
 use std::time::Duration;
 
-pub(super) fn invalidate(cache: Arc<dyn Cache>, pattern: &InvalidationPattern) {
-    cache.invalidate_pattern(pattern.to_pattern_string().as_str());
+/// Returns the amount of deleted keys
+pub(super) async fn invalidate(cache: Arc<dyn Cache>, pattern: &InvalidationPattern) -> usize {
+    cache
+        .invalidate_pattern(pattern.to_pattern_string().as_str())
+        .await
 }
 
 pub(super) async fn cache_response<S>(
@@ -19,34 +30,126 @@ where
     S: Serialize,
 {
     let cache = cache_response.cache.clone();
-    let cache_key = format!("{}_{}", CACHE_RESP_PREFIX, cache_response.key);
-    let cached = cache.fetch(&cache_key);
+    let cache_key = format!("{}_{}", namespaced(CACHE_RESP_PREFIX), cache_response.key);
+    let cached = cache.fetch(&cache_key).await;
     match cached {
-        Some(value) => Ok(content::Json(value)),
+        Some(value) => {
+            cache_response
+                .cache_status
+                .record(CacheLookup::Hit, &cache_key);
+            if let Some(ttl) = cache.ttl(&cache_key).await {
+                cache_response.response_ttl.set(ttl);
+            }
+            Ok(content::Json(value))
+        }
         None => {
-            let resp_string = serde_json::to_string(&cache_response.generate().await?)?;
-            cache.create(&cache_key, &resp_string, cache_response.duration);
+            cache_response
+                .cache_status
+                .record(CacheLookup::Miss, &cache_key);
+            let response = cache_response.generate().await?;
+            let resp_string = response_budget::to_string_within_budget(
+                &response,
+                cache_response.response_budget,
+                response_hex_data_limit(),
+            )?;
+            // Responses built from last known good copies are not stored, so the next request
+            // tries the upstream again
+            let is_stale = cache_response.data_freshness.is_stale();
+            // Handlers may recover from calls refused by the budget, leaving the response incomplete
+            let is_incomplete = cache_response
+                .call_budget
+                .as_ref()
+                .map_or(false, |call_budget| call_budget.is_exceeded());
+            if !is_stale && !is_incomplete && !cache_response.should_skip_cache(&response) {
+                cache
+                    .create(&cache_key, &resp_string, cache_response.duration)
+                    .await;
+                cache_response.response_ttl.set(cache_response.duration);
+            }
             Ok(content::Json(resp_string))
         }
     }
 }
 
+pub(super) async fn cached_request_data(operation: &RequestCached) -> Option<String> {
+    let cache_key = format!("{}_{}", namespaced(CACHE_REQS_PREFIX), &operation.url);
+    operation
+        .cache
+        .fetch(&cache_key)
+        .await
+        .map(|cached| CachedWithCode::split(&cached))
+        .filter(|cached_with_code| !cached_with_code.is_error())
+        .map(|cached_with_code| cached_with_code.data)
+}
+
+pub(super) async fn overwrite_request_cache(operation: &RequestCached, data: &str) {
+    let cache_key = format!("{}_{}", namespaced(CACHE_REQS_PREFIX), &operation.url);
+    operation
+        .cache
+        .create(
+            &cache_key,
+            &CachedWithCode::join(200, data),
+            operation.cache_duration,
+        )
+        .await;
+}
+
+pub(super) async fn is_request_cached(operation: &RequestCached) -> bool {
+    let cache_key = format!("{}_{}", namespaced(CACHE_REQS_PREFIX), &operation.url);
+    operation.cache.has_key(&cache_key).await
+}
+
+pub(super) async fn invalidate_request_cache(operation: &RequestCached) {
+    let cache_key = format!("{}_{}", namespaced(CACHE_REQS_PREFIX), &operation.url);
+    operation.cache.invalidate(&cache_key).await;
+}
+
 pub(super) async fn request_cached(operation: &RequestCached) -> ApiResult<String> {
     let cache = operation.cache.clone();
     let client = operation.client.clone();
-    let cache_key = format!("{}_{}", CACHE_REQS_PREFIX, &operation.url);
-    match cache.fetch(&cache_key) {
-        Some(cached) => CachedWithCode::split(&cached).to_result(),
+    let cache_key = format!("{}_{}", namespaced(CACHE_REQS_PREFIX), &operation.url);
+    let last_known_good_key = format!(
+        "{}_{}",
+        namespaced(CACHE_LAST_KNOWN_GOOD_PREFIX),
+        &operation.url
+    );
+    match cache.fetch(&cache_key).await {
+        Some(cached) => match CachedWithCode::split(&cached).to_result() {
+            Err(error) => last_known_good_or(operation, &last_known_good_key, error).await,
+            data => data,
+        },
         None => {
-            let http_request = {
-                let mut request = Request::new(String::from(&operation.url));
-                request.timeout(Duration::from_millis(operation.request_timeout));
+            let http_request = |url: &str| {
+                let mut request = Request::new(url.to_string());
+                if let Some(timeout) = operation.request_timeout {
+                    request.timeout(Duration::from_millis(timeout));
+                }
                 request
             };
 
-            let response = client.get(http_request).await;
+            // Reads against a transaction service with a fallback are retried once there,
+            // and the primary is skipped until it is considered healthy again
+            let response = match client.get(http_request(&operation.url)).await {
+                // Not a failure of the upstream service, and not worth caching
+                Err(error) if is_refused(&error) => return Err(error),
+                Err(error) if error.status >= 500 => {
+                    failover::report_failure(&operation.url);
+                    match failover::fallback_url(&operation.url) {
+                        Some(fallback_url) => client.get(http_request(&fallback_url)).await,
+                        None => Err(error),
+                    }
+                }
+                response => {
+                    if response.is_ok() {
+                        failover::report_success(&operation.url);
+                    }
+                    response
+                }
+            };
 
             match response {
+                // The fallback call can be refused as well
+                Err(error) if is_refused(&error) => Err(error),
                 Err(error) => {
                     let default_message: String = String::from("Unknown error");
                     let response_body: &String =
@@ -57,27 +160,65 @@ pub(super) async fn request_cached(operation: &RequestCached) -> ApiResult<Strin
                     // If cache_all_errors is enabled we cache both client and server errors
                     // else we just cache client errors
                     if is_client_error || operation.cache_all_errors {
-                        cache.create(
-                            &cache_key,
-                            &CachedWithCode::join(error.status, &response_body),
-                            operation.error_cache_duration,
-                        );
+                        cache
+                            .create(
+                                &cache_key,
+                                &CachedWithCode::join(error.status, &response_body),
+                                operation.error_cache_duration,
+                            )
+                            .await;
                     }
 
-                    Err(error)
+                    last_known_good_or(operation, &last_known_good_key, error).await
                 }
                 Ok(response) => {
                     let status_code = response.status_code;
                     let response_body = response.body;
 
-                    cache.create(
-                        &cache_key,
-                        &CachedWithCode::join(status_code, &response_body),
-                        operation.cache_duration,
-                    );
+                    cache
+                        .create(
+                            &cache_key,
+                            &CachedWithCode::join(status_code, &response_body),
+                            operation.cache_duration,
+                        )
+                        .await;
+                    if operation.last_known_good_duration > 0 {
+                        cache
+                            .create(
+                                &last_known_good_key,
+                                &response_body,
+                                operation.last_known_good_duration,
+                            )
+                            .await;
+                    }
                     Ok(response_body.to_string())
                 }
             }
         }
     }
 }
+
+/// Server errors are replaced by the last known good copy of the response, if one is kept
+async fn last_known_good_or(
+    operation: &RequestCached,
+    last_known_good_key: &str,
+    error: ApiError,
+) -> ApiResult<String> {
+    if error.status < 500 || operation.last_known_good_duration == 0 {
+        return Err(error);
+    }
+    match operation.cache.fetch(last_known_good_key).await {
+        Some(last_known_good) => {
+            log::warn!("Serving last known good response for {}", &operation.url);
+            operation.data_freshness.mark_stale();
+            Ok(last_known_good)
+        }
+        None => Err(error),
+    }
+}
+
+/// Calls refused by this instance (exceeded call budget, shed by the upstream queue) before
+/// reaching the upstream service
+fn is_refused(error: &ApiError) -> bool {
+    call_budget::is_exceeded_error(error) || upstream_queue::is_shed_error(error)
+}
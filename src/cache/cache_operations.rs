@@ -1,13 +1,22 @@
-use crate::cache::cache_op_executors::{cache_response, invalidate, request_cached};
-use crate::cache::{Cache, CACHE_REQS_PREFIX, CACHE_REQS_RESP_PREFIX, CACHE_RESP_PREFIX};
+use crate::cache::cache_op_executors::{
+    cache_response, cached_request_data, invalidate, invalidate_request_cache, is_request_cached,
+    overwrite_request_cache, request_cached,
+};
+use crate::cache::invalidation_log::{self, InvalidationEntry};
+use crate::cache::{
+    namespaced, Cache, CACHE_REQS_PREFIX, CACHE_REQS_RESP_PREFIX, CACHE_RESP_PREFIX,
+};
 use crate::config::{
-    base_config_service_uri, default_request_timeout, request_cache_duration,
+    base_config_service_uri, last_known_good_cache_duration, request_cache_duration,
     request_error_cache_duration,
 };
 use crate::providers::info::generate_token_key;
+use crate::utils::cache_control::{CacheStatus, DataFreshness, ResponseTtl};
+use crate::utils::call_budget::CallBudget;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
 use crate::utils::http_client::HttpClient;
+use chrono::Utc;
 use rocket::futures::future::BoxFuture;
 use rocket::futures::FutureExt;
 use rocket::response::content;
@@ -25,16 +34,17 @@ pub struct Invalidate {
     pub(super) cache: Arc<dyn Cache>,
     pattern: InvalidationPattern,
     database: Database,
+    source: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub enum InvalidationScope {
     Requests,
     Responses,
     Both,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(tag = "invalidate", content = "pattern_details")]
 pub enum InvalidationPattern {
     Any(InvalidationScope, String),
@@ -87,11 +97,11 @@ impl InvalidationPattern {
 }
 
 impl InvalidationScope {
-    pub(super) fn invalidation_scope_string(&self) -> &str {
+    pub(super) fn invalidation_scope_string(&self) -> String {
         match &self {
-            InvalidationScope::Requests => CACHE_REQS_PREFIX,
-            InvalidationScope::Responses => CACHE_RESP_PREFIX,
-            InvalidationScope::Both => CACHE_REQS_RESP_PREFIX,
+            InvalidationScope::Requests => namespaced(CACHE_REQS_PREFIX),
+            InvalidationScope::Responses => namespaced(CACHE_RESP_PREFIX),
+            InvalidationScope::Both => namespaced(CACHE_REQS_RESP_PREFIX),
         }
     }
 }
@@ -102,17 +112,50 @@ impl Invalidate {
             cache,
             pattern,
             database: Database::Default,
+            source: String::from("UNKNOWN"),
         }
     }
 
+    /// What triggered the invalidation, recorded in the invalidation log
+    pub fn source(&mut self, source: impl Into<String>) -> &mut Self {
+        self.source = source.into();
+        self
+    }
+
     fn database(&mut self, database: Database) -> &mut Self {
         self.database = database;
         self
     }
 
-    pub fn execute(&self) {
-        invalidate(self.cache.clone(), &self.pattern)
+    pub async fn execute(&self) {
+        let keys = invalidate(self.cache.clone(), &self.pattern).await;
+        invalidation_log::record(
+            self.cache.as_ref(),
+            &InvalidationEntry {
+                timestamp: Utc::now().timestamp_millis(),
+                source: self.source.to_string(),
+                pattern: self.pattern.to_pattern_string(),
+                keys,
+            },
+        )
+        .await;
+    }
+}
+
+/// Key of the cached response to a request for `uri`. The query parameters are sorted, so that
+/// the order clients send them in doesn't matter and other entry points (e.g. the prefetch of the
+/// hooks) can store responses under the keys the routes look up.
+pub fn response_key(uri: &str) -> String {
+    let (path, query) = match uri.split_once('?') {
+        Some(split) => split,
+        None => return uri.to_string(),
+    };
+    let mut params: Vec<&str> = query.split('&').filter(|it| !it.is_empty()).collect();
+    if params.is_empty() {
+        return path.to_string();
     }
+    params.sort_unstable();
+    format!("{}?{}", path, params.join("&"))
 }
 
 pub struct CacheResponse<'a, R>
@@ -125,6 +168,12 @@ where
     pub duration: usize,
     // "dyn" allows setting the type of the BoxFuture to different times in runtime
     pub resp_generator: Option<Box<dyn Fn() -> BoxFuture<'a, ApiResult<R>> + Send + Sync + 'a>>,
+    pub skip_cache_if: Option<Box<dyn Fn(&R) -> bool + Send + Sync + 'a>>,
+    pub(super) response_ttl: ResponseTtl,
+    pub(super) data_freshness: DataFreshness,
+    pub(super) cache_status: CacheStatus,
+    pub(super) call_budget: Option<Arc<CallBudget>>,
+    pub(super) response_budget: Option<usize>,
 }
 
 impl<'a, R> CacheResponse<'a, R>
@@ -133,11 +182,17 @@ where
 {
     pub fn new(context: &RequestContext) -> Self {
         CacheResponse {
-            key: context.request_id.to_string(),
+            key: response_key(&context.request_id),
             cache: context.cache(),
             database: Database::Default,
             duration: request_cache_duration(),
             resp_generator: None,
+            skip_cache_if: None,
+            response_ttl: context.response_ttl(),
+            data_freshness: context.data_freshness(),
+            cache_status: context.cache_status(),
+            call_budget: context.call_budget(),
+            response_budget: context.response_budget(),
         }
     }
 
@@ -146,6 +201,13 @@ where
         self
     }
 
+    /// Defaults to the [response_key] of the current request, set to share entries with another
+    /// endpoint
+    pub fn key(&mut self, key: String) -> &mut Self {
+        self.key = key;
+        self
+    }
+
     pub fn duration(&mut self, duration: usize) -> &mut Self {
         self.duration = duration;
         self
@@ -160,6 +222,21 @@ where
         self
     }
 
+    /// Responses matching the predicate are returned but not stored (e.g. partial responses)
+    pub fn skip_cache_if<F>(&mut self, skip_cache_if: F) -> &mut Self
+    where
+        F: Fn(&R) -> bool + Send + Sync + 'a,
+    {
+        self.skip_cache_if = Some(Box::new(skip_cache_if));
+        self
+    }
+
+    pub(super) fn should_skip_cache(&self, response: &R) -> bool {
+        self.skip_cache_if
+            .as_ref()
+            .map_or(false, |skip_cache_if| skip_cache_if(response))
+    }
+
     pub async fn generate(&self) -> ApiResult<R> {
         (self.resp_generator.as_ref().unwrap())().await
     }
@@ -174,10 +251,13 @@ pub struct RequestCached {
     pub(super) client: Arc<dyn HttpClient>,
     pub(super) cache: Arc<dyn Cache>,
     pub url: String,
-    pub request_timeout: u64,
+    /// `None` for the `DEFAULT_REQUEST_TIMEOUT`
+    pub request_timeout: Option<u64>,
     pub cache_duration: usize,
     pub error_cache_duration: usize,
     pub cache_all_errors: bool,
+    pub last_known_good_duration: usize,
+    pub(super) data_freshness: DataFreshness,
 }
 
 impl RequestCached {
@@ -187,10 +267,12 @@ impl RequestCached {
             client: client.clone(),
             cache: cache.clone(),
             url,
-            request_timeout: default_request_timeout(),
+            request_timeout: None,
             cache_duration: request_cache_duration(),
             error_cache_duration: request_error_cache_duration(),
             cache_all_errors: false,
+            last_known_good_duration: 0,
+            data_freshness: DataFreshness::default(),
         }
     }
 
@@ -200,10 +282,12 @@ impl RequestCached {
             client: context.http_client(),
             cache: context.cache(),
             url,
-            request_timeout: default_request_timeout(),
+            request_timeout: None,
             cache_duration: request_cache_duration(),
             error_cache_duration: request_error_cache_duration(),
             cache_all_errors: false,
+            last_known_good_duration: 0,
+            data_freshness: context.data_freshness(),
         }
     }
 
@@ -213,7 +297,7 @@ impl RequestCached {
     }
 
     pub fn request_timeout(&mut self, request_timeout: u64) -> &mut Self {
-        self.request_timeout = request_timeout;
+        self.request_timeout = Some(request_timeout);
         self
     }
 
@@ -232,8 +316,42 @@ impl RequestCached {
         self
     }
 
+    /// Keeps a copy of successful responses for [last_known_good_cache_duration], which is served
+    /// (and the request marked as stale) when the upstream fails with a server error and the
+    /// regular cache entry is gone. Only meant for read-only data.
+    pub fn keep_last_known_good(&mut self) -> &mut Self {
+        self.last_known_good_duration = last_known_good_cache_duration();
+        self
+    }
+
+    /// Freshness of the request this call is part of, for calls not built from a [RequestContext]
+    pub fn data_freshness(&mut self, data_freshness: &DataFreshness) -> &mut Self {
+        self.data_freshness = data_freshness.clone();
+        self
+    }
+
     pub async fn execute(&self) -> ApiResult<String> {
-        assert!(self.request_timeout > 0);
+        assert!(self.request_timeout.map_or(true, |timeout| timeout > 0));
         request_cached(self).await
     }
+
+    /// Returns the cached response body, if a successful response is cached. Never hits the network.
+    pub async fn cached(&self) -> Option<String> {
+        cached_request_data(self).await
+    }
+
+    /// Stores `data` as a successful response for this request, for `cache_duration`
+    pub async fn overwrite(&self, data: &str) {
+        overwrite_request_cache(self, data).await
+    }
+
+    /// Whether a response (successful or not) is cached for this request
+    pub async fn is_cached(&self) -> bool {
+        is_request_cached(self).await
+    }
+
+    /// Removes the cached response, so the next execution hits the network
+    pub async fn invalidate(&self) {
+        invalidate_request_cache(self).await
+    }
 }
@@ -0,0 +1,61 @@
+//! Compression of cached values, so that large payloads (history, collectibles) take less memory
+//! in Redis. Compressed values start with a marker that no cached string starts with, followed by
+//! the version of the format. Values without the marker are read as they are, which keeps values
+//! written before compression was enabled readable.
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::convert::TryInto;
+use std::io::{Read, Write};
+
+const COMPRESSED_MARKER: &[u8] = b"\x00csz";
+// Length of the value (u32 LE) followed by the zlib (deflate) stream of its UTF-8 bytes
+const FORMAT_ZLIB: u8 = 1;
+const HEADER_LENGTH: usize = 9;
+
+/// Values of at least `threshold` bytes are compressed, `0` disables compression.
+/// Values that don't get smaller are stored as they are.
+pub fn encode(value: &str, threshold: usize) -> Vec<u8> {
+    if threshold == 0 || value.len() < threshold || value.len() > u32::MAX as usize {
+        return value.as_bytes().to_vec();
+    }
+    let mut encoded = Vec::with_capacity(value.len() / 2);
+    encoded.extend_from_slice(COMPRESSED_MARKER);
+    encoded.push(FORMAT_ZLIB);
+    encoded.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    let mut encoder = ZlibEncoder::new(encoded, Compression::fast());
+    let encoded = match encoder
+        .write_all(value.as_bytes())
+        .and_then(|_| encoder.finish())
+    {
+        Ok(encoded) => encoded,
+        Err(_) => return value.as_bytes().to_vec(),
+    };
+    if encoded.len() >= value.len() {
+        return value.as_bytes().to_vec();
+    }
+    encoded
+}
+
+/// `None` if the value is corrupted or of an unknown format, so it is treated as a cache miss
+pub fn decode(bytes: Vec<u8>) -> Option<String> {
+    if !bytes.starts_with(COMPRESSED_MARKER) {
+        return String::from_utf8(bytes).ok();
+    }
+    match bytes.get(COMPRESSED_MARKER.len()) {
+        Some(&FORMAT_ZLIB) => {
+            let length = u32::from_le_bytes(bytes.get(5..HEADER_LENGTH)?.try_into().ok()?);
+            let mut decoded = String::with_capacity(length as usize);
+            ZlibDecoder::new(&bytes[HEADER_LENGTH..])
+                .read_to_string(&mut decoded)
+                .ok()?;
+            // A truncated stream may decode without an error
+            if decoded.len() == length as usize {
+                Some(decoded)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,45 @@
+//! Every cache invalidation is appended to a capped Redis stream, so that reports of clients
+//! seeing stale data can be checked against what was actually invalidated and when.
+use crate::cache::Cache;
+use crate::config::invalidation_log_size;
+use serde::{Deserialize, Serialize};
+
+pub const INVALIDATIONS_STREAM: &str = "invalidations";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidationEntry {
+    /// In ms
+    pub timestamp: i64,
+    /// What triggered the invalidation, e.g. `HOOK NEW_CONFIRMATION` or `FLUSH`
+    pub source: String,
+    pub pattern: String,
+    /// Amount of deleted keys
+    pub keys: usize,
+}
+
+/// Appends the entry to the log, which keeps about the last `INVALIDATION_LOG_SIZE` entries
+pub async fn record(cache: &dyn Cache, entry: &InvalidationEntry) {
+    let max_len = invalidation_log_size();
+    if max_len == 0 {
+        return;
+    }
+    match serde_json::to_string(entry) {
+        Ok(serialized) => {
+            cache
+                .append_to_stream(INVALIDATIONS_STREAM, &serialized, max_len)
+                .await
+        }
+        Err(error) => log::error!("Could not serialize invalidation log entry: {}", error),
+    }
+}
+
+/// Entries from `since` (in ms) on, oldest first
+pub async fn read(cache: &dyn Cache, since: i64, limit: usize) -> Vec<InvalidationEntry> {
+    cache
+        .read_stream(INVALIDATIONS_STREAM, since, limit)
+        .await
+        .into_iter()
+        .filter_map(|(_, entry)| serde_json::from_str(&entry).ok())
+        .collect()
+}
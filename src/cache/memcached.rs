@@ -0,0 +1,480 @@
+//! Memcached backend of the cache, for deployments whose infrastructure is standardized on
+//! Memcached. Memcached only stores plain values, so:
+//! - hashes and streams are stored as JSON documents, updated with compare-and-swap
+//! - the expiry of every value is stored along with it, as Memcached can't report time to live
+//! - keys are tracked in sharded index entries, so that patterns can be listed and invalidated
+use crate::cache::compression;
+use crate::cache::{Cache, PoolStats};
+use crate::config::{
+    memcached_compression_threshold, memcached_pool_size, memcached_timeout, memcached_uri,
+};
+use chrono::Utc;
+use ethcontract_common::hash::keccak256;
+use memcache::{Client, ConnectionManager, MemcacheError};
+use r2d2::Pool;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const KEY_INDEX_BASE: &str = "memcached_keys";
+const KEY_INDEX_SHARDS: u8 = 64;
+// Longer keys, and keys with whitespace or control characters, are stored under their hash
+const MAX_KEY_LENGTH: usize = 250;
+const MAX_CAS_ATTEMPTS: usize = 10;
+
+pub struct MemcachedCache {
+    uri: String,
+    pool_size: u32,
+    timeout: Duration,
+    // Created on first use, its pool connects on demand
+    client: Mutex<Option<Arc<Client>>>,
+    compression_threshold: usize,
+}
+
+/// The settings are validated at startup, connecting to Memcached only fails the cache calls
+pub fn create_memcached_cache() -> MemcachedCache {
+    MemcachedCache::new(
+        &memcached_uri(),
+        memcached_pool_size() as u32,
+        memcached_timeout(),
+    )
+}
+
+/// Stored value, with its expiry in ms (`0` if it doesn't expire)
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub expires_at: i64,
+    pub value: String,
+}
+
+impl Entry {
+    pub fn to_bytes(&self, compression_threshold: usize) -> Vec<u8> {
+        let mut bytes = format!("{};", self.expires_at).into_bytes();
+        bytes.extend(compression::encode(&self.value, compression_threshold));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        let separator = bytes.iter().position(|byte| *byte == b';')?;
+        Some(Entry {
+            expires_at: std::str::from_utf8(&bytes[..separator])
+                .ok()?
+                .parse()
+                .ok()?,
+            value: compression::decode(bytes[separator + 1..].to_vec())?,
+        })
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        is_expired(self.expires_at, now)
+    }
+
+    // Expiration times above 30 days are read by Memcached as unix timestamps (in seconds)
+    fn exptime(&self) -> u32 {
+        if self.expires_at > 0 {
+            ((self.expires_at + 999) / 1000) as u32
+        } else {
+            0
+        }
+    }
+}
+
+fn is_expired(expires_at: i64, now: i64) -> bool {
+    expires_at > 0 && expires_at <= now
+}
+
+// The memcache client is synchronous, its calls are moved off of the async workers
+fn blocking<T>(call: impl FnOnce() -> T) -> T {
+    rocket::tokio::task::block_in_place(call)
+}
+
+fn now() -> i64 {
+    Utc::now().timestamp_millis()
+}
+
+pub fn storage_key(key: &str) -> String {
+    if key.len() <= MAX_KEY_LENGTH && key.bytes().all(|byte| byte.is_ascii_graphic()) {
+        key.to_string()
+    } else {
+        format!("h_{}", to_hex_string!(keccak256(key.as_bytes())))
+    }
+}
+
+fn index_key(key: &str) -> String {
+    index_shard_key(keccak256(key.as_bytes())[0] % KEY_INDEX_SHARDS)
+}
+
+fn index_shard_key(shard: u8) -> String {
+    format!("{}_{}", KEY_INDEX_BASE, shard)
+}
+
+/// Glob style matching of the `*` and `?` wildcards (`\` escapes them), like Redis patterns
+pub fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    // Position of the last `*` and of the value it was matched up to
+    let mut last_star: Option<(usize, usize)> = None;
+    while v < value.len() {
+        let step = match pattern.get(p) {
+            Some('*') => {
+                last_star = Some((p, v));
+                p += 1;
+                continue;
+            }
+            Some('?') => Some(1),
+            Some('\\') if pattern.get(p + 1) == Some(&value[v]) => Some(2),
+            Some(c) if *c != '\\' && *c == value[v] => Some(1),
+            _ => None,
+        };
+        match (step, last_star) {
+            (Some(step), _) => {
+                p += step;
+                v += 1;
+            }
+            (None, Some((star, matched))) => {
+                p = star + 1;
+                v = matched + 1;
+                last_star = Some((star, matched + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Id following `last` (`<ms>-<sequence>`, like Redis stream ids)
+pub fn next_stream_id(last: Option<&str>, now: i64) -> String {
+    match last.and_then(parse_stream_id) {
+        Some((ms, sequence)) if ms >= now => format!("{}-{}", ms, sequence + 1),
+        _ => format!("{}-0", now),
+    }
+}
+
+fn parse_stream_id(id: &str) -> Option<(i64, u64)> {
+    let mut parts = id.splitn(2, '-');
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
+fn parse_json<T: serde::de::DeserializeOwned + Default>(entry: Option<&Entry>) -> T {
+    entry
+        .and_then(|entry| serde_json::from_str(&entry.value).ok())
+        .unwrap_or_default()
+}
+
+fn json_entry<T: serde::Serialize>(value: &T, expires_at: i64) -> Option<Entry> {
+    Some(Entry {
+        expires_at,
+        value: serde_json::to_string(value).ok()?,
+    })
+}
+
+impl MemcachedCache {
+    pub fn new(uri: &str, pool_size: u32, timeout: u64) -> Self {
+        MemcachedCache {
+            uri: uri.to_string(),
+            pool_size,
+            timeout: Duration::from_millis(timeout),
+            client: Mutex::new(None),
+            compression_threshold: memcached_compression_threshold(),
+        }
+    }
+
+    fn client(&self) -> Option<Arc<Client>> {
+        let mut client = self.client.lock().unwrap();
+        if client.is_none() {
+            match connect(&self.uri, self.pool_size, self.timeout) {
+                Ok(connected) => *client = Some(Arc::new(connected)),
+                Err(error) => log::warn!("Could not connect to Memcached: {}", error),
+            }
+        }
+        client.clone()
+    }
+
+    /// `None` if Memcached could not be reached or the call failed
+    fn with_client<T>(&self, call: impl FnOnce(&Client) -> Result<T, MemcacheError>) -> Option<T> {
+        match call(self.client()?.as_ref()) {
+            Ok(result) => Some(result),
+            Err(error) => {
+                log::warn!("Memcached call failed: {}", error);
+                None
+            }
+        }
+    }
+
+    /// Value and cas unique of the key
+    fn gets(&self, storage_key: &str) -> Option<Option<(Vec<u8>, Option<u64>)>> {
+        let mut values: HashMap<String, (Vec<u8>, u32, Option<u64>)> =
+            self.with_client(|client| client.gets(&[storage_key]))?;
+        Some(
+            values
+                .remove(storage_key)
+                .map(|(bytes, _, cas_unique)| (bytes, cas_unique)),
+        )
+    }
+
+    fn read(&self, key: &str) -> Option<Entry> {
+        let (bytes, _) = self.gets(&storage_key(key))??;
+        Entry::from_bytes(bytes).filter(|entry| !entry.is_expired(now()))
+    }
+
+    fn write(&self, key: &str, entry: &Entry) {
+        let bytes = entry.to_bytes(self.compression_threshold);
+        self.with_client(|client| client.set(&storage_key(key), &bytes[..], entry.exptime()));
+    }
+
+    /// Compare-and-swap loop, `update` returns the entry replacing the current one (`None` if
+    /// missing or expired), or `None` to keep it
+    fn update(
+        &self,
+        key: &str,
+        mut update: impl FnMut(Option<Entry>) -> Option<Entry>,
+    ) -> Option<Entry> {
+        let storage_key = storage_key(key);
+        for _ in 0..MAX_CAS_ATTEMPTS {
+            let (current, cas_unique) = match self.gets(&storage_key)? {
+                Some((bytes, cas_unique)) => (
+                    Entry::from_bytes(bytes).filter(|entry| !entry.is_expired(now())),
+                    cas_unique,
+                ),
+                None => (None, None),
+            };
+            let updated = update(current)?;
+            let bytes = updated.to_bytes(self.compression_threshold);
+            let stored = self.with_client(|client| {
+                let stored = match cas_unique {
+                    Some(cas_unique) => {
+                        client.cas(&storage_key, &bytes[..], updated.exptime(), cas_unique)
+                    }
+                    None => client
+                        .add(&storage_key, &bytes[..], updated.exptime())
+                        .map(|_| true),
+                };
+                match stored {
+                    // Changed or added by another instance in the meantime
+                    Err(MemcacheError::CommandError(_)) => Ok(false),
+                    stored => stored,
+                }
+            })?;
+            if stored {
+                return Some(updated);
+            }
+        }
+        log::warn!(
+            "Memcached entry {} could not be updated after {} attempts",
+            key,
+            MAX_CAS_ATTEMPTS
+        );
+        None
+    }
+
+    fn index(&self, key: &str, expires_at: i64) {
+        let now = now();
+        self.update(&index_key(key), |entry| {
+            let mut keys: HashMap<String, i64> = parse_json(entry.as_ref());
+            if keys.get(key) == Some(&expires_at) {
+                return None;
+            }
+            keys.retain(|_, expires_at| !is_expired(*expires_at, now));
+            keys.insert(key.to_string(), expires_at);
+            json_entry(&keys, 0)
+        });
+    }
+
+    fn unindex(&self, key: &str) {
+        self.update(&index_key(key), |entry| {
+            let mut keys: HashMap<String, i64> = parse_json(entry.as_ref());
+            keys.remove(key)?;
+            json_entry(&keys, 0)
+        });
+    }
+
+    fn delete(&self, key: &str) {
+        self.with_client(|client| client.delete(&storage_key(key)));
+        self.unindex(key);
+    }
+
+    fn matching_keys(&self, pattern: &str) -> Vec<String> {
+        let now = now();
+        (0..KEY_INDEX_SHARDS)
+            .filter_map(|shard| self.read(&index_shard_key(shard)))
+            .flat_map(|entry| parse_json::<HashMap<String, i64>>(Some(&entry)))
+            .filter(|(key, expires_at)| !is_expired(*expires_at, now) && glob_match(pattern, key))
+            .map(|(key, _)| key)
+            .collect()
+    }
+}
+
+#[rocket::async_trait]
+impl Cache for MemcachedCache {
+    async fn fetch(&self, id: &str) -> Option<String> {
+        blocking(|| self.read(id).map(|entry| entry.value))
+    }
+
+    async fn create(&self, id: &str, dest: &str, timeout: usize) {
+        blocking(|| {
+            let entry = Entry {
+                expires_at: now() + timeout as i64,
+                value: dest.to_string(),
+            };
+            self.write(id, &entry);
+            self.index(id, entry.expires_at);
+        })
+    }
+
+    async fn insert_in_hash(&self, hash: &str, id: &str, dest: &str) {
+        blocking(|| {
+            let updated = self.update(hash, |entry| {
+                let expires_at = entry.as_ref().map_or(0, |entry| entry.expires_at);
+                let mut fields: HashMap<String, String> = parse_json(entry.as_ref());
+                fields.insert(id.to_string(), dest.to_string());
+                json_entry(&fields, expires_at)
+            });
+            if let Some(updated) = updated {
+                self.index(hash, updated.expires_at);
+            }
+        })
+    }
+
+    async fn get_from_hash(&self, hash: &str, id: &str) -> Option<String> {
+        blocking(|| {
+            let mut fields: HashMap<String, String> = parse_json(self.read(hash).as_ref());
+            fields.remove(id)
+        })
+    }
+
+    async fn increment_in_hash(&self, hash: &str, id: &str, timeout: usize) -> usize {
+        blocking(|| {
+            let mut count = 0;
+            let updated = self.update(hash, |entry| {
+                let mut fields: HashMap<String, String> = parse_json(entry.as_ref());
+                count = fields
+                    .get(id)
+                    .and_then(|count| count.parse::<usize>().ok())
+                    .unwrap_or_default()
+                    + 1;
+                fields.insert(id.to_string(), count.to_string());
+                json_entry(&fields, now() + timeout as i64)
+            });
+            if let Some(updated) = updated {
+                self.index(hash, updated.expires_at);
+            }
+            count
+        })
+    }
+
+    async fn fetch_hash(&self, hash: &str) -> HashMap<String, String> {
+        blocking(|| parse_json(self.read(hash).as_ref()))
+    }
+
+    async fn has_key(&self, id: &str) -> bool {
+        blocking(|| self.read(id).is_some())
+    }
+
+    async fn ttl(&self, id: &str) -> Option<usize> {
+        blocking(|| {
+            let entry = self.read(id)?;
+            if entry.expires_at > 0 {
+                Some((entry.expires_at - now()).max(1) as usize)
+            } else {
+                None
+            }
+        })
+    }
+
+    async fn expire_entity(&self, id: &str, timeout: usize) {
+        blocking(|| {
+            let expires_at = now() + timeout as i64;
+            let updated = self.update(id, |entry| {
+                entry.map(|entry| Entry {
+                    expires_at,
+                    value: entry.value,
+                })
+            });
+            if updated.is_some() {
+                self.index(id, expires_at);
+            }
+        })
+    }
+
+    async fn keys(&self, pattern: &str) -> Vec<String> {
+        blocking(|| self.matching_keys(pattern))
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) -> usize {
+        blocking(|| {
+            let keys = self.matching_keys(pattern);
+            for key in keys.iter() {
+                self.delete(key);
+            }
+            keys.len()
+        })
+    }
+
+    async fn invalidate(&self, id: &str) {
+        blocking(|| {
+            self.delete(id);
+        })
+    }
+
+    async fn info(&self) -> Option<String> {
+        blocking(|| {
+            // Formatted like the INFO fields of Redis
+            let stats = self.with_client(|client| client.stats())?;
+            let stats: Vec<String> = stats
+                .into_iter()
+                .flat_map(|(_, server_stats)| server_stats.into_iter())
+                .map(|(name, value)| format!("{}:{}", name, value))
+                .collect();
+            Some(format!("# Memcached\r\n{}\r\n", stats.join("\r\n")))
+        })
+    }
+
+    // The pool of the memcache client is private to it
+    fn pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
+
+    async fn append_to_stream(&self, stream: &str, entry: &str, max_len: usize) {
+        blocking(|| {
+            let updated = self.update(stream, |current| {
+                let mut entries: Vec<(String, String)> = parse_json(current.as_ref());
+                let id = next_stream_id(entries.last().map(|(id, _)| id.as_str()), now());
+                entries.push((id, entry.to_string()));
+                let overflow = entries.len().saturating_sub(max_len);
+                entries.drain(..overflow);
+                json_entry(&entries, 0)
+            });
+            match updated {
+                Some(_) => self.index(stream, 0),
+                None => log::warn!("Could not append to stream {}", stream),
+            }
+        })
+    }
+
+    async fn read_stream(&self, stream: &str, since: i64, count: usize) -> Vec<(String, String)> {
+        blocking(|| {
+            parse_json::<Vec<(String, String)>>(self.read(stream).as_ref())
+                .into_iter()
+                .filter(|(id, _)| parse_stream_id(id).map_or(false, |(ms, _)| ms >= since))
+                .take(count)
+                .collect()
+        })
+    }
+}
+
+/// Connections are opened on demand, checking one out and the calls on it time out after
+/// `timeout`, so that an unavailable Memcached only makes the cache calls fail
+fn connect(uri: &str, pool_size: u32, timeout: Duration) -> Result<Client, MemcacheError> {
+    let url = Url::parse(uri)?;
+    let pool = Pool::builder()
+        .max_size(pool_size)
+        .min_idle(Some(0))
+        .connection_timeout(timeout)
+        .build_unchecked(ConnectionManager::new(url));
+    let client = Client::with_pool(pool)?;
+    client.set_read_timeout(Some(timeout))?;
+    client.set_write_timeout(Some(timeout))?;
+    Ok(client)
+}
@@ -0,0 +1,176 @@
+//! Copies the request and response caches of another namespace (e.g. the one of the previous
+//! deployment) into the `CACHE_NAMESPACE` of this instance, so that deployments changing the
+//! namespace start with a warm cache instead of sending every request upstream at once.
+//! Values are re-serialized and keep their remaining time to live, values that can't be parsed
+//! anymore and values already cached in the new namespace are skipped.
+use crate::cache::{
+    namespaced_in, Cache, CACHE_LAST_KNOWN_GOOD_PREFIX, CACHE_REQS_PREFIX, CACHE_RESP_PREFIX,
+};
+use crate::config::cache_namespace;
+use crate::utils::errors::ApiResult;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+// Outside of the namespaced prefixes and without expiry, so that it outlives the migration
+const MIGRATION_KEY: &str = "cache_migration";
+const MIGRATION_STATUS_FIELD: &str = "status";
+const MIGRATED_PREFIXES: &[&str] = &[
+    CACHE_REQS_PREFIX,
+    CACHE_RESP_PREFIX,
+    CACHE_LAST_KNOWN_GOOD_PREFIX,
+];
+// Progress is written every this many keys
+const PROGRESS_INTERVAL: usize = 500;
+// Migrations still running after this long (in ms) were interrupted, e.g. by a restart
+const ABANDONED_MIGRATION_AGE: i64 = 60 * 60 * 1000;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMigrationRequest {
+    /// Empty for the keys written without a namespace
+    pub from_namespace: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MigrationState {
+    Running,
+    Completed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMigration {
+    pub from_namespace: String,
+    pub to_namespace: String,
+    pub state: MigrationState,
+    pub migrated: usize,
+    pub skipped: usize,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+/// The running or last migration
+pub async fn status(cache: &dyn Cache) -> Option<CacheMigration> {
+    cache
+        .get_from_hash(MIGRATION_KEY, MIGRATION_STATUS_FIELD)
+        .await
+        .and_then(|migration| serde_json::from_str(&migration).ok())
+}
+
+/// Starts migrating the keys of `from_namespace` in the background, one migration at a time
+pub async fn start(cache: Arc<dyn Cache>, from_namespace: &str) -> ApiResult<CacheMigration> {
+    let to_namespace = cache_namespace();
+    if from_namespace == to_namespace {
+        return Err(client_error!(
+            422,
+            "The cache is already in the requested namespace"
+        ));
+    }
+    let now = Utc::now().timestamp_millis();
+    let is_running = status(cache.as_ref()).await.map_or(false, |migration| {
+        migration.state == MigrationState::Running
+            && now - migration.started_at < ABANDONED_MIGRATION_AGE
+    });
+    if is_running {
+        return Err(client_error!(409, "A cache migration is already running"));
+    }
+    let migration = CacheMigration {
+        from_namespace: from_namespace.to_string(),
+        to_namespace,
+        state: MigrationState::Running,
+        migrated: 0,
+        skipped: 0,
+        started_at: now,
+        finished_at: None,
+    };
+    save(cache.as_ref(), &migration).await;
+    let started = migration.clone();
+    // A migration goes through every key of the namespace
+    rocket::tokio::spawn(async move {
+        let migration = migrate(cache.as_ref(), migration).await;
+        log::info!(
+            "Cache migration from {:?} to {:?} completed: {} migrated, {} skipped",
+            migration.from_namespace,
+            migration.to_namespace,
+            migration.migrated,
+            migration.skipped
+        );
+    });
+    Ok(started)
+}
+
+pub async fn migrate(cache: &dyn Cache, mut migration: CacheMigration) -> CacheMigration {
+    for prefix in MIGRATED_PREFIXES {
+        let source_prefix = format!("{}_", namespaced_in(&migration.from_namespace, prefix));
+        let target_prefix = format!("{}_", namespaced_in(&migration.to_namespace, prefix));
+        for key in cache.keys(&format!("{}*", source_prefix)).await {
+            let target_key = format!("{}{}", target_prefix, &key[source_prefix.len()..]);
+            if migrate_key(cache, prefix, &key, &target_key).await {
+                migration.migrated += 1;
+            } else {
+                migration.skipped += 1;
+            }
+            if (migration.migrated + migration.skipped) % PROGRESS_INTERVAL == 0 {
+                save(cache, &migration).await;
+            }
+        }
+    }
+    migration.state = MigrationState::Completed;
+    migration.finished_at = Some(Utc::now().timestamp_millis());
+    save(cache, &migration).await;
+    migration
+}
+
+async fn migrate_key(cache: &dyn Cache, prefix: &str, key: &str, target_key: &str) -> bool {
+    if cache.has_key(target_key).await {
+        return false;
+    }
+    let reserialized = cache
+        .fetch(key)
+        .await
+        .and_then(|value| reserialize(prefix, &value));
+    match (reserialized, cache.ttl(key).await) {
+        (Some(value), Some(ttl)) => {
+            cache.create(target_key, &value, ttl).await;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Cached requests are stored as `<status code>;<body>`, responses and last known good copies as
+/// their body. Bodies of successful requests and responses are JSON.
+pub fn reserialize(prefix: &str, value: &str) -> Option<String> {
+    if prefix != CACHE_REQS_PREFIX {
+        return reserialize_json(value);
+    }
+    let mut parts = value.splitn(2, ';');
+    let code: u16 = parts.next()?.parse().ok()?;
+    let body = parts.next()?;
+    if (200..400).contains(&code) {
+        Some(format!("{};{}", code, reserialize_json(body)?))
+    } else {
+        // Error bodies are kept as received, they are not necessarily JSON
+        Some(value.to_string())
+    }
+}
+
+fn reserialize_json(value: &str) -> Option<String> {
+    serde_json::from_str::<Value>(value)
+        .ok()
+        .and_then(|value| serde_json::to_string(&value).ok())
+}
+
+async fn save(cache: &dyn Cache, migration: &CacheMigration) {
+    match serde_json::to_string(migration) {
+        Ok(serialized) => {
+            cache
+                .insert_in_hash(MIGRATION_KEY, MIGRATION_STATUS_FIELD, &serialized)
+                .await
+        }
+        Err(error) => log::warn!("Could not store the cache migration status: {}", error),
+    }
+}
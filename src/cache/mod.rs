@@ -1,26 +1,95 @@
 mod cache_op_executors;
 pub mod cache_operations;
+mod compression;
 mod inner_cache;
+pub mod invalidation_log;
+pub mod memcached;
+pub mod migration;
 pub mod redis;
+pub mod snapshot;
 
 #[cfg(test)]
 mod tests;
 
+use crate::config::{cache_backend, cache_namespace};
 use mockall::automock;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 const CACHE_REQS_PREFIX: &'static str = "c_reqs";
 const CACHE_RESP_PREFIX: &'static str = "c_resp";
 const CACHE_REQS_RESP_PREFIX: &'static str = "c_re";
+// Outside of the "c_re" prefix, so invalidations leave last known good copies in place
+const CACHE_LAST_KNOWN_GOOD_PREFIX: &'static str = "c_lkg";
+
+/// `prefix` within the configured `CACHE_NAMESPACE`
+pub(crate) fn namespaced(prefix: &str) -> String {
+    namespaced_in(&cache_namespace(), prefix)
+}
+
+pub(crate) fn namespaced_in(namespace: &str, prefix: &str) -> String {
+    if namespace.is_empty() {
+        prefix.to_string()
+    } else {
+        format!("{}_{}", namespace, prefix)
+    }
+}
+
+pub const REDIS_BACKEND: &str = "redis";
+pub const MEMCACHED_BACKEND: &str = "memcached";
+
+/// Cache of the configured `CACHE_BACKEND`, validated at startup
+pub fn create_cache() -> Arc<dyn Cache> {
+    match cache_backend().as_str() {
+        MEMCACHED_BACKEND => Arc::new(memcached::create_memcached_cache()),
+        _ => Arc::new(redis::create_service_cache()),
+    }
+}
+
+/// Connection pool of the cache backend, on this instance
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    pub max_size: usize,
+    /// Open connections, in use or idle
+    pub connections: usize,
+    pub in_use: usize,
+    pub idle: usize,
+    /// Checkouts waiting for a connection
+    pub waiting: usize,
+    /// Checkouts that didn't get a connection in time, since the instance started
+    pub checkout_timeouts: u64,
+    /// Checkouts that failed to open a connection, since the instance started
+    pub checkout_errors: u64,
+}
 
 #[automock]
+#[rocket::async_trait]
 pub trait Cache: Send + Sync {
-    fn fetch(&self, id: &str) -> Option<String>;
-    fn create(&self, id: &str, dest: &str, timeout: usize);
-    fn insert_in_hash(&self, hash: &str, id: &str, dest: &str);
-    fn get_from_hash(&self, hash: &str, id: &str) -> Option<String>;
-    fn has_key(&self, id: &str) -> bool;
-    fn expire_entity(&self, id: &str, timeout: usize);
-    fn invalidate_pattern(&self, pattern: &str);
-    fn invalidate(&self, id: &str);
-    fn info(&self) -> Option<String>;
+    async fn fetch(&self, id: &str) -> Option<String>;
+    async fn create(&self, id: &str, dest: &str, timeout: usize);
+    async fn insert_in_hash(&self, hash: &str, id: &str, dest: &str);
+    async fn get_from_hash(&self, hash: &str, id: &str) -> Option<String>;
+    /// Increments the counter `id` of the hash, which then expires after `timeout` ms. Returns the
+    /// incremented count.
+    async fn increment_in_hash(&self, hash: &str, id: &str, timeout: usize) -> usize;
+    /// Every field of the hash, empty if it doesn't exist
+    async fn fetch_hash(&self, hash: &str) -> HashMap<String, String>;
+    async fn has_key(&self, id: &str) -> bool;
+    /// Remaining time to live in milliseconds, `None` if the key doesn't exist or doesn't expire
+    async fn ttl(&self, id: &str) -> Option<usize>;
+    async fn expire_entity(&self, id: &str, timeout: usize);
+    /// Keys matching the glob style `pattern`
+    async fn keys(&self, pattern: &str) -> Vec<String>;
+    /// Deletes the keys matching the glob style `pattern`, returns how many were deleted
+    async fn invalidate_pattern(&self, pattern: &str) -> usize;
+    async fn invalidate(&self, id: &str);
+    async fn info(&self) -> Option<String>;
+    /// `None` if the backend doesn't report the state of its pool
+    fn pool_stats(&self) -> Option<PoolStats>;
+    /// Appends `entry` to the stream, which is trimmed to about `max_len` entries
+    async fn append_to_stream(&self, stream: &str, entry: &str, max_len: usize);
+    /// Entries of the stream (with their ids) added from `since` ms on, oldest first
+    async fn read_stream(&self, stream: &str, since: i64, count: usize) -> Vec<(String, String)>;
 }
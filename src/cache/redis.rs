@@ -1,95 +1,301 @@
-use crate::cache::Cache;
-use crate::config::{redis_scan_count, redis_uri};
-use r2d2::{Pool, PooledConnection};
-use redis::{self, pipe, Commands, FromRedisValue, Iter, ToRedisArgs};
+use crate::cache::compression;
+use crate::cache::{Cache, PoolStats};
+use crate::config::{
+    redis_compression_threshold, redis_connection_timeout, redis_pool_size, redis_scan_count,
+    redis_uri,
+};
+use deadpool_redis::{Config, Connection, Pool, PoolConfig, PoolError, Runtime, Timeouts};
+use redis::{self, pipe, AsyncCommands, RedisResult};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
-type RedisPool = Pool<redis::Client>;
-type RedisConnection = PooledConnection<redis::Client>;
-
-pub struct ServiceCache(RedisPool);
+pub struct ServiceCache {
+    pool: Pool,
+    compression_threshold: usize,
+    checkout_timeouts: AtomicU64,
+    checkout_errors: AtomicU64,
+}
 
 pub fn create_service_cache() -> ServiceCache {
-    ServiceCache(create_pool())
+    ServiceCache::new(
+        create_pool(&redis_uri(), redis_pool_size(), redis_connection_timeout()),
+        redis_compression_threshold(),
+    )
 }
 
-fn create_pool() -> RedisPool {
-    let client = redis::Client::open(redis_uri()).unwrap();
-    Pool::builder().max_size(15).build(client).unwrap()
+fn create_pool(uri: &str, max_size: usize, connection_timeout: u64) -> Pool {
+    let timeout = Some(Duration::from_millis(connection_timeout));
+    let mut pool_config = PoolConfig::new(max_size);
+    pool_config.timeouts = Timeouts {
+        wait: timeout,
+        create: timeout,
+        recycle: timeout,
+    };
+    let mut config = Config::from_url(uri);
+    config.pool = Some(pool_config);
+    // Connections are opened on demand, so that an unavailable Redis fails checkouts instead of
+    // the start of the gateway
+    config.create_pool(Some(Runtime::Tokio1)).unwrap()
 }
 
 impl ServiceCache {
-    fn conn(&self) -> RedisConnection {
-        self.0.get().unwrap()
+    fn new(pool: Pool, compression_threshold: usize) -> Self {
+        ServiceCache {
+            pool,
+            compression_threshold,
+            checkout_timeouts: AtomicU64::new(0),
+            checkout_errors: AtomicU64::new(0),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_pool(uri: &str, max_size: usize, connection_timeout: u64) -> Self {
+        ServiceCache::new(create_pool(uri, max_size, connection_timeout), 0)
+    }
+
+    /// `None` once the pool is exhausted or Redis can't be reached for `REDIS_CONNECTION_TIMEOUT`,
+    /// reads are then cache misses and writes are dropped
+    pub(crate) async fn conn(&self) -> Option<Connection> {
+        match self.pool.get().await {
+            Ok(conn) => Some(conn),
+            Err(error) => {
+                let counter = match error {
+                    PoolError::Timeout(_) => &self.checkout_timeouts,
+                    _ => &self.checkout_errors,
+                };
+                counter.fetch_add(1, Ordering::Relaxed);
+                log::warn!("Could not check out a Redis connection: {}", error);
+                None
+            }
+        }
+    }
+
+    fn encode(&self, value: &str) -> Vec<u8> {
+        compression::encode(value, self.compression_threshold)
+    }
+}
+
+fn log_failed_write(operation: &str, key: &str, result: RedisResult<()>) {
+    if let Err(error) = result {
+        log::warn!("Redis {} of {} failed: {}", operation, key, error);
     }
 }
 
+#[rocket::async_trait]
 impl Cache for ServiceCache {
-    fn fetch(&self, id: &str) -> Option<String> {
-        match self.conn().get(id) {
-            Ok(value) => Some(value),
+    async fn fetch(&self, id: &str) -> Option<String> {
+        match self.conn().await?.get::<_, Option<Vec<u8>>>(id).await {
+            Ok(Some(value)) => compression::decode(value),
             _ => None,
         }
     }
 
-    fn create(&self, id: &str, dest: &str, timeout: usize) {
-        let _: () = self.conn().pset_ex(id, dest, timeout).unwrap();
+    async fn create(&self, id: &str, dest: &str, timeout: usize) {
+        if let Some(mut conn) = self.conn().await {
+            let result = conn.pset_ex(id, self.encode(dest), timeout).await;
+            log_failed_write("PSETEX", id, result);
+        }
     }
 
-    fn insert_in_hash(&self, hash: &str, id: &str, dest: &str) {
-        let _: () = self.conn().hset(hash, id, dest).unwrap();
+    async fn insert_in_hash(&self, hash: &str, id: &str, dest: &str) {
+        if let Some(mut conn) = self.conn().await {
+            let result: RedisResult<usize> = conn.hset(hash, id, self.encode(dest)).await;
+            log_failed_write("HSET", hash, result.map(|_| ()));
+        }
     }
 
-    fn get_from_hash(&self, hash: &str, id: &str) -> Option<String> {
-        self.conn().hget(hash, id).ok()
+    async fn get_from_hash(&self, hash: &str, id: &str) -> Option<String> {
+        match self
+            .conn()
+            .await?
+            .hget::<_, _, Option<Vec<u8>>>(hash, id)
+            .await
+        {
+            Ok(Some(value)) => compression::decode(value),
+            _ => None,
+        }
     }
 
-    fn has_key(&self, id: &str) -> bool {
-        let result: Option<usize> = self.conn().exists(id).ok();
+    /// 0 if the counter could not be incremented
+    async fn increment_in_hash(&self, hash: &str, id: &str, timeout: usize) -> usize {
+        let mut conn = match self.conn().await {
+            Some(conn) => conn,
+            None => return 0,
+        };
+        let result: RedisResult<(usize,)> = pipe()
+            .atomic()
+            .hincr(hash, id, 1)
+            .pexpire(hash, timeout)
+            .ignore()
+            .query_async(&mut conn)
+            .await;
+        match result {
+            Ok((count,)) => count,
+            Err(error) => {
+                log::warn!("Redis HINCRBY of {} failed: {}", hash, error);
+                0
+            }
+        }
+    }
+
+    async fn fetch_hash(&self, hash: &str) -> HashMap<String, String> {
+        let mut conn = match self.conn().await {
+            Some(conn) => conn,
+            None => return HashMap::new(),
+        };
+        match conn.hgetall::<_, HashMap<String, Vec<u8>>>(hash).await {
+            Ok(fields) => fields
+                .into_iter()
+                .filter_map(|(field, value)| Some((field, compression::decode(value)?)))
+                .collect(),
+            _ => HashMap::new(),
+        }
+    }
+
+    async fn has_key(&self, id: &str) -> bool {
+        let result: Option<usize> = match self.conn().await {
+            Some(mut conn) => conn.exists(id).await.ok(),
+            None => None,
+        };
         result.map(|it| it != 0).unwrap_or(false)
     }
 
-    fn expire_entity(&self, id: &str, timeout: usize) {
-        let _: () = self.conn().pexpire(id, timeout).unwrap();
+    async fn ttl(&self, id: &str) -> Option<usize> {
+        let ttl: i64 = self.conn().await?.pttl(id).await.ok()?;
+        if ttl > 0 {
+            Some(ttl as usize)
+        } else {
+            None
+        }
+    }
+
+    async fn expire_entity(&self, id: &str, timeout: usize) {
+        if let Some(mut conn) = self.conn().await {
+            let result: RedisResult<usize> = conn.pexpire(id, timeout).await;
+            log_failed_write("PEXPIRE", id, result.map(|_| ()));
+        }
     }
 
-    fn invalidate_pattern(&self, pattern: &str) {
-        pipeline_delete(
-            &mut self.conn(),
-            scan_match_count(&mut self.conn(), pattern, redis_scan_count()),
-        );
+    async fn keys(&self, pattern: &str) -> Vec<String> {
+        match self.conn().await {
+            Some(mut conn) => scan_match_count(&mut conn, pattern, redis_scan_count()).await,
+            None => vec![],
+        }
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) -> usize {
+        // Keys are collected first so that the scan and the deletion share a connection
+        let mut conn = match self.conn().await {
+            Some(conn) => conn,
+            None => return 0,
+        };
+        let keys = scan_match_count(&mut conn, pattern, redis_scan_count()).await;
+        let count = keys.len();
+        pipeline_delete(&mut conn, keys).await;
+        count
+    }
+
+    async fn invalidate(&self, id: &str) {
+        if let Some(mut conn) = self.conn().await {
+            let result: RedisResult<usize> = conn.del(id).await;
+            log_failed_write("DEL", id, result.map(|_| ()));
+        }
     }
 
-    fn invalidate(&self, id: &str) {
-        let _: () = self.conn().del(id).unwrap();
+    async fn info(&self) -> Option<String> {
+        redis::cmd("INFO")
+            .query_async(&mut self.conn().await?)
+            .await
+            .ok()
     }
 
-    fn info(&self) -> Option<String> {
-        info(&mut self.conn())
+    fn pool_stats(&self) -> Option<PoolStats> {
+        let status = self.pool.status();
+        // A negative number of available connections is the number of checkouts waiting for one
+        let idle = status.available.max(0) as usize;
+        Some(PoolStats {
+            max_size: status.max_size,
+            connections: status.size,
+            in_use: status.size.saturating_sub(idle),
+            idle,
+            waiting: (-status.available).max(0) as usize,
+            checkout_timeouts: self.checkout_timeouts.load(Ordering::Relaxed),
+            checkout_errors: self.checkout_errors.load(Ordering::Relaxed),
+        })
+    }
+
+    async fn append_to_stream(&self, stream: &str, entry: &str, max_len: usize) {
+        let mut conn = match self.conn().await {
+            Some(conn) => conn,
+            None => return,
+        };
+        // Approximate trimming is much cheaper than trimming to the exact length
+        let result: RedisResult<String> = redis::cmd("XADD")
+            .arg(stream)
+            .arg("MAXLEN")
+            .arg("~")
+            .arg(max_len)
+            .arg("*")
+            .arg("entry")
+            .arg(entry)
+            .query_async(&mut conn)
+            .await;
+        if let Err(error) = result {
+            log::warn!("Could not append to stream {}: {}", stream, error);
+        }
+    }
+
+    async fn read_stream(&self, stream: &str, since: i64, count: usize) -> Vec<(String, String)> {
+        let mut conn = match self.conn().await {
+            Some(conn) => conn,
+            None => return vec![],
+        };
+        let entries: Vec<(String, HashMap<String, String>)> = redis::cmd("XRANGE")
+            .arg(stream)
+            .arg(since)
+            .arg("+")
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut conn)
+            .await
+            .unwrap_or_default();
+        entries
+            .into_iter()
+            .filter_map(|(id, mut fields)| Some((id, fields.remove("entry")?)))
+            .collect()
     }
 }
 
-fn pipeline_delete(con: &mut redis::Connection, keys: Iter<String>) {
+async fn pipeline_delete(con: &mut Connection, keys: Vec<String>) {
+    if keys.is_empty() {
+        return;
+    }
     let pipeline = &mut pipe();
     for key in keys {
         pipeline.del(key);
     }
-    pipeline.execute(con);
+    let result: RedisResult<()> = pipeline.query_async(con).await;
+    log_failed_write("DEL", "pattern keys", result);
 }
 
-fn scan_match_count<'r, P: ToRedisArgs, C: ToRedisArgs, RV: FromRedisValue>(
-    con: &'r mut redis::Connection,
-    pattern: P,
-    count: C,
-) -> redis::Iter<'r, RV> {
+async fn scan_match_count(con: &mut Connection, pattern: &str, count: usize) -> Vec<String> {
     let mut cmd = redis::cmd("SCAN");
     cmd.cursor_arg(0)
         .arg("MATCH")
         .arg(pattern)
         .arg("COUNT")
         .arg(count);
-    cmd.iter(con).unwrap()
-}
-
-fn info(con: &mut redis::Connection) -> Option<String> {
-    redis::cmd("INFO").query(con).ok()
+    let mut keys = match cmd.iter_async::<String>(con).await {
+        Ok(keys) => keys,
+        Err(error) => {
+            log::warn!("Redis SCAN failed: {}", error);
+            return vec![];
+        }
+    };
+    let mut result = vec![];
+    while let Some(key) = keys.next_item().await {
+        result.push(key);
+    }
+    result
 }
@@ -0,0 +1,156 @@
+//! Snapshots of the cached chain configurations, token lists and master copies, so that new
+//! instances can warm their cache from a running one instead of the upstream services
+use crate::cache::{namespaced, Cache, CACHE_REQS_PREFIX};
+use crate::providers::info::TOKENS_KEY_BASE;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const MASTER_COPIES_PATH: &str = "/v1/about/master-copies/";
+// Set once a token list is completely populated, see DefaultInfoProvider::check_token_cache
+const TOKEN_LIST_STATE: &str = "state";
+const TOKEN_LIST_POPULATING: &str = "populating";
+const TOKEN_LIST_POPULATED: &str = "populated";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheSnapshot {
+    pub created_at: i64,
+    /// Cached responses of the config service (chains) and of the transaction services
+    /// (master copies)
+    pub entries: Vec<SnapshotEntry>,
+    pub token_lists: Vec<SnapshotTokenList>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotEntry {
+    pub key: String,
+    pub value: String,
+    /// Remaining time to live in ms when the snapshot was taken
+    pub ttl: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotTokenList {
+    pub key: String,
+    /// Token infos by token address
+    pub tokens: BTreeMap<String, String>,
+    /// Remaining time to live in ms when the snapshot was taken
+    pub ttl: usize,
+}
+
+/// Amount of cache entries written by an import
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotImport {
+    pub entries: usize,
+    pub token_lists: usize,
+}
+
+fn chain_requests_pattern() -> String {
+    format!(
+        "{}_{}*",
+        namespaced(CACHE_REQS_PREFIX),
+        config_uri!("/v1/chains/")
+    )
+}
+
+fn master_copies_pattern() -> String {
+    format!("{}_*{}", namespaced(CACHE_REQS_PREFIX), MASTER_COPIES_PATH)
+}
+
+fn token_lists_pattern() -> String {
+    format!("{}_*", TOKENS_KEY_BASE)
+}
+
+fn is_snapshot_entry(key: &str) -> bool {
+    let chain_requests_prefix = chain_requests_pattern();
+    let chain_requests_prefix = chain_requests_prefix.trim_end_matches('*');
+    key.starts_with(chain_requests_prefix)
+        || (key.starts_with(&namespaced(CACHE_REQS_PREFIX)) && key.ends_with(MASTER_COPIES_PATH))
+}
+
+fn is_snapshot_token_list(key: &str) -> bool {
+    key.starts_with(&format!("{}_", TOKENS_KEY_BASE))
+}
+
+/// Entries without a time to live (expired while taking the snapshot) and token lists that are
+/// not completely populated are left out
+pub async fn export(cache: &dyn Cache) -> CacheSnapshot {
+    let mut keys = cache.keys(&chain_requests_pattern()).await;
+    keys.extend(cache.keys(&master_copies_pattern()).await);
+    keys.sort();
+    keys.dedup();
+    let mut entries = vec![];
+    for key in keys {
+        let value = match cache.fetch(&key).await {
+            Some(value) => value,
+            None => continue,
+        };
+        if let Some(ttl) = cache.ttl(&key).await {
+            entries.push(SnapshotEntry { key, value, ttl });
+        }
+    }
+
+    let mut token_list_keys = cache.keys(&token_lists_pattern()).await;
+    token_list_keys.sort();
+    let mut token_lists = vec![];
+    for key in token_list_keys {
+        let mut tokens: BTreeMap<String, String> =
+            cache.fetch_hash(&key).await.into_iter().collect();
+        let state = tokens.remove(TOKEN_LIST_STATE);
+        if state.as_deref() != Some(TOKEN_LIST_POPULATED) {
+            continue;
+        }
+        if let Some(ttl) = cache.ttl(&key).await {
+            token_lists.push(SnapshotTokenList { key, tokens, ttl });
+        }
+    }
+
+    CacheSnapshot {
+        created_at: Utc::now().timestamp_millis(),
+        entries,
+        token_lists,
+    }
+}
+
+/// Writes the entries of the snapshot that are not cached yet, so that fresher local entries are
+/// kept. Keys that are not part of snapshots are ignored.
+pub async fn import(cache: &dyn Cache, snapshot: &CacheSnapshot) -> SnapshotImport {
+    let mut imported = SnapshotImport {
+        entries: 0,
+        token_lists: 0,
+    };
+    for entry in snapshot.entries.iter() {
+        if !is_snapshot_entry(&entry.key) || entry.ttl == 0 || cache.has_key(&entry.key).await {
+            continue;
+        }
+        cache.create(&entry.key, &entry.value, entry.ttl).await;
+        imported.entries += 1;
+    }
+
+    for token_list in snapshot.token_lists.iter() {
+        if !is_snapshot_token_list(&token_list.key)
+            || token_list.ttl == 0
+            || cache.has_key(&token_list.key).await
+        {
+            continue;
+        }
+        // Same sequence as DefaultInfoProvider::check_token_cache, so the list isn't reported as
+        // populated before it is complete
+        cache
+            .insert_in_hash(&token_list.key, TOKEN_LIST_STATE, TOKEN_LIST_POPULATING)
+            .await;
+        for (address, token) in token_list.tokens.iter() {
+            cache.insert_in_hash(&token_list.key, address, token).await;
+        }
+        cache.expire_entity(&token_list.key, token_list.ttl).await;
+        cache
+            .insert_in_hash(&token_list.key, TOKEN_LIST_STATE, TOKEN_LIST_POPULATED)
+            .await;
+        imported.token_lists += 1;
+    }
+    imported
+}
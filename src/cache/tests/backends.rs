@@ -0,0 +1,58 @@
+use crate::cache::{create_cache, Cache};
+use dotenv::dotenv;
+
+// Shared by every backend, run against the one configured with CACHE_BACKEND
+async fn assert_cache_contract(cache: &dyn Cache, prefix: &str) {
+    let key = format!("{}_value", prefix);
+    cache.create(&key, "200;value", 60000).await;
+    assert_eq!(cache.fetch(&key).await, Some(String::from("200;value")));
+    assert!(cache.has_key(&key).await);
+    assert!(cache.ttl(&key).await.map_or(false, |ttl| ttl <= 60000));
+    cache.expire_entity(&key, 120000).await;
+    assert!(cache.ttl(&key).await.map_or(false, |ttl| ttl > 60000));
+
+    let hash = format!("{}_hash", prefix);
+    cache.insert_in_hash(&hash, "a", "1").await;
+    cache.insert_in_hash(&hash, "b", "2").await;
+    assert_eq!(
+        cache.get_from_hash(&hash, "a").await,
+        Some(String::from("1"))
+    );
+    assert_eq!(cache.get_from_hash(&hash, "c").await, None);
+    assert_eq!(cache.fetch_hash(&hash).await.len(), 2);
+    assert_eq!(cache.ttl(&hash).await, None);
+
+    let counters = format!("{}_counters", prefix);
+    assert_eq!(cache.increment_in_hash(&counters, "a", 60000).await, 1);
+    assert_eq!(cache.increment_in_hash(&counters, "a", 60000).await, 2);
+    assert_eq!(cache.increment_in_hash(&counters, "b", 60000).await, 1);
+
+    let stream = format!("{}_stream", prefix);
+    for entry in &["first", "second", "third"] {
+        cache.append_to_stream(&stream, entry, 100).await;
+    }
+    let entries: Vec<String> = cache
+        .read_stream(&stream, 0, 2)
+        .await
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect();
+    assert_eq!(entries, vec!["first", "second"]);
+
+    let mut keys = cache.keys(&format!("{}_*", prefix)).await;
+    keys.sort();
+    assert_eq!(keys, vec![counters, hash, stream, key.to_string()]);
+
+    cache.invalidate(&key).await;
+    assert!(!cache.has_key(&key).await);
+    assert_eq!(cache.invalidate_pattern(&format!("{}_*", prefix)).await, 3);
+    assert!(cache.keys(&format!("{}_*", prefix)).await.is_empty());
+}
+
+#[rocket::async_test]
+async fn configured_backend_fulfills_cache_contract() {
+    dotenv().ok();
+    let prefix = format!("cache_contract_{}", rand::random::<u32>());
+
+    assert_cache_contract(create_cache().as_ref(), &prefix).await;
+}
@@ -1,7 +1,18 @@
-use crate::cache::cache_operations::{InvalidationPattern, InvalidationScope};
-use crate::cache::{CACHE_REQS_PREFIX, CACHE_REQS_RESP_PREFIX, CACHE_RESP_PREFIX};
+use crate::cache::cache_operations::{
+    response_key, CacheResponse, InvalidationPattern, InvalidationScope, RequestCached,
+};
+use crate::cache::{
+    Cache, MockCache, CACHE_REQS_PREFIX, CACHE_REQS_RESP_PREFIX, CACHE_RESP_PREFIX,
+};
 use crate::config::base_config_service_uri;
 use crate::providers::info::TOKENS_KEY_BASE;
+use crate::utils::cache_control::DataFreshness;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::{HttpClient, MockHttpClient, Response};
+use mockall::predicate::eq;
+use serde_json::json;
+use std::sync::Arc;
 
 #[test]
 fn invalidation_pattern_any_string() {
@@ -114,3 +125,221 @@ fn invalidation_scope_responses_to_string() {
         InvalidationScope::Responses.invalidation_scope_string()
     )
 }
+
+#[rocket::async_test]
+async fn request_cached_returns_cached_success_only() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_reqs_https://example.com/success"))
+        .return_const(Some(String::from("200;{\"data\":1}")));
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_reqs_https://example.com/error"))
+        .return_const(Some(String::from("404;Not found")));
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+    let client: Arc<dyn HttpClient> = Arc::new(MockHttpClient::new());
+
+    let success = RequestCached::new(String::from("https://example.com/success"), &client, &cache)
+        .cached()
+        .await;
+    let error = RequestCached::new(String::from("https://example.com/error"), &client, &cache)
+        .cached()
+        .await;
+
+    assert_eq!(Some(String::from("{\"data\":1}")), success);
+    assert_eq!(None, error);
+}
+
+#[rocket::async_test]
+async fn request_cached_overwrite() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_create()
+        .times(1)
+        .with(
+            eq("c_reqs_https://example.com/safe"),
+            eq("200;{\"data\":2}"),
+            eq(1000),
+        )
+        .return_const(());
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+    let client: Arc<dyn HttpClient> = Arc::new(MockHttpClient::new());
+
+    RequestCached::new(String::from("https://example.com/safe"), &client, &cache)
+        .cache_duration(1000)
+        .overwrite("{\"data\":2}")
+        .await;
+}
+
+#[rocket::async_test]
+async fn request_cached_keeps_last_known_good_copy() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_reqs_https://example.com/chains/4"))
+        .return_const(None);
+    mock_cache
+        .expect_create()
+        .times(1)
+        .withf(|key, value, _| key == "c_reqs_https://example.com/chains/4" && value == "200;{}")
+        .return_const(());
+    mock_cache
+        .expect_create()
+        .times(1)
+        .withf(|key, value, _| key == "c_lkg_https://example.com/chains/4" && value == "{}")
+        .return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_get().times(1).return_once(|_| {
+        Ok(Response {
+            status_code: 200,
+            body: String::from("{}"),
+        })
+    });
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+    let client: Arc<dyn HttpClient> = Arc::new(mock_http_client);
+    let data_freshness = DataFreshness::default();
+
+    let actual = RequestCached::new(
+        String::from("https://example.com/chains/4"),
+        &client,
+        &cache,
+    )
+    .keep_last_known_good()
+    .data_freshness(&data_freshness)
+    .execute()
+    .await;
+
+    assert_eq!(String::from("{}"), actual.unwrap());
+    assert!(!data_freshness.is_stale());
+}
+
+#[rocket::async_test]
+async fn request_cached_serves_last_known_good_copy_on_server_error() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_reqs_https://example.com/chains/5"))
+        .return_const(None);
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_lkg_https://example.com/chains/5"))
+        .return_const(Some(String::from("{\"chainId\":\"5\"}")));
+    mock_cache.expect_create().times(0);
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_get()
+        .times(1)
+        .return_once(|_| Err(ApiError::new_from_message_with_code(502, String::from(""))));
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+    let client: Arc<dyn HttpClient> = Arc::new(mock_http_client);
+    let data_freshness = DataFreshness::default();
+
+    let actual = RequestCached::new(
+        String::from("https://example.com/chains/5"),
+        &client,
+        &cache,
+    )
+    .keep_last_known_good()
+    .data_freshness(&data_freshness)
+    .execute()
+    .await;
+
+    assert_eq!(String::from("{\"chainId\":\"5\"}"), actual.unwrap());
+    assert!(data_freshness.is_stale());
+}
+
+#[rocket::async_test]
+async fn request_cached_client_error_not_replaced_by_last_known_good_copy() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_reqs_https://example.com/chains/6"))
+        .return_const(None);
+    mock_cache
+        .expect_create()
+        .times(1)
+        .withf(|key, _, _| key == "c_reqs_https://example.com/chains/6")
+        .return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_get().times(1).return_once(|_| {
+        Err(ApiError::new_from_message_with_code(
+            404,
+            String::from("Not found"),
+        ))
+    });
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+    let client: Arc<dyn HttpClient> = Arc::new(mock_http_client);
+    let data_freshness = DataFreshness::default();
+
+    let actual = RequestCached::new(
+        String::from("https://example.com/chains/6"),
+        &client,
+        &cache,
+    )
+    .keep_last_known_good()
+    .data_freshness(&data_freshness)
+    .execute()
+    .await;
+
+    assert_eq!(404, actual.unwrap_err().status);
+    assert!(!data_freshness.is_stale());
+}
+
+#[test]
+fn response_key_sorts_query_parameters() {
+    assert_eq!(
+        response_key("/v1/chains/4/safes/0x1230/balances/USD?trusted=false&exclude_spam=true"),
+        "/v1/chains/4/safes/0x1230/balances/USD?exclude_spam=true&trusted=false"
+    );
+    assert_eq!(
+        response_key("/v1/chains/4/safes/0x1230/balances/USD?exclude_spam=true&trusted=false"),
+        "/v1/chains/4/safes/0x1230/balances/USD?exclude_spam=true&trusted=false"
+    );
+}
+
+#[test]
+fn response_key_without_query_parameters() {
+    assert_eq!(
+        response_key("/v1/chains/4/safes/0x1230"),
+        "/v1/chains/4/safes/0x1230"
+    );
+    assert_eq!(
+        response_key("/v1/chains/4/safes/0x1230?"),
+        "/v1/chains/4/safes/0x1230"
+    );
+}
+
+#[rocket::async_test]
+async fn cache_response_caches_response_trimmed_to_budget() {
+    let mut mock_cache = MockCache::new();
+    mock_cache.expect_fetch().times(1).return_const(None);
+    mock_cache
+        .expect_create()
+        .times(1)
+        .withf(|_, value, _| value == r#"{"name":"Test","truncated":true}"#)
+        .return_const(());
+    let context = RequestContext::mock(
+        String::from("/v1/chains/4/safes/0x1/balances/usd"),
+        String::from("localhost"),
+        MockHttpClient::new(),
+        mock_cache,
+    )
+    .with_response_budget(40);
+
+    let actual = CacheResponse::new(&context)
+        .resp_generator(|| async {
+            Ok(json!({"name": "Test", "logoUri": "https://tokens.example/logo.png"}))
+        })
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(r#"{"name":"Test","truncated":true}"#, actual.0);
+}
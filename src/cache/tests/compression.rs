@@ -0,0 +1,93 @@
+use crate::cache::compression::{decode, encode};
+
+fn history_payload() -> String {
+    let items: Vec<String> = (0..200)
+        .map(|nonce| {
+            format!(
+                r#"{{"type":"TRANSACTION","transaction":{{"id":"multisig_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x{:064x}","timestamp":1606744033000,"txStatus":"SUCCESS","executionInfo":{{"type":"MULTISIG","nonce":{},"confirmationsRequired":2,"confirmationsSubmitted":2}}}},"conflictType":"None"}}"#,
+                nonce * 7919, nonce
+            )
+        })
+        .collect();
+    format!(
+        r#"200;{{"next":null,"previous":null,"results":[{}]}}"#,
+        items.join(",")
+    )
+}
+
+#[test]
+fn encode_compresses_large_values() {
+    let payload = history_payload();
+
+    let encoded = encode(&payload, 1024);
+
+    assert!(encoded.len() < payload.len() / 2);
+    assert_eq!(decode(encoded), Some(payload));
+}
+
+#[test]
+fn encode_keeps_values_below_threshold() {
+    let payload = history_payload();
+
+    assert_eq!(
+        encode("200;{\"data\":1}", 1024),
+        b"200;{\"data\":1}".to_vec()
+    );
+    assert_eq!(
+        encode(&payload, payload.len() + 1),
+        payload.as_bytes().to_vec()
+    );
+}
+
+#[test]
+fn encode_with_zero_threshold_is_disabled() {
+    let payload = history_payload();
+
+    assert_eq!(encode(&payload, 0), payload.as_bytes().to_vec());
+}
+
+#[test]
+fn encode_keeps_incompressible_values() {
+    let payload = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+    assert_eq!(encode(payload, 1), payload.as_bytes().to_vec());
+}
+
+#[test]
+fn decode_round_trips_repeated_and_unicode_values() {
+    let payloads = [
+        "a".repeat(5000),
+        "€uro ✓ ".repeat(300),
+        format!("{}{}", "x".repeat(70000), "tail beyond the deflate window"),
+    ];
+
+    for payload in payloads.iter() {
+        assert_eq!(decode(encode(payload, 1)).as_ref(), Some(payload));
+    }
+}
+
+#[test]
+fn decode_rejects_corrupted_values() {
+    let mut encoded = encode(&history_payload(), 1);
+    encoded.truncate(encoded.len() / 2);
+
+    assert_eq!(decode(encoded), None);
+    assert_eq!(decode(b"\x00csz\x01".to_vec()), None);
+    assert_eq!(decode(b"\x00csz\x01\x05\x00\x00\x00hello".to_vec()), None);
+}
+
+#[test]
+fn decode_rejects_unknown_formats() {
+    let mut encoded = encode(&history_payload(), 1);
+    encoded[4] = 2;
+
+    assert_eq!(decode(encoded), None);
+}
+
+#[test]
+fn decode_reads_uncompressed_values() {
+    let payload = history_payload();
+
+    assert_eq!(decode(payload.as_bytes().to_vec()), Some(payload));
+    assert_eq!(decode(b"".to_vec()), Some(String::new()));
+}
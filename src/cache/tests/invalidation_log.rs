@@ -0,0 +1,66 @@
+use crate::cache::invalidation_log::{read, record, InvalidationEntry};
+use crate::cache::MockCache;
+use mockall::predicate::eq;
+
+fn entry(source: &str) -> InvalidationEntry {
+    InvalidationEntry {
+        timestamp: 1637833423000,
+        source: source.to_string(),
+        pattern: String::from("c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*"),
+        keys: 3,
+    }
+}
+
+#[rocket::async_test]
+async fn record_appends_entry_to_invalidations_stream() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_append_to_stream()
+        .withf(|stream, entry_json, _| {
+            stream == "invalidations"
+                && entry_json == serde_json::to_string(&entry("HOOK NEW_CONFIRMATION")).unwrap()
+        })
+        .times(1)
+        .return_const(());
+
+    record(&mock_cache, &entry("HOOK NEW_CONFIRMATION")).await;
+}
+
+#[rocket::async_test]
+async fn read_skips_malformed_entries() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_read_stream()
+        .with(eq("invalidations"), eq(1637833420000), eq(100))
+        .times(1)
+        .return_const(vec![
+            (
+                String::from("1637833423000-0"),
+                serde_json::to_string(&entry("FLUSH")).unwrap(),
+            ),
+            (String::from("1637833424000-0"), String::from("{}")),
+            (
+                String::from("1637833425000-0"),
+                serde_json::to_string(&entry("PROPOSAL")).unwrap(),
+            ),
+        ]);
+
+    let actual = read(&mock_cache, 1637833420000, 100).await;
+
+    assert_eq!(actual, vec![entry("FLUSH"), entry("PROPOSAL")]);
+}
+
+#[test]
+fn invalidation_entry_json() {
+    let actual = serde_json::to_value(&entry("CHAIN_DISCOVERY")).unwrap();
+
+    assert_eq!(
+        actual,
+        serde_json::json!({
+            "timestamp": 1637833423000i64,
+            "source": "CHAIN_DISCOVERY",
+            "pattern": "c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*",
+            "keys": 3
+        })
+    );
+}
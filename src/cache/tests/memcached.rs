@@ -0,0 +1,89 @@
+use crate::cache::memcached::{glob_match, next_stream_id, storage_key, Entry, MemcachedCache};
+use crate::cache::Cache;
+
+#[test]
+fn glob_match_supports_redis_wildcards() {
+    let key = "c_reqs_https://safe-transaction.gnosis.io/api/v1/safes/0x1230/balances/";
+
+    assert!(glob_match("c_re*/0x1230/balances*", key));
+    assert!(glob_match("*", key));
+    assert!(glob_match("c_reqs_http?://*", key));
+    assert!(glob_match("c_re*0x1230*", key));
+    assert!(!glob_match("c_re*/0x1230/collectibles*", key));
+    assert!(!glob_match("c_resp*", key));
+    assert!(glob_match("a\\*b", "a*b"));
+    assert!(!glob_match("a\\*b", "axb"));
+}
+
+#[test]
+fn storage_key_hashes_invalid_memcached_keys() {
+    let long_key = format!("c_reqs_{}", "a".repeat(300));
+
+    assert_eq!(storage_key("c_reqs_short"), "c_reqs_short");
+    assert!(storage_key(&long_key).starts_with("h_0x"));
+    assert_eq!(storage_key(&long_key), storage_key(&long_key));
+    assert_ne!(storage_key("with space"), "with space");
+}
+
+#[test]
+fn entry_round_trip() {
+    let entry = Entry {
+        expires_at: 1636041600000,
+        value: String::from("200;{\"a\":\"b;c\"}"),
+    };
+
+    assert_eq!(Entry::from_bytes(entry.to_bytes(0)), Some(entry));
+    assert_eq!(Entry::from_bytes(b"not an entry".to_vec()), None);
+}
+
+#[test]
+fn entry_expiry() {
+    let entry = Entry {
+        expires_at: 1000,
+        value: String::new(),
+    };
+    let persistent = Entry {
+        expires_at: 0,
+        value: String::new(),
+    };
+
+    assert!(!entry.is_expired(999));
+    assert!(entry.is_expired(1000));
+    assert!(!persistent.is_expired(i64::MAX));
+}
+
+#[test]
+fn next_stream_id_increments_sequence_within_same_ms() {
+    assert_eq!(next_stream_id(None, 1000), "1000-0");
+    assert_eq!(next_stream_id(Some("999-4"), 1000), "1000-0");
+    assert_eq!(next_stream_id(Some("1000-4"), 1000), "1000-5");
+    // Clock went backwards, ids keep increasing
+    assert_eq!(next_stream_id(Some("1200-0"), 1000), "1200-1");
+}
+
+#[rocket::async_test]
+async fn unreachable_memcached_times_out_without_panicking() {
+    // Nothing listens on port 1
+    let cache = MemcachedCache::new("memcache://127.0.0.1:1", 1, 50);
+
+    cache.create("unreachable", "200;value", 60000).await;
+    cache.insert_in_hash("unreachable_hash", "a", "1").await;
+    cache
+        .append_to_stream("unreachable_stream", "entry", 10)
+        .await;
+
+    assert_eq!(cache.fetch("unreachable").await, None);
+    assert_eq!(cache.get_from_hash("unreachable_hash", "a").await, None);
+    assert_eq!(
+        cache
+            .increment_in_hash("unreachable_hash", "b", 60000)
+            .await,
+        0
+    );
+    assert!(!cache.has_key("unreachable").await);
+    assert!(cache
+        .read_stream("unreachable_stream", 0, 10)
+        .await
+        .is_empty());
+    assert_eq!(cache.info().await, None);
+}
@@ -0,0 +1,121 @@
+use crate::cache::migration::{migrate, reserialize, CacheMigration, MigrationState};
+use crate::cache::MockCache;
+use mockall::predicate::eq;
+
+fn running_migration() -> CacheMigration {
+    CacheMigration {
+        from_namespace: String::new(),
+        to_namespace: String::from("v2"),
+        state: MigrationState::Running,
+        migrated: 0,
+        skipped: 0,
+        started_at: 1636041600000,
+        finished_at: None,
+    }
+}
+
+#[test]
+fn reserialize_requests_keeps_status_code() {
+    assert_eq!(
+        reserialize("c_reqs", "200;{ \"count\": 1 }"),
+        Some(String::from("200;{\"count\":1}"))
+    );
+    assert_eq!(
+        reserialize("c_reqs", "404;Not found"),
+        Some(String::from("404;Not found"))
+    );
+    assert_eq!(reserialize("c_reqs", "200;<html>"), None);
+    assert_eq!(reserialize("c_reqs", "not cached by the gateway"), None);
+}
+
+#[test]
+fn reserialize_responses() {
+    assert_eq!(
+        reserialize("c_resp", "{ \"results\": [] }"),
+        Some(String::from("{\"results\":[]}"))
+    );
+    assert_eq!(reserialize("c_lkg", "{"), None);
+}
+
+#[rocket::async_test]
+async fn migrate_copies_keys_into_target_namespace() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_keys()
+        .with(eq("c_reqs_*"))
+        .times(1)
+        .return_const(vec![
+            String::from("c_reqs_https://example.com/chains/4"),
+            String::from("c_reqs_https://example.com/chains/5"),
+            String::from("c_reqs_https://example.com/broken"),
+        ]);
+    mock_cache
+        .expect_keys()
+        .with(eq("c_resp_*"))
+        .times(1)
+        .return_const(vec![]);
+    mock_cache
+        .expect_keys()
+        .with(eq("c_lkg_*"))
+        .times(1)
+        .return_const(vec![]);
+    mock_cache
+        .expect_has_key()
+        .with(eq("v2_c_reqs_https://example.com/chains/4"))
+        .times(1)
+        .return_const(false);
+    // Already cached by the new deployment
+    mock_cache
+        .expect_has_key()
+        .with(eq("v2_c_reqs_https://example.com/chains/5"))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_has_key()
+        .with(eq("v2_c_reqs_https://example.com/broken"))
+        .times(1)
+        .return_const(false);
+    mock_cache
+        .expect_fetch()
+        .with(eq("c_reqs_https://example.com/chains/4"))
+        .times(1)
+        .return_const(Some(String::from("200;{ \"chainId\": \"4\" }")));
+    mock_cache
+        .expect_fetch()
+        .with(eq("c_reqs_https://example.com/broken"))
+        .times(1)
+        .return_const(Some(String::from("200;{")));
+    mock_cache
+        .expect_ttl()
+        .with(eq("c_reqs_https://example.com/chains/4"))
+        .times(1)
+        .return_const(Some(1000));
+    mock_cache
+        .expect_ttl()
+        .with(eq("c_reqs_https://example.com/broken"))
+        .times(1)
+        .return_const(Some(1000));
+    mock_cache
+        .expect_create()
+        .with(
+            eq("v2_c_reqs_https://example.com/chains/4"),
+            eq("200;{\"chainId\":\"4\"}"),
+            eq(1000),
+        )
+        .times(1)
+        .return_const(());
+    mock_cache
+        .expect_insert_in_hash()
+        .withf(|key, field, status| {
+            key == "cache_migration" && field == "status" && status.contains("\"COMPLETED\"")
+        })
+        .times(1)
+        .return_const(());
+
+    let actual = migrate(&mock_cache, running_migration()).await;
+
+    assert_eq!(actual.state, MigrationState::Completed);
+    assert_eq!(actual.migrated, 1);
+    assert_eq!(actual.skipped, 2);
+    assert!(actual.finished_at.is_some());
+}
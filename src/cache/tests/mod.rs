@@ -1,2 +1,9 @@
+mod backends;
 mod cache_inner;
 mod cache_operations;
+mod compression;
+mod invalidation_log;
+mod memcached;
+mod migration;
+mod redis;
+mod snapshot;
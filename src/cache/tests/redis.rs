@@ -0,0 +1,62 @@
+use crate::cache::redis::ServiceCache;
+use crate::cache::Cache;
+use crate::config::redis_uri;
+use dotenv::dotenv;
+use std::time::{Duration, Instant};
+
+// Nothing listens on port 1
+const UNREACHABLE_REDIS: &str = "redis://127.0.0.1:1/";
+
+async fn assert_cache_unavailable(cache: &ServiceCache, key: &str) {
+    cache.create(key, "200;value", 60000).await;
+    cache.insert_in_hash(key, "a", "1").await;
+    cache.expire_entity(key, 60000).await;
+    cache.invalidate(key).await;
+    cache.append_to_stream(key, "entry", 10).await;
+
+    assert_eq!(cache.fetch(key).await, None);
+    assert_eq!(cache.get_from_hash(key, "a").await, None);
+    assert_eq!(cache.increment_in_hash(key, "a", 60000).await, 0);
+    assert!(cache.fetch_hash(key).await.is_empty());
+    assert!(!cache.has_key(key).await);
+    assert_eq!(cache.ttl(key).await, None);
+    assert!(cache.keys("*").await.is_empty());
+    assert_eq!(cache.invalidate_pattern("*").await, 0);
+    assert!(cache.read_stream(key, 0, 10).await.is_empty());
+}
+
+#[rocket::async_test]
+async fn unreachable_redis_times_out_without_panicking() {
+    let cache = ServiceCache::with_pool(UNREACHABLE_REDIS, 1, 50);
+
+    let started = Instant::now();
+    assert_cache_unavailable(&cache, "pool_unreachable").await;
+
+    assert_eq!(cache.info().await, None);
+    // Every checkout waits at most for the connection timeout
+    assert!(started.elapsed() < Duration::from_secs(5));
+    let stats = cache.pool_stats().unwrap();
+    assert_eq!(stats.connections, 0);
+    assert!(stats.checkout_timeouts + stats.checkout_errors > 0);
+}
+
+#[rocket::async_test]
+async fn exhausted_pool_times_out_without_panicking() {
+    dotenv().ok();
+    let cache = ServiceCache::with_pool(&redis_uri(), 1, 50);
+    let key = format!("pool_exhausted_{}", rand::random::<u32>());
+
+    let checked_out = cache.conn().await.expect("Redis is not reachable");
+    let stats = cache.pool_stats().unwrap();
+    assert_eq!((stats.max_size, stats.in_use, stats.idle), (1, 1, 0));
+
+    assert_cache_unavailable(&cache, &key).await;
+    assert!(cache.pool_stats().unwrap().checkout_timeouts > 0);
+    drop(checked_out);
+
+    cache.create(&key, "200;value", 60000).await;
+    assert_eq!(cache.fetch(&key).await, Some(String::from("200;value")));
+    cache.invalidate(&key).await;
+    let stats = cache.pool_stats().unwrap();
+    assert_eq!((stats.in_use, stats.idle, stats.waiting), (0, 1, 0));
+}
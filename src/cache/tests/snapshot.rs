@@ -0,0 +1,195 @@
+use crate::cache::snapshot::{export, import, CacheSnapshot, SnapshotEntry, SnapshotTokenList};
+use crate::cache::MockCache;
+use mockall::predicate::eq;
+use std::collections::{BTreeMap, HashMap};
+
+const MASTER_COPIES_KEY: &str =
+    "c_reqs_https://safe-transaction.rinkeby.gnosis.io/api/v1/about/master-copies/";
+const TOKEN_LIST_KEY: &str = "dip_ti_4";
+
+fn chain_key() -> String {
+    format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"))
+}
+
+fn chain_requests_pattern() -> String {
+    format!("c_reqs_{}*", config_uri!("/v1/chains/"))
+}
+
+#[rocket::async_test]
+async fn export_cached_chains_token_lists_and_master_copies() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_keys()
+        .with(eq(chain_requests_pattern()))
+        .times(1)
+        .return_const(vec![chain_key()]);
+    mock_cache
+        .expect_keys()
+        .with(eq("c_reqs_*/v1/about/master-copies/"))
+        .times(1)
+        .return_const(vec![String::from(MASTER_COPIES_KEY)]);
+    mock_cache
+        .expect_keys()
+        .with(eq("dip_ti_*"))
+        .times(1)
+        .return_const(vec![
+            String::from(TOKEN_LIST_KEY),
+            String::from("dip_ti_137"),
+        ]);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key()))
+        .times(1)
+        .return_const(Some(String::from("{\"chainId\":\"4\"}")));
+    mock_cache
+        .expect_fetch()
+        .with(eq(MASTER_COPIES_KEY))
+        .times(1)
+        .return_const(Some(String::from("[]")));
+    mock_cache
+        .expect_ttl()
+        .with(eq(chain_key()))
+        .times(1)
+        .return_const(Some(1000));
+    mock_cache
+        .expect_ttl()
+        .with(eq(MASTER_COPIES_KEY))
+        .times(1)
+        .return_const(Some(2000));
+    mock_cache
+        .expect_fetch_hash()
+        .with(eq(TOKEN_LIST_KEY))
+        .times(1)
+        .return_const(
+            vec![
+                (String::from("state"), String::from("populated")),
+                (String::from("0x1"), String::from("{}")),
+            ]
+            .into_iter()
+            .collect::<HashMap<String, String>>(),
+        );
+    // Still populating, left out
+    mock_cache
+        .expect_fetch_hash()
+        .with(eq("dip_ti_137"))
+        .times(1)
+        .return_const(
+            vec![(String::from("state"), String::from("populating"))]
+                .into_iter()
+                .collect::<HashMap<String, String>>(),
+        );
+    mock_cache
+        .expect_ttl()
+        .with(eq(TOKEN_LIST_KEY))
+        .times(1)
+        .return_const(Some(3000));
+
+    let actual = export(&mock_cache).await;
+
+    let mut tokens = BTreeMap::new();
+    tokens.insert(String::from("0x1"), String::from("{}"));
+    assert_eq!(
+        actual.entries,
+        vec![
+            SnapshotEntry {
+                key: String::from(MASTER_COPIES_KEY),
+                value: String::from("[]"),
+                ttl: 2000,
+            },
+            SnapshotEntry {
+                key: chain_key(),
+                value: String::from("{\"chainId\":\"4\"}"),
+                ttl: 1000,
+            },
+        ]
+    );
+    assert_eq!(
+        actual.token_lists,
+        vec![SnapshotTokenList {
+            key: String::from(TOKEN_LIST_KEY),
+            tokens,
+            ttl: 3000,
+        }]
+    );
+}
+
+#[rocket::async_test]
+async fn import_keeps_cached_entries_and_ignores_foreign_keys() {
+    let mut tokens = BTreeMap::new();
+    tokens.insert(String::from("0x1"), String::from("{}"));
+    let snapshot = CacheSnapshot {
+        created_at: 0,
+        entries: vec![
+            SnapshotEntry {
+                key: chain_key(),
+                value: String::from("{\"chainId\":\"4\"}"),
+                ttl: 1000,
+            },
+            SnapshotEntry {
+                key: String::from(MASTER_COPIES_KEY),
+                value: String::from("[]"),
+                ttl: 2000,
+            },
+            SnapshotEntry {
+                key: String::from("c_resp_/v1/chains/4/safes/0x1"),
+                value: String::from("{}"),
+                ttl: 2000,
+            },
+        ],
+        token_lists: vec![SnapshotTokenList {
+            key: String::from(TOKEN_LIST_KEY),
+            tokens,
+            ttl: 3000,
+        }],
+    };
+
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key()))
+        .times(1)
+        .return_const(false);
+    mock_cache
+        .expect_has_key()
+        .with(eq(MASTER_COPIES_KEY))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_has_key()
+        .with(eq(TOKEN_LIST_KEY))
+        .times(1)
+        .return_const(false);
+    let expected_chain_key = chain_key();
+    mock_cache
+        .expect_create()
+        .withf(move |key, value, ttl| {
+            key == expected_chain_key && value == "{\"chainId\":\"4\"}" && *ttl == 1000
+        })
+        .times(1)
+        .return_const(());
+    mock_cache
+        .expect_insert_in_hash()
+        .with(eq(TOKEN_LIST_KEY), eq("state"), eq("populating"))
+        .times(1)
+        .return_const(());
+    mock_cache
+        .expect_insert_in_hash()
+        .with(eq(TOKEN_LIST_KEY), eq("0x1"), eq("{}"))
+        .times(1)
+        .return_const(());
+    mock_cache
+        .expect_expire_entity()
+        .with(eq(TOKEN_LIST_KEY), eq(3000))
+        .times(1)
+        .return_const(());
+    mock_cache
+        .expect_insert_in_hash()
+        .with(eq(TOKEN_LIST_KEY), eq("state"), eq("populated"))
+        .times(1)
+        .return_const(());
+
+    let actual = import(&mock_cache, &snapshot).await;
+
+    assert_eq!(actual.entries, 1);
+    assert_eq!(actual.token_lists, 1);
+}
@@ -1,27 +1,38 @@
 use crate::common::models::addresses::AddressEx;
 use crate::common::models::data_decoded::{DataDecoded, ParamValue, Parameter, ValueDecodedType};
 use crate::config::feature_flag_nested_decoding;
+use crate::providers::address_info::{
+    address_ex_index, address_ex_or_default, AddressInfoIndex, AddressSource,
+};
 use crate::providers::ext::InfoProviderExt;
 use crate::providers::info::InfoProvider;
+use crate::routes::transactions::models::details::MultiSendAction;
 use crate::routes::transactions::models::SettingsInfo;
 use crate::utils::{
     ADD_OWNER_WITH_THRESHOLD, CHANGE_MASTER_COPY, CHANGE_THRESHOLD, DISABLE_MODULE, ENABLE_MODULE,
     MULTI_SEND, MULTI_SEND_TRANSACTIONS_PARAM, REMOVE_OWNER, SET_FALLBACK_HANDLER, SWAP_OWNER,
 };
-use std::collections::HashMap;
 
 impl DataDecoded {
     pub(crate) async fn to_settings_info(
         &self,
         info_provider: &(impl InfoProvider + Sync),
     ) -> Option<SettingsInfo> {
+        // The contract of the settings change is resolved with the batched lookup
+        let contract = match self.method.as_str() {
+            SET_FALLBACK_HANDLER | CHANGE_MASTER_COPY | ENABLE_MODULE => {
+                self.get_parameter_single_value_at(0)
+            }
+            DISABLE_MODULE => self.get_parameter_single_value_at(1),
+            _ => None,
+        };
+        let lookups: Vec<&str> = contract.iter().map(String::as_str).collect();
+        let index = address_ex_index(info_provider, &lookups, AddressSource::Contracts).await;
         match self.method.as_str() {
             SET_FALLBACK_HANDLER => {
                 let handler = self.get_parameter_single_value_at(0)?;
                 Some(SettingsInfo::SetFallbackHandler {
-                    handler: info_provider
-                        .address_ex_from_contracts_or_default(&handler)
-                        .await,
+                    handler: address_ex_or_default(&index, &handler),
                 })
             }
             ADD_OWNER_WITH_THRESHOLD => {
@@ -49,25 +60,19 @@ impl DataDecoded {
             CHANGE_MASTER_COPY => {
                 let implementation = self.get_parameter_single_value_at(0)?;
                 Some(SettingsInfo::ChangeImplementation {
-                    implementation: info_provider
-                        .address_ex_from_contracts_or_default(&implementation)
-                        .await,
+                    implementation: address_ex_or_default(&index, &implementation),
                 })
             }
             ENABLE_MODULE => {
                 let module = self.get_parameter_single_value_at(0)?;
                 Some(SettingsInfo::EnableModule {
-                    module: info_provider
-                        .address_ex_from_contracts_or_default(&module)
-                        .await,
+                    module: address_ex_or_default(&index, &module),
                 })
             }
             DISABLE_MODULE => {
                 let module = self.get_parameter_single_value_at(1)?;
                 Some(SettingsInfo::DisableModule {
-                    module: info_provider
-                        .address_ex_from_contracts_or_default(&module)
-                        .await,
+                    module: address_ex_or_default(&index, &module),
                 })
             }
             CHANGE_THRESHOLD => Some(SettingsInfo::ChangeThreshold {
@@ -81,13 +86,13 @@ impl DataDecoded {
 impl DataDecoded {
     pub(crate) async fn build_address_info_index(
         &self,
-        info_provider: &impl InfoProvider,
-    ) -> Option<HashMap<String, AddressEx>> {
+        info_provider: &(impl InfoProvider + Sync),
+    ) -> Option<AddressInfoIndex> {
         if !feature_flag_nested_decoding() {
             return None;
         }
 
-        let mut index: HashMap<String, AddressEx> = HashMap::new();
+        let mut addresses: Vec<String> = vec![];
         if self.method == MULTI_SEND {
             if let Some(value_decoded_type) =
                 &self.get_parameter_value_decoded(MULTI_SEND_TRANSACTIONS_PARAM)
@@ -95,48 +100,70 @@ impl DataDecoded {
                 match value_decoded_type {
                     ValueDecodedType::InternalTransaction(transactions) => {
                         for transaction in transactions.iter() {
-                            insert_value_into_index(&transaction.to, &mut index, info_provider)
-                                .await;
-                            put_parameter_into_index(
+                            addresses.push(transaction.to.to_owned());
+                            collect_parameter_addresses(
                                 &transaction
                                     .data_decoded
                                     .as_ref()
                                     .map(|it| it.parameters.to_owned())
                                     .flatten(),
-                                &mut index,
-                                info_provider,
+                                &mut addresses,
                             )
-                            .await
                         }
                     }
                 }
             }
         } else {
-            put_parameter_into_index(&self.parameters, &mut index, info_provider).await;
+            collect_parameter_addresses(&self.parameters, &mut addresses);
         }
+
+        let index = info_provider.address_info_index(&addresses).await;
         if index.is_empty() {
             None
         } else {
             Some(index)
         }
     }
+
+    /// Sub-transactions of a MultiSend in execution order, their targets enriched with the info
+    /// found in `address_info_index`. `None` for other methods.
+    pub(crate) fn multi_send_actions(
+        &self,
+        address_info_index: Option<&AddressInfoIndex>,
+    ) -> Option<Vec<MultiSendAction>> {
+        if self.method != MULTI_SEND {
+            return None;
+        }
+        let transactions = match self.get_parameter_value_decoded(MULTI_SEND_TRANSACTIONS_PARAM)? {
+            ValueDecodedType::InternalTransaction(transactions) => transactions,
+        };
+        Some(
+            transactions
+                .into_iter()
+                .map(|transaction| MultiSendAction {
+                    to: address_info_index
+                        .and_then(|index| index.get(&transaction.to))
+                        .cloned()
+                        .unwrap_or_else(|| AddressEx::address_only(&transaction.to)),
+                    value: transaction.value,
+                    hex_data: transaction.data,
+                    data_decoded: transaction.data_decoded,
+                    operation: transaction.operation,
+                })
+                .collect(),
+        )
+    }
 }
 
-async fn put_parameter_into_index(
-    parameters: &Option<Vec<Parameter>>,
-    index: &mut HashMap<String, AddressEx>,
-    info_provider: &impl InfoProvider,
-) {
+fn collect_parameter_addresses(parameters: &Option<Vec<Parameter>>, addresses: &mut Vec<String>) {
     if let Some(parameters) = parameters {
         for parameter in parameters {
             match &parameter.value {
-                ParamValue::SingleValue(value) => {
-                    insert_value_into_index(value, index, info_provider).await
-                }
+                ParamValue::SingleValue(value) => addresses.push(value.to_owned()),
                 ParamValue::ArrayValue(values) => {
                     for value in values {
                         if let ParamValue::SingleValue(value) = value {
-                            insert_value_into_index(value, index, info_provider).await
+                            addresses.push(value.to_owned())
                         }
                     }
                 }
@@ -144,19 +171,3 @@ async fn put_parameter_into_index(
         }
     }
 }
-
-async fn insert_value_into_index(
-    value: &String,
-    index: &mut HashMap<String, AddressEx>,
-    info_provider: &impl InfoProvider,
-) {
-    if value.len() == 42
-        && value.starts_with("0x")
-        && value != "0x0000000000000000000000000000000000000000"
-        && !index.contains_key(value)
-    {
-        if let Some(address_ex) = info_provider.address_ex_from_any_source(&value).await.ok() {
-            index.insert(value.to_owned(), address_ex);
-        };
-    }
-}
@@ -6,8 +6,11 @@ pub mod transfers;
 mod tests;
 
 use crate::common::models::addresses::AddressEx;
+use crate::providers::address_info::{address_ex_index, address_ex_or_default, AddressSource};
+use crate::providers::address_risk;
 use crate::providers::info::InfoProvider;
 use crate::routes::transactions::models::TransferDirection;
+use rocket::futures::future::join_all;
 
 pub(crate) fn get_transfer_direction(safe: &str, from: &str, to: &str) -> TransferDirection {
     if safe == from {
@@ -27,12 +30,49 @@ pub(crate) async fn get_address_ex_from_any_source(
     address: &str,
     info_provider: &impl InfoProvider,
 ) -> AddressEx {
-    if safe != address {
-        info_provider
-            .address_ex_from_any_source(address)
-            .await
-            .unwrap_or(AddressEx::address_only(address))
-    } else {
-        AddressEx::address_only(address)
-    }
+    get_addresses_ex_from_any_source(safe, &[address], info_provider)
+        .await
+        .remove(0)
+}
+
+/// `sender` and `recipient` of a transfer, resolved in one batch
+pub(crate) async fn get_transfer_parties(
+    safe: &str,
+    sender: &str,
+    recipient: &str,
+    info_provider: &impl InfoProvider,
+) -> (AddressEx, AddressEx) {
+    let mut parties =
+        get_addresses_ex_from_any_source(safe, &[sender, recipient], info_provider).await;
+    let recipient = parties.remove(1);
+    (parties.remove(0), recipient)
+}
+
+/// [AddressEx] of every address in the order of `addresses`, resolved with a single batched
+/// lookup. The Safe itself is never looked up, see [get_address_ex_from_any_source].
+pub(crate) async fn get_addresses_ex_from_any_source(
+    safe: &str,
+    addresses: &[&str],
+    info_provider: &impl InfoProvider,
+) -> Vec<AddressEx> {
+    let lookups: Vec<&str> = addresses
+        .iter()
+        .copied()
+        .filter(|address| *address != safe)
+        .collect();
+    let index = address_ex_index(info_provider, &lookups, AddressSource::AnySource).await;
+    join_all(addresses.iter().map(|address| {
+        let index = &index;
+        async move {
+            if *address == safe {
+                return AddressEx::address_only(address);
+            }
+            let mut address_ex = address_ex_or_default(index, address);
+            if address_risk::is_enabled() {
+                address_ex.risk_flags = info_provider.address_risk_flags(address).await;
+            }
+            address_ex
+        }
+    }))
+    .await
 }
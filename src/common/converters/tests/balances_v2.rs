@@ -23,6 +23,7 @@ fn native_token_balance() {
         balance: "7457594371050000001".to_string(),
         fiat_balance: "2523.79908".to_string(),
         fiat_conversion: "338.420".to_string(),
+        spam: false,
     };
 
     let token_to_usd = BigDecimal::from_str("338.42").unwrap();
@@ -55,6 +56,7 @@ fn erc20_token_balance_usd_balance() {
         balance: "5002".to_string(),
         fiat_balance: "0.00142".to_string(),
         fiat_conversion: "28.54620".to_string(),
+        spam: false,
     };
 
     let token_to_usd = BigDecimal::from_str("28.5462").unwrap();
@@ -87,6 +89,7 @@ fn erc20_token_balance_fiat_is_twice_usd() {
         balance: "5002".to_string(),
         fiat_balance: "0.00285".to_string(),
         fiat_conversion: "57.09240".to_string(),
+        spam: false,
     };
 
     let token_to_usd = BigDecimal::from_str("28.5462").unwrap();
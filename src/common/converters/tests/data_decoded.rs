@@ -1,5 +1,5 @@
 use crate::common::models::addresses::AddressEx;
-use crate::common::models::data_decoded::{DataDecoded, ParamValue, Parameter};
+use crate::common::models::data_decoded::{DataDecoded, Operation, ParamValue, Parameter};
 use crate::providers::info::*;
 use crate::routes::transactions::models::{SettingsChange, SettingsInfo};
 use mockall::predicate::eq;
@@ -41,6 +41,7 @@ async fn data_decoded_set_fallback_handler_to_settings_info_with_address_info()
                 value: "0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             })
         });
 
@@ -55,6 +56,7 @@ async fn data_decoded_set_fallback_handler_to_settings_info_with_address_info()
                 value: "0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             },
         }),
     };
@@ -216,6 +218,7 @@ async fn data_decoded_change_implementation_to_settings_info_with_address_info()
                 value: "0xb6029EA3B2c51D09a50B53CA8012FeEB05bDa35A".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             })
         });
 
@@ -230,6 +233,7 @@ async fn data_decoded_change_implementation_to_settings_info_with_address_info()
                 value: "0xb6029EA3B2c51D09a50B53CA8012FeEB05bDa35A".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             },
         }),
     };
@@ -274,6 +278,7 @@ async fn data_decoded_enable_module_to_settings_info_with_address_info() {
                 value: "0xF5dC3718EEbC5b003F1672A499F2ACBE77Ba790d".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             })
         });
 
@@ -288,6 +293,7 @@ async fn data_decoded_enable_module_to_settings_info_with_address_info() {
                 value: "0xF5dC3718EEbC5b003F1672A499F2ACBE77Ba790d".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             },
         }),
     };
@@ -332,6 +338,7 @@ async fn data_decoded_disable_module_to_settings_info_with_address_info() {
                 value: "0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             })
         });
 
@@ -346,6 +353,7 @@ async fn data_decoded_disable_module_to_settings_info_with_address_info() {
                 value: "0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string(),
                 name: Some("Address name".to_string()),
                 logo_uri: Some("logo.url".to_string()),
+                risk_flags: vec![],
             },
         }),
     };
@@ -462,6 +470,7 @@ async fn address_info_index_not_multi_send_address_single_value() {
                 value: "0xb6029EA3B2c51D09a50B53CA8012FeEB05bDa35A".to_string(),
                 name: Some("Master Copy".to_string()),
                 logo_uri: Some("url.de".to_string()),
+                risk_flags: vec![],
             })
         });
 
@@ -477,6 +486,7 @@ async fn address_info_index_not_multi_send_address_single_value() {
                 value: "0xb6029EA3B2c51D09a50B53CA8012FeEB05bDa35A".to_string(),
                 name: Some("Master Copy".to_string()),
                 logo_uri: Some("url.de".to_string()),
+                risk_flags: vec![],
             },
         );
         map
@@ -511,6 +521,7 @@ async fn address_info_index_not_multi_send_address_array_value() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -524,6 +535,7 @@ async fn address_info_index_not_multi_send_address_array_value() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -551,6 +563,7 @@ async fn address_info_index_not_multi_send_address_array_value() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -567,6 +580,7 @@ async fn address_info_index_not_multi_send_address_array_value() {
                 value: "0x4FB84d2dFc50017aFa759107a389759c8fD077DE".to_owned(),
                 name: Some("0x4FB84d2dFc50017aFa759107a389759c8fD077DE_name".to_string()),
                 logo_uri: Some("0x4FB84d2dFc50017aFa759107a389759c8fD077DE_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -576,6 +590,7 @@ async fn address_info_index_not_multi_send_address_array_value() {
                 value: "0x111111111117dC0aa78b770fA6A738034120C302".to_owned(),
                 name: Some("0x111111111117dC0aa78b770fA6A738034120C302_name".to_string()),
                 logo_uri: Some("0x111111111117dC0aa78b770fA6A738034120C302_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -585,6 +600,7 @@ async fn address_info_index_not_multi_send_address_array_value() {
                 value: "0x991c44331f0E59510Bcff76edBA06C3f552Eef8B".to_owned(),
                 name: Some("0x991c44331f0E59510Bcff76edBA06C3f552Eef8B_name".to_string()),
                 logo_uri: Some("0x991c44331f0E59510Bcff76edBA06C3f552Eef8B_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -611,6 +627,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -624,6 +641,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -637,6 +655,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -650,6 +669,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -663,6 +683,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -680,6 +701,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: "0x111111125434b319222CdBf8C261674aDB56F3ae".to_owned(),
                 name: Some("0x111111125434b319222CdBf8C261674aDB56F3ae_name".to_string()),
                 logo_uri: Some("0x111111125434b319222CdBf8C261674aDB56F3ae_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -689,6 +711,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: "0xd47140F6Ab73f6d6B6675Fb1610Bb5E9B5d96FE5".to_owned(),
                 name: Some("0xd47140F6Ab73f6d6B6675Fb1610Bb5E9B5d96FE5_name".to_string()),
                 logo_uri: Some("0xd47140F6Ab73f6d6B6675Fb1610Bb5E9B5d96FE5_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -698,6 +721,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".to_owned(),
                 name: Some("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE_name".to_string()),
                 logo_uri: Some("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -707,6 +731,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".to_owned(),
                 name: Some("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2_name".to_string()),
                 logo_uri: Some("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -716,6 +741,7 @@ async fn address_info_index_multi_send_single_level_of_nesting() {
                 value: "0xBc79855178842FDBA0c353494895DEEf509E26bB".to_owned(),
                 name: Some("0xBc79855178842FDBA0c353494895DEEf509E26bB_name".to_string()),
                 logo_uri: Some("0xBc79855178842FDBA0c353494895DEEf509E26bB_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -742,6 +768,7 @@ async fn address_info_index_multi_send_two_levels_of_nesting() {
                 value: address.to_owned(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -755,6 +782,7 @@ async fn address_info_index_multi_send_two_levels_of_nesting() {
                 value: address.to_owned(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -768,6 +796,7 @@ async fn address_info_index_multi_send_two_levels_of_nesting() {
                 value: address.to_owned(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         })
         .in_sequence(&mut sequence);
@@ -787,6 +816,7 @@ async fn address_info_index_multi_send_two_levels_of_nesting() {
                 value: "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".to_owned(),
                 name: Some("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE_name".to_string()),
                 logo_uri: Some("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -796,6 +826,7 @@ async fn address_info_index_multi_send_two_levels_of_nesting() {
                 value: "0x991c44331f0E59510Bcff76edBA06C3f552Eef8B".to_owned(),
                 name: Some("0x991c44331f0E59510Bcff76edBA06C3f552Eef8B_name".to_string()),
                 logo_uri: Some("0x991c44331f0E59510Bcff76edBA06C3f552Eef8B_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -805,6 +836,7 @@ async fn address_info_index_multi_send_two_levels_of_nesting() {
                 value: "0x68881260bd04E9dAc7F77a314360ce05435B4818".to_owned(),
                 name: Some("0x68881260bd04E9dAc7F77a314360ce05435B4818_name".to_string()),
                 logo_uri: Some("0x68881260bd04E9dAc7F77a314360ce05435B4818_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -830,6 +862,7 @@ async fn address_info_index_skip_address_info_for_0x0() {
                 value: address.to_owned(),
                 name: Some(format!("{}_name", &address)),
                 logo_uri: Some(format!("{}_url", &address)),
+                risk_flags: vec![],
             })
         });
 
@@ -846,6 +879,7 @@ async fn address_info_index_skip_address_info_for_0x0() {
                 value: "0x441E604Ad49602c0B9C0B08D0781eCF96740786a".to_string(),
                 name: Some("0x441E604Ad49602c0B9C0B08D0781eCF96740786a_name".to_string()),
                 logo_uri: Some("0x441E604Ad49602c0B9C0B08D0781eCF96740786a_url".to_string()),
+                risk_flags: vec![],
             },
         );
 
@@ -881,3 +915,52 @@ async fn address_info_index_no_results_returns_none() {
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn multi_send_actions_in_execution_order_with_address_info() {
+    let data_decoded =
+        serde_json::from_str::<DataDecoded>(crate::tests::json::DATA_DECODED_MULTI_SEND).unwrap();
+    let token = AddressEx {
+        value: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        name: Some("Compound USDT".to_string()),
+        logo_uri: Some("url.de".to_string()),
+        risk_flags: vec![],
+    };
+    let mut address_info_index = HashMap::new();
+    address_info_index.insert(token.value.to_string(), token.clone());
+
+    let actual = data_decoded
+        .multi_send_actions(Some(&address_info_index))
+        .unwrap();
+
+    assert_eq!(3, actual.len());
+    for action in actual {
+        assert_eq!(token, action.to);
+        assert_eq!(Some("0".to_string()), action.value);
+        assert_eq!(Operation::CALL, action.operation);
+        assert!(action.hex_data.unwrap().starts_with("0xa9059cbb"));
+        assert_eq!("transfer", action.data_decoded.unwrap().method);
+    }
+}
+
+#[test]
+fn multi_send_actions_without_address_info() {
+    let data_decoded =
+        serde_json::from_str::<DataDecoded>(crate::tests::json::DATA_DECODED_MULTI_SEND).unwrap();
+
+    let actual = data_decoded.multi_send_actions(None).unwrap();
+
+    assert_eq!(
+        AddressEx::address_only("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+        actual[0].to
+    );
+}
+
+#[test]
+fn multi_send_actions_of_other_methods() {
+    let data_decoded =
+        serde_json::from_str::<DataDecoded>(crate::tests::json::DATA_DECODED_CHANGE_MASTER_COPY)
+            .unwrap();
+
+    assert_eq!(None, data_decoded.multi_send_actions(None));
+}
@@ -16,6 +16,7 @@ async fn get_address_info_address_diff_than_safe() {
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -23,6 +24,7 @@ async fn get_address_info_address_diff_than_safe() {
         value: address.to_string(),
         name: Some("".to_string()),
         logo_uri: None,
+        risk_flags: vec![],
     };
 
     let actual = get_address_ex_from_any_source(safe, address, &mut mock_info_provider).await;
@@ -65,6 +65,7 @@ async fn erc20_transfer_dto_to_incoming_transfer_transaction_with_address_info()
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -116,6 +117,7 @@ async fn erc20_transfer_dto_to_outgoing_transfer_transaction_with_address_info()
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -64,11 +64,12 @@ async fn erc721_transfer_dto_to_incoming_transfer_transaction_with_address_info(
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
     let expected = Transfer {
-        sender: AddressEx { value: "0x938bae50a210b80EA233112800Cd5Bc2e7644300".to_string(), name: Some("".to_string()), logo_uri: None },
+        sender: AddressEx { value: "0x938bae50a210b80EA233112800Cd5Bc2e7644300".to_string(), name: Some("".to_string()), logo_uri: None, risk_flags: vec![], },
         recipient: AddressEx::address_only("0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
         direction: TransferDirection::Incoming,
         transfer_info: TransferInfo::Erc721(
@@ -110,6 +111,7 @@ async fn erc721_transfer_dto_to_outgoing_transfer_transaction_with_address_info(
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -49,6 +49,7 @@ async fn ether_transfer_dto_ether_incoming_transfer_transaction_with_address_inf
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -61,6 +62,7 @@ async fn ether_transfer_dto_ether_incoming_transfer_transaction_with_address_inf
             value: "0xfFfa5813ED9a5DB4880D7303DB7d0cBe41bC771F".to_string(),
             name: Some("".to_string()),
             logo_uri: None,
+            risk_flags: vec![],
         },
         recipient: AddressEx::address_only("0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
         direction: TransferDirection::Incoming,
@@ -90,6 +92,7 @@ async fn ether_transfer_dto_ether_outgoing_transfer_transaction_with_address_inf
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -103,6 +106,7 @@ async fn ether_transfer_dto_ether_outgoing_transfer_transaction_with_address_inf
             value: "0xfFfa5813ED9a5DB4880D7303DB7d0cBe41bC771F".to_string(),
             name: Some("".to_string()),
             logo_uri: None,
+            risk_flags: vec![],
         },
         direction: TransferDirection::Outgoing,
         transfer_info: (TransferInfo::NativeCoin(NativeCoinTransfer {
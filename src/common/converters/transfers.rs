@@ -1,5 +1,5 @@
 use super::get_transfer_direction;
-use crate::common::converters::get_address_ex_from_any_source;
+use crate::common::converters::get_transfer_parties;
 use crate::common::models::backend::transfers::{
     Erc20Transfer as Erc20TransferDto, Erc721Transfer as Erc721TransferDto,
     EtherTransfer as EtherTransferDto, Transfer as TransferDto,
@@ -48,6 +48,7 @@ impl TransferDto {
             tx_hash: self.get_transaction_hash(),
             detailed_execution_info: None,
             safe_app_info: None,
+            logs: None,
         })
     }
 
@@ -60,7 +61,7 @@ impl TransferDto {
         }
     }
 
-    pub(super) fn get_transaction_hash(&self) -> Option<String> {
+    pub(crate) fn get_transaction_hash(&self) -> Option<String> {
         match self {
             TransferDto::Erc721(transfer) => Some(transfer.transaction_hash.to_owned()),
             TransferDto::Erc20(transfer) => Some(transfer.transaction_hash.to_owned()),
@@ -76,9 +77,11 @@ impl Erc20TransferDto {
         info_provider: &impl InfoProvider,
         safe: &str,
     ) -> ServiceTransfer {
+        let (sender, recipient) =
+            get_transfer_parties(safe, &self.from, &self.to, info_provider).await;
         ServiceTransfer {
-            sender: get_address_ex_from_any_source(safe, &self.from, info_provider).await,
-            recipient: get_address_ex_from_any_source(safe, &self.to, info_provider).await,
+            sender,
+            recipient,
             direction: get_transfer_direction(safe, &self.from, &self.to),
             transfer_info: self.to_transfer_info(info_provider).await,
         }
@@ -103,9 +106,11 @@ impl Erc721TransferDto {
         info_provider: &impl InfoProvider,
         safe: &str,
     ) -> ServiceTransfer {
+        let (sender, recipient) =
+            get_transfer_parties(safe, &self.from, &self.to, info_provider).await;
         ServiceTransfer {
-            sender: get_address_ex_from_any_source(safe, &self.from, info_provider).await,
-            recipient: get_address_ex_from_any_source(safe, &self.to, info_provider).await,
+            sender,
+            recipient,
             direction: get_transfer_direction(safe, &self.from, &self.to),
             transfer_info: self.to_transfer_info(info_provider).await,
         }
@@ -130,9 +135,11 @@ impl EtherTransferDto {
         info_provider: &impl InfoProvider,
         safe: &str,
     ) -> ServiceTransfer {
+        let (sender, recipient) =
+            get_transfer_parties(safe, &self.from, &self.to, info_provider).await;
         ServiceTransfer {
-            sender: get_address_ex_from_any_source(safe, &self.from, info_provider).await,
-            recipient: get_address_ex_from_any_source(safe, &self.to, info_provider).await,
+            sender,
+            recipient,
             direction: get_transfer_direction(safe, &self.from, &self.to),
             transfer_info: self.to_transfer_info(),
         }
@@ -1,13 +1,31 @@
-use serde::Serialize;
+use crate::utils::checksum::serialize_checksummed;
+use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Debug, PartialEq)]
+#[derive(Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressEx {
+    /// Serialized checksummed
+    #[serde(serialize_with = "serialize_checksummed")]
     pub value: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logo_uri: Option<String>,
+    /// See [address_risk](crate::providers::address_risk)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub risk_flags: Vec<RiskFlag>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RiskFlag {
+    KnownScam,
+    Sanctioned,
+    Phishing,
+    Malicious,
+    // Flags of newer denylists or reputation providers, not reported to clients
+    #[serde(other)]
+    Unknown,
 }
 
 impl AddressEx {
@@ -16,6 +34,7 @@ impl AddressEx {
             value: "0x0000000000000000000000000000000000000000".to_owned(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         }
     }
     pub fn address_only(address: &str) -> Self {
@@ -23,6 +42,7 @@ impl AddressEx {
             value: address.to_owned(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         }
     }
 }
@@ -6,6 +6,10 @@ pub struct ChainInfo {
     pub recommended_master_copy_version: String,
     pub transaction_service: String,
     pub vpc_transaction_service: String,
+    #[serde(default)]
+    pub transaction_service_fallback_uri: Option<String>,
+    #[serde(default)]
+    pub vpc_transaction_service_fallback_uri: Option<String>,
     pub chain_id: String,
     pub chain_name: String,
     pub short_name: String,
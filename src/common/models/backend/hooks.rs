@@ -1,14 +1,16 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
 #[serde(tag = "type")]
 pub struct Payload {
     pub address: String,
+    #[serde(rename = "chainId", default)]
+    pub chain_id: Option<String>,
     #[serde(flatten)]
     pub details: Option<PayloadDetails>,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PayloadDetails {
     NewConfirmation(NewConfirmation),
@@ -16,38 +18,41 @@ pub enum PayloadDetails {
     PendingMultisigTransaction(PendingMultisigTransaction),
     IncomingEther(IncomingEther),
     IncomingToken(IncomingToken),
+    OwnerAdded(OwnerAdded),
+    OwnerRemoved(OwnerRemoved),
+    ThresholdChanged(ThresholdChanged),
     #[serde(other)]
     Unknown,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct NewConfirmation {
     pub owner: String,
     pub safe_tx_hash: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecutedMultisigTransaction {
     pub safe_tx_hash: String,
     pub tx_hash: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PendingMultisigTransaction {
     pub safe_tx_hash: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingEther {
     pub tx_hash: String,
     pub value: String,
 }
 
-#[derive(Deserialize, Debug, Hash)]
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingToken {
     pub tx_hash: String,
@@ -55,3 +60,21 @@ pub struct IncomingToken {
     pub token_id: Option<String>,
     pub value: Option<String>,
 }
+
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerAdded {
+    pub owner: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerRemoved {
+    pub owner: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdChanged {
+    pub threshold: u64,
+}
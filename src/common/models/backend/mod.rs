@@ -4,6 +4,7 @@ pub mod balances_v2;
 pub mod chains;
 pub mod hooks;
 pub mod notifications;
+pub mod relay;
 pub mod safe_apps;
 pub mod safes;
 pub mod transactions;
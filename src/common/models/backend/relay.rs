@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsoredCall {
+    pub chain_id: String,
+    pub target: String,
+    pub data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sponsor_api_key: Option<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayTaskCreated {
+    pub task_id: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct RelayTaskResponse {
+    pub task: RelayTask,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayTask {
+    pub chain_id: u64,
+    pub task_id: String,
+    pub task_state: String,
+    pub transaction_hash: Option<String>,
+    pub last_check_message: Option<String>,
+}
@@ -1,5 +1,7 @@
+use crate::utils::checksum::checksummed;
 use crate::utils::json;
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 #[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Clone, Copy, Hash)]
@@ -16,7 +18,8 @@ pub struct DataDecoded {
     pub parameters: Option<Vec<Parameter>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq)]
+/// Values of `address` types are serialized checksummed
+#[derive(Deserialize, Debug, Clone, Hash, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Parameter {
     pub name: String,
@@ -52,6 +55,39 @@ pub struct InternalTransaction {
     pub data_decoded: Option<DataDecoded>,
 }
 
+impl Serialize for Parameter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let fields = if self.value_decoded.is_some() { 4 } else { 3 };
+        let mut state = serializer.serialize_struct("Parameter", fields)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("type", &self.param_type)?;
+        if self.param_type == "address" || self.param_type.starts_with("address[") {
+            state.serialize_field("value", &self.value.checksummed())?;
+        } else {
+            state.serialize_field("value", &self.value)?;
+        }
+        match &self.value_decoded {
+            Some(value_decoded) => state.serialize_field("valueDecoded", value_decoded)?,
+            None => state.skip_field("valueDecoded")?,
+        }
+        state.end()
+    }
+}
+
+impl ParamValue {
+    fn checksummed(&self) -> ParamValue {
+        match self {
+            ParamValue::SingleValue(value) => ParamValue::SingleValue(checksummed(value)),
+            ParamValue::ArrayValue(values) => {
+                ParamValue::ArrayValue(values.iter().map(ParamValue::checksummed).collect())
+            }
+        }
+    }
+}
+
 impl From<String> for ParamValue {
     fn from(item: String) -> Self {
         ParamValue::SingleValue(item)
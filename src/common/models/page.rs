@@ -1,11 +1,30 @@
-use serde::{Deserialize, Serialize};
+use reqwest::Url;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Shared shape of every list response: `count`, `next`, `previous`, `pageInfo` and `results`.
+/// `pageInfo` is derived from `next` when serializing.
+#[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Page<T> {
+    /// Total amount of upstream items, if known. For transaction lists these are transactions,
+    /// not list items (labels, date labels, conflict headers)
+    #[serde(default)]
+    pub count: Option<u64>,
     pub next: Option<String>,
     pub previous: Option<String>,
     pub results: Vec<T>,
+    // Set when enrichment was cut short by the latency budget of the route
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incomplete: Option<bool>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next: bool,
+    /// Value of the `cursor` query parameter of `next`
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -16,7 +35,7 @@ pub struct PageMetadata {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SafeList {
-    safes: Vec<String>,
+    pub safes: Vec<String>,
 }
 
 impl<T> Page<T> {
@@ -25,9 +44,46 @@ impl<T> Page<T> {
         U: From<T>,
     {
         Page {
+            count: self.count,
             next: self.next,
             previous: self.previous,
             results: self.results.into_iter().map(|it| U::from(it)).collect(),
+            incomplete: self.incomplete,
+        }
+    }
+
+    pub fn page_info(&self) -> PageInfo {
+        let cursor = self.next.as_ref().and_then(|next| {
+            Url::parse(next)
+                .ok()?
+                .query_pairs()
+                .find_map(|(key, value)| {
+                    if key == "cursor" {
+                        Some(value.to_string())
+                    } else {
+                        None
+                    }
+                })
+        });
+        PageInfo {
+            has_next: self.next.is_some(),
+            cursor,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Page<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut page = serializer.serialize_struct("Page", 6)?;
+        page.serialize_field("count", &self.count)?;
+        page.serialize_field("next", &self.next)?;
+        page.serialize_field("previous", &self.previous)?;
+        page.serialize_field("pageInfo", &self.page_info())?;
+        page.serialize_field("results", &self.results)?;
+        match self.incomplete {
+            Some(incomplete) => page.serialize_field("incomplete", &incomplete)?,
+            None => page.skip_field("incomplete")?,
         }
+        page.end()
     }
 }
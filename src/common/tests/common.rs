@@ -1,6 +1,7 @@
 use crate::common::models::data_decoded::{
     DataDecoded, InternalTransaction, Operation, ParamValue, Parameter, ValueDecodedType,
 };
+use crate::common::models::page::{Page, PageInfo};
 use crate::tests::json;
 
 #[test]
@@ -147,3 +148,88 @@ fn deserialize_decoded_value() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn serialise_page_omits_incomplete_when_absent() {
+    let page: Page<String> = Page {
+        count: None,
+        next: None,
+        previous: None,
+        results: vec!["0x1".to_string()],
+        incomplete: None,
+    };
+
+    let actual = serde_json::to_value(&page).unwrap();
+
+    assert_eq!(
+        serde_json::json!({
+            "count": null,
+            "next": null,
+            "previous": null,
+            "pageInfo": { "hasNext": false, "cursor": null },
+            "results": ["0x1"]
+        }),
+        actual
+    );
+}
+
+#[test]
+fn serialise_partial_page() {
+    let page: Page<String> = Page {
+        count: None,
+        next: None,
+        previous: None,
+        results: vec![],
+        incomplete: Some(true),
+    };
+
+    let actual = serde_json::to_value(&page).unwrap();
+
+    assert_eq!(
+        serde_json::json!({
+            "count": null,
+            "next": null,
+            "previous": null,
+            "pageInfo": { "hasNext": false, "cursor": null },
+            "results": [],
+            "incomplete": true
+        }),
+        actual
+    );
+}
+
+#[test]
+fn serialise_page_info_from_next() {
+    let page: Page<String> = Page {
+        count: Some(42),
+        next: Some(String::from("https://safe-client.gnosis.io/v1/chains/4/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/transactions/history?cursor=limit%3D20%26offset%3D20&timezone_offset=0")),
+        previous: None,
+        results: vec![],
+        incomplete: None,
+    };
+
+    let actual = serde_json::to_value(&page).unwrap();
+
+    assert_eq!(actual["count"], 42);
+    assert_eq!(
+        actual["pageInfo"],
+        serde_json::json!({ "hasNext": true, "cursor": "limit=20&offset=20" })
+    );
+}
+
+#[test]
+fn deserialise_page_count_from_backend() {
+    let page = serde_json::from_str::<Page<serde_json::Value>>(
+        r#"{"count": 2, "next": null, "previous": null, "results": [{}, {}]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(page.count, Some(2));
+    assert_eq!(
+        page.page_info(),
+        PageInfo {
+            has_next: false,
+            cursor: None
+        }
+    );
+}
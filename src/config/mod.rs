@@ -1,33 +1,365 @@
+use crate::cache::REDIS_BACKEND;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::env;
+use std::fs;
 use std::str::FromStr;
 
+pub mod settings;
+
 #[cfg(test)]
 mod tests;
 
+thread_local! {
+    // Set while the settings are validated, so that invalid values are reported instead of panicking
+    static VALIDATION_ERRORS: RefCell<Option<Vec<String>>> = RefCell::new(None);
+}
+
+/// Backend of the cache shared by the instances, `redis` or `memcached`
+pub fn cache_backend() -> String {
+    env_with_default("CACHE_BACKEND", String::from(REDIS_BACKEND))
+}
+
+/// Prefix of the request and response caches, changed by deployments that are incompatible with
+/// the cached values of the running version
+pub fn cache_namespace() -> String {
+    env_with_default("CACHE_NAMESPACE", String::new())
+}
+
 pub fn redis_uri() -> String {
-    env::var("REDIS_URI").expect("REDIS_URI missing in env")
+    required_env("REDIS_URI")
+}
+
+/// `memcache://<host>:<port>`, required with the `memcached` cache backend
+pub fn memcached_uri() -> String {
+    required_env("MEMCACHED_URI")
+}
+
+pub fn config_service_uri() -> String {
+    required_env("CONFIG_SERVICE_URI")
 }
 
 pub fn base_config_service_uri() -> String {
-    format!("{}{}", env::var("CONFIG_SERVICE_URI").unwrap(), "/api")
+    format!("{}{}", config_service_uri(), "/api")
+}
+
+pub fn exchange_api_base_uri() -> String {
+    required_env("EXCHANGE_API_BASE_URI")
+}
+
+pub fn exchange_api_key() -> String {
+    required_env("EXCHANGE_API_KEY")
 }
 
 pub fn base_exchange_api_uri() -> String {
     format!(
         "{}?access_key={}",
-        env::var("EXCHANGE_API_BASE_URI").unwrap(),
-        env::var("EXCHANGE_API_KEY").unwrap()
+        exchange_api_base_uri(),
+        exchange_api_key()
     )
 }
 
-pub fn webhook_token() -> String {
-    env::var("WEBHOOK_TOKEN").expect("WEBHOOK_TOKEN missing in env")
+pub fn rpc_api_key() -> Option<String> {
+    env::var("RPC_API_KEY").ok()
+}
+
+/// Extra headers attached to every request to an upstream host, configured as JSON,
+/// e.g. `{"safe-transaction.example.com": {"Authorization": "Basic dXNlcjpwYXNz"}}`
+pub fn upstream_headers() -> HashMap<String, HashMap<String, String>> {
+    env_json("UPSTREAM_HEADERS")
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamTls {
+    /// PEM file with additional root certificates to trust for this host
+    pub ca_bundle: Option<String>,
+    /// PKCS#12 (`.p12`/`.pfx`) file with the client certificate and key used for mTLS
+    pub client_identity: Option<String>,
+    #[serde(default)]
+    pub client_identity_password: String,
+}
+
+/// TLS settings per upstream host, configured as JSON,
+/// e.g. `{"safe-transaction.internal:8443": {"caBundle": "/etc/ssl/internal-ca.pem"}}`
+pub fn upstream_tls() -> HashMap<String, UpstreamTls> {
+    env_json("UPSTREAM_TLS")
+}
+
+/// Fallback transaction service uri per chain id, configured as JSON, used when the
+/// config service doesn't provide one, e.g. `{"4": "https://safe-transaction-secondary.rinkeby.gnosis.io"}`
+pub fn transaction_service_fallback_uris() -> HashMap<String, String> {
+    env_json("TRANSACTION_SERVICE_FALLBACK_URIS")
+}
+
+/// Upstreams read requests are mirrored to, configured as JSON with the base uri of the
+/// mirrored service as key, e.g. `{"https://safe-transaction.rinkeby.gnosis.io": "https://safe-transaction.rinkeby.staging.gnosisdev.com"}`
+pub fn shadow_upstreams() -> HashMap<String, String> {
+    env_json("SHADOW_UPSTREAMS")
+}
+
+/// Share of the read requests to an upstream of `SHADOW_UPSTREAMS` that is mirrored
+pub fn shadow_rate() -> f32 {
+    env_with_default("SHADOW_RATE", 0.1)
+}
+
+/// Rejects requests to hosts that are neither configured nor provided by the config service
+pub fn outbound_url_validation() -> bool {
+    env_with_default("OUTBOUND_URL_VALIDATION", true)
+}
+
+/// Comma separated hosts requests are allowed to on top of the ones derived from the configuration
+pub fn outbound_allowed_hosts() -> Vec<String> {
+    env::var("OUTBOUND_ALLOWED_HOSTS")
+        .map(|value| {
+            value
+                .split(',')
+                .map(|host| host.trim().to_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Comma separated route groups this deployment doesn't serve, out of `transactions`, `balances`,
+/// `collectibles`, `hooks` and `admin`
+pub fn disabled_route_groups() -> Vec<String> {
+    env::var("DISABLED_ROUTE_GROUPS")
+        .map(|value| {
+            value
+                .split(',')
+                .map(|group| group.trim().to_lowercase())
+                .filter(|group| !group.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Chain id served per host, configured as JSON, so that requests to chain scoped domains don't need
+/// the chain in the path, e.g. `{"polygon.gateway.example.com": "137"}`
+pub fn chain_hosts() -> HashMap<String, String> {
+    env_json("CHAIN_HOSTS")
+}
+
+/// Tokens (e.g. stablecoins) whose exchange rates are returned next to the native coin, per chain
+/// id, configured as JSON, e.g. `{"1": ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"]}`
+pub fn exchange_rate_tokens() -> HashMap<String, Vec<String>> {
+    env_json("EXCHANGE_RATE_TOKENS")
+}
+
+/// Legacy shared token of the admin, hook and diagnostics routes, accepted with every scope
+pub fn webhook_token() -> Option<String> {
+    env::var("WEBHOOK_TOKEN").ok()
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct AdminApiKey {
+    pub token: String,
+    /// `hooks`, `cache-admin`, `diagnostics` or `exports`
+    pub scopes: Vec<String>,
+}
+
+/// Named keys of the admin, hook and diagnostics routes, configured as JSON,
+/// e.g. `{"tx-service": {"token": "<token>", "scopes": ["hooks"]}}`. Keys of
+/// [admin_api_keys_file] are loaded first, keys of `ADMIN_API_KEYS` replace those with the same name.
+pub fn admin_api_keys() -> HashMap<String, AdminApiKey> {
+    let mut keys = match admin_api_keys_file() {
+        Some(file) => match fs::read_to_string(&file) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|error| {
+                config_error(
+                    format!("Parsing of ADMIN_API_KEYS_FILE failed: {}", error),
+                    HashMap::new(),
+                )
+            }),
+            Err(error) => config_error(
+                format!("ADMIN_API_KEYS_FILE could not be read: {}", error),
+                HashMap::new(),
+            ),
+        },
+        None => HashMap::new(),
+    };
+    keys.extend(env_json::<HashMap<String, AdminApiKey>>("ADMIN_API_KEYS"));
+    keys
+}
+
+/// JSON file with keys in the format of [admin_api_keys], e.g. a mounted secret
+pub fn admin_api_keys_file() -> Option<String> {
+    env::var("ADMIN_API_KEYS_FILE").ok()
+}
+
+/// JSON lines file the audit log of write operations is appended to, disabled if not set
+pub fn audit_log_file() -> Option<String> {
+    env::var("AUDIT_LOG_FILE").ok()
+}
+
+/// Size (in bytes) the audit log is rotated at, keeping the previous file as `<AUDIT_LOG_FILE>.1`.
+/// 0 disables the rotation.
+pub fn audit_log_max_size() -> usize {
+    env_with_default("AUDIT_LOG_MAX_SIZE", 100 * 1024 * 1024)
+}
+
+/// Base uri of the relayer (Gelato relay API) for sponsored executions, relaying is disabled if not set
+pub fn relay_service_uri() -> Option<String> {
+    env::var("RELAY_SERVICE_URI").ok()
+}
+
+pub fn relay_api_key() -> Option<String> {
+    env::var("RELAY_API_KEY").ok()
+}
+
+/// Endpoint receiving the buffered client analytics events as JSON batches, ingestion is disabled if not set
+pub fn analytics_sink_uri() -> Option<String> {
+    env::var("ANALYTICS_SINK_URI").ok()
+}
+
+/// Broker the hook events are published to, `kafka` or `nats`, publishing is disabled if not set
+pub fn hook_events_broker() -> Option<String> {
+    env::var("HOOK_EVENTS_BROKER")
+        .ok()
+        .map(|broker| broker.trim().to_lowercase())
+}
+
+/// Kafka REST proxy uri (`https://rest-proxy:8082`) or NATS server uri (`nats://nats:4222`),
+/// credentials can be part of it
+pub fn hook_events_uri() -> Option<String> {
+    env::var("HOOK_EVENTS_URI").ok()
+}
+
+/// Kafka topic of the hook events, or prefix of their NATS subjects
+pub fn hook_events_topic() -> String {
+    env_with_default("HOOK_EVENTS_TOPIC", "safe-events".into())
+}
+
+/// Attempts to publish a hook event (including the first one) before it is dropped
+pub fn hook_events_max_attempts() -> usize {
+    env_with_default("HOOK_EVENTS_MAX_ATTEMPTS", 3)
+}
+
+/// Delay (in ms) before publishing the events the broker didn't take again, doubling after every
+/// attempt
+pub fn hook_events_retry_interval() -> u64 {
+    env_with_default("HOOK_EVENTS_RETRY_INTERVAL", 1000)
+}
+
+/// Token list (tokens by chain id) used while the token endpoint of the transaction service is
+/// unavailable, before the list bundled with the gateway
+pub fn static_token_list_uri() -> Option<String> {
+    env::var("STATIC_TOKEN_LIST_URI").ok()
+}
+
+/// Comma separated denylist files (`{"<address>": [<risk flag>]}`) flagging the addresses
+/// transactions interact with
+pub fn address_risk_files() -> Vec<String> {
+    env::var("ADDRESS_RISK_FILES")
+        .map(|value| {
+            value
+                .split(',')
+                .map(|file| file.trim().to_string())
+                .filter(|file| !file.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reputation provider returning the risk flags of an address at `<uri>/<address>`
+pub fn address_reputation_uri() -> Option<String> {
+    env::var("ADDRESS_REPUTATION_URI").ok()
+}
+
+/// Chain configs (the chain format of the config service) served while the config service is
+/// unreachable and the chain is not cached, before the seed bundled with the gateway
+pub fn chain_seed_file() -> Option<String> {
+    env::var("CHAIN_SEED_FILE").ok()
+}
+
+/// Endpoint receiving a JSON alert when the error budget of a route burns faster than
+/// [slo_burn_rate_threshold], alerts are only logged if not set
+pub fn slo_alert_webhook_uri() -> Option<String> {
+    env::var("SLO_ALERT_WEBHOOK_URI").ok()
+}
+
+/// Rolling window (in ms) latency and error budgets are tracked over
+pub fn slo_window() -> u64 {
+    env_with_default("SLO_WINDOW", 60 * 60 * 1000)
+}
+
+/// Rolling window (in ms) the requests per Safe are reported over
+pub fn usage_window() -> u64 {
+    env_with_default("USAGE_WINDOW", 60 * 60 * 1000)
+}
+
+/// Requests per Safe are counted in buckets of this length (in ms), the window moves bucket wise
+pub fn usage_bucket() -> u64 {
+    env_with_default("USAGE_BUCKET", 60 * 1000)
+}
+
+/// Requests slower than this (in ms) count against the error budget, like server errors
+pub fn slo_latency_target() -> u64 {
+    env_with_default("SLO_LATENCY_TARGET", 1000)
+}
+
+/// Share of requests per route expected to succeed within [slo_latency_target]
+pub fn slo_availability_target() -> f32 {
+    env_with_default("SLO_AVAILABILITY_TARGET", 0.99)
+}
+
+pub fn slo_burn_rate_threshold() -> f32 {
+    env_with_default("SLO_BURN_RATE_THRESHOLD", 2.0)
+}
+
+/// Routes with fewer requests within the window don't alert
+pub fn slo_min_requests() -> usize {
+    env_with_default("SLO_MIN_REQUESTS", 20)
+}
+
+/// Largest chain asset (in bytes) proxied through the gateway
+pub fn asset_max_size() -> usize {
+    env_with_default("ASSET_MAX_SIZE", 512 * 1024)
+}
+
+/// Token logos fetched at the same time into the asset cache after the token list of a chain was
+/// loaded, 0 disables the prefetching
+pub fn logo_prefetch_concurrency() -> usize {
+    env_with_default("LOGO_PREFETCH_CONCURRENCY", 0)
+}
+
+/// Most token logos prefetched per token list load
+pub fn logo_prefetch_limit() -> usize {
+    env_with_default("LOGO_PREFETCH_LIMIT", 500)
+}
+
+/// Most transaction ids accepted per request by the bulk transaction details endpoint
+pub fn transaction_details_batch_size() -> usize {
+    env_with_default("TRANSACTION_DETAILS_BATCH_SIZE", 20)
+}
+
+/// Amount of analytics events held in memory, events are rejected with a 429 while it is full
+pub fn analytics_buffer_size() -> usize {
+    env_with_default("ANALYTICS_BUFFER_SIZE", 10000)
+}
+
+/// Amount of analytics events forwarded per request to the sink, also the most accepted per request
+pub fn analytics_batch_size() -> usize {
+    env_with_default("ANALYTICS_BATCH_SIZE", 100)
+}
+
+/// Longest time (in ms) analytics events are buffered before being forwarded
+pub fn analytics_flush_interval() -> u64 {
+    env_with_default("ANALYTICS_FLUSH_INTERVAL", 5000)
 }
 
 pub fn scheme() -> String {
     env_with_default("SCHEME", "https".into())
 }
 
+/// Rocket profile the instance runs with, `release` for release builds unless `ROCKET_PROFILE`
+/// says otherwise
+pub fn rocket_profile() -> String {
+    rocket::Config::figment().profile().to_string()
+}
+
 // TIME DURATION VALUES
 fn indefinite_timeout() -> usize {
     env_with_default("INDEFINITE_TIMEOUT", 60 * 60 * 1000)
@@ -50,6 +382,11 @@ pub fn address_info_cache_duration() -> usize {
     env_with_default("ADDRESS_INFO_CACHE_DURATION", indefinite_timeout())
 }
 
+// Reputations change, unlike the contract info of an address
+pub fn address_reputation_cache_duration() -> usize {
+    env_with_default("ADDRESS_REPUTATION_CACHE_DURATION", 24 * 60 * 60 * 1000)
+}
+
 pub fn token_info_cache_duration() -> usize {
     env_with_default("TOKEN_INFO_CACHE_DURATION", 60 * 60 * 24 * 1000)
 }
@@ -58,6 +395,23 @@ pub fn chain_info_cache_duration() -> usize {
     env_with_default("CHAIN_INFO_CACHE_DURATION", indefinite_timeout())
 }
 
+/// After a chain id was looked up again because of a cached not found response, it isn't looked up
+/// again for this long (in ms)
+pub fn unknown_chain_cache_duration() -> usize {
+    env_with_default("UNKNOWN_CHAIN_CACHE_DURATION", 60 * 1000)
+}
+
+/// Last known good copies of read-only upstream responses, served when the upstream fails and
+/// the regular cache entry is gone. 0 disables them
+pub fn last_known_good_cache_duration() -> usize {
+    env_with_default("LAST_KNOWN_GOOD_CACHE_DURATION", 60 * 60 * 24 * 1000)
+}
+
+/// Chain assets (e.g. currency logos) proxied through the gateway
+pub fn asset_cache_duration() -> usize {
+    env_with_default("ASSET_CACHE_DURATION", 60 * 60 * 24 * 1000)
+}
+
 pub fn chain_info_response_cache_duration() -> usize {
     env_with_default("CHAIN_INFO_RESPONSE_CACHE_DURATION", 1) // set to negligible value
 }
@@ -94,6 +448,19 @@ pub fn token_price_cache_duration() -> usize {
     env_with_default("TOKEN_PRICE_CACHE_DURATION", 1) // set to negligible value
 }
 
+pub fn execution_estimation_cache_duration() -> usize {
+    env_with_default("EXECUTION_ESTIMATION_CACHE_DURATION", 15 * 1000)
+}
+
+// Roughly a block, gas prices are estimated from the latest blocks
+pub fn gas_price_cache_duration() -> usize {
+    env_with_default("GAS_PRICE_CACHE_DURATION", 10 * 1000)
+}
+
+pub fn exchange_rates_cache_duration() -> usize {
+    env_with_default("EXCHANGE_RATES_CACHE_DURATION", 60 * 1000)
+}
+
 // REQUEST TIMEOUTS
 pub fn internal_client_connect_timeout() -> u64 {
     env_with_default("INTERNAL_CLIENT_CONNECT_TIMEOUT", 1000)
@@ -131,6 +498,31 @@ pub fn collectibles_request_timeout() -> u64 {
     env_with_default("COLLECTIBLES_REQUEST_TIMEOUT", 20000)
 }
 
+pub fn rpc_request_timeout() -> u64 {
+    env_with_default("RPC_REQUEST_TIMEOUT", 10000)
+}
+
+pub fn relay_request_timeout() -> u64 {
+    env_with_default("RELAY_REQUEST_TIMEOUT", 10000)
+}
+
+// Time (in ms) spent on enrichment lookups before a partial page is returned, 0 disables it
+pub fn tx_history_latency_budget() -> u64 {
+    env_with_default("TX_HISTORY_LATENCY_BUDGET", 0)
+}
+
+pub fn tx_queued_latency_budget() -> u64 {
+    env_with_default("TX_QUEUED_LATENCY_BUDGET", 0)
+}
+
+pub fn tx_queued_poll_max_wait() -> u64 {
+    env_with_default("TX_QUEUED_POLL_MAX_WAIT", 30000)
+}
+
+pub fn tx_queued_poll_interval() -> u64 {
+    env_with_default("TX_QUEUED_POLL_INTERVAL", 1000)
+}
+
 pub fn default_request_timeout() -> u64 {
     env_with_default("DEFAULT_REQUEST_TIMEOUT", 10000)
 }
@@ -149,6 +541,36 @@ pub fn redis_scan_count() -> usize {
     env_with_default("REDIS_SCAN_COUNT", 300)
 }
 
+/// Most connections to Redis held by the pool of each instance
+pub fn redis_pool_size() -> usize {
+    env_with_default("REDIS_POOL_SIZE", 15)
+}
+
+/// Longest time (in ms) a cache operation waits for a connection of the pool, and for opening one
+pub fn redis_connection_timeout() -> u64 {
+    env_with_default("REDIS_CONNECTION_TIMEOUT", 5000)
+}
+
+// Size in bytes from which cached values are compressed, 0 disables compression
+pub fn redis_compression_threshold() -> usize {
+    env_with_default("REDIS_COMPRESSION_THRESHOLD", 0)
+}
+
+/// Most connections to Memcached held by the pool of each instance
+pub fn memcached_pool_size() -> usize {
+    env_with_default("MEMCACHED_POOL_SIZE", 15)
+}
+
+/// Longest time (in ms) a Memcached call waits for a reply
+pub fn memcached_timeout() -> u64 {
+    env_with_default("MEMCACHED_TIMEOUT", 5000)
+}
+
+// Size in bytes from which values cached in Memcached are compressed, 0 disables compression
+pub fn memcached_compression_threshold() -> usize {
+    env_with_default("MEMCACHED_COMPRESSION_THRESHOLD", 0)
+}
+
 pub fn feature_flag_nested_decoding() -> bool {
     env_with_default("FEATURE_FLAG_NESTED_DECODING", true)
 }
@@ -157,18 +579,327 @@ pub fn feature_flag_balances_rate_implementation() -> bool {
     env_with_default("FEATURE_FLAG_BALANCES_RATE_IMPLEMENTATION", false)
 }
 
+pub fn feature_flag_hook_prefetch() -> bool {
+    env_with_default("FEATURE_FLAG_HOOK_PREFETCH", false)
+}
+
+/// Checks transaction service responses against the models they are parsed into, logging the
+/// differences as schema drift
+pub fn feature_flag_schema_validation() -> bool {
+    env_with_default("FEATURE_FLAG_SCHEMA_VALIDATION", false)
+}
+
+/// Counts the requests per Safe in Redis, reported via `/admin/usage`
+pub fn feature_flag_usage_tracking() -> bool {
+    env_with_default("FEATURE_FLAG_USAGE_TRACKING", false)
+}
+
+/// Reports transactions that were not executed before the Safe nonce moved past them as
+/// `OBSOLETE` instead of `CANCELLED`
+pub fn feature_flag_queued_tx_obsolete() -> bool {
+    env_with_default("FEATURE_FLAG_QUEUED_TX_OBSOLETE", false)
+}
+
+/// Posts a `READY_TO_EXECUTE` event to the callback registered for the Safe once a queued
+/// transaction has all required confirmations
+pub fn feature_flag_ready_callbacks() -> bool {
+    env_with_default("FEATURE_FLAG_READY_CALLBACKS", false)
+}
+
+/// Adds the execution cost, from the receipt of the ethereum transaction, to the details of
+/// executed multisig transactions
+pub fn feature_flag_execution_cost() -> bool {
+    env_with_default("FEATURE_FLAG_EXECUTION_COST", false)
+}
+
+/// Adds the event logs of the ethereum transaction, with well-known events decoded, to the
+/// details of executed transactions
+pub fn feature_flag_decoded_logs() -> bool {
+    env_with_default("FEATURE_FLAG_DECODED_LOGS", false)
+}
+
+/// Recovers the signer of submitted confirmations and rejects signatures of non-owners and owners
+/// that confirmed already, before they are forwarded to the transaction service
+pub fn feature_flag_confirmation_signature_check() -> bool {
+    env_with_default("FEATURE_FLAG_CONFIRMATION_SIGNATURE_CHECK", false)
+}
+
+/// Answers the write routes with a 503 from the start, until switched off via
+/// `/admin/read-only/<token>`
+pub fn read_only_mode() -> bool {
+    env_with_default("READ_ONLY_MODE", false)
+}
+
+/// Adds the hash of the response cache key as `X-Cache-Key` next to the `X-Cache` header
+pub fn cache_debug_headers() -> bool {
+    env_with_default("CACHE_DEBUG_HEADERS", false)
+}
+
+/// Adds a human-readable `description` to transaction summaries
+pub fn feature_flag_transaction_descriptions() -> bool {
+    env_with_default("FEATURE_FLAG_TRANSACTION_DESCRIPTIONS", false)
+}
+
+/// Hooks for the same Safe arriving within this window (in ms) are applied together once it ends,
+/// 0 applies every hook right away
+pub fn hook_debounce_window() -> u64 {
+    env_with_default("HOOK_DEBOUNCE_WINDOW", 0)
+}
+
+pub fn hook_prefetch_fiat() -> String {
+    env_with_default("HOOK_PREFETCH_FIAT", "USD".into())
+}
+
 pub fn vpc_transaction_service_uri() -> bool {
     env_with_default("VPC_TRANSACTION_SERVICE_URI", true)
 }
 
+// Time a transaction service is skipped in favour of its fallback after failing
+pub fn transaction_service_unhealthy_duration() -> u64 {
+    env_with_default("TRANSACTION_SERVICE_UNHEALTHY_DURATION", 30000)
+}
+
+// Sponsored executions per Safe within RELAY_QUOTA_WINDOW (in ms)
+pub fn relay_quota_limit() -> usize {
+    env_with_default("RELAY_QUOTA_LIMIT", 5)
+}
+
+pub fn relay_quota_window() -> usize {
+    env_with_default("RELAY_QUOTA_WINDOW", 24 * 60 * 60 * 1000)
+}
+
+/// Window (in ms) within which the metadata of a collectible is refreshed at most once
+pub fn nft_refresh_window() -> usize {
+    env_with_default("NFT_REFRESH_WINDOW", 5 * 60 * 1000)
+}
+
+/// Gateway `ipfs://` uris of refreshed collectible metadata are resolved with
+pub fn nft_ipfs_gateway() -> String {
+    env_with_default(
+        "NFT_IPFS_GATEWAY",
+        "https://cloudflare-ipfs.com/ipfs/".into(),
+    )
+}
+
+// Days of daily balance snapshots kept per Safe
+pub fn balance_history_max_days() -> usize {
+    env_with_default("BALANCE_HISTORY_MAX_DAYS", 90)
+}
+
 pub fn concurrent_balance_token_requests() -> usize {
     env_with_default("CONCURRENT_BALANCE_TOKEN_REQUESTS", 5)
 }
 
+pub fn recent_recipients_scan_size() -> usize {
+    env_with_default("RECENT_RECIPIENTS_SCAN_SIZE", 100)
+}
+
+/// Amount of queued transactions the queue summary counters are computed from
+pub fn tx_queued_summary_size() -> usize {
+    env_with_default("TX_QUEUED_SUMMARY_SIZE", 100)
+}
+
+/// Upstream calls a single incoming request may make before it is aborted with a 503, 0 disables
+/// the budget
+pub fn upstream_call_budget() -> usize {
+    env_with_default("UPSTREAM_CALL_BUDGET", 0)
+}
+
+/// Largest JSON body (in bytes) accepted from clients, larger bodies are answered with a 413
+pub fn max_request_body_size() -> usize {
+    env_with_default("MAX_REQUEST_BODY_SIZE", 1024 * 1024)
+}
+
+/// Largest upstream response (in bytes) read by the gateway, larger responses fail with a 502
+pub fn upstream_max_response_size() -> usize {
+    env_with_default("UPSTREAM_MAX_RESPONSE_SIZE", 10 * 1024 * 1024)
+}
+
+/// Deepest nesting of arrays and objects accepted in upstream responses, checked before parsing
+pub fn upstream_max_json_depth() -> usize {
+    env_with_default("UPSTREAM_MAX_JSON_DEPTH", 64)
+}
+
+/// Largest json response (in bytes) served to clients before its heavy optional fields are
+/// trimmed, for the routes without a budget in `RESPONSE_SIZE_BUDGETS`. 0 disables the budget.
+pub fn response_size_budget() -> usize {
+    env_with_default("RESPONSE_SIZE_BUDGET", 0)
+}
+
+/// Response size budgets (in bytes) per route, taking precedence over `RESPONSE_SIZE_BUDGET`,
+/// configured as JSON with the route paths (without their query) as keys, e.g.
+/// `{"/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>": 262144}`
+pub fn response_size_budgets() -> HashMap<String, usize> {
+    env_json("RESPONSE_SIZE_BUDGETS")
+}
+
+/// Longest `hexData` (in characters) kept in responses trimmed to their size budget
+pub fn response_hex_data_limit() -> usize {
+    env_with_default("RESPONSE_HEX_DATA_LIMIT", 1024)
+}
+
+/// Upstream calls in flight at the same time on this instance, further calls wait for a slot.
+/// 0 doesn't limit them.
+pub fn upstream_max_concurrency() -> usize {
+    env_with_default("UPSTREAM_MAX_CONCURRENCY", 0)
+}
+
+/// Longest time (in ms) an upstream call waits for a slot before it is shed with a 503
+pub fn upstream_queue_timeout() -> u64 {
+    env_with_default("UPSTREAM_QUEUE_TIMEOUT", 1000)
+}
+
+/// Derives the default timeout of upstream calls from the observed p99 latency of the upstream
+/// host, within `ADAPTIVE_TIMEOUT_MIN` and `ADAPTIVE_TIMEOUT_MAX`. Explicit timeouts are kept.
+pub fn feature_flag_adaptive_timeouts() -> bool {
+    env_with_default("FEATURE_FLAG_ADAPTIVE_TIMEOUTS", false)
+}
+
+/// Shortest adaptive timeout (in ms) of the calls of an upstream host
+pub fn adaptive_timeout_min() -> u64 {
+    env_with_default("ADAPTIVE_TIMEOUT_MIN", 2 * 1000)
+}
+
+/// Longest adaptive timeout (in ms) of the calls of an upstream host
+pub fn adaptive_timeout_max() -> u64 {
+    env_with_default("ADAPTIVE_TIMEOUT_MAX", 30 * 1000)
+}
+
+/// Adaptive timeouts are the p99 latency of the upstream host times this factor
+pub fn adaptive_timeout_factor() -> f32 {
+    env_with_default("ADAPTIVE_TIMEOUT_FACTOR", 3.0)
+}
+
+/// Calls of an upstream host within the `SLO_WINDOW` before its timeout adapts, hosts with fewer
+/// calls keep the `DEFAULT_REQUEST_TIMEOUT`
+pub fn adaptive_timeout_min_samples() -> usize {
+    env_with_default("ADAPTIVE_TIMEOUT_MIN_SAMPLES", 100)
+}
+
+/// Comma separated endpoints whose writes failing upstream with a server error are queued and
+/// retried instead of failing, out of `notification_registration` and `notification_unregistration`
+pub fn retry_queue_endpoints() -> Vec<String> {
+    env::var("RETRY_QUEUE_ENDPOINTS")
+        .map(|value| {
+            value
+                .split(',')
+                .map(|endpoint| endpoint.trim().to_lowercase())
+                .filter(|endpoint| !endpoint.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Attempts of a queued write (including the failed one) before it is dropped
+pub fn retry_queue_max_attempts() -> usize {
+    env_with_default("RETRY_QUEUE_MAX_ATTEMPTS", 8)
+}
+
+/// Interval (in ms) the retry queue is checked at, and delay before the first retry. The delay
+/// doubles after every failed retry.
+pub fn retry_queue_interval() -> u64 {
+    env_with_default("RETRY_QUEUE_INTERVAL", 30 * 1000)
+}
+
+/// Chains whose info is loaded at the same time while warming up the cache at liftoff, 0 disables
+/// the warm up
+pub fn chain_warm_up_concurrency() -> usize {
+    env_with_default("CHAIN_WARM_UP_CONCURRENCY", 5)
+}
+
+/// Interval (in ms) the versions of the transaction services are checked at after liftoff, 0
+/// disables the check
+pub fn upstream_version_check_interval() -> u64 {
+    env_with_default("UPSTREAM_VERSION_CHECK_INTERVAL", 60 * 60 * 1000)
+}
+
+/// Port the gRPC server listens on, next to the REST one (only with the `grpc` feature)
+#[cfg(feature = "grpc")]
+pub fn grpc_port() -> u16 {
+    env_with_default("GRPC_PORT", 50051)
+}
+
+/// Entries kept in the cache invalidation log (approximately), 0 disables the log
+pub fn invalidation_log_size() -> usize {
+    env_with_default("INVALIDATION_LOG_SIZE", 10000)
+}
+
+/// Days after their submission queued transactions are reported as `EXPIRED`, 0 disables expiry
+pub fn tx_queued_expiry_days() -> usize {
+    env_with_default("TX_QUEUED_EXPIRY_DAYS", 0)
+}
+
+/// Amount of executed multisig transactions scanned for token approvals by the allowances endpoint
+pub fn allowances_scan_size() -> usize {
+    env_with_default("ALLOWANCES_SCAN_SIZE", 100)
+}
+
+pub fn recent_recipients_limit() -> usize {
+    env_with_default("RECENT_RECIPIENTS_LIMIT", 10)
+}
+
+/// Amount of balances, the largest by fiat balance, returned by the Safe dashboard endpoint
+pub fn dashboard_balances_size() -> usize {
+    env_with_default("DASHBOARD_BALANCES_SIZE", 5)
+}
+
+/// Amount of history transactions returned by the Safe dashboard endpoint
+pub fn dashboard_history_size() -> usize {
+    env_with_default("DASHBOARD_HISTORY_SIZE", 3)
+}
+
+/// Comma separated token addresses that are always classified as spam
+pub fn spam_token_denylist() -> Vec<String> {
+    env::var("SPAM_TOKEN_DENYLIST")
+        .map(|value| {
+            value
+                .split(',')
+                .map(|address| address.trim().to_lowercase())
+                .filter(|address| !address.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Comma separated words that mark a token (or collectible) as spam when its name or symbol
+/// contains one of them as a whole word, ignoring case. Scam airdrops usually advertise a website
+/// or a "claim".
+pub fn spam_name_patterns() -> Vec<String> {
+    env_with_default(
+        "SPAM_NAME_PATTERNS",
+        String::from("http://,https://,www.,.com,.io,.net,.org,.xyz,claim,visit,airdrop,voucher"),
+    )
+    .split(',')
+    .map(|pattern| pattern.trim().to_lowercase())
+    .filter(|pattern| !pattern.is_empty())
+    .collect()
+}
+
 pub fn log_threshold() -> f32 {
     env_with_default("LOG_THRESHOLD", 1.0)
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSampling {
+    /// Share of the successful responses logged, within [0.0, 1.0]
+    pub success: f32,
+    /// Share of the error responses (status 400 and above) logged, all of them by default
+    #[serde(default = "full_log_sampling")]
+    pub error: f32,
+}
+
+fn full_log_sampling() -> f32 {
+    1.0
+}
+
+/// Share of the responses logged per route, taking precedence over `LOG_THRESHOLD`, configured as
+/// JSON with the route paths (without their query) as keys, e.g.
+/// `{"/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>": {"success": 0.01, "error": 1.0}}`
+pub fn log_sampling() -> HashMap<String, LogSampling> {
+    env_json("LOG_SAMPLING")
+}
+
 pub fn build_number() -> Option<String> {
     option_env!("BUILD_NUMBER").map(|it| it.to_string())
 }
@@ -179,14 +910,58 @@ pub fn version() -> String {
         .to_string()
 }
 
+/// Runs `load` collecting every configuration error, instead of panicking on the first one
+pub fn collect_config_errors<T>(load: impl FnOnce() -> T) -> (T, Vec<String>) {
+    VALIDATION_ERRORS.with(|errors| *errors.borrow_mut() = Some(vec![]));
+    let value = load();
+    let errors = VALIDATION_ERRORS
+        .with(|errors| errors.borrow_mut().take())
+        .unwrap_or_default();
+    (value, errors)
+}
+
+fn config_error<T>(message: String, fallback: T) -> T {
+    let collected = VALIDATION_ERRORS.with(|errors| match errors.borrow_mut().as_mut() {
+        Some(errors) => {
+            errors.push(message.clone());
+            true
+        }
+        None => false,
+    });
+    if collected {
+        fallback
+    } else {
+        panic!("{}", message)
+    }
+}
+
+fn required_env(key: &str) -> String {
+    env::var(key).unwrap_or_else(|_| config_error(format!("{} missing in env", key), String::new()))
+}
+
+fn env_json<T: DeserializeOwned + Default>(key: &str) -> T {
+    match env::var(key) {
+        Ok(value) => serde_json::from_str(&value).unwrap_or_else(|error| {
+            config_error(
+                format!("Parsing of {} env var failed: {}", key, error),
+                T::default(),
+            )
+        }),
+        Err(_) => T::default(),
+    }
+}
+
 fn env_with_default<T: FromStr>(key: &str, default: T) -> T
 where
     <T as FromStr>::Err: std::fmt::Debug,
 {
     match env::var(key) {
-        Ok(value) => value
-            .parse()
-            .expect(&format!("Parsing of {} env var key failed", &key)),
+        Ok(value) => value.parse().unwrap_or_else(|error| {
+            config_error(
+                format!("Parsing of {} env var key failed: {:?}", key, error),
+                default,
+            )
+        }),
         Err(_) => default,
     }
 }
@@ -0,0 +1,801 @@
+use crate::cache::{MEMCACHED_BACKEND, REDIS_BACKEND};
+use crate::config::*;
+use crate::routes::RouteGroup;
+use crate::utils::auth::Scope;
+use crate::utils::hook_events;
+use crate::utils::retry_queue;
+use reqwest::Url;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Typed snapshot of the configuration, validated once at startup so that a misconfigured
+/// deployment fails fast with every problem listed, instead of on the first request using it.
+/// Serializing it redacts the secrets.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    pub services: ServiceSettings,
+    pub secrets: SecretSettings,
+    pub cache_durations: CacheDurations,
+    pub timeouts: Timeouts,
+    pub features: FeatureSettings,
+    pub limits: LimitSettings,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceSettings {
+    pub config_service_uri: String,
+    pub exchange_api_base_uri: String,
+    pub cache_backend: String,
+    pub cache_namespace: String,
+    /// Empty with other cache backends
+    #[serde(serialize_with = "redact_uri_password")]
+    pub redis_uri: String,
+    pub memcached_uri: Option<String>,
+    pub relay_service_uri: Option<String>,
+    pub analytics_sink_uri: Option<String>,
+    #[serde(serialize_with = "redact_optional_uri_password")]
+    pub hook_events_uri: Option<String>,
+    pub slo_alert_webhook_uri: Option<String>,
+    pub static_token_list_uri: Option<String>,
+    pub nft_ipfs_gateway: String,
+    pub chain_seed_file: Option<String>,
+    pub address_risk_files: Vec<String>,
+    pub address_reputation_uri: Option<String>,
+    pub transaction_service_fallback_uris: HashMap<String, String>,
+    pub shadow_upstreams: HashMap<String, String>,
+    pub chain_hosts: HashMap<String, String>,
+    pub upstream_header_hosts: Vec<String>,
+    pub upstream_tls: HashMap<String, UpstreamTlsSettings>,
+    pub audit_log_file: Option<String>,
+    pub outbound_allowed_hosts: Vec<String>,
+    pub scheme: String,
+    pub profile: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamTlsSettings {
+    pub ca_bundle: Option<String>,
+    pub client_identity: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretSettings {
+    #[serde(serialize_with = "redact")]
+    pub webhook_token: Option<String>,
+    /// Scopes per key name, tokens are left out
+    pub admin_api_keys: HashMap<String, Vec<String>>,
+    #[serde(serialize_with = "redact")]
+    pub exchange_api_key: Option<String>,
+    #[serde(serialize_with = "redact")]
+    pub rpc_api_key: Option<String>,
+    #[serde(serialize_with = "redact")]
+    pub relay_api_key: Option<String>,
+}
+
+/// In milliseconds
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheDurations {
+    pub short_error: usize,
+    pub long_error: usize,
+    pub request_error: usize,
+    pub safe_info: usize,
+    pub address_info: usize,
+    pub address_reputation: usize,
+    pub token_info: usize,
+    pub chain_info: usize,
+    pub chain_info_response: usize,
+    pub unknown_chain: usize,
+    pub last_known_good: usize,
+    pub asset: usize,
+    pub exchange_api: usize,
+    pub request: usize,
+    pub about: usize,
+    pub balances: usize,
+    pub safe_app_manifest: usize,
+    pub owners_for_safes: usize,
+    pub safe_apps: usize,
+    pub token_price: usize,
+    pub execution_estimation: usize,
+    pub gas_price: usize,
+    pub exchange_rates: usize,
+}
+
+/// In milliseconds
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Timeouts {
+    pub internal_client_connect: u64,
+    pub default_request: u64,
+    pub safe_app_info_request: u64,
+    pub transaction_request: u64,
+    pub safe_info_request: u64,
+    pub token_info_request: u64,
+    pub chain_info_request: u64,
+    pub contract_info_request: u64,
+    pub balances_request: u64,
+    pub collectibles_request: u64,
+    pub rpc_request: u64,
+    pub relay_request: u64,
+    pub tx_history_latency_budget: u64,
+    pub tx_queued_latency_budget: u64,
+    pub tx_queued_poll_max_wait: u64,
+    pub tx_queued_poll_interval: u64,
+    pub transaction_service_unhealthy_duration: u64,
+    pub hook_debounce_window: u64,
+    pub upstream_queue: u64,
+    pub analytics_flush_interval: u64,
+    pub slo_window: u64,
+    pub slo_latency_target: u64,
+    pub usage_window: u64,
+    pub usage_bucket: u64,
+    pub redis_connection: u64,
+    pub memcached: u64,
+    pub retry_queue_interval: u64,
+    pub hook_events_retry_interval: u64,
+    pub upstream_version_check_interval: u64,
+    pub adaptive_timeout_min: u64,
+    pub adaptive_timeout_max: u64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureSettings {
+    pub nested_decoding: bool,
+    pub balances_rate_implementation: bool,
+    pub hook_prefetch: bool,
+    pub schema_validation: bool,
+    pub usage_tracking: bool,
+    pub queued_tx_obsolete: bool,
+    pub execution_cost: bool,
+    pub decoded_logs: bool,
+    pub transaction_descriptions: bool,
+    pub confirmation_signature_check: bool,
+    pub adaptive_timeouts: bool,
+    pub read_only_mode: bool,
+    pub cache_debug_headers: bool,
+    pub ready_callbacks: bool,
+    pub disabled_route_groups: Vec<String>,
+    pub retry_queue_endpoints: Vec<String>,
+    pub hook_events_broker: Option<String>,
+    pub hook_events_topic: String,
+    pub exchange_rate_tokens: HashMap<String, Vec<String>>,
+    pub hook_prefetch_fiat: String,
+    pub vpc_transaction_service_uri: bool,
+    pub log_all_error_responses: bool,
+    pub outbound_url_validation: bool,
+    pub log_threshold: f32,
+    pub log_sampling: HashMap<String, LogSampling>,
+    pub shadow_rate: f32,
+    pub slo_availability_target: f32,
+    pub slo_burn_rate_threshold: f32,
+    pub adaptive_timeout_factor: f32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitSettings {
+    pub redis_scan_count: usize,
+    pub redis_compression_threshold: usize,
+    pub redis_pool_size: usize,
+    pub memcached_pool_size: usize,
+    pub memcached_compression_threshold: usize,
+    pub concurrent_balance_token_requests: usize,
+    pub recent_recipients_scan_size: usize,
+    pub recent_recipients_limit: usize,
+    pub dashboard_balances_size: usize,
+    pub dashboard_history_size: usize,
+    pub tx_queued_summary_size: usize,
+    pub tx_queued_expiry_days: usize,
+    pub upstream_call_budget: usize,
+    pub upstream_max_concurrency: usize,
+    pub max_request_body_size: usize,
+    pub upstream_max_response_size: usize,
+    pub upstream_max_json_depth: usize,
+    pub response_size_budget: usize,
+    pub response_size_budgets: HashMap<String, usize>,
+    pub response_hex_data_limit: usize,
+    pub chain_warm_up_concurrency: usize,
+    pub invalidation_log_size: usize,
+    pub allowances_scan_size: usize,
+    pub asset_max_size: usize,
+    pub logo_prefetch_concurrency: usize,
+    pub logo_prefetch_limit: usize,
+    pub transaction_details_batch_size: usize,
+    pub analytics_buffer_size: usize,
+    pub analytics_batch_size: usize,
+    pub slo_min_requests: usize,
+    pub relay_quota_limit: usize,
+    pub relay_quota_window: usize,
+    pub nft_refresh_window: usize,
+    pub balance_history_max_days: usize,
+    pub retry_queue_max_attempts: usize,
+    pub hook_events_max_attempts: usize,
+    pub adaptive_timeout_min_samples: usize,
+    pub spam_token_denylist: Vec<String>,
+    pub spam_name_patterns: Vec<String>,
+}
+
+/// Every problem found in the configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsReport {
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for SettingsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Invalid configuration, {} problem(s) found:",
+            self.errors.len()
+        )?;
+        for error in self.errors.iter() {
+            writeln!(f, "  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, SettingsReport> {
+        let (settings, mut errors) = collect_config_errors(Settings::from_env);
+        errors.extend(settings.validate());
+        if errors.is_empty() {
+            Ok(settings)
+        } else {
+            Err(SettingsReport { errors })
+        }
+    }
+
+    fn from_env() -> Self {
+        Settings {
+            services: ServiceSettings {
+                config_service_uri: config_service_uri(),
+                exchange_api_base_uri: exchange_api_base_uri(),
+                cache_backend: cache_backend(),
+                cache_namespace: cache_namespace(),
+                redis_uri: if cache_backend() == REDIS_BACKEND {
+                    redis_uri()
+                } else {
+                    String::new()
+                },
+                memcached_uri: if cache_backend() == MEMCACHED_BACKEND {
+                    Some(memcached_uri())
+                } else {
+                    None
+                },
+                relay_service_uri: relay_service_uri(),
+                analytics_sink_uri: analytics_sink_uri(),
+                hook_events_uri: hook_events_uri(),
+                slo_alert_webhook_uri: slo_alert_webhook_uri(),
+                static_token_list_uri: static_token_list_uri(),
+                nft_ipfs_gateway: nft_ipfs_gateway(),
+                chain_seed_file: chain_seed_file(),
+                address_risk_files: address_risk_files(),
+                address_reputation_uri: address_reputation_uri(),
+                transaction_service_fallback_uris: transaction_service_fallback_uris(),
+                shadow_upstreams: shadow_upstreams(),
+                chain_hosts: chain_hosts(),
+                upstream_header_hosts: upstream_headers()
+                    .into_iter()
+                    .map(|(host, _)| host)
+                    .collect(),
+                upstream_tls: upstream_tls()
+                    .into_iter()
+                    .map(|(host, tls)| {
+                        let settings = UpstreamTlsSettings {
+                            ca_bundle: tls.ca_bundle,
+                            client_identity: tls.client_identity,
+                        };
+                        (host, settings)
+                    })
+                    .collect(),
+                audit_log_file: audit_log_file(),
+                outbound_allowed_hosts: outbound_allowed_hosts(),
+                scheme: scheme(),
+                profile: rocket_profile(),
+            },
+            secrets: SecretSettings {
+                webhook_token: webhook_token(),
+                admin_api_keys: admin_api_keys()
+                    .into_iter()
+                    .map(|(name, key)| (name, key.scopes))
+                    .collect(),
+                exchange_api_key: Some(exchange_api_key()),
+                rpc_api_key: rpc_api_key(),
+                relay_api_key: relay_api_key(),
+            },
+            cache_durations: CacheDurations {
+                short_error: short_error_duration(),
+                long_error: long_error_duration(),
+                request_error: request_error_cache_duration(),
+                safe_info: safe_info_cache_duration(),
+                address_info: address_info_cache_duration(),
+                address_reputation: address_reputation_cache_duration(),
+                token_info: token_info_cache_duration(),
+                chain_info: chain_info_cache_duration(),
+                chain_info_response: chain_info_response_cache_duration(),
+                unknown_chain: unknown_chain_cache_duration(),
+                last_known_good: last_known_good_cache_duration(),
+                asset: asset_cache_duration(),
+                exchange_api: exchange_api_cache_duration(),
+                request: request_cache_duration(),
+                about: about_cache_duration(),
+                balances: balances_cache_duration(),
+                safe_app_manifest: safe_app_manifest_cache_duration(),
+                owners_for_safes: owners_for_safes_cache_duration(),
+                safe_apps: safe_apps_cache_duration(),
+                token_price: token_price_cache_duration(),
+                execution_estimation: execution_estimation_cache_duration(),
+                gas_price: gas_price_cache_duration(),
+                exchange_rates: exchange_rates_cache_duration(),
+            },
+            timeouts: Timeouts {
+                internal_client_connect: internal_client_connect_timeout(),
+                default_request: default_request_timeout(),
+                safe_app_info_request: safe_app_info_request_timeout(),
+                transaction_request: transaction_request_timeout(),
+                safe_info_request: safe_info_request_timeout(),
+                token_info_request: token_info_request_timeout(),
+                chain_info_request: chain_info_request_timeout(),
+                contract_info_request: contract_info_request_timeout(),
+                balances_request: balances_request_timeout(),
+                collectibles_request: collectibles_request_timeout(),
+                rpc_request: rpc_request_timeout(),
+                relay_request: relay_request_timeout(),
+                tx_history_latency_budget: tx_history_latency_budget(),
+                tx_queued_latency_budget: tx_queued_latency_budget(),
+                tx_queued_poll_max_wait: tx_queued_poll_max_wait(),
+                tx_queued_poll_interval: tx_queued_poll_interval(),
+                transaction_service_unhealthy_duration: transaction_service_unhealthy_duration(),
+                hook_debounce_window: hook_debounce_window(),
+                upstream_queue: upstream_queue_timeout(),
+                analytics_flush_interval: analytics_flush_interval(),
+                slo_window: slo_window(),
+                slo_latency_target: slo_latency_target(),
+                usage_window: usage_window(),
+                usage_bucket: usage_bucket(),
+                redis_connection: redis_connection_timeout(),
+                memcached: memcached_timeout(),
+                retry_queue_interval: retry_queue_interval(),
+                hook_events_retry_interval: hook_events_retry_interval(),
+                upstream_version_check_interval: upstream_version_check_interval(),
+                adaptive_timeout_min: adaptive_timeout_min(),
+                adaptive_timeout_max: adaptive_timeout_max(),
+            },
+            features: FeatureSettings {
+                nested_decoding: feature_flag_nested_decoding(),
+                balances_rate_implementation: feature_flag_balances_rate_implementation(),
+                hook_prefetch: feature_flag_hook_prefetch(),
+                schema_validation: feature_flag_schema_validation(),
+                usage_tracking: feature_flag_usage_tracking(),
+                queued_tx_obsolete: feature_flag_queued_tx_obsolete(),
+                execution_cost: feature_flag_execution_cost(),
+                decoded_logs: feature_flag_decoded_logs(),
+                transaction_descriptions: feature_flag_transaction_descriptions(),
+                confirmation_signature_check: feature_flag_confirmation_signature_check(),
+                adaptive_timeouts: feature_flag_adaptive_timeouts(),
+                read_only_mode: read_only_mode(),
+                cache_debug_headers: cache_debug_headers(),
+                ready_callbacks: feature_flag_ready_callbacks(),
+                disabled_route_groups: disabled_route_groups(),
+                retry_queue_endpoints: retry_queue_endpoints(),
+                hook_events_broker: hook_events_broker(),
+                hook_events_topic: hook_events_topic(),
+                exchange_rate_tokens: exchange_rate_tokens(),
+                hook_prefetch_fiat: hook_prefetch_fiat(),
+                vpc_transaction_service_uri: vpc_transaction_service_uri(),
+                log_all_error_responses: log_all_error_responses(),
+                outbound_url_validation: outbound_url_validation(),
+                log_threshold: log_threshold(),
+                log_sampling: log_sampling(),
+                shadow_rate: shadow_rate(),
+                slo_availability_target: slo_availability_target(),
+                slo_burn_rate_threshold: slo_burn_rate_threshold(),
+                adaptive_timeout_factor: adaptive_timeout_factor(),
+            },
+            limits: LimitSettings {
+                redis_scan_count: redis_scan_count(),
+                redis_compression_threshold: redis_compression_threshold(),
+                redis_pool_size: redis_pool_size(),
+                memcached_pool_size: memcached_pool_size(),
+                memcached_compression_threshold: memcached_compression_threshold(),
+                concurrent_balance_token_requests: concurrent_balance_token_requests(),
+                recent_recipients_scan_size: recent_recipients_scan_size(),
+                recent_recipients_limit: recent_recipients_limit(),
+                dashboard_balances_size: dashboard_balances_size(),
+                dashboard_history_size: dashboard_history_size(),
+                tx_queued_summary_size: tx_queued_summary_size(),
+                tx_queued_expiry_days: tx_queued_expiry_days(),
+                upstream_call_budget: upstream_call_budget(),
+                upstream_max_concurrency: upstream_max_concurrency(),
+                max_request_body_size: max_request_body_size(),
+                upstream_max_response_size: upstream_max_response_size(),
+                upstream_max_json_depth: upstream_max_json_depth(),
+                response_size_budget: response_size_budget(),
+                response_size_budgets: response_size_budgets(),
+                response_hex_data_limit: response_hex_data_limit(),
+                chain_warm_up_concurrency: chain_warm_up_concurrency(),
+                invalidation_log_size: invalidation_log_size(),
+                allowances_scan_size: allowances_scan_size(),
+                asset_max_size: asset_max_size(),
+                logo_prefetch_concurrency: logo_prefetch_concurrency(),
+                logo_prefetch_limit: logo_prefetch_limit(),
+                transaction_details_batch_size: transaction_details_batch_size(),
+                analytics_buffer_size: analytics_buffer_size(),
+                analytics_batch_size: analytics_batch_size(),
+                slo_min_requests: slo_min_requests(),
+                relay_quota_limit: relay_quota_limit(),
+                relay_quota_window: relay_quota_window(),
+                nft_refresh_window: nft_refresh_window(),
+                balance_history_max_days: balance_history_max_days(),
+                retry_queue_max_attempts: retry_queue_max_attempts(),
+                hook_events_max_attempts: hook_events_max_attempts(),
+                adaptive_timeout_min_samples: adaptive_timeout_min_samples(),
+                spam_token_denylist: spam_token_denylist(),
+                spam_name_patterns: spam_name_patterns(),
+            },
+        }
+    }
+
+    /// Checks across values, on top of the parsing done while loading
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        let services = &self.services;
+        let mut uris = vec![
+            ("CONFIG_SERVICE_URI", Some(&services.config_service_uri)),
+            (
+                "EXCHANGE_API_BASE_URI",
+                Some(&services.exchange_api_base_uri),
+            ),
+            ("REDIS_URI", Some(&services.redis_uri)),
+            ("MEMCACHED_URI", services.memcached_uri.as_ref()),
+            ("RELAY_SERVICE_URI", services.relay_service_uri.as_ref()),
+            ("ANALYTICS_SINK_URI", services.analytics_sink_uri.as_ref()),
+            ("HOOK_EVENTS_URI", services.hook_events_uri.as_ref()),
+            (
+                "SLO_ALERT_WEBHOOK_URI",
+                services.slo_alert_webhook_uri.as_ref(),
+            ),
+            (
+                "STATIC_TOKEN_LIST_URI",
+                services.static_token_list_uri.as_ref(),
+            ),
+            ("NFT_IPFS_GATEWAY", Some(&services.nft_ipfs_gateway)),
+            (
+                "ADDRESS_REPUTATION_URI",
+                services.address_reputation_uri.as_ref(),
+            ),
+        ];
+        uris.extend(
+            services
+                .transaction_service_fallback_uris
+                .values()
+                .map(|uri| ("TRANSACTION_SERVICE_FALLBACK_URIS", Some(uri))),
+        );
+        let mut shadowed_uris: Vec<&String> = services.shadow_upstreams.keys().collect();
+        shadowed_uris.sort();
+        for uri in shadowed_uris {
+            uris.push(("SHADOW_UPSTREAMS", Some(uri)));
+            uris.push(("SHADOW_UPSTREAMS", Some(&services.shadow_upstreams[uri])));
+        }
+        for (key, uri) in uris {
+            // Missing values are already reported while loading
+            if let Some(uri) = uri.filter(|uri| !uri.is_empty()) {
+                if Url::parse(uri).is_err() {
+                    errors.push(format!("{} is not a valid URL: {}", key, uri));
+                }
+            }
+        }
+        for (host, tls) in services.upstream_tls.iter() {
+            for file in tls.ca_bundle.iter().chain(tls.client_identity.iter()) {
+                if !Path::new(file).is_file() {
+                    errors.push(format!(
+                        "UPSTREAM_TLS file for {} not found: {}",
+                        host, file
+                    ));
+                }
+            }
+        }
+        if let Some(file) = services.chain_seed_file.as_ref() {
+            if !Path::new(file).is_file() {
+                errors.push(format!("CHAIN_SEED_FILE not found: {}", file));
+            }
+        }
+        for file in services.address_risk_files.iter() {
+            if !Path::new(file).is_file() {
+                errors.push(format!("ADDRESS_RISK_FILES file not found: {}", file));
+            }
+        }
+        // Invalid URLs are reported above
+        if let Some(uri) = services.memcached_uri.as_ref() {
+            if Url::parse(uri).map_or(false, |url| {
+                url.scheme() != "memcache" || url.host_str().is_none()
+            }) {
+                errors.push(format!(
+                    "MEMCACHED_URI must be a memcache://<host>:<port> URL: {}",
+                    uri
+                ));
+            }
+        }
+        if ![REDIS_BACKEND, MEMCACHED_BACKEND].contains(&services.cache_backend.as_str()) {
+            errors.push(format!(
+                "CACHE_BACKEND must be redis or memcached: {}",
+                services.cache_backend
+            ));
+        }
+        if !services
+            .cache_namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric())
+        {
+            errors.push(format!(
+                "CACHE_NAMESPACE must be alphanumeric: {}",
+                services.cache_namespace
+            ));
+        }
+        for group in self.features.disabled_route_groups.iter() {
+            if RouteGroup::from_name(group).is_none() {
+                errors.push(format!(
+                    "DISABLED_ROUTE_GROUPS has an unknown group: {}",
+                    group
+                ));
+            }
+        }
+        for endpoint in self.features.retry_queue_endpoints.iter() {
+            if !retry_queue::ENDPOINTS.contains(&endpoint.as_str()) {
+                errors.push(format!(
+                    "RETRY_QUEUE_ENDPOINTS has an unknown endpoint: {}",
+                    endpoint
+                ));
+            }
+        }
+        if let Some(broker) = self.features.hook_events_broker.as_ref() {
+            if !hook_events::BROKERS.contains(&broker.as_str()) {
+                errors.push(format!(
+                    "HOOK_EVENTS_BROKER must be kafka or nats: {}",
+                    broker
+                ));
+            }
+            if !hook_events::is_valid_topic(&self.features.hook_events_topic) {
+                errors.push(format!(
+                    "HOOK_EVENTS_TOPIC must be a Kafka topic name (letters, digits, '.', '_' and '-'): {}",
+                    self.features.hook_events_topic
+                ));
+            } else if broker == hook_events::NATS
+                && !hook_events::is_valid_subject_prefix(&self.features.hook_events_topic)
+            {
+                errors.push(format!(
+                    "HOOK_EVENTS_TOPIC must be a NATS subject (tokens separated by '.'): {}",
+                    self.features.hook_events_topic
+                ));
+            }
+            if services.hook_events_uri.is_none() {
+                errors.push(String::from(
+                    "HOOK_EVENTS_URI must be set with HOOK_EVENTS_BROKER",
+                ));
+            }
+        }
+        let secrets = &self.secrets;
+        if secrets.webhook_token.is_none() && secrets.admin_api_keys.is_empty() {
+            errors.push(String::from("WEBHOOK_TOKEN or ADMIN_API_KEYS must be set"));
+        }
+        let mut key_names: Vec<&String> = secrets.admin_api_keys.keys().collect();
+        key_names.sort();
+        for name in key_names {
+            for scope in secrets.admin_api_keys[name].iter() {
+                if Scope::from_name(scope).is_none() {
+                    errors.push(format!(
+                        "ADMIN_API_KEYS key {} has an unknown scope: {}",
+                        name, scope
+                    ));
+                }
+            }
+        }
+        if !["http", "https"].contains(&services.scheme.as_str()) {
+            errors.push(format!("SCHEME must be http or https: {}", services.scheme));
+        }
+        // The file sink is meant for development, in production the log has to be kept on a
+        // volume that outlives the instance
+        if services.profile == "release" && services.audit_log_file.is_none() {
+            errors.push(String::from(
+                "AUDIT_LOG_FILE must be set with the release profile",
+            ));
+        }
+
+        let timeouts = &self.timeouts;
+        let request_timeouts = [
+            (
+                "INTERNAL_CLIENT_CONNECT_TIMEOUT",
+                timeouts.internal_client_connect,
+            ),
+            ("DEFAULT_REQUEST_TIMEOUT", timeouts.default_request),
+            (
+                "SAFE_APP_INFO_REQUEST_TIMEOUT",
+                timeouts.safe_app_info_request,
+            ),
+            ("TRANSACTION_REQUEST_TIMEOUT", timeouts.transaction_request),
+            ("SAFE_INFO_REQUEST_TIMEOUT", timeouts.safe_info_request),
+            ("TOKEN_INFO_REQUEST_TIMEOUT", timeouts.token_info_request),
+            ("CHAIN_INFO_REQUEST_TIMEOUT", timeouts.chain_info_request),
+            (
+                "CONTRACT_INFO_REQUEST_TIMEOUT",
+                timeouts.contract_info_request,
+            ),
+            ("BALANCES_REQUEST_TIMEOUT", timeouts.balances_request),
+            (
+                "COLLECTIBLES_REQUEST_TIMEOUT",
+                timeouts.collectibles_request,
+            ),
+            ("RPC_REQUEST_TIMEOUT", timeouts.rpc_request),
+            ("RELAY_REQUEST_TIMEOUT", timeouts.relay_request),
+            (
+                "ANALYTICS_FLUSH_INTERVAL",
+                timeouts.analytics_flush_interval,
+            ),
+            ("SLO_WINDOW", timeouts.slo_window),
+            ("SLO_LATENCY_TARGET", timeouts.slo_latency_target),
+            ("USAGE_WINDOW", timeouts.usage_window),
+            ("USAGE_BUCKET", timeouts.usage_bucket),
+            ("REDIS_CONNECTION_TIMEOUT", timeouts.redis_connection),
+            ("MEMCACHED_TIMEOUT", timeouts.memcached),
+            ("RETRY_QUEUE_INTERVAL", timeouts.retry_queue_interval),
+            (
+                "HOOK_EVENTS_RETRY_INTERVAL",
+                timeouts.hook_events_retry_interval,
+            ),
+            ("ADAPTIVE_TIMEOUT_MIN", timeouts.adaptive_timeout_min),
+        ];
+        for (key, timeout) in request_timeouts.iter() {
+            if *timeout == 0 {
+                errors.push(format!("{} must be greater than 0", key));
+            }
+        }
+        if timeouts.tx_queued_poll_interval == 0
+            || timeouts.tx_queued_poll_interval > timeouts.tx_queued_poll_max_wait
+        {
+            errors.push(String::from(
+                "TX_QUEUED_POLL_INTERVAL must be greater than 0 and at most TX_QUEUED_POLL_MAX_WAIT",
+            ));
+        }
+        if timeouts.usage_bucket > timeouts.usage_window {
+            errors.push(String::from("USAGE_BUCKET must be at most USAGE_WINDOW"));
+        }
+        if timeouts.adaptive_timeout_min > timeouts.adaptive_timeout_max {
+            errors.push(String::from(
+                "ADAPTIVE_TIMEOUT_MIN must be at most ADAPTIVE_TIMEOUT_MAX",
+            ));
+        }
+
+        let features = &self.features;
+        if !(0.0..=1.0).contains(&features.log_threshold) {
+            errors.push(String::from("LOG_THRESHOLD must be within [0.0, 1.0]"));
+        }
+        let mut sampled_routes: Vec<&String> = features.log_sampling.keys().collect();
+        sampled_routes.sort();
+        for route in sampled_routes {
+            let sampling = &features.log_sampling[route];
+            if !(0.0..=1.0).contains(&sampling.success) || !(0.0..=1.0).contains(&sampling.error) {
+                errors.push(format!(
+                    "LOG_SAMPLING rates of {} must be within [0.0, 1.0]",
+                    route
+                ));
+            }
+        }
+        if !(0.0..=1.0).contains(&features.shadow_rate) {
+            errors.push(String::from("SHADOW_RATE must be within [0.0, 1.0]"));
+        }
+        // The error budget is what is left of the availability target, it can't be empty
+        if !(features.slo_availability_target > 0.0 && features.slo_availability_target < 1.0) {
+            errors.push(String::from(
+                "SLO_AVAILABILITY_TARGET must be within ]0.0, 1.0[",
+            ));
+        }
+        if features.adaptive_timeout_factor < 1.0 {
+            errors.push(String::from("ADAPTIVE_TIMEOUT_FACTOR must be at least 1.0"));
+        }
+        if features.slo_burn_rate_threshold <= 0.0 {
+            errors.push(String::from(
+                "SLO_BURN_RATE_THRESHOLD must be greater than 0",
+            ));
+        }
+        if features.hook_prefetch && features.hook_prefetch_fiat.is_empty() {
+            errors.push(String::from(
+                "HOOK_PREFETCH_FIAT must be set when FEATURE_FLAG_HOOK_PREFETCH is enabled",
+            ));
+        }
+
+        let limits = &self.limits;
+        let positive_limits = [
+            ("REDIS_SCAN_COUNT", limits.redis_scan_count),
+            ("REDIS_POOL_SIZE", limits.redis_pool_size),
+            ("MEMCACHED_POOL_SIZE", limits.memcached_pool_size),
+            (
+                "CONCURRENT_BALANCE_TOKEN_REQUESTS",
+                limits.concurrent_balance_token_requests,
+            ),
+            (
+                "RECENT_RECIPIENTS_SCAN_SIZE",
+                limits.recent_recipients_scan_size,
+            ),
+            ("DASHBOARD_BALANCES_SIZE", limits.dashboard_balances_size),
+            ("DASHBOARD_HISTORY_SIZE", limits.dashboard_history_size),
+            ("TX_QUEUED_SUMMARY_SIZE", limits.tx_queued_summary_size),
+            ("ALLOWANCES_SCAN_SIZE", limits.allowances_scan_size),
+            ("ASSET_MAX_SIZE", limits.asset_max_size),
+            ("LOGO_PREFETCH_LIMIT", limits.logo_prefetch_limit),
+            ("MAX_REQUEST_BODY_SIZE", limits.max_request_body_size),
+            (
+                "UPSTREAM_MAX_RESPONSE_SIZE",
+                limits.upstream_max_response_size,
+            ),
+            ("UPSTREAM_MAX_JSON_DEPTH", limits.upstream_max_json_depth),
+            (
+                "TRANSACTION_DETAILS_BATCH_SIZE",
+                limits.transaction_details_batch_size,
+            ),
+            ("ANALYTICS_BUFFER_SIZE", limits.analytics_buffer_size),
+            ("ANALYTICS_BATCH_SIZE", limits.analytics_batch_size),
+            ("RELAY_QUOTA_WINDOW", limits.relay_quota_window),
+            ("NFT_REFRESH_WINDOW", limits.nft_refresh_window),
+            ("BALANCE_HISTORY_MAX_DAYS", limits.balance_history_max_days),
+            ("RETRY_QUEUE_MAX_ATTEMPTS", limits.retry_queue_max_attempts),
+            ("HOOK_EVENTS_MAX_ATTEMPTS", limits.hook_events_max_attempts),
+            (
+                "ADAPTIVE_TIMEOUT_MIN_SAMPLES",
+                limits.adaptive_timeout_min_samples,
+            ),
+        ];
+        for (key, limit) in positive_limits.iter() {
+            if *limit == 0 {
+                errors.push(format!("{} must be greater than 0", key));
+            }
+        }
+        if limits.recent_recipients_limit > limits.recent_recipients_scan_size {
+            errors.push(String::from(
+                "RECENT_RECIPIENTS_LIMIT must be at most RECENT_RECIPIENTS_SCAN_SIZE",
+            ));
+        }
+        if limits.analytics_batch_size > limits.analytics_buffer_size {
+            errors.push(String::from(
+                "ANALYTICS_BATCH_SIZE must be at most ANALYTICS_BUFFER_SIZE",
+            ));
+        }
+        errors
+    }
+}
+
+fn redact<S: Serializer>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) if !value.is_empty() => serializer.serialize_str("********"),
+        _ => serializer.serialize_none(),
+    }
+}
+
+fn redact_optional_uri_password<S: Serializer>(
+    uri: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match uri {
+        Some(uri) => redact_uri_password(uri, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn redact_uri_password<S: Serializer>(uri: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    match Url::parse(uri) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some("********"));
+            serializer.serialize_str(url.as_str())
+        }
+        Ok(_) => serializer.serialize_str(uri),
+        Err(_) if uri.is_empty() => serializer.serialize_str(uri),
+        Err(_) => serializer.serialize_str("********"),
+    }
+}
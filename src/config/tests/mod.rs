@@ -13,6 +13,176 @@ fn test_env() {
 
 fn build_usize_test_cases() -> Vec<USizeEnvValue> {
     vec![
+        USizeEnvValue {
+            expected_default: 24 * 60 * 60 * 1000,
+            env_key: String::from("ADDRESS_REPUTATION_CACHE_DURATION"),
+            generator: Box::new(super::address_reputation_cache_duration),
+        },
+        USizeEnvValue {
+            expected_default: 90,
+            env_key: String::from("BALANCE_HISTORY_MAX_DAYS"),
+            generator: Box::new(super::balance_history_max_days),
+        },
+        USizeEnvValue {
+            expected_default: 8,
+            env_key: String::from("RETRY_QUEUE_MAX_ATTEMPTS"),
+            generator: Box::new(super::retry_queue_max_attempts),
+        },
+        USizeEnvValue {
+            expected_default: 3,
+            env_key: String::from("HOOK_EVENTS_MAX_ATTEMPTS"),
+            generator: Box::new(super::hook_events_max_attempts),
+        },
+        USizeEnvValue {
+            expected_default: 100 * 1024 * 1024,
+            env_key: String::from("AUDIT_LOG_MAX_SIZE"),
+            generator: Box::new(super::audit_log_max_size),
+        },
+        USizeEnvValue {
+            expected_default: 5,
+            env_key: String::from("RELAY_QUOTA_LIMIT"),
+            generator: Box::new(super::relay_quota_limit),
+        },
+        USizeEnvValue {
+            expected_default: 24 * 60 * 60 * 1000,
+            env_key: String::from("RELAY_QUOTA_WINDOW"),
+            generator: Box::new(super::relay_quota_window),
+        },
+        USizeEnvValue {
+            expected_default: 5 * 60 * 1000,
+            env_key: String::from("NFT_REFRESH_WINDOW"),
+            generator: Box::new(super::nft_refresh_window),
+        },
+        USizeEnvValue {
+            expected_default: 0,
+            env_key: String::from("REDIS_COMPRESSION_THRESHOLD"),
+            generator: Box::new(super::redis_compression_threshold),
+        },
+        USizeEnvValue {
+            expected_default: 15,
+            env_key: String::from("REDIS_POOL_SIZE"),
+            generator: Box::new(super::redis_pool_size),
+        },
+        USizeEnvValue {
+            expected_default: 15,
+            env_key: String::from("MEMCACHED_POOL_SIZE"),
+            generator: Box::new(super::memcached_pool_size),
+        },
+        USizeEnvValue {
+            expected_default: 0,
+            env_key: String::from("MEMCACHED_COMPRESSION_THRESHOLD"),
+            generator: Box::new(super::memcached_compression_threshold),
+        },
+        USizeEnvValue {
+            expected_default: 100,
+            env_key: String::from("RECENT_RECIPIENTS_SCAN_SIZE"),
+            generator: Box::new(super::recent_recipients_scan_size),
+        },
+        USizeEnvValue {
+            expected_default: 100,
+            env_key: String::from("TX_QUEUED_SUMMARY_SIZE"),
+            generator: Box::new(super::tx_queued_summary_size),
+        },
+        USizeEnvValue {
+            expected_default: 0,
+            env_key: String::from("TX_QUEUED_EXPIRY_DAYS"),
+            generator: Box::new(super::tx_queued_expiry_days),
+        },
+        USizeEnvValue {
+            expected_default: 0,
+            env_key: String::from("UPSTREAM_CALL_BUDGET"),
+            generator: Box::new(super::upstream_call_budget),
+        },
+        USizeEnvValue {
+            expected_default: 0,
+            env_key: String::from("UPSTREAM_MAX_CONCURRENCY"),
+            generator: Box::new(super::upstream_max_concurrency),
+        },
+        USizeEnvValue {
+            expected_default: 5,
+            env_key: String::from("CHAIN_WARM_UP_CONCURRENCY"),
+            generator: Box::new(super::chain_warm_up_concurrency),
+        },
+        USizeEnvValue {
+            expected_default: 10000,
+            env_key: String::from("INVALIDATION_LOG_SIZE"),
+            generator: Box::new(super::invalidation_log_size),
+        },
+        USizeEnvValue {
+            expected_default: 100,
+            env_key: String::from("ALLOWANCES_SCAN_SIZE"),
+            generator: Box::new(super::allowances_scan_size),
+        },
+        USizeEnvValue {
+            expected_default: 512 * 1024,
+            env_key: String::from("ASSET_MAX_SIZE"),
+            generator: Box::new(super::asset_max_size),
+        },
+        USizeEnvValue {
+            expected_default: 1024 * 1024,
+            env_key: String::from("MAX_REQUEST_BODY_SIZE"),
+            generator: Box::new(super::max_request_body_size),
+        },
+        USizeEnvValue {
+            expected_default: 10 * 1024 * 1024,
+            env_key: String::from("UPSTREAM_MAX_RESPONSE_SIZE"),
+            generator: Box::new(super::upstream_max_response_size),
+        },
+        USizeEnvValue {
+            expected_default: 64,
+            env_key: String::from("UPSTREAM_MAX_JSON_DEPTH"),
+            generator: Box::new(super::upstream_max_json_depth),
+        },
+        USizeEnvValue {
+            expected_default: 0,
+            env_key: String::from("RESPONSE_SIZE_BUDGET"),
+            generator: Box::new(super::response_size_budget),
+        },
+        USizeEnvValue {
+            expected_default: 1024,
+            env_key: String::from("RESPONSE_HEX_DATA_LIMIT"),
+            generator: Box::new(super::response_hex_data_limit),
+        },
+        USizeEnvValue {
+            expected_default: 100,
+            env_key: String::from("ADAPTIVE_TIMEOUT_MIN_SAMPLES"),
+            generator: Box::new(super::adaptive_timeout_min_samples),
+        },
+        USizeEnvValue {
+            expected_default: 20,
+            env_key: String::from("TRANSACTION_DETAILS_BATCH_SIZE"),
+            generator: Box::new(super::transaction_details_batch_size),
+        },
+        USizeEnvValue {
+            expected_default: 10000,
+            env_key: String::from("ANALYTICS_BUFFER_SIZE"),
+            generator: Box::new(super::analytics_buffer_size),
+        },
+        USizeEnvValue {
+            expected_default: 100,
+            env_key: String::from("ANALYTICS_BATCH_SIZE"),
+            generator: Box::new(super::analytics_batch_size),
+        },
+        USizeEnvValue {
+            expected_default: 20,
+            env_key: String::from("SLO_MIN_REQUESTS"),
+            generator: Box::new(super::slo_min_requests),
+        },
+        USizeEnvValue {
+            expected_default: 10,
+            env_key: String::from("RECENT_RECIPIENTS_LIMIT"),
+            generator: Box::new(super::recent_recipients_limit),
+        },
+        USizeEnvValue {
+            expected_default: 5,
+            env_key: String::from("DASHBOARD_BALANCES_SIZE"),
+            generator: Box::new(super::dashboard_balances_size),
+        },
+        USizeEnvValue {
+            expected_default: 3,
+            env_key: String::from("DASHBOARD_HISTORY_SIZE"),
+            generator: Box::new(super::dashboard_history_size),
+        },
         USizeEnvValue {
             expected_default: 60 * 60 * 1000,
             env_key: String::from("SAFE_INFO_CACHE_DURATION"),
@@ -33,6 +203,21 @@ fn build_usize_test_cases() -> Vec<USizeEnvValue> {
             env_key: String::from("CHAIN_INFO_CACHE_DURATION"),
             generator: Box::new(super::chain_info_cache_duration),
         },
+        USizeEnvValue {
+            expected_default: 60 * 1000,
+            env_key: String::from("UNKNOWN_CHAIN_CACHE_DURATION"),
+            generator: Box::new(super::unknown_chain_cache_duration),
+        },
+        USizeEnvValue {
+            expected_default: 60 * 60 * 24 * 1000,
+            env_key: String::from("LAST_KNOWN_GOOD_CACHE_DURATION"),
+            generator: Box::new(super::last_known_good_cache_duration),
+        },
+        USizeEnvValue {
+            expected_default: 60 * 60 * 24 * 1000,
+            env_key: String::from("ASSET_CACHE_DURATION"),
+            generator: Box::new(super::asset_cache_duration),
+        },
         USizeEnvValue {
             expected_default: 60 * 60 * 12 * 1000,
             env_key: String::from("EXCHANGE_API_CACHE_DURATION"),
@@ -63,11 +248,36 @@ fn build_usize_test_cases() -> Vec<USizeEnvValue> {
             env_key: String::from("OWNERS_FOR_SAFES_CACHE_DURATION"),
             generator: Box::new(super::owners_for_safes_cache_duration),
         },
+        USizeEnvValue {
+            expected_default: 15 * 1000,
+            env_key: String::from("EXECUTION_ESTIMATION_CACHE_DURATION"),
+            generator: Box::new(super::execution_estimation_cache_duration),
+        },
+        USizeEnvValue {
+            expected_default: 10 * 1000,
+            env_key: String::from("GAS_PRICE_CACHE_DURATION"),
+            generator: Box::new(super::gas_price_cache_duration),
+        },
+        USizeEnvValue {
+            expected_default: 60 * 1000,
+            env_key: String::from("EXCHANGE_RATES_CACHE_DURATION"),
+            generator: Box::new(super::exchange_rates_cache_duration),
+        },
     ]
 }
 
 fn build_u64_test_cases() -> Vec<U64EnvValue> {
     vec![
+        U64EnvValue {
+            expected_default: 5000,
+            env_key: String::from("REDIS_CONNECTION_TIMEOUT"),
+            generator: Box::new(super::redis_connection_timeout),
+        },
+        U64EnvValue {
+            expected_default: 5000,
+            env_key: String::from("MEMCACHED_TIMEOUT"),
+            generator: Box::new(super::memcached_timeout),
+        },
         U64EnvValue {
             expected_default: 1000,
             env_key: String::from("INTERNAL_CLIENT_CONNECT_TIMEOUT"),
@@ -108,6 +318,96 @@ fn build_u64_test_cases() -> Vec<U64EnvValue> {
             env_key: String::from("COLLECTIBLES_REQUEST_TIMEOUT"),
             generator: Box::new(super::collectibles_request_timeout),
         },
+        U64EnvValue {
+            expected_default: 10000,
+            env_key: String::from("RPC_REQUEST_TIMEOUT"),
+            generator: Box::new(super::rpc_request_timeout),
+        },
+        U64EnvValue {
+            expected_default: 10000,
+            env_key: String::from("RELAY_REQUEST_TIMEOUT"),
+            generator: Box::new(super::relay_request_timeout),
+        },
+        U64EnvValue {
+            expected_default: 0,
+            env_key: String::from("TX_HISTORY_LATENCY_BUDGET"),
+            generator: Box::new(super::tx_history_latency_budget),
+        },
+        U64EnvValue {
+            expected_default: 0,
+            env_key: String::from("TX_QUEUED_LATENCY_BUDGET"),
+            generator: Box::new(super::tx_queued_latency_budget),
+        },
+        U64EnvValue {
+            expected_default: 30000,
+            env_key: String::from("TX_QUEUED_POLL_MAX_WAIT"),
+            generator: Box::new(super::tx_queued_poll_max_wait),
+        },
+        U64EnvValue {
+            expected_default: 1000,
+            env_key: String::from("TX_QUEUED_POLL_INTERVAL"),
+            generator: Box::new(super::tx_queued_poll_interval),
+        },
+        U64EnvValue {
+            expected_default: 0,
+            env_key: String::from("HOOK_DEBOUNCE_WINDOW"),
+            generator: Box::new(super::hook_debounce_window),
+        },
+        U64EnvValue {
+            expected_default: 1000,
+            env_key: String::from("UPSTREAM_QUEUE_TIMEOUT"),
+            generator: Box::new(super::upstream_queue_timeout),
+        },
+        U64EnvValue {
+            expected_default: 30000,
+            env_key: String::from("RETRY_QUEUE_INTERVAL"),
+            generator: Box::new(super::retry_queue_interval),
+        },
+        U64EnvValue {
+            expected_default: 1000,
+            env_key: String::from("HOOK_EVENTS_RETRY_INTERVAL"),
+            generator: Box::new(super::hook_events_retry_interval),
+        },
+        U64EnvValue {
+            expected_default: 3600000,
+            env_key: String::from("UPSTREAM_VERSION_CHECK_INTERVAL"),
+            generator: Box::new(super::upstream_version_check_interval),
+        },
+        U64EnvValue {
+            expected_default: 2000,
+            env_key: String::from("ADAPTIVE_TIMEOUT_MIN"),
+            generator: Box::new(super::adaptive_timeout_min),
+        },
+        U64EnvValue {
+            expected_default: 30000,
+            env_key: String::from("ADAPTIVE_TIMEOUT_MAX"),
+            generator: Box::new(super::adaptive_timeout_max),
+        },
+        U64EnvValue {
+            expected_default: 5000,
+            env_key: String::from("ANALYTICS_FLUSH_INTERVAL"),
+            generator: Box::new(super::analytics_flush_interval),
+        },
+        U64EnvValue {
+            expected_default: 60 * 60 * 1000,
+            env_key: String::from("SLO_WINDOW"),
+            generator: Box::new(super::slo_window),
+        },
+        U64EnvValue {
+            expected_default: 60 * 60 * 1000,
+            env_key: String::from("USAGE_WINDOW"),
+            generator: Box::new(super::usage_window),
+        },
+        U64EnvValue {
+            expected_default: 60 * 1000,
+            env_key: String::from("USAGE_BUCKET"),
+            generator: Box::new(super::usage_bucket),
+        },
+        U64EnvValue {
+            expected_default: 1000,
+            env_key: String::from("SLO_LATENCY_TARGET"),
+            generator: Box::new(super::slo_latency_target),
+        },
         U64EnvValue {
             expected_default: 10000,
             env_key: String::from("DEFAULT_REQUEST_TIMEOUT"),
@@ -178,3 +478,5 @@ impl TestCase for U64EnvValue {
         );
     }
 }
+
+mod settings;
@@ -0,0 +1,350 @@
+use crate::config::settings::*;
+use crate::config::{collect_config_errors, env_with_default, required_env, LogSampling};
+use std::collections::HashMap;
+
+fn valid_settings() -> Settings {
+    Settings {
+        services: ServiceSettings {
+            config_service_uri: String::from("https://safe-config.gnosis.io"),
+            exchange_api_base_uri: String::from("http://api.exchangeratesapi.io/latest"),
+            cache_backend: String::from("redis"),
+            cache_namespace: String::new(),
+            redis_uri: String::from("redis://:secret@localhost:6379"),
+            memcached_uri: None,
+            relay_service_uri: None,
+            analytics_sink_uri: None,
+            hook_events_uri: None,
+            slo_alert_webhook_uri: None,
+            static_token_list_uri: None,
+            nft_ipfs_gateway: String::from("https://cloudflare-ipfs.com/ipfs/"),
+            chain_seed_file: None,
+            address_risk_files: vec![],
+            address_reputation_uri: None,
+            transaction_service_fallback_uris: HashMap::new(),
+            shadow_upstreams: HashMap::new(),
+            chain_hosts: HashMap::new(),
+            upstream_header_hosts: vec![],
+            upstream_tls: HashMap::new(),
+            audit_log_file: None,
+            outbound_allowed_hosts: vec![],
+            scheme: String::from("https"),
+            profile: String::from("debug"),
+        },
+        secrets: SecretSettings {
+            webhook_token: Some(String::from("webhook_token")),
+            admin_api_keys: HashMap::new(),
+            exchange_api_key: Some(String::from("exchange_api_key")),
+            rpc_api_key: None,
+            relay_api_key: None,
+        },
+        cache_durations: CacheDurations {
+            short_error: 60000,
+            long_error: 900000,
+            request_error: 60000,
+            safe_info: 3600000,
+            address_info: 3600000,
+            address_reputation: 86400000,
+            token_info: 3600000,
+            chain_info: 3600000,
+            chain_info_response: 3600000,
+            unknown_chain: 60000,
+            last_known_good: 86400000,
+            asset: 86400000,
+            exchange_api: 43200000,
+            request: 3600000,
+            about: 900000,
+            balances: 60000,
+            safe_app_manifest: 3600000,
+            owners_for_safes: 60000,
+            safe_apps: 3600000,
+            token_price: 10000,
+            execution_estimation: 15000,
+            gas_price: 10000,
+            exchange_rates: 60000,
+        },
+        timeouts: Timeouts {
+            internal_client_connect: 1000,
+            default_request: 10000,
+            safe_app_info_request: 3000,
+            transaction_request: 30000,
+            safe_info_request: 10000,
+            token_info_request: 15000,
+            chain_info_request: 15000,
+            contract_info_request: 3000,
+            balances_request: 20000,
+            collectibles_request: 20000,
+            rpc_request: 10000,
+            relay_request: 10000,
+            tx_history_latency_budget: 0,
+            tx_queued_latency_budget: 0,
+            tx_queued_poll_max_wait: 30000,
+            tx_queued_poll_interval: 1000,
+            transaction_service_unhealthy_duration: 30000,
+            hook_debounce_window: 0,
+            upstream_queue: 1000,
+            analytics_flush_interval: 5000,
+            slo_window: 3600000,
+            slo_latency_target: 1000,
+            usage_window: 3600000,
+            usage_bucket: 60000,
+            redis_connection: 5000,
+            memcached: 5000,
+            retry_queue_interval: 30000,
+            hook_events_retry_interval: 1000,
+            upstream_version_check_interval: 3600000,
+            adaptive_timeout_min: 2000,
+            adaptive_timeout_max: 30000,
+        },
+        features: FeatureSettings {
+            nested_decoding: true,
+            balances_rate_implementation: false,
+            hook_prefetch: false,
+            schema_validation: false,
+            usage_tracking: false,
+            queued_tx_obsolete: false,
+            execution_cost: false,
+            decoded_logs: false,
+            transaction_descriptions: false,
+            confirmation_signature_check: false,
+            adaptive_timeouts: false,
+            read_only_mode: false,
+            cache_debug_headers: false,
+            ready_callbacks: false,
+            disabled_route_groups: vec![],
+            retry_queue_endpoints: vec![],
+            hook_events_broker: None,
+            hook_events_topic: String::from("safe-events"),
+            exchange_rate_tokens: HashMap::new(),
+            hook_prefetch_fiat: String::from("USD"),
+            vpc_transaction_service_uri: true,
+            log_all_error_responses: false,
+            outbound_url_validation: true,
+            log_threshold: 1.0,
+            log_sampling: HashMap::new(),
+            shadow_rate: 0.1,
+            slo_availability_target: 0.99,
+            slo_burn_rate_threshold: 2.0,
+            adaptive_timeout_factor: 3.0,
+        },
+        limits: LimitSettings {
+            redis_scan_count: 300,
+            redis_compression_threshold: 0,
+            redis_pool_size: 15,
+            memcached_pool_size: 15,
+            memcached_compression_threshold: 0,
+            concurrent_balance_token_requests: 5,
+            recent_recipients_scan_size: 100,
+            recent_recipients_limit: 10,
+            dashboard_balances_size: 5,
+            dashboard_history_size: 3,
+            tx_queued_summary_size: 100,
+            tx_queued_expiry_days: 0,
+            upstream_call_budget: 0,
+            upstream_max_concurrency: 0,
+            max_request_body_size: 1048576,
+            upstream_max_response_size: 10485760,
+            upstream_max_json_depth: 64,
+            response_size_budget: 0,
+            response_size_budgets: HashMap::new(),
+            response_hex_data_limit: 1024,
+            chain_warm_up_concurrency: 5,
+            invalidation_log_size: 10000,
+            allowances_scan_size: 100,
+            asset_max_size: 524288,
+            logo_prefetch_concurrency: 0,
+            logo_prefetch_limit: 500,
+            transaction_details_batch_size: 20,
+            analytics_buffer_size: 10000,
+            analytics_batch_size: 100,
+            slo_min_requests: 20,
+            relay_quota_limit: 5,
+            relay_quota_window: 86400000,
+            nft_refresh_window: 300000,
+            balance_history_max_days: 90,
+            retry_queue_max_attempts: 8,
+            hook_events_max_attempts: 3,
+            adaptive_timeout_min_samples: 100,
+            spam_token_denylist: vec![],
+            spam_name_patterns: vec![String::from("claim")],
+        },
+    }
+}
+
+#[test]
+fn collect_config_errors_reports_instead_of_panicking() {
+    std::env::set_var("SETTINGS_TEST_INVALID_NUMBER", "not-a-number");
+    std::env::remove_var("SETTINGS_TEST_MISSING_VALUE");
+
+    let ((number, missing), errors) = collect_config_errors(|| {
+        (
+            env_with_default::<usize>("SETTINGS_TEST_INVALID_NUMBER", 42),
+            required_env("SETTINGS_TEST_MISSING_VALUE"),
+        )
+    });
+
+    assert_eq!(number, 42);
+    assert_eq!(missing, "");
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].starts_with("Parsing of SETTINGS_TEST_INVALID_NUMBER env var key failed"));
+    assert_eq!(errors[1], "SETTINGS_TEST_MISSING_VALUE missing in env");
+}
+
+#[test]
+#[should_panic(expected = "SETTINGS_TEST_REQUIRED_VALUE missing in env")]
+fn config_errors_panic_outside_of_validation() {
+    std::env::remove_var("SETTINGS_TEST_REQUIRED_VALUE");
+
+    required_env("SETTINGS_TEST_REQUIRED_VALUE");
+}
+
+#[test]
+fn validate_valid_settings() {
+    assert_eq!(valid_settings().validate(), Vec::<String>::new());
+}
+
+#[test]
+fn validate_reports_every_invalid_value() {
+    let mut settings = valid_settings();
+    settings.services.config_service_uri = String::from("safe-config.gnosis.io");
+    settings.services.relay_service_uri = Some(String::from("not a url"));
+    settings.services.upstream_tls.insert(
+        String::from("safe-transaction.internal:8443"),
+        UpstreamTlsSettings {
+            ca_bundle: Some(String::from("/not/existing/ca.pem")),
+            client_identity: None,
+        },
+    );
+    settings.services.shadow_upstreams.insert(
+        String::from("https://safe-transaction.rinkeby.gnosis.io"),
+        String::from("staging"),
+    );
+    settings.services.chain_seed_file = Some(String::from("/not/existing/chains.json"));
+    settings.services.memcached_uri = Some(String::from("memcached://127.0.0.1:11211"));
+    settings.services.cache_backend = String::from("dynamodb");
+    settings.services.cache_namespace = String::from("v2_*");
+    settings.features.disabled_route_groups = vec![String::from("hooks"), String::from("safes")];
+    settings.features.retry_queue_endpoints = vec![String::from("proposals")];
+    settings.features.hook_events_broker = Some(String::from("sqs"));
+    settings.features.hook_events_topic = String::from("safe-events/../admin");
+    settings.secrets.admin_api_keys.insert(
+        String::from("ops"),
+        vec![String::from("diagnostics"), String::from("flush")],
+    );
+    settings.timeouts.rpc_request = 0;
+    settings.timeouts.tx_queued_poll_max_wait = 1000;
+    settings.timeouts.tx_queued_poll_interval = 2000;
+    settings.timeouts.usage_bucket = 7200000;
+    settings.features.log_threshold = 1.5;
+    settings.features.log_sampling.insert(
+        String::from("/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>"),
+        LogSampling {
+            success: 0.01,
+            error: 2.0,
+        },
+    );
+    settings.features.shadow_rate = -0.5;
+    settings.limits.recent_recipients_limit = 200;
+    settings.limits.memcached_pool_size = 0;
+
+    let expected = vec![
+        "CONFIG_SERVICE_URI is not a valid URL: safe-config.gnosis.io",
+        "RELAY_SERVICE_URI is not a valid URL: not a url",
+        "SHADOW_UPSTREAMS is not a valid URL: staging",
+        "UPSTREAM_TLS file for safe-transaction.internal:8443 not found: /not/existing/ca.pem",
+        "CHAIN_SEED_FILE not found: /not/existing/chains.json",
+        "MEMCACHED_URI must be a memcache://<host>:<port> URL: memcached://127.0.0.1:11211",
+        "CACHE_BACKEND must be redis or memcached: dynamodb",
+        "CACHE_NAMESPACE must be alphanumeric: v2_*",
+        "DISABLED_ROUTE_GROUPS has an unknown group: safes",
+        "RETRY_QUEUE_ENDPOINTS has an unknown endpoint: proposals",
+        "HOOK_EVENTS_BROKER must be kafka or nats: sqs",
+        "HOOK_EVENTS_TOPIC must be a Kafka topic name (letters, digits, '.', '_' and '-'): safe-events/../admin",
+        "HOOK_EVENTS_URI must be set with HOOK_EVENTS_BROKER",
+        "ADMIN_API_KEYS key ops has an unknown scope: flush",
+        "RPC_REQUEST_TIMEOUT must be greater than 0",
+        "TX_QUEUED_POLL_INTERVAL must be greater than 0 and at most TX_QUEUED_POLL_MAX_WAIT",
+        "USAGE_BUCKET must be at most USAGE_WINDOW",
+        "LOG_THRESHOLD must be within [0.0, 1.0]",
+        "LOG_SAMPLING rates of /v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat> must be within [0.0, 1.0]",
+        "SHADOW_RATE must be within [0.0, 1.0]",
+        "MEMCACHED_POOL_SIZE must be greater than 0",
+        "RECENT_RECIPIENTS_LIMIT must be at most RECENT_RECIPIENTS_SCAN_SIZE",
+    ];
+
+    assert_eq!(settings.validate(), expected);
+}
+
+#[test]
+fn validate_requires_a_token() {
+    let mut settings = valid_settings();
+    settings.secrets.webhook_token = None;
+
+    assert_eq!(
+        settings.validate(),
+        vec!["WEBHOOK_TOKEN or ADMIN_API_KEYS must be set"]
+    );
+
+    settings
+        .secrets
+        .admin_api_keys
+        .insert(String::from("tx-service"), vec![String::from("hooks")]);
+    assert_eq!(settings.validate(), Vec::<String>::new());
+}
+
+#[test]
+fn validate_requires_nats_subjects_with_nats() {
+    let mut settings = valid_settings();
+    settings.services.hook_events_uri = Some(String::from("nats://localhost:4222"));
+    settings.features.hook_events_broker = Some(String::from("nats"));
+    settings.features.hook_events_topic = String::from("safe..events");
+
+    assert_eq!(
+        settings.validate(),
+        vec!["HOOK_EVENTS_TOPIC must be a NATS subject (tokens separated by '.'): safe..events"]
+    );
+
+    settings.features.hook_events_broker = Some(String::from("kafka"));
+    assert_eq!(settings.validate(), Vec::<String>::new());
+}
+
+#[test]
+fn validate_requires_an_audit_log_file_with_the_release_profile() {
+    let mut settings = valid_settings();
+    settings.services.profile = String::from("release");
+
+    assert_eq!(
+        settings.validate(),
+        vec!["AUDIT_LOG_FILE must be set with the release profile"]
+    );
+
+    settings.services.audit_log_file = Some(String::from("/var/log/audit.log"));
+    assert_eq!(settings.validate(), Vec::<String>::new());
+}
+
+#[test]
+fn settings_serialization_redacts_secrets() {
+    let actual = serde_json::to_value(&valid_settings()).unwrap();
+
+    assert_eq!(
+        actual["services"]["redisUri"],
+        "redis://:********@localhost:6379"
+    );
+    assert_eq!(actual["secrets"]["webhookToken"], "********");
+    assert_eq!(actual["secrets"]["exchangeApiKey"], "********");
+    assert!(actual["secrets"]["rpcApiKey"].is_null());
+    assert!(!actual.to_string().contains("secret@"));
+}
+
+#[test]
+fn settings_report_lists_every_error() {
+    let report = SettingsReport {
+        errors: vec![
+            String::from("REDIS_URI missing in env"),
+            String::from("LOG_THRESHOLD must be within [0.0, 1.0]"),
+        ],
+    };
+
+    let expected = "Invalid configuration, 2 problem(s) found:\n  - REDIS_URI missing in env\n  - LOG_THRESHOLD must be within [0.0, 1.0]\n";
+
+    assert_eq!(report.to_string(), expected);
+}
@@ -0,0 +1,139 @@
+use crate::cache::Cache;
+use crate::config::{disabled_route_groups, max_request_body_size};
+use crate::monitoring;
+use crate::routes::{core_routes, error_catchers, RouteGroup};
+use crate::utils::cache_control::CacheControl;
+use crate::utils::call_budget::CallBudgetGuard;
+use crate::utils::capabilities::ClientCapabilityNegotiation;
+use crate::utils::chain_hosts::ChainHosts;
+use crate::utils::chain_warm_up::ChainWarmUp;
+use crate::utils::checksum::AddressChecksums;
+use crate::utils::cors::CORS;
+use crate::utils::data_policies::DataMaxAge;
+use crate::utils::http_client::HttpClient;
+use crate::utils::retry_queue::RetryQueueWorker;
+use crate::utils::serialization::SerializationProfiles;
+use crate::utils::trace_id::TraceIds;
+use crate::utils::upstream_versions::UpstreamVersionCheck;
+use rocket::data::{ByteUnit, Limits};
+use rocket::{Build, Config, Rocket, Route};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Assembles the rocket instance of the gateway, serving the core routes plus the enabled
+/// [RouteGroup]s (all of them by default)
+pub struct GatewayBuilder {
+    cache: Arc<dyn Cache>,
+    http_client: Arc<dyn HttpClient>,
+    route_groups: BTreeSet<RouteGroup>,
+    core_routes: bool,
+    routes: Vec<Route>,
+    fairings: bool,
+}
+
+impl GatewayBuilder {
+    pub fn new(cache: Arc<dyn Cache>, http_client: Arc<dyn HttpClient>) -> Self {
+        GatewayBuilder {
+            cache,
+            http_client,
+            route_groups: RouteGroup::ALL.iter().copied().collect(),
+            core_routes: true,
+            routes: vec![],
+            fairings: true,
+        }
+    }
+
+    /// Leaves out the groups in `DISABLED_ROUTE_GROUPS`, unknown groups are reported by
+    /// [Settings::load](crate::config::settings::Settings::load)
+    pub fn from_config(cache: Arc<dyn Cache>, http_client: Arc<dyn HttpClient>) -> Self {
+        disabled_route_groups()
+            .iter()
+            .filter_map(|name| RouteGroup::from_name(name))
+            .fold(GatewayBuilder::new(cache, http_client), |builder, group| {
+                builder.disable(group)
+            })
+    }
+
+    /// Serves only the given groups, on top of the core routes
+    #[cfg(any(test, feature = "testing"))]
+    pub fn route_groups(mut self, route_groups: &[RouteGroup]) -> Self {
+        self.route_groups = route_groups.iter().copied().collect();
+        self
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub fn enable(mut self, route_group: RouteGroup) -> Self {
+        self.route_groups.insert(route_group);
+        self
+    }
+
+    pub fn disable(mut self, route_group: RouteGroup) -> Self {
+        self.route_groups.remove(&route_group);
+        self
+    }
+
+    /// Whether the routes every deployment serves are mounted, tests can mount only the routes
+    /// under test
+    #[cfg(any(test, feature = "testing"))]
+    pub fn core_routes(mut self, core_routes: bool) -> Self {
+        self.core_routes = core_routes;
+        self
+    }
+
+    /// Mounts `routes` on top of the enabled groups
+    #[cfg(any(test, feature = "testing"))]
+    pub fn routes(mut self, routes: Vec<Route>) -> Self {
+        self.routes.extend(routes);
+        self
+    }
+
+    /// Whether the monitoring, caching and CORS fairings are attached
+    #[cfg(any(test, feature = "testing"))]
+    pub fn fairings(mut self, fairings: bool) -> Self {
+        self.fairings = fairings;
+        self
+    }
+
+    pub fn build(self) -> Rocket<Build> {
+        let mut routes = if self.core_routes {
+            core_routes()
+        } else {
+            vec![]
+        };
+        for route_group in self.route_groups.iter() {
+            routes.extend(route_group.routes());
+        }
+        routes.extend(self.routes);
+
+        // Bodies of routes taking `Json` are cut off at the `json` limit
+        let limits =
+            Limits::default().limit("json", ByteUnit::from(max_request_body_size() as u64));
+        let rocket = rocket::custom(Config::figment().merge(("limits", limits)))
+            .mount("/", routes)
+            .register("/", error_catchers())
+            .manage(self.cache)
+            .manage(self.http_client);
+        if !self.fairings {
+            return rocket;
+        }
+        #[cfg(feature = "grpc")]
+        let rocket = rocket.attach(crate::grpc::server::GrpcServer());
+        rocket
+            .attach(TraceIds())
+            .attach(ChainHosts())
+            .attach(CallBudgetGuard())
+            .attach(monitoring::performance::PerformanceMonitor())
+            .attach(monitoring::slo::SloMonitor())
+            .attach(monitoring::usage::UsageTracker())
+            .attach(CacheControl())
+            .attach(DataMaxAge())
+            .attach(AddressChecksums())
+            // Before the serialization profiles, which rename the keys it looks for
+            .attach(ClientCapabilityNegotiation())
+            .attach(SerializationProfiles())
+            .attach(CORS())
+            .attach(ChainWarmUp())
+            .attach(RetryQueueWorker())
+            .attach(UpstreamVersionCheck())
+    }
+}
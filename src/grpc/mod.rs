@@ -0,0 +1,123 @@
+//! Read operations exposed to internal service consumers through the gRPC contract in
+//! `proto/gateway.proto`, served by [server::GrpcServer]. Only compiled with the `grpc` feature.
+//!
+//! The operations reuse the same handlers (and caches) as the REST endpoints, so both
+//! interfaces always return the same data.
+
+use crate::cache::Cache;
+use crate::common::models::page::Page;
+use crate::config::{feature_flag_balances_rate_implementation, scheme};
+use crate::routes::balances::models::Balances;
+use crate::routes::balances::{handlers, handlers_v2};
+use crate::routes::safes::handlers::safes::get_safe_info_ex;
+use crate::routes::safes::models::SafeState;
+use crate::routes::transactions::handlers::queued::get_queued_transactions;
+use crate::routes::transactions::models::summary::TransactionListItem;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::HttpClient;
+use std::sync::Arc;
+
+pub mod server;
+
+#[cfg(test)]
+mod tests;
+
+/// Models and service stubs generated from `proto/gateway.proto` by `build.rs`
+pub mod proto {
+    tonic::include_proto!("safe_client_gateway.v1");
+}
+
+#[derive(Clone)]
+pub struct GatewayReadService {
+    host: String,
+    http_client: Arc<dyn HttpClient>,
+    cache: Arc<dyn Cache>,
+}
+
+impl GatewayReadService {
+    /// `host` is used for the absolute urls (e.g. pagination) contained in the responses
+    pub fn new(host: &str, http_client: Arc<dyn HttpClient>, cache: Arc<dyn Cache>) -> Self {
+        GatewayReadService {
+            host: format!("{}://{}", scheme(), host),
+            http_client,
+            cache,
+        }
+    }
+
+    pub async fn safe_info(
+        &self,
+        chain_id: &String,
+        safe_address: &String,
+    ) -> ApiResult<SafeState> {
+        let context = self.context(format!("/v1/chains/{}/safes/{}", chain_id, safe_address));
+        get_safe_info_ex(&context, chain_id, safe_address).await
+    }
+
+    pub async fn balances(
+        &self,
+        chain_id: &str,
+        safe_address: &str,
+        fiat: &str,
+        trusted: bool,
+        exclude_spam: bool,
+    ) -> ApiResult<Balances> {
+        let context = self.context(format!(
+            "/v1/chains/{}/safes/{}/balances/{}",
+            chain_id, safe_address, fiat
+        ));
+        if feature_flag_balances_rate_implementation() {
+            handlers_v2::balances(
+                &context,
+                chain_id,
+                safe_address,
+                fiat,
+                trusted,
+                exclude_spam,
+            )
+            .await
+        } else {
+            handlers::balances(
+                &context,
+                chain_id,
+                safe_address,
+                fiat,
+                trusted,
+                exclude_spam,
+            )
+            .await
+        }
+    }
+
+    /// Streaming consumers follow `next` until it is `None`
+    pub async fn queued_transactions(
+        &self,
+        chain_id: &String,
+        safe_address: &String,
+        cursor: &Option<String>,
+    ) -> ApiResult<Page<TransactionListItem>> {
+        let context = self.context(format!(
+            "/v1/chains/{}/safes/{}/transactions/queued",
+            chain_id, safe_address
+        ));
+        get_queued_transactions(
+            &context,
+            chain_id,
+            safe_address,
+            cursor,
+            &None,
+            &None,
+            false,
+        )
+        .await
+    }
+
+    fn context(&self, request_id: String) -> RequestContext {
+        RequestContext::new(
+            request_id,
+            self.host.to_string(),
+            self.http_client.clone(),
+            self.cache.clone(),
+        )
+    }
+}
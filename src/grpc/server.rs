@@ -0,0 +1,232 @@
+use crate::cache::Cache;
+use crate::common::models::addresses::AddressEx;
+use crate::config::grpc_port;
+use crate::grpc::proto::gateway_server::{Gateway, GatewayServer};
+use crate::grpc::{proto, GatewayReadService};
+use crate::routes::balances::models::{Balance, Balances};
+use crate::routes::safes::models::SafeState;
+use crate::routes::transactions::models::summary::{
+    ExecutionInfo, TransactionListItem, TransactionSummary,
+};
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::HttpClient;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Orbit, Rocket};
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+// Transactions buffered per stream while the consumer is reading
+const STREAM_BUFFER: usize = 32;
+
+#[tonic::async_trait]
+impl Gateway for GatewayReadService {
+    async fn get_safe_info(
+        &self,
+        request: Request<proto::SafeRequest>,
+    ) -> Result<Response<proto::SafeState>, Status> {
+        let request = request.into_inner();
+        let safe_state = self
+            .safe_info(&request.chain_id, &request.safe_address)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(safe_state_message(safe_state)))
+    }
+
+    async fn get_balances(
+        &self,
+        request: Request<proto::BalancesRequest>,
+    ) -> Result<Response<proto::Balances>, Status> {
+        let request = request.into_inner();
+        let balances = self
+            .balances(
+                &request.chain_id,
+                &request.safe_address,
+                &request.fiat,
+                request.trusted,
+                request.exclude_spam,
+            )
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(balances_message(balances)))
+    }
+
+    type StreamQueuedTransactionsStream = ReceiverStream<Result<proto::TransactionSummary, Status>>;
+
+    /// Follows the pages of the queue until the last one or until the consumer goes away. An
+    /// error loading a page ends the stream with that error.
+    async fn stream_queued_transactions(
+        &self,
+        request: Request<proto::SafeRequest>,
+    ) -> Result<Response<Self::StreamQueuedTransactionsStream>, Status> {
+        let request = request.into_inner();
+        let service = self.clone();
+        let (sender, receiver) = rocket::tokio::sync::mpsc::channel(STREAM_BUFFER);
+        rocket::tokio::spawn(async move {
+            let mut cursor = None;
+            loop {
+                let page = match service
+                    .queued_transactions(&request.chain_id, &request.safe_address, &cursor)
+                    .await
+                {
+                    Ok(page) => page,
+                    Err(error) => {
+                        let _ = sender.send(Err(to_status(error))).await;
+                        return;
+                    }
+                };
+                cursor = page.page_info().cursor;
+                for item in page.results {
+                    if let TransactionListItem::Transaction { transaction, .. } = item {
+                        if sender
+                            .send(Ok(transaction_summary_message(transaction)))
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                }
+                if cursor.is_none() {
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+/// Serves [GatewayReadService] on `GRPC_PORT` after liftoff, on the address of the REST endpoints
+pub struct GrpcServer();
+
+#[rocket::async_trait]
+impl Fairing for GrpcServer {
+    fn info(&self) -> Info {
+        Info {
+            name: "GrpcServer",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let (cache, http_client) = match (
+            rocket.state::<Arc<dyn Cache>>(),
+            rocket.state::<Arc<dyn HttpClient>>(),
+        ) {
+            (Some(cache), Some(http_client)) => (cache.clone(), http_client.clone()),
+            _ => return,
+        };
+        let config = rocket.config();
+        let host = format!("{}:{}", config.address, config.port);
+        let address = SocketAddr::new(config.address, grpc_port());
+        let service = GatewayReadService::new(&host, http_client, cache);
+        rocket::tokio::spawn(async move {
+            log::info!("Serving gRPC on {}", address);
+            if let Err(error) = tonic::transport::Server::builder()
+                .add_service(GatewayServer::new(service))
+                .serve(address)
+                .await
+            {
+                log::error!("gRPC server on {} stopped: {}", address, error);
+            }
+        });
+    }
+}
+
+/// Same meaning as the status of the equivalent REST response
+pub fn to_status(error: ApiError) -> Status {
+    let message = error.details.message.unwrap_or_default();
+    match error.status {
+        400 | 422 => Status::invalid_argument(message),
+        401 | 403 => Status::permission_denied(message),
+        404 => Status::not_found(message),
+        429 => Status::resource_exhausted(message),
+        503 | 504 => Status::unavailable(message),
+        _ => Status::internal(message),
+    }
+}
+
+/// Enums are sent as the strings of their JSON representation
+fn enum_name(value: &impl Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+fn address_message(address: AddressEx) -> proto::AddressEx {
+    proto::AddressEx {
+        value: address.value,
+        name: address.name,
+        logo_uri: address.logo_uri,
+    }
+}
+
+pub fn safe_state_message(safe_state: SafeState) -> proto::SafeState {
+    let safe_config = safe_state.safe_config;
+    proto::SafeState {
+        address: Some(address_message(safe_config.address)),
+        chain_id: safe_config.chain_id,
+        nonce: safe_config.nonce,
+        threshold: safe_config.threshold,
+        owners: safe_config
+            .owners
+            .into_iter()
+            .map(address_message)
+            .collect(),
+        implementation: Some(address_message(safe_config.implementation.address)),
+        modules: safe_config
+            .modules
+            .unwrap_or_default()
+            .into_iter()
+            .map(address_message)
+            .collect(),
+        fallback_handler: safe_config.fallback_handler.flatten().map(address_message),
+        guard: safe_config.guard.flatten().map(address_message),
+        version: safe_config.version,
+        collectibles_tag: safe_state.safe_state.collectibles_tag,
+        tx_queued_tag: safe_state.safe_state.tx_queued_tag,
+        tx_history_tag: safe_state.safe_state.tx_history_tag,
+        implementation_version_state: enum_name(&safe_config.implementation_version_state),
+    }
+}
+
+fn balance_message(balance: Balance) -> proto::Balance {
+    let token_info = balance.token_info;
+    proto::Balance {
+        token_info: Some(proto::TokenInfo {
+            token_type: enum_name(&token_info.token_type),
+            address: token_info.address,
+            decimals: token_info.decimals,
+            symbol: token_info.symbol,
+            name: token_info.name,
+            logo_uri: token_info.logo_uri,
+        }),
+        balance: balance.balance,
+        fiat_balance: balance.fiat_balance,
+        fiat_conversion: balance.fiat_conversion,
+    }
+}
+
+pub fn balances_message(balances: Balances) -> proto::Balances {
+    proto::Balances {
+        fiat_total: balances.fiat_total,
+        items: balances.items.into_iter().map(balance_message).collect(),
+    }
+}
+
+pub fn transaction_summary_message(transaction: TransactionSummary) -> proto::TransactionSummary {
+    let nonce = match &transaction.execution_info {
+        Some(ExecutionInfo::Multisig(execution_info)) => Some(execution_info.nonce),
+        _ => None,
+    };
+    proto::TransactionSummary {
+        tx_info_json: serde_json::to_string(&transaction.tx_info).unwrap_or_default(),
+        tx_status: enum_name(&transaction.tx_status),
+        id: transaction.id,
+        timestamp: Some(transaction.timestamp),
+        nonce,
+    }
+}
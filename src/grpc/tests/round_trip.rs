@@ -0,0 +1,166 @@
+use crate::cache::MockCache;
+use crate::grpc::proto::gateway_client::GatewayClient;
+use crate::grpc::proto::gateway_server::GatewayServer;
+use crate::grpc::proto::SafeRequest;
+use crate::grpc::GatewayReadService;
+use crate::tests::json::{
+    BACKEND_QUEUED_TRANSACTION_LIST_PAGE_NO_CONFLICTS, CHAIN_INFO_RINKEBY, SAFE_WITH_MODULES,
+};
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::MockHttpClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::StreamExt;
+use tonic::transport::{Channel, Server};
+use tonic::Code;
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+/// Cache serving the chain info, the safe info of `SAFE` and its queue, every upstream request
+/// fails
+fn mock_cache() -> MockCache {
+    let chain_key = format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"));
+    let has_chain_key = chain_key.to_string();
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .returning(move |key| key == has_chain_key);
+    mock_cache.expect_fetch().returning(move |key| {
+        if key == chain_key {
+            Some(String::from(CHAIN_INFO_RINKEBY))
+        } else if key.starts_with("c_reqs_") && key.ends_with(&format!("/v1/safes/{}/", SAFE)) {
+            Some(format!("200;{}", SAFE_WITH_MODULES))
+        } else if key.starts_with("c_reqs_") && key.contains("nonce__gte=") {
+            Some(format!(
+                "200;{}",
+                BACKEND_QUEUED_TRANSACTION_LIST_PAGE_NO_CONFLICTS
+            ))
+        } else {
+            None
+        }
+    });
+    mock_cache.expect_create().return_const(());
+    mock_cache.expect_insert_in_hash().return_const(());
+    mock_cache.expect_get_from_hash().returning(|_, _| None);
+    mock_cache.expect_increment_in_hash().return_const(1usize);
+    mock_cache.expect_fetch_hash().returning(|_| HashMap::new());
+    mock_cache.expect_ttl().returning(|_| None);
+    mock_cache.expect_expire_entity().return_const(());
+    mock_cache.expect_keys().returning(|_| vec![]);
+    mock_cache.expect_invalidate_pattern().return_const(0usize);
+    mock_cache.expect_invalidate().return_const(());
+    mock_cache.expect_info().returning(|| None);
+    mock_cache.expect_append_to_stream().return_const(());
+    mock_cache.expect_read_stream().returning(|_, _, _| vec![]);
+    mock_cache
+}
+
+fn mock_http_client() -> MockHttpClient {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_get().returning(|_| {
+        Err(ApiError::new_from_message_with_code(
+            404,
+            String::from("Not found"),
+        ))
+    });
+    mock_http_client
+}
+
+/// Client of a server started on a free local port
+async fn client() -> GatewayClient<Channel> {
+    let service = GatewayReadService::new(
+        "localhost",
+        Arc::new(mock_http_client()),
+        Arc::new(mock_cache()),
+    );
+    let listener = rocket::tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    let address = listener.local_addr().unwrap();
+    rocket::tokio::spawn(
+        Server::builder()
+            .add_service(GatewayServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    GatewayClient::connect(format!("http://{}", address))
+        .await
+        .unwrap()
+}
+
+fn safe_request(safe_address: &str) -> SafeRequest {
+    SafeRequest {
+        chain_id: String::from("4"),
+        safe_address: String::from(safe_address),
+    }
+}
+
+#[rocket::async_test]
+async fn get_safe_info_returns_safe_state() {
+    let mut client = client().await;
+
+    let actual = client
+        .get_safe_info(safe_request(SAFE))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(actual.address.unwrap().value, SAFE);
+    assert_eq!(actual.chain_id, "4");
+    assert_eq!(actual.nonce, 180);
+    assert_eq!(actual.threshold, 3);
+    assert_eq!(actual.owners.len(), 5);
+    assert_eq!(actual.modules.len(), 3);
+    assert_eq!(
+        actual.fallback_handler.unwrap().value,
+        "0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44"
+    );
+    assert_eq!(actual.version.as_deref(), Some("1.1.1"));
+}
+
+#[rocket::async_test]
+async fn get_safe_info_unknown_safe_is_not_found() {
+    let mut client = client().await;
+
+    let actual = client
+        .get_safe_info(safe_request("0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(actual.code(), Code::NotFound);
+}
+
+#[rocket::async_test]
+async fn stream_queued_transactions_streams_whole_queue() {
+    let mut client = client().await;
+
+    let actual: Vec<(String, Option<u64>)> = client
+        .stream_queued_transactions(safe_request(SAFE))
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|transaction| {
+            let transaction = transaction.unwrap();
+            (transaction.id, transaction.nonce)
+        })
+        .collect()
+        .await;
+
+    assert_eq!(
+        actual,
+        vec![
+            (
+                format!("multisig_{}_0x0fe072e76498e0db46fc79113662026a4f8fb34e840491aefeff6dec21c766cb", SAFE),
+                Some(392)
+            ),
+            (
+                format!("multisig_{}_0x2e4af4b451a493470f38625c5f78f710f02303eb32780896cb55357c00d48faa", SAFE),
+                Some(393)
+            ),
+            (
+                format!("multisig_{}_0xca7a464a3479af396c2975b4b3f5f7b90fc56747404ebaad5ec838c2954d2f9c", SAFE),
+                Some(394)
+            ),
+        ]
+    );
+}
@@ -0,0 +1,42 @@
+#![deny(unused_must_use)]
+
+extern crate dotenv;
+extern crate log;
+extern crate semver;
+
+#[macro_use]
+extern crate rocket;
+
+#[doc(hidden)]
+#[macro_use]
+pub mod macros;
+
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
+pub mod common;
+#[doc(hidden)]
+pub mod config;
+
+#[cfg(feature = "grpc")]
+#[doc(hidden)]
+pub mod grpc;
+
+#[doc(hidden)]
+pub mod gateway;
+
+#[doc(hidden)]
+pub mod monitoring;
+#[doc(hidden)]
+pub mod providers;
+
+/// Collection of all endpoints all endpoints
+pub mod routes;
+/// Fixtures for tests of deployments extending the gateway, see the `testing` feature
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[doc(hidden)]
+pub mod utils;
+
+#[cfg(test)]
+mod tests;
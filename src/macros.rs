@@ -68,10 +68,20 @@ macro_rules! to_hex_string {
 }
 
 pub fn get_transaction_service_host(chain_info: ChainInfo) -> String {
-    if vpc_transaction_service_uri() {
-        chain_info.vpc_transaction_service
+    let (host, fallback_host) = if vpc_transaction_service_uri() {
+        (
+            chain_info.vpc_transaction_service,
+            chain_info.vpc_transaction_service_fallback_uri,
+        )
     } else {
-        chain_info.transaction_service
+        (
+            chain_info.transaction_service,
+            chain_info.transaction_service_fallback_uri,
+        )
+    };
+    match fallback_host {
+        Some(_) => crate::providers::failover::active_uri(&host),
+        None => host,
     }
 }
 
@@ -0,0 +1,248 @@
+use crate::config::{audit_log_file, audit_log_max_size};
+use crate::utils::errors::ApiResult;
+use chrono::Utc;
+use ethcontract_common::hash::keccak256;
+use lazy_static::lazy_static;
+use rocket::request::{self, FromRequest, Request};
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::mpsc::{channel, SendError, Sender};
+use std::sync::Mutex;
+use std::thread;
+
+// Bytes read at a time while reading the log from its end
+const TAIL_CHUNK_SIZE: u64 = 64 * 1024;
+
+lazy_static! {
+    // Entries are written by a single thread, so that requests don't wait for the file system and
+    // lines of concurrent requests don't interleave
+    static ref AUDIT_LOG_WRITER: Mutex<Option<Sender<(String, AuditEntry)>>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuditOperation {
+    ProposeTransaction,
+    ConfirmTransaction,
+    CreateDelegate,
+    DeleteDelegate,
+    DeleteSafeDelegate,
+    SetSafeLabel,
+    SetTransactionNote,
+    RegisterCounterfactualSafe,
+    CreateSafe,
+    Relay,
+    HookUpdate,
+    HookBatch,
+    Flush,
+    CacheImport,
+    QueuePurge,
+    TokenOverride,
+    ReadyCallback,
+    CacheMigration,
+    ReadOnlyMode,
+}
+
+impl AuditOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOperation::ProposeTransaction => "PROPOSE_TRANSACTION",
+            AuditOperation::ConfirmTransaction => "CONFIRM_TRANSACTION",
+            AuditOperation::CreateDelegate => "CREATE_DELEGATE",
+            AuditOperation::DeleteDelegate => "DELETE_DELEGATE",
+            AuditOperation::DeleteSafeDelegate => "DELETE_SAFE_DELEGATE",
+            AuditOperation::SetSafeLabel => "SET_SAFE_LABEL",
+            AuditOperation::SetTransactionNote => "SET_TRANSACTION_NOTE",
+            AuditOperation::RegisterCounterfactualSafe => "REGISTER_COUNTERFACTUAL_SAFE",
+            AuditOperation::CreateSafe => "CREATE_SAFE",
+            AuditOperation::Relay => "RELAY",
+            AuditOperation::HookUpdate => "HOOK_UPDATE",
+            AuditOperation::HookBatch => "HOOK_BATCH",
+            AuditOperation::Flush => "FLUSH",
+            AuditOperation::CacheImport => "CACHE_IMPORT",
+            AuditOperation::QueuePurge => "QUEUE_PURGE",
+            AuditOperation::TokenOverride => "TOKEN_OVERRIDE",
+            AuditOperation::ReadyCallback => "READY_CALLBACK",
+            AuditOperation::CacheMigration => "CACHE_MIGRATION",
+            AuditOperation::ReadOnlyMode => "READ_ONLY_MODE",
+        }
+    }
+}
+
+/// Identity of the client as far as the gateway can tell, writes are not authenticated per user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Caller {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Caller {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(Caller {
+            ip: request.client_ip().map(|ip| ip.to_string()),
+            user_agent: request.headers().get_one("User-Agent").map(String::from),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: i64,
+    pub operation: String,
+    pub target: String,
+    pub caller: Caller,
+    pub payload_hash: String,
+    pub status: u16,
+}
+
+impl AuditEntry {
+    pub fn new<R>(
+        operation: AuditOperation,
+        target: &str,
+        caller: &Caller,
+        payload_hash: String,
+        result: &ApiResult<R>,
+    ) -> Self {
+        AuditEntry {
+            timestamp: Utc::now().timestamp_millis(),
+            operation: operation.as_str().to_string(),
+            target: target.to_string(),
+            caller: caller.clone(),
+            payload_hash,
+            status: match result {
+                Ok(_) => 200,
+                Err(error) => error.status,
+            },
+        }
+    }
+}
+
+/// Records the outcome of a write operation, if an audit log file is configured.
+/// The entry is written in the background, failing to write it does not fail the operation.
+pub fn record<R>(
+    operation: AuditOperation,
+    target: &str,
+    caller: &Caller,
+    payload_hash: String,
+    result: &ApiResult<R>,
+) {
+    let path = match audit_log_file() {
+        Some(path) => path,
+        None => return,
+    };
+    let entry = AuditEntry::new(operation, target, caller, payload_hash, result);
+    let mut writer = AUDIT_LOG_WRITER.lock().unwrap();
+    let sender = writer.get_or_insert_with(spawn_writer);
+    // The writer only stops if it panicked, the entry is handed to a new one
+    if let Err(SendError(message)) = sender.send((path, entry)) {
+        let sender = spawn_writer();
+        let _ = sender.send(message);
+        *writer = Some(sender);
+    }
+}
+
+fn spawn_writer() -> Sender<(String, AuditEntry)> {
+    let (sender, receiver) = channel::<(String, AuditEntry)>();
+    thread::spawn(move || {
+        for (path, entry) in receiver {
+            if let Err(error) = append_entry(&path, &entry, audit_log_max_size() as u64) {
+                log::error!("Could not write audit log entry to {}: {}", path, error);
+            }
+        }
+    });
+    sender
+}
+
+/// Newest entries first, optionally only for one operation. The log is read from its end (and
+/// then the rotated file), only as far as needed for `limit` entries.
+pub async fn read_entries(operation: Option<String>, limit: usize) -> ApiResult<Vec<AuditEntry>> {
+    let path = audit_log_file().ok_or(client_error!(404, "Audit log is not enabled"))?;
+    rocket::tokio::task::spawn_blocking(move || newest_entries(&path, &operation, limit))
+        .await
+        .map_err(|error| api_error!("Audit log could not be read: {}", error))
+}
+
+/// Requests are not stored, only a hash to match entries against known payloads
+pub fn payload_hash<T: Serialize>(payload: &T) -> String {
+    let serialized = serde_json::to_vec(payload).unwrap_or_default();
+    to_hex_string!(keccak256(serialized))
+}
+
+/// File the log is moved to once it reaches `AUDIT_LOG_MAX_SIZE`, replacing the previous one
+pub fn rotated_path(path: &str) -> String {
+    format!("{}.1", path)
+}
+
+/// Appends `entry`, first rotating the log if the line would grow it beyond `max_size` bytes
+/// (0 disables the rotation)
+pub fn append_entry(path: &str, entry: &AuditEntry, max_size: u64) -> io::Result<()> {
+    let line = serde_json::to_string(entry)?;
+    let size = fs::metadata(path).map_or(0, |metadata| metadata.len());
+    if max_size > 0 && size > 0 && size + line.len() as u64 + 1 > max_size {
+        fs::rename(path, rotated_path(path))?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+pub fn newest_entries(path: &str, operation: &Option<String>, limit: usize) -> Vec<AuditEntry> {
+    let mut entries = vec![];
+    for path in [path.to_string(), rotated_path(path)].iter() {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(_) => continue,
+        };
+        let result = visit_lines_from_end(&mut file, |line| {
+            let matches = serde_json::from_str::<AuditEntry>(line)
+                .ok()
+                .filter(|entry| {
+                    operation.as_ref().map_or(true, |operation| {
+                        operation.eq_ignore_ascii_case(&entry.operation)
+                    })
+                });
+            entries.extend(matches);
+            entries.len() < limit
+        });
+        if let Err(error) = result {
+            log::error!("Could not read audit log {}: {}", path, error);
+        }
+        if entries.len() >= limit {
+            break;
+        }
+    }
+    entries.truncate(limit);
+    entries
+}
+
+/// Calls `visit` with every line of `file`, last line first, until it returns `false`
+fn visit_lines_from_end(file: &mut File, mut visit: impl FnMut(&str) -> bool) -> io::Result<()> {
+    let mut position = file.metadata()?.len();
+    // Start of the line continuing in the chunk read before
+    let mut rest: Vec<u8> = vec![];
+    while position > 0 {
+        let chunk_size = min(TAIL_CHUNK_SIZE, position);
+        position -= chunk_size;
+        file.seek(SeekFrom::Start(position))?;
+        let mut chunk = vec![0; chunk_size as usize];
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&rest);
+        let mut lines: Vec<&[u8]> = chunk.split(|byte| *byte == b'\n').collect();
+        // The first line may start in the chunk before
+        rest = lines.remove(0).to_vec();
+        for line in lines.iter().rev() {
+            if !line.is_empty() && !visit(&String::from_utf8_lossy(line)) {
+                return Ok(());
+            }
+        }
+    }
+    if !rest.is_empty() {
+        visit(&String::from_utf8_lossy(&rest));
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Snapshot of the health of this instance, aggregated from the other monitors so that simple
+//! dashboards only need to poll `/admin/dashboard/<token>`.
+use crate::cache::Cache;
+use crate::monitoring::slo::{self, RouteSlo};
+use crate::monitoring::upstream_latency::{self, ChainUpstreamLatency};
+use crate::providers::failover::{self, ServiceCircuit};
+use crate::routes::analytics::handlers::buffered_events;
+use crate::routes::hooks::handlers::pending_hooks;
+use crate::utils::cache_control::{self, CacheHitStats};
+use crate::utils::upstream_queue::{self, UpstreamQueueStats};
+use chrono::Utc;
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Dashboard {
+    /// In ms
+    pub generated_at: i64,
+    pub chains: Vec<ChainUpstreamLatency>,
+    pub upstream_services: Vec<ServiceCircuit>,
+    pub cache: CacheHitStats,
+    /// `None` if the cache doesn't report its memory
+    pub cache_memory: Option<CacheMemory>,
+    pub queues: QueueDepths,
+    pub errors: ErrorCounts,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMemory {
+    pub used_bytes: u64,
+    pub peak_bytes: Option<u64>,
+    /// `None` without a memory limit
+    pub max_bytes: Option<u64>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueDepths {
+    pub upstream: UpstreamQueueStats,
+    pub analytics_events: usize,
+    pub pending_hooks: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCounts {
+    /// Within the SLO window
+    pub requests: usize,
+    pub errors: usize,
+    /// Only routes with errors, most errors first
+    pub routes: Vec<RouteSlo>,
+}
+
+pub async fn snapshot(cache: &dyn Cache) -> Dashboard {
+    Dashboard {
+        generated_at: Utc::now().timestamp_millis(),
+        chains: upstream_latency::report(),
+        upstream_services: failover::circuits(),
+        cache: cache_control::hit_stats(),
+        cache_memory: cache.info().await.as_deref().and_then(memory_usage),
+        queues: QueueDepths {
+            upstream: upstream_queue::stats(),
+            analytics_events: buffered_events(),
+            pending_hooks: pending_hooks(),
+        },
+        errors: error_counts(slo::report()),
+    }
+}
+
+/// Memory section of the output of the Redis `INFO` command
+pub fn memory_usage(info: &str) -> Option<CacheMemory> {
+    let field = |name: &str| {
+        info.lines()
+            .filter_map(|line| line.trim().strip_prefix(name))
+            .filter_map(|value| value.strip_prefix(':'))
+            .find_map(|value| value.parse::<u64>().ok())
+    };
+    Some(CacheMemory {
+        used_bytes: field("used_memory")?,
+        peak_bytes: field("used_memory_peak"),
+        max_bytes: field("maxmemory").filter(|max_bytes| *max_bytes > 0),
+    })
+}
+
+pub fn error_counts(report: Vec<RouteSlo>) -> ErrorCounts {
+    let requests = report.iter().map(|route| route.requests).sum();
+    let errors = report.iter().map(|route| route.errors).sum();
+    let mut routes: Vec<RouteSlo> = report
+        .into_iter()
+        .filter(|route| route.errors > 0)
+        .collect();
+    routes.sort_by(|left, right| right.errors.cmp(&left.errors));
+    ErrorCounts {
+        requests,
+        errors,
+        routes,
+    }
+}
@@ -1,4 +1,10 @@
+pub mod audit;
+pub mod dashboard;
 pub mod performance;
+pub mod schema_drift;
+pub mod slo;
+pub mod upstream_latency;
+pub mod usage;
 
 #[cfg(test)]
 mod tests;
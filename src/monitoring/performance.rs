@@ -1,8 +1,16 @@
 use crate::config;
+use crate::config::LogSampling;
+use crate::utils::trace_id::TraceId;
 use chrono::Utc;
+use lazy_static::lazy_static;
 use rocket::fairing::{Fairing, Info, Kind};
 use rocket::http::uri::Path;
 use rocket::{Data, Request, Response};
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref LOG_SAMPLING: HashMap<String, LogSampling> = config::log_sampling();
+}
 
 pub struct PerformanceMonitor();
 
@@ -20,34 +28,51 @@ impl Fairing for PerformanceMonitor {
     }
 
     async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
-        if rand::random::<f32>() <= config::log_threshold() {
+        let route = request
+            .route()
+            .map(|route| route.uri.to_string())
+            .unwrap_or(request.uri().path().to_string());
+        let status_code = response.status().code;
+        let rate = sample_rate(&LOG_SAMPLING, &route, status_code, config::log_threshold());
+        if rand::random::<f32>() <= rate {
             let request_path = request.uri().path();
 
             let chain_id = extract_chain_id(&request_path);
 
-            let route = request
-                .route()
-                .map(|route| route.uri.to_string())
-                .unwrap_or(request.uri().path().to_string());
             let cached = request
                 .local_cache(|| Utc::now().timestamp_millis())
                 .to_owned();
             let method = request.method().as_str();
-            let status_code = response.status().code;
             let delta = Utc::now().timestamp_millis() - cached;
             log::info!(
-                "MT::{}::{}::{}::{}::{}::{}",
+                "MT::{}::{}::{}::{}::{}::{}::{}",
                 method,
                 route,
                 delta,
                 status_code,
                 request.uri().to_string(), // full path with query params
-                chain_id
+                chain_id,
+                TraceId::of(request).unwrap_or_default()
             );
         }
     }
 }
 
+/// Share of the responses of `route` that are logged, `default` for routes without sampling
+pub(super) fn sample_rate(
+    sampling: &HashMap<String, LogSampling>,
+    route: &str,
+    status_code: u16,
+    default: f32,
+) -> f32 {
+    let path = route.split('?').next().unwrap_or(route);
+    match sampling.get(path) {
+        Some(sampling) if status_code >= 400 => sampling.error,
+        Some(sampling) => sampling.success,
+        None => default,
+    }
+}
+
 pub(super) fn extract_chain_id(path: &Path) -> String {
     let chain_id = path.segments().get(2);
     let contains_chains = path.segments().get(1).map_or(false, |it| it == "chains");
@@ -0,0 +1,321 @@
+use crate::config::feature_flag_schema_validation;
+use crate::utils::errors::ApiResult;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::de::value::StringDeserializer;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess};
+use serde::de::{IntoDeserializer, Visitor};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Mutex;
+
+// Name serde_json uses for RawValue fields, which must be handed over to serde_json as they are
+const RAW_VALUE_TOKEN: &str = "$serde_json::private::RawValue";
+
+lazy_static! {
+    static ref SCHEMA_DRIFT_COUNTS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+    static ref LOGGED_DRIFTS: Mutex<BTreeSet<(String, SchemaDrift)>> = Mutex::new(BTreeSet::new());
+    static ref MODULE_PATH: Regex = Regex::new(r"[a-z0-9_]+::").unwrap();
+}
+
+/// Difference between an upstream response and the model it is parsed into
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SchemaDrift {
+    /// Json path of a field the model doesn't know about. Array elements share the `[]` path and
+    /// entries of maps the `*` path.
+    UnknownField(String),
+    /// The response can't be parsed into the model, e.g. because of a missing field
+    Invalid(String),
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaDrift::UnknownField(path) => write!(f, "kind=unknown_field path={}", path),
+            SchemaDrift::Invalid(reason) => write!(f, "kind=invalid reason=\"{}\"", reason),
+        }
+    }
+}
+
+/// Same as `serde_json::from_str`. With `FEATURE_FLAG_SCHEMA_VALIDATION` the differences between
+/// the response and `T` are logged as warnings and counted per model, see [drift_counts].
+pub fn parse<T: DeserializeOwned>(body: &str) -> ApiResult<T> {
+    if !feature_flag_schema_validation() {
+        return Ok(serde_json::from_str(body)?);
+    }
+    let (result, drifts) = validate::<T>(body);
+    if !drifts.is_empty() {
+        let model = model_name::<T>();
+        // Every drift is counted, but only logged the first time as most responses of an endpoint
+        // share it
+        let mut logged = LOGGED_DRIFTS.lock().unwrap();
+        for drift in drifts.iter() {
+            if logged.insert((model.to_string(), drift.clone())) {
+                log::warn!("Schema drift model={} {}", model, drift);
+            }
+        }
+        *SCHEMA_DRIFT_COUNTS
+            .lock()
+            .unwrap()
+            .entry(model)
+            .or_default() += drifts.len() as u64;
+    }
+    Ok(result?)
+}
+
+/// Parses `body` into `T`, collecting the fields of every object parsed into a struct that are
+/// not fields of that struct. Values of enums are not inspected.
+pub fn validate<T: DeserializeOwned>(body: &str) -> (serde_json::Result<T>, Vec<SchemaDrift>) {
+    let value: Value = match serde_json::from_str(body) {
+        Ok(value) => value,
+        // Not json at all, not a change of the schema
+        Err(error) => return (Err(error), vec![]),
+    };
+    let drifts = RefCell::new(BTreeSet::new());
+    let result = T::deserialize(Tracked {
+        value,
+        path: String::from("$"),
+        drifts: &drifts,
+    });
+    let mut drifts: Vec<SchemaDrift> = drifts.into_inner().into_iter().collect();
+    if let Err(error) = &result {
+        drifts.push(SchemaDrift::Invalid(error.to_string()));
+    }
+    (result, drifts)
+}
+
+/// Amount of schema drifts seen by this instance, per model
+pub fn drift_counts() -> BTreeMap<String, u64> {
+    SCHEMA_DRIFT_COUNTS.lock().unwrap().clone()
+}
+
+/// Type name without module paths, e.g. `Page<MultisigTransaction>`
+fn model_name<T>() -> String {
+    MODULE_PATH
+        .replace_all(std::any::type_name::<T>(), "")
+        .to_string()
+}
+
+type Drifts = RefCell<BTreeSet<SchemaDrift>>;
+
+/// Deserializes a [Value] like serde_json does, keeping track of the json path
+struct Tracked<'a> {
+    value: Value,
+    path: String,
+    drifts: &'a Drifts,
+}
+
+impl<'a> Tracked<'a> {
+    fn visit<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+        is_struct: bool,
+    ) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Object(map) => visitor.visit_map(TrackedMap {
+                entries: map.into_iter(),
+                next_value: None,
+                path: self.path,
+                is_struct,
+                drifts: self.drifts,
+            }),
+            Value::Array(values) => visitor.visit_seq(TrackedSeq {
+                values: values.into_iter(),
+                path: format!("{}[]", self.path),
+                drifts: self.drifts,
+            }),
+            value => value.deserialize_any(visitor),
+        }
+    }
+}
+
+macro_rules! delegate_to_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+                self.value.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for Tracked<'a> {
+    type Error = serde_json::Error;
+
+    delegate_to_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string deserialize_bytes
+        deserialize_byte_buf deserialize_unit deserialize_identifier
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.visit(visitor, false)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.value.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        if name == RAW_VALUE_TOKEN {
+            self.value.deserialize_newtype_struct(name, visitor)
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.visit(visitor, false)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.visit(visitor, false)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.visit(visitor, false)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        self.visit(visitor, false)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        if let Value::Object(map) = &self.value {
+            let mut drifts = self.drifts.borrow_mut();
+            map.keys()
+                .filter(|key| !fields.contains(&key.as_str()))
+                .for_each(|key| {
+                    drifts.insert(SchemaDrift::UnknownField(format!("{}.{}", self.path, key)));
+                });
+        }
+        self.visit(visitor, true)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> serde_json::Result<V::Value> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> serde_json::Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+struct TrackedMap<'a> {
+    entries: serde_json::map::IntoIter,
+    next_value: Option<(String, Value)>,
+    path: String,
+    // Keys of other maps (e.g. balances per address) are not part of the schema
+    is_struct: bool,
+    drifts: &'a Drifts,
+}
+
+impl<'de, 'a> MapAccess<'de> for TrackedMap<'a> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> serde_json::Result<Option<K::Value>> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                let path = if self.is_struct {
+                    format!("{}.{}", self.path, key)
+                } else {
+                    format!("{}.*", self.path)
+                };
+                self.next_value = Some((path, value));
+                let key: StringDeserializer<serde_json::Error> = key.into_deserializer();
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> serde_json::Result<V::Value> {
+        let (path, value) = self
+            .next_value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(Tracked {
+            value,
+            path,
+            drifts: self.drifts,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct TrackedSeq<'a> {
+    values: std::vec::IntoIter<Value>,
+    path: String,
+    drifts: &'a Drifts,
+}
+
+impl<'de, 'a> SeqAccess<'de> for TrackedSeq<'a> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> serde_json::Result<Option<T::Value>> {
+        match self.values.next() {
+            Some(value) => seed
+                .deserialize(Tracked {
+                    value,
+                    path: self.path.to_string(),
+                    drifts: self.drifts,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
@@ -0,0 +1,279 @@
+use crate::config::{
+    slo_alert_webhook_uri, slo_availability_target, slo_burn_rate_threshold, slo_latency_target,
+    slo_min_requests, slo_window,
+};
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{HttpClient, Request};
+use chrono::Utc;
+use lazy_static::lazy_static;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Data, Response};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Bounds the memory used per route on busy routes, the oldest samples are dropped first
+const MAX_SAMPLES_PER_ROUTE: usize = 10000;
+
+lazy_static! {
+    static ref SLO_TRACKER: SloTracker = SloTracker::default();
+}
+
+/// Objectives every route is measured against. A request counts against the error budget if it
+/// fails with a server error or takes longer than the latency target.
+#[derive(Debug, Clone)]
+pub struct SloTargets {
+    /// Rolling window in ms
+    pub window: u64,
+    /// In ms
+    pub latency: u64,
+    /// Share of requests within the objectives, e.g. `0.99`
+    pub availability: f32,
+    /// Burn rate (error rate relative to the error budget) from which alerts are sent
+    pub burn_rate_threshold: f32,
+    /// Routes with fewer requests in the window never alert
+    pub min_requests: usize,
+}
+
+impl SloTargets {
+    pub fn from_config() -> Self {
+        SloTargets {
+            window: slo_window(),
+            latency: slo_latency_target(),
+            availability: slo_availability_target(),
+            burn_rate_threshold: slo_burn_rate_threshold(),
+            min_requests: slo_min_requests(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteSlo {
+    pub route: String,
+    pub requests: usize,
+    /// Requests that failed or missed the latency target
+    pub errors: usize,
+    pub p95_latency: u64,
+    pub error_rate: f32,
+    /// 1 when no budget was used, negative once it is exhausted
+    pub error_budget_remaining: f32,
+    pub burn_rate: f32,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SloAlert<'a> {
+    #[serde(flatten)]
+    route_slo: &'a RouteSlo,
+    burn_rate_threshold: f32,
+}
+
+struct Sample {
+    timestamp: i64,
+    latency: u64,
+    is_error: bool,
+}
+
+#[derive(Default)]
+struct RouteSamples {
+    samples: VecDeque<Sample>,
+    // So that an alert is sent once per crossing of the threshold, not for every request
+    alerting: bool,
+}
+
+impl RouteSamples {
+    fn expire(&mut self, now: i64, window: u64) {
+        let oldest = now - window as i64;
+        while self
+            .samples
+            .front()
+            .map_or(false, |sample| sample.timestamp < oldest)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    fn slo(&self, route: &str, targets: &SloTargets) -> RouteSlo {
+        let requests = self.samples.len();
+        let errors = self
+            .samples
+            .iter()
+            .filter(|sample| sample.is_error || sample.latency > targets.latency)
+            .count();
+        let error_rate = if requests == 0 {
+            0.0
+        } else {
+            errors as f32 / requests as f32
+        };
+        let error_budget = 1.0 - targets.availability;
+        let burn_rate = error_rate / error_budget;
+        RouteSlo {
+            route: route.to_string(),
+            requests,
+            errors,
+            p95_latency: self.p95_latency(),
+            error_rate,
+            error_budget_remaining: 1.0 - burn_rate,
+            burn_rate,
+        }
+    }
+
+    fn p95_latency(&self) -> u64 {
+        p95(self.samples.iter().map(|sample| sample.latency).collect())
+    }
+}
+
+/// 95th percentile of `latencies`, 0 without latencies
+pub(crate) fn p95(latencies: Vec<u64>) -> u64 {
+    percentile(latencies, 95)
+}
+
+/// `rank`th percentile of `latencies`, 0 without latencies
+pub(crate) fn percentile(mut latencies: Vec<u64>, rank: usize) -> u64 {
+    if latencies.is_empty() {
+        return 0;
+    }
+    latencies.sort_unstable();
+    // Nearest rank, rounded up
+    let index = (latencies.len() * rank + 99) / 100 - 1;
+    latencies[index]
+}
+
+/// Rolling latency and error budget per route
+#[derive(Default)]
+pub struct SloTracker {
+    routes: Mutex<HashMap<String, RouteSamples>>,
+}
+
+impl SloTracker {
+    /// Returns the state of the route if its burn rate crossed the alert threshold with this request
+    pub fn record(
+        &self,
+        route: &str,
+        latency: u64,
+        is_error: bool,
+        now: i64,
+        targets: &SloTargets,
+    ) -> Option<RouteSlo> {
+        let mut routes = self.routes.lock().unwrap();
+        let route_samples = routes.entry(route.to_string()).or_default();
+        route_samples.expire(now, targets.window);
+        if route_samples.samples.len() >= MAX_SAMPLES_PER_ROUTE {
+            route_samples.samples.pop_front();
+        }
+        route_samples.samples.push_back(Sample {
+            timestamp: now,
+            latency,
+            is_error,
+        });
+
+        let route_slo = route_samples.slo(route, targets);
+        let is_burning = route_slo.requests >= targets.min_requests
+            && route_slo.burn_rate >= targets.burn_rate_threshold;
+        let crossed = is_burning && !route_samples.alerting;
+        route_samples.alerting = is_burning;
+        if crossed {
+            Some(route_slo)
+        } else {
+            None
+        }
+    }
+
+    /// Routes with requests within the window, sorted by route
+    pub fn report(&self, now: i64, targets: &SloTargets) -> Vec<RouteSlo> {
+        let mut routes = self.routes.lock().unwrap();
+        let mut report: Vec<RouteSlo> = routes
+            .iter_mut()
+            .filter_map(|(route, route_samples)| {
+                route_samples.expire(now, targets.window);
+                if route_samples.samples.is_empty() {
+                    None
+                } else {
+                    Some(route_samples.slo(route, targets))
+                }
+            })
+            .collect();
+        report.sort_by(|left, right| left.route.cmp(&right.route));
+        report
+    }
+}
+
+/// State of every route served by this instance
+pub fn report() -> Vec<RouteSlo> {
+    SLO_TRACKER.report(Utc::now().timestamp_millis(), &SloTargets::from_config())
+}
+
+async fn send_alert(
+    http_client: Arc<dyn HttpClient>,
+    webhook_uri: String,
+    route_slo: RouteSlo,
+    burn_rate_threshold: f32,
+) -> ApiResult<()> {
+    let alert = SloAlert {
+        route_slo: &route_slo,
+        burn_rate_threshold,
+    };
+    let mut request = Request::new(webhook_uri);
+    request.body(Some(serde_json::to_string(&alert)?));
+    http_client.post(request).await?;
+    Ok(())
+}
+
+/// Feeds every routed request to the [SloTracker] and posts an alert to `SLO_ALERT_WEBHOOK_URI`
+/// when the error budget of a route burns faster than `SLO_BURN_RATE_THRESHOLD`
+pub struct SloMonitor();
+
+#[rocket::async_trait]
+impl Fairing for SloMonitor {
+    fn info(&self) -> Info {
+        Info {
+            name: "SloMonitor",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut rocket::Request<'_>, _data: &mut Data<'_>) {
+        request.local_cache(|| Utc::now().timestamp_millis());
+    }
+
+    async fn on_response<'r>(&self, request: &'r rocket::Request<'_>, response: &mut Response<'r>) {
+        // Unmatched paths are not tracked, they would add a route per path
+        let route = match request.route() {
+            Some(route) => format!("{} {}", request.method(), route.uri),
+            None => return,
+        };
+        let now = Utc::now().timestamp_millis();
+        let started = *request.local_cache(|| now);
+        let latency = (now - started).max(0) as u64;
+        let is_error = response.status().code >= 500;
+
+        let targets = SloTargets::from_config();
+        let route_slo = match SLO_TRACKER.record(&route, latency, is_error, now, &targets) {
+            Some(route_slo) => route_slo,
+            None => return,
+        };
+        log::warn!(
+            "Error budget of {} burning at {} times the allowed rate",
+            route_slo.route,
+            route_slo.burn_rate
+        );
+        let http_client = request.rocket().state::<Arc<dyn HttpClient>>();
+        if let (Some(webhook_uri), Some(http_client)) = (slo_alert_webhook_uri(), http_client) {
+            let http_client = http_client.clone();
+            rocket::tokio::spawn(async move {
+                let route = route_slo.route.to_string();
+                if let Err(error) = send_alert(
+                    http_client,
+                    webhook_uri,
+                    route_slo,
+                    targets.burn_rate_threshold,
+                )
+                .await
+                {
+                    log::error!("Could not send SLO alert for {}: {}", route, error);
+                }
+            });
+        }
+    }
+}
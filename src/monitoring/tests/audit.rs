@@ -0,0 +1,145 @@
+use crate::monitoring::audit::{
+    append_entry, newest_entries, payload_hash, rotated_path, AuditEntry, AuditOperation, Caller,
+};
+use crate::routes::transactions::models::requests::ConfirmationRequest;
+use crate::utils::errors::{ApiError, ApiResult};
+
+fn caller() -> Caller {
+    Caller {
+        ip: Some("127.0.0.1".to_string()),
+        user_agent: Some("safe-android".to_string()),
+    }
+}
+
+fn confirmation_request(signature: &str) -> ConfirmationRequest {
+    ConfirmationRequest {
+        signed_safe_tx_hash: signature.to_string(),
+    }
+}
+
+#[test]
+fn payload_hash_is_stable_per_payload() {
+    let hash = payload_hash(&confirmation_request("0x01"));
+
+    assert_eq!(66, hash.len());
+    assert_eq!(hash, payload_hash(&confirmation_request("0x01")));
+    assert_ne!(hash, payload_hash(&confirmation_request("0x02")));
+}
+
+#[test]
+fn audit_entry_success() {
+    let result: ApiResult<()> = Ok(());
+
+    let actual = AuditEntry::new(
+        AuditOperation::ConfirmTransaction,
+        "0x728cabc8b4ff6e1d1de2e5b2d2e2a9fa2ff2c6b4fd8e0c84e27b1f7d8a2dbbdd",
+        &caller(),
+        "0x1234".to_string(),
+        &result,
+    );
+
+    assert_eq!("CONFIRM_TRANSACTION", actual.operation);
+    assert_eq!(caller(), actual.caller);
+    assert_eq!("0x1234", actual.payload_hash);
+    assert_eq!(200, actual.status);
+}
+
+#[test]
+fn audit_entry_failure_keeps_status() {
+    let result: ApiResult<()> = Err(ApiError::new_from_message_with_code(
+        422,
+        String::from("Invalid signature"),
+    ));
+
+    let actual = AuditEntry::new(
+        AuditOperation::ProposeTransaction,
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        &caller(),
+        "0x1234".to_string(),
+        &result,
+    );
+
+    assert_eq!("PROPOSE_TRANSACTION", actual.operation);
+    assert_eq!(422, actual.status);
+}
+
+fn audit_log_path(name: &str) -> String {
+    let path = std::env::temp_dir()
+        .join(format!("audit_{}_{}.log", name, std::process::id()))
+        .to_str()
+        .unwrap()
+        .to_string();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(rotated_path(&path));
+    path
+}
+
+fn entry(operation: AuditOperation, target: &str) -> AuditEntry {
+    let result: ApiResult<()> = Ok(());
+    AuditEntry::new(operation, target, &caller(), "0x1234".to_string(), &result)
+}
+
+fn targets(entries: Vec<AuditEntry>) -> Vec<String> {
+    entries.into_iter().map(|entry| entry.target).collect()
+}
+
+#[test]
+fn newest_entries_reads_from_the_end() {
+    let path = audit_log_path("tail");
+    for index in 0..5 {
+        let operation = if index % 2 == 0 {
+            AuditOperation::ConfirmTransaction
+        } else {
+            AuditOperation::Flush
+        };
+        append_entry(&path, &entry(operation, &index.to_string()), 0).unwrap();
+    }
+
+    assert_eq!(
+        vec!["4", "3", "2"],
+        targets(newest_entries(&path, &None, 3))
+    );
+    assert_eq!(
+        vec!["4", "2", "0"],
+        targets(newest_entries(
+            &path,
+            &Some("confirm_transaction".to_string()),
+            10
+        ))
+    );
+}
+
+#[test]
+fn append_entry_rotates_at_max_size() {
+    let path = audit_log_path("rotation");
+    let line_size = serde_json::to_string(&entry(AuditOperation::Flush, "0"))
+        .unwrap()
+        .len() as u64
+        + 1;
+    for index in 0..5 {
+        append_entry(
+            &path,
+            &entry(AuditOperation::Flush, &index.to_string()),
+            2 * line_size,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(line_size, std::fs::metadata(&path).unwrap().len());
+    assert_eq!(
+        2 * line_size,
+        std::fs::metadata(rotated_path(&path)).unwrap().len()
+    );
+    // The oldest entry was dropped with the second rotation
+    assert_eq!(
+        vec!["4", "3", "2"],
+        targets(newest_entries(&path, &None, 10))
+    );
+}
+
+#[test]
+fn newest_entries_missing_file_is_empty() {
+    let path = audit_log_path("missing");
+
+    assert!(newest_entries(&path, &None, 10).is_empty());
+}
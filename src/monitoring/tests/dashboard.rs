@@ -0,0 +1,64 @@
+use crate::monitoring::dashboard::{error_counts, memory_usage, CacheMemory, ErrorCounts};
+use crate::monitoring::slo::RouteSlo;
+
+fn route_slo(route: &str, requests: usize, errors: usize) -> RouteSlo {
+    RouteSlo {
+        route: String::from(route),
+        requests,
+        errors,
+        p95_latency: 100,
+        error_rate: errors as f32 / requests as f32,
+        error_budget_remaining: 1.0,
+        burn_rate: 0.0,
+    }
+}
+
+#[test]
+fn memory_usage_from_redis_info() {
+    let info = "# Memory\r\nused_memory:1048576\r\nused_memory_human:1.00M\r\n\
+        used_memory_peak:2097152\r\nmaxmemory:0\r\nmaxmemory_policy:noeviction\r\n";
+
+    assert_eq!(
+        Some(CacheMemory {
+            used_bytes: 1048576,
+            peak_bytes: Some(2097152),
+            max_bytes: None,
+        }),
+        memory_usage(info)
+    );
+    assert_eq!(
+        Some(4194304),
+        memory_usage("used_memory:1048576\r\nmaxmemory:4194304\r\n")
+            .unwrap()
+            .max_bytes
+    );
+}
+
+#[test]
+fn memory_usage_without_memory_section() {
+    assert_eq!(None, memory_usage("# Memcached\r\ncurr_items:15\r\n"));
+    assert_eq!(None, memory_usage(""));
+}
+
+#[test]
+fn error_counts_only_lists_failing_routes() {
+    let report = vec![
+        route_slo("GET /about", 10, 0),
+        route_slo("GET /v1/chains/<chain_id>", 20, 1),
+        route_slo("GET /v1/chains/<chain_id>/safes/<safe_address>", 30, 6),
+    ];
+
+    let actual = error_counts(report);
+
+    assert_eq!(
+        ErrorCounts {
+            requests: 60,
+            errors: 7,
+            routes: vec![
+                route_slo("GET /v1/chains/<chain_id>/safes/<safe_address>", 30, 6),
+                route_slo("GET /v1/chains/<chain_id>", 20, 1),
+            ],
+        },
+        actual
+    );
+}
@@ -1 +1,8 @@
+mod audit;
+mod dashboard;
 mod path_patterns;
+mod performance;
+mod schema_drift;
+mod slo;
+mod upstream_latency;
+mod usage;
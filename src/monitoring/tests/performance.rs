@@ -0,0 +1,48 @@
+use crate::config::LogSampling;
+use crate::monitoring::performance::sample_rate;
+use std::collections::HashMap;
+
+const BALANCES_ROUTE: &str = "/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>";
+
+fn sampling() -> HashMap<String, LogSampling> {
+    let mut sampling = HashMap::new();
+    sampling.insert(
+        String::from(BALANCES_ROUTE),
+        LogSampling {
+            success: 0.01,
+            error: 1.0,
+        },
+    );
+    sampling
+}
+
+#[test]
+fn sample_rate_of_sampled_route_depends_on_status() {
+    let route = format!("{}?<trusted>&<exclude_spam>&<at_block>", BALANCES_ROUTE);
+
+    assert_eq!(sample_rate(&sampling(), &route, 200, 0.5), 0.01);
+    assert_eq!(sample_rate(&sampling(), &route, 304, 0.5), 0.01);
+    assert_eq!(sample_rate(&sampling(), &route, 404, 0.5), 1.0);
+    assert_eq!(sample_rate(&sampling(), &route, 500, 0.5), 1.0);
+}
+
+#[test]
+fn sample_rate_defaults_to_log_threshold() {
+    assert_eq!(
+        sample_rate(&sampling(), "/v1/chains/<chain_id>", 200, 0.5),
+        0.5
+    );
+}
+
+#[test]
+fn log_sampling_error_rate_defaults_to_all() {
+    let sampling: LogSampling = serde_json::from_str(r#"{"success": 0.1}"#).unwrap();
+
+    assert_eq!(
+        sampling,
+        LogSampling {
+            success: 0.1,
+            error: 1.0,
+        }
+    );
+}
@@ -0,0 +1,74 @@
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::common::models::page::Page;
+use crate::monitoring::schema_drift::{validate, SchemaDrift};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Owner {
+    address: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Safe {
+    nonce: u64,
+    guard: Option<String>,
+    owners: Vec<Owner>,
+    balances: HashMap<String, Owner>,
+}
+
+#[test]
+fn validate_reports_unknown_fields_by_path() {
+    let body = r#"{
+        "nonce": 1,
+        "guard": null,
+        "fallbackHandler": "0x1",
+        "owners": [{ "address": "0x2", "ens": "alice.eth" }, { "address": "0x3", "ens": null }],
+        "balances": { "0x4": { "address": "0x4", "decimals": 18 } }
+    }"#;
+
+    let (result, drifts) = validate::<Safe>(body);
+
+    assert_eq!(result.unwrap().nonce, 1);
+    assert_eq!(
+        drifts,
+        vec![
+            SchemaDrift::UnknownField(String::from("$.balances.*.decimals")),
+            SchemaDrift::UnknownField(String::from("$.fallbackHandler")),
+            SchemaDrift::UnknownField(String::from("$.owners[].ens")),
+        ]
+    );
+}
+
+#[test]
+fn validate_reports_missing_fields() {
+    let body = r#"{ "guard": "0x1", "owners": [], "balances": {} }"#;
+
+    let (result, drifts) = validate::<Safe>(body);
+
+    assert!(result.is_err());
+    assert_eq!(
+        drifts,
+        vec![SchemaDrift::Invalid(String::from("missing field `nonce`"))]
+    );
+}
+
+#[test]
+fn validate_parses_like_serde_json() {
+    let body = crate::tests::json::BACKEND_QUEUED_TRANSACTION_LIST_PAGE_CONFLICT_393;
+
+    let (result, _) = validate::<Page<MultisigTransaction>>(body);
+
+    let expected = serde_json::from_str::<Page<MultisigTransaction>>(body).unwrap();
+    assert_eq!(result.unwrap().results, expected.results);
+}
+
+#[test]
+fn validate_skips_invalid_json() {
+    let (result, drifts) = validate::<Safe>("<html>Bad Gateway</html>");
+
+    assert!(result.is_err());
+    assert!(drifts.is_empty());
+}
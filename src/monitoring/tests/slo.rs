@@ -0,0 +1,84 @@
+use crate::monitoring::slo::{RouteSlo, SloTargets, SloTracker};
+
+const ROUTE: &str = "GET /v1/chains/<chain_id>/safes/<safe_address>";
+
+fn targets(min_requests: usize) -> SloTargets {
+    SloTargets {
+        window: 60000,
+        latency: 1000,
+        availability: 0.5,
+        burn_rate_threshold: 1.0,
+        min_requests,
+    }
+}
+
+#[test]
+fn report_tracks_p95_latency_and_error_budget() {
+    let tracker = SloTracker::default();
+    let targets = targets(100);
+    for latency in 1..=20 {
+        tracker.record(ROUTE, latency, latency % 4 == 0, 1000, &targets);
+    }
+
+    let actual = tracker.report(1000, &targets);
+
+    assert_eq!(
+        actual,
+        vec![RouteSlo {
+            route: String::from(ROUTE),
+            requests: 20,
+            errors: 5,
+            p95_latency: 19,
+            error_rate: 0.25,
+            error_budget_remaining: 0.5,
+            burn_rate: 0.5,
+        }]
+    );
+}
+
+#[test]
+fn slow_requests_use_error_budget() {
+    let tracker = SloTracker::default();
+    let targets = targets(100);
+    tracker.record(ROUTE, 200, false, 1000, &targets);
+    tracker.record(ROUTE, 1500, false, 1000, &targets);
+
+    let actual = tracker.report(1000, &targets);
+
+    assert_eq!(actual[0].errors, 1);
+    assert_eq!(actual[0].p95_latency, 1500);
+}
+
+#[test]
+fn alert_sent_once_per_threshold_crossing() {
+    let tracker = SloTracker::default();
+    let targets = targets(4);
+
+    // Not enough requests yet
+    for _ in 0..3 {
+        assert!(tracker.record(ROUTE, 10, true, 1000, &targets).is_none());
+    }
+    let alert = tracker.record(ROUTE, 10, true, 1000, &targets);
+    assert_eq!(alert.unwrap().burn_rate, 2.0);
+    assert!(tracker.record(ROUTE, 10, true, 1000, &targets).is_none());
+
+    // Error rate back under the budget after 6 successes (5 errors out of 11 requests)
+    for _ in 0..6 {
+        assert!(tracker.record(ROUTE, 10, false, 1000, &targets).is_none());
+    }
+    let alert = tracker.record(ROUTE, 10, true, 1000, &targets);
+    assert_eq!(alert.unwrap().requests, 12);
+}
+
+#[test]
+fn samples_expire_after_window() {
+    let tracker = SloTracker::default();
+    let targets = targets(1);
+    tracker.record(ROUTE, 10, true, 1000, &targets);
+    tracker.record("GET /about", 10, false, 30000, &targets);
+
+    let actual = tracker.report(61001, &targets);
+
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].route, "GET /about");
+}
@@ -0,0 +1,154 @@
+use crate::monitoring::upstream_latency::{
+    chain_of_path, ChainUpstreamLatency, TimeoutBounds, UpstreamLatencyTracker,
+};
+
+const HOST: &str = "safe-transaction.rinkeby.gnosis.io";
+const OTHER_HOST: &str = "safe-config.gnosis.io";
+
+fn bounds() -> TimeoutBounds {
+    TimeoutBounds {
+        min: 2000,
+        max: 30000,
+        factor: 3.0,
+        min_samples: 10,
+    }
+}
+
+#[test]
+fn report_tracks_latency_per_chain() {
+    let tracker = UpstreamLatencyTracker::default();
+    for latency in 1..=20 {
+        tracker.record("4", Some(HOST), latency * 10, latency % 5 == 0, 1000, 60000);
+    }
+    tracker.record("1", Some(HOST), 300, false, 1000, 60000);
+
+    let actual = tracker.report(1000, 60000);
+
+    assert_eq!(
+        actual,
+        vec![
+            ChainUpstreamLatency {
+                chain_id: String::from("1"),
+                calls: 1,
+                errors: 0,
+                average_latency: 300,
+                p95_latency: 300,
+            },
+            ChainUpstreamLatency {
+                chain_id: String::from("4"),
+                calls: 20,
+                errors: 4,
+                average_latency: 105,
+                p95_latency: 190,
+            },
+        ]
+    );
+}
+
+#[test]
+fn report_drops_calls_outside_the_window() {
+    let tracker = UpstreamLatencyTracker::default();
+    tracker.record("4", Some(HOST), 100, false, 1000, 60000);
+    tracker.record("1", Some(HOST), 200, true, 50000, 60000);
+
+    let actual = tracker.report(70000, 60000);
+
+    assert_eq!(1, actual.len());
+    assert_eq!("1", actual[0].chain_id);
+}
+
+#[test]
+fn chain_of_chain_scoped_paths() {
+    assert_eq!(
+        Some(String::from("4")),
+        chain_of_path("/v1/chains/4/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b")
+    );
+    assert_eq!(Some(String::from("137")), chain_of_path("/v1/chains/137"));
+    assert_eq!(None, chain_of_path("/v1/chains/"));
+    assert_eq!(None, chain_of_path("/v1/chains"));
+    assert_eq!(None, chain_of_path("/about/"));
+}
+
+#[test]
+fn timeout_for_stays_within_bounds() {
+    assert_eq!(bounds().timeout_for(100), 2000);
+    assert_eq!(bounds().timeout_for(1500), 4500);
+    assert_eq!(bounds().timeout_for(20000), 30000);
+}
+
+#[test]
+fn p99_latency_requires_min_samples() {
+    let tracker = UpstreamLatencyTracker::default();
+    for latency in 1..=100 {
+        tracker.record("4", Some(HOST), latency * 10, false, 1000, 60000);
+    }
+    tracker.record("4", Some(OTHER_HOST), 300, false, 1000, 60000);
+
+    assert_eq!(tracker.p99_latency(HOST, 1000, 60000, 10), Some(990));
+    assert_eq!(tracker.p99_latency(OTHER_HOST, 1000, 60000, 10), None);
+    assert_eq!(
+        tracker.p99_latency("unknown.gnosis.io", 1000, 60000, 10),
+        None
+    );
+}
+
+#[test]
+fn adaptive_timeout_is_derived_again_after_refresh() {
+    let tracker = UpstreamLatencyTracker::default();
+    for _ in 0..10 {
+        tracker.record("4", Some(HOST), 1000, false, 1000, 60000);
+    }
+    assert_eq!(
+        tracker.adaptive_timeout(HOST, 1000, 60000, &bounds()),
+        Some(3000)
+    );
+
+    for _ in 0..90 {
+        tracker.record("4", Some(HOST), 5000, false, 2000, 60000);
+    }
+    assert_eq!(
+        tracker.adaptive_timeout(HOST, 2000, 60000, &bounds()),
+        Some(3000)
+    );
+    assert_eq!(
+        tracker.adaptive_timeout(HOST, 7000, 60000, &bounds()),
+        Some(15000)
+    );
+}
+
+#[test]
+fn adaptive_timeout_without_enough_calls() {
+    let tracker = UpstreamLatencyTracker::default();
+    tracker.record("4", Some(HOST), 1000, false, 1000, 60000);
+
+    assert_eq!(tracker.adaptive_timeout(HOST, 1000, 60000, &bounds()), None);
+}
+
+#[test]
+fn adaptive_timeout_is_derived_per_host() {
+    let tracker = UpstreamLatencyTracker::default();
+    for _ in 0..10 {
+        tracker.record("4", Some(HOST), 5000, false, 1000, 60000);
+        tracker.record("4", Some(OTHER_HOST), 100, false, 1000, 60000);
+    }
+
+    assert_eq!(
+        tracker.adaptive_timeout(HOST, 1000, 60000, &bounds()),
+        Some(15000)
+    );
+    assert_eq!(
+        tracker.adaptive_timeout(OTHER_HOST, 1000, 60000, &bounds()),
+        Some(2000)
+    );
+}
+
+#[test]
+fn calls_without_host_are_only_reported_for_the_chain() {
+    let tracker = UpstreamLatencyTracker::default();
+    for _ in 0..10 {
+        tracker.record("4", None, 1000, false, 1000, 60000);
+    }
+
+    assert_eq!(tracker.report(1000, 60000)[0].calls, 10);
+    assert_eq!(tracker.p99_latency(HOST, 1000, 60000, 1), None);
+}
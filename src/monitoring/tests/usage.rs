@@ -0,0 +1,158 @@
+use crate::cache::MockCache;
+use crate::monitoring::usage::{
+    record, safe_of_route, safe_usage, top_consumers, SafeRequests, UsageBucket, UsageWindow,
+};
+use mockall::predicate::eq;
+use std::collections::HashMap;
+
+const SAFE_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+fn usage_window() -> UsageWindow {
+    UsageWindow {
+        window: 3000,
+        bucket: 1000,
+    }
+}
+
+#[test]
+fn safe_of_route_reads_route_params() {
+    let actual = safe_of_route(
+        "/v1/chains/<chain_id>/safes/<safe_address>/collectibles?<trusted>&<exclude_spam>",
+        "/v1/chains/4/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/collectibles",
+    );
+
+    assert_eq!(
+        actual,
+        Some((String::from("4"), String::from(SAFE_ADDRESS)))
+    );
+}
+
+#[test]
+fn safe_of_route_ignores_routes_without_safe() {
+    assert_eq!(
+        safe_of_route(
+            "/v1/chains/<chain_id>/owners/<owner_address>/safes",
+            "/v1/chains/4/owners/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/safes",
+        ),
+        None
+    );
+    assert_eq!(
+        safe_of_route("/v1/chains/<chain_id>/safes/<safe_address>", "/v1/chains/4"),
+        None
+    );
+}
+
+#[test]
+fn bucket_starts_cover_window() {
+    assert_eq!(usage_window().bucket_starts(5500), vec![3000, 4000, 5000]);
+    assert_eq!(
+        UsageWindow {
+            window: 2500,
+            bucket: 1000,
+        }
+        .bucket_starts(5000),
+        vec![3000, 4000, 5000]
+    );
+}
+
+#[rocket::async_test]
+async fn record_increments_current_bucket() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_increment_in_hash()
+        .with(
+            eq("usage_5000"),
+            eq("4_0x1230b3d59858296a31053c1b8562ecf89a2f888b"),
+            eq(4000),
+        )
+        .times(1)
+        .return_const(1usize);
+
+    record(&mock_cache, "4", SAFE_ADDRESS, 5500, &usage_window()).await;
+}
+
+#[rocket::async_test]
+async fn safe_usage_sums_buckets() {
+    let field = "4_0x1230b3d59858296a31053c1b8562ecf89a2f888b";
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("usage_3000"), eq(field))
+        .times(1)
+        .return_const(Some(String::from("2")));
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("usage_4000"), eq(field))
+        .times(1)
+        .return_const(None);
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("usage_5000"), eq(field))
+        .times(1)
+        .return_const(Some(String::from("5")));
+
+    let actual = safe_usage(&mock_cache, "4", SAFE_ADDRESS, 5500, &usage_window()).await;
+
+    assert_eq!(actual.since, 3000);
+    assert_eq!(actual.requests, 7);
+    assert_eq!(
+        actual.buckets,
+        vec![
+            UsageBucket {
+                start: 3000,
+                requests: 2,
+            },
+            UsageBucket {
+                start: 4000,
+                requests: 0,
+            },
+            UsageBucket {
+                start: 5000,
+                requests: 5,
+            },
+        ]
+    );
+}
+
+#[rocket::async_test]
+async fn top_consumers_sorted_by_requests_within_window() {
+    let bucket = |entries: &[(&str, &str)]| -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(field, count)| (field.to_string(), count.to_string()))
+            .collect()
+    };
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch_hash()
+        .with(eq("usage_3000"))
+        .times(1)
+        .return_const(bucket(&[("4_0xa", "3"), ("1_0xb", "1")]));
+    mock_cache
+        .expect_fetch_hash()
+        .with(eq("usage_4000"))
+        .times(1)
+        .return_const(bucket(&[("1_0xb", "1"), ("1_0xc", "2")]));
+    mock_cache
+        .expect_fetch_hash()
+        .with(eq("usage_5000"))
+        .times(1)
+        .return_const(bucket(&[("1_0xb", "1"), ("137_0xd", "1")]));
+
+    let actual = top_consumers(&mock_cache, 5500, &usage_window(), 3).await;
+
+    let consumer = |chain_id: &str, safe_address: &str, requests: usize| SafeRequests {
+        chain_id: chain_id.to_string(),
+        safe_address: safe_address.to_string(),
+        requests,
+    };
+    assert_eq!(actual.since, 3000);
+    assert_eq!(
+        actual.consumers,
+        vec![
+            consumer("1", "0xb", 3),
+            consumer("4", "0xa", 3),
+            consumer("1", "0xc", 2),
+        ]
+    );
+}
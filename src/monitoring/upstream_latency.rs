@@ -0,0 +1,346 @@
+//! Rolling latency of the upstream calls made while serving the routes of each chain
+//! (`/v1/chains/<chain_id>/...`), over the last `SLO_WINDOW` ms.
+//!
+//! With `FEATURE_FLAG_ADAPTIVE_TIMEOUTS` the default timeout of those calls is derived from the
+//! latency of the upstream host: its p99 times `ADAPTIVE_TIMEOUT_FACTOR`, within
+//! `ADAPTIVE_TIMEOUT_MIN` and `ADAPTIVE_TIMEOUT_MAX`, so that consistently slow upstreams don't fail
+//! spuriously and fast upstreams fail fast. Hosts with fewer than `ADAPTIVE_TIMEOUT_MIN_SAMPLES`
+//! calls keep the default timeout, calls with an explicit timeout always keep theirs.
+use crate::config::{
+    adaptive_timeout_factor, adaptive_timeout_max, adaptive_timeout_min,
+    adaptive_timeout_min_samples, feature_flag_adaptive_timeouts, slo_window,
+};
+use crate::monitoring::slo::{p95, percentile};
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{BinaryResponse, HttpClient, Request, Response};
+use crate::utils::upstream_queue;
+use chrono::Utc;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Bounds the memory used per chain and per host on busy chains, the oldest samples are dropped
+/// first
+const MAX_SAMPLES: usize = 10000;
+/// How long (in ms) an adaptive timeout is used before it is derived again, so that the
+/// percentile isn't computed for every call
+const ADAPTIVE_TIMEOUT_REFRESH: i64 = 5000;
+
+lazy_static! {
+    static ref UPSTREAM_LATENCY: Arc<UpstreamLatencyTracker> =
+        Arc::new(UpstreamLatencyTracker::default());
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainUpstreamLatency {
+    pub chain_id: String,
+    pub calls: usize,
+    /// Calls failing with a server error or timing out
+    pub errors: usize,
+    pub average_latency: u64,
+    pub p95_latency: u64,
+}
+
+struct Sample {
+    timestamp: i64,
+    latency: u64,
+    is_error: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeoutBounds {
+    /// In ms
+    pub min: u64,
+    /// In ms
+    pub max: u64,
+    pub factor: f32,
+    pub min_samples: usize,
+}
+
+impl TimeoutBounds {
+    /// `None` unless adaptive timeouts are enabled
+    pub fn configured() -> Option<Self> {
+        if !feature_flag_adaptive_timeouts() {
+            return None;
+        }
+        Some(TimeoutBounds {
+            min: adaptive_timeout_min(),
+            max: adaptive_timeout_max(),
+            factor: adaptive_timeout_factor(),
+            min_samples: adaptive_timeout_min_samples(),
+        })
+    }
+
+    /// Timeout (in ms) for calls with a p99 latency of `p99_latency`
+    pub fn timeout_for(&self, p99_latency: u64) -> u64 {
+        let timeout = (p99_latency as f64 * self.factor as f64).ceil() as u64;
+        timeout.max(self.min).min(self.max)
+    }
+}
+
+struct AdaptiveTimeout {
+    derived_at: i64,
+    timeout: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct UpstreamLatencyTracker {
+    chains: Mutex<HashMap<String, VecDeque<Sample>>>,
+    hosts: Mutex<HashMap<String, VecDeque<Sample>>>,
+    timeouts: Mutex<HashMap<String, AdaptiveTimeout>>,
+}
+
+impl UpstreamLatencyTracker {
+    /// Call of `host` (`None` if the url has none) made while serving a route of the chain
+    pub fn record(
+        &self,
+        chain_id: &str,
+        host: Option<&str>,
+        latency: u64,
+        is_error: bool,
+        now: i64,
+        window: u64,
+    ) {
+        let sample = || Sample {
+            timestamp: now,
+            latency,
+            is_error,
+        };
+        push(&self.chains, chain_id, sample(), now, window);
+        if let Some(host) = host {
+            push(&self.hosts, host, sample(), now, window);
+        }
+    }
+
+    /// Chains with calls within the window, sorted by chain id
+    pub fn report(&self, now: i64, window: u64) -> Vec<ChainUpstreamLatency> {
+        let mut chains = self.chains.lock().unwrap();
+        let mut report: Vec<ChainUpstreamLatency> = chains
+            .iter_mut()
+            .filter_map(|(chain_id, samples)| {
+                expire(samples, now, window);
+                if samples.is_empty() {
+                    return None;
+                }
+                let latencies: Vec<u64> = samples.iter().map(|sample| sample.latency).collect();
+                Some(ChainUpstreamLatency {
+                    chain_id: chain_id.to_string(),
+                    calls: samples.len(),
+                    errors: samples.iter().filter(|sample| sample.is_error).count(),
+                    average_latency: latencies.iter().sum::<u64>() / latencies.len() as u64,
+                    p95_latency: p95(latencies),
+                })
+            })
+            .collect();
+        report.sort_by(|left, right| left.chain_id.cmp(&right.chain_id));
+        report
+    }
+
+    /// p99 latency of the calls of the host within the window, `None` with fewer than
+    /// `min_samples` calls
+    pub fn p99_latency(
+        &self,
+        host: &str,
+        now: i64,
+        window: u64,
+        min_samples: usize,
+    ) -> Option<u64> {
+        let mut hosts = self.hosts.lock().unwrap();
+        let samples = hosts.get_mut(host)?;
+        expire(samples, now, window);
+        if samples.len() < min_samples.max(1) {
+            return None;
+        }
+        Some(percentile(
+            samples.iter().map(|sample| sample.latency).collect(),
+            99,
+        ))
+    }
+
+    /// Default timeout (in ms) of the calls of the host, derived again every
+    /// `ADAPTIVE_TIMEOUT_REFRESH` ms. `None` if the host keeps the `DEFAULT_REQUEST_TIMEOUT`.
+    pub fn adaptive_timeout(
+        &self,
+        host: &str,
+        now: i64,
+        window: u64,
+        bounds: &TimeoutBounds,
+    ) -> Option<u64> {
+        if let Some(adaptive) = self.timeouts.lock().unwrap().get(host) {
+            if now - adaptive.derived_at < ADAPTIVE_TIMEOUT_REFRESH {
+                return adaptive.timeout;
+            }
+        }
+        let timeout = self
+            .p99_latency(host, now, window, bounds.min_samples)
+            .map(|p99_latency| bounds.timeout_for(p99_latency));
+        self.timeouts.lock().unwrap().insert(
+            host.to_string(),
+            AdaptiveTimeout {
+                derived_at: now,
+                timeout,
+            },
+        );
+        timeout
+    }
+}
+
+fn push(
+    samples_by_key: &Mutex<HashMap<String, VecDeque<Sample>>>,
+    key: &str,
+    sample: Sample,
+    now: i64,
+    window: u64,
+) {
+    let mut samples_by_key = samples_by_key.lock().unwrap();
+    let samples = samples_by_key.entry(key.to_string()).or_default();
+    expire(samples, now, window);
+    if samples.len() >= MAX_SAMPLES {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+fn expire(samples: &mut VecDeque<Sample>, now: i64, window: u64) {
+    let oldest = now - window as i64;
+    while samples
+        .front()
+        .map_or(false, |sample| sample.timestamp < oldest)
+    {
+        samples.pop_front();
+    }
+}
+
+/// State of every chain served by this instance
+pub fn report() -> Vec<ChainUpstreamLatency> {
+    UPSTREAM_LATENCY.report(Utc::now().timestamp_millis(), slo_window())
+}
+
+/// `<chain_id>` of `/v1/chains/<chain_id>/...` paths
+pub fn chain_of_path(path: &str) -> Option<String> {
+    let mut segments = path.trim_start_matches('/').split('/');
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some("v1"), Some("chains"), Some(chain_id)) if !chain_id.is_empty() => {
+            Some(chain_id.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// `http_client` recording the latency of its calls for `chain_id`
+pub fn tracked(http_client: Arc<dyn HttpClient>, chain_id: String) -> Arc<dyn HttpClient> {
+    Arc::new(LatencyTrackedHttpClient::new(
+        http_client,
+        chain_id,
+        UPSTREAM_LATENCY.clone(),
+    ))
+}
+
+pub struct LatencyTrackedHttpClient {
+    http_client: Arc<dyn HttpClient>,
+    chain_id: String,
+    tracker: Arc<UpstreamLatencyTracker>,
+}
+
+impl LatencyTrackedHttpClient {
+    pub fn new(
+        http_client: Arc<dyn HttpClient>,
+        chain_id: String,
+        tracker: Arc<UpstreamLatencyTracker>,
+    ) -> Self {
+        LatencyTrackedHttpClient {
+            http_client,
+            chain_id,
+            tracker,
+        }
+    }
+
+    // Explicit timeouts are set for the call (e.g. relays or token info), they are kept
+    fn with_adaptive_timeout(&self, mut request: Request, host: Option<&str>) -> Request {
+        if request.has_explicit_timeout() {
+            return request;
+        }
+        if let (Some(bounds), Some(host)) = (TimeoutBounds::configured(), host) {
+            let timeout = self.tracker.adaptive_timeout(
+                host,
+                Utc::now().timestamp_millis(),
+                slo_window(),
+                &bounds,
+            );
+            if let Some(timeout) = timeout {
+                request.timeout(Duration::from_millis(timeout));
+            }
+        }
+        request
+    }
+
+    fn record<T>(&self, host: Option<&str>, started: Instant, result: &ApiResult<T>) {
+        // Calls shed by the upstream queue never reached the upstream
+        let is_error = match result {
+            Ok(_) => false,
+            Err(error) if upstream_queue::is_shed_error(error) => return,
+            Err(error) => error.status >= 500,
+        };
+        self.tracker.record(
+            &self.chain_id,
+            host,
+            started.elapsed().as_millis() as u64,
+            is_error,
+            Utc::now().timestamp_millis(),
+            slo_window(),
+        );
+    }
+}
+
+#[rocket::async_trait]
+impl HttpClient for LatencyTrackedHttpClient {
+    async fn get(&self, request: Request) -> ApiResult<Response> {
+        let host = request.host();
+        let started = Instant::now();
+        let result = self
+            .http_client
+            .get(self.with_adaptive_timeout(request, host.as_deref()))
+            .await;
+        self.record(host.as_deref(), started, &result);
+        result
+    }
+
+    async fn post(&self, request: Request) -> ApiResult<Response> {
+        let host = request.host();
+        let started = Instant::now();
+        let result = self
+            .http_client
+            .post(self.with_adaptive_timeout(request, host.as_deref()))
+            .await;
+        self.record(host.as_deref(), started, &result);
+        result
+    }
+
+    async fn delete(&self, request: Request) -> ApiResult<Response> {
+        let host = request.host();
+        let started = Instant::now();
+        let result = self
+            .http_client
+            .delete(self.with_adaptive_timeout(request, host.as_deref()))
+            .await;
+        self.record(host.as_deref(), started, &result);
+        result
+    }
+
+    async fn get_binary(&self, request: Request, max_size: usize) -> ApiResult<BinaryResponse> {
+        let host = request.host();
+        let started = Instant::now();
+        let result = self
+            .http_client
+            .get_binary(
+                self.with_adaptive_timeout(request, host.as_deref()),
+                max_size,
+            )
+            .await;
+        self.record(host.as_deref(), started, &result);
+        result
+    }
+}
@@ -0,0 +1,240 @@
+//! Requests per Safe over a rolling window, so that operators can spot integrations hammering
+//! single Safes. Requests are counted in Redis (shared by every instance) in one hash per bucket
+//! of `USAGE_BUCKET` ms, with a field per Safe. Buckets expire once they leave the window.
+use crate::cache::Cache;
+use crate::config::{feature_flag_usage_tracking, usage_bucket, usage_window};
+use crate::utils::chain_hosts::matches_template;
+use chrono::Utc;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Request, Response};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const USAGE_KEY_BASE: &str = "usage";
+const CHAIN_ID_PARAM: &str = "<chain_id>";
+const SAFE_ADDRESS_PARAM: &str = "<safe_address>";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageWindow {
+    /// Rolling window in ms
+    pub window: u64,
+    /// Bucket length in ms
+    pub bucket: u64,
+}
+
+impl UsageWindow {
+    pub fn from_config() -> Self {
+        UsageWindow {
+            window: usage_window(),
+            bucket: usage_bucket(),
+        }
+    }
+
+    /// Start of every bucket within the window ending at `now`, oldest first
+    pub fn bucket_starts(&self, now: i64) -> Vec<i64> {
+        let bucket = self.bucket.max(1) as i64;
+        let current = now - now.rem_euclid(bucket);
+        let buckets = ((self.window as i64 + bucket - 1) / bucket).max(1);
+        (0..buckets)
+            .rev()
+            .map(|index| current - index * bucket)
+            .collect()
+    }
+
+    /// Buckets outlive the window by one bucket, so that the oldest one is complete
+    fn bucket_timeout(&self) -> usize {
+        (self.window + self.bucket) as usize
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageBucket {
+    pub start: i64,
+    pub requests: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeUsage {
+    pub chain_id: String,
+    pub safe_address: String,
+    /// Start of the oldest bucket of the window
+    pub since: i64,
+    pub requests: usize,
+    /// Requests per bucket, oldest first
+    pub buckets: Vec<UsageBucket>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeRequests {
+    pub chain_id: String,
+    pub safe_address: String,
+    pub requests: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    pub since: i64,
+    /// Safes with the most requests within the window, most requests first
+    pub consumers: Vec<SafeRequests>,
+}
+
+fn bucket_key(bucket_start: i64) -> String {
+    format!("{}_{}", USAGE_KEY_BASE, bucket_start)
+}
+
+// Chain ids don't contain `_`, so the field can be split on the first one
+fn safe_field(chain_id: &str, safe_address: &str) -> String {
+    format!("{}_{}", chain_id, safe_address.to_lowercase())
+}
+
+/// Chain id and Safe address of `path`, if it is served by a route `template` taking both
+pub fn safe_of_route(template: &str, path: &str) -> Option<(String, String)> {
+    if !matches_template(template, path) {
+        return None;
+    }
+    let template_path = template.split('?').next().unwrap_or("");
+    let template_segments = template_path
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let path_segments = path.split('/').filter(|segment| !segment.is_empty());
+    let mut chain_id = None;
+    let mut safe_address = None;
+    for (template_segment, path_segment) in template_segments.zip(path_segments) {
+        match template_segment {
+            CHAIN_ID_PARAM => chain_id = Some(path_segment.to_string()),
+            SAFE_ADDRESS_PARAM => safe_address = Some(path_segment.to_string()),
+            _ => {}
+        }
+    }
+    Some((chain_id?, safe_address?))
+}
+
+pub async fn record(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe_address: &str,
+    now: i64,
+    usage_window: &UsageWindow,
+) {
+    let bucket_start = *usage_window.bucket_starts(now).last().unwrap();
+    cache
+        .increment_in_hash(
+            &bucket_key(bucket_start),
+            &safe_field(chain_id, safe_address),
+            usage_window.bucket_timeout(),
+        )
+        .await;
+}
+
+pub async fn safe_usage(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe_address: &str,
+    now: i64,
+    usage_window: &UsageWindow,
+) -> SafeUsage {
+    let field = safe_field(chain_id, safe_address);
+    let bucket_starts = usage_window.bucket_starts(now);
+    let mut buckets: Vec<UsageBucket> = vec![];
+    for start in bucket_starts.iter() {
+        buckets.push(UsageBucket {
+            start: *start,
+            requests: cache
+                .get_from_hash(&bucket_key(*start), &field)
+                .await
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(0),
+        });
+    }
+    SafeUsage {
+        chain_id: chain_id.to_string(),
+        safe_address: safe_address.to_string(),
+        since: bucket_starts[0],
+        requests: buckets.iter().map(|bucket| bucket.requests).sum(),
+        buckets,
+    }
+}
+
+/// The `limit` Safes with the most requests within the window. Ties are sorted by chain id and
+/// address, so that the report is stable.
+pub async fn top_consumers(
+    cache: &dyn Cache,
+    now: i64,
+    usage_window: &UsageWindow,
+    limit: usize,
+) -> UsageReport {
+    let bucket_starts = usage_window.bucket_starts(now);
+    let mut totals: HashMap<String, usize> = HashMap::new();
+    for start in bucket_starts.iter() {
+        for (field, count) in cache.fetch_hash(&bucket_key(*start)).await {
+            *totals.entry(field).or_default() += count.parse::<usize>().unwrap_or(0);
+        }
+    }
+    let mut consumers: Vec<SafeRequests> = totals
+        .into_iter()
+        .filter_map(|(field, requests)| {
+            let mut parts = field.splitn(2, '_');
+            Some(SafeRequests {
+                chain_id: parts.next()?.to_string(),
+                safe_address: parts.next()?.to_string(),
+                requests,
+            })
+        })
+        .collect();
+    consumers.sort_by(|left, right| {
+        right
+            .requests
+            .cmp(&left.requests)
+            .then_with(|| left.chain_id.cmp(&right.chain_id))
+            .then_with(|| left.safe_address.cmp(&right.safe_address))
+    });
+    consumers.truncate(limit);
+    UsageReport {
+        since: bucket_starts[0],
+        consumers,
+    }
+}
+
+/// Counts every response of a route taking a `<chain_id>` and a `<safe_address>` against that
+/// Safe, if `FEATURE_FLAG_USAGE_TRACKING` is enabled
+pub struct UsageTracker();
+
+#[rocket::async_trait]
+impl Fairing for UsageTracker {
+    fn info(&self) -> Info {
+        Info {
+            name: "UsageTracker",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, _response: &mut Response<'r>) {
+        if !feature_flag_usage_tracking() {
+            return;
+        }
+        let template = match request.route() {
+            Some(route) => route.uri.to_string(),
+            None => return,
+        };
+        let path = request.uri().path().to_string();
+        let (chain_id, safe_address) = match safe_of_route(&template, &path) {
+            Some(safe) => safe,
+            None => return,
+        };
+        if let Some(cache) = request.rocket().state::<Arc<dyn Cache>>() {
+            record(
+                cache.as_ref(),
+                &chain_id,
+                &safe_address,
+                Utc::now().timestamp_millis(),
+                &UsageWindow::from_config(),
+            )
+            .await;
+        }
+    }
+}
@@ -1,5 +1,58 @@
+use crate::common::models::addresses::AddressEx;
+use crate::providers::info::InfoProvider;
 use crate::utils::json::default_if_null;
+use rocket::futures::future::join_all;
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+/// Resolved [AddressEx] by address, built in a single batch by [address_ex_index]
+pub type AddressInfoIndex = HashMap<String, AddressEx>;
+
+/// Where the [AddressEx] of an address is looked up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddressSource {
+    /// Contract info only
+    Contracts,
+    /// Token info, then contract info
+    AnySource,
+}
+
+/// Resolves every distinct address of `addresses` only once, the lookups run concurrently and
+/// are memoized for the request by the [InfoProvider]. Unresolved addresses are missing.
+pub async fn address_ex_index<P: InfoProvider + ?Sized>(
+    info_provider: &P,
+    addresses: &[&str],
+    source: AddressSource,
+) -> AddressInfoIndex {
+    // Lookups are started in the order in which the addresses first appear
+    let mut seen = HashSet::new();
+    let unique_addresses: Vec<&str> = addresses
+        .iter()
+        .copied()
+        .filter(|address| seen.insert(*address))
+        .collect();
+    join_all(unique_addresses.into_iter().map(|address| async move {
+        let address_ex = match source {
+            AddressSource::Contracts => info_provider.address_ex_from_contracts(address).await,
+            AddressSource::AnySource => info_provider.address_ex_from_any_source(address).await,
+        };
+        address_ex
+            .ok()
+            .map(|address_ex| (address.to_string(), address_ex))
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// [AddressEx] of `address` in `index`, only the address if it wasn't resolved
+pub fn address_ex_or_default(index: &AddressInfoIndex, address: &str) -> AddressEx {
+    index
+        .get(address)
+        .cloned()
+        .unwrap_or_else(|| AddressEx::address_only(address))
+}
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -12,3 +65,9 @@ pub struct ContractInfo {
     pub logo_uri: Option<String>,
     // pub contract_abi: Option<ContractAbi>, //Ignored for now
 }
+
+pub(crate) fn is_indexable_address(value: &str) -> bool {
+    value.len() == 42
+        && value.starts_with("0x")
+        && value != "0x0000000000000000000000000000000000000000"
+}
@@ -0,0 +1,120 @@
+//! Reputation of the addresses transactions interact with, attached as `riskFlags` to their
+//! [AddressEx](crate::common::models::addresses::AddressEx) so that clients can warn before
+//! signing. Flags of the denylist files of `ADDRESS_RISK_FILES` are combined with the flags
+//! reported by the reputation provider at `ADDRESS_REPUTATION_URI`, which is best effort.
+use crate::cache::cache_operations::RequestCached;
+use crate::cache::Cache;
+use crate::common::models::addresses::RiskFlag;
+use crate::config::{
+    address_reputation_cache_duration, address_reputation_uri, address_risk_files,
+    contract_info_request_timeout, short_error_duration,
+};
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::HttpClient;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Flags by lowercase address
+pub type Denylist = HashMap<String, Vec<RiskFlag>>;
+
+lazy_static! {
+    static ref DENYLIST: Denylist = load_denylists(&address_risk_files());
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Reputation {
+    #[serde(default)]
+    risk_flags: Vec<RiskFlag>,
+}
+
+/// Addresses are only checked if there is a denylist or a reputation provider
+pub fn is_enabled() -> bool {
+    !DENYLIST.is_empty() || address_reputation_uri().is_some()
+}
+
+pub async fn risk_flags(
+    client: &Arc<dyn HttpClient>,
+    cache: &Arc<dyn Cache>,
+    address: &str,
+) -> Vec<RiskFlag> {
+    let listed = DENYLIST
+        .get(&address.to_lowercase())
+        .cloned()
+        .unwrap_or_default();
+    let reported = match address_reputation_uri() {
+        Some(uri) => reported_flags(client, cache, &uri, address).await,
+        None => vec![],
+    };
+    combine_flags(listed.into_iter().chain(reported))
+}
+
+/// Sorted, without duplicates and without the flags unknown to the gateway
+pub fn combine_flags(flags: impl IntoIterator<Item = RiskFlag>) -> Vec<RiskFlag> {
+    flags
+        .into_iter()
+        .filter(|flag| *flag != RiskFlag::Unknown)
+        .collect::<BTreeSet<RiskFlag>>()
+        .into_iter()
+        .collect()
+}
+
+/// Files that can't be read are skipped, startup validation already reported missing ones
+pub fn load_denylists(files: &[String]) -> Denylist {
+    let mut denylist = Denylist::new();
+    for file in files {
+        match read_denylist(file) {
+            Ok(entries) => merge_denylist(&mut denylist, entries),
+            Err(error) => log::warn!("Could not load address denylist {}: {}", file, error),
+        }
+    }
+    denylist
+}
+
+pub fn merge_denylist(denylist: &mut Denylist, entries: Denylist) {
+    for (address, flags) in entries {
+        denylist
+            .entry(address.to_lowercase())
+            .or_default()
+            .extend(flags);
+    }
+}
+
+fn read_denylist(file: &str) -> Result<Denylist, String> {
+    let content = std::fs::read_to_string(file).map_err(|error| error.to_string())?;
+    serde_json::from_str(&content).map_err(|error| error.to_string())
+}
+
+async fn reported_flags(
+    client: &Arc<dyn HttpClient>,
+    cache: &Arc<dyn Cache>,
+    uri: &str,
+    address: &str,
+) -> Vec<RiskFlag> {
+    let url = format!("{}/{}", uri.trim_end_matches('/'), address);
+    match load_reputation(client, cache, url).await {
+        Ok(reputation) => reputation.risk_flags,
+        // Addresses unknown to the provider have no reputation
+        Err(error) if error.status == 404 => vec![],
+        Err(error) => {
+            log::warn!("Could not load the reputation of {}: {}", address, error);
+            vec![]
+        }
+    }
+}
+
+async fn load_reputation(
+    client: &Arc<dyn HttpClient>,
+    cache: &Arc<dyn Cache>,
+    url: String,
+) -> ApiResult<Reputation> {
+    let body = RequestCached::new(url, client, cache)
+        .cache_duration(address_reputation_cache_duration())
+        .error_cache_duration(short_error_duration())
+        .request_timeout(contract_info_request_timeout())
+        .execute()
+        .await?;
+    Ok(serde_json::from_str(&body)?)
+}
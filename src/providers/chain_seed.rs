@@ -0,0 +1,60 @@
+//! Chain configs served while the config service is unreachable and the chain is not cached yet
+//! (e.g. at a cold start during an outage), so that the primary chains of a deployment keep
+//! working. Chains of the file configured with `CHAIN_SEED_FILE` are looked up first and the seed
+//! bundled with the gateway after it. Both use the chain format of the config service.
+use crate::common::models::backend::chains::ChainInfo;
+use crate::config::chain_seed_file;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref BUNDLED_CHAIN_SEED: Vec<ChainInfo> =
+        serde_json::from_str(include_str!("chain_seed.json")).expect("Invalid bundled chain seed");
+    static ref FILE_CHAIN_SEED: Option<Vec<ChainInfo>> = chain_seed_file().and_then(|file| {
+        match load_chain_seed(&file) {
+            Ok(chains) => Some(chains),
+            Err(error) => {
+                log::warn!("Could not load chain seed from {}: {}", file, error);
+                None
+            }
+        }
+    });
+}
+
+pub fn seeded_chain(chain_id: &str) -> Option<ChainInfo> {
+    find_chain(FILE_CHAIN_SEED.as_deref(), &BUNDLED_CHAIN_SEED, chain_id)
+}
+
+/// Every seeded chain, a chain of the file replacing the bundled chain with the same id
+pub fn seeded_chains() -> Vec<ChainInfo> {
+    merge_chains(FILE_CHAIN_SEED.as_deref(), &BUNDLED_CHAIN_SEED)
+}
+
+pub fn find_chain(
+    file: Option<&[ChainInfo]>,
+    bundled: &[ChainInfo],
+    chain_id: &str,
+) -> Option<ChainInfo> {
+    file.into_iter()
+        .flat_map(|chains| chains.iter())
+        .chain(bundled.iter())
+        .find(|chain| chain.chain_id == chain_id)
+        .cloned()
+}
+
+pub fn merge_chains(file: Option<&[ChainInfo]>, bundled: &[ChainInfo]) -> Vec<ChainInfo> {
+    let mut chains: Vec<ChainInfo> = file.map(<[ChainInfo]>::to_vec).unwrap_or_default();
+    for chain in bundled {
+        if !chains
+            .iter()
+            .any(|seeded| seeded.chain_id == chain.chain_id)
+        {
+            chains.push(chain.clone());
+        }
+    }
+    chains
+}
+
+fn load_chain_seed(file: &str) -> Result<Vec<ChainInfo>, String> {
+    let content = std::fs::read_to_string(file).map_err(|error| error.to_string())?;
+    serde_json::from_str(&content).map_err(|error| error.to_string())
+}
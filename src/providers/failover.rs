@@ -0,0 +1,135 @@
+use crate::common::models::backend::chains::ChainInfo;
+use crate::config::{transaction_service_fallback_uris, transaction_service_unhealthy_duration};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+lazy_static! {
+    static ref FALLBACK_OVERRIDES: HashMap<String, String> = transaction_service_fallback_uris();
+    // Keyed by the primary transaction service uri, shared by all requests of this instance
+    static ref SERVICE_HEALTH: Mutex<HashMap<String, ServiceHealth>> = Mutex::new(HashMap::new());
+}
+
+struct ServiceHealth {
+    fallback_uri: String,
+    unhealthy_until: Option<Instant>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CircuitState {
+    /// Requests go to the primary
+    Closed,
+    /// The primary failed recently, requests go to the fallback
+    Open,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceCircuit {
+    pub primary_uri: String,
+    pub fallback_uri: String,
+    pub state: CircuitState,
+    /// Remaining time (in ms) requests go to the fallback
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_for: Option<u64>,
+}
+
+/// Fills in the fallback uris configured locally for chains that don't get one from the
+/// config service, and registers them so failing requests can be retried against them
+pub fn apply_fallbacks(chain_info: &mut ChainInfo) {
+    if let Some(fallback_uri) = FALLBACK_OVERRIDES.get(&chain_info.chain_id) {
+        chain_info
+            .transaction_service_fallback_uri
+            .get_or_insert_with(|| fallback_uri.to_string());
+        chain_info
+            .vpc_transaction_service_fallback_uri
+            .get_or_insert_with(|| fallback_uri.to_string());
+    }
+    if let Some(fallback_uri) = &chain_info.transaction_service_fallback_uri {
+        register_fallback(&chain_info.transaction_service, fallback_uri);
+    }
+    if let Some(fallback_uri) = &chain_info.vpc_transaction_service_fallback_uri {
+        register_fallback(&chain_info.vpc_transaction_service, fallback_uri);
+    }
+}
+
+pub fn register_fallback(primary_uri: &str, fallback_uri: &str) {
+    let mut health = SERVICE_HEALTH.lock().unwrap();
+    health
+        .entry(primary_uri.to_string())
+        .and_modify(|entry| entry.fallback_uri = fallback_uri.to_string())
+        .or_insert_with(|| ServiceHealth {
+            fallback_uri: fallback_uri.to_string(),
+            unhealthy_until: None,
+        });
+}
+
+/// The fallback of `primary_uri` while the primary is marked as unhealthy, `primary_uri` otherwise
+pub fn active_uri(primary_uri: &str) -> String {
+    let health = SERVICE_HEALTH.lock().unwrap();
+    match health.get(primary_uri) {
+        Some(ServiceHealth {
+            fallback_uri,
+            unhealthy_until: Some(until),
+        }) if Instant::now() < *until => fallback_uri.to_string(),
+        _ => primary_uri.to_string(),
+    }
+}
+
+/// The same `url` pointed at the fallback, if it targets a primary that has one
+pub fn fallback_url(url: &str) -> Option<String> {
+    let health = SERVICE_HEALTH.lock().unwrap();
+    health
+        .iter()
+        .find(|(primary_uri, _)| url.starts_with(primary_uri.as_str()))
+        .map(|(primary_uri, entry)| format!("{}{}", entry.fallback_uri, &url[primary_uri.len()..]))
+}
+
+/// Marks the primary targeted by `url` as unhealthy, so the next requests go to its fallback
+pub fn report_failure(url: &str) {
+    let unhealthy_until =
+        Instant::now() + Duration::from_millis(transaction_service_unhealthy_duration());
+    update_health(url, Some(unhealthy_until));
+}
+
+pub fn report_success(url: &str) {
+    update_health(url, None);
+}
+
+fn update_health(url: &str, unhealthy_until: Option<Instant>) {
+    let mut health = SERVICE_HEALTH.lock().unwrap();
+    health
+        .iter_mut()
+        .filter(|(primary_uri, _)| url.starts_with(primary_uri.as_str()))
+        .for_each(|(_, entry)| entry.unhealthy_until = unhealthy_until);
+}
+
+/// Every primary with a fallback, sorted by primary uri
+pub fn circuits() -> Vec<ServiceCircuit> {
+    let now = Instant::now();
+    let health = SERVICE_HEALTH.lock().unwrap();
+    let mut circuits: Vec<ServiceCircuit> = health
+        .iter()
+        .map(|(primary_uri, entry)| {
+            let open_for = entry
+                .unhealthy_until
+                .filter(|until| now < *until)
+                .map(|until| (until - now).as_millis() as u64);
+            ServiceCircuit {
+                primary_uri: primary_uri.to_string(),
+                fallback_uri: entry.fallback_uri.to_string(),
+                state: if open_for.is_some() {
+                    CircuitState::Open
+                } else {
+                    CircuitState::Closed
+                },
+                open_for,
+            }
+        })
+        .collect();
+    circuits.sort_by(|left, right| left.primary_uri.cmp(&right.primary_uri));
+    circuits
+}
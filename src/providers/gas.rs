@@ -0,0 +1,114 @@
+//! Gas prices estimated from the chain RPC. Chains with the `EIP1559` feature get fee tiers
+//! estimated from the priority fees of the latest blocks (`eth_feeHistory`), other chains and
+//! nodes that don't support `eth_feeHistory` the legacy `eth_gasPrice`.
+use crate::common::models::backend::chains::ChainInfo;
+use crate::providers::rpc::{FeeHistory, RpcProvider};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use serde::Serialize;
+
+pub const EIP1559_FEATURE: &str = "EIP1559";
+// Blocks the priority fees are sampled from
+const FEE_HISTORY_BLOCKS: u64 = 20;
+// Priority fee percentiles of the slow, standard and fast tiers
+const FEE_HISTORY_PERCENTILES: [u8; 3] = [10, 50, 90];
+// The base fee can grow by 12.5% per block, doubling it covers 6 full blocks in a row
+const BASE_FEE_MULTIPLIER: u64 = 2;
+
+/// Amounts in wei
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GasPriceEstimation {
+    #[serde(rename_all = "camelCase")]
+    Eip1559 {
+        /// Base fee of the next block
+        base_fee_per_gas: String,
+        slow: FeeTier,
+        standard: FeeTier,
+        fast: FeeTier,
+    },
+    #[serde(rename_all = "camelCase")]
+    Legacy { gas_price: String },
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTier {
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+}
+
+pub fn supports_eip1559(chain_info: &ChainInfo) -> bool {
+    chain_info
+        .features
+        .iter()
+        .any(|feature| feature == EIP1559_FEATURE)
+}
+
+pub async fn estimate_gas_price(
+    context: &RequestContext,
+    chain_info: &ChainInfo,
+) -> ApiResult<GasPriceEstimation> {
+    let rpc = RpcProvider::new(context, chain_info);
+    if supports_eip1559(chain_info) {
+        match rpc
+            .fee_history(FEE_HISTORY_BLOCKS, &FEE_HISTORY_PERCENTILES)
+            .await?
+        {
+            Ok(fee_history) => return fee_tiers(&fee_history),
+            Err(error) => log::warn!(
+                "eth_feeHistory failed for chain {}: {}",
+                chain_info.chain_id,
+                error.message
+            ),
+        }
+    }
+    let gas_price = rpc
+        .gas_price()
+        .await?
+        .map_err(|error| api_error!("eth_gasPrice failed: {}", error.message))?;
+    Ok(GasPriceEstimation::Legacy {
+        gas_price: gas_price.to_string(),
+    })
+}
+
+/// Priority fees are the median of the sampled blocks per percentile. Faster tiers never pay
+/// less than slower ones.
+pub fn fee_tiers(fee_history: &FeeHistory) -> ApiResult<GasPriceEstimation> {
+    let base_fee = *fee_history
+        .base_fee_per_gas
+        .last()
+        .ok_or(api_error!("Empty fee history"))?;
+    let mut priority_fees = vec![];
+    for index in 0..FEE_HISTORY_PERCENTILES.len() {
+        let mut rewards: Vec<u64> = fee_history
+            .reward
+            .iter()
+            .filter_map(|block| block.get(index).copied())
+            .collect();
+        let median = median(&mut rewards);
+        let previous = priority_fees.last().copied().unwrap_or(0);
+        priority_fees.push(median.max(previous));
+    }
+    let tier = |priority_fee: u64| FeeTier {
+        max_fee_per_gas: base_fee
+            .saturating_mul(BASE_FEE_MULTIPLIER)
+            .saturating_add(priority_fee)
+            .to_string(),
+        max_priority_fee_per_gas: priority_fee.to_string(),
+    };
+    Ok(GasPriceEstimation::Eip1559 {
+        base_fee_per_gas: base_fee.to_string(),
+        slow: tier(priority_fees[0]),
+        standard: tier(priority_fees[1]),
+        fast: tier(priority_fees[2]),
+    })
+}
+
+fn median(values: &mut [u64]) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    values[values.len() / 2]
+}
@@ -1,36 +1,50 @@
-use crate::cache::cache_operations::RequestCached;
+use crate::cache::cache_operations::{
+    Invalidate, InvalidationPattern, InvalidationScope, RequestCached,
+};
 use crate::cache::Cache;
-use crate::common::models::addresses::AddressEx;
+use crate::common::models::addresses::{AddressEx, RiskFlag};
 use crate::common::models::backend::chains::ChainInfo;
 use crate::common::models::backend::safes::MasterCopy;
 use crate::common::models::page::Page;
 use crate::config::{
     address_info_cache_duration, chain_info_cache_duration, chain_info_request_timeout,
-    contract_info_request_timeout, default_request_timeout, long_error_duration,
-    request_cache_duration, safe_app_info_request_timeout, safe_app_manifest_cache_duration,
-    safe_info_cache_duration, safe_info_request_timeout, short_error_duration,
-    token_info_cache_duration, token_info_request_timeout,
+    contract_info_request_timeout, long_error_duration, request_cache_duration,
+    safe_app_info_request_timeout, safe_app_manifest_cache_duration, safe_info_cache_duration,
+    safe_info_request_timeout, short_error_duration, token_info_cache_duration,
+    token_info_request_timeout, unknown_chain_cache_duration,
 };
+use crate::monitoring::schema_drift;
 use crate::providers::address_info::ContractInfo;
+use crate::providers::address_risk;
+use crate::providers::chain_seed;
+use crate::providers::failover;
+use crate::providers::token_list;
+use crate::providers::token_overrides;
+use crate::utils::cache_control::DataFreshness;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
 use crate::utils::http_client::{HttpClient, Request};
 use crate::utils::json::default_if_null;
+use crate::utils::logo_prefetch;
+use crate::utils::outbound;
 use crate::utils::urls::build_manifest_url;
 use lazy_static::lazy_static;
 use mockall::automock;
 use rocket::futures::TryFutureExt;
 use rocket::tokio::sync::Mutex;
+use rocket::tokio::time::timeout;
 use semver::Version;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
 use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub const TOKENS_KEY_BASE: &'static str = "dip_ti";
+pub const UNKNOWN_CHAIN_KEY_BASE: &'static str = "dip_uc";
 lazy_static! {
     pub static ref SAFE_V_1_3_0: Version = Version::new(1, 3, 0);
 }
@@ -46,7 +60,7 @@ pub enum TokenType {
     Unknown,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SafeInfo {
     pub address: String,
@@ -99,17 +113,38 @@ pub trait InfoProvider {
     async fn safe_app_info(&self, url: &str) -> ApiResult<SafeAppInfo>;
     async fn address_ex_from_any_source(&self, address: &str) -> ApiResult<AddressEx>;
     async fn address_ex_from_contracts(&self, address: &str) -> ApiResult<AddressEx>;
+    async fn address_risk_flags(&self, address: &str) -> Vec<RiskFlag>;
     fn chain_id(&self) -> &str;
 }
 
+type Memoized<T> = Arc<Mutex<HashMap<String, Option<T>>>>;
+
+/// Lookups of all [DefaultInfoProvider]s created for one request, so that converters and handlers
+/// creating their own provider don't repeat them (e.g. for every transaction of a page).
+/// Entries are scoped by chain.
+#[derive(Clone, Default)]
+pub struct InfoMemo {
+    // Mutex is an async Mutex, meaning that the lock is non-blocking
+    safes: Memoized<SafeInfo>,
+    tokens: Memoized<TokenInfo>,
+    chains: Memoized<ChainInfo>,
+    // Contract lookups are memoized including misses, as the same addresses (modules, fallback
+    // handlers, recipients) show up repeatedly within one page
+    contracts: Memoized<AddressEx>,
+}
+
 pub struct DefaultInfoProvider<'p> {
     pub chain_id: &'p str,
     client: Arc<dyn HttpClient>,
     cache: Arc<dyn Cache>,
-    // Mutex is an async Mutex, meaning that the lock is non-blocking
-    safe_cache: Mutex<HashMap<String, Option<SafeInfo>>>,
-    token_cache: Mutex<HashMap<String, Option<TokenInfo>>>,
-    chain_cache: Mutex<HashMap<String, Option<ChainInfo>>>,
+    safe_cache: Memoized<SafeInfo>,
+    token_cache: Memoized<TokenInfo>,
+    chain_cache: Memoized<ChainInfo>,
+    contract_cache: Memoized<AddressEx>,
+    // Enrichment lookups (tokens, contracts, safe apps) are skipped once the deadline has passed
+    deadline: Option<Instant>,
+    incomplete: AtomicBool,
+    data_freshness: DataFreshness,
 }
 
 #[rocket::async_trait]
@@ -125,16 +160,24 @@ impl InfoProvider for DefaultInfoProvider<'_> {
 
     async fn safe_info(&self, safe: &str) -> ApiResult<SafeInfo> {
         let safe_cache = &mut self.safe_cache.lock().await;
-        Self::cached(safe_cache, || self.load_safe_info(safe.to_string()), safe).await
+        Self::cached(
+            safe_cache,
+            || self.load_safe_info(safe.to_string()),
+            self.memo_key(safe),
+        )
+        .await
     }
 
     async fn token_info(&self, token: &str) -> ApiResult<TokenInfo> {
+        // Token cache population is not cancelled, as that would leave the shared cache
+        // in a "populating" state; the budget is only checked before starting
+        self.check_budget()?;
         if token != "0x0000000000000000000000000000000000000000" {
             let token_cache = &mut self.token_cache.lock().await;
             Self::cached(
                 token_cache,
                 || self.load_token_info(token.to_string()),
-                token,
+                self.memo_key(token),
             )
             .await
         } else {
@@ -145,12 +188,15 @@ impl InfoProvider for DefaultInfoProvider<'_> {
     async fn safe_app_info(&self, url: &str) -> ApiResult<SafeAppInfo> {
         let manifest_url = build_manifest_url(url)?;
 
-        let manifest_json = RequestCached::new(manifest_url, &self.client, &self.cache)
-            .cache_duration(safe_app_manifest_cache_duration())
-            .error_cache_duration(long_error_duration())
-            .cache_all_errors()
-            .request_timeout(safe_app_info_request_timeout())
-            .execute()
+        let manifest_json = self
+            .within_budget(
+                RequestCached::new(manifest_url, &self.client, &self.cache)
+                    .cache_duration(safe_app_manifest_cache_duration())
+                    .error_cache_duration(long_error_duration())
+                    .cache_all_errors()
+                    .request_timeout(safe_app_info_request_timeout())
+                    .execute(),
+            )
             .await?;
         let manifest = serde_json::from_str::<Manifest>(&manifest_json)?;
         Ok(SafeAppInfo {
@@ -161,23 +207,25 @@ impl InfoProvider for DefaultInfoProvider<'_> {
     }
 
     async fn address_ex_from_contracts(&self, address: &str) -> ApiResult<AddressEx> {
-        let url = core_uri!(self, "/v1/contracts/{}/", address)?;
-        let contract_info_json = RequestCached::new(url, &self.client, &self.cache)
-            .cache_duration(address_info_cache_duration())
-            .error_cache_duration(long_error_duration())
-            .request_timeout(contract_info_request_timeout())
-            .execute()
-            .await?;
-        let contract_info = serde_json::from_str::<ContractInfo>(&contract_info_json)?;
-        if contract_info.display_name.trim().is_empty() {
-            bail!("No display name")
-        } else {
-            Ok(AddressEx {
-                value: address.to_owned(),
-                name: Some(contract_info.display_name.to_owned()),
-                logo_uri: contract_info.logo_uri.to_owned(),
-            })
+        // The lock is not held while loading, so batched lookups can run concurrently
+        let memo_key = self.memo_key(address);
+        if let Some(cached) = self.contract_cache.lock().await.get(&memo_key) {
+            return cached
+                .clone()
+                .ok_or(api_error!("Cached value not available"));
+        }
+        let result = self
+            .within_budget(self.load_address_ex_from_contracts(address.to_string()))
+            .await;
+        if self.is_incomplete() && result.is_err() {
+            // Do not memoize lookups that were cut short by the latency budget
+            return result;
         }
+        self.contract_cache
+            .lock()
+            .await
+            .insert(memo_key, result.as_ref().ok().cloned());
+        result
     }
 
     async fn address_ex_from_any_source(&self, address: &str) -> ApiResult<AddressEx> {
@@ -186,26 +234,80 @@ impl InfoProvider for DefaultInfoProvider<'_> {
                 value: address.to_owned(),
                 name: Some(it.name),
                 logo_uri: it.logo_uri,
+                risk_flags: vec![],
             })
             .or_else(|_| async move { self.address_ex_from_contracts(&address).await })
             .await
     }
+
+    async fn address_risk_flags(&self, address: &str) -> Vec<RiskFlag> {
+        address_risk::risk_flags(&self.client, &self.cache, address).await
+    }
 }
 
 impl<'a> DefaultInfoProvider<'a> {
+    /// Shares the lookups with every other provider created for the request of `context`
     pub fn new(chain_id: &'a str, context: &RequestContext) -> Self {
+        let info_memo = context.info_memo();
         DefaultInfoProvider {
             chain_id,
             client: context.http_client(),
             cache: context.cache(),
-            safe_cache: Default::default(),
-            token_cache: Default::default(),
-            chain_cache: Default::default(),
+            safe_cache: info_memo.safes,
+            token_cache: info_memo.tokens,
+            chain_cache: info_memo.chains,
+            contract_cache: info_memo.contracts,
+            deadline: None,
+            incomplete: AtomicBool::new(false),
+            data_freshness: context.data_freshness(),
         }
     }
+
+    /// Limits the time spent on enrichment lookups, starting now. A budget of 0 disables it.
+    pub fn latency_budget(&mut self, budget_ms: u64) -> &mut Self {
+        self.deadline = if budget_ms > 0 {
+            Some(Instant::now() + Duration::from_millis(budget_ms))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Whether any enrichment lookup was skipped because the latency budget was exceeded
+    pub fn is_incomplete(&self) -> bool {
+        self.incomplete.load(Ordering::Relaxed)
+    }
 }
 
 impl DefaultInfoProvider<'_> {
+    fn memo_key(&self, key: &str) -> String {
+        format!("{}_{}", self.chain_id, key)
+    }
+
+    fn check_budget(&self) -> ApiResult<()> {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => {
+                self.incomplete.store(true, Ordering::Relaxed);
+                bail!("Latency budget exceeded")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn within_budget<T>(&self, call: impl Future<Output = ApiResult<T>>) -> ApiResult<T> {
+        let deadline = match self.deadline {
+            Some(deadline) => deadline,
+            None => return call.await,
+        };
+        match timeout(deadline.saturating_duration_since(Instant::now()), call).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.incomplete.store(true, Ordering::Relaxed);
+                bail!("Latency budget exceeded")
+            }
+        }
+    }
+
     async fn cached<'a, T, Fut>(
         local_cache: &'a mut HashMap<String, Option<T>>,
         generator: impl FnOnce() -> Fut,
@@ -234,9 +336,11 @@ impl DefaultInfoProvider<'_> {
             .cache_duration(safe_info_cache_duration())
             .error_cache_duration(short_error_duration())
             .request_timeout(safe_info_request_timeout())
+            .keep_last_known_good()
+            .data_freshness(&self.data_freshness)
             .execute()
             .await?;
-        Ok(serde_json::from_str(&data).ok())
+        Ok(schema_drift::parse(&data).ok())
     }
 
     async fn populate_token_cache(&self) -> ApiResult<()> {
@@ -252,50 +356,170 @@ impl DefaultInfoProvider<'_> {
         let token_key = generate_token_key(self.chain_id);
         for token in data.results.iter() {
             self.cache
-                .insert_in_hash(&token_key, &token.address, &serde_json::to_string(&token)?);
+                .insert_in_hash(&token_key, &token.address, &serde_json::to_string(&token)?)
+                .await;
         }
+        logo_prefetch::schedule(
+            self.client.clone(),
+            self.cache.clone(),
+            data.results
+                .into_iter()
+                .filter_map(|token| token.logo_uri)
+                .collect(),
+        );
         Ok(())
     }
 
     async fn check_token_cache(&self) -> ApiResult<()> {
         let token_key = generate_token_key(&self.chain_id);
-        if self.cache.has_key(&token_key) {
+        if self.cache.has_key(&token_key).await {
             return Ok(());
         }
-        self.cache.insert_in_hash(&token_key, "state", "populating");
+        self.cache
+            .insert_in_hash(&token_key, "state", "populating")
+            .await;
         let result = self.populate_token_cache().await;
         if result.is_ok() {
             self.cache
-                .expire_entity(&token_key, token_info_cache_duration());
-            self.cache.insert_in_hash(&token_key, "state", "populated");
+                .expire_entity(&token_key, token_info_cache_duration())
+                .await;
+            self.cache
+                .insert_in_hash(&token_key, "state", "populated")
+                .await;
         } else {
-            self.cache.expire_entity(&token_key, short_error_duration());
-            self.cache.insert_in_hash(&token_key, "state", "errored");
+            self.cache
+                .expire_entity(&token_key, short_error_duration())
+                .await;
+            self.cache
+                .insert_in_hash(&token_key, "state", "errored")
+                .await;
         }
         result
     }
 
     async fn load_token_info(&self, token: String) -> ApiResult<Option<TokenInfo>> {
-        self.check_token_cache().await?;
-        match self
-            .cache
-            .get_from_hash(&generate_token_key(&self.chain_id), &token)
-        {
-            Some(cached) => Ok(Some(serde_json::from_str::<TokenInfo>(&cached)?)),
+        match self.load_upstream_token_info(token).await? {
+            Some(token_info) => Ok(Some(
+                token_overrides::apply_override(self.cache.as_ref(), self.chain_id, token_info)
+                    .await,
+            )),
             None => Ok(None),
         }
     }
 
+    async fn load_upstream_token_info(&self, token: String) -> ApiResult<Option<TokenInfo>> {
+        let token_key = generate_token_key(&self.chain_id);
+        let populated = self.check_token_cache().await;
+        let cached = match populated {
+            Ok(_) => self.cache.get_from_hash(&token_key, &token).await,
+            Err(_) => None,
+        };
+        if let Some(cached) = cached {
+            return Ok(Some(serde_json::from_str::<TokenInfo>(&cached)?));
+        }
+        // Populating the token cache failed just now or within the last short error duration
+        let is_unavailable = populated.is_err()
+            || self
+                .cache
+                .get_from_hash(&token_key, "state")
+                .await
+                .as_deref()
+                == Some("errored");
+        if is_unavailable {
+            if let Some(token_info) =
+                token_list::static_token_info(&self.client, &self.cache, self.chain_id, &token)
+                    .await
+            {
+                // Not stored in the response cache, the static lists may be outdated
+                self.data_freshness.mark_stale();
+                return Ok(Some(token_info));
+            }
+        }
+        populated.map(|_| None)
+    }
+
     async fn load_chain_info(&self) -> ApiResult<Option<ChainInfo>> {
         let url = config_uri!("/v1/chains/{}/", self.chain_id);
-        let data = RequestCached::new(url, &self.client, &self.cache)
+        let mut request = RequestCached::new(url, &self.client, &self.cache);
+        request
             .cache_duration(chain_info_cache_duration())
             .error_cache_duration(short_error_duration())
             .request_timeout(chain_info_request_timeout())
+            .keep_last_known_good()
+            .data_freshness(&self.data_freshness);
+        let was_cached = request.is_cached().await;
+        let data = match request.execute().await {
+            // The chain may have been added since the not found response was cached, it is
+            // looked up once more (and then not again for a while) before reporting it as unknown
+            Err(error) if error.status == 404 && was_cached => {
+                if !self.should_rediscover_chain().await {
+                    return Err(error);
+                }
+                request.invalidate().await;
+                let data = request.execute().await?;
+                // So that the chains list includes the new chain
+                Invalidate::new(
+                    InvalidationPattern::Any(
+                        InvalidationScope::Requests,
+                        config_uri!("/v1/chains/?limit="),
+                    ),
+                    self.cache.clone(),
+                )
+                .source("CHAIN_DISCOVERY")
+                .execute()
+                .await;
+                data
+            }
+            // Cold start during a config service outage, keep_last_known_good had nothing to serve
+            Err(error) if error.status >= 500 => {
+                let chain_info = chain_seed::seeded_chain(self.chain_id).ok_or(error)?;
+                self.data_freshness.mark_stale();
+                return Ok(Some(Self::checked_chain_info(chain_info)));
+            }
+            result => result?,
+        };
+        let result = serde_json::from_str::<ChainInfo>(&data)
+            .ok()
+            .map(Self::checked_chain_info);
+        Ok(result)
+    }
+
+    fn checked_chain_info(mut chain_info: ChainInfo) -> ChainInfo {
+        failover::apply_fallbacks(&mut chain_info);
+        outbound::allow_chain(&chain_info);
+        chain_info
+    }
+
+    async fn should_rediscover_chain(&self) -> bool {
+        let key = generate_unknown_chain_key(self.chain_id);
+        if self.cache.has_key(&key).await {
+            return false;
+        }
+        self.cache
+            .create(&key, "", unknown_chain_cache_duration())
+            .await;
+        true
+    }
+
+    async fn load_address_ex_from_contracts(&self, address: String) -> ApiResult<AddressEx> {
+        let url = core_uri!(self, "/v1/contracts/{}/", address)?;
+        let contract_info_json = RequestCached::new(url, &self.client, &self.cache)
+            .cache_duration(address_info_cache_duration())
+            .error_cache_duration(long_error_duration())
+            .request_timeout(contract_info_request_timeout())
             .execute()
             .await?;
-        let result = serde_json::from_str::<ChainInfo>(&data).ok();
-        Ok(result)
+        let contract_info = serde_json::from_str::<ContractInfo>(&contract_info_json)?;
+        if contract_info.display_name.trim().is_empty() {
+            bail!("No display name")
+        } else {
+            Ok(AddressEx {
+                value: address.to_owned(),
+                name: Some(contract_info.display_name.to_owned()),
+                logo_uri: contract_info.logo_uri.to_owned(),
+                risk_flags: vec![],
+            })
+        }
     }
 
     pub async fn master_copies(&self) -> ApiResult<Vec<MasterCopy>> {
@@ -303,7 +527,6 @@ impl DefaultInfoProvider<'_> {
         let body = RequestCached::new(url, &self.client, &self.cache)
             .cache_duration(request_cache_duration())
             .error_cache_duration(short_error_duration())
-            .request_timeout(default_request_timeout())
             .execute()
             .await?;
         Ok(serde_json::from_str(&body)?)
@@ -313,3 +536,7 @@ impl DefaultInfoProvider<'_> {
 pub fn generate_token_key(chain_id: &str) -> String {
     format!("{}_{}", TOKENS_KEY_BASE, chain_id)
 }
+
+pub fn generate_unknown_chain_key(chain_id: &str) -> String {
+    format!("{}_{}", UNKNOWN_CHAIN_KEY_BASE, chain_id)
+}
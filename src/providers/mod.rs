@@ -1,4 +1,11 @@
 pub mod address_info;
+pub mod address_risk;
+pub mod chain_seed;
 pub mod ext;
+pub mod failover;
 pub mod fiat;
+pub mod gas;
 pub mod info;
+pub mod rpc;
+pub mod token_list;
+pub mod token_overrides;
@@ -0,0 +1,283 @@
+use crate::common::models::backend::chains::{ChainInfo, RpcAuthentication};
+use crate::config::{rpc_api_key, rpc_request_timeout};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{HttpClient, Request};
+use ethabi::Uint;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Errors reported by the node itself (e.g. a reverting call), as opposed to transport errors
+pub type RpcResult<T> = Result<T, RpcError>;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default)]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    /// Whether the node rejected the call because its execution reverts, as opposed to failing
+    /// for reasons unrelated to the call (e.g. rate limits or missing state)
+    pub fn is_execution_reverted(&self) -> bool {
+        // Geth reports reverts with data as code 3, without data only the message identifies them
+        let message = self.message.to_lowercase();
+        self.code == 3
+            || message.contains("revert")
+            || message.contains("always failing transaction")
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCall {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    pub to: String,
+    pub data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// Result of `eth_feeHistory`, in wei
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeHistory {
+    /// Base fee of every requested block plus the one of the next block
+    pub base_fee_per_gas: Vec<u64>,
+    /// Priority fees of every requested block, per requested percentile
+    pub reward: Vec<Vec<u64>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RawFeeHistory {
+    base_fee_per_gas: Vec<Value>,
+    #[serde(default)]
+    reward: Vec<Vec<Value>>,
+}
+
+/// Part of the `eth_getTransactionReceipt` result used by the gateway, amounts in wei
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceipt {
+    pub gas_used: u64,
+    /// Missing on nodes predating EIP-1559
+    pub effective_gas_price: Option<u64>,
+    /// Missing on receipts cached before logs were read
+    #[serde(default)]
+    pub logs: Option<Vec<ReceiptLog>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptLog {
+    pub log_index: u64,
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RawTransactionReceipt {
+    gas_used: Value,
+    #[serde(default)]
+    effective_gas_price: Option<Value>,
+    #[serde(default)]
+    logs: Vec<RawReceiptLog>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RawReceiptLog {
+    log_index: Value,
+    address: String,
+    topics: Vec<String>,
+    data: String,
+}
+
+pub struct RpcProvider {
+    client: Arc<dyn HttpClient>,
+    rpc_uri: String,
+}
+
+impl RpcProvider {
+    pub fn new(context: &RequestContext, chain_info: &ChainInfo) -> Self {
+        RpcProvider {
+            client: context.http_client(),
+            rpc_uri: build_rpc_uri(chain_info),
+        }
+    }
+
+    /// Outer [ApiResult] fails if the node could not be reached,
+    /// inner [RpcResult] fails if the node answered with a JSON-RPC error
+    pub async fn call_method(&self, method: &str, params: Value) -> ApiResult<RpcResult<Value>> {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let mut request = Request::new(self.rpc_uri.to_string());
+        request
+            .body(Some(payload.to_string()))
+            .timeout(Duration::from_millis(rpc_request_timeout()));
+
+        let response = self.client.post(request).await?;
+        let rpc_response: RpcResponse = serde_json::from_str(&response.body)?;
+        match (rpc_response.result, rpc_response.error) {
+            (_, Some(error)) => Ok(Err(error)),
+            (Some(result), None) => Ok(Ok(result)),
+            (None, None) => Err(api_error!("Empty RPC response for {}", method)),
+        }
+    }
+
+    /// `eth_call` against the latest block, returning the raw hex encoded return data
+    pub async fn call(&self, call: &EthCall) -> ApiResult<RpcResult<String>> {
+        self.call_at(call, "latest").await
+    }
+
+    /// `eth_call` against `block` (a block tag or hex encoded number). Past blocks are only
+    /// available on archive nodes.
+    pub async fn call_at(&self, call: &EthCall, block: &str) -> ApiResult<RpcResult<String>> {
+        let result = self.call_method("eth_call", json!([call, block])).await?;
+        match result {
+            Ok(value) => Ok(Ok(value
+                .as_str()
+                .ok_or(api_error!("Invalid RPC call result: {}", value))?
+                .to_string())),
+            Err(error) => Ok(Err(error)),
+        }
+    }
+
+    /// Native coin balance of `address` at `block`, in wei
+    pub async fn balance_at(&self, address: &str, block: &str) -> ApiResult<RpcResult<Uint>> {
+        let result = self
+            .call_method("eth_getBalance", json!([address, block]))
+            .await?;
+        match result {
+            Ok(value) => {
+                let balance = value
+                    .as_str()
+                    .ok_or(api_error!("Invalid RPC balance: {}", value))?;
+                Ok(Ok(Uint::from_str_radix(
+                    balance.trim_start_matches("0x"),
+                    16,
+                )
+                .map_err(|_| api_error!("Invalid RPC balance: {}", balance))?))
+            }
+            Err(error) => Ok(Err(error)),
+        }
+    }
+
+    pub async fn estimate_gas(&self, call: &EthCall) -> ApiResult<RpcResult<u64>> {
+        let result = self.call_method("eth_estimateGas", json!([call])).await?;
+        match result {
+            Ok(value) => Ok(Ok(parse_hex_quantity(&value)?)),
+            Err(error) => Ok(Err(error)),
+        }
+    }
+
+    pub async fn gas_price(&self) -> ApiResult<RpcResult<u64>> {
+        let result = self.call_method("eth_gasPrice", json!([])).await?;
+        match result {
+            Ok(value) => Ok(Ok(parse_hex_quantity(&value)?)),
+            Err(error) => Ok(Err(error)),
+        }
+    }
+
+    /// Fee history of the latest `block_count` blocks, with the priority fees paid at the given
+    /// `reward_percentiles` of every block
+    pub async fn fee_history(
+        &self,
+        block_count: u64,
+        reward_percentiles: &[u8],
+    ) -> ApiResult<RpcResult<FeeHistory>> {
+        let result = self
+            .call_method(
+                "eth_feeHistory",
+                json!([format!("{:#x}", block_count), "latest", reward_percentiles]),
+            )
+            .await?;
+        let value = match result {
+            Ok(value) => value,
+            Err(error) => return Ok(Err(error)),
+        };
+        let raw: RawFeeHistory = serde_json::from_value(value)?;
+        let reward = raw
+            .reward
+            .iter()
+            .map(|block| block.iter().map(parse_hex_quantity).collect())
+            .collect::<ApiResult<Vec<Vec<u64>>>>()?;
+        Ok(Ok(FeeHistory {
+            base_fee_per_gas: raw
+                .base_fee_per_gas
+                .iter()
+                .map(parse_hex_quantity)
+                .collect::<ApiResult<Vec<u64>>>()?,
+            reward,
+        }))
+    }
+
+    pub async fn transaction_receipt(
+        &self,
+        tx_hash: &str,
+    ) -> ApiResult<RpcResult<TransactionReceipt>> {
+        let result = self
+            .call_method("eth_getTransactionReceipt", json!([tx_hash]))
+            .await?;
+        let value = match result {
+            Ok(value) => value,
+            Err(error) => return Ok(Err(error)),
+        };
+        let raw: RawTransactionReceipt = serde_json::from_value(value)?;
+        Ok(Ok(TransactionReceipt {
+            gas_used: parse_hex_quantity(&raw.gas_used)?,
+            effective_gas_price: raw
+                .effective_gas_price
+                .as_ref()
+                .map(parse_hex_quantity)
+                .transpose()?,
+            logs: Some(
+                raw.logs
+                    .into_iter()
+                    .map(|log| {
+                        Ok(ReceiptLog {
+                            log_index: parse_hex_quantity(&log.log_index)?,
+                            address: log.address,
+                            topics: log.topics,
+                            data: log.data,
+                        })
+                    })
+                    .collect::<ApiResult<Vec<ReceiptLog>>>()?,
+            ),
+        }))
+    }
+}
+
+fn build_rpc_uri(chain_info: &ChainInfo) -> String {
+    match (&chain_info.rpc_uri.authentication, rpc_api_key()) {
+        (RpcAuthentication::ApiKeyPath, Some(api_key)) => {
+            format!("{}{}", chain_info.rpc_uri.value, api_key)
+        }
+        _ => chain_info.rpc_uri.value.to_string(),
+    }
+}
+
+pub(crate) fn parse_hex_quantity(value: &Value) -> ApiResult<u64> {
+    let quantity = value
+        .as_str()
+        .ok_or(api_error!("Invalid RPC quantity: {}", value))?;
+    u64::from_str_radix(quantity.trim_start_matches("0x"), 16)
+        .map_err(|_| api_error!("Invalid RPC quantity: {}", quantity))
+}
@@ -0,0 +1,72 @@
+//! Static token lists used while the token endpoint of the transaction service is unavailable, so
+//! that balances and transfers keep their symbols, decimals and logos during outages.
+//! Tokens of the transaction service always take precedence. Without them, the list loaded from
+//! `STATIC_TOKEN_LIST_URI` is looked up first and the list bundled with the gateway after it.
+use crate::cache::cache_operations::RequestCached;
+use crate::cache::Cache;
+use crate::config::{
+    short_error_duration, static_token_list_uri, token_info_cache_duration,
+    token_info_request_timeout,
+};
+use crate::providers::info::TokenInfo;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::HttpClient;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Tokens by chain id
+pub type StaticTokenList = HashMap<String, Vec<TokenInfo>>;
+
+lazy_static! {
+    static ref BUNDLED_TOKEN_LIST: StaticTokenList =
+        serde_json::from_str(include_str!("token_list.json")).expect("Invalid bundled token list");
+}
+
+pub async fn static_token_info(
+    client: &Arc<dyn HttpClient>,
+    cache: &Arc<dyn Cache>,
+    chain_id: &str,
+    address: &str,
+) -> Option<TokenInfo> {
+    let configured = match static_token_list_uri() {
+        Some(uri) => match load_token_list(client, cache, &uri).await {
+            Ok(token_list) => Some(token_list),
+            Err(error) => {
+                log::warn!("Could not load static token list from {}: {}", uri, error);
+                None
+            }
+        },
+        None => None,
+    };
+    find_token(configured.as_ref(), &BUNDLED_TOKEN_LIST, chain_id, address)
+}
+
+pub fn find_token(
+    configured: Option<&StaticTokenList>,
+    bundled: &StaticTokenList,
+    chain_id: &str,
+    address: &str,
+) -> Option<TokenInfo> {
+    configured
+        .into_iter()
+        .chain(std::iter::once(bundled))
+        .filter_map(|token_list| token_list.get(chain_id))
+        .flat_map(|tokens| tokens.iter())
+        .find(|token| token.address.eq_ignore_ascii_case(address))
+        .cloned()
+}
+
+async fn load_token_list(
+    client: &Arc<dyn HttpClient>,
+    cache: &Arc<dyn Cache>,
+    uri: &str,
+) -> ApiResult<StaticTokenList> {
+    let body = RequestCached::new(uri.to_string(), client, cache)
+        .cache_duration(token_info_cache_duration())
+        .error_cache_duration(short_error_duration())
+        .request_timeout(token_info_request_timeout())
+        .execute()
+        .await?;
+    Ok(serde_json::from_str(&body)?)
+}
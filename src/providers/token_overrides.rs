@@ -0,0 +1,103 @@
+//! Token metadata corrected by operators, merged over the token info of the transaction service
+//! (or the static token lists) so that bad symbols, decimals, logos or spam classifications can be
+//! fixed right away. Overrides are stored without expiry, one entry per chain.
+use crate::cache::Cache;
+use crate::providers::info::TokenInfo;
+use crate::utils::errors::ApiResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const TOKEN_OVERRIDES_KEY: &str = "token_overrides";
+
+/// Fields left empty keep the upstream value
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    /// Replaces the spam classification of balances holding the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spam: Option<bool>,
+}
+
+/// Overrides of a chain, by lowercase token address
+pub type TokenOverrides = HashMap<String, TokenOverride>;
+
+impl TokenOverride {
+    pub fn is_empty(&self) -> bool {
+        self == &TokenOverride::default()
+    }
+
+    pub fn apply(&self, token_info: &mut TokenInfo) {
+        if let Some(symbol) = &self.symbol {
+            token_info.symbol = symbol.to_string();
+        }
+        if let Some(name) = &self.name {
+            token_info.name = name.to_string();
+        }
+        if let Some(decimals) = self.decimals {
+            token_info.decimals = decimals;
+        }
+        if let Some(logo_uri) = &self.logo_uri {
+            token_info.logo_uri = Some(logo_uri.to_string());
+        }
+    }
+}
+
+pub async fn chain_overrides(cache: &dyn Cache, chain_id: &str) -> TokenOverrides {
+    cache
+        .get_from_hash(TOKEN_OVERRIDES_KEY, chain_id)
+        .await
+        .and_then(|overrides| serde_json::from_str(&overrides).ok())
+        .unwrap_or_default()
+}
+
+pub fn find_override<'a>(
+    overrides: &'a TokenOverrides,
+    address: &str,
+) -> Option<&'a TokenOverride> {
+    overrides.get(&address.to_lowercase())
+}
+
+pub async fn apply_override(
+    cache: &dyn Cache,
+    chain_id: &str,
+    mut token_info: TokenInfo,
+) -> TokenInfo {
+    if let Some(token_override) =
+        find_override(&chain_overrides(cache, chain_id).await, &token_info.address)
+    {
+        token_override.apply(&mut token_info);
+    }
+    token_info
+}
+
+/// Replaces the override of the token, an empty override removes it. Returns the overrides of the
+/// chain.
+pub async fn set_override(
+    cache: &dyn Cache,
+    chain_id: &str,
+    address: &str,
+    token_override: &TokenOverride,
+) -> ApiResult<TokenOverrides> {
+    let mut overrides = chain_overrides(cache, chain_id).await;
+    if token_override.is_empty() {
+        overrides.remove(&address.to_lowercase());
+    } else {
+        overrides.insert(address.to_lowercase(), token_override.clone());
+    }
+    cache
+        .insert_in_hash(
+            TOKEN_OVERRIDES_KEY,
+            chain_id,
+            &serde_json::to_string(&overrides)?,
+        )
+        .await;
+    Ok(overrides)
+}
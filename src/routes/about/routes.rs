@@ -1,10 +1,15 @@
 use crate::cache::cache_operations::CacheResponse;
-use crate::config::{about_cache_duration, webhook_token};
+use crate::config::about_cache_duration;
+use crate::config::settings::Settings;
+use crate::monitoring::{schema_drift, slo};
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
 use crate::routes::about::handlers;
+use crate::utils::auth::{self, Scope};
+use crate::utils::call_budget;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
 use crate::utils::http_client::Request;
+use crate::utils::{data_policies, hook_events, payload_limits, upstream_queue, upstream_versions};
 use rocket::response::content;
 
 /**
@@ -52,6 +57,44 @@ pub async fn get_chains_about(
 pub async fn get_about() -> ApiResult<content::Json<String>> {
     Ok(content::Json(serde_json::to_string(&handlers::about())?))
 }
+
+/**
+ * `/about/policies` <br />
+ * Returns [Vec] of [DataPolicy](crate::utils::data_policies::DataPolicy)
+ *
+ * # Data policies
+ *
+ * How long the data of every cached read route can be cached, by the gateway and by the upstream responses it is built from, in ms. `maxAge` is the upper bound of the age of the data of a route, responses carry the bound of their data (in seconds) in the `X-Data-Max-Age` header. Routes that are `invalidatedByHooks` are usually refreshed much sooner, as soon as the transaction service reports a change.
+ *
+ * ## Path
+ *
+ * `/about/policies`
+ */
+#[get("/about/policies")]
+pub fn get_policies() -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        data_policies::configured_policies(),
+    )?))
+}
+
+/**
+ * `/about/chains-status` <br />
+ * Returns [ChainsStatus](crate::utils::upstream_versions::ChainsStatus)
+ *
+ * # Chains status
+ *
+ * Compatibility of the transaction service of every chain with this instance, as of the last check (at startup and every `UPSTREAM_VERSION_CHECK_INTERVAL` ms). `supportedVersions` are the version requirements of the gateway, every chain reports the `version` its transaction service returned, its `compatibility` (`COMPATIBLE`, `INCOMPATIBLE` or `UNKNOWN` if the version could not be read) and a `warning` unless it is compatible. `chains` is empty until the first check finished.
+ *
+ * ## Path
+ *
+ * `/about/chains-status`
+ */
+#[get("/about/chains-status")]
+pub fn get_chains_status() -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &upstream_versions::chains_status(),
+    )?))
+}
 /**
  * `/v1/chains/<chain_id>/about/master-copies` <br />
  * Returns a list of `MasterCopy`
@@ -105,9 +148,74 @@ pub async fn backbone(
 
 #[doc(hidden)]
 #[get("/about/redis/<token>")]
-pub fn redis(context: RequestContext, token: String) -> ApiResult<String> {
-    if token != webhook_token() {
-        bail!("Invalid token");
-    }
-    Ok(context.cache().info().unwrap_or(String::new()))
+pub async fn redis(context: RequestContext, token: String) -> ApiResult<String> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(context.cache().info().await.unwrap_or(String::new()))
+}
+
+#[doc(hidden)]
+#[get("/about/cache-pool/<token>")]
+pub fn cache_pool(context: RequestContext, token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(content::Json(serde_json::to_string(
+        &context.cache().pool_stats(),
+    )?))
+}
+
+#[doc(hidden)]
+#[get("/about/config/<token>")]
+pub fn config(token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    let settings = Settings::load().map_err(|report| api_error!("{}", report))?;
+    Ok(content::Json(serde_json::to_string(&settings)?))
+}
+
+#[doc(hidden)]
+#[get("/about/metrics/<token>")]
+pub fn metrics(token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(content::Json(serde_json::to_string(&slo::report())?))
+}
+
+#[doc(hidden)]
+#[get("/about/schema-drift/<token>")]
+pub fn schema_drift(token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(content::Json(serde_json::to_string(
+        &schema_drift::drift_counts(),
+    )?))
+}
+
+#[doc(hidden)]
+#[get("/about/hook-events/<token>")]
+pub fn hook_events(token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(content::Json(serde_json::to_string(&hook_events::stats())?))
+}
+
+#[doc(hidden)]
+#[get("/about/call-budget/<token>")]
+pub fn call_budget(token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(content::Json(serde_json::to_string(
+        &call_budget::exceeded_counts(),
+    )?))
+}
+
+#[doc(hidden)]
+#[get("/about/upstream-queue/<token>")]
+pub fn upstream_queue(token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(content::Json(serde_json::to_string(
+        &upstream_queue::stats(),
+    )?))
+}
+
+#[doc(hidden)]
+#[get("/about/payload-limits/<token>")]
+pub fn payload_limits(token: String) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    Ok(content::Json(serde_json::to_string(
+        &payload_limits::stats(),
+    )?))
 }
@@ -1,8 +1,9 @@
 extern crate dotenv;
 
 use crate::cache::redis::create_service_cache;
-use crate::cache::{Cache, MockCache};
+use crate::cache::{Cache, MockCache, PoolStats};
 use crate::config::{build_number, chain_info_request_timeout, version, webhook_token};
+use crate::monitoring::slo::SloMonitor;
 use crate::routes::about::models::{About, ChainAbout};
 use crate::routes::safes::models::Implementation;
 use crate::utils::http_client::{HttpClient, MockHttpClient, Request, Response};
@@ -46,6 +47,8 @@ fn setup_rocket_with_mock_cache(
                 super::super::routes::get_about,
                 super::super::routes::get_chains_about,
                 super::super::routes::redis,
+                super::super::routes::cache_pool,
+                super::super::routes::metrics,
                 super::super::routes::get_master_copies,
             ],
         )
@@ -252,7 +255,7 @@ async fn get_redis() {
         .await
         .expect("valid rocket instance");
     let response = {
-        let mut response = client.get(format!("/about/redis/{}", webhook_token()));
+        let mut response = client.get(format!("/about/redis/{}", webhook_token().unwrap()));
         response.add_header(Header::new("Host", "test.gnosis.io"));
         response.dispatch().await
     };
@@ -262,3 +265,83 @@ async fn get_redis() {
     assert_eq!(response.status(), Status::Ok);
     assert_eq!(response.into_string().await.unwrap(), expected);
 }
+
+#[rocket::async_test]
+async fn get_cache_pool() {
+    let mock_cache = {
+        let mut mock_cache = MockCache::new();
+        mock_cache
+            .expect_pool_stats()
+            .times(1)
+            .return_once(move || {
+                Some(PoolStats {
+                    max_size: 15,
+                    connections: 4,
+                    in_use: 3,
+                    idle: 1,
+                    waiting: 0,
+                    checkout_timeouts: 2,
+                    checkout_errors: 0,
+                })
+            });
+        mock_cache
+    };
+
+    let client = Client::tracked(setup_rocket_with_mock_cache(
+        MockHttpClient::new(),
+        mock_cache,
+    ))
+    .await
+    .expect("valid rocket instance");
+    let response = {
+        let mut response = client.get(format!("/about/cache-pool/{}", webhook_token().unwrap()));
+        response.add_header(Header::new("Host", "test.gnosis.io"));
+        response.dispatch().await
+    };
+
+    let expected = serde_json::json!({
+        "maxSize": 15,
+        "connections": 4,
+        "inUse": 3,
+        "idle": 1,
+        "waiting": 0,
+        "checkoutTimeouts": 2,
+        "checkoutErrors": 0,
+    });
+
+    assert_eq!(response.status(), Status::Ok);
+    let actual: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[rocket::async_test]
+async fn get_metrics_reports_served_routes() {
+    let client = Client::tracked(
+        setup_rocket_with_mock_cache(MockHttpClient::new(), MockCache::new()).attach(SloMonitor()),
+    )
+    .await
+    .expect("valid rocket instance");
+    let about_response = {
+        let mut response = client.get("/about");
+        response.add_header(Header::new("Host", "test.gnosis.io"));
+        response.dispatch().await
+    };
+    assert_eq!(about_response.status(), Status::Ok);
+
+    let response = {
+        let mut response = client.get(format!("/about/metrics/{}", webhook_token().unwrap()));
+        response.add_header(Header::new("Host", "test.gnosis.io"));
+        response.dispatch().await
+    };
+
+    assert_eq!(response.status(), Status::Ok);
+    let actual: Vec<serde_json::Value> =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    let about_slo = actual
+        .iter()
+        .find(|route_slo| route_slo["route"] == "GET /about")
+        .expect("GET /about is tracked");
+    assert!(about_slo["requests"].as_u64().unwrap() >= 1);
+    assert!(about_slo["p95Latency"].is_u64());
+}
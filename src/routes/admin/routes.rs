@@ -0,0 +1,508 @@
+use crate::cache::invalidation_log;
+use crate::cache::migration::{self, CacheMigrationRequest};
+use crate::cache::snapshot::{self, CacheSnapshot};
+use crate::config::feature_flag_usage_tracking;
+use crate::monitoring::audit::{self, AuditOperation, Caller};
+use crate::monitoring::dashboard;
+use crate::monitoring::usage::{self, UsageWindow};
+use crate::providers::token_overrides::{self, TokenOverride};
+use crate::routes::transactions::handlers::expiry;
+use crate::routes::transactions::handlers::ready_callbacks::{self, ReadyCallback};
+use crate::utils::auth::{self, Scope};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::read_only::{self, ReadOnlyMode};
+use chrono::Utc;
+use rocket::data::{Data, ToByteUnit};
+use rocket::response::content;
+use rocket::serde::json::{Error, Json};
+use std::cmp::min;
+
+// Token lists of every chain easily exceed the default json limit
+const MAX_SNAPSHOT_MEBIBYTES: u64 = 64;
+const MAX_USAGE_CONSUMERS: usize = 100;
+const MAX_INVALIDATIONS: usize = 1000;
+
+/**
+ * `/admin/export/chains/<token>` <br />
+ * Returns a [CacheSnapshot](crate::cache::snapshot::CacheSnapshot)
+ *
+ * Dumps the cached chain configurations, token lists and master copies of this instance, to be
+ * imported by new instances via `/admin/import/chains/<token>`. Only available to keys with the
+ * `exports` scope.
+ */
+#[get("/admin/export/chains/<token>")]
+pub async fn get_chains_export(
+    context: RequestContext,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Exports)?;
+    let snapshot = snapshot::export(context.cache().as_ref()).await;
+    Ok(content::Json(serde_json::to_string(&snapshot)?))
+}
+
+/**
+ * `/admin/import/chains/<token>` <br />
+ * Returns a [SnapshotImport](crate::cache::snapshot::SnapshotImport)
+ *
+ * Warms the cache with a [CacheSnapshot](crate::cache::snapshot::CacheSnapshot) exported by
+ * another instance. Entries keep the time to live they had when exported and entries already
+ * cached by this instance are not overwritten.
+ */
+#[post("/admin/import/chains/<token>", format = "json", data = "<data>")]
+pub async fn post_chains_import(
+    context: RequestContext,
+    caller: Caller,
+    token: String,
+    data: Data<'_>,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let body = data
+        .open(MAX_SNAPSHOT_MEBIBYTES.mebibytes())
+        .into_string()
+        .await
+        .map_err(|error| api_error!("Could not read snapshot: {}", error))?;
+    if !body.is_complete() {
+        return Err(client_error!(413, "Snapshot is too large"));
+    }
+    let body = body.into_inner();
+    let payload_hash = audit::payload_hash(&body);
+    let result = match serde_json::from_str::<CacheSnapshot>(&body) {
+        Ok(snapshot) => Ok(snapshot::import(context.cache().as_ref(), &snapshot).await),
+        Err(error) => Err(ApiError::new_from_message_with_code(422, error.to_string())),
+    };
+    audit::record(
+        AuditOperation::CacheImport,
+        "*",
+        &caller,
+        payload_hash,
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/admin/usage/<chain_id>/<safe_address>/<token>` <br />
+ * Returns a [SafeUsage](crate::monitoring::usage::SafeUsage)
+ *
+ * Requests served for the Safe within the rolling `USAGE_WINDOW`, per bucket of `USAGE_BUCKET`
+ * ms. Only available to keys with the `diagnostics` scope and with
+ * `FEATURE_FLAG_USAGE_TRACKING` enabled.
+ */
+#[get("/admin/usage/<chain_id>/<safe_address>/<token>")]
+pub async fn get_safe_usage(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    if !feature_flag_usage_tracking() {
+        return Err(client_error!(503, "Usage tracking is not enabled"));
+    }
+    let safe_usage = usage::safe_usage(
+        context.cache().as_ref(),
+        &chain_id,
+        &safe_address,
+        Utc::now().timestamp_millis(),
+        &UsageWindow::from_config(),
+    )
+    .await;
+    Ok(content::Json(serde_json::to_string(&safe_usage)?))
+}
+
+/**
+ * `/admin/usage/top/<token>?<limit>` <br />
+ * Returns a [UsageReport](crate::monitoring::usage::UsageReport)
+ *
+ * The Safes with the most requests within the rolling `USAGE_WINDOW`, across chains. `<limit>`
+ * defaults to 20 and is capped at 100.
+ */
+#[get("/admin/usage/top/<token>?<limit>")]
+pub async fn get_top_consumers(
+    context: RequestContext,
+    token: String,
+    limit: Option<usize>,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    if !feature_flag_usage_tracking() {
+        return Err(client_error!(503, "Usage tracking is not enabled"));
+    }
+    let report = usage::top_consumers(
+        context.cache().as_ref(),
+        Utc::now().timestamp_millis(),
+        &UsageWindow::from_config(),
+        min(limit.unwrap_or(20), MAX_USAGE_CONSUMERS),
+    )
+    .await;
+    Ok(content::Json(serde_json::to_string(&report)?))
+}
+
+/**
+ * `/admin/queued/purge/<chain_id>/<safe_address>/<token>` <br />
+ * Returns an [ExpiredPurge](crate::routes::transactions::handlers::expiry::ExpiredPurge)
+ *
+ * Drops the cached entries of the Safe's queued transactions that are `EXPIRED` or `OBSOLETE`
+ * under the expiry policy (`TX_QUEUED_EXPIRY_DAYS`, `FEATURE_FLAG_QUEUED_TX_OBSOLETE`), along
+ * with its cached transaction lists. Responds with 503 if no policy is configured.
+ */
+#[post("/admin/queued/purge/<chain_id>/<safe_address>/<token>")]
+pub async fn post_queued_purge(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    safe_address: String,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let result = expiry::purge_expired_transactions(&context, &chain_id, &safe_address).await;
+    audit::record(
+        AuditOperation::QueuePurge,
+        &safe_address,
+        &caller,
+        audit::payload_hash(&chain_id),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/admin/invalidations/<token>?<since>&<limit>` <br />
+ * Returns a list of [InvalidationEntry](crate::cache::invalidation_log::InvalidationEntry)
+ *
+ * Cache invalidations recorded from `<since>` (timestamp in ms, defaults to the oldest entry
+ * kept) on, oldest first. `<limit>` defaults to 100 and is capped at 1000. The log keeps about
+ * the last `INVALIDATION_LOG_SIZE` invalidations.
+ */
+#[get("/admin/invalidations/<token>?<since>&<limit>")]
+pub async fn get_invalidations(
+    context: RequestContext,
+    token: String,
+    since: Option<i64>,
+    limit: Option<usize>,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    let invalidations = invalidation_log::read(
+        context.cache().as_ref(),
+        since.unwrap_or(0),
+        min(limit.unwrap_or(100), MAX_INVALIDATIONS),
+    )
+    .await;
+    Ok(content::Json(serde_json::to_string(&invalidations)?))
+}
+
+/**
+ * `/admin/tokens/<chain_id>/<token>` <br />
+ * Returns a map of [TokenOverride](crate::providers::token_overrides::TokenOverride) by token
+ * address
+ *
+ * Token metadata overrides of the chain, set via `/admin/tokens/<chain_id>/<token_address>/<token>`.
+ */
+#[get("/admin/tokens/<chain_id>/<token>")]
+pub async fn get_token_overrides(
+    context: RequestContext,
+    chain_id: String,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let overrides = token_overrides::chain_overrides(context.cache().as_ref(), &chain_id).await;
+    Ok(content::Json(serde_json::to_string(&overrides)?))
+}
+
+/**
+ * `/admin/tokens/<chain_id>/<token_address>/<token>` <br />
+ * Returns a map of [TokenOverride](crate::providers::token_overrides::TokenOverride) by token
+ * address
+ *
+ * Replaces the metadata override of the token, merged over the token info of the transaction
+ * service in every response from then on. Fields left out keep the upstream value, `spam`
+ * replaces the spam classification of balances. An empty body removes the override.
+ *
+ * Example request body:
+ *
+ * ```json
+ * {
+ *   "symbol": "USDC.e",
+ *   "decimals": 6,
+ *   "logoUri": "https://example.com/usdc.png",
+ *   "spam": false
+ * }
+ * ```
+ */
+#[put(
+    "/admin/tokens/<chain_id>/<token_address>/<token>",
+    format = "json",
+    data = "<token_override>"
+)]
+pub async fn put_token_override<'e>(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    token_address: String,
+    token: String,
+    token_override: Result<Json<TokenOverride>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let token_override = token_override?.0;
+    let result = token_overrides::set_override(
+        context.cache().as_ref(),
+        &chain_id,
+        &token_address,
+        &token_override,
+    )
+    .await;
+    audit::record(
+        AuditOperation::TokenOverride,
+        &token_address,
+        &caller,
+        audit::payload_hash(&token_override),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/admin/tokens/<chain_id>/<token_address>/<token>` <br />
+ * Returns a map of [TokenOverride](crate::providers::token_overrides::TokenOverride) by token
+ * address
+ *
+ * Removes the metadata override of the token.
+ */
+#[delete("/admin/tokens/<chain_id>/<token_address>/<token>")]
+pub async fn delete_token_override(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    token_address: String,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let token_override = TokenOverride::default();
+    let result = token_overrides::set_override(
+        context.cache().as_ref(),
+        &chain_id,
+        &token_address,
+        &token_override,
+    )
+    .await;
+    audit::record(
+        AuditOperation::TokenOverride,
+        &token_address,
+        &caller,
+        audit::payload_hash(&token_override),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/admin/callbacks/<chain_id>/<safe_address>/<token>` <br />
+ * Returns the [ReadyCallback](crate::routes::transactions::handlers::ready_callbacks::ReadyCallback)
+ * of the Safe, `null` if there is none
+ */
+#[get("/admin/callbacks/<chain_id>/<safe_address>/<token>")]
+pub async fn get_ready_callback(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Hooks)?;
+    let callback =
+        ready_callbacks::get_callback(context.cache().as_ref(), &chain_id, &safe_address).await;
+    Ok(content::Json(serde_json::to_string(&callback)?))
+}
+
+/**
+ * `/admin/callbacks/<chain_id>/<safe_address>/<token>` <br />
+ * Returns the registered [ReadyCallback](crate::routes::transactions::handlers::ready_callbacks::ReadyCallback)
+ *
+ * Registers the url an integrator is notified at, with a `READY_TO_EXECUTE` event, once a queued
+ * transaction of the Safe has all required confirmations (requires
+ * `FEATURE_FLAG_READY_CALLBACKS`). Replaces the previous callback of the Safe.
+ *
+ * Example request body:
+ *
+ * ```json
+ * {
+ *   "url": "https://bot.example.com/safe-events"
+ * }
+ * ```
+ */
+#[put(
+    "/admin/callbacks/<chain_id>/<safe_address>/<token>",
+    format = "json",
+    data = "<callback>"
+)]
+pub async fn put_ready_callback<'e>(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    safe_address: String,
+    token: String,
+    callback: Result<Json<ReadyCallback>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Hooks)?;
+    let callback = callback?.0;
+    let result = ready_callbacks::set_callback(
+        context.cache().as_ref(),
+        &chain_id,
+        &safe_address,
+        Some(&callback),
+    )
+    .await;
+    audit::record(
+        AuditOperation::ReadyCallback,
+        &safe_address,
+        &caller,
+        audit::payload_hash(&callback),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/admin/callbacks/<chain_id>/<safe_address>/<token>` <br />
+ *
+ * Removes the callback of the Safe.
+ */
+#[delete("/admin/callbacks/<chain_id>/<safe_address>/<token>")]
+pub async fn delete_ready_callback(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    safe_address: String,
+    token: String,
+) -> ApiResult<()> {
+    auth::authorize(&token, Scope::Hooks)?;
+    let result =
+        ready_callbacks::set_callback(context.cache().as_ref(), &chain_id, &safe_address, None)
+            .await;
+    audit::record(
+        AuditOperation::ReadyCallback,
+        &safe_address,
+        &caller,
+        audit::payload_hash(&chain_id),
+        &result,
+    );
+    result.map(|_| ())
+}
+
+/**
+ * `/admin/cache/migrations/<token>` <br />
+ * Returns a [CacheMigration](crate::cache::migration::CacheMigration)
+ *
+ * Starts copying the cached requests and responses of the namespace of the request (e.g. the
+ * `CACHE_NAMESPACE` of the previous deployment) into the namespace of this instance, in the
+ * background. Only one migration runs at a time.
+ */
+#[post(
+    "/admin/cache/migrations/<token>",
+    format = "json",
+    data = "<migration_request>"
+)]
+pub async fn post_cache_migration<'e>(
+    context: RequestContext,
+    caller: Caller,
+    token: String,
+    migration_request: Result<Json<CacheMigrationRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let migration_request = migration_request?.0;
+    let result = migration::start(context.cache(), &migration_request.from_namespace).await;
+    audit::record(
+        AuditOperation::CacheMigration,
+        "*",
+        &caller,
+        audit::payload_hash(&migration_request.from_namespace),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/admin/cache/migrations/<token>` <br />
+ * Returns a [CacheMigration](crate::cache::migration::CacheMigration)
+ *
+ * Progress of the running cache migration, or the result of the last one.
+ */
+#[get("/admin/cache/migrations/<token>")]
+pub async fn get_cache_migration(
+    context: RequestContext,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let migration = migration::status(context.cache().as_ref())
+        .await
+        .ok_or_else(|| client_error!(404, "No cache migration was started"))?;
+    Ok(content::Json(serde_json::to_string(&migration)?))
+}
+
+/**
+ * `/admin/read-only/<token>` <br />
+ * Returns the current [ReadOnlyMode](crate::utils::read_only::ReadOnlyMode)
+ */
+#[get("/admin/read-only/<token>")]
+pub async fn get_read_only_mode(
+    context: RequestContext,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let mode = read_only::current(context.cache().as_ref()).await;
+    Ok(content::Json(serde_json::to_string(&mode)?))
+}
+
+/**
+ * `/admin/read-only/<token>` <br />
+ * Returns the new [ReadOnlyMode](crate::utils::read_only::ReadOnlyMode)
+ *
+ * Switches the read-only mode of every instance sharing the cache, overriding `READ_ONLY_MODE`.
+ * While enabled, write routes are answered with a 503 and the `message` (or a default
+ * maintenance message).
+ *
+ * Example request body:
+ *
+ * ```json
+ * {
+ *   "enabled": true,
+ *   "message": "Maintenance of the transaction service until 14:00 UTC"
+ * }
+ * ```
+ */
+#[put("/admin/read-only/<token>", format = "json", data = "<mode>")]
+pub async fn put_read_only_mode<'e>(
+    context: RequestContext,
+    caller: Caller,
+    token: String,
+    mode: Result<Json<ReadOnlyMode>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let mode = mode?.0;
+    let result = read_only::set(context.cache().as_ref(), &mode).await;
+    audit::record(
+        AuditOperation::ReadOnlyMode,
+        "*",
+        &caller,
+        audit::payload_hash(&mode),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/admin/dashboard/<token>` <br />
+ * Returns a [Dashboard](crate::monitoring::dashboard::Dashboard)
+ *
+ * Snapshot of the health of this instance: rolling upstream latency per chain, the circuit of
+ * every upstream with a fallback, cache hit rates and Redis memory, queue depths and the error
+ * counts within the SLO window.
+ */
+#[get("/admin/dashboard/<token>")]
+pub async fn get_dashboard(
+    context: RequestContext,
+    token: String,
+) -> ApiResult<content::Json<String>> {
+    auth::authorize(&token, Scope::Diagnostics)?;
+    let dashboard = dashboard::snapshot(context.cache().as_ref()).await;
+    Ok(content::Json(serde_json::to_string(&dashboard)?))
+}
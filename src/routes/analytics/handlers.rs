@@ -0,0 +1,147 @@
+use crate::config::{
+    analytics_batch_size, analytics_buffer_size, analytics_flush_interval, analytics_sink_uri,
+};
+use crate::routes::analytics::models::{
+    AnalyticsEvent, AnalyticsEventsRequest, AnalyticsEventsResponse,
+};
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{HttpClient, Request};
+use crate::utils::validation::{Validate, Validator};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+lazy_static! {
+    static ref ANALYTICS_BUFFER: EventBuffer = EventBuffer::default();
+}
+
+#[derive(Serialize, Debug)]
+struct AnalyticsBatch<'e> {
+    events: &'e [AnalyticsEvent],
+}
+
+/// Events are buffered and forwarded in batches by a background flush, so that clients are
+/// answered right away. Once the buffer is full (e.g. the sink is down) events are rejected with
+/// a 429, telling clients to keep them and retry later.
+pub fn post_events(
+    http_client: Arc<dyn HttpClient>,
+    request: AnalyticsEventsRequest,
+) -> ApiResult<AnalyticsEventsResponse> {
+    let sink_uri =
+        analytics_sink_uri().ok_or(client_error!(503, "Analytics ingestion is not enabled"))?;
+    let mut validator = Validator::default();
+    request.validate(&mut validator);
+    validator.check(
+        "events",
+        !request.events.is_empty() && request.events.len() <= analytics_batch_size(),
+        "must contain between 1 and ANALYTICS_BATCH_SIZE events",
+    );
+    validator.finish()?;
+
+    let accepted = request.events.len();
+    if !ANALYTICS_BUFFER.push(request.events, analytics_buffer_size()) {
+        return Err(client_error!(429, "Analytics buffer is full, retry later"));
+    }
+    if ANALYTICS_BUFFER.schedule_flush() {
+        rocket::tokio::spawn(flush_events(http_client, sink_uri));
+    }
+    Ok(AnalyticsEventsResponse { accepted })
+}
+
+/// Forwards everything buffered once the flush interval passed. Batches the sink fails to accept
+/// are put back and retried with the next flush.
+async fn flush_events(http_client: Arc<dyn HttpClient>, sink_uri: String) {
+    loop {
+        rocket::tokio::time::sleep(Duration::from_millis(analytics_flush_interval())).await;
+        loop {
+            let batch = ANALYTICS_BUFFER.take_batch(analytics_batch_size());
+            if batch.is_empty() {
+                break;
+            }
+            if let Err(error) = send_batch(&http_client, &sink_uri, &batch).await {
+                log::warn!(
+                    "Forwarding {} analytics events failed: {}",
+                    batch.len(),
+                    error
+                );
+                ANALYTICS_BUFFER.requeue(batch, analytics_buffer_size());
+                break;
+            }
+        }
+        ANALYTICS_BUFFER.flush_done();
+        // Events pushed while finishing, or put back after a failure, need another flush
+        if ANALYTICS_BUFFER.is_empty() || !ANALYTICS_BUFFER.schedule_flush() {
+            return;
+        }
+    }
+}
+
+/// Events waiting to be forwarded to the sink
+pub fn buffered_events() -> usize {
+    ANALYTICS_BUFFER.len()
+}
+
+async fn send_batch(
+    http_client: &Arc<dyn HttpClient>,
+    sink_uri: &str,
+    events: &[AnalyticsEvent],
+) -> ApiResult<()> {
+    let mut request = Request::new(sink_uri.to_string());
+    request.body(Some(serde_json::to_string(&AnalyticsBatch { events })?));
+    http_client.post(request).await?;
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct EventBuffer {
+    events: Mutex<VecDeque<AnalyticsEvent>>,
+    flush_scheduled: AtomicBool,
+}
+
+impl EventBuffer {
+    /// All or nothing, `false` if there is no room for every event
+    pub fn push(&self, events: Vec<AnalyticsEvent>, capacity: usize) -> bool {
+        let mut buffered = self.events.lock().unwrap();
+        if buffered.len() + events.len() > capacity {
+            return false;
+        }
+        buffered.extend(events);
+        true
+    }
+
+    /// Oldest events first
+    pub fn take_batch(&self, batch_size: usize) -> Vec<AnalyticsEvent> {
+        let mut buffered = self.events.lock().unwrap();
+        let batch_size = batch_size.min(buffered.len());
+        buffered.drain(..batch_size).collect()
+    }
+
+    /// Puts a batch back in front, dropping its newest events if they don't fit anymore
+    pub fn requeue(&self, batch: Vec<AnalyticsEvent>, capacity: usize) {
+        let mut buffered = self.events.lock().unwrap();
+        let room = capacity.saturating_sub(buffered.len());
+        for event in batch.into_iter().take(room).rev() {
+            buffered.push_front(event);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `true` if no flush is scheduled yet, the caller is then in charge of it
+    pub fn schedule_flush(&self) -> bool {
+        !self.flush_scheduled.swap(true, Ordering::SeqCst)
+    }
+
+    pub fn flush_done(&self) {
+        self.flush_scheduled.store(false, Ordering::SeqCst);
+    }
+}
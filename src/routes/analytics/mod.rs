@@ -0,0 +1,7 @@
+#[doc(hidden)]
+pub mod handlers;
+pub mod models;
+pub mod routes;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,95 @@
+use crate::utils::validation::{Validate, Validator};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const MAX_PROPERTIES: usize = 20;
+
+lazy_static! {
+    // Addresses, hashes and signatures would make the events attributable to a user
+    static ref HEX_IDENTIFIER: Regex = Regex::new(r"(?i)0x[0-9a-f]{40}").unwrap();
+}
+
+/// <summary>Example body of AnalyticsEventsRequest</summary>
+///
+/// ```json
+/// {
+///   "events": [
+///     {
+///       "kind": "SCREEN_VIEW",
+///       "name": "transactions_queue",
+///       "timestamp": 1633430400000,
+///       "properties": {
+///         "platform": "android",
+///         "appVersion": "3.1.0"
+///       }
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsEventsRequest {
+    pub events: Vec<AnalyticsEvent>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsEvent {
+    pub kind: AnalyticsEventKind,
+    pub name: String,
+    /// Time at which the event happened on the client, in ms
+    pub timestamp: i64,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnalyticsEventKind {
+    ScreenView,
+    FeatureUsage,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsEventsResponse {
+    pub accepted: usize,
+}
+
+impl Validate for AnalyticsEventsRequest {
+    fn validate(&self, validator: &mut Validator) {
+        validator.each("events", &self.events, |validator, field, event| {
+            validator.nested(field, event);
+        });
+    }
+}
+
+impl Validate for AnalyticsEvent {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .length("name", &self.name, 1, 64)
+            .check(
+                "timestamp",
+                self.timestamp > 0,
+                "must be a positive timestamp",
+            )
+            .check(
+                "properties",
+                self.properties.len() <= MAX_PROPERTIES,
+                "must have at most 20 entries",
+            );
+        for (key, value) in self.properties.iter() {
+            let field = format!("properties.{}", key);
+            validator
+                .length(&field, key, 1, 64)
+                .length(&field, value, 0, 256)
+                .check(
+                    &field,
+                    !HEX_IDENTIFIER.is_match(value),
+                    "must not contain addresses or hashes",
+                );
+        }
+    }
+}
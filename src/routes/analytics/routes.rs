@@ -0,0 +1,37 @@
+use crate::routes::analytics::handlers;
+use crate::routes::analytics::models::AnalyticsEventsRequest;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use rocket::response::content;
+use rocket::serde::json::Error;
+use rocket::serde::json::Json;
+
+/**
+ * `/v1/analytics/events` <br />
+ * Returns [AnalyticsEventsResponse](crate::routes::analytics::models::AnalyticsEventsResponse)
+ *
+ * # Analytics events
+ *
+ * Accepts a batch of anonymous client events (screen views, feature usage), forwarded to the sink configured with `ANALYTICS_SINK_URI`. Batches hold up to `ANALYTICS_BATCH_SIZE` events; properties must not contain addresses or hashes.
+ *
+ * Events are buffered and forwarded at least every `ANALYTICS_FLUSH_INTERVAL` ms. While the buffer is full requests fail with `429` and should be retried later.
+ *
+ * ## Path
+ *
+ * `POST /v1/analytics/events`
+ *
+ * The expected [crate::routes::analytics::models::AnalyticsEventsRequest] body for this request can be found in the sections of the models
+ */
+#[post(
+    "/v1/analytics/events",
+    format = "application/json",
+    data = "<events_request>"
+)]
+pub async fn post_analytics_events<'e>(
+    context: RequestContext,
+    events_request: Result<Json<AnalyticsEventsRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &handlers::post_events(context.http_client(), events_request?.0)?,
+    )?))
+}
@@ -0,0 +1,107 @@
+use crate::routes::analytics::handlers::EventBuffer;
+use crate::routes::analytics::models::{
+    AnalyticsEvent, AnalyticsEventKind, AnalyticsEventsRequest,
+};
+use crate::utils::validation::{Validate, Validator};
+
+fn event(name: &str) -> AnalyticsEvent {
+    AnalyticsEvent {
+        kind: AnalyticsEventKind::ScreenView,
+        name: name.to_string(),
+        timestamp: 1633430400000,
+        properties: Default::default(),
+    }
+}
+
+fn names(events: &[AnalyticsEvent]) -> Vec<&str> {
+    events.iter().map(|event| event.name.as_str()).collect()
+}
+
+#[test]
+fn event_buffer_push_all_or_nothing() {
+    let buffer = EventBuffer::default();
+
+    assert!(buffer.push(vec![event("a"), event("b")], 3));
+    assert!(!buffer.push(vec![event("c"), event("d")], 3));
+    assert!(buffer.push(vec![event("c")], 3));
+
+    assert_eq!(names(&buffer.take_batch(10)), vec!["a", "b", "c"]);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn event_buffer_take_batch_oldest_first() {
+    let buffer = EventBuffer::default();
+    buffer.push(vec![event("a"), event("b"), event("c")], 10);
+
+    assert_eq!(names(&buffer.take_batch(2)), vec!["a", "b"]);
+    assert_eq!(buffer.len(), 1);
+}
+
+#[test]
+fn event_buffer_requeue_in_front_within_capacity() {
+    let buffer = EventBuffer::default();
+    buffer.push(vec![event("a"), event("b")], 10);
+    let batch = buffer.take_batch(2);
+    buffer.push(vec![event("c"), event("d")], 10);
+
+    buffer.requeue(batch, 3);
+
+    assert_eq!(names(&buffer.take_batch(10)), vec!["a", "c", "d"]);
+}
+
+#[test]
+fn event_buffer_single_scheduled_flush() {
+    let buffer = EventBuffer::default();
+
+    assert!(buffer.schedule_flush());
+    assert!(!buffer.schedule_flush());
+    buffer.flush_done();
+    assert!(buffer.schedule_flush());
+}
+
+#[test]
+fn analytics_events_request_reports_every_invalid_field() {
+    let mut identifying_event = event("");
+    identifying_event.timestamp = 0;
+    identifying_event.properties.insert(
+        "recipient".to_string(),
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+    );
+    let request = AnalyticsEventsRequest {
+        events: vec![event("transactions_queue"), identifying_event],
+    };
+
+    let mut validator = Validator::default();
+    request.validate(&mut validator);
+
+    assert_eq!(
+        validator.errors(),
+        &[
+            "events[1].name: must be between 1 and 64 characters",
+            "events[1].timestamp: must be a positive timestamp",
+            "events[1].properties.recipient: must not contain addresses or hashes",
+        ]
+    );
+}
+
+#[test]
+fn analytics_events_request_deserialize() {
+    let request = serde_json::from_str::<AnalyticsEventsRequest>(
+        r#"{
+        "events": [
+            {
+                "kind": "FEATURE_USAGE",
+                "name": "address_book_export",
+                "timestamp": 1633430400000
+            }
+        ]
+    }"#,
+    )
+    .unwrap();
+
+    let mut expected = event("address_book_export");
+    expected.kind = AnalyticsEventKind::FeatureUsage;
+    assert_eq!(request.events, vec![expected]);
+    assert!(request.validated().is_ok());
+}
@@ -0,0 +1,4 @@
+pub mod routes;
+
+#[cfg(test)]
+mod tests;
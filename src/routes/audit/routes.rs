@@ -0,0 +1,24 @@
+use crate::monitoring::audit::read_entries;
+use crate::utils::auth::DiagnosticsKey;
+use crate::utils::errors::ApiResult;
+use rocket::response::content;
+use std::cmp::min;
+
+const MAX_AUDIT_ENTRIES: usize = 1000;
+
+/**
+ * `/v1/audit?<operation>&<limit>` <br />
+ * Returns the most recent [AuditEntry](crate::monitoring::audit::AuditEntry) items, newest first
+ *
+ * Requires a key with the `diagnostics` scope in the `X-Api-Key` header. `<operation>` filters by
+ * operation (e.g. `PROPOSE_TRANSACTION`), `<limit>` defaults to 100 and is capped at 1000.
+ */
+#[get("/v1/audit?<operation>&<limit>")]
+pub async fn get_audit_entries(
+    _key: DiagnosticsKey,
+    operation: Option<String>,
+    limit: Option<usize>,
+) -> ApiResult<content::Json<String>> {
+    let entries = read_entries(operation, min(limit.unwrap_or(100), MAX_AUDIT_ENTRIES)).await?;
+    Ok(content::Json(serde_json::to_string(&entries)?))
+}
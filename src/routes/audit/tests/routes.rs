@@ -0,0 +1,40 @@
+use crate::config::webhook_token;
+use crate::utils::auth::API_KEY_HEADER;
+use dotenv::dotenv;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+
+async fn client() -> Client {
+    dotenv().ok();
+    Client::tracked(rocket::build().mount("/", routes![super::super::routes::get_audit_entries]))
+        .await
+        .expect("valid rocket instance")
+}
+
+#[rocket::async_test]
+async fn get_audit_entries_requires_diagnostics_key_header() {
+    let client = client().await;
+
+    let response = client.get("/v1/audit").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client
+        .get("/v1/audit")
+        .header(Header::new(API_KEY_HEADER, "not_a_key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+async fn get_audit_entries_accepts_diagnostics_key_header() {
+    let client = client().await;
+
+    let response = client
+        .get("/v1/audit?limit=1")
+        .header(Header::new(API_KEY_HEADER, webhook_token().unwrap()))
+        .dispatch()
+        .await;
+
+    assert_ne!(response.status(), Status::Unauthorized);
+}
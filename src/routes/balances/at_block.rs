@@ -0,0 +1,142 @@
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, TokenInfo, TokenType};
+use crate::providers::rpc::{EthCall, RpcProvider};
+use crate::routes::balances::history;
+use crate::routes::balances::models::{Balance, Balances};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::transactions::{decode_hex, parse_address};
+use bigdecimal::{num_bigint::BigInt, BigDecimal, Zero};
+use ethabi::{ParamType, Token, Uint};
+use ethcontract_common::hash::keccak256;
+use rocket::futures::future::join_all;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+pub const ERC20_BALANCE_OF_SIGNATURE: &str = "balanceOf(address)";
+
+/// Balances of the Safe at `block`, read from the chain via `eth_call` instead of the current
+/// state of the transaction service. The tokens are the ones currently listed for the Safe, and
+/// fiat values use the current prices, as there is no source for past prices.
+///
+/// Past state is only served by archive nodes, requests failing on the node are answered with
+/// a 422.
+pub async fn balances_at_block(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    fiat: &str,
+    trusted: bool,
+    exclude_spam: bool,
+    block: u64,
+) -> ApiResult<Balances> {
+    let current =
+        history::current_balances(context, chain_id, safe_address, fiat, trusted, exclude_spam)
+            .await?;
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let rpc_provider = RpcProvider::new(context, &info_provider.chain_info().await?);
+    let block_tag = format!("{:#x}", block);
+
+    // Also tells whether the node has the state of the block at all
+    let native_balance = rpc_provider
+        .balance_at(safe_address, &block_tag)
+        .await?
+        .map_err(|error| {
+            ApiError::new_from_message_with_code(
+                422,
+                format!(
+                    "Balances at block {} are not available on chain {}: {}",
+                    block, chain_id, error.message
+                ),
+            )
+        })?;
+    let amounts = join_all(current.items.iter().map(|balance| {
+        read_amount(
+            &rpc_provider,
+            &balance.token_info,
+            safe_address,
+            &block_tag,
+            native_balance,
+        )
+    }))
+    .await;
+
+    let mut items = vec![];
+    for (balance, amount) in current.items.into_iter().zip(amounts) {
+        let amount = amount?;
+        if !amount.is_zero() {
+            items.push(balance_with_amount(balance, &amount));
+        }
+    }
+    Ok(sorted_balances(items))
+}
+
+/// Tokens that revert (e.g. not deployed yet at the block) count as a zero balance
+async fn read_amount(
+    rpc_provider: &RpcProvider,
+    token_info: &TokenInfo,
+    owner: &str,
+    block: &str,
+    native_balance: Uint,
+) -> ApiResult<Uint> {
+    if token_info.token_type == TokenType::NativeToken {
+        return Ok(native_balance);
+    }
+    let call = EthCall {
+        from: None,
+        to: token_info.address.to_string(),
+        data: balance_of_call_data(owner)?,
+        value: None,
+    };
+    match rpc_provider.call_at(&call, block).await? {
+        Ok(result) => Ok(decode_balance(&result).unwrap_or_else(Uint::zero)),
+        Err(_) => Ok(Uint::zero()),
+    }
+}
+
+pub fn balance_of_call_data(owner: &str) -> ApiResult<String> {
+    let mut encoded = keccak256(ERC20_BALANCE_OF_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[Token::Address(parse_address(owner)?)]));
+    Ok(to_hex_string!(encoded))
+}
+
+/// `None` if the return data is not a single uint256 (e.g. empty for accounts without code)
+pub fn decode_balance(result: &str) -> Option<Uint> {
+    let data = decode_hex(result).ok()?;
+    match ethabi::decode(&[ParamType::Uint(256)], &data).ok()?.pop()? {
+        Token::Uint(amount) => Some(amount),
+        _ => None,
+    }
+}
+
+/// `balance` with `amount` (in the smallest unit of the token), valued at its current
+/// `fiat_conversion`
+pub fn balance_with_amount(balance: Balance, amount: &Uint) -> Balance {
+    let amount = BigInt::from_str(&amount.to_string()).unwrap_or_else(|_| Zero::zero());
+    let token_balance = BigDecimal::new(amount.clone(), balance.token_info.decimals as i64);
+    let fiat_conversion =
+        BigDecimal::from_str(&balance.fiat_conversion).unwrap_or_else(|_| Zero::zero());
+    Balance {
+        balance: amount.to_string(),
+        fiat_balance: (token_balance * fiat_conversion).with_scale(5).to_string(),
+        ..balance
+    }
+}
+
+/// Sorted by fiat balance, highest first, with their fiat total
+pub fn sorted_balances(mut items: Vec<Balance>) -> Balances {
+    let fiat_total: BigDecimal = items
+        .iter()
+        .map(|balance| BigDecimal::from_str(&balance.fiat_balance).unwrap_or_else(|_| Zero::zero()))
+        .sum();
+    items.sort_by(|a, b| {
+        b.fiat_balance
+            .parse::<f64>()
+            .unwrap_or(0.0)
+            .partial_cmp(&a.fiat_balance.parse::<f64>().unwrap_or(0.0))
+            .unwrap_or(Ordering::Equal)
+    });
+    Balances {
+        fiat_total: fiat_total.with_scale(5).to_string(),
+        items,
+    }
+}
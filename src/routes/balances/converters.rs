@@ -1,7 +1,9 @@
 use crate::common::models::backend::balances::Balance as BalanceDto;
 use crate::common::models::backend::chains::NativeCurrency;
 use crate::providers::info::{TokenInfo, TokenType};
+use crate::providers::token_overrides::{find_override, TokenOverrides};
 use crate::routes::balances::models::Balance;
+use crate::utils::spam::is_spam_balance;
 
 impl BalanceDto {
     pub fn to_balance(&self, usd_to_fiat: f64, native_coin: &NativeCurrency) -> Balance {
@@ -18,33 +20,47 @@ impl BalanceDto {
         } else {
             self.token.as_ref().map(|it| it.logo_uri.to_string())
         };
+        let token_info = TokenInfo {
+            token_type,
+            address: self
+                .token_address
+                .to_owned()
+                .unwrap_or(String::from("0x0000000000000000000000000000000000000000")),
+            decimals: self
+                .token
+                .as_ref()
+                .map(|it| it.decimals)
+                .unwrap_or(native_coin.decimals),
+            symbol: self
+                .token
+                .as_ref()
+                .map(|it| it.symbol.to_string())
+                .unwrap_or(native_coin.symbol.to_string()),
+            name: self
+                .token
+                .as_ref()
+                .map(|it| it.name.to_string())
+                .unwrap_or(native_coin.name.to_string()),
+            logo_uri,
+        };
+        let spam = is_spam_balance(&token_info);
         Balance {
-            token_info: TokenInfo {
-                token_type,
-                address: self
-                    .token_address
-                    .to_owned()
-                    .unwrap_or(String::from("0x0000000000000000000000000000000000000000")),
-                decimals: self
-                    .token
-                    .as_ref()
-                    .map(|it| it.decimals)
-                    .unwrap_or(native_coin.decimals),
-                symbol: self
-                    .token
-                    .as_ref()
-                    .map(|it| it.symbol.to_string())
-                    .unwrap_or(native_coin.symbol.to_string()),
-                name: self
-                    .token
-                    .as_ref()
-                    .map(|it| it.name.to_string())
-                    .unwrap_or(native_coin.name.to_string()),
-                logo_uri,
-            },
+            token_info,
             balance: self.balance.to_owned(),
             fiat_balance: fiat_balance.to_string(),
             fiat_conversion: fiat_conversion.to_string(),
+            spam,
         }
     }
 }
+
+/// Token metadata and spam classification as corrected by operators
+pub fn with_token_override(mut balance: Balance, token_overrides: &TokenOverrides) -> Balance {
+    if let Some(token_override) = find_override(token_overrides, &balance.token_info.address) {
+        token_override.apply(&mut balance.token_info);
+        balance.spam = token_override
+            .spam
+            .unwrap_or_else(|| is_spam_balance(&balance.token_info));
+    }
+    balance
+}
@@ -1,11 +1,24 @@
 use crate::common::models::backend::balances_v2::Balance as BalanceDto;
 use crate::common::models::backend::chains::NativeCurrency;
 use crate::providers::info::{TokenInfo, TokenType};
+use crate::providers::token_overrides::{find_override, TokenOverrides};
 use crate::routes::balances::models::Balance;
+use crate::utils::spam::is_spam_balance;
 use bigdecimal::{num_bigint::BigInt, BigDecimal, ToPrimitive, Zero};
 use std::str::FromStr;
 
 impl BalanceDto {
+    /// Decimals corrected by operators, so that the fiat balance is computed with them
+    pub fn override_decimals(&mut self, token_overrides: &TokenOverrides) {
+        if let (Some(token_address), Some(token)) = (&self.token_address, self.token.as_mut()) {
+            if let Some(decimals) =
+                find_override(token_overrides, token_address).and_then(|it| it.decimals)
+            {
+                token.decimals = decimals;
+            }
+        }
+    }
+
     pub fn to_balance_v2(
         &self,
         token_to_usd: &BigDecimal,
@@ -36,33 +49,36 @@ impl BalanceDto {
             )
         };
 
+        let token_info = TokenInfo {
+            token_type,
+            address: self
+                .token_address
+                .to_owned()
+                .unwrap_or(String::from("0x0000000000000000000000000000000000000000")),
+            decimals: self
+                .token
+                .as_ref()
+                .map(|it| it.decimals)
+                .unwrap_or(native_coin.decimals),
+            symbol: self
+                .token
+                .as_ref()
+                .map(|it| it.symbol.to_string())
+                .unwrap_or(native_coin.symbol.to_string()),
+            name: self
+                .token
+                .as_ref()
+                .map(|it| it.name.to_string())
+                .unwrap_or(native_coin.name.to_string()),
+            logo_uri,
+        };
+        let spam = is_spam_balance(&token_info);
         Balance {
-            token_info: TokenInfo {
-                token_type,
-                address: self
-                    .token_address
-                    .to_owned()
-                    .unwrap_or(String::from("0x0000000000000000000000000000000000000000")),
-                decimals: self
-                    .token
-                    .as_ref()
-                    .map(|it| it.decimals)
-                    .unwrap_or(native_coin.decimals),
-                symbol: self
-                    .token
-                    .as_ref()
-                    .map(|it| it.symbol.to_string())
-                    .unwrap_or(native_coin.symbol.to_string()),
-                name: self
-                    .token
-                    .as_ref()
-                    .map(|it| it.name.to_string())
-                    .unwrap_or(native_coin.name.to_string()),
-                logo_uri,
-            },
+            token_info,
             balance: self.balance.to_owned(),
             fiat_balance: fiat_balance.to_string(),
             fiat_conversion: fiat_conversion.to_string(),
+            spam,
         }
     }
 }
@@ -0,0 +1,110 @@
+use crate::config::exchange_rate_tokens;
+use crate::providers::fiat::FiatInfoProvider;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, TokenInfo, TokenType};
+use crate::routes::balances::handlers_v2::get_token_usd_rate;
+use crate::routes::balances::models::{ExchangeRates, TokenExchangeRate};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use bigdecimal::BigDecimal;
+use rocket::futures::future::join_all;
+use std::collections::BTreeMap;
+
+pub const MAX_EXCHANGE_RATE_CURRENCIES: usize = 20;
+const NATIVE_COIN_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+// Enough for the rates of tokens worth fractions of a cent
+const RATE_SCALE: i64 = 8;
+
+/// Fiat value of the native coin and the `EXCHANGE_RATE_TOKENS` of the chain in every requested
+/// currency, so that clients can convert amounts without a price source of their own. Tokens
+/// without a price are left out, the native coin is always returned.
+pub async fn exchange_rates(
+    context: &RequestContext,
+    chain_id: &str,
+    currencies: Option<&str>,
+) -> ApiResult<ExchangeRates> {
+    let currencies = requested_currencies(currencies)?;
+    let fiat_info_provider = FiatInfoProvider::new(context);
+    let mut usd_to_currencies = vec![];
+    for currency in currencies {
+        let usd_to_currency = fiat_info_provider.exchange_usd_to(&currency).await?;
+        usd_to_currencies.push((currency, usd_to_currency));
+    }
+
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let native_currency = info_provider.chain_info().await?.native_currency;
+    let native_coin_price =
+        get_token_usd_rate(context, NATIVE_COIN_ADDRESS.to_string(), &info_provider).await?;
+    let mut items = vec![TokenExchangeRate {
+        token_info: TokenInfo {
+            token_type: TokenType::NativeToken,
+            address: NATIVE_COIN_ADDRESS.to_string(),
+            decimals: native_currency.decimals,
+            symbol: native_currency.symbol,
+            name: native_currency.name,
+            logo_uri: Some(native_currency.logo_uri),
+        },
+        rates: token_rates(&native_coin_price.fiat_price, &usd_to_currencies),
+    }];
+
+    let tokens = exchange_rate_tokens().remove(chain_id).unwrap_or_default();
+    let token_items = join_all(
+        tokens
+            .iter()
+            .map(|token| token_exchange_rate(context, &info_provider, token, &usd_to_currencies)),
+    )
+    .await;
+    items.extend(token_items.into_iter().flatten());
+    Ok(ExchangeRates { items })
+}
+
+async fn token_exchange_rate(
+    context: &RequestContext,
+    info_provider: &DefaultInfoProvider<'_>,
+    token: &str,
+    usd_to_currencies: &[(String, BigDecimal)],
+) -> Option<TokenExchangeRate> {
+    let token_info = info_provider.token_info(token).await.ok()?;
+    let price = get_token_usd_rate(context, token.to_string(), info_provider)
+        .await
+        .ok()?;
+    Some(TokenExchangeRate {
+        token_info,
+        rates: token_rates(&price.fiat_price, usd_to_currencies),
+    })
+}
+
+/// Comma separated, case insensitive currency codes, `USD` if none are requested
+pub fn requested_currencies(currencies: Option<&str>) -> ApiResult<Vec<String>> {
+    let mut requested: Vec<String> = vec![];
+    for currency in currencies.unwrap_or("").split(',') {
+        let currency = currency.trim().to_uppercase();
+        if !currency.is_empty() && !requested.contains(&currency) {
+            requested.push(currency);
+        }
+    }
+    if requested.is_empty() {
+        requested.push(String::from("USD"));
+    }
+    if requested.len() > MAX_EXCHANGE_RATE_CURRENCIES {
+        return Err(client_error!(422, "Too many currencies requested"));
+    }
+    Ok(requested)
+}
+
+/// Value of one whole token per currency, from its `usd_price`
+pub fn token_rates(
+    usd_price: &BigDecimal,
+    usd_to_currencies: &[(String, BigDecimal)],
+) -> BTreeMap<String, String> {
+    usd_to_currencies
+        .iter()
+        .map(|(currency, usd_to_currency)| {
+            (
+                currency.to_string(),
+                (usd_price * usd_to_currency)
+                    .with_scale(RATE_SCALE)
+                    .to_string(),
+            )
+        })
+        .collect()
+}
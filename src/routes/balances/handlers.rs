@@ -2,8 +2,11 @@ use crate::cache::cache_operations::RequestCached;
 use crate::common::models::backend::balances::Balance as BalanceDto;
 use crate::common::models::backend::chains::NativeCurrency;
 use crate::config::{balances_cache_duration, balances_request_timeout};
+use crate::monitoring::schema_drift;
 use crate::providers::fiat::FiatInfoProvider;
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::token_overrides;
+use crate::routes::balances::converters::with_token_override;
 use crate::routes::balances::models::{Balance, Balances};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
@@ -31,9 +34,10 @@ pub async fn balances(
     let body = RequestCached::new_from_context(url, context)
         .cache_duration(balances_cache_duration())
         .request_timeout(balances_request_timeout())
+        .keep_last_known_good()
         .execute()
         .await?;
-    let backend_balances: Vec<BalanceDto> = serde_json::from_str(&body)?;
+    let backend_balances: Vec<BalanceDto> = schema_drift::parse(&body)?;
 
     let usd_to_fiat = fiat_info_provider
         .exchange_usd_to(fiat)
@@ -43,13 +47,17 @@ pub async fn balances(
         .unwrap_or(f64::from(0));
 
     let native_currency: NativeCurrency = info_provider.chain_info().await?.native_currency;
+    let token_overrides =
+        token_overrides::chain_overrides(context.cache().as_ref(), chain_id).await;
 
     let mut total_fiat = 0.0;
 
     let mut service_balances: Vec<Balance> = backend_balances
         .into_iter()
-        .map(|it| {
-            let balance = it.to_balance(usd_to_fiat, &native_currency);
+        .map(|it| it.to_balance(usd_to_fiat, &native_currency))
+        .map(|balance| with_token_override(balance, &token_overrides))
+        .filter(|balance| !(exclude_spam && balance.spam))
+        .map(|balance| {
             total_fiat += balance.fiat_balance.parse::<f64>().unwrap_or(0.0);
             balance
         })
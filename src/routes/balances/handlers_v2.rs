@@ -6,8 +6,11 @@ use crate::config::{
     balances_cache_duration, balances_request_timeout, concurrent_balance_token_requests,
     token_price_cache_duration,
 };
+use crate::monitoring::schema_drift;
 use crate::providers::fiat::FiatInfoProvider;
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::token_overrides;
+use crate::routes::balances::converters::with_token_override;
 use crate::routes::balances::models::{Balance, Balances, TokenPrice};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
@@ -35,9 +38,10 @@ pub async fn balances(
     let body = RequestCached::new_from_context(url, context)
         .cache_duration(balances_cache_duration())
         .request_timeout(balances_request_timeout())
+        .keep_last_known_good()
         .execute()
         .await?;
-    let backend_balances: Vec<BalanceDto> = serde_json::from_str(&body)?;
+    let mut backend_balances: Vec<BalanceDto> = schema_drift::parse(&body)?;
 
     let usd_to_fiat = fiat_info_provider
         .exchange_usd_to(fiat)
@@ -45,6 +49,11 @@ pub async fn balances(
         .unwrap_or(BigDecimal::from(0));
 
     let native_currency: NativeCurrency = info_provider.chain_info().await?.native_currency;
+    let token_overrides =
+        token_overrides::chain_overrides(context.cache().as_ref(), chain_id).await;
+    for backend_balance in backend_balances.iter_mut() {
+        backend_balance.override_decimals(&token_overrides);
+    }
 
     let mut total_fiat = 0.0;
 
@@ -65,7 +74,11 @@ pub async fn balances(
                 .and_then(|t| Some(t.fiat_price.to_owned()))
                 .unwrap_or(BigDecimal::from(0));
 
-            let balance = it.to_balance_v2(&token_to_usd, &usd_to_fiat, &native_currency);
+            it.to_balance_v2(&token_to_usd, &usd_to_fiat, &native_currency)
+        })
+        .map(|balance| with_token_override(balance, &token_overrides))
+        .filter(|balance| !(exclude_spam && balance.spam))
+        .map(|balance| {
             total_fiat += balance.fiat_balance.parse::<f64>().unwrap_or(0.0);
             balance
         })
@@ -117,7 +130,7 @@ async fn get_token_prices(
 ///
 /// returns: Result<TokenPrice, ApiError>
 ///
-async fn get_token_usd_rate(
+pub(crate) async fn get_token_usd_rate(
     context: &RequestContext,
     token_address: String,
     info_provider: &impl InfoProvider,
@@ -128,7 +141,7 @@ async fn get_token_usd_rate(
         .cache_duration(token_price_cache_duration())
         .execute()
         .await?;
-    let response: BackendTokenPrice = serde_json::from_str(&body)?;
+    let response: BackendTokenPrice = schema_drift::parse(&body)?;
 
     return Ok(TokenPrice {
         address: token_address.to_string(),
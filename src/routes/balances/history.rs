@@ -0,0 +1,141 @@
+use crate::cache::Cache;
+use crate::config::{balance_history_max_days, feature_flag_balances_rate_implementation};
+use crate::routes::balances::models::{BalanceHistory, BalanceSnapshot, Balances};
+use crate::routes::balances::{handlers, handlers_v2};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use chrono::{Duration, NaiveDate, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+// Snapshots are stored without expiry, one entry per safe and fiat, pruned on write
+const BALANCE_HISTORY_KEY: &str = "balance_history";
+
+/// Balances with the implementation selected by `FEATURE_FLAG_BALANCES_RATE_IMPLEMENTATION`.
+/// The fiat total of the default listing (all tokens, spam excluded) is kept as the snapshot
+/// of the day, so the history fills up as balances are requested.
+pub async fn balances_with_snapshot(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    fiat: &str,
+    trusted: bool,
+    exclude_spam: bool,
+) -> ApiResult<Balances> {
+    let balances =
+        current_balances(context, chain_id, safe_address, fiat, trusted, exclude_spam).await?;
+    if !trusted && exclude_spam {
+        record_snapshot(
+            &context.cache(),
+            &history_key(chain_id, safe_address, fiat),
+            Utc::today().naive_utc(),
+            &balances.fiat_total,
+        )
+        .await?;
+    }
+    Ok(balances)
+}
+
+/// Balances with the implementation selected by `FEATURE_FLAG_BALANCES_RATE_IMPLEMENTATION`,
+/// without recording a snapshot
+pub async fn current_balances(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    fiat: &str,
+    trusted: bool,
+    exclude_spam: bool,
+) -> ApiResult<Balances> {
+    if feature_flag_balances_rate_implementation() {
+        handlers_v2::balances(context, chain_id, safe_address, fiat, trusted, exclude_spam).await
+    } else {
+        handlers::balances(context, chain_id, safe_address, fiat, trusted, exclude_spam).await
+    }
+}
+
+pub async fn balance_history(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    fiat: &str,
+    days: usize,
+) -> ApiResult<BalanceHistory> {
+    // Makes sure the snapshot of today is up to date
+    balances_with_snapshot(context, chain_id, safe_address, fiat, false, true).await?;
+    let snapshots =
+        stored_snapshots(&context.cache(), &history_key(chain_id, safe_address, fiat)).await;
+    Ok(BalanceHistory {
+        fiat_code: fiat.to_uppercase(),
+        items: history_items(&snapshots, Utc::today().naive_utc(), days),
+    })
+}
+
+/// One item per day of the last `days` days, oldest first. Days without a snapshot take the
+/// total of the previous snapshot, days before the first snapshot are left out.
+pub fn history_items(
+    snapshots: &BTreeMap<NaiveDate, String>,
+    today: NaiveDate,
+    days: usize,
+) -> Vec<BalanceSnapshot> {
+    let first_day = today - Duration::days(days.max(1) as i64 - 1);
+    let mut last_total = snapshots
+        .range(..first_day)
+        .next_back()
+        .map(|(_, total)| total.to_string());
+    let mut items = vec![];
+    let mut day = first_day;
+    while day <= today {
+        if let Some(total) = snapshots.get(&day) {
+            last_total = Some(total.to_string());
+        }
+        if let Some(total) = &last_total {
+            items.push(BalanceSnapshot {
+                date: day.to_string(),
+                timestamp: day.and_hms(0, 0, 0).timestamp_millis(),
+                fiat_total: total.to_string(),
+            });
+        }
+        day = day.succ();
+    }
+    items
+}
+
+async fn record_snapshot(
+    cache: &Arc<dyn Cache>,
+    key: &str,
+    today: NaiveDate,
+    fiat_total: &str,
+) -> ApiResult<()> {
+    let mut snapshots = stored_snapshots(cache, key).await;
+    if snapshots.get(&today).map(String::as_str) == Some(fiat_total) {
+        return Ok(());
+    }
+    snapshots.insert(today, fiat_total.to_string());
+    let oldest_day = today - Duration::days(balance_history_max_days() as i64);
+    let snapshots = snapshots.split_off(&oldest_day);
+    cache
+        .insert_in_hash(
+            BALANCE_HISTORY_KEY,
+            key,
+            &serde_json::to_string(&snapshots)?,
+        )
+        .await;
+    Ok(())
+}
+
+async fn stored_snapshots(cache: &Arc<dyn Cache>, key: &str) -> BTreeMap<NaiveDate, String> {
+    cache
+        .get_from_hash(BALANCE_HISTORY_KEY, key)
+        .await
+        .and_then(|snapshots| serde_json::from_str(&snapshots).ok())
+        .unwrap_or_default()
+}
+
+fn history_key(chain_id: &str, safe_address: &str, fiat: &str) -> String {
+    format!(
+        "{}_{}_{}",
+        chain_id,
+        safe_address.to_lowercase(),
+        fiat.to_uppercase()
+    )
+}
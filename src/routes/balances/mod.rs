@@ -1,10 +1,19 @@
 #[doc(hidden)]
+pub mod at_block;
+#[doc(hidden)]
 pub mod converters;
 #[doc(hidden)]
 pub mod converters_v2;
 #[doc(hidden)]
+pub mod exchange_rates;
+#[doc(hidden)]
 pub mod handlers;
 #[doc(hidden)]
 pub mod handlers_v2;
+#[doc(hidden)]
+pub mod history;
 pub mod models;
 pub mod routes;
+
+#[cfg(test)]
+mod tests;
@@ -1,6 +1,7 @@
 use crate::providers::info::TokenInfo;
 use bigdecimal::BigDecimal;
 use serde::Serialize;
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +10,7 @@ pub struct Balance {
     pub balance: String,
     pub fiat_balance: String,
     pub fiat_conversion: String,
+    pub spam: bool,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -28,3 +30,37 @@ pub struct TokenPrice {
     pub fiat_price: BigDecimal,
     pub timestamp: String,
 }
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistory {
+    pub fiat_code: String,
+    /// Daily [BalanceSnapshot] entries, oldest first
+    pub items: Vec<BalanceSnapshot>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceSnapshot {
+    /// UTC day, as `YYYY-MM-DD`
+    pub date: String,
+    /// Start of the day in milliseconds
+    pub timestamp: i64,
+    /// Aggregated fiat balance of the day
+    pub fiat_total: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeRates {
+    /// The native coin first, then the configured tokens
+    pub items: Vec<TokenExchangeRate>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenExchangeRate {
+    pub token_info: TokenInfo,
+    /// Value of one whole token, per currency code
+    pub rates: BTreeMap<String, String>,
+}
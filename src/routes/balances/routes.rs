@@ -1,16 +1,17 @@
-use rocket::futures::FutureExt;
 use rocket::response::content;
+use std::cmp::min;
 
 use crate::cache::cache_operations::CacheResponse;
-use crate::config::{balances_cache_duration, feature_flag_balances_rate_implementation};
-use crate::routes::balances::handlers;
+use crate::config::{
+    balance_history_max_days, balances_cache_duration, exchange_rates_cache_duration,
+};
 use crate::routes::balances::handlers::fiat_codes;
-use crate::routes::balances::handlers_v2;
+use crate::routes::balances::{at_block, exchange_rates, history};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
 
 /**
- * `/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>?<trusted>&<exclude_spam>`<br/>
+ * `/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>?<trusted>&<exclude_spam>&<at_block>`<br/>
  * Returns [Balances](crate::models::handlers::balances::Balances)
  *
  * # Balances
@@ -28,9 +29,14 @@ use crate::utils::errors::ApiResult;
  * ## Query parameters
  *
  * - `<trusted>` : A token is defined as trusted by our core handlers process when adding them. Default value is `false`
- * - `<exclude_spam>`: A token is defined as spam by our core handlers process when adding them. Default value is `true`
+ * - `<exclude_spam>`: A token is defined as spam by our core handlers process when adding them. Default value is `true`. Tokens flagged by the gateway heuristics (words of `SPAM_NAME_PATTERNS` in their name or symbol, `SPAM_TOKEN_DENYLIST`) are excluded as well, otherwise they are returned with `spam: true`
+ * - `<at_block>`: block number to read the balances at, via `eth_call` on the RPC of the chain instead of the current state of the transaction service. Requires an archive node, otherwise the request fails with a 422. Only the tokens currently listed for the Safe are read, valued at the current fiat prices
  */
-#[get("/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>?<trusted>&<exclude_spam>")]
+// Ranked after `balances/history`, which would otherwise collide with the `<fiat>` segment
+#[get(
+    "/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>?<trusted>&<exclude_spam>&<at_block>",
+    rank = 2
+)]
 pub async fn get_balances(
     context: RequestContext,
     chain_id: String,
@@ -38,36 +44,75 @@ pub async fn get_balances(
     fiat: String,
     trusted: Option<bool>,
     exclude_spam: Option<bool>,
+    at_block: Option<u64>,
 ) -> ApiResult<content::Json<String>> {
+    let trusted = trusted.unwrap_or(false);
+    let exclude_spam = exclude_spam.unwrap_or(true);
     CacheResponse::new(&context)
         .duration(balances_cache_duration())
-        .resp_generator(|| {
-            if feature_flag_balances_rate_implementation() {
-                handlers_v2::balances(
-                    &context,
-                    chain_id.as_str(),
-                    safe_address.as_str(),
-                    fiat.as_str(),
-                    trusted.unwrap_or(false),
-                    exclude_spam.unwrap_or(true),
-                )
-                .left_future()
-            } else {
-                handlers::balances(
-                    &context,
-                    chain_id.as_str(),
-                    safe_address.as_str(),
-                    fiat.as_str(),
-                    trusted.unwrap_or(false),
-                    exclude_spam.unwrap_or(true),
-                )
-                .right_future()
+        .resp_generator(|| async {
+            match at_block {
+                Some(block) => {
+                    at_block::balances_at_block(
+                        &context,
+                        &chain_id,
+                        &safe_address,
+                        &fiat,
+                        trusted,
+                        exclude_spam,
+                        block,
+                    )
+                    .await
+                }
+                None => {
+                    history::balances_with_snapshot(
+                        &context,
+                        &chain_id,
+                        &safe_address,
+                        &fiat,
+                        trusted,
+                        exclude_spam,
+                    )
+                    .await
+                }
             }
         })
         .execute()
         .await
 }
 
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/balances/history?<days>&<fiat>`<br/>
+ * Returns [BalanceHistory](crate::routes::balances::models::BalanceHistory)
+ *
+ * # Balance History
+ *
+ * Daily fiat totals of the Safe, to render portfolio graphs. The gateway stores a snapshot of the fiat total every day the balances of the Safe are requested (the default listing, with `trusted=false` and `exclude_spam=true`); days without a snapshot repeat the previous total. There is no upstream source for past balances, so the history only goes back to the first snapshot.
+ *
+ * ## Query parameters
+ *
+ * - `<days>`: amount of days, up to `BALANCE_HISTORY_MAX_DAYS`. Default value is `30`
+ * - `<fiat>`: fiat code of the totals. Default value is `USD`
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/balances/history?<days>&<fiat>")]
+pub async fn get_balance_history(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    days: Option<usize>,
+    fiat: Option<String>,
+) -> ApiResult<content::Json<String>> {
+    let days = min(days.unwrap_or(30), balance_history_max_days());
+    let fiat = fiat.unwrap_or_else(|| String::from("USD"));
+    CacheResponse::new(&context)
+        .duration(balances_cache_duration())
+        .resp_generator(|| {
+            history::balance_history(&context, &chain_id, &safe_address, &fiat, days)
+        })
+        .execute()
+        .await
+}
+
 /**
  * `/v1/balances/supported-fiat-codes` <br/>
  * Returns [Vec] of [String]
@@ -83,3 +128,53 @@ pub async fn get_supported_fiat(context: RequestContext) -> ApiResult<content::J
         .execute()
         .await
 }
+
+/**
+ * `/v1/chains/<chain_id>/exchange-rates?<currencies>` <br/>
+ * Returns [ExchangeRates](crate::routes::balances::models::ExchangeRates)
+ *
+ * # Exchange rates
+ *
+ * Value of one whole native coin of the chain, and of every token configured for the chain with `EXCHANGE_RATE_TOKENS` (e.g. stablecoins), in each requested currency. Clients can convert arbitrary amounts with them locally. Tokens without a price are left out. Responses are cached for `EXCHANGE_RATES_CACHE_DURATION`.
+ *
+ * ## Query parameters
+ *
+ * - `<currencies>`: comma separated fiat codes out of the supported fiat codes, e.g. `usd,eur`. Defaults to `USD`, at most 20 can be requested. Unknown codes fail with `422`
+ *
+ * ## Models
+ *
+ * ```json
+ * {
+ *   "items": [
+ *     {
+ *       "tokenInfo": {
+ *         "type": "NATIVE_TOKEN",
+ *         "address": "0x0000000000000000000000000000000000000000",
+ *         "decimals": 18,
+ *         "symbol": "ETH",
+ *         "name": "Ether",
+ *         "logoUri": "https://safe-transaction-assets.gnosis-safe.io/chains/4/currency_logo.png"
+ *       },
+ *       "rates": {
+ *         "EUR": "2834.41000000",
+ *         "USD": "3079.90000000"
+ *       }
+ *     }
+ *   ]
+ * }
+ * ```
+ */
+#[get("/v1/chains/<chain_id>/exchange-rates?<currencies>")]
+pub async fn get_exchange_rates(
+    context: RequestContext,
+    chain_id: String,
+    currencies: Option<String>,
+) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .duration(exchange_rates_cache_duration())
+        .resp_generator(|| {
+            exchange_rates::exchange_rates(&context, &chain_id, currencies.as_deref())
+        })
+        .execute()
+        .await
+}
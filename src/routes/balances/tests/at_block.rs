@@ -0,0 +1,94 @@
+use crate::cache::MockCache;
+use crate::providers::info::{TokenInfo, TokenType};
+use crate::providers::rpc::RpcProvider;
+use crate::routes::balances::at_block::{
+    balance_of_call_data, balance_with_amount, decode_balance, sorted_balances,
+};
+use crate::routes::balances::models::Balance;
+use crate::testing::builders::ChainInfoBuilder;
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::{MockHttpClient, Response};
+use ethabi::Uint;
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+fn balance(symbol: &str, decimals: u64, fiat_conversion: &str) -> Balance {
+    Balance {
+        token_info: TokenInfo {
+            token_type: TokenType::Erc20,
+            address: String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+            decimals,
+            symbol: String::from(symbol),
+            name: String::from(symbol),
+            logo_uri: None,
+        },
+        balance: String::from("1"),
+        fiat_balance: String::from("0"),
+        fiat_conversion: String::from(fiat_conversion),
+        spam: false,
+    }
+}
+
+#[test]
+fn balance_of_call_data_encodes_owner() {
+    assert_eq!(
+        "0x70a082310000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b",
+        balance_of_call_data(SAFE).unwrap()
+    );
+}
+
+#[test]
+fn decode_balance_of_return_data() {
+    assert_eq!(
+        Some(Uint::from(1500)),
+        decode_balance("0x00000000000000000000000000000000000000000000000000000000000005dc")
+    );
+    assert_eq!(None, decode_balance("0x"));
+}
+
+#[test]
+fn balance_with_amount_values_at_current_price() {
+    let actual = balance_with_amount(balance("USDC", 6, "1.5"), &Uint::from(2500000));
+
+    assert_eq!("2500000", actual.balance);
+    assert_eq!("3.75000", actual.fiat_balance);
+    assert_eq!("1.5", actual.fiat_conversion);
+}
+
+#[test]
+fn sorted_balances_by_fiat_balance() {
+    let usdc = balance_with_amount(balance("USDC", 6, "1"), &Uint::from(1000000));
+    let dai = balance_with_amount(balance("DAI", 18, "1"), &Uint::exp10(19));
+
+    let actual = sorted_balances(vec![usdc, dai]);
+
+    assert_eq!("11.00000", actual.fiat_total);
+    assert_eq!("DAI", actual.items[0].token_info.symbol);
+    assert_eq!("USDC", actual.items[1].token_info.symbol);
+}
+
+#[rocket::async_test]
+async fn balance_at_block_from_rpc() {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_post().times(1).return_once(|_| {
+        Ok(Response {
+            status_code: 200,
+            body: String::from(r#"{"jsonrpc":"2.0","id":1,"result":"0xde0b6b3a7640000"}"#),
+        })
+    });
+    let context = RequestContext::mock(
+        String::from("/v1/chains/1/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/balances/usd"),
+        String::from("localhost"),
+        mock_http_client,
+        MockCache::new(),
+    );
+    let chain_info = ChainInfoBuilder::new("1").build();
+
+    let actual = RpcProvider::new(&context, &chain_info)
+        .balance_at(SAFE, "0xc35000")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(Uint::exp10(18), actual);
+}
@@ -0,0 +1,39 @@
+use crate::routes::balances::exchange_rates::{requested_currencies, token_rates};
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+#[test]
+fn requested_currencies_defaults_to_usd() {
+    assert_eq!(requested_currencies(None).unwrap(), vec!["USD"]);
+    assert_eq!(requested_currencies(Some(" , ")).unwrap(), vec!["USD"]);
+}
+
+#[test]
+fn requested_currencies_normalizes_codes() {
+    assert_eq!(
+        requested_currencies(Some("usd, eur,USD,chf")).unwrap(),
+        vec!["USD", "EUR", "CHF"]
+    );
+}
+
+#[test]
+fn requested_currencies_rejects_too_many_codes() {
+    let currencies: Vec<String> = (0..21).map(|index| format!("C{}", index)).collect();
+
+    let error = requested_currencies(Some(&currencies.join(","))).unwrap_err();
+
+    assert_eq!(error.status, 422);
+}
+
+#[test]
+fn token_rates_converts_usd_price() {
+    let usd_to_currencies = [
+        (String::from("USD"), BigDecimal::from(1)),
+        (String::from("EUR"), BigDecimal::from_str("0.92").unwrap()),
+    ];
+
+    let actual = token_rates(&BigDecimal::from_str("3079.9").unwrap(), &usd_to_currencies);
+
+    assert_eq!(actual["USD"], "3079.90000000");
+    assert_eq!(actual["EUR"], "2833.50800000");
+}
@@ -0,0 +1,53 @@
+use crate::routes::balances::history::history_items;
+use crate::routes::balances::models::BalanceSnapshot;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+fn snapshot(date: &str, timestamp: i64, fiat_total: &str) -> BalanceSnapshot {
+    BalanceSnapshot {
+        date: date.to_string(),
+        timestamp,
+        fiat_total: fiat_total.to_string(),
+    }
+}
+
+#[test]
+fn history_items_fills_gaps_with_previous_total() {
+    let mut snapshots = BTreeMap::new();
+    snapshots.insert(NaiveDate::from_ymd(2021, 9, 30), String::from("100.5"));
+    snapshots.insert(NaiveDate::from_ymd(2021, 10, 2), String::from("250"));
+
+    let actual = history_items(&snapshots, NaiveDate::from_ymd(2021, 10, 3), 3);
+
+    assert_eq!(
+        actual,
+        vec![
+            snapshot("2021-10-01", 1633046400000, "100.5"),
+            snapshot("2021-10-02", 1633132800000, "250"),
+            snapshot("2021-10-03", 1633219200000, "250"),
+        ]
+    );
+}
+
+#[test]
+fn history_items_starts_at_first_snapshot() {
+    let mut snapshots = BTreeMap::new();
+    snapshots.insert(NaiveDate::from_ymd(2021, 10, 2), String::from("250"));
+
+    let actual = history_items(&snapshots, NaiveDate::from_ymd(2021, 10, 3), 30);
+
+    assert_eq!(
+        actual,
+        vec![
+            snapshot("2021-10-02", 1633132800000, "250"),
+            snapshot("2021-10-03", 1633219200000, "250"),
+        ]
+    );
+}
+
+#[test]
+fn history_items_without_snapshots() {
+    let actual = history_items(&BTreeMap::new(), NaiveDate::from_ymd(2021, 10, 3), 30);
+
+    assert!(actual.is_empty());
+}
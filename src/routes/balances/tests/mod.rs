@@ -0,0 +1,3 @@
+mod at_block;
+mod exchange_rates;
+mod history;
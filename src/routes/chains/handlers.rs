@@ -1,11 +1,40 @@
 use crate::cache::cache_operations::RequestCached;
+use crate::cache::Cache;
 use crate::common::models::backend::chains::ChainInfo as BackendChainInfo;
 use crate::common::models::page::Page;
-use crate::config::{chain_info_cache_duration, chain_info_request_timeout};
+use crate::config::{
+    asset_cache_duration, asset_max_size, chain_info_cache_duration, chain_info_request_timeout,
+};
+use crate::providers::chain_seed;
+use crate::providers::gas::{estimate_gas_price, GasPriceEstimation};
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
-use crate::routes::chains::models::ChainInfo as ServiceChainInfo;
+use crate::routes::chains::models::{
+    Asset, ChainAsset, ChainChanges, ChainInfo as ServiceChainInfo,
+};
 use crate::utils::context::RequestContext;
-use crate::utils::errors::ApiResult;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::http_client::{HttpClient, Request};
+use crate::utils::outbound;
+use chrono::Utc;
+use ethcontract_common::hash::keccak256;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const ASSET_KEY_BASE: &'static str = "c_asset";
+// Outside of the cache prefixes, so that flushing the cache doesn't report every chain as changed
+pub const CHAIN_CHANGES_KEY: &'static str = "chain_changes";
+const REMOVED_CHAIN_HASH: &str = "removed";
+// Bounds the requests to the config service, in case its `next` links never end
+const MAX_CHAIN_PAGES: usize = 50;
+
+/// Raster images and SVGs, served with a CSP preventing scripts from running
+const ALLOWED_ASSET_CONTENT_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "image/svg+xml",
+];
 
 pub async fn get_chains_paginated(
     context: &RequestContext,
@@ -16,16 +45,170 @@ pub async fn get_chains_paginated(
         limit.as_ref().unwrap_or(&"".to_string())
     );
 
-    let body = RequestCached::new_from_context(url, context)
+    let body = match RequestCached::new_from_context(url, context)
         .request_timeout(chain_info_request_timeout())
         .cache_duration(chain_info_cache_duration())
         .execute()
-        .await?;
+        .await
+    {
+        Err(error) if error.status >= 500 => return seeded_chains_page(context).ok_or(error),
+        result => result?,
+    };
 
     let page = serde_json::from_str::<Page<BackendChainInfo>>(&body)?;
     Ok(page.map_inner())
 }
 
+/// Single page of the seeded chains, while the config service is unreachable
+fn seeded_chains_page(context: &RequestContext) -> Option<Page<ServiceChainInfo>> {
+    let chains = chain_seed::seeded_chains();
+    if chains.is_empty() {
+        return None;
+    }
+    context.data_freshness().mark_stale();
+    let page = Page {
+        count: Some(chains.len() as u64),
+        next: None,
+        previous: None,
+        results: chains,
+        incomplete: None,
+    };
+    Some(page.map_inner())
+}
+
+/// Every chain of the config service, following the `next` links of the chains list
+pub async fn get_all_chains(context: &RequestContext) -> ApiResult<Vec<Value>> {
+    let mut next = Some(config_uri!("/v1/chains/?limit="));
+    let mut chains: Vec<Value> = vec![];
+    let mut pages = 0;
+    while let Some(url) = next {
+        if pages == MAX_CHAIN_PAGES {
+            bail!("Chains list exceeds {} pages", MAX_CHAIN_PAGES);
+        }
+        let body = RequestCached::new_from_context(url, context)
+            .request_timeout(chain_info_request_timeout())
+            .cache_duration(chain_info_cache_duration())
+            .execute()
+            .await?;
+        let page = serde_json::from_str::<Page<Value>>(&body)?;
+        chains.extend(page.results);
+        next = page.next;
+        pages += 1;
+    }
+    Ok(chains)
+}
+
+/// Chains whose config changed at or after `since` (in ms), compared to the chains seen by
+/// previous calls. Chains are first reported as changed when they are first seen.
+pub async fn get_chain_changes(context: &RequestContext, since: i64) -> ApiResult<ChainChanges> {
+    let chains = get_all_chains(context).await?;
+    let checked_at = Utc::now().timestamp_millis();
+    let tracked = track_chain_changes(context.cache().as_ref(), &chains, checked_at).await;
+    let is_changed = |chain_id: &str| {
+        tracked
+            .get(chain_id)
+            .map_or(false, |chain| chain.changed_at >= since)
+    };
+    let mut changed = vec![];
+    for chain in chains {
+        let chain_id = chain_id_of(&chain).unwrap_or_default().to_string();
+        if is_changed(&chain_id) {
+            changed.push(serde_json::from_value::<BackendChainInfo>(chain)?.into());
+        }
+    }
+    let removed = tracked
+        .iter()
+        .filter(|(_, chain)| chain.content_hash == REMOVED_CHAIN_HASH && chain.changed_at >= since)
+        .map(|(chain_id, _)| chain_id.to_string())
+        .collect();
+    Ok(ChainChanges {
+        checked_at,
+        changed,
+        removed,
+    })
+}
+
+/// Hash of the config of a chain and when it last changed, stored as `<changed_at>;<hash>`
+#[derive(Debug, PartialEq)]
+pub struct TrackedChain {
+    pub content_hash: String,
+    pub changed_at: i64,
+}
+
+impl TrackedChain {
+    fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(2, ';');
+        Some(TrackedChain {
+            changed_at: parts.next()?.parse().ok()?,
+            content_hash: parts.next()?.to_string(),
+        })
+    }
+
+    fn to_cached(&self) -> String {
+        format!("{};{}", self.changed_at, self.content_hash)
+    }
+}
+
+pub(crate) fn chain_id_of(chain: &Value) -> Option<&str> {
+    chain.get("chainId").and_then(Value::as_str)
+}
+
+/// Compares the content hash of every chain of the complete `chains` list to the stored one,
+/// updating the chains that changed, appeared or disappeared since the last comparison
+pub async fn track_chain_changes(
+    cache: &dyn Cache,
+    chains: &[Value],
+    now: i64,
+) -> BTreeMap<String, TrackedChain> {
+    let mut tracked: BTreeMap<String, TrackedChain> = cache
+        .fetch_hash(CHAIN_CHANGES_KEY)
+        .await
+        .into_iter()
+        .filter_map(|(chain_id, value)| Some((chain_id, TrackedChain::parse(&value)?)))
+        .collect();
+    let mut current = BTreeSet::new();
+    for chain in chains {
+        if let Some(chain_id) = chain_id_of(chain) {
+            let content_hash = to_hex_string!(keccak256(chain.to_string().as_bytes()));
+            update_tracked_chain(cache, &mut tracked, chain_id, content_hash, now).await;
+            current.insert(chain_id.to_string());
+        }
+    }
+    let disappeared: Vec<String> = tracked
+        .keys()
+        .filter(|chain_id| !current.contains(*chain_id))
+        .cloned()
+        .collect();
+    for chain_id in disappeared {
+        let content_hash = REMOVED_CHAIN_HASH.to_string();
+        update_tracked_chain(cache, &mut tracked, &chain_id, content_hash, now).await;
+    }
+    tracked
+}
+
+async fn update_tracked_chain(
+    cache: &dyn Cache,
+    tracked: &mut BTreeMap<String, TrackedChain>,
+    chain_id: &str,
+    content_hash: String,
+    now: i64,
+) {
+    let is_unchanged = tracked
+        .get(chain_id)
+        .map_or(false, |chain| chain.content_hash == content_hash);
+    if is_unchanged {
+        return;
+    }
+    let chain = TrackedChain {
+        content_hash,
+        changed_at: now,
+    };
+    cache
+        .insert_in_hash(CHAIN_CHANGES_KEY, chain_id, &chain.to_cached())
+        .await;
+    tracked.insert(chain_id.to_string(), chain);
+}
+
 pub async fn get_single_chain(
     context: &RequestContext,
     chain_id: &str,
@@ -33,3 +216,113 @@ pub async fn get_single_chain(
     let info_provider = DefaultInfoProvider::new(&chain_id, &context);
     Ok(info_provider.chain_info().await?.into())
 }
+
+pub async fn get_gas_price(
+    context: &RequestContext,
+    chain_id: &str,
+) -> ApiResult<GasPriceEstimation> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let chain_info = info_provider.chain_info().await?;
+    estimate_gas_price(context, &chain_info).await
+}
+
+/// Keyed by the upstream uri, so that changes to the chain info are picked up right away
+pub fn generate_asset_key(uri: &str) -> String {
+    format!("{}_{}", ASSET_KEY_BASE, uri)
+}
+
+pub async fn get_chain_asset(
+    context: &RequestContext,
+    chain_id: &str,
+    kind: &str,
+) -> ApiResult<Asset> {
+    let asset = ChainAsset::from_kind(kind).ok_or_else(|| client_error!(404, "Unknown asset"))?;
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let chain_info = info_provider.chain_info().await?;
+    let uri = asset.uri(&chain_info).to_string();
+    if uri.is_empty() {
+        return Err(client_error!(404, "Asset not set for this chain"));
+    }
+
+    let (asset, ttl) = load_asset(
+        context.http_client().as_ref(),
+        context.cache().as_ref(),
+        &uri,
+    )
+    .await?;
+    if let Some(ttl) = ttl {
+        context.response_ttl().set(ttl);
+    }
+    Ok(asset)
+}
+
+/// The logo of a token known to the transaction service
+pub async fn get_token_logo(
+    context: &RequestContext,
+    chain_id: &str,
+    token_address: &str,
+) -> ApiResult<Asset> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let uri = info_provider
+        .token_info(token_address)
+        .await?
+        .logo_uri
+        .filter(|uri| !uri.is_empty())
+        .ok_or_else(|| client_error!(404, "Token has no logo"))?;
+    let (asset, ttl) = load_asset(
+        context.http_client().as_ref(),
+        context.cache().as_ref(),
+        &uri,
+    )
+    .await?;
+    if let Some(ttl) = ttl {
+        context.response_ttl().set(ttl);
+    }
+    Ok(asset)
+}
+
+/// The asset at `uri` from the asset cache, fetched and cached if it is missing, with the time
+/// (in ms) it stays cached
+pub async fn load_asset(
+    http_client: &dyn HttpClient,
+    cache: &dyn Cache,
+    uri: &str,
+) -> ApiResult<(Asset, Option<usize>)> {
+    let cache_key = generate_asset_key(uri);
+    if let Some(asset) = cache
+        .fetch(&cache_key)
+        .await
+        .and_then(|cached| Asset::from_cached(&cached))
+    {
+        return Ok((asset, cache.ttl(&cache_key).await));
+    }
+
+    // The uri is provided by the config or transaction service, like the chain's services
+    outbound::allow_url(uri);
+    let response = http_client
+        .get_binary(Request::new(uri.to_string()), asset_max_size())
+        .await?;
+    let content_type = response
+        .content_type
+        .as_deref()
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|content_type| content_type.trim().to_lowercase())
+        .filter(|content_type| ALLOWED_ASSET_CONTENT_TYPES.contains(&content_type.as_str()))
+        .ok_or_else(|| {
+            ApiError::new_from_message_with_code(
+                502,
+                format!(
+                    "Unsupported content type {:?} for {}",
+                    response.content_type, uri
+                ),
+            )
+        })?;
+    let asset = Asset {
+        content_type,
+        body: response.body,
+    };
+    cache
+        .create(&cache_key, &asset.to_cached(), asset_cache_duration())
+        .await;
+    Ok((asset, Some(asset_cache_duration())))
+}
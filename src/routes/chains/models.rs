@@ -1,4 +1,10 @@
+use crate::common::models::backend::chains::ChainInfo as BackendChainInfo;
+use crate::utils::transactions::decode_hex;
+use rocket::http::{ContentType, Header};
+use rocket::response::{self, Responder, Response};
+use rocket::Request;
 use serde::Serialize;
+use std::io::Cursor;
 
 #[derive(Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -21,6 +27,16 @@ pub struct ChainInfo {
     pub features: Vec<String>,
 }
 
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainChanges {
+    /// To be passed as `since` to get the changes after this response
+    pub checked_at: i64,
+    pub changed: Vec<ChainInfo>,
+    /// Ids of the chains that are not supported anymore
+    pub removed: Vec<String>,
+}
+
 #[derive(Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct NativeCurrency {
@@ -77,3 +93,62 @@ pub struct BlockExplorerUriTemplate {
     pub tx_hash: String,
     pub api: String,
 }
+
+/// Assets referenced in the chain info that are served through the gateway
+#[derive(Debug, PartialEq)]
+pub enum ChainAsset {
+    NativeCurrencyLogo,
+}
+
+impl ChainAsset {
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "native-currency-logo" => Some(ChainAsset::NativeCurrencyLogo),
+            _ => None,
+        }
+    }
+
+    pub fn uri<'c>(&self, chain_info: &'c BackendChainInfo) -> &'c str {
+        match self {
+            ChainAsset::NativeCurrencyLogo => &chain_info.native_currency.logo_uri,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Asset {
+    /// Mime type without parameters, e.g. `image/png`
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl Asset {
+    pub fn to_cached(&self) -> String {
+        format!("{};{}", self.content_type, to_hex_string!(self.body))
+    }
+
+    pub fn from_cached(cached: &str) -> Option<Self> {
+        let (content_type, body) = cached.split_at(cached.find(';')?);
+        Some(Asset {
+            content_type: content_type.to_string(),
+            body: decode_hex(&body[1..]).ok()?,
+        })
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Asset {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
+        let content_type =
+            ContentType::parse_flexible(&self.content_type).unwrap_or(ContentType::Binary);
+        Response::build()
+            .header(content_type)
+            .header(Header::new("X-Content-Type-Options", "nosniff"))
+            // Assets (SVGs in particular) can't run scripts when opened on the gateway origin
+            .header(Header::new(
+                "Content-Security-Policy",
+                "default-src 'none'; style-src 'unsafe-inline'; sandbox",
+            ))
+            .sized_body(self.body.len(), Cursor::new(self.body))
+            .ok()
+    }
+}
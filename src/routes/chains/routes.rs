@@ -1,6 +1,9 @@
 use crate::cache::cache_operations::CacheResponse;
-use crate::config::chain_info_response_cache_duration;
-use crate::routes::chains::handlers::{get_chains_paginated, get_single_chain};
+use crate::config::{chain_info_response_cache_duration, gas_price_cache_duration};
+use crate::routes::chains::handlers::{
+    self, get_chains_paginated, get_gas_price as gas_price, get_single_chain,
+};
+use crate::routes::chains::models::Asset;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
 use rocket::response::content;
@@ -54,3 +57,99 @@ pub async fn get_chains(
         .execute()
         .await
 }
+
+/**
+ * `/v1/chains/changes?<since>` <br/>
+ * Returns [ChainChanges](crate::routes::chains::models::ChainChanges)
+ *
+ * # Chain changes
+ *
+ * For clients keeping the chains in a local cache: returns the [ChainInfo](crate::routes::chains::models::ChainInfo) of the chains whose config changed since `<since>` (in ms), and the ids of the chains that were removed. Changes are detected by comparing content hashes of the chains whenever this endpoint reads the chains list, so they are reported with the time they were detected, never earlier than they happened.
+ *
+ * ## Query parameters
+ *
+ * - `<since>`: `checkedAt` of the previous response. Without it every chain is returned
+ */
+#[get("/v1/chains/changes?<since>")]
+pub async fn get_chain_changes(
+    context: RequestContext,
+    since: Option<i64>,
+) -> ApiResult<content::Json<String>> {
+    let changes = handlers::get_chain_changes(&context, since.unwrap_or(0)).await?;
+    Ok(content::Json(serde_json::to_string(&changes)?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/gas-price` <br/>
+ * Returns a [GasPriceEstimation](crate::providers::gas::GasPriceEstimation)
+ *
+ * # Gas price
+ *
+ * Estimated from the chain RPC. For chains with the `EIP1559` feature, `slow`, `standard` and `fast` tiers of `maxFeePerGas` and `maxPriorityFeePerGas` are estimated from the priority fees paid in the latest blocks (`eth_feeHistory`). Other chains, and nodes without `eth_feeHistory`, get the legacy `gasPrice`. Amounts are in wei.
+ *
+ * ```json
+ * {
+ *   "type": "EIP1559",
+ *   "baseFeePerGas": "30000000000",
+ *   "slow": { "maxFeePerGas": "61000000000", "maxPriorityFeePerGas": "1000000000" },
+ *   "standard": { "maxFeePerGas": "61500000000", "maxPriorityFeePerGas": "1500000000" },
+ *   "fast": { "maxFeePerGas": "62000000000", "maxPriorityFeePerGas": "2000000000" }
+ * }
+ * ```
+ *
+ * ```json
+ * {
+ *   "type": "LEGACY",
+ *   "gasPrice": "5000000000"
+ * }
+ * ```
+ */
+#[get("/v1/chains/<chain_id>/gas-price")]
+pub async fn get_gas_price(
+    context: RequestContext,
+    chain_id: String,
+) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .duration(gas_price_cache_duration())
+        .resp_generator(|| gas_price(&context, &chain_id))
+        .execute()
+        .await
+}
+
+/**
+ * `/v1/chains/<chain_id>/assets/<kind>` <br/>
+ * Returns the asset (image) with its upstream content type
+ *
+ * # Chain assets
+ *
+ * Proxies and caches the assets referenced in the chain info, so that clients only need to allow
+ * the gateway origin. Only images (PNG, JPEG, GIF, WebP and SVG) up to `ASSET_MAX_SIZE` bytes are served.
+ *
+ * ## Path
+ *
+ * - `/v1/chains/<chain_id>/assets/native-currency-logo` returns the logo of the native currency of `<chain_id>`
+ *
+ */
+#[get("/v1/chains/<chain_id>/assets/<kind>")]
+pub async fn get_chain_asset(
+    context: RequestContext,
+    chain_id: String,
+    kind: String,
+) -> ApiResult<Asset> {
+    handlers::get_chain_asset(&context, &chain_id, &kind).await
+}
+
+/**
+ * `/v1/chains/<chain_id>/assets/tokens/<token_address>` <br/>
+ * Returns the logo of the token with its upstream content type
+ *
+ * Proxies and caches the `logoUri` of tokens known to the transaction service, like the chain assets. With `LOGO_PREFETCH_CONCURRENCY` set, the logos of a chain are fetched into the cache whenever its token list is loaded.
+ */
+#[get("/v1/chains/<chain_id>/assets/tokens/<token_address>")]
+pub async fn get_token_logo(
+    context: RequestContext,
+    chain_id: String,
+    token_address: String,
+) -> ApiResult<Asset> {
+    handlers::get_token_logo(&context, &chain_id, &token_address).await
+}
@@ -0,0 +1,162 @@
+use crate::cache::MockCache;
+use crate::routes::chains::handlers::generate_asset_key;
+use crate::routes::chains::models::Asset;
+use crate::testing::setup::setup_rocket_with_mock_cache;
+use crate::utils::http_client::{BinaryResponse, MockHttpClient};
+use mockall::predicate::eq;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::{Client, LocalRequest};
+
+const LOGO_URI: &str = "https://test.token.image.url";
+const PNG_HEADER: &[u8] = &[0x89, 0x50, 0x4e, 0x47];
+
+fn cached_rinkeby() -> MockCache {
+    let chain_key = format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"));
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key))
+        .return_const(Some(format!(
+            "200;{}",
+            crate::tests::json::CHAIN_INFO_RINKEBY
+        )));
+    mock_cache
+}
+
+async fn client(mock_http_client: MockHttpClient, mock_cache: MockCache) -> Client {
+    Client::tracked(setup_rocket_with_mock_cache(
+        mock_http_client,
+        mock_cache,
+        routes![super::super::routes::get_chain_asset],
+    ))
+    .await
+    .expect("valid rocket instance")
+}
+
+fn asset_request<'c>(client: &'c Client, kind: &str) -> LocalRequest<'c> {
+    let mut request = client.get(format!("/v1/chains/4/assets/{}", kind));
+    request.add_header(Header::new("Host", "test.gnosis.io"));
+    request
+}
+
+#[rocket::async_test]
+async fn native_currency_logo_fetched_and_cached() {
+    let mut mock_cache = cached_rinkeby();
+    mock_cache
+        .expect_fetch()
+        .with(eq(generate_asset_key(LOGO_URI)))
+        .times(1)
+        .return_const(None);
+    mock_cache
+        .expect_create()
+        .withf(|key, value, _| {
+            key == generate_asset_key(LOGO_URI) && value == "image/png;0x89504e47"
+        })
+        .times(1)
+        .return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_get_binary()
+        .times(1)
+        .withf(|request, _| request.url() == LOGO_URI)
+        .return_once(|_, _| {
+            Ok(BinaryResponse {
+                content_type: Some(String::from("image/PNG; charset=binary")),
+                body: PNG_HEADER.to_vec(),
+            })
+        });
+
+    let client = client(mock_http_client, mock_cache).await;
+    let response = asset_request(&client, "native-currency-logo")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::PNG));
+    assert_eq!(
+        response.headers().get_one("X-Content-Type-Options"),
+        Some("nosniff")
+    );
+    assert_eq!(response.into_bytes().await.unwrap(), PNG_HEADER);
+}
+
+#[rocket::async_test]
+async fn cached_native_currency_logo_served_without_fetching() {
+    let mut mock_cache = cached_rinkeby();
+    mock_cache
+        .expect_fetch()
+        .with(eq(generate_asset_key(LOGO_URI)))
+        .times(1)
+        .return_const(Some(String::from("image/svg+xml;0x3c737667")));
+    mock_cache
+        .expect_ttl()
+        .with(eq(generate_asset_key(LOGO_URI)))
+        .times(1)
+        .return_const(Some(1000));
+    mock_cache.expect_create().times(0);
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_get_binary().times(0);
+
+    let client = client(mock_http_client, mock_cache).await;
+    let response = asset_request(&client, "native-currency-logo")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::SVG));
+    assert_eq!(response.into_string().await.unwrap(), "<svg");
+}
+
+#[rocket::async_test]
+async fn native_currency_logo_with_unsupported_content_type_not_served() {
+    let mut mock_cache = cached_rinkeby();
+    mock_cache
+        .expect_fetch()
+        .with(eq(generate_asset_key(LOGO_URI)))
+        .times(1)
+        .return_const(None);
+    mock_cache.expect_create().times(0);
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_get_binary()
+        .times(1)
+        .return_once(|_, _| {
+            Ok(BinaryResponse {
+                content_type: Some(String::from("text/html")),
+                body: b"<html></html>".to_vec(),
+            })
+        });
+
+    let client = client(mock_http_client, mock_cache).await;
+    let response = asset_request(&client, "native-currency-logo")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadGateway);
+}
+
+#[rocket::async_test]
+async fn unknown_asset_kind_not_found() {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_get_binary().times(0);
+
+    let client = client(mock_http_client, MockCache::new()).await;
+    let response = asset_request(&client, "chain-logo").dispatch().await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn asset_cache_round_trip() {
+    let asset = Asset {
+        content_type: String::from("image/png"),
+        body: PNG_HEADER.to_vec(),
+    };
+
+    assert_eq!(Asset::from_cached(&asset.to_cached()), Some(asset));
+    assert_eq!(Asset::from_cached("image/png"), None);
+}
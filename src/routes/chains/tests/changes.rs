@@ -0,0 +1,104 @@
+use crate::cache::MockCache;
+use crate::routes::chains::handlers::{track_chain_changes, TrackedChain, CHAIN_CHANGES_KEY};
+use ethcontract_common::hash::keccak256;
+use mockall::predicate::eq;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn content_hash(chain: &Value) -> String {
+    to_hex_string!(keccak256(chain.to_string().as_bytes()))
+}
+
+fn tracked(entries: &[(&str, String)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(chain_id, value)| (chain_id.to_string(), value.to_string()))
+        .collect()
+}
+
+#[rocket::async_test]
+async fn track_chain_changes_records_new_chains() {
+    let chain = json!({ "chainId": "4", "chainName": "Rinkeby" });
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch_hash()
+        .with(eq(CHAIN_CHANGES_KEY))
+        .times(1)
+        .return_const(HashMap::new());
+    mock_cache
+        .expect_insert_in_hash()
+        .with(
+            eq(CHAIN_CHANGES_KEY),
+            eq("4"),
+            eq(format!("1000;{}", content_hash(&chain))),
+        )
+        .times(1)
+        .return_const(());
+
+    let actual = track_chain_changes(&mock_cache, &[chain.clone()], 1000).await;
+
+    assert_eq!(
+        actual.get("4"),
+        Some(&TrackedChain {
+            content_hash: content_hash(&chain),
+            changed_at: 1000,
+        })
+    );
+}
+
+#[rocket::async_test]
+async fn track_chain_changes_keeps_unchanged_chains() {
+    let chain = json!({ "chainId": "4", "chainName": "Rinkeby" });
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch_hash()
+        .times(1)
+        .return_const(tracked(&[("4", format!("500;{}", content_hash(&chain)))]));
+    mock_cache.expect_insert_in_hash().times(0);
+
+    let actual = track_chain_changes(&mock_cache, &[chain], 1000).await;
+
+    assert_eq!(actual.get("4").unwrap().changed_at, 500);
+}
+
+#[rocket::async_test]
+async fn track_chain_changes_updates_changed_and_removed_chains() {
+    let previous = json!({ "chainId": "4", "chainName": "Rinkeby" });
+    let current = json!({ "chainId": "4", "chainName": "Rinkeby Testnet" });
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch_hash()
+        .times(1)
+        .return_const(tracked(&[
+            ("4", format!("500;{}", content_hash(&previous))),
+            ("5", format!("500;{}", content_hash(&previous))),
+            ("100", String::from("200;removed")),
+        ]));
+    mock_cache
+        .expect_insert_in_hash()
+        .with(
+            eq(CHAIN_CHANGES_KEY),
+            eq("4"),
+            eq(format!("1000;{}", content_hash(&current))),
+        )
+        .times(1)
+        .return_const(());
+    mock_cache
+        .expect_insert_in_hash()
+        .with(eq(CHAIN_CHANGES_KEY), eq("5"), eq("1000;removed"))
+        .times(1)
+        .return_const(());
+
+    let actual = track_chain_changes(&mock_cache, &[current], 1000).await;
+
+    assert_eq!(actual.get("4").unwrap().changed_at, 1000);
+    assert_eq!(
+        actual.get("5"),
+        Some(&TrackedChain {
+            content_hash: String::from("removed"),
+            changed_at: 1000,
+        })
+    );
+    // Already removed before
+    assert_eq!(actual.get("100").unwrap().changed_at, 200);
+}
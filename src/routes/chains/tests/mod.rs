@@ -1 +1,3 @@
+mod assets;
 mod chains;
+mod changes;
@@ -1,10 +1,18 @@
 use crate::cache::cache_operations::RequestCached;
+use crate::common::models::page::{Page, PageMetadata};
 use crate::config::collectibles_request_timeout;
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::collectibles::metadata::with_refreshed_metadata;
+use crate::routes::transactions::handlers::build_absolute_uri;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
-use rocket::response::content;
+use crate::utils::response_budget::json_within_budget;
+use crate::utils::spam::is_spam_collectible;
 use rocket::response::content::Json;
+use serde_json::Value;
+
+pub const DEFAULT_COLLECTIBLES_LIMIT: u64 = 20;
+pub const MAX_COLLECTIBLES_LIMIT: u64 = 100;
 
 pub async fn collectibles(
     context: &RequestContext,
@@ -14,19 +22,128 @@ pub async fn collectibles(
     exclude_spam: Option<bool>,
 ) -> ApiResult<Json<String>> {
     let info_provider = DefaultInfoProvider::new(chain_id, &context);
+    let exclude_spam = exclude_spam.unwrap_or(true);
 
     let url = core_uri!(
         info_provider,
         "/v1/safes/{}/collectibles/?trusted={}&exclude_spam={}",
         safe_address,
         trusted.unwrap_or(false),
-        exclude_spam.unwrap_or(true)
+        exclude_spam
     )?;
 
-    Ok(content::Json(
-        RequestCached::new_from_context(url, &context)
-            .request_timeout(collectibles_request_timeout())
-            .execute()
-            .await?,
-    ))
+    let body = RequestCached::new_from_context(url, &context)
+        .request_timeout(collectibles_request_timeout())
+        .execute()
+        .await?;
+    let collectibles: Vec<Value> = serde_json::from_str(&body)?;
+
+    let collectibles = mark_spam(collectibles, exclude_spam);
+    let collectibles =
+        with_refreshed_metadata(context.cache().as_ref(), chain_id, collectibles).await;
+
+    json_within_budget(context, &collectibles)
+}
+
+/// Pages over the paginated collectibles of the transaction service, each page cached on its own
+pub async fn collectibles_page(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    cursor: &Option<String>,
+    limit: Option<u64>,
+    trusted: Option<bool>,
+    exclude_spam: Option<bool>,
+) -> ApiResult<Page<Value>> {
+    let info_provider = DefaultInfoProvider::new(chain_id, &context);
+    let trusted = trusted.unwrap_or(false);
+    let exclude_spam = exclude_spam.unwrap_or(true);
+    let page_metadata = collectibles_page_metadata(cursor, limit);
+
+    let url = core_uri!(
+        info_provider,
+        "/v2/safes/{}/collectibles/?{}&trusted={}&exclude_spam={}",
+        safe_address,
+        page_metadata.to_url_string(),
+        trusted,
+        exclude_spam
+    )?;
+    let body = RequestCached::new_from_context(url, &context)
+        .request_timeout(collectibles_request_timeout())
+        .execute()
+        .await?;
+    let page: Page<Value> = serde_json::from_str(&body)?;
+    // Spam is filtered per page, so pages may hold less than `limit` collectibles
+    let results = with_refreshed_metadata(
+        context.cache().as_ref(),
+        chain_id,
+        mark_spam(page.results, exclude_spam),
+    )
+    .await;
+
+    let build_cursor = |offset: u64| {
+        build_absolute_uri(
+            context,
+            uri!(crate::routes::collectibles::routes::get_collectibles_page(
+                chain_id,
+                safe_address,
+                Some(
+                    PageMetadata {
+                        offset,
+                        limit: page_metadata.limit,
+                    }
+                    .to_url_string()
+                ),
+                Some(page_metadata.limit),
+                Some(trusted),
+                Some(exclude_spam)
+            )),
+        )
+    };
+    Ok(Page {
+        count: page.count,
+        next: page
+            .next
+            .as_ref()
+            .map(|_| build_cursor(page_metadata.offset + page_metadata.limit)),
+        previous: page
+            .previous
+            .as_ref()
+            .map(|_| build_cursor(page_metadata.offset.saturating_sub(page_metadata.limit))),
+        results,
+        incomplete: None,
+    })
+}
+
+/// `limit` takes precedence over the limit of the cursor and is capped at
+/// [MAX_COLLECTIBLES_LIMIT]
+pub fn collectibles_page_metadata(cursor: &Option<String>, limit: Option<u64>) -> PageMetadata {
+    let mut page_metadata = match cursor {
+        Some(cursor) => PageMetadata::from_cursor(cursor),
+        None => PageMetadata {
+            offset: 0,
+            limit: DEFAULT_COLLECTIBLES_LIMIT,
+        },
+    };
+    if let Some(limit) = limit {
+        page_metadata.limit = limit;
+    }
+    page_metadata.limit = page_metadata.limit.max(1).min(MAX_COLLECTIBLES_LIMIT);
+    page_metadata
+}
+
+fn mark_spam(collectibles: Vec<Value>, exclude_spam: bool) -> Vec<Value> {
+    collectibles
+        .into_iter()
+        .filter_map(|mut collectible| {
+            let spam = is_spam_collectible(&collectible);
+            if exclude_spam && spam {
+                return None;
+            }
+            if let Some(fields) = collectible.as_object_mut() {
+                fields.insert("spam".to_string(), Value::Bool(spam));
+            }
+            Some(collectible)
+        })
+        .collect()
 }
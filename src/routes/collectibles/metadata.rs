@@ -0,0 +1,286 @@
+//! Metadata of single collectibles refreshed on request, for images and names the transaction
+//! service indexed before they were set (or that changed since). The token uri is read from the
+//! contract (`tokenURI` of ERC721, `uri` of ERC1155), its json is fetched and stored per chain,
+//! and both collectibles routes serve the stored metadata instead of the indexed one.
+//!
+//! `ipfs://` uris are fetched via `NFT_IPFS_GATEWAY`, other hosts only if they are allowed for
+//! outbound requests, json `data:` uris are read inline.
+use crate::cache::Cache;
+use crate::config::{collectibles_request_timeout, nft_ipfs_gateway, nft_refresh_window};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::rpc::{EthCall, RpcProvider};
+use crate::routes::collectibles::models::CollectibleMetadata;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::http_client::Request;
+use crate::utils::transactions::{decode_hex, parse_address};
+use ethabi::{ParamType, Token, Uint};
+use ethcontract_common::hash::keccak256;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub const ERC721_TOKEN_URI_SIGNATURE: &str = "tokenURI(uint256)";
+pub const ERC1155_URI_SIGNATURE: &str = "uri(uint256)";
+
+const NFT_METADATA_KEY_BASE: &str = "nft_metadata";
+const NFT_REFRESH_KEY_BASE: &str = "nft_refresh";
+const NFT_REFRESH_FIELD: &str = "refreshes";
+const IPFS_SCHEME: &str = "ipfs://";
+const JSON_DATA_URI_PREFIX: &str = "data:application/json";
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Where the metadata json of a token uri is read from
+#[derive(Debug, PartialEq)]
+pub enum MetadataSource {
+    Url(String),
+    Inline(String),
+}
+
+/// Reads the metadata of the collectible `token_id` of `address` again and stores it, at most
+/// once per `NFT_REFRESH_WINDOW` for every collectible
+pub async fn refresh_metadata(
+    context: &RequestContext,
+    chain_id: &str,
+    address: &str,
+    token_id: &str,
+) -> ApiResult<CollectibleMetadata> {
+    parse_address(address).map_err(|_| client_error!(422, "Invalid collectible address"))?;
+    let id = Uint::from_dec_str(token_id)
+        .map_err(|_| client_error!(422, "Token id must be a decimal number"))?;
+    let cache = context.cache();
+    if !claim_refresh(cache.as_ref(), chain_id, address, token_id).await {
+        return Err(client_error!(
+            429,
+            "Metadata of this collectible was refreshed recently"
+        ));
+    }
+
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let rpc_provider = RpcProvider::new(context, &info_provider.chain_info().await?);
+    let uri = read_token_uri(&rpc_provider, address, &id).await?;
+    let body = match metadata_source(&uri, &id)? {
+        MetadataSource::Url(url) => {
+            let mut request = Request::new(url);
+            request.timeout(Duration::from_millis(collectibles_request_timeout()));
+            context.http_client().get(request).await?.body
+        }
+        MetadataSource::Inline(body) => body,
+    };
+    let metadata: Value = serde_json::from_str(&body).map_err(|_| {
+        ApiError::new_from_message_with_code(422, format!("Metadata at {} is not valid json", uri))
+    })?;
+
+    let collectible = collectible_metadata(address, token_id, &uri, metadata);
+    cache
+        .insert_in_hash(
+            &metadata_key(chain_id),
+            &metadata_field(address, token_id),
+            &serde_json::to_string(&collectible)?,
+        )
+        .await;
+    Ok(collectible)
+}
+
+/// `collectibles` with the metadata refreshed on `chain_id`, a single cache read per response
+pub async fn with_refreshed_metadata(
+    cache: &dyn Cache,
+    chain_id: &str,
+    collectibles: Vec<Value>,
+) -> Vec<Value> {
+    let refreshed = cache.fetch_hash(&metadata_key(chain_id)).await;
+    if refreshed.is_empty() {
+        return collectibles;
+    }
+    apply_refreshed_metadata(collectibles, &refreshed)
+}
+
+pub fn apply_refreshed_metadata(
+    collectibles: Vec<Value>,
+    refreshed: &HashMap<String, String>,
+) -> Vec<Value> {
+    collectibles
+        .into_iter()
+        .map(|mut collectible| {
+            let field = match (
+                collectible.get("address").and_then(Value::as_str),
+                collectible.get("id").and_then(Value::as_str),
+            ) {
+                (Some(address), Some(id)) => metadata_field(address, id),
+                _ => return collectible,
+            };
+            let metadata = refreshed
+                .get(&field)
+                .and_then(|metadata| serde_json::from_str::<CollectibleMetadata>(metadata).ok());
+            if let (Some(metadata), Some(fields)) = (metadata, collectible.as_object_mut()) {
+                fields.insert("uri".to_string(), Value::String(metadata.uri));
+                if let Some(name) = metadata.name {
+                    fields.insert("name".to_string(), Value::String(name));
+                }
+                if let Some(description) = metadata.description {
+                    fields.insert("description".to_string(), Value::String(description));
+                }
+                if let Some(image_uri) = metadata.image_uri {
+                    fields.insert("imageUri".to_string(), Value::String(image_uri));
+                }
+                fields.insert("metadata".to_string(), metadata.metadata);
+            }
+            collectible
+        })
+        .collect()
+}
+
+pub fn collectible_metadata(
+    address: &str,
+    token_id: &str,
+    uri: &str,
+    metadata: Value,
+) -> CollectibleMetadata {
+    let text = |key: &str| {
+        metadata
+            .get(key)
+            .and_then(Value::as_str)
+            .map(|value| value.to_string())
+    };
+    CollectibleMetadata {
+        address: address.to_string(),
+        id: token_id.to_string(),
+        uri: uri.to_string(),
+        name: text("name"),
+        description: text("description"),
+        image_uri: text("image")
+            .or_else(|| text("image_url"))
+            .map(|image| ipfs_to_gateway(&image)),
+        metadata,
+    }
+}
+
+/// ERC1155 contracts don't implement `tokenURI`, their `uri` is read instead
+async fn read_token_uri(rpc_provider: &RpcProvider, address: &str, id: &Uint) -> ApiResult<String> {
+    for signature in [ERC721_TOKEN_URI_SIGNATURE, ERC1155_URI_SIGNATURE].iter() {
+        let call = EthCall {
+            from: None,
+            to: address.to_string(),
+            data: token_uri_call_data(signature, id),
+            value: None,
+        };
+        if let Ok(result) = rpc_provider.call(&call).await? {
+            if let Some(uri) = decode_token_uri(&result).filter(|uri| !uri.is_empty()) {
+                return Ok(uri);
+            }
+        }
+    }
+    Err(client_error!(422, "Collectible has no token uri"))
+}
+
+pub fn token_uri_call_data(signature: &str, id: &Uint) -> String {
+    let mut encoded = keccak256(signature.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[Token::Uint(*id)]));
+    to_hex_string!(encoded)
+}
+
+/// `None` if the return data is not a single string
+pub fn decode_token_uri(result: &str) -> Option<String> {
+    let data = decode_hex(result).ok()?;
+    match ethabi::decode(&[ParamType::String], &data).ok()?.pop()? {
+        Token::String(uri) => Some(uri),
+        _ => None,
+    }
+}
+
+/// The `{id}` placeholder of ERC1155 uris is replaced by the hex encoded, 64 digits token id
+pub fn metadata_source(uri: &str, id: &Uint) -> ApiResult<MetadataSource> {
+    let uri = uri.trim().replace("{id}", &format!("{:064x}", id));
+    if uri.starts_with(JSON_DATA_URI_PREFIX) {
+        return decode_data_uri(&uri)
+            .map(MetadataSource::Inline)
+            .ok_or_else(|| client_error!(422, "Invalid metadata data uri"));
+    }
+    if uri.starts_with(IPFS_SCHEME) || uri.starts_with("https://") || uri.starts_with("http://") {
+        return Ok(MetadataSource::Url(ipfs_to_gateway(&uri)));
+    }
+    Err(ApiError::new_from_message_with_code(
+        422,
+        format!("Unsupported token uri: {}", uri),
+    ))
+}
+
+pub fn ipfs_to_gateway(uri: &str) -> String {
+    match uri.strip_prefix(IPFS_SCHEME) {
+        Some(path) => format!(
+            "{}/{}",
+            nft_ipfs_gateway().trim_end_matches('/'),
+            path.trim_start_matches("ipfs/")
+        ),
+        None => uri.to_string(),
+    }
+}
+
+fn decode_data_uri(uri: &str) -> Option<String> {
+    let (header, data) = uri.split_at(uri.find(',')?);
+    let data = &data[1..];
+    if header.ends_with(";base64") {
+        String::from_utf8(decode_base64(data)?).ok()
+    } else {
+        String::from_utf8(percent_decode(data)?).ok()
+    }
+}
+
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in data.bytes().filter(|byte| *byte != b'=') {
+        let value = BASE64_ALPHABET.iter().position(|c| *c == byte)? as u32;
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(decoded)
+}
+
+fn percent_decode(data: &str) -> Option<Vec<u8>> {
+    let bytes = data.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = data.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    Some(decoded)
+}
+
+// Only the first refresh within the window goes through
+async fn claim_refresh(cache: &dyn Cache, chain_id: &str, address: &str, token_id: &str) -> bool {
+    cache
+        .increment_in_hash(
+            &format!(
+                "{}_{}_{}",
+                NFT_REFRESH_KEY_BASE,
+                chain_id,
+                metadata_field(address, token_id)
+            ),
+            NFT_REFRESH_FIELD,
+            nft_refresh_window(),
+        )
+        .await
+        == 1
+}
+
+fn metadata_key(chain_id: &str) -> String {
+    format!("{}_{}", NFT_METADATA_KEY_BASE, chain_id)
+}
+
+fn metadata_field(address: &str, token_id: &str) -> String {
+    format!("{}_{}", address.to_lowercase(), token_id)
+}
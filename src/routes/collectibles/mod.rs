@@ -1,3 +1,9 @@
 #[doc(hidden)]
 pub mod handlers;
+#[doc(hidden)]
+pub mod metadata;
+pub mod models;
 pub mod routes;
+
+#[cfg(test)]
+mod tests;
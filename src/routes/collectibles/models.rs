@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Metadata of a collectible as read from its token uri by the gateway. Replaces the fields of
+/// the collectible in the responses of the transaction service.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectibleMetadata {
+    pub address: String,
+    pub id: String,
+    pub uri: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image_uri: Option<String>,
+    pub metadata: Value,
+}
@@ -1,6 +1,8 @@
-use crate::routes::collectibles::handlers::collectibles;
+use crate::routes::collectibles::handlers::{collectibles, collectibles_page};
+use crate::routes::collectibles::metadata::refresh_metadata;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
+use crate::utils::response_budget::json_within_budget;
 use rocket::response::content;
 
 /**
@@ -11,6 +13,8 @@ use rocket::response::content;
  *
  * The collectibles endpoint does not implement any logic in the client-gateway. The response from the core services is cached and then forwarded to the clients.
  *
+ * Every collectible of the Safe is returned at once, which can time out for Safes holding many of them. Prefer the paginated `/v2/chains/<chain_id>/safes/<safe_address>/collectibles`.
+ *
  * ## Path
  *
  * - `/v1/chains/<chain_id>/safes/<safe_address>/collectibles?<trusted>&<exclude_spam>` : Returns a list of the ERC721 tokens stored in a safe
@@ -18,7 +22,7 @@ use rocket::response::content;
  * ## Query parameters
  *
  * `<trusted>` : A token is defined as trusted by our core handlers process when adding them. Default value is `false`
- * `<exclude_spam>`: A token is defined as spam by our core handlers process when adding them. Default value is `true`
+ * `<exclude_spam>`: A token is defined as spam by our core handlers process when adding them. Default value is `true`. Tokens flagged by the gateway heuristics (words of `SPAM_NAME_PATTERNS` in their name or symbol, `SPAM_TOKEN_DENYLIST`) are excluded as well, otherwise they are returned with `spam: true`
  *
  * ## Models
  *
@@ -166,3 +170,116 @@ pub async fn get_collectibles(
     )
     .await
 }
+
+/**
+ * `/v2/chains/<chain_id>/safes/<safe_address>/collectibles?<cursor>&<limit>&<trusted>&<exclude_spam>` <br />
+ * Returns a [Page](crate::common::models::page::Page) of collectibles
+ *
+ * # Collectibles
+ *
+ * Same collectibles as `/v1/chains/<chain_id>/safes/<safe_address>/collectibles`, one page at a time. Every page of the core services is cached on its own.
+ *
+ * ## Query parameters
+ *
+ * `<cursor>` : Taken from the `next` or `previous` link (or `pageInfo.cursor`) of the previous page
+ * `<limit>` : Collectibles per page, defaults to 20 and is capped at 100. Takes precedence over the limit of the cursor
+ * `<trusted>`, `<exclude_spam>` : Same as for `/v1/chains/<chain_id>/safes/<safe_address>/collectibles`. Spam is excluded per page, so pages may hold fewer collectibles than `<limit>`
+ *
+ * ## Models
+ *
+ * ```json
+ * {
+ *   "count": 120,
+ *   "next": "https://safe-client.gnosis.io/v2/chains/4/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/collectibles?cursor=limit%3D20%26offset%3D20&limit=20&trusted=false&exclude_spam=true",
+ *   "previous": null,
+ *   "pageInfo": {
+ *     "hasNext": true,
+ *     "cursor": "limit=20&offset=20"
+ *   },
+ *   "results": [
+ *     {
+ *       "address": "string",
+ *       "tokenName": "string",
+ *       "tokenSymbol": "string",
+ *       "logoUri": "string",
+ *       "id": "string",
+ *       "uri": "string",
+ *       "name": "string",
+ *       "description": "string",
+ *       "imageUri": "string",
+ *       "metadata": {},
+ *       "spam": false
+ *     }
+ *   ]
+ * }
+ * ```
+ */
+#[get(
+    "/v2/chains/<chain_id>/safes/<safe_address>/collectibles?<cursor>&<limit>&<trusted>&<exclude_spam>"
+)]
+pub async fn get_collectibles_page(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    trusted: Option<bool>,
+    exclude_spam: Option<bool>,
+) -> ApiResult<content::Json<String>> {
+    let page = collectibles_page(
+        &context,
+        chain_id.as_str(),
+        safe_address.as_str(),
+        &cursor,
+        limit,
+        trusted,
+        exclude_spam,
+    )
+    .await?;
+    json_within_budget(&context, &page)
+}
+
+/**
+ * `/v1/chains/<chain_id>/collectibles/<address>/<token_id>/refresh` <br />
+ * Returns [CollectibleMetadata](crate::routes::collectibles::models::CollectibleMetadata)
+ *
+ * # Collectible refresh
+ *
+ * Reads the metadata of a single collectible again from its token uri (`tokenURI` for ERC721, `uri` for ERC1155), for images or names that were missing or changed since the transaction service indexed them. Both collectibles routes return the refreshed metadata from then on.
+ *
+ * `ipfs://` uris are read via `NFT_IPFS_GATEWAY`. Other hosts must be allowed for outbound requests (`OUTBOUND_ALLOWED_HOSTS`), json `data:` uris are read inline.
+ *
+ * Every collectible can be refreshed once per `NFT_REFRESH_WINDOW`, after that requests fail with `429`. Collectibles without a token uri, or with metadata that is not json, fail with `422`.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/collectibles/<address>/<token_id>/refresh` : `<token_id>` is the decimal id of the token
+ *
+ * ## Models
+ *
+ * ```json
+ * {
+ *   "address": "0xD753e03c05533F85bA9695C139771b1E9698a53C",
+ *   "id": "2",
+ *   "uri": "ipfs://QmTy8w65yBXgyfG2ZBg5TrfB2hPjrDQH3RCQFJGkARStJb/2",
+ *   "name": "Chiken dinner",
+ *   "description": "This token is meant for testing. ",
+ *   "imageUri": "https://cloudflare-ipfs.com/ipfs/QmYSmtCNNe5bitJmGAwC8h5E9EC9Fy4YS1ihbkqirwaqUz/2.png",
+ *   "metadata": {
+ *     "name": "Chiken dinner",
+ *     "description": "This token is meant for testing. ",
+ *     "image": "ipfs://QmYSmtCNNe5bitJmGAwC8h5E9EC9Fy4YS1ihbkqirwaqUz/2.png"
+ *   }
+ * }
+ * ```
+ */
+#[post("/v1/chains/<chain_id>/collectibles/<address>/<token_id>/refresh")]
+pub async fn post_collectible_refresh(
+    context: RequestContext,
+    chain_id: String,
+    address: String,
+    token_id: String,
+) -> ApiResult<content::Json<String>> {
+    let metadata = refresh_metadata(&context, &chain_id, &address, &token_id).await?;
+    Ok(content::Json(serde_json::to_string(&metadata)?))
+}
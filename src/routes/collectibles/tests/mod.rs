@@ -0,0 +1,282 @@
+use crate::cache::MockCache;
+use crate::common::models::page::PageMetadata;
+use crate::routes::collectibles::handlers::{collectibles_page, collectibles_page_metadata};
+use crate::routes::collectibles::metadata::{
+    apply_refreshed_metadata, collectible_metadata, decode_token_uri, metadata_source,
+    refresh_metadata, token_uri_call_data, MetadataSource, ERC721_TOKEN_URI_SIGNATURE,
+};
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::MockHttpClient;
+use ethabi::{Token, Uint};
+use mockall::predicate::eq;
+use serde_json::json;
+use std::collections::HashMap;
+
+const SAFE_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+#[test]
+fn collectibles_page_metadata_defaults() {
+    assert_eq!(
+        collectibles_page_metadata(&None, None),
+        PageMetadata {
+            offset: 0,
+            limit: 20,
+        }
+    );
+}
+
+#[test]
+fn collectibles_page_metadata_limit_overrides_cursor() {
+    let cursor = Some(String::from("limit=10&offset=30"));
+
+    assert_eq!(
+        collectibles_page_metadata(&cursor, None),
+        PageMetadata {
+            offset: 30,
+            limit: 10,
+        }
+    );
+    assert_eq!(
+        collectibles_page_metadata(&cursor, Some(5)),
+        PageMetadata {
+            offset: 30,
+            limit: 5,
+        }
+    );
+}
+
+#[test]
+fn collectibles_page_metadata_caps_limit() {
+    assert_eq!(collectibles_page_metadata(&None, Some(1000)).limit, 100);
+    assert_eq!(collectibles_page_metadata(&None, Some(0)).limit, 1);
+}
+
+#[rocket::async_test]
+async fn collectibles_page_links_gateway_cursors() {
+    let chain_key = format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"));
+    let page_path = format!(
+        "/v2/safes/{}/collectibles/?limit=2&offset=2&trusted=false&exclude_spam=true",
+        SAFE_ADDRESS
+    );
+    let backend_page = serde_json::json!({
+        "count": 5,
+        "next": "https://safe-transaction.rinkeby.gnosis.io/api/v2/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/collectibles/?limit=2&offset=4",
+        "previous": "https://safe-transaction.rinkeby.gnosis.io/api/v2/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/collectibles/?limit=2",
+        "results": [
+            { "address": "0xD753e03c05533F85bA9695C139771b1E9698a53C", "tokenName": "Main", "tokenSymbol": "JOSE", "id": "2", "name": "Chiken dinner" },
+            { "address": "0xD753e03c05533F85bA9695C139771b1E9698a53C", "tokenName": "Main", "tokenSymbol": "JOSE", "id": "4", "name": "Chiken dinner" }
+        ]
+    });
+
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key))
+        .times(1)
+        .return_const(Some(String::from(crate::tests::json::CHAIN_INFO_RINKEBY)));
+    mock_cache
+        .expect_fetch()
+        .withf(move |key| key.starts_with("c_reqs_") && key.ends_with(&page_path))
+        .times(1)
+        .return_const(Some(backend_page.to_string()));
+    mock_cache
+        .expect_fetch_hash()
+        .with(eq(String::from("nft_metadata_4")))
+        .times(1)
+        .return_const(HashMap::new());
+    let context = RequestContext::mock(
+        String::from("/v2/chains/4/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/collectibles"),
+        String::from("https://safe-client.gnosis.io"),
+        MockHttpClient::new(),
+        mock_cache,
+    );
+
+    let actual = collectibles_page(
+        &context,
+        "4",
+        SAFE_ADDRESS,
+        &Some(String::from("limit=2&offset=2")),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let link = |cursor: &str| {
+        format!(
+            "https://safe-client.gnosis.io/v2/chains/4/safes/{}/collectibles?cursor={}&limit=2&trusted=false&exclude_spam=true",
+            SAFE_ADDRESS, cursor
+        )
+    };
+    assert_eq!(actual.count, Some(5));
+    assert_eq!(actual.next, Some(link("limit%3D2%26offset%3D4")));
+    assert_eq!(actual.previous, Some(link("limit%3D2%26offset%3D0")));
+    assert_eq!(actual.results.len(), 2);
+    assert_eq!(actual.results[0]["spam"], false);
+}
+
+#[test]
+fn token_uri_call_data_encodes_token_id() {
+    assert_eq!(
+        token_uri_call_data(ERC721_TOKEN_URI_SIGNATURE, &Uint::from(2)),
+        "0xc87b56dd0000000000000000000000000000000000000000000000000000000000000002"
+    );
+}
+
+#[test]
+fn decode_token_uri_reads_string() {
+    let result = to_hex_string!(ethabi::encode(&[Token::String(String::from(
+        "ipfs://QmTy8w65yBXgyfG2ZBg5TrfB2hPjrDQH3RCQFJGkARStJb/2"
+    ))]));
+
+    assert_eq!(
+        decode_token_uri(&result),
+        Some(String::from(
+            "ipfs://QmTy8w65yBXgyfG2ZBg5TrfB2hPjrDQH3RCQFJGkARStJb/2"
+        ))
+    );
+    assert_eq!(decode_token_uri("0x"), None);
+}
+
+#[test]
+fn metadata_source_resolves_ipfs_and_erc1155_ids() {
+    assert_eq!(
+        metadata_source(
+            "ipfs://QmTy8w65yBXgyfG2ZBg5TrfB2hPjrDQH3RCQFJGkARStJb/2",
+            &Uint::from(2)
+        )
+        .unwrap(),
+        MetadataSource::Url(String::from(
+            "https://cloudflare-ipfs.com/ipfs/QmTy8w65yBXgyfG2ZBg5TrfB2hPjrDQH3RCQFJGkARStJb/2"
+        ))
+    );
+    assert_eq!(
+        metadata_source("https://api.example.com/tokens/{id}.json", &Uint::from(255)).unwrap(),
+        MetadataSource::Url(String::from(
+            "https://api.example.com/tokens/00000000000000000000000000000000000000000000000000000000000000ff.json"
+        ))
+    );
+}
+
+#[test]
+fn metadata_source_decodes_data_uris() {
+    assert_eq!(
+        metadata_source(
+            "data:application/json;base64,eyJuYW1lIjoiQ2hpa2VuIGRpbm5lciJ9",
+            &Uint::from(2)
+        )
+        .unwrap(),
+        MetadataSource::Inline(String::from("{\"name\":\"Chiken dinner\"}"))
+    );
+    assert_eq!(
+        metadata_source(
+            "data:application/json;utf8,{\"name\":\"Chiken%20dinner\"}",
+            &Uint::from(2)
+        )
+        .unwrap(),
+        MetadataSource::Inline(String::from("{\"name\":\"Chiken dinner\"}"))
+    );
+}
+
+#[test]
+fn metadata_source_rejects_unsupported_uris() {
+    let error = metadata_source("ar://8Vje5kmuRKaJwYht19rl", &Uint::from(2)).unwrap_err();
+
+    assert_eq!(error.status, 422);
+}
+
+#[test]
+fn apply_refreshed_metadata_replaces_indexed_fields() {
+    let refreshed = collectible_metadata(
+        "0xD753e03c05533F85bA9695C139771b1E9698a53C",
+        "2",
+        "ipfs://QmTy8w65yBXgyfG2ZBg5TrfB2hPjrDQH3RCQFJGkARStJb/2",
+        json!({ "name": "Chiken dinner", "image": "ipfs://QmYSmtCNNe5bitJmGAwC8h5E9EC9Fy4YS1ihbkqirwaqUz/2.png" }),
+    );
+    let mut stored = HashMap::new();
+    stored.insert(
+        String::from("0xd753e03c05533f85ba9695c139771b1e9698a53c_2"),
+        serde_json::to_string(&refreshed).unwrap(),
+    );
+    let collectibles = vec![
+        json!({ "address": "0xD753e03c05533F85bA9695C139771b1E9698a53C", "id": "2", "name": null, "description": "This token is meant for testing. ", "imageUri": null }),
+        json!({ "address": "0xD753e03c05533F85bA9695C139771b1E9698a53C", "id": "4", "name": null, "imageUri": null }),
+    ];
+
+    let actual = apply_refreshed_metadata(collectibles, &stored);
+
+    assert_eq!(actual[0]["name"], "Chiken dinner");
+    assert_eq!(
+        actual[0]["description"],
+        "This token is meant for testing. "
+    );
+    assert_eq!(
+        actual[0]["imageUri"],
+        "https://cloudflare-ipfs.com/ipfs/QmYSmtCNNe5bitJmGAwC8h5E9EC9Fy4YS1ihbkqirwaqUz/2.png"
+    );
+    assert_eq!(actual[1]["name"], json!(null));
+}
+
+#[rocket::async_test]
+async fn refresh_metadata_is_rate_limited_per_collectible() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_increment_in_hash()
+        .with(
+            eq(String::from(
+                "nft_refresh_4_0xd753e03c05533f85ba9695c139771b1e9698a53c_2",
+            )),
+            eq(String::from("refreshes")),
+            eq(5 * 60 * 1000),
+        )
+        .times(1)
+        .return_const(2usize);
+    let context = RequestContext::mock(
+        String::from(
+            "/v1/chains/4/collectibles/0xD753e03c05533F85bA9695C139771b1E9698a53C/2/refresh",
+        ),
+        String::from("https://safe-client.gnosis.io"),
+        MockHttpClient::new(),
+        mock_cache,
+    );
+
+    let error = refresh_metadata(
+        &context,
+        "4",
+        "0xD753e03c05533F85bA9695C139771b1E9698a53C",
+        "2",
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.status, 429);
+}
+
+#[rocket::async_test]
+async fn refresh_metadata_rejects_invalid_token_ids() {
+    let context = RequestContext::mock(
+        String::from(
+            "/v1/chains/4/collectibles/0xD753e03c05533F85bA9695C139771b1E9698a53C/two/refresh",
+        ),
+        String::from("https://safe-client.gnosis.io"),
+        MockHttpClient::new(),
+        MockCache::new(),
+    );
+
+    let error = refresh_metadata(
+        &context,
+        "4",
+        "0xD753e03c05533F85bA9695C139771b1E9698a53C",
+        "two",
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.status, 422);
+}
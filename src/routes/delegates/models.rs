@@ -1,3 +1,4 @@
+use crate::utils::validation::{Validate, Validator};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -34,3 +35,33 @@ pub struct SafeDelegateDelete {
     delegate: String,
     signature: String,
 }
+
+impl Validate for DelegateCreate {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .optional_address("safe", &self.safe)
+            .address("delegate", &self.delegate)
+            .address("delegator", &self.delegator)
+            .signature("signature", &self.signature)
+            // Same limit as the transaction service
+            .length("label", &self.label, 1, 50);
+    }
+}
+
+impl Validate for DelegateDelete {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .address("delegate", &self.delegate)
+            .address("delegator", &self.delegator)
+            .signature("signature", &self.signature);
+    }
+}
+
+impl Validate for SafeDelegateDelete {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .address("safe", &self.safe)
+            .address("delegate", &self.delegate)
+            .signature("signature", &self.signature);
+    }
+}
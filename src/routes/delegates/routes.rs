@@ -1,9 +1,12 @@
+use crate::monitoring::audit::{self, AuditOperation, Caller};
 use crate::routes::delegates::handlers;
 use crate::routes::delegates::models::{DelegateCreate, DelegateDelete, SafeDelegateDelete};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
+use crate::utils::read_only;
+use crate::utils::validation::Validate;
 use rocket::response::content;
-use rocket::serde::json::Json;
+use rocket::serde::json::{Error, Json};
 
 #[get(
     "/v1/chains/<chain_id>/delegates?<safe>&<delegate>&<delegator>&<label>",
@@ -30,10 +33,23 @@ pub async fn get_delegates<'e>(
 )]
 pub async fn post_delegate<'e>(
     context: RequestContext,
+    caller: Caller,
     chain_id: String,
-    safe_delegate: Json<DelegateCreate>,
+    safe_delegate: Result<Json<DelegateCreate>, Error<'e>>,
 ) -> ApiResult<()> {
-    return handlers::post_delegate(&context, chain_id, safe_delegate.0).await;
+    read_only::ensure_writable(&context).await?;
+    let safe_delegate = safe_delegate?.0;
+    safe_delegate.validated()?;
+    let payload_hash = audit::payload_hash(&safe_delegate);
+    let result = handlers::post_delegate(&context, chain_id.to_string(), safe_delegate).await;
+    audit::record(
+        AuditOperation::CreateDelegate,
+        &chain_id,
+        &caller,
+        payload_hash,
+        &result,
+    );
+    result
 }
 
 #[delete(
@@ -43,12 +59,30 @@ pub async fn post_delegate<'e>(
 )]
 pub async fn delete_delegate<'e>(
     context: RequestContext,
+    caller: Caller,
     chain_id: String,
     delegate_address: String,
-    delegate_delete: Json<DelegateDelete>,
+    delegate_delete: Result<Json<DelegateDelete>, Error<'e>>,
 ) -> ApiResult<()> {
-    return handlers::delete_delegate(&context, chain_id, delegate_address, delegate_delete.0)
-        .await;
+    read_only::ensure_writable(&context).await?;
+    let delegate_delete = delegate_delete?.0;
+    delegate_delete.validated()?;
+    let payload_hash = audit::payload_hash(&delegate_delete);
+    let result = handlers::delete_delegate(
+        &context,
+        chain_id,
+        delegate_address.to_string(),
+        delegate_delete,
+    )
+    .await;
+    audit::record(
+        AuditOperation::DeleteDelegate,
+        &delegate_address,
+        &caller,
+        payload_hash,
+        &result,
+    );
+    result
 }
 
 #[delete(
@@ -58,17 +92,30 @@ pub async fn delete_delegate<'e>(
 )]
 pub async fn delete_safe_delegate<'e>(
     context: RequestContext,
+    caller: Caller,
     chain_id: String,
     safe_address: String,
     delegate_address: String,
-    delegate_delete: Json<SafeDelegateDelete>,
+    delegate_delete: Result<Json<SafeDelegateDelete>, Error<'e>>,
 ) -> ApiResult<()> {
-    return handlers::delete_safe_delegate(
+    read_only::ensure_writable(&context).await?;
+    let delegate_delete = delegate_delete?.0;
+    delegate_delete.validated()?;
+    let payload_hash = audit::payload_hash(&delegate_delete);
+    let result = handlers::delete_safe_delegate(
         &context,
         chain_id,
-        safe_address,
+        safe_address.to_string(),
         delegate_address,
-        delegate_delete.0,
+        delegate_delete,
     )
     .await;
+    audit::record(
+        AuditOperation::DeleteSafeDelegate,
+        &safe_address,
+        &caller,
+        payload_hash,
+        &result,
+    );
+    result
 }
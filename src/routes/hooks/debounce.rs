@@ -0,0 +1,42 @@
+use crate::common::models::backend::hooks::Payload;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Groups the hooks received per key (chain and Safe) while their window is open, so that bursts
+/// (e.g. an indexer catching up) result in a single invalidation and prefetch
+#[derive(Default)]
+pub struct HookDebouncer {
+    pending: Mutex<HashMap<String, Vec<Payload>>>,
+}
+
+impl HookDebouncer {
+    /// `true` if `payload` opens the window for `key`, the caller is then in charge of
+    /// applying the collected payloads with [HookDebouncer::take] once the window ends
+    pub fn enqueue(&self, key: &str, payload: Payload) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+        match pending.get_mut(key) {
+            Some(payloads) => {
+                payloads.push(payload);
+                false
+            }
+            None => {
+                pending.insert(key.to_string(), vec![payload]);
+                true
+            }
+        }
+    }
+
+    /// Closes the window for `key`, returning the payloads received in the meantime
+    pub fn take(&self, key: &str) -> Vec<Payload> {
+        self.pending.lock().unwrap().remove(key).unwrap_or_default()
+    }
+
+    /// Payloads waiting for their window to end
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap().values().map(Vec::len).sum()
+    }
+}
+
+pub fn debounce_key(chain_id: &str, safe_address: &str) -> String {
+    format!("{}_{}", chain_id, safe_address.to_lowercase())
+}
@@ -1,38 +1,415 @@
-use crate::cache::cache_operations::{Invalidate, InvalidationPattern, InvalidationScope};
+use crate::cache::cache_operations::{
+    CacheResponse, Invalidate, InvalidationPattern, InvalidationScope, RequestCached,
+};
 use crate::cache::Cache;
 use crate::common::models::backend::hooks::{Payload, PayloadDetails};
-use crate::utils::errors::ApiResult;
+use crate::common::models::page::Page;
+use crate::config::{
+    balances_cache_duration, feature_flag_hook_prefetch, feature_flag_ready_callbacks,
+    hook_debounce_window, hook_prefetch_fiat, safe_info_cache_duration,
+};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::balances::history::balances_with_snapshot;
+use crate::routes::hooks::debounce::{debounce_key, HookDebouncer};
+use crate::routes::safes::handlers::counterfactual::get_safe_info_or_pending;
+use crate::routes::transactions::handlers::queued::get_queued_transactions;
+use crate::routes::transactions::handlers::ready_callbacks;
+use crate::routes::transactions::models::summary::TransactionListItem;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::hook_events;
+use lazy_static::lazy_static;
+use rocket::futures::join;
+use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-pub fn invalidate_caches(cache: Arc<dyn Cache>, payload: &Payload) -> ApiResult<()> {
-    Invalidate::new(
-        InvalidationPattern::Any(InvalidationScope::Both, payload.address.to_owned()),
-        cache.clone(),
-    )
-    .execute();
-    payload.details.as_ref().map(|d| match d {
-        PayloadDetails::NewConfirmation(data) => {
-            Invalidate::new(
-                InvalidationPattern::Any(InvalidationScope::Both, String::from(&data.safe_tx_hash)),
-                cache.clone(),
-            )
-            .execute();
+// Bounds the work done by a single request, bigger bursts need to be split by the sender
+pub const MAX_BATCH_EVENTS: usize = 1000;
+
+lazy_static! {
+    static ref HOOK_DEBOUNCER: HookDebouncer = HookDebouncer::default();
+}
+
+/// Invalidates the caches affected by the hook. With a debounce window configured, hooks for the
+/// same Safe are collected until the window ends and then applied together.
+pub async fn update_caches(context: &RequestContext, payload: &Payload) -> ApiResult<()> {
+    hook_events::publish(context.http_client(), std::slice::from_ref(payload));
+    let window = hook_debounce_window();
+    let chain_id = match payload.chain_id.as_ref() {
+        Some(chain_id) if window > 0 => chain_id,
+        _ => return apply_hooks(context, &[payload.to_owned()]).await,
+    };
+
+    let key = debounce_key(chain_id, &payload.address);
+    if HOOK_DEBOUNCER.enqueue(&key, payload.to_owned()) {
+        let context = RequestContext::new(
+            context.request_id.to_string(),
+            context.host.to_string(),
+            context.http_client(),
+            context.cache(),
+        );
+        rocket::tokio::spawn(async move {
+            rocket::tokio::time::sleep(Duration::from_millis(window)).await;
+            let payloads = HOOK_DEBOUNCER.take(&key);
+            if let Err(error) = apply_hooks(&context, &payloads).await {
+                log::error!("Debounced hooks for {} failed: {}", key, error);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Hooks waiting for their debounce window to end
+pub fn pending_hooks() -> usize {
+    HOOK_DEBOUNCER.pending()
+}
+
+/// Applies a burst of hooks at once, grouped per Safe so that every invalidation target is only
+/// invalidated once. The batch already is the burst, so it isn't debounced.
+pub async fn update_caches_batch(context: &RequestContext, payloads: &[Payload]) -> ApiResult<()> {
+    if payloads.len() > MAX_BATCH_EVENTS {
+        return Err(ApiError::new_from_message_with_code(
+            422,
+            format!("A batch can't have more than {} events", MAX_BATCH_EVENTS),
+        ));
+    }
+    hook_events::publish(context.http_client(), payloads);
+    let mut result = Ok(());
+    for group in group_payloads(payloads) {
+        if let Err(error) = apply_hooks(context, &group).await {
+            log::warn!("Hooks for {} failed: {}", group[0].address, error);
+            if result.is_ok() {
+                result = Err(error);
+            }
         }
-        PayloadDetails::ExecutedMultisigTransaction(data) => {
-            Invalidate::new(
-                InvalidationPattern::Any(InvalidationScope::Both, String::from(&data.safe_tx_hash)),
-                cache.clone(),
-            )
-            .execute();
+    }
+    result
+}
+
+/// Payloads per chain and Safe, in the order the Safes first appear in `payloads`
+pub fn group_payloads(payloads: &[Payload]) -> Vec<Vec<Payload>> {
+    let mut indexes: HashMap<String, usize> = HashMap::new();
+    let mut groups: Vec<Vec<Payload>> = vec![];
+    for payload in payloads {
+        let key = debounce_key(
+            payload.chain_id.as_deref().unwrap_or_default(),
+            &payload.address,
+        );
+        match indexes.get(&key) {
+            Some(index) => groups[*index].push(payload.to_owned()),
+            None => {
+                indexes.insert(key, groups.len());
+                groups.push(vec![payload.to_owned()]);
+            }
         }
-        PayloadDetails::PendingMultisigTransaction(data) => {
-            Invalidate::new(
-                InvalidationPattern::Any(InvalidationScope::Both, String::from(&data.safe_tx_hash)),
-                cache.clone(),
-            )
-            .execute();
+    }
+    groups
+}
+
+/// Every invalidation target is only invalidated once. For settings changes that can be applied
+/// from the payloads, the cached safe info is written back updated instead of being refetched.
+async fn apply_hooks(context: &RequestContext, payloads: &[Payload]) -> ApiResult<()> {
+    let last_payload = match payloads.last() {
+        Some(payload) => payload,
+        None => return Ok(()),
+    };
+    // Needs to be read before invalidating, as the invalidation also removes it
+    let updated_safe_info = updated_safe_info(context, payloads).await;
+
+    let mut targets: Vec<String> = vec![];
+    for target in payloads.iter().flat_map(invalidation_targets) {
+        if !targets.contains(&target) {
+            targets.push(target);
         }
-        _ => {}
+    }
+    invalidate_targets(context.cache(), targets, &hook_source(payloads)).await;
+
+    if let Some((url, safe_info)) = updated_safe_info {
+        RequestCached::new_from_context(url, context)
+            .cache_duration(safe_info_cache_duration())
+            .overwrite(&safe_info)
+            .await;
+    }
+
+    if feature_flag_hook_prefetch() {
+        prefetch_caches(context, last_payload);
+    }
+    if feature_flag_ready_callbacks() {
+        check_confirmed_transactions(context, payloads);
+    }
+    Ok(())
+}
+
+/// Checks, in the background, whether the newly confirmed transactions are ready to be executed
+fn check_confirmed_transactions(context: &RequestContext, payloads: &[Payload]) {
+    for payload in payloads {
+        let (chain_id, data) = match (payload.chain_id.as_ref(), payload.details.as_ref()) {
+            (Some(chain_id), Some(PayloadDetails::NewConfirmation(data))) => (chain_id, data),
+            _ => continue,
+        };
+        let context = RequestContext::new(
+            context.request_id.to_string(),
+            context.host.to_string(),
+            context.http_client(),
+            context.cache(),
+        );
+        let chain_id = chain_id.to_string();
+        let safe_address = payload.address.to_string();
+        let safe_tx_hash = data.safe_tx_hash.to_string();
+        rocket::tokio::spawn(async move {
+            if let Err(error) = ready_callbacks::check_transaction(
+                &context,
+                &chain_id,
+                &safe_address,
+                &safe_tx_hash,
+            )
+            .await
+            {
+                log::debug!("Confirmations of {} not checked: {}", safe_tx_hash, error);
+            }
+        });
+    }
+}
+
+/// Refreshes the responses clients poll right after an event in the background, so the first
+/// poll is a cache hit
+fn prefetch_caches(context: &RequestContext, payload: &Payload) {
+    let chain_id = match payload.chain_id.as_ref() {
+        Some(chain_id) => chain_id.to_string(),
+        None => return,
+    };
+    let safe_address = payload.address.to_string();
+    let context = RequestContext::new(
+        context.request_id.to_string(),
+        context.host.to_string(),
+        context.http_client(),
+        context.cache(),
+    );
+    rocket::tokio::spawn(async move {
+        prefetch_responses(&context, &chain_id, &safe_address).await;
     });
+}
+
+/// Stores the safe info, balances and queue of the Safe as the routes would for the requests
+/// clients send (see [response_key](crate::cache::cache_operations::response_key)), with the
+/// same generators and defaults as the routes. Balances are stored for the plain request and for
+/// the default listing of the web client (`trusted=false&exclude_spam=true`).
+pub async fn prefetch_responses(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+) {
+    let route_context = |uri: String| {
+        RequestContext::new(
+            uri,
+            context.host.to_string(),
+            context.http_client(),
+            context.cache(),
+        )
+    };
+    let safe_context = route_context(format!("/v1/chains/{}/safes/{}", chain_id, safe_address));
+    let fiat = hook_prefetch_fiat();
+    let balances_uri = format!(
+        "/v1/chains/{}/safes/{}/balances/{}",
+        chain_id, safe_address, fiat
+    );
+    let balances_contexts = [
+        route_context(balances_uri.to_string()),
+        route_context(format!("{}?trusted=false&exclude_spam=true", balances_uri)),
+    ];
+    let queued_context = route_context(format!(
+        "/v1/chains/{}/safes/{}/transactions/queued",
+        chain_id, safe_address
+    ));
+
+    let (safe_result, balances_result, queued_result) = join!(
+        prefetch_safe_info(&safe_context, chain_id, safe_address),
+        async {
+            // One after the other, the second one is served by the upstream responses cached by
+            // the first one
+            for balances_context in balances_contexts.iter() {
+                prefetch_balances(balances_context, chain_id, safe_address, &fiat).await?;
+            }
+            Ok::<(), ApiError>(())
+        },
+        prefetch_queued(&queued_context, chain_id, safe_address)
+    );
+    for result in [safe_result, balances_result, queued_result].iter() {
+        if let Err(error) = result {
+            log::debug!("Prefetch for {} failed: {}", safe_address, error);
+        }
+    }
+}
+
+async fn prefetch_safe_info(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+) -> ApiResult<()> {
+    CacheResponse::new(context)
+        .resp_generator(|| get_safe_info_or_pending(context, chain_id, safe_address))
+        .execute()
+        .await?;
+    Ok(())
+}
+
+async fn prefetch_balances(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    fiat: &str,
+) -> ApiResult<()> {
+    // Same defaults as the balances route
+    CacheResponse::new(context)
+        .duration(balances_cache_duration())
+        .resp_generator(|| {
+            balances_with_snapshot(context, chain_id, safe_address, fiat, false, true)
+        })
+        .execute()
+        .await?;
+    Ok(())
+}
+
+async fn prefetch_queued(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+) -> ApiResult<()> {
+    CacheResponse::new(context)
+        .resp_generator(|| {
+            get_queued_transactions(context, chain_id, safe_address, &None, &None, &None, false)
+        })
+        .skip_cache_if(|page: &Page<TransactionListItem>| page.incomplete.is_some())
+        .execute()
+        .await?;
+    Ok(())
+}
+
+async fn updated_safe_info(
+    context: &RequestContext,
+    payloads: &[Payload],
+) -> Option<(String, String)> {
+    let payload = payloads.first()?;
+    let chain_id = payload.chain_id.as_ref()?;
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let url = core_uri!(info_provider, "/v1/safes/{}/", payload.address).ok()?;
+    let cached = RequestCached::new_from_context(url.to_string(), context)
+        .cached()
+        .await?;
+    // Patched as json, so that the fields the gateway doesn't model are kept
+    let mut safe_info = serde_json::from_str::<Value>(&cached).ok()?;
+    apply_settings_changes(&mut safe_info, payloads)?;
+    Some((url, safe_info.to_string()))
+}
+
+/// Applies the owner and threshold changes of `payloads` to the safe info of the transaction
+/// service. `None` if the safe info has to be refetched instead: for any other event in the
+/// batch (e.g. an executed transaction, which may change settings without an event of its own)
+/// and for changes that can't stem from a single Safe transaction.
+///
+/// Changes the cached safe info doesn't reflect yet were executed after it was cached, by a Safe
+/// transaction that also increased the nonce, so the nonce is increased with them. Changes
+/// executed by a module don't increase the nonce, the module transaction hook following them
+/// invalidates the safe info.
+pub fn apply_settings_changes(safe_info: &mut Value, payloads: &[Payload]) -> Option<()> {
+    let (mut added, mut removed, mut threshold_changes) = (0, 0, 0);
+    for payload in payloads {
+        match payload.details.as_ref()? {
+            PayloadDetails::OwnerAdded(data) => {
+                let owners = safe_info.get_mut("owners")?.as_array_mut()?;
+                if !owners.iter().any(|owner| is_address(owner, &data.owner)) {
+                    owners.push(Value::String(data.owner.to_owned()));
+                    added += 1;
+                }
+            }
+            PayloadDetails::OwnerRemoved(data) => {
+                let owners = safe_info.get_mut("owners")?.as_array_mut()?;
+                let count = owners.len();
+                owners.retain(|owner| !is_address(owner, &data.owner));
+                if owners.len() != count {
+                    removed += 1;
+                }
+            }
+            PayloadDetails::ThresholdChanged(data) => {
+                if safe_info.get("threshold")?.as_u64()? != data.threshold {
+                    safe_info["threshold"] = Value::from(data.threshold);
+                    threshold_changes += 1;
+                }
+            }
+            _ => return None,
+        }
+    }
+    // A Safe transaction adds, removes or swaps at most one owner and sets the threshold once
+    if added > 1 || removed > 1 || threshold_changes > 1 {
+        return None;
+    }
+    if added + removed + threshold_changes > 0 {
+        let nonce = safe_info.get("nonce")?.as_u64()?;
+        safe_info["nonce"] = Value::from(nonce + 1);
+    }
+    Some(())
+}
+
+fn is_address(value: &Value, address: &str) -> bool {
+    value
+        .as_str()
+        .map_or(false, |value| value.eq_ignore_ascii_case(address))
+}
+
+pub async fn invalidate_caches(cache: Arc<dyn Cache>, payload: &Payload) -> ApiResult<()> {
+    invalidate_targets(
+        cache,
+        invalidation_targets(payload),
+        &hook_source(std::slice::from_ref(payload)),
+    )
+    .await;
     Ok(())
 }
+
+/// Source of the invalidations in the invalidation log, e.g. `HOOK NEW_CONFIRMATION` or for
+/// debounced hooks `HOOK NEW_CONFIRMATION,EXECUTED_MULTISIG_TRANSACTION`
+pub fn hook_source(payloads: &[Payload]) -> String {
+    let mut hook_types: Vec<String> = vec![];
+    for payload in payloads {
+        let hook_type = payload
+            .details
+            .as_ref()
+            .and_then(|details| serde_json::to_value(details).ok())
+            .and_then(|details| details["type"].as_str().map(String::from))
+            .unwrap_or_else(|| String::from("UNKNOWN"));
+        if !hook_types.contains(&hook_type) {
+            hook_types.push(hook_type);
+        }
+    }
+    format!("HOOK {}", hook_types.join(","))
+}
+
+/// The Safe address, followed by the `safe_tx_hash` for transaction related hooks
+pub fn invalidation_targets(payload: &Payload) -> Vec<String> {
+    let mut targets = vec![payload.address.to_owned()];
+    match payload.details.as_ref() {
+        Some(PayloadDetails::NewConfirmation(data)) => targets.push(data.safe_tx_hash.to_owned()),
+        Some(PayloadDetails::ExecutedMultisigTransaction(data)) => {
+            targets.push(data.safe_tx_hash.to_owned())
+        }
+        Some(PayloadDetails::PendingMultisigTransaction(data)) => {
+            targets.push(data.safe_tx_hash.to_owned())
+        }
+        _ => {}
+    }
+    targets
+}
+
+async fn invalidate_targets(cache: Arc<dyn Cache>, targets: Vec<String>, source: &str) {
+    for target in targets {
+        Invalidate::new(
+            InvalidationPattern::Any(InvalidationScope::Both, target),
+            cache.clone(),
+        )
+        .source(source)
+        .execute()
+        .await;
+    }
+}
@@ -1,3 +1,4 @@
+pub mod debounce;
 #[doc(hidden)]
 pub mod handlers;
 pub mod routes;
@@ -1,28 +1,65 @@
 use crate::cache::cache_operations::{Invalidate, InvalidationPattern};
 use crate::common::models::backend::hooks::Payload;
-use crate::config::webhook_token;
-use crate::routes::hooks::handlers::invalidate_caches;
+use crate::monitoring::audit::{self, AuditOperation, Caller};
+use crate::routes::hooks::handlers::{update_caches, update_caches_batch};
+use crate::utils::auth::{self, Scope};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
 use rocket::serde::json::Json;
 
 #[post("/v1/hook/update/<token>", format = "json", data = "<update>")]
-pub fn update(context: RequestContext, token: String, update: Json<Payload>) -> ApiResult<()> {
-    if token != webhook_token() {
-        bail!("Invalid token");
-    }
-    invalidate_caches(context.cache(), &update)
+pub async fn update(
+    context: RequestContext,
+    caller: Caller,
+    token: String,
+    update: Json<Payload>,
+) -> ApiResult<()> {
+    auth::authorize(&token, Scope::Hooks)?;
+    let result = update_caches(&context, &update).await;
+    audit::record(
+        AuditOperation::HookUpdate,
+        &update.address,
+        &caller,
+        audit::payload_hash(&update.0),
+        &result,
+    );
+    result
+}
+
+/// Events of several Safes at once, e.g. from a queue consumer catching up
+#[post("/v1/hooks/events/batch/<token>", format = "json", data = "<events>")]
+pub async fn batch(
+    context: RequestContext,
+    caller: Caller,
+    token: String,
+    events: Json<Vec<Payload>>,
+) -> ApiResult<()> {
+    auth::authorize(&token, Scope::Hooks)?;
+    let result = update_caches_batch(&context, &events).await;
+    audit::record(
+        AuditOperation::HookBatch,
+        "*",
+        &caller,
+        audit::payload_hash(&events.0),
+        &result,
+    );
+    result
 }
 
 #[post("/v1/flush/<token>", format = "json", data = "<invalidation_pattern>")]
-pub fn flush(
+pub async fn flush(
     context: RequestContext,
+    caller: Caller,
     token: String,
     invalidation_pattern: Json<InvalidationPattern>,
 ) -> ApiResult<()> {
-    if token != webhook_token() {
-        bail!("Invalid token");
-    }
-    Invalidate::new(invalidation_pattern.0, context.cache()).execute();
-    Ok(())
+    auth::authorize(&token, Scope::CacheAdmin)?;
+    let payload_hash = audit::payload_hash(&invalidation_pattern.0);
+    Invalidate::new(invalidation_pattern.0, context.cache())
+        .source("FLUSH")
+        .execute()
+        .await;
+    let result = Ok(());
+    audit::record(AuditOperation::Flush, "*", &caller, payload_hash, &result);
+    result
 }
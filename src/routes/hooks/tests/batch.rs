@@ -0,0 +1,101 @@
+use crate::cache::MockCache;
+use crate::common::models::backend::hooks::{NewConfirmation, Payload, PayloadDetails};
+use crate::routes::hooks::handlers::{group_payloads, update_caches_batch, MAX_BATCH_EVENTS};
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::MockHttpClient;
+use mockall::predicate::eq;
+use std::sync::Arc;
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const OTHER_SAFE: &str = "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0";
+const SAFE_TX_HASH: &str = "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621";
+
+fn confirmation(address: &str, chain_id: Option<&str>) -> Payload {
+    Payload {
+        address: address.to_string(),
+        chain_id: chain_id.map(str::to_string),
+        details: Some(PayloadDetails::NewConfirmation(NewConfirmation {
+            owner: OTHER_SAFE.to_string(),
+            safe_tx_hash: SAFE_TX_HASH.to_string(),
+        })),
+    }
+}
+
+fn context(mock_cache: MockCache) -> RequestContext {
+    RequestContext::new(
+        String::from("/v1/hooks/events/batch"),
+        String::from("host"),
+        Arc::new(MockHttpClient::new()),
+        Arc::new(mock_cache),
+    )
+}
+
+#[test]
+fn group_payloads_per_chain_and_safe() {
+    let payloads = vec![
+        confirmation(SAFE, Some("4")),
+        confirmation(OTHER_SAFE, Some("4")),
+        confirmation(&SAFE.to_lowercase(), Some("4")),
+        confirmation(SAFE, Some("1")),
+    ];
+
+    let groups = group_payloads(&payloads);
+
+    let addresses: Vec<Vec<String>> = groups
+        .iter()
+        .map(|group| group.iter().map(|it| it.address.to_string()).collect())
+        .collect();
+    assert_eq!(
+        vec![
+            vec![SAFE.to_string(), SAFE.to_lowercase()],
+            vec![OTHER_SAFE.to_string()],
+            vec![SAFE.to_string()],
+        ],
+        addresses
+    );
+}
+
+#[test]
+fn group_payloads_empty() {
+    assert!(group_payloads(&[]).is_empty());
+}
+
+#[rocket::async_test]
+async fn batch_invalidates_every_target_once() {
+    let payloads = vec![
+        confirmation(SAFE, None),
+        confirmation(SAFE, None),
+        Payload {
+            address: OTHER_SAFE.to_string(),
+            chain_id: None,
+            details: None,
+        },
+    ];
+    let mut mock_cache = MockCache::new();
+    for target in [SAFE, SAFE_TX_HASH, OTHER_SAFE].iter() {
+        mock_cache
+            .expect_invalidate_pattern()
+            .with(eq(format!("c_re*{}*", target)))
+            .times(1)
+            .return_const(0usize);
+    }
+    mock_cache
+        .expect_append_to_stream()
+        .times(3)
+        .return_const(());
+
+    update_caches_batch(&context(mock_cache), &payloads)
+        .await
+        .unwrap();
+}
+
+#[rocket::async_test]
+async fn batch_over_limit_is_rejected() {
+    let payloads = vec![confirmation(SAFE, None); MAX_BATCH_EVENTS + 1];
+
+    let error = update_caches_batch(&context(MockCache::new()), &payloads)
+        .await
+        .unwrap_err();
+
+    assert_eq!(422, error.status);
+}
@@ -0,0 +1,63 @@
+use crate::common::models::backend::hooks::{NewConfirmation, Payload, PayloadDetails};
+use crate::routes::hooks::debounce::{debounce_key, HookDebouncer};
+use crate::routes::hooks::handlers::invalidation_targets;
+
+fn payload(safe_tx_hash: &str) -> Payload {
+    Payload {
+        address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: Some("4".to_string()),
+        details: Some(PayloadDetails::NewConfirmation(NewConfirmation {
+            owner: "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
+            safe_tx_hash: safe_tx_hash.to_string(),
+        })),
+    }
+}
+
+#[test]
+fn enqueue_opens_window_once_per_key() {
+    let debouncer = HookDebouncer::default();
+    let key = debounce_key("4", "0x1230B3d59858296A31053C1b8562Ecf89A2f888b");
+
+    assert!(debouncer.enqueue(&key, payload("0x1")));
+    assert!(!debouncer.enqueue(&key, payload("0x2")));
+    assert!(debouncer.enqueue(
+        &debounce_key("1", "0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
+        payload("0x3")
+    ));
+
+    let safe_tx_hashes: Vec<String> = debouncer
+        .take(&key)
+        .iter()
+        .map(|payload| invalidation_targets(payload).pop().unwrap())
+        .collect();
+    assert_eq!(safe_tx_hashes, vec!["0x1", "0x2"]);
+}
+
+#[test]
+fn take_closes_window() {
+    let debouncer = HookDebouncer::default();
+    let key = debounce_key("4", "0x1230B3d59858296A31053C1b8562Ecf89A2f888b");
+
+    assert!(debouncer.take(&key).is_empty());
+    debouncer.enqueue(&key, payload("0x1"));
+    assert_eq!(debouncer.take(&key).len(), 1);
+
+    assert!(debouncer.take(&key).is_empty());
+    assert!(debouncer.enqueue(&key, payload("0x2")));
+}
+
+#[test]
+fn debounce_key_ignores_address_case() {
+    assert_eq!(
+        debounce_key("4", "0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
+        debounce_key("4", "0x1230b3d59858296a31053c1b8562ecf89a2f888b")
+    );
+}
+
+#[test]
+fn invalidation_targets_for_confirmation() {
+    assert_eq!(
+        invalidation_targets(&payload("0x1")),
+        vec!["0x1230B3d59858296A31053C1b8562Ecf89A2f888b", "0x1"]
+    );
+}
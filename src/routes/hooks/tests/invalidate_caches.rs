@@ -3,15 +3,16 @@ use crate::common::models::backend::hooks::{
     ExecutedMultisigTransaction, NewConfirmation, Payload, PayloadDetails,
     PendingMultisigTransaction,
 };
-use crate::routes::hooks::handlers::invalidate_caches;
+use crate::routes::hooks::handlers::{hook_source, invalidate_caches};
 use mockall::predicate::*;
 use mockall::Sequence;
 use std::sync::Arc;
 
-#[test]
-fn invalidate_with_empty_payload() {
+#[rocket::async_test]
+async fn invalidate_with_empty_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: None,
     };
 
@@ -20,20 +21,30 @@ fn invalidate_with_empty_payload() {
     mock_cache.expect_fetch().times(0);
     mock_cache.expect_create().times(0);
     mock_cache.expect_invalidate().times(0);
+    mock_cache
+        .expect_append_to_stream()
+        .withf(|stream, entry, _| {
+            stream == "invalidations" && entry.contains("\"source\":\"HOOK UNKNOWN\"")
+        })
+        .times(1)
+        .return_const(());
 
     mock_cache
         .expect_invalidate_pattern()
         .times(1)
-        .return_const(())
+        .return_const(0usize)
         .with(eq("c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*"));
 
-    invalidate_caches(Arc::new(mock_cache), &payload).unwrap();
+    invalidate_caches(Arc::new(mock_cache), &payload)
+        .await
+        .unwrap();
 }
 
-#[test]
-fn invalidate_new_confirmation_payload() {
+#[rocket::async_test]
+async fn invalidate_new_confirmation_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: Some(PayloadDetails::NewConfirmation(NewConfirmation {
             owner: "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
             safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
@@ -47,28 +58,38 @@ fn invalidate_new_confirmation_payload() {
     mock_cache.expect_fetch().times(0);
     mock_cache.expect_create().times(0);
     mock_cache.expect_invalidate().times(0);
+    mock_cache
+        .expect_append_to_stream()
+        .withf(|stream, entry, _| {
+            stream == "invalidations" && entry.contains("\"source\":\"HOOK NEW_CONFIRMATION\"")
+        })
+        .times(2)
+        .return_const(());
     mock_cache
         .expect_invalidate_pattern()
         .times(1)
-        .return_const(())
+        .return_const(0usize)
         .with(eq("c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*"))
         .in_sequence(&mut sequence);
     mock_cache
         .expect_invalidate_pattern()
         .times(1)
-        .return_const(())
+        .return_const(0usize)
         .with(eq(
             "c_re*0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621*",
         ))
         .in_sequence(&mut sequence);
 
-    invalidate_caches(Arc::new(mock_cache), &payload).unwrap();
+    invalidate_caches(Arc::new(mock_cache), &payload)
+        .await
+        .unwrap();
 }
 
-#[test]
-fn invalidate_executed_multisig_transaction_payload() {
+#[rocket::async_test]
+async fn invalidate_executed_multisig_transaction_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: Some(PayloadDetails::ExecutedMultisigTransaction(
             ExecutedMultisigTransaction {
                 safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
@@ -85,28 +106,39 @@ fn invalidate_executed_multisig_transaction_payload() {
     mock_cache.expect_fetch().times(0);
     mock_cache.expect_create().times(0);
     mock_cache.expect_invalidate().times(0);
+    mock_cache
+        .expect_append_to_stream()
+        .withf(|stream, entry, _| {
+            stream == "invalidations"
+                && entry.contains("\"source\":\"HOOK EXECUTED_MULTISIG_TRANSACTION\"")
+        })
+        .times(2)
+        .return_const(());
     mock_cache
         .expect_invalidate_pattern()
         .times(1)
-        .return_const(())
+        .return_const(0usize)
         .with(eq("c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*"))
         .in_sequence(&mut sequence);
     mock_cache
         .expect_invalidate_pattern()
         .times(1)
-        .return_const(())
+        .return_const(0usize)
         .with(eq(
             "c_re*0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621*",
         ))
         .in_sequence(&mut sequence);
 
-    invalidate_caches(Arc::new(mock_cache), &payload).unwrap();
+    invalidate_caches(Arc::new(mock_cache), &payload)
+        .await
+        .unwrap();
 }
 
-#[test]
-fn invalidate_pending_multisig_transaction_payload() {
+#[rocket::async_test]
+async fn invalidate_pending_multisig_transaction_payload() {
     let payload = Payload {
         address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: None,
         details: Some(PayloadDetails::PendingMultisigTransaction(
             PendingMultisigTransaction {
                 safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
@@ -120,20 +152,51 @@ fn invalidate_pending_multisig_transaction_payload() {
     mock_cache.expect_fetch().times(0);
     mock_cache.expect_create().times(0);
     mock_cache.expect_invalidate().times(0);
+    mock_cache
+        .expect_append_to_stream()
+        .withf(|stream, entry, _| {
+            stream == "invalidations"
+                && entry.contains("\"source\":\"HOOK PENDING_MULTISIG_TRANSACTION\"")
+        })
+        .times(2)
+        .return_const(());
     mock_cache
         .expect_invalidate_pattern()
         .times(1)
-        .return_const(())
+        .return_const(0usize)
         .with(eq("c_re*0x1230B3d59858296A31053C1b8562Ecf89A2f888b*"))
         .in_sequence(&mut sequence);
     mock_cache
         .expect_invalidate_pattern()
         .times(1)
-        .return_const(())
+        .return_const(0usize)
         .with(eq(
             "c_re*0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621*",
         ))
         .in_sequence(&mut sequence);
 
-    invalidate_caches(Arc::new(mock_cache), &payload).unwrap();
+    invalidate_caches(Arc::new(mock_cache), &payload)
+        .await
+        .unwrap();
+}
+
+#[test]
+fn hook_source_lists_hook_types_once() {
+    let payload = |details: Option<PayloadDetails>| Payload {
+        address: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
+        chain_id: Some(String::from("4")),
+        details,
+    };
+    let pending = || {
+        Some(PayloadDetails::PendingMultisigTransaction(
+            PendingMultisigTransaction {
+                safe_tx_hash: "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
+                    .to_string(),
+            },
+        ))
+    };
+
+    let actual = hook_source(&[payload(pending()), payload(None), payload(pending())]);
+
+    assert_eq!(actual, "HOOK PENDING_MULTISIG_TRANSACTION,UNKNOWN");
 }
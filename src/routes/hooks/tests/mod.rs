@@ -1,2 +1,6 @@
+mod batch;
+mod debounce;
 mod invalidate_caches;
+mod prefetch;
+mod safe_info_updates;
 mod safes;
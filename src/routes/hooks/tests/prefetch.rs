@@ -0,0 +1,113 @@
+use crate::cache::{Cache, MockCache};
+use crate::gateway::GatewayBuilder;
+use crate::routes::hooks::handlers::prefetch_responses;
+use crate::routes::safes::routes::get_safe_info;
+use crate::tests::json::{CHAIN_INFO_RINKEBY, SAFE_WITH_MODULES};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::{HttpClient, MockHttpClient};
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+/// Cache backed by a map, seeded with the chain info and the safe info of `SAFE`
+fn in_memory_cache() -> MockCache {
+    let chain_key = format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"));
+    let entries = Arc::new(Mutex::new(HashMap::new()));
+    entries
+        .lock()
+        .unwrap()
+        .insert(chain_key, String::from(CHAIN_INFO_RINKEBY));
+    let mut mock_cache = MockCache::new();
+    let fetched = entries.clone();
+    mock_cache.expect_fetch().returning(move |key| {
+        let fetched = fetched.lock().unwrap();
+        match fetched.get(key) {
+            Some(value) => Some(value.to_string()),
+            None if key.starts_with("c_reqs_")
+                && key.ends_with(&format!("/v1/safes/{}/", SAFE)) =>
+            {
+                Some(format!("200;{}", SAFE_WITH_MODULES))
+            }
+            None => None,
+        }
+    });
+    let contained = entries.clone();
+    mock_cache
+        .expect_has_key()
+        .returning(move |key| contained.lock().unwrap().contains_key(key));
+    let created = entries;
+    mock_cache.expect_create().returning(move |key, value, _| {
+        created
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+    });
+    mock_cache.expect_insert_in_hash().return_const(());
+    mock_cache.expect_get_from_hash().returning(|_, _| None);
+    mock_cache.expect_increment_in_hash().return_const(1usize);
+    mock_cache.expect_fetch_hash().returning(|_| HashMap::new());
+    mock_cache.expect_ttl().returning(|_| None);
+    mock_cache.expect_expire_entity().return_const(());
+    mock_cache.expect_keys().returning(|_| vec![]);
+    mock_cache.expect_invalidate_pattern().return_const(0usize);
+    mock_cache.expect_invalidate().return_const(());
+    mock_cache.expect_info().returning(|| None);
+    mock_cache.expect_append_to_stream().return_const(());
+    mock_cache.expect_read_stream().returning(|_, _, _| vec![]);
+    mock_cache
+}
+
+#[rocket::async_test]
+async fn safe_info_route_hits_cache_after_prefetch() {
+    let upstream_calls = Arc::new(AtomicUsize::new(0));
+    let counted_calls = upstream_calls.clone();
+    let mut mock_http_client = MockHttpClient::new();
+    // Everything but the seeded safe info is missing upstream
+    mock_http_client.expect_get().returning(move |_| {
+        counted_calls.fetch_add(1, Ordering::SeqCst);
+        Err(ApiError::new_from_message_with_code(
+            404,
+            String::from("Not found"),
+        ))
+    });
+    let cache: Arc<dyn Cache> = Arc::new(in_memory_cache());
+    let http_client: Arc<dyn HttpClient> = Arc::new(mock_http_client);
+    let context = RequestContext::new(
+        String::from("/v1/hooks/events"),
+        String::from("http://test.gnosis.io"),
+        http_client.clone(),
+        cache.clone(),
+    );
+
+    prefetch_responses(&context, &String::from("4"), &String::from(SAFE)).await;
+
+    let prefetched = cache
+        .fetch(&format!("c_resp_/v1/chains/4/safes/{}", SAFE))
+        .await
+        .expect("safe info prefetched");
+    let calls_after_prefetch = upstream_calls.load(Ordering::SeqCst);
+    let client = Client::tracked(
+        GatewayBuilder::new(cache, http_client)
+            .route_groups(&[])
+            .core_routes(false)
+            .fairings(false)
+            .routes(routes![get_safe_info])
+            .build(),
+    )
+    .await
+    .expect("valid rocket instance");
+    let response = {
+        let mut response = client.get(format!("/v1/chains/4/safes/{}", SAFE));
+        response.add_header(Header::new("Host", "test.gnosis.io"));
+        response.dispatch().await
+    };
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), prefetched);
+    assert_eq!(upstream_calls.load(Ordering::SeqCst), calls_after_prefetch);
+}
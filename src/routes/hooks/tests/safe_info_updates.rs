@@ -0,0 +1,204 @@
+use crate::cache::MockCache;
+use crate::common::models::backend::hooks::{
+    ExecutedMultisigTransaction, OwnerAdded, OwnerRemoved, Payload, PayloadDetails,
+    ThresholdChanged,
+};
+use crate::routes::hooks::handlers::{apply_settings_changes, update_caches_batch};
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::MockHttpClient;
+use mockall::predicate::eq;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const OWNER: &str = "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23";
+const NEW_OWNER: &str = "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd";
+
+fn safe_info() -> Value {
+    json!({
+        "address": SAFE,
+        "nonce": 7,
+        "threshold": 1,
+        "owners": [OWNER],
+        "masterCopy": "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552",
+        "modules": [],
+        "fallbackHandler": "0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4",
+        "guard": "0x0000000000000000000000000000000000000000",
+        "version": "1.3.0",
+        // Not part of the SafeInfo model of the gateway
+        "ownersHistory": []
+    })
+}
+
+fn hook(details: PayloadDetails) -> Payload {
+    Payload {
+        address: SAFE.to_string(),
+        chain_id: Some(String::from("4")),
+        details: Some(details),
+    }
+}
+
+fn owner_added(owner: &str) -> Payload {
+    hook(PayloadDetails::OwnerAdded(OwnerAdded {
+        owner: owner.to_string(),
+    }))
+}
+
+fn owner_removed(owner: &str) -> Payload {
+    hook(PayloadDetails::OwnerRemoved(OwnerRemoved {
+        owner: owner.to_string(),
+    }))
+}
+
+fn threshold_changed(threshold: u64) -> Payload {
+    hook(PayloadDetails::ThresholdChanged(ThresholdChanged {
+        threshold,
+    }))
+}
+
+fn is_safe_info_key(key: &str) -> bool {
+    key.starts_with("c_reqs_") && key.ends_with(&format!("/api/v1/safes/{}/", SAFE))
+}
+
+/// Safe info cached by the hooks for `cached`, after applying `payloads`
+async fn cached_after_hooks(cached: Value, payloads: &[Payload]) -> Option<Value> {
+    let chain_key = format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"));
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key))
+        .return_const(Some(String::from(crate::tests::json::CHAIN_INFO_RINKEBY)));
+    mock_cache
+        .expect_fetch()
+        .withf(|key| is_safe_info_key(key))
+        .times(1)
+        .return_const(Some(format!("200;{}", cached)));
+    mock_cache
+        .expect_invalidate_pattern()
+        .with(eq(format!("c_re*{}*", SAFE)))
+        .times(1)
+        .return_const(0usize);
+    mock_cache.expect_append_to_stream().return_const(());
+    let written = Arc::new(Mutex::new(None));
+    let written_value = written.clone();
+    mock_cache
+        .expect_create()
+        .withf(|key, _, _| is_safe_info_key(key))
+        .returning(move |_, value, _| {
+            *written_value.lock().unwrap() =
+                Some(serde_json::from_str(value.trim_start_matches("200;")).unwrap());
+        });
+    let context = RequestContext::new(
+        String::from("/v1/hooks/events/batch"),
+        String::from("host"),
+        Arc::new(MockHttpClient::new()),
+        Arc::new(mock_cache),
+    );
+
+    update_caches_batch(&context, payloads).await.unwrap();
+
+    let mut written = written.lock().unwrap();
+    written.take()
+}
+
+#[rocket::async_test]
+async fn owner_added_hook_caches_safe_info_with_owner() {
+    let actual = cached_after_hooks(safe_info(), &[owner_added(NEW_OWNER)]).await;
+
+    let mut expected = safe_info();
+    expected["owners"] = json!([OWNER, NEW_OWNER]);
+    expected["nonce"] = json!(8);
+    assert_eq!(actual, Some(expected));
+}
+
+#[rocket::async_test]
+async fn owner_removed_hook_caches_safe_info_without_owner() {
+    let mut cached = safe_info();
+    cached["owners"] = json!([OWNER, NEW_OWNER]);
+
+    let actual = cached_after_hooks(cached, &[owner_removed(&NEW_OWNER.to_lowercase())]).await;
+
+    let mut expected = safe_info();
+    expected["nonce"] = json!(8);
+    assert_eq!(actual, Some(expected));
+}
+
+#[rocket::async_test]
+async fn threshold_changed_hook_caches_safe_info_with_threshold() {
+    let mut cached = safe_info();
+    cached["owners"] = json!([OWNER, NEW_OWNER]);
+
+    let actual = cached_after_hooks(cached, &[threshold_changed(2)]).await;
+
+    let mut expected = safe_info();
+    expected["owners"] = json!([OWNER, NEW_OWNER]);
+    expected["threshold"] = json!(2);
+    expected["nonce"] = json!(8);
+    assert_eq!(actual, Some(expected));
+}
+
+#[rocket::async_test]
+async fn owner_added_with_threshold_is_a_single_transaction() {
+    let actual =
+        cached_after_hooks(safe_info(), &[owner_added(NEW_OWNER), threshold_changed(2)]).await;
+
+    let mut expected = safe_info();
+    expected["owners"] = json!([OWNER, NEW_OWNER]);
+    expected["threshold"] = json!(2);
+    expected["nonce"] = json!(8);
+    assert_eq!(actual, Some(expected));
+}
+
+#[test]
+fn executed_transaction_hook_refetches_safe_info() {
+    let executed = hook(PayloadDetails::ExecutedMultisigTransaction(
+        ExecutedMultisigTransaction {
+            safe_tx_hash: String::from(
+                "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621",
+            ),
+            tx_hash: String::from(
+                "0x0ebb2c317f55c96469e0ed2014f5833dc02a70b42f0ac52f4630938900caa698",
+            ),
+        },
+    ));
+    let mut safe_info = safe_info();
+
+    assert_eq!(
+        apply_settings_changes(&mut safe_info, &[owner_added(NEW_OWNER), executed]),
+        None
+    );
+}
+
+#[test]
+fn settings_changes_reflected_already_keep_nonce() {
+    let mut safe_info = safe_info();
+    safe_info["owners"] = json!([OWNER, NEW_OWNER]);
+    let expected = safe_info.clone();
+
+    apply_settings_changes(
+        &mut safe_info,
+        &[owner_added(NEW_OWNER), threshold_changed(1)],
+    )
+    .unwrap();
+
+    assert_eq!(safe_info, expected);
+}
+
+#[test]
+fn settings_changes_of_several_transactions_refetch_safe_info() {
+    let mut safe_info = safe_info();
+
+    let actual = apply_settings_changes(
+        &mut safe_info,
+        &[
+            owner_added(NEW_OWNER),
+            owner_added("0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0"),
+        ],
+    );
+
+    assert_eq!(actual, None);
+}
@@ -2,8 +2,13 @@ use crate::common::models::addresses::AddressEx;
 use crate::common::models::backend::chains::ChainInfo;
 use crate::common::models::backend::safes::MasterCopy;
 use crate::providers::info::*;
-use crate::routes::safes::converters::calculate_version_state;
-use crate::routes::safes::models::{Implementation, ImplementationVersionState, SafeInfoEx};
+use crate::routes::safes::converters::{
+    calculate_version_state, is_l2_implementation, safe_info_warnings,
+};
+use crate::routes::safes::models::{
+    Implementation, ImplementationInfo, ImplementationVersionState, SafeInfoEx, SafeInfoWarning,
+};
+use crate::utils::safe_version::SafeCapability;
 use rocket::serde::json::json;
 
 #[rocket::async_test]
@@ -36,6 +41,7 @@ async fn to_safe_info_ex_no_address_info() {
             value: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         },
         chain_id: "4".to_string(),
         nonce: 180,
@@ -45,58 +51,74 @@ async fn to_safe_info_ex_no_address_info() {
                 value: "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x37e9F140A9Df5DCBc783C6c220660a4E15CBFe72".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xA3DAa0d9Ae02dAA17a664c232aDa1B739eF5ae8D".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
         ],
-        implementation: AddressEx {
-            value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
-            name: None,
-            logo_uri: None,
+        implementation: ImplementationInfo {
+            address: AddressEx {
+                value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
+                name: None,
+                logo_uri: None,
+                risk_flags: vec![],
+            },
+            version: Some("1.1.1".to_string()),
+            is_l2: false,
         },
         modules: Some(vec![
             AddressEx {
                 value: "0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x10A7EC8D10CD175dC33781fB9Cf3394220Fac78c".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xF5dC3718EEbC5b003F1672A499F2ACBE77Ba790d".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
         ]),
-        fallback_handler: Some(AddressEx {
+        fallback_handler: Some(Some(AddressEx {
             value: "0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44".to_string(),
             name: None,
             logo_uri: None,
-        }),
+            risk_flags: vec![],
+        })),
         guard: None,
         version: Some("1.1.1".to_string()),
         implementation_version_state: ImplementationVersionState::UpToDate,
+        capabilities: vec![SafeCapability::Modules, SafeCapability::FallbackHandler],
+        warnings: vec![SafeInfoWarning::UnknownFallbackHandler],
     };
 
     let actual = safe_info
@@ -136,6 +158,7 @@ async fn to_safe_info_ex_no_address_info_up_to_date() {
             value: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         },
         chain_id: "4".to_string(),
         nonce: 180,
@@ -145,58 +168,74 @@ async fn to_safe_info_ex_no_address_info_up_to_date() {
                 value: "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x37e9F140A9Df5DCBc783C6c220660a4E15CBFe72".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xA3DAa0d9Ae02dAA17a664c232aDa1B739eF5ae8D".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
         ],
-        implementation: AddressEx {
-            value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
-            name: None,
-            logo_uri: None,
+        implementation: ImplementationInfo {
+            address: AddressEx {
+                value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
+                name: None,
+                logo_uri: None,
+                risk_flags: vec![],
+            },
+            version: Some("1.1.1".to_string()),
+            is_l2: false,
         },
         modules: Some(vec![
             AddressEx {
                 value: "0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x10A7EC8D10CD175dC33781fB9Cf3394220Fac78c".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xF5dC3718EEbC5b003F1672A499F2ACBE77Ba790d".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
         ]),
-        fallback_handler: Some(AddressEx {
+        fallback_handler: Some(Some(AddressEx {
             value: "0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44".to_string(),
             name: None,
             logo_uri: None,
-        }),
+            risk_flags: vec![],
+        })),
         guard: None,
         version: Some("1.1.1".to_string()),
         implementation_version_state: ImplementationVersionState::UpToDate,
+        capabilities: vec![SafeCapability::Modules, SafeCapability::FallbackHandler],
+        warnings: vec![SafeInfoWarning::UnknownFallbackHandler],
     };
 
     let actual = safe_info
@@ -219,6 +258,7 @@ async fn to_safe_info_ex_address_info() {
                 value: address.to_string(),
                 name: Some(format!("name_{}", &address)),
                 logo_uri: Some(format!("logo_uri_{}", &address)),
+                risk_flags: vec![],
             })
         });
     mock_info_provider
@@ -242,6 +282,7 @@ async fn to_safe_info_ex_address_info() {
             value: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         },
         chain_id: "4".to_string(),
         nonce: 180,
@@ -251,58 +292,74 @@ async fn to_safe_info_ex_address_info() {
                 value: "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x37e9F140A9Df5DCBc783C6c220660a4E15CBFe72".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xA3DAa0d9Ae02dAA17a664c232aDa1B739eF5ae8D".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x65F8236309e5A99Ff0d129d04E486EBCE20DC7B0".to_string(),
                 name: None,
                 logo_uri: None,
+                risk_flags: vec![],
             },
         ],
-        implementation: AddressEx {
-            value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
-            name: Some("name_0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string()),
-            logo_uri: Some("logo_uri_0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string()),
+        implementation: ImplementationInfo {
+            address: AddressEx {
+                value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
+                name: Some("name_0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string()),
+                logo_uri: Some("logo_uri_0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string()),
+                risk_flags: vec![],
+            },
+            version: Some("1.1.1".to_string()),
+            is_l2: false,
         },
         modules: Some(vec![
             AddressEx {
                 value: "0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string(),
                 name: Some("name_0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string()),
                 logo_uri: Some("logo_uri_0x25F73b24B866963B0e560fFF9bbA7908be0263E8".to_string()),
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0x10A7EC8D10CD175dC33781fB9Cf3394220Fac78c".to_string(),
                 name: Some("name_0x10A7EC8D10CD175dC33781fB9Cf3394220Fac78c".to_string()),
                 logo_uri: Some("logo_uri_0x10A7EC8D10CD175dC33781fB9Cf3394220Fac78c".to_string()),
+                risk_flags: vec![],
             },
             AddressEx {
                 value: "0xF5dC3718EEbC5b003F1672A499F2ACBE77Ba790d".to_string(),
                 name: Some("name_0xF5dC3718EEbC5b003F1672A499F2ACBE77Ba790d".to_string()),
                 logo_uri: Some("logo_uri_0xF5dC3718EEbC5b003F1672A499F2ACBE77Ba790d".to_string()),
+                risk_flags: vec![],
             },
         ]),
-        fallback_handler: Some(AddressEx {
+        fallback_handler: Some(Some(AddressEx {
             value: "0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44".to_string(),
             name: Some("name_0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44".to_string()),
             logo_uri: Some("logo_uri_0xd5D82B6aDDc9027B22dCA772Aa68D5d74cdBdF44".to_string()),
-        }),
+            risk_flags: vec![],
+        })),
         guard: None,
         version: Some("1.1.1".to_string()),
         implementation_version_state: ImplementationVersionState::UpToDate,
+        capabilities: vec![SafeCapability::Modules, SafeCapability::FallbackHandler],
+        warnings: vec![],
     };
 
     let actual = safe_info
@@ -353,6 +410,7 @@ async fn to_safe_info_ex_nullable_fields_are_all_null() {
             value: "0x1230B3d59858296A31053C1b8562Ecf89A2f888b".to_string(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         },
         chain_id: "4".to_string(),
         nonce: 180,
@@ -361,17 +419,25 @@ async fn to_safe_info_ex_nullable_fields_are_all_null() {
             value: "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         }],
-        implementation: AddressEx {
-            value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
-            name: None,
-            logo_uri: None,
+        implementation: ImplementationInfo {
+            address: AddressEx {
+                value: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
+                name: None,
+                logo_uri: None,
+                risk_flags: vec![],
+            },
+            version: None,
+            is_l2: false,
         },
         modules: None,
-        fallback_handler: None,
-        guard: None,
+        fallback_handler: Some(None),
+        guard: Some(None),
         version: None,
         implementation_version_state: ImplementationVersionState::Unknown,
+        capabilities: vec![],
+        warnings: vec![],
     };
 
     let actual = safe_info
@@ -394,6 +460,7 @@ async fn to_safe_info_guard_and_fallback_handler_defined() {
                 value: address.to_string(),
                 name: Some(format!("name_{}", &address)),
                 logo_uri: Some(format!("logo_uri_{}", &address)),
+                risk_flags: vec![],
             })
         });
     mock_info_provider
@@ -417,6 +484,7 @@ async fn to_safe_info_guard_and_fallback_handler_defined() {
             value: "0x4cb09344de5bCCD45F045c5Defa0E0452869FF0f".to_string(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         },
         chain_id: "4".to_string(),
         nonce: 7,
@@ -425,25 +493,35 @@ async fn to_safe_info_guard_and_fallback_handler_defined() {
             value: "0x5aC255889882aCd3da2aA939679E3f3d4cea221e".to_string(),
             name: None,
             logo_uri: None,
+            risk_flags: vec![],
         }],
-        implementation: AddressEx {
-            value: "0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string(),
-            name: Some("name_0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string()),
-            logo_uri: Some("logo_uri_0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string()),
+        implementation: ImplementationInfo {
+            address: AddressEx {
+                value: "0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string(),
+                name: Some("name_0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string()),
+                logo_uri: Some("logo_uri_0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string()),
+                risk_flags: vec![],
+            },
+            version: Some("1.3.0".to_string()),
+            is_l2: false,
         },
         modules: None,
-        fallback_handler: Some(AddressEx {
+        fallback_handler: Some(Some(AddressEx {
             value: "0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4".to_string(),
             name: Some("name_0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4".to_string()),
             logo_uri: Some("logo_uri_0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4".to_string()),
-        }),
-        guard: Some(AddressEx {
+            risk_flags: vec![],
+        })),
+        guard: Some(Some(AddressEx {
             value: "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D".to_string(),
             name: Some("name_0x40A2aCCbd92BCA938b02010E17A5b8929b49130D".to_string()),
             logo_uri: Some("logo_uri_0x40A2aCCbd92BCA938b02010E17A5b8929b49130D".to_string()),
-        }),
+            risk_flags: vec![],
+        })),
         version: Some("1.3.0".to_string()),
         implementation_version_state: ImplementationVersionState::UpToDate,
+        capabilities: SafeCapability::ALL.to_vec(),
+        warnings: vec![],
     };
 
     let actual = safe_info
@@ -453,6 +531,34 @@ async fn to_safe_info_guard_and_fallback_handler_defined() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn safe_info_warnings_for_unknown_guard() {
+    let guard = Some(Some(AddressEx::address_only(
+        "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D",
+    )));
+    let fallback_handler = Some(Some(AddressEx {
+        value: "0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4".to_string(),
+        name: Some("CompatibilityFallbackHandler".to_string()),
+        logo_uri: None,
+        risk_flags: vec![],
+    }));
+
+    assert_eq!(
+        safe_info_warnings(&fallback_handler, &guard),
+        vec![SafeInfoWarning::UnknownGuard]
+    );
+    assert_eq!(
+        json!(safe_info_warnings(&fallback_handler, &guard)),
+        json!(["UNKNOWN_GUARD"])
+    );
+}
+
+#[test]
+fn safe_info_warnings_without_guard_and_fallback_handler() {
+    assert!(safe_info_warnings(&Some(None), &Some(None)).is_empty());
+    assert!(safe_info_warnings(&None, &None).is_empty());
+}
+
 #[test]
 fn calculate_version_state_up_to_date() {
     let supported_master_copies = vec![
@@ -518,6 +624,65 @@ fn calculate_version_state_outdated() {
     assert_eq!(actual, ImplementationVersionState::Outdated);
 }
 
+#[test]
+fn calculate_version_state_l2_variants() {
+    let supported_master_copies = vec![MasterCopy {
+        address: "0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string(),
+        version: "1.3.0+L2".to_string(),
+        deployer: "".to_string(),
+        deployed_block_number: 0,
+        last_indexed_block_number: 0,
+    }];
+
+    let actual_l2_recommended = calculate_version_state(
+        "1.3.0",
+        "0x3E5c63644E683549055b9Be8653de26E0B4CD36E",
+        &supported_master_copies,
+        "1.3.0+L2".to_string(),
+    );
+    let actual_l2_safe = calculate_version_state(
+        "1.3.0+L2",
+        "0x3E5c63644E683549055b9Be8653de26E0B4CD36E",
+        &supported_master_copies,
+        "1.3.0".to_string(),
+    );
+
+    assert_eq!(actual_l2_recommended, ImplementationVersionState::UpToDate);
+    assert_eq!(actual_l2_safe, ImplementationVersionState::UpToDate);
+}
+
+#[test]
+fn is_l2_implementation_from_safe_or_master_copy_version() {
+    let supported_master_copies = vec![MasterCopy {
+        address: "0x3E5c63644E683549055b9Be8653de26E0B4CD36E".to_string(),
+        version: "1.3.0+L2".to_string(),
+        deployer: "".to_string(),
+        deployed_block_number: 0,
+        last_indexed_block_number: 0,
+    }];
+
+    assert!(is_l2_implementation(
+        Some("1.3.0"),
+        "0x3e5c63644e683549055b9be8653de26e0b4cd36e",
+        &supported_master_copies
+    ));
+    assert!(is_l2_implementation(
+        Some("1.3.0+L2"),
+        "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552",
+        &[]
+    ));
+    assert!(!is_l2_implementation(
+        Some("1.3.0"),
+        "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552",
+        &supported_master_copies
+    ));
+    assert!(!is_l2_implementation(
+        None,
+        "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552",
+        &[]
+    ));
+}
+
 #[test]
 fn calculate_version_state_unknown() {
     let actual = calculate_version_state(
@@ -549,6 +714,25 @@ fn calculate_version_state_version_up_to_date_but_address_mismatch() {
     assert_eq!(actual, ImplementationVersionState::Unknown);
 }
 
+#[test]
+fn calculate_version_state_ignores_address_case() {
+    let supported_master_copies = vec![MasterCopy {
+        address: "0x34CfAC646f301356fAa8B21e94227e3583Fe3F5F".to_string(),
+        version: "1.1.1".to_string(),
+        deployer: "".to_string(),
+        deployed_block_number: 0,
+        last_indexed_block_number: 0,
+    }];
+    let actual = calculate_version_state(
+        "1.1.1",
+        "0x34cfac646f301356faa8b21e94227e3583fe3f5f",
+        &supported_master_copies,
+        "1.1.1".to_string(),
+    );
+
+    assert_eq!(actual, ImplementationVersionState::UpToDate);
+}
+
 #[test]
 fn implementation_from_master_copy() {
     let master_copy = MasterCopy {
@@ -1,12 +1,20 @@
 extern crate rocket;
 
+use crate::utils::payload_limits;
+use crate::utils::trace_id::TraceId;
 use rocket::response::Redirect;
 use rocket::serde::json::{json, Value};
 use rocket::Catcher;
-use rocket::Route;
+use rocket::{Request, Route};
 
 /// # About endpoint
 pub mod about;
+#[doc(hidden)]
+pub mod admin;
+/// # Analytics endpoint
+pub mod analytics;
+#[doc(hidden)]
+pub mod audit;
 /// # Balance endpoints
 pub mod balances;
 /// # Chain endpoints
@@ -22,6 +30,8 @@ pub mod health;
 pub mod hooks;
 /// # Notification endpoints
 pub mod notifications;
+/// # Relay endpoints
+pub mod relay;
 /// # SafeApps endpoints
 pub mod safe_apps;
 /// # Safe endpoints
@@ -33,20 +43,134 @@ pub mod safes;
 /// The types served by the gate way are `Transfer`, `SettingsChange` and `Custom`. Additionally, we treat the `Creation` transaction as one additional type, as it is meant to be group with the rest of the items in the same UI component in the apps.
 pub mod transactions;
 
+/// Routes that can be left out of a deployment, e.g. a read-only mirror without hooks and admin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RouteGroup {
+    Transactions,
+    Balances,
+    Collectibles,
+    Hooks,
+    /// Admin and audit endpoints
+    Admin,
+}
+
+impl RouteGroup {
+    pub const ALL: [RouteGroup; 5] = [
+        RouteGroup::Transactions,
+        RouteGroup::Balances,
+        RouteGroup::Collectibles,
+        RouteGroup::Hooks,
+        RouteGroup::Admin,
+    ];
+
+    /// Name used by `DISABLED_ROUTE_GROUPS`
+    pub fn name(&self) -> &'static str {
+        match self {
+            RouteGroup::Transactions => "transactions",
+            RouteGroup::Balances => "balances",
+            RouteGroup::Collectibles => "collectibles",
+            RouteGroup::Hooks => "hooks",
+            RouteGroup::Admin => "admin",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        RouteGroup::ALL
+            .iter()
+            .find(|group| group.name().eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    #[doc(hidden)]
+    pub fn routes(&self) -> Vec<Route> {
+        match self {
+            RouteGroup::Transactions => routes![
+                transactions::routes::get_transactions,
+                transactions::routes::get_transaction_raw_ids,
+                transactions::routes::put_transaction_note,
+                transactions::routes::get_transaction_notes,
+                transactions::routes::post_transactions_details,
+                transactions::routes::get_transactions_history,
+                transactions::routes::get_transfers,
+                transactions::routes::get_transactions_queued,
+                transactions::routes::get_transactions_queued_poll,
+                transactions::routes::get_transactions_queued_diff,
+                transactions::routes::get_transactions_queued_summary,
+                transactions::routes::get_transactions_by_nonce,
+                transactions::routes::post_transaction,
+                transactions::routes::post_replacement_preview,
+                transactions::routes::post_build_transfer,
+                transactions::routes::post_owner_change,
+                transactions::routes::post_verify_safe_tx_hash,
+                transactions::routes::post_confirmation,
+            ],
+            RouteGroup::Balances => routes![
+                balances::routes::get_balances,
+                balances::routes::get_balance_history,
+                balances::routes::get_supported_fiat,
+                balances::routes::get_exchange_rates,
+            ],
+            RouteGroup::Collectibles => routes![
+                collectibles::routes::get_collectibles,
+                collectibles::routes::get_collectibles_page,
+                collectibles::routes::post_collectible_refresh,
+            ],
+            RouteGroup::Hooks => routes![
+                hooks::routes::update,
+                hooks::routes::batch,
+                hooks::routes::flush
+            ],
+            RouteGroup::Admin => routes![
+                admin::routes::get_chains_export,
+                admin::routes::post_chains_import,
+                admin::routes::get_safe_usage,
+                admin::routes::get_top_consumers,
+                admin::routes::post_queued_purge,
+                admin::routes::get_invalidations,
+                admin::routes::get_token_overrides,
+                admin::routes::put_token_override,
+                admin::routes::delete_token_override,
+                admin::routes::get_ready_callback,
+                admin::routes::put_ready_callback,
+                admin::routes::delete_ready_callback,
+                admin::routes::post_cache_migration,
+                admin::routes::get_cache_migration,
+                admin::routes::get_read_only_mode,
+                admin::routes::put_read_only_mode,
+                admin::routes::get_dashboard,
+                audit::routes::get_audit_entries,
+            ],
+        }
+    }
+}
+
+/// Routes every deployment serves
 #[doc(hidden)]
-pub fn active_routes() -> Vec<Route> {
+pub fn core_routes() -> Vec<Route> {
     routes![
         root,
         about::routes::backbone,
         about::routes::get_about,
+        about::routes::get_policies,
+        about::routes::get_chains_status,
         about::routes::get_chains_about,
         about::routes::redis,
+        about::routes::cache_pool,
+        about::routes::config,
+        about::routes::metrics,
+        about::routes::schema_drift,
+        about::routes::call_budget,
+        about::routes::hook_events,
+        about::routes::upstream_queue,
+        about::routes::payload_limits,
         about::routes::get_master_copies,
-        balances::routes::get_balances,
-        balances::routes::get_supported_fiat,
+        analytics::routes::post_analytics_events,
         chains::routes::get_chain,
         chains::routes::get_chains,
-        collectibles::routes::get_collectibles,
+        chains::routes::get_chain_changes,
+        chains::routes::get_gas_price,
+        chains::routes::get_chain_asset,
+        chains::routes::get_token_logo,
         contracts::routes::post_data_decoder,
         delegates::routes::delete_delegate,
         delegates::routes::delete_safe_delegate,
@@ -54,42 +178,69 @@ pub fn active_routes() -> Vec<Route> {
         delegates::routes::post_delegate,
         notifications::routes::post_notification_registration,
         notifications::routes::delete_notification_registration,
+        relay::routes::post_relay,
+        relay::routes::get_relay_task,
         safes::routes::get_safe_info,
+        safes::routes::get_safe_dashboard,
         safes::routes::get_owners,
         safes::routes::post_safe_gas_estimation,
+        safes::routes::get_safe_recent_recipients,
+        safes::routes::get_safe_allowances,
+        safes::routes::put_safe_label,
+        safes::routes::get_safe_labels,
+        safes::routes::post_counterfactual_safe,
+        safes::routes::post_safe_creation,
         safe_apps::routes::get_safe_apps,
-        transactions::routes::get_transactions,
-        transactions::routes::get_transactions_history,
-        transactions::routes::get_transactions_queued,
-        transactions::routes::post_transaction,
-        transactions::routes::post_confirmation,
-        hooks::routes::update,
-        hooks::routes::flush,
         health::routes::health
     ]
 }
 
 #[doc(hidden)]
 pub fn error_catchers() -> Vec<Catcher> {
-    catchers![not_found, panic]
+    catchers![not_found, payload_too_large, panic]
 }
 
 #[doc(hidden)]
 #[catch(404)]
-fn not_found() -> Value {
-    json!({
-        "status": "error",
-        "reason": "Resource was not found."
-    })
+fn not_found(request: &Request) -> Value {
+    with_trace_id(
+        request,
+        json!({
+            "status": "error",
+            "reason": "Resource was not found."
+        }),
+    )
+}
+
+#[doc(hidden)]
+#[catch(413)]
+fn payload_too_large(request: &Request) -> Value {
+    with_trace_id(
+        request,
+        json!({
+            "status": "error",
+            "reason": payload_limits::reject_request_body()
+        }),
+    )
 }
 
 #[doc(hidden)]
 #[catch(500)]
-fn panic() -> Value {
-    json!({
-        "status": "error",
-        "reason": "Server error occurred."
-    })
+fn panic(request: &Request) -> Value {
+    with_trace_id(
+        request,
+        json!({
+            "status": "error",
+            "reason": "Server error occurred."
+        }),
+    )
+}
+
+fn with_trace_id(request: &Request, mut body: Value) -> Value {
+    if let Some(trace_id) = TraceId::of(request) {
+        body["traceId"] = Value::String(trace_id);
+    }
+    body
 }
 
 #[doc(hidden)]
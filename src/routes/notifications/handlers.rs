@@ -6,6 +6,10 @@ use crate::routes::notifications::models::{
 use crate::utils::context::RequestContext;
 use crate::utils::errors::{ApiError, ApiResult};
 use crate::utils::http_client::Request;
+use crate::utils::retry_queue::{
+    self, WriteMethod, NOTIFICATION_REGISTRATION, NOTIFICATION_UNREGISTRATION,
+};
+use rocket::http::Status;
 use serde_json::json;
 use serde_json::value::RawValue;
 use serde_json::{self, value::Value};
@@ -15,7 +19,7 @@ pub async fn delete_registration(
     chain_id: String,
     uuid: String,
     safe_address: String,
-) -> ApiResult<()> {
+) -> ApiResult<Status> {
     let info_provider = DefaultInfoProvider::new(&chain_id, &context);
     let url = core_uri!(
         info_provider,
@@ -24,16 +28,32 @@ pub async fn delete_registration(
         safe_address
     )?;
 
-    let request = Request::new(url);
-    context.http_client().delete(request).await?;
-
-    Ok(())
+    let request = Request::new(url.to_string());
+    match context.http_client().delete(request).await {
+        Ok(_) => Ok(Status::Ok),
+        Err(error) => {
+            let is_queued = retry_queue::queue_failed(
+                context.cache().as_ref(),
+                NOTIFICATION_UNREGISTRATION,
+                WriteMethod::Delete,
+                &url,
+                None,
+                &error,
+            )
+            .await;
+            if is_queued {
+                Ok(Status::Accepted)
+            } else {
+                Err(error)
+            }
+        }
+    }
 }
 
 pub async fn post_registration(
     context: &RequestContext,
     registration_request: NotificationRegistrationRequest,
-) -> ApiResult<()> {
+) -> ApiResult<Status> {
     let client = context.http_client();
     let mut requests = Vec::with_capacity(registration_request.safe_registrations.len());
 
@@ -43,33 +63,47 @@ pub async fn post_registration(
         let backend_request =
             build_backend_request(&registration_request.device_data, safe_registration);
 
+        let body = serde_json::to_string(&backend_request)?;
         let request = {
-            let mut request = Request::new(url);
-            request.body(Some(serde_json::to_string(&backend_request)?));
+            let mut request = Request::new(url.to_string());
+            request.body(Some(body.to_string()));
             request
         };
-        requests.push((&safe_registration.chain_id, client.post(request)));
+        requests.push((&safe_registration.chain_id, url, body, client.post(request)));
     }
 
+    let cache = context.cache();
+    let mut queued = false;
     let (error_chain_ids, error_body) = {
         let mut error_chain_ids: Vec<&str> = vec![];
         let mut errors: Vec<Value> = vec![];
-        for (chain_id, request) in requests.into_iter() {
-            match request.await {
-                Err(api_error) => {
+        for (chain_id, url, body, request) in requests.into_iter() {
+            if let Err(api_error) = request.await {
+                let is_queued = retry_queue::queue_failed(
+                    cache.as_ref(),
+                    NOTIFICATION_REGISTRATION,
+                    WriteMethod::Post,
+                    &url,
+                    Some(body),
+                    &api_error,
+                )
+                .await;
+                if is_queued {
+                    queued = true
+                } else {
                     error_chain_ids.push(chain_id);
                     errors.push(json!({
                         chain_id :   RawValue::from_string(api_error.details.message.unwrap_or(String::from("Unknown notification registration issue")))?
                     }))
                 }
-                _ => {}
             }
         }
         (error_chain_ids, json!(errors))
     };
 
     if error_chain_ids.is_empty() {
-        Ok(())
+        // Registrations queued for a retry are not confirmed yet
+        Ok(if queued { Status::Accepted } else { Status::Ok })
     } else {
         Err(ApiError::new_from_message_with_debug(
             format!(
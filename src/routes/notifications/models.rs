@@ -1,3 +1,4 @@
+use crate::utils::validation::{Validate, Validator};
 use serde::{Deserialize, Serialize};
 
 /// NotificationRegistrationRequest
@@ -72,3 +73,51 @@ pub struct SafeRegistration {
     pub safes: Vec<String>,
     pub signatures: Vec<String>,
 }
+
+impl Validate for NotificationRegistrationRequest {
+    fn validate(&self, validator: &mut Validator) {
+        let device_data = &self.device_data;
+        // Same limits as the transaction service
+        validator
+            .optional_length("uuid", &device_data.uuid, 36, 36)
+            .length(
+                "cloudMessagingToken",
+                &device_data.cloud_messaging_token,
+                1,
+                200,
+            )
+            .uint("buildNumber", &device_data.build_number)
+            .length("bundle", &device_data.bundle, 1, 100)
+            .length("version", &device_data.version, 1, 100)
+            .optional_uint("timestamp", &device_data.timestamp)
+            .check(
+                "safeRegistrations",
+                !self.safe_registrations.is_empty(),
+                "must not be empty",
+            )
+            .each(
+                "safeRegistrations",
+                &self.safe_registrations,
+                |validator, field, safe_registration| {
+                    validator.nested(field, safe_registration);
+                },
+            );
+    }
+}
+
+impl Validate for SafeRegistration {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .uint("chainId", &self.chain_id)
+            .each("safes", &self.safes, |validator, field, safe| {
+                validator.address(field, safe);
+            })
+            .each(
+                "signatures",
+                &self.signatures,
+                |validator, field, signature| {
+                    validator.signature(field, signature);
+                },
+            );
+    }
+}
@@ -2,6 +2,9 @@ use crate::routes::notifications::handlers::{delete_registration, post_registrat
 use crate::routes::notifications::models::NotificationRegistrationRequest;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
+use crate::utils::read_only;
+use crate::utils::validation::Validate;
+use rocket::http::Status;
 use rocket::serde::json::Error;
 use rocket::serde::json::Json;
 
@@ -13,6 +16,10 @@ use rocket::serde::json::Json;
  *
  * This endpoint provides a way for registering devices for push notifications.
  *
+ * With `notification_registration` in `RETRY_QUEUE_ENDPOINTS`, registrations failing on the
+ * transaction service with a server error are retried in the background and the request is
+ * answered with a `202`.
+ *
  * One can subscribe to as many safes in different chains as [SafeRegistration](crate::models::handlers::notifications::SafeRegistration) provided in the payload
  *
  * ## Path
@@ -31,8 +38,11 @@ use rocket::serde::json::Json;
 pub async fn post_notification_registration<'e>(
     context: RequestContext,
     registration_request: Result<Json<NotificationRegistrationRequest>, Error<'e>>,
-) -> ApiResult<()> {
-    post_registration(&context, registration_request?.0).await
+) -> ApiResult<Status> {
+    read_only::ensure_writable(&context).await?;
+    let registration_request = registration_request?.0;
+    registration_request.validated()?;
+    post_registration(&context, registration_request).await
 }
 
 /**
@@ -45,6 +55,9 @@ pub async fn post_notification_registration<'e>(
  *
  * Clients are expected to manage the `uuid` provided originally to the backend.
  *
+ * Like registrations, answered with a `202` when queued for a retry with
+ * `notification_unregistration` in `RETRY_QUEUE_ENDPOINTS`.
+ *
  * ## Path
  *
  * `DELETE /v1/chains/<chain_id>/notifications/devices/<uuid>/safes/<safe_address>`
@@ -59,6 +72,7 @@ pub async fn delete_notification_registration(
     chain_id: String,
     uuid: String,
     safe_address: String,
-) -> ApiResult<()> {
+) -> ApiResult<Status> {
+    read_only::ensure_writable(&context).await?;
     delete_registration(&context, chain_id, uuid, safe_address).await
 }
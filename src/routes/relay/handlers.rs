@@ -0,0 +1,169 @@
+use crate::cache::Cache;
+use crate::common::models::backend::relay::{RelayTaskCreated, RelayTaskResponse, SponsoredCall};
+use crate::config::{
+    relay_api_key, relay_quota_limit, relay_quota_window, relay_request_timeout, relay_service_uri,
+};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::relay::models::{RelayRequest, RelayResponse, RelayTaskStatus};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::Request;
+use crate::utils::transactions::EXEC_TRANSACTION_SIGNATURE;
+use chrono::Utc;
+use ethcontract_common::hash::keccak256;
+use std::sync::Arc;
+use std::time::Duration;
+
+const RELAY_QUOTA_KEY_BASE: &str = "relay_quota";
+
+pub async fn post_relay(
+    context: &RequestContext,
+    chain_id: &str,
+    relay_request: &RelayRequest,
+) -> ApiResult<RelayResponse> {
+    let relay_uri = relay_service_uri().ok_or(client_error!(503, "Relaying is not enabled"))?;
+    if !is_exec_transaction(&relay_request.data) {
+        return Err(client_error!(
+            422,
+            "Only execTransaction calls can be relayed"
+        ));
+    }
+    // Only Safes known to the transaction service are sponsored
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    info_provider.safe_info(&relay_request.to).await?;
+
+    let cache = context.cache();
+    let now = Utc::now().timestamp_millis();
+    let window = relay_quota_window();
+    let quota = RelayQuota::load(&cache, chain_id, &relay_request.to, now, window).await;
+    if quota.used >= relay_quota_limit() {
+        return Err(client_error!(429, "Relay quota exceeded for this Safe"));
+    }
+
+    let sponsored_call = SponsoredCall {
+        chain_id: chain_id.to_string(),
+        target: relay_request.to.to_string(),
+        data: relay_request.data.to_string(),
+        gas_limit: relay_request.gas_limit.clone(),
+        sponsor_api_key: relay_api_key(),
+    };
+    let task = submit_sponsored_call(context, &relay_uri, &sponsored_call).await?;
+
+    let used = quota.used + 1;
+    RelayQuota { used, ..quota }
+        .store(&cache, chain_id, &relay_request.to, now, window)
+        .await;
+    Ok(RelayResponse {
+        task_id: task.task_id,
+        remaining_relays: relay_quota_limit().saturating_sub(used),
+    })
+}
+
+pub(crate) async fn submit_sponsored_call(
+    context: &RequestContext,
+    relay_uri: &str,
+    sponsored_call: &SponsoredCall,
+) -> ApiResult<RelayTaskCreated> {
+    let request = {
+        let mut request = Request::new(format!("{}/relays/v2/sponsored-call", relay_uri));
+        request
+            .body(Some(serde_json::to_string(sponsored_call)?))
+            .timeout(Duration::from_millis(relay_request_timeout()));
+        request
+    };
+    let response = context.http_client().post(request).await?;
+    Ok(serde_json::from_str::<RelayTaskCreated>(&response.body)?)
+}
+
+pub async fn get_relay_task(
+    context: &RequestContext,
+    chain_id: &str,
+    task_id: &str,
+) -> ApiResult<RelayTaskStatus> {
+    let relay_uri = relay_service_uri().ok_or(client_error!(503, "Relaying is not enabled"))?;
+    let request = {
+        let mut request = Request::new(format!("{}/tasks/status/{}", relay_uri, task_id));
+        request.timeout(Duration::from_millis(relay_request_timeout()));
+        request
+    };
+    let response = context.http_client().get(request).await?;
+    let task = serde_json::from_str::<RelayTaskResponse>(&response.body)?.task;
+    if chain_id.parse::<u64>().ok() != Some(task.chain_id) {
+        return Err(client_error!(404, "Relay task not found"));
+    }
+    Ok(RelayTaskStatus {
+        task_id: task.task_id,
+        task_state: task.task_state,
+        transaction_hash: task.transaction_hash,
+        last_check_message: task.last_check_message,
+    })
+}
+
+pub fn is_exec_transaction(data: &str) -> bool {
+    let selector = to_hex_string!(keccak256(EXEC_TRANSACTION_SIGNATURE.as_bytes())[..4]);
+    data.to_lowercase().starts_with(&selector)
+}
+
+/// Relays used by a Safe within the current quota window, stored as `<used>;<window start>`
+#[derive(Debug, PartialEq)]
+pub struct RelayQuota {
+    pub used: usize,
+    pub window_start: i64,
+}
+
+impl RelayQuota {
+    pub async fn load(
+        cache: &Arc<dyn Cache>,
+        chain_id: &str,
+        safe_address: &str,
+        now: i64,
+        window: usize,
+    ) -> Self {
+        cache
+            .fetch(&quota_key(chain_id, safe_address))
+            .await
+            .and_then(|value| Self::parse(&value))
+            .filter(|quota| now - quota.window_start < window as i64)
+            .unwrap_or(RelayQuota {
+                used: 0,
+                window_start: now,
+            })
+    }
+
+    /// The entry expires with the window, so a new window starts with a full quota
+    pub async fn store(
+        &self,
+        cache: &Arc<dyn Cache>,
+        chain_id: &str,
+        safe_address: &str,
+        now: i64,
+        window: usize,
+    ) {
+        let elapsed = (now - self.window_start).max(0) as usize;
+        let remaining_window = window.saturating_sub(elapsed).max(1);
+        cache
+            .create(
+                &quota_key(chain_id, safe_address),
+                &format!("{};{}", self.used, self.window_start),
+                remaining_window,
+            )
+            .await;
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(2, ';');
+        Some(RelayQuota {
+            used: parts.next()?.parse().ok()?,
+            window_start: parts.next()?.parse().ok()?,
+        })
+    }
+}
+
+fn quota_key(chain_id: &str, safe_address: &str) -> String {
+    format!(
+        "{}_{}_{}",
+        RELAY_QUOTA_KEY_BASE,
+        chain_id,
+        safe_address.to_lowercase()
+    )
+}
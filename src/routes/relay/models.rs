@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// <summary>Example body of RelayRequest</summary>
+///
+/// ```json
+/// {
+///   "to": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+///   "data": "0x6a761202000000000000000000000000...",
+///   "gasLimit": "150000"
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayRequest {
+    /// Safe executing the transaction
+    pub to: String,
+    /// `execTransaction` call data, including the signatures
+    pub data: String,
+    pub gas_limit: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayResponse {
+    pub task_id: String,
+    pub remaining_relays: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayTaskStatus {
+    pub task_id: String,
+    pub task_state: String,
+    pub transaction_hash: Option<String>,
+    pub last_check_message: Option<String>,
+}
@@ -0,0 +1,66 @@
+use crate::monitoring::audit::{self, AuditOperation, Caller};
+use crate::routes::relay::handlers;
+use crate::routes::relay::models::RelayRequest;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::read_only;
+use rocket::response::content;
+use rocket::serde::json::Error;
+use rocket::serde::json::Json;
+
+/**
+ * `/v1/chains/<chain_id>/relay` <br />
+ * Returns [RelayResponse](crate::routes::relay::models::RelayResponse)
+ *
+ * # Relay
+ *
+ * Submits a gas-sponsored execution of a Safe transaction to the relayer configured with `RELAY_SERVICE_URI`. Only `execTransaction` calls to Safes known to the transaction service are relayed.
+ *
+ * Each Safe can be relayed `RELAY_QUOTA_LIMIT` times per `RELAY_QUOTA_WINDOW`, after that requests fail with `429`. The response contains the relays left in the current window.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/relay`
+ *
+ * The expected [crate::routes::relay::models::RelayRequest] body for this request can be found in the sections of the models
+ */
+#[post(
+    "/v1/chains/<chain_id>/relay",
+    format = "application/json",
+    data = "<relay_request>"
+)]
+pub async fn post_relay<'e>(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    relay_request: Result<Json<RelayRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    read_only::ensure_writable(&context).await?;
+    let relay_request = relay_request?.0;
+    let result = handlers::post_relay(&context, &chain_id, &relay_request).await;
+    audit::record(
+        AuditOperation::Relay,
+        &relay_request.to,
+        &caller,
+        audit::payload_hash(&relay_request),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/relay/<task_id>` <br />
+ * Returns [RelayTaskStatus](crate::routes::relay::models::RelayTaskStatus)
+ *
+ * Status of a relay task as reported by the relayer, `transactionHash` is set once the execution was submitted. Tasks of other chains are not found.
+ */
+#[get("/v1/chains/<chain_id>/relay/<task_id>")]
+pub async fn get_relay_task(
+    context: RequestContext,
+    chain_id: String,
+    task_id: String,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &handlers::get_relay_task(&context, &chain_id, &task_id).await?,
+    )?))
+}
@@ -0,0 +1,92 @@
+use crate::cache::{Cache, MockCache};
+use crate::routes::relay::handlers::{is_exec_transaction, RelayQuota};
+use mockall::predicate::eq;
+use std::sync::Arc;
+
+const SAFE_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const QUOTA_KEY: &str = "relay_quota_4_0x1230b3d59858296a31053c1b8562ecf89a2f888b";
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+#[test]
+fn is_exec_transaction_checks_selector() {
+    assert!(is_exec_transaction(
+        "0x6a761202000000000000000000000000d9ba894e0097f8cc2bbc9d24d308b98e36dc6d02"
+    ));
+    assert!(is_exec_transaction("0x6A761202"));
+    assert!(!is_exec_transaction(
+        "0xa9059cbb000000000000000000000000d9ba894e0097f8cc2bbc9d24d308b98e36dc6d02"
+    ));
+    assert!(!is_exec_transaction("0x"));
+}
+
+#[rocket::async_test]
+async fn relay_quota_load_within_window() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq(QUOTA_KEY))
+        .return_const(Some(format!("3;{}", 1000)));
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+
+    let actual = RelayQuota::load(&cache, "4", SAFE_ADDRESS, 1000 + DAY - 1, DAY as usize).await;
+
+    assert_eq!(
+        actual,
+        RelayQuota {
+            used: 3,
+            window_start: 1000
+        }
+    );
+}
+
+#[rocket::async_test]
+async fn relay_quota_load_starts_new_window() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .return_const(Some(format!("3;{}", 1000)));
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+
+    let expired = RelayQuota::load(&cache, "4", SAFE_ADDRESS, 1000 + DAY, DAY as usize).await;
+
+    let mut mock_cache = MockCache::new();
+    mock_cache.expect_fetch().times(1).return_const(None);
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+
+    let missing = RelayQuota::load(&cache, "4", SAFE_ADDRESS, 5000, DAY as usize).await;
+
+    assert_eq!(
+        expired,
+        RelayQuota {
+            used: 0,
+            window_start: 1000 + DAY
+        }
+    );
+    assert_eq!(
+        missing,
+        RelayQuota {
+            used: 0,
+            window_start: 5000
+        }
+    );
+}
+
+#[rocket::async_test]
+async fn relay_quota_store_expires_with_window() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_create()
+        .times(1)
+        .with(eq(QUOTA_KEY), eq("4;1000"), eq((DAY - 500) as usize))
+        .return_const(());
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+
+    RelayQuota {
+        used: 4,
+        window_start: 1000,
+    }
+    .store(&cache, "4", SAFE_ADDRESS, 1500, DAY as usize)
+    .await;
+}
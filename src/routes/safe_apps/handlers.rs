@@ -4,6 +4,7 @@ use crate::config::safe_apps_cache_duration;
 use crate::routes::safe_apps::models::SafeApp;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
+use crate::utils::outbound;
 
 pub async fn safe_apps(context: &RequestContext, chain_id: &String) -> ApiResult<Vec<SafeApp>> {
     let url = config_uri!("/v1/safe-apps/?chainId={}", chain_id);
@@ -15,6 +16,10 @@ pub async fn safe_apps(context: &RequestContext, chain_id: &String) -> ApiResult
 
     Ok(serde_json::from_str::<Vec<BackendSafeApp>>(&data)?
         .into_iter()
-        .map(|backend_safe_app| backend_safe_app.into())
+        .map(|backend_safe_app| {
+            // Listed apps are trusted, so their manifests can be loaded
+            outbound::allow_url(&backend_safe_app.url);
+            backend_safe_app.into()
+        })
         .collect::<Vec<SafeApp>>())
 }
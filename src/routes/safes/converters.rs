@@ -1,11 +1,15 @@
 use crate::common::models::addresses::AddressEx;
 use crate::common::models::backend::safes::MasterCopy;
-use crate::providers::ext::InfoProviderExt;
+use crate::providers::address_info::{address_ex_index, address_ex_or_default, AddressSource};
 use crate::providers::info::{InfoProvider, SafeInfo};
-use crate::routes::safes::models::{Implementation, ImplementationVersionState, SafeInfoEx};
-use semver::Version;
+use crate::routes::safes::models::{
+    Implementation, ImplementationInfo, ImplementationVersionState, SafeInfoEx, SafeInfoWarning,
+};
+use crate::utils::safe_version::{is_l2_version, parse_base_version, SafeCapability, SafeVersion};
 use std::cmp::Ordering;
 
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
 // We need to add Sync as trait bound as info_provider moves across threads
 impl SafeInfo {
     pub async fn to_safe_info_ex(
@@ -29,53 +33,130 @@ impl SafeInfo {
                         min_chain_version,
                     )
                 });
+        let safe_version = SafeVersion::parse(self.version.as_ref());
+        let emits_fallback_handler = safe_version.emits(SafeCapability::FallbackHandler);
+        let emits_guard = safe_version.emits(SafeCapability::Guard);
+
+        // Every contract of the Safe is resolved in one batch
+        let mut lookups = vec![];
+        if emits_fallback_handler && self.fallback_handler != ZERO_ADDRESS {
+            lookups.push(self.fallback_handler.as_str());
+        }
+        if emits_guard && self.guard != ZERO_ADDRESS {
+            lookups.push(&self.guard);
+        }
+        lookups.push(&self.master_copy);
+        lookups.extend(self.modules.iter().flatten().map(String::as_str));
+        let index = address_ex_index(info_provider, &lookups, AddressSource::Contracts).await;
+        let optional_address_ex = |address: &str| {
+            if address != ZERO_ADDRESS {
+                Some(address_ex_or_default(&index, address))
+            } else {
+                None
+            }
+        };
+
+        let fallback_handler = if emits_fallback_handler {
+            Some(optional_address_ex(&self.fallback_handler))
+        } else {
+            None
+        };
+        let guard = if emits_guard {
+            Some(optional_address_ex(&self.guard))
+        } else {
+            None
+        };
+
+        let warnings = safe_info_warnings(&fallback_handler, &guard);
+        let is_l2 = is_l2_implementation(
+            self.version.as_deref(),
+            &self.master_copy,
+            &supported_master_copies,
+        );
 
         SafeInfoEx {
             address: AddressEx::address_only(&self.address),
             chain_id: info_provider.chain_id().to_string(),
             nonce: self.nonce,
             threshold: self.threshold,
-            implementation: info_provider
-                .address_ex_from_contracts_or_default(&self.master_copy)
-                .await,
+            implementation: ImplementationInfo {
+                address: address_ex_or_default(&index, &self.master_copy),
+                version: self.version.to_owned(),
+                is_l2,
+            },
             owners: self
                 .owners
                 .iter()
                 .map(|owner| AddressEx::address_only(&owner))
                 .collect(),
-            modules: info_provider
-                .multiple_address_ex_from_contracts(&self.modules)
-                .await,
-            fallback_handler: info_provider
-                .address_ex_from_contracts_optional(&self.fallback_handler)
-                .await,
-            guard: info_provider
-                .address_ex_from_contracts_optional(&self.guard)
-                .await,
+            modules: self
+                .modules
+                .as_ref()
+                .filter(|modules| !modules.is_empty())
+                .map(|modules| {
+                    modules
+                        .iter()
+                        .map(|module| address_ex_or_default(&index, module))
+                        .collect()
+                }),
+            fallback_handler,
+            guard,
             version: self.version.to_owned(),
             implementation_version_state,
+            capabilities: safe_version.capabilities(),
+            warnings,
         }
     }
 }
 
+/// Fallback handlers and guards without a known contract name are reported, as they can execute
+/// arbitrary code on behalf of the Safe
+pub(crate) fn safe_info_warnings(
+    fallback_handler: &Option<Option<AddressEx>>,
+    guard: &Option<Option<AddressEx>>,
+) -> Vec<SafeInfoWarning> {
+    let is_unknown = |address: &Option<Option<AddressEx>>| matches!(address, Some(Some(address)) if address.name.is_none());
+    let mut warnings = vec![];
+    if is_unknown(fallback_handler) {
+        warnings.push(SafeInfoWarning::UnknownFallbackHandler);
+    }
+    if is_unknown(guard) {
+        warnings.push(SafeInfoWarning::UnknownGuard);
+    }
+    warnings
+}
+
+/// The version reported for the Safe may lack the `+L2` of its master copy
+pub(crate) fn is_l2_implementation(
+    safe_version: Option<&str>,
+    safe_implementation_address: &str,
+    supported_master_copies: &[MasterCopy],
+) -> bool {
+    safe_version.map_or(false, is_l2_version)
+        || supported_master_copies.iter().any(|master_copy| {
+            master_copy
+                .address
+                .eq_ignore_ascii_case(safe_implementation_address)
+                && is_l2_version(&master_copy.version)
+        })
+}
+
 pub(crate) fn calculate_version_state(
     safe_version: &str,
     safe_implementation_address: &str,
     supported_master_copies: &Vec<MasterCopy>,
     min_chain_version: String,
 ) -> ImplementationVersionState {
-    let sem_ver_safe = Version::parse(safe_version);
-    let sem_ver_min = Version::parse(&min_chain_version);
+    // `+L2` variants are as up to date as the version they are a variant of
+    let sem_ver_safe = parse_base_version(safe_version);
+    let sem_ver_min = parse_base_version(&min_chain_version);
 
-    let supported_addresses = supported_master_copies
+    // The transaction service doesn't guarantee checksummed addresses in both responses
+    let is_supported = supported_master_copies
         .iter()
-        .map(|it| it.address.as_str())
-        .collect::<Vec<&str>>();
+        .any(|it| it.address.eq_ignore_ascii_case(safe_implementation_address));
 
-    if sem_ver_min.is_err()
-        || sem_ver_safe.is_err()
-        || !supported_addresses.contains(&safe_implementation_address)
-    {
+    if sem_ver_min.is_none() || sem_ver_safe.is_none() || !is_supported {
         return ImplementationVersionState::Unknown;
     }
 
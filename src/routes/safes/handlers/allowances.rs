@@ -0,0 +1,175 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::common::models::data_decoded::{DataDecoded, ValueDecodedType};
+use crate::common::models::page::Page;
+use crate::config::{allowances_scan_size, transaction_request_timeout};
+use crate::monitoring::schema_drift;
+use crate::providers::ext::InfoProviderExt;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::rpc::{EthCall, RpcProvider};
+use crate::routes::safes::models::TokenAllowance;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::transactions::{decode_hex, parse_address};
+use crate::utils::{MULTI_SEND, MULTI_SEND_TRANSACTIONS_PARAM};
+use chrono::{DateTime, Utc};
+use ethabi::{ParamType, Token, Uint};
+use ethcontract_common::hash::keccak256;
+use rocket::futures::future::join_all;
+use std::collections::HashSet;
+
+pub const APPROVE_METHOD: &str = "approve";
+pub const ERC20_ALLOWANCE_SIGNATURE: &str = "allowance(address,address)";
+
+/// Token and spender of an approval, with the execution date of the latest transaction setting it
+#[derive(Debug, PartialEq)]
+pub struct Approval {
+    pub token: String,
+    pub spender: String,
+    pub execution_date: Option<DateTime<Utc>>,
+}
+
+/// Approvals are found in the recent executed transactions of the Safe (also within multi sends),
+/// their current amount is then read on chain, as it can have been spent or reset in the meantime
+pub async fn get_allowances(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+) -> ApiResult<Vec<TokenAllowance>> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/multisig-transactions/?executed=true&limit={}",
+        safe_address,
+        allowances_scan_size()
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let transactions: Page<MultisigTransaction> = schema_drift::parse(&body)?;
+    let approvals = collect_approvals(&transactions.results);
+
+    let rpc_provider = RpcProvider::new(context, &info_provider.chain_info().await?);
+    let amounts = join_all(
+        approvals
+            .iter()
+            .map(|approval| read_allowance(&rpc_provider, safe_address, approval)),
+    )
+    .await;
+
+    let mut active = vec![];
+    for (approval, amount) in approvals.into_iter().zip(amounts) {
+        // Calls reverting (e.g. the target not being an ERC20 contract) are not reported
+        match amount? {
+            Some(amount) if !amount.is_zero() => active.push((approval, amount)),
+            _ => {}
+        }
+    }
+
+    let spenders: Vec<String> = active
+        .iter()
+        .map(|(approval, _)| approval.spender.to_string())
+        .collect();
+    let address_info_index = info_provider.address_info_index(&spenders).await;
+
+    let mut allowances = vec![];
+    for (approval, amount) in active {
+        allowances.push(TokenAllowance {
+            token_info: info_provider.token_info(&approval.token).await.ok(),
+            spender: address_info_index
+                .get(&approval.spender)
+                .cloned()
+                .unwrap_or(AddressEx::address_only(&approval.spender)),
+            amount: amount.to_string(),
+            last_approval_timestamp: approval
+                .execution_date
+                .map(|execution_date| execution_date.timestamp_millis()),
+            token_address: approval.token,
+        });
+    }
+    Ok(allowances)
+}
+
+/// Transactions are expected newest first, so the first approval of a token and spender pair
+/// is its latest one
+pub fn collect_approvals(transactions: &[MultisigTransaction]) -> Vec<Approval> {
+    let mut seen = HashSet::new();
+    let mut approvals = vec![];
+    for transaction in transactions {
+        let safe_transaction = &transaction.safe_transaction;
+        let calls: Vec<(String, DataDecoded)> = match safe_transaction.data_decoded.as_ref() {
+            Some(data_decoded) if data_decoded.method == MULTI_SEND => {
+                match data_decoded.get_parameter_value_decoded(MULTI_SEND_TRANSACTIONS_PARAM) {
+                    Some(ValueDecodedType::InternalTransaction(internal_transactions)) => {
+                        internal_transactions
+                            .into_iter()
+                            .filter_map(|internal_transaction| {
+                                let to = internal_transaction.to;
+                                internal_transaction
+                                    .data_decoded
+                                    .map(|data_decoded| (to, data_decoded))
+                            })
+                            .collect()
+                    }
+                    None => vec![],
+                }
+            }
+            Some(data_decoded) => vec![(safe_transaction.to.to_string(), data_decoded.to_owned())],
+            None => vec![],
+        };
+
+        for (token, data_decoded) in calls {
+            if data_decoded.method != APPROVE_METHOD {
+                continue;
+            }
+            if let Some(spender) = data_decoded.get_parameter_single_value_at(0) {
+                if seen.insert((token.to_lowercase(), spender.to_lowercase())) {
+                    approvals.push(Approval {
+                        token,
+                        spender,
+                        execution_date: transaction.execution_date,
+                    });
+                }
+            }
+        }
+    }
+    approvals
+}
+
+/// `None` if the call reverted
+async fn read_allowance(
+    rpc_provider: &RpcProvider,
+    owner: &str,
+    approval: &Approval,
+) -> ApiResult<Option<Uint>> {
+    let call = EthCall {
+        from: None,
+        to: approval.token.to_string(),
+        data: allowance_call_data(owner, &approval.spender)?,
+        value: None,
+    };
+    match rpc_provider.call(&call).await? {
+        Ok(result) => Ok(decode_allowance(&result)),
+        Err(_) => Ok(None),
+    }
+}
+
+pub fn allowance_call_data(owner: &str, spender: &str) -> ApiResult<String> {
+    let mut encoded = keccak256(ERC20_ALLOWANCE_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[
+        Token::Address(parse_address(owner)?),
+        Token::Address(parse_address(spender)?),
+    ]));
+    Ok(to_hex_string!(encoded))
+}
+
+/// `None` if the return data is not a single uint256 (e.g. empty for accounts without code)
+pub fn decode_allowance(result: &str) -> Option<Uint> {
+    let data = decode_hex(result).ok()?;
+    match ethabi::decode(&[ParamType::Uint(256)], &data).ok()?.pop()? {
+        Token::Uint(amount) => Some(amount),
+        _ => None,
+    }
+}
@@ -0,0 +1,165 @@
+use crate::cache::cache_operations::{Invalidate, InvalidationPattern, InvalidationScope};
+use crate::cache::Cache;
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::page::SafeList;
+use crate::routes::safes::handlers::safes::{get_owners_for_safe, get_safe_info_ex};
+use crate::routes::safes::models::{
+    CounterfactualSafe, CounterfactualSafeRequest, PendingSafeInfo, SafeDeploymentStatus,
+    SafeInfoResponse,
+};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::validation::Validate;
+use chrono::Utc;
+use std::collections::HashMap;
+
+// Counterfactual Safes are stored without expiry, one entry per predicted address, and served
+// for as long as the transaction service doesn't know the Safe
+const COUNTERFACTUAL_SAFES_KEY_BASE: &str = "counterfactual_safes";
+
+pub async fn register_counterfactual_safe(
+    context: &RequestContext,
+    chain_id: &str,
+    request: &CounterfactualSafeRequest,
+) -> ApiResult<CounterfactualSafe> {
+    request.validated()?;
+    let safe = counterfactual_safe(chain_id, request, Utc::now().timestamp_millis());
+    let cache = context.cache();
+    cache
+        .insert_in_hash(
+            &counterfactual_safes_key(chain_id),
+            &safe.address.to_lowercase(),
+            &serde_json::to_string(&safe)?,
+        )
+        .await;
+    // Cached 404s of the Safe and the Safe lists of its owners
+    for address in std::iter::once(&safe.address).chain(safe.owners.iter()) {
+        Invalidate::new(
+            InvalidationPattern::Any(InvalidationScope::Both, String::from(address)),
+            context.cache(),
+        )
+        .source("COUNTERFACTUAL_SAFE")
+        .execute()
+        .await;
+    }
+    Ok(safe)
+}
+
+/// The indexed Safe, or the registered counterfactual Safe if the transaction service doesn't
+/// know it (yet)
+pub async fn get_safe_info_or_pending(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+) -> ApiResult<SafeInfoResponse> {
+    match get_safe_info_ex(context, chain_id, safe_address).await {
+        Ok(safe_state) => Ok(SafeInfoResponse::Deployed(safe_state)),
+        Err(error) if error.status == 404 => {
+            match stored_safe(context.cache().as_ref(), chain_id, safe_address).await {
+                Some(safe) => Ok(SafeInfoResponse::PendingDeployment(pending_safe_info(
+                    &safe,
+                ))),
+                None => Err(error),
+            }
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// The indexed Safes of the owner followed by the counterfactual Safes it owns
+pub async fn get_owners_with_pending(
+    context: &RequestContext,
+    chain_id: &str,
+    owner_address: &str,
+) -> ApiResult<SafeList> {
+    let indexed = get_owners_for_safe(context, chain_id, owner_address).await?;
+    let stored = context
+        .cache()
+        .fetch_hash(&counterfactual_safes_key(chain_id))
+        .await;
+    Ok(with_pending_safes(indexed, &stored, owner_address))
+}
+
+pub fn with_pending_safes(
+    mut safe_list: SafeList,
+    stored: &HashMap<String, String>,
+    owner_address: &str,
+) -> SafeList {
+    let mut pending: Vec<CounterfactualSafe> = stored
+        .values()
+        .filter_map(|safe| serde_json::from_str::<CounterfactualSafe>(safe).ok())
+        .filter(|safe| {
+            safe.owners
+                .iter()
+                .any(|owner| owner.eq_ignore_ascii_case(owner_address))
+        })
+        .collect();
+    pending.sort_by_key(|safe| safe.created_at);
+    for safe in pending {
+        if !safe_list
+            .safes
+            .iter()
+            .any(|indexed| indexed.eq_ignore_ascii_case(&safe.address))
+        {
+            safe_list.safes.push(safe.address);
+        }
+    }
+    safe_list
+}
+
+pub fn counterfactual_safe(
+    chain_id: &str,
+    request: &CounterfactualSafeRequest,
+    created_at: i64,
+) -> CounterfactualSafe {
+    CounterfactualSafe {
+        address: request.address.to_string(),
+        chain_id: chain_id.to_string(),
+        owners: request.owners.clone(),
+        threshold: request.threshold,
+        salt_nonce: request.salt_nonce.to_string(),
+        factory_address: request.factory_address.clone(),
+        master_copy: request.master_copy.clone(),
+        fallback_handler: request.fallback_handler.clone(),
+        created_at,
+    }
+}
+
+pub fn pending_safe_info(safe: &CounterfactualSafe) -> PendingSafeInfo {
+    let address_ex = |address: &Option<String>| address.as_deref().map(AddressEx::address_only);
+    PendingSafeInfo {
+        address: AddressEx::address_only(&safe.address),
+        chain_id: safe.chain_id.to_string(),
+        nonce: 0,
+        threshold: safe.threshold,
+        owners: safe
+            .owners
+            .iter()
+            .map(|owner| AddressEx::address_only(owner))
+            .collect(),
+        status: SafeDeploymentStatus::PendingDeployment,
+        salt_nonce: safe.salt_nonce.to_string(),
+        factory_address: address_ex(&safe.factory_address),
+        master_copy: address_ex(&safe.master_copy),
+        fallback_handler: address_ex(&safe.fallback_handler),
+        created_at: safe.created_at,
+    }
+}
+
+async fn stored_safe(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe_address: &str,
+) -> Option<CounterfactualSafe> {
+    cache
+        .get_from_hash(
+            &counterfactual_safes_key(chain_id),
+            &safe_address.to_lowercase(),
+        )
+        .await
+        .and_then(|safe| serde_json::from_str(&safe).ok())
+}
+
+fn counterfactual_safes_key(chain_id: &str) -> String {
+    format!("{}_{}", COUNTERFACTUAL_SAFES_KEY_BASE, chain_id)
+}
@@ -0,0 +1,257 @@
+//! Deployment of new Safes through the proxy factory. The `createProxyWithNonce` transaction is
+//! built from the setup of the Safe and either returned for the client to submit or relayed via
+//! `RELAY_SERVICE_URI`. The address of the Safe is predicted like the factory deploys it
+//! (`CREATE2` with the proxy creation code read from the factory), relayed deployments are
+//! registered as counterfactual Safes so that they are served before they are indexed.
+use crate::common::models::backend::relay::SponsoredCall;
+use crate::config::{relay_api_key, relay_quota_limit, relay_quota_window, relay_service_uri};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::providers::rpc::{EthCall, RpcProvider};
+use crate::routes::relay::handlers::{submit_sponsored_call, RelayQuota};
+use crate::routes::safes::handlers::counterfactual::register_counterfactual_safe;
+use crate::routes::safes::models::{
+    CounterfactualSafeRequest, SafeCreationRequest, SafeCreationResponse, SafeCreationTransaction,
+};
+use crate::utils::checksum::to_checksum_address;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::transactions::{decode_hex, parse_address};
+use crate::utils::validation::Validate;
+use chrono::Utc;
+use ethabi::{Address, ParamType, Token, Uint};
+use ethcontract_common::hash::keccak256;
+
+// Canonical v1.3.0 deployments, at the same address on every chain
+pub const DEFAULT_PROXY_FACTORY: &str = "0xa6B71E26C5e0845f74c812102Ca7114b6a896AB2";
+pub const DEFAULT_MASTER_COPY: &str = "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552";
+pub const DEFAULT_MASTER_COPY_L2: &str = "0x3E5c63644E683549055b9Be8653de26E0B4CD36E";
+pub const DEFAULT_FALLBACK_HANDLER: &str = "0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4";
+
+pub const SETUP_SIGNATURE: &str =
+    "setup(address[],uint256,address,bytes,address,address,uint256,address)";
+pub const CREATE_PROXY_WITH_NONCE_SIGNATURE: &str = "createProxyWithNonce(address,bytes,uint256)";
+pub const PROXY_CREATION_CODE_SIGNATURE: &str = "proxyCreationCode()";
+
+/// Contracts a Safe is deployed with
+#[derive(Debug, PartialEq)]
+pub struct SafeDeployment {
+    pub factory_address: String,
+    pub master_copy: String,
+    pub fallback_handler: String,
+}
+
+pub async fn create_safe(
+    context: &RequestContext,
+    chain_id: &str,
+    request: &SafeCreationRequest,
+) -> ApiResult<SafeCreationResponse> {
+    request.validated()?;
+    let relay_uri = if request.relay {
+        Some(relay_service_uri().ok_or(client_error!(503, "Relaying is not enabled"))?)
+    } else {
+        None
+    };
+
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let chain_info = info_provider.chain_info().await?;
+    let deployment = safe_deployment(request, chain_info.l2);
+    let initializer = setup_data(&request.owners, request.threshold, &deployment)?;
+    let salt_nonce = Uint::from_dec_str(&request.salt_nonce)
+        .map_err(|_| client_error!(422, "saltNonce: must be an unsigned integer"))?;
+
+    let rpc_provider = RpcProvider::new(context, &chain_info);
+    let creation_code = proxy_creation_code(&rpc_provider, &deployment.factory_address).await?;
+    let safe_address = predicted_safe_address(
+        &deployment.factory_address,
+        &deployment.master_copy,
+        &initializer,
+        &salt_nonce,
+        &creation_code,
+    )?;
+    let transaction = SafeCreationTransaction {
+        to: deployment.factory_address.to_string(),
+        data: create_proxy_data(&deployment.master_copy, &initializer, &salt_nonce)?,
+        value: String::from("0"),
+    };
+
+    let task_id = match relay_uri {
+        Some(relay_uri) => Some(
+            relay_deployment(
+                context,
+                chain_id,
+                &relay_uri,
+                request,
+                &deployment,
+                &safe_address,
+                &transaction,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+    Ok(SafeCreationResponse {
+        safe_address,
+        transaction,
+        task_id,
+    })
+}
+
+// Counts against the relay quota of the predicted Safe
+async fn relay_deployment(
+    context: &RequestContext,
+    chain_id: &str,
+    relay_uri: &str,
+    request: &SafeCreationRequest,
+    deployment: &SafeDeployment,
+    safe_address: &str,
+    transaction: &SafeCreationTransaction,
+) -> ApiResult<String> {
+    let cache = context.cache();
+    let now = Utc::now().timestamp_millis();
+    let window = relay_quota_window();
+    let quota = RelayQuota::load(&cache, chain_id, safe_address, now, window).await;
+    if quota.used >= relay_quota_limit() {
+        return Err(client_error!(429, "Relay quota exceeded for this Safe"));
+    }
+
+    let sponsored_call = SponsoredCall {
+        chain_id: chain_id.to_string(),
+        target: transaction.to.to_string(),
+        data: transaction.data.to_string(),
+        gas_limit: None,
+        sponsor_api_key: relay_api_key(),
+    };
+    let task = submit_sponsored_call(context, relay_uri, &sponsored_call).await?;
+    RelayQuota {
+        used: quota.used + 1,
+        ..quota
+    }
+    .store(&cache, chain_id, safe_address, now, window)
+    .await;
+
+    register_counterfactual_safe(
+        context,
+        chain_id,
+        &CounterfactualSafeRequest {
+            address: safe_address.to_string(),
+            owners: request.owners.clone(),
+            threshold: request.threshold,
+            salt_nonce: request.salt_nonce.to_string(),
+            factory_address: Some(deployment.factory_address.to_string()),
+            master_copy: Some(deployment.master_copy.to_string()),
+            fallback_handler: Some(deployment.fallback_handler.to_string()),
+        },
+    )
+    .await?;
+    Ok(task.task_id)
+}
+
+/// Contracts of `request`, the canonical deployments for the ones that are not set. Safes on L2
+/// chains default to the master copy emitting events for the transaction service.
+pub fn safe_deployment(request: &SafeCreationRequest, is_l2: bool) -> SafeDeployment {
+    let default_master_copy = if is_l2 {
+        DEFAULT_MASTER_COPY_L2
+    } else {
+        DEFAULT_MASTER_COPY
+    };
+    let or_default = |address: &Option<String>, default: &str| {
+        to_checksum_address(address.as_deref().unwrap_or(default))
+    };
+    SafeDeployment {
+        factory_address: or_default(&request.factory_address, DEFAULT_PROXY_FACTORY),
+        master_copy: or_default(&request.master_copy, default_master_copy),
+        fallback_handler: or_default(&request.fallback_handler, DEFAULT_FALLBACK_HANDLER),
+    }
+}
+
+/// `setup` call data initializing the proxy, without modules and payment
+pub fn setup_data(
+    owners: &[String],
+    threshold: u64,
+    deployment: &SafeDeployment,
+) -> ApiResult<Vec<u8>> {
+    let owners = owners
+        .iter()
+        .map(|owner| parse_address(owner).map(Token::Address))
+        .collect::<ApiResult<Vec<Token>>>()?;
+    let mut encoded = keccak256(SETUP_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[
+        Token::Array(owners),
+        Token::Uint(Uint::from(threshold)),
+        Token::Address(Address::zero()),
+        Token::Bytes(vec![]),
+        Token::Address(parse_address(&deployment.fallback_handler)?),
+        Token::Address(Address::zero()),
+        Token::Uint(Uint::zero()),
+        Token::Address(Address::zero()),
+    ]));
+    Ok(encoded)
+}
+
+pub fn create_proxy_data(
+    master_copy: &str,
+    initializer: &[u8],
+    salt_nonce: &Uint,
+) -> ApiResult<String> {
+    let mut encoded = keccak256(CREATE_PROXY_WITH_NONCE_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[
+        Token::Address(parse_address(master_copy)?),
+        Token::Bytes(initializer.to_vec()),
+        Token::Uint(*salt_nonce),
+    ]));
+    Ok(to_hex_string!(encoded))
+}
+
+/// Address `createProxyWithNonce` deploys the proxy at:
+/// `keccak256(0xff ++ factory ++ salt ++ keccak256(creation code ++ master copy))[12..]`, with
+/// `salt = keccak256(keccak256(initializer) ++ salt nonce)`
+pub fn predicted_safe_address(
+    factory_address: &str,
+    master_copy: &str,
+    initializer: &[u8],
+    salt_nonce: &Uint,
+    creation_code: &[u8],
+) -> ApiResult<String> {
+    let mut salt = keccak256(initializer).to_vec();
+    salt.extend(ethabi::encode(&[Token::Uint(*salt_nonce)]));
+
+    let mut deployment_data = creation_code.to_vec();
+    deployment_data.extend(ethabi::encode(&[Token::Address(parse_address(
+        master_copy,
+    )?)]));
+
+    let mut create2 = vec![0xff];
+    create2.extend(parse_address(factory_address)?.as_bytes());
+    create2.extend(keccak256(&salt).iter());
+    create2.extend(keccak256(&deployment_data).iter());
+    Ok(to_checksum_address(&to_hex_string!(
+        keccak256(&create2)[12..]
+    )))
+}
+
+async fn proxy_creation_code(
+    rpc_provider: &RpcProvider,
+    factory_address: &str,
+) -> ApiResult<Vec<u8>> {
+    let call = EthCall {
+        from: None,
+        to: factory_address.to_string(),
+        data: to_hex_string!(keccak256(PROXY_CREATION_CODE_SIGNATURE.as_bytes())[..4]),
+        value: None,
+    };
+    rpc_provider
+        .call(&call)
+        .await?
+        .ok()
+        .and_then(|result| decode_creation_code(&result))
+        .ok_or_else(|| client_error!(422, "Factory address is not a proxy factory"))
+}
+
+/// `None` if the return data is not a single, non empty `bytes`
+pub fn decode_creation_code(result: &str) -> Option<Vec<u8>> {
+    let data = decode_hex(result).ok()?;
+    match ethabi::decode(&[ParamType::Bytes], &data).ok()?.pop()? {
+        Token::Bytes(code) if !code.is_empty() => Some(code),
+        _ => None,
+    }
+}
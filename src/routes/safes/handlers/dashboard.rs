@@ -0,0 +1,80 @@
+use crate::common::models::page::PageMetadata;
+use crate::config::{dashboard_balances_size, dashboard_history_size};
+use crate::routes::balances::history::balances_with_snapshot;
+use crate::routes::balances::models::Balances;
+use crate::routes::safes::handlers::counterfactual::get_safe_info_or_pending;
+use crate::routes::safes::models::{SafeDashboard, SafeInfoResponse};
+use crate::routes::transactions::handlers::history::get_history_transactions;
+use crate::routes::transactions::handlers::queued::get_queued_summary;
+use crate::routes::transactions::models::summary::TransactionListItem;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use rocket::futures::join;
+
+pub async fn get_safe_dashboard(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    fiat: &str,
+) -> ApiResult<SafeDashboard> {
+    // First page of the history route, with the backend transactions the dashboard needs
+    let history_cursor = Some(
+        PageMetadata {
+            offset: 0,
+            limit: dashboard_history_size() as u64,
+        }
+        .to_url_string(),
+    );
+    // Same defaults as the balances and queued routes, so that their cached responses are shared
+    let (safe, balances, queued, history) = join!(
+        get_safe_info_or_pending(context, chain_id, safe_address),
+        balances_with_snapshot(context, chain_id, safe_address, fiat, false, true),
+        get_queued_summary(context, chain_id, safe_address, &None, &None),
+        get_history_transactions(context, chain_id, safe_address, &history_cursor, &None)
+    );
+    let safe = safe?;
+    // The transaction service doesn't know counterfactual Safes, which have nothing to show yet
+    if let SafeInfoResponse::PendingDeployment(_) = safe {
+        return Ok(safe_dashboard(
+            safe,
+            Balances {
+                fiat_total: String::from("0"),
+                items: vec![],
+            },
+            0,
+            vec![],
+            0,
+            0,
+        ));
+    }
+    Ok(safe_dashboard(
+        safe,
+        balances?,
+        queued?.total,
+        history?.results,
+        dashboard_balances_size(),
+        dashboard_history_size(),
+    ))
+}
+
+pub fn safe_dashboard(
+    safe: SafeInfoResponse,
+    balances: Balances,
+    queued: u64,
+    history: Vec<TransactionListItem>,
+    balances_size: usize,
+    history_size: usize,
+) -> SafeDashboard {
+    SafeDashboard {
+        safe,
+        fiat_total: balances.fiat_total,
+        // Balances are sorted by fiat balance already
+        balances: balances.items.into_iter().take(balances_size).collect(),
+        queued,
+        history: history
+            .into_iter()
+            .filter(|item| matches!(item, TransactionListItem::Transaction { .. }))
+            .take(history_size)
+            .collect(),
+    }
+}
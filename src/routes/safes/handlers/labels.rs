@@ -0,0 +1,67 @@
+use crate::cache::Cache;
+use crate::routes::safes::models::{SafeLabel, SafeLabelRequest};
+use crate::utils::context::RequestContext;
+use crate::utils::device::Device;
+use crate::utils::errors::ApiResult;
+use std::sync::Arc;
+
+// Labels are stored without expiry, one entry per device
+const SAFE_LABELS_KEY: &str = "safe_labels";
+pub const MAX_LABEL_LENGTH: usize = 50;
+
+pub async fn set_safe_label(
+    context: &RequestContext,
+    device: &Device,
+    chain_id: &str,
+    safe_address: &str,
+    request: &SafeLabelRequest,
+) -> ApiResult<Vec<SafeLabel>> {
+    let cache = context.cache();
+    device.authenticate(&cache).await?;
+    let label = request.label.trim();
+    if label.chars().count() > MAX_LABEL_LENGTH {
+        return Err(client_error!(422, "Label is too long"));
+    }
+
+    let mut labels = stored_labels(&cache, &device.uuid).await;
+    update_labels(&mut labels, chain_id, safe_address, label);
+    cache
+        .insert_in_hash(
+            SAFE_LABELS_KEY,
+            &device.uuid,
+            &serde_json::to_string(&labels)?,
+        )
+        .await;
+    Ok(labels)
+}
+
+pub async fn get_safe_labels(
+    context: &RequestContext,
+    device: &Device,
+) -> ApiResult<Vec<SafeLabel>> {
+    let cache = context.cache();
+    device.authenticate(&cache).await?;
+    Ok(stored_labels(&cache, &device.uuid).await)
+}
+
+/// Replaces the label of the safe on that chain, an empty label removes it
+pub fn update_labels(labels: &mut Vec<SafeLabel>, chain_id: &str, safe_address: &str, label: &str) {
+    labels.retain(|stored| {
+        stored.chain_id != chain_id || !stored.address.eq_ignore_ascii_case(safe_address)
+    });
+    if !label.is_empty() {
+        labels.push(SafeLabel {
+            chain_id: chain_id.to_string(),
+            address: safe_address.to_string(),
+            label: label.to_string(),
+        });
+    }
+}
+
+async fn stored_labels(cache: &Arc<dyn Cache>, device_uuid: &str) -> Vec<SafeLabel> {
+    cache
+        .get_from_hash(SAFE_LABELS_KEY, device_uuid)
+        .await
+        .and_then(|labels| serde_json::from_str(&labels).ok())
+        .unwrap_or_default()
+}
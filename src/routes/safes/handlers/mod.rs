@@ -1,2 +1,8 @@
+pub mod allowances;
+pub mod counterfactual;
+pub mod creation;
+pub mod dashboard;
 pub mod estimations;
+pub mod labels;
+pub mod recipients;
 pub mod safes;
@@ -0,0 +1,83 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::backend::transfers::Transfer;
+use crate::common::models::page::Page;
+use crate::config::{
+    recent_recipients_limit, recent_recipients_scan_size, transaction_request_timeout,
+};
+use crate::monitoring::schema_drift;
+use crate::providers::ext::InfoProviderExt;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::safes::models::RecentRecipient;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+pub async fn get_recent_recipients(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+) -> ApiResult<Vec<RecentRecipient>> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    // Same backend request as the first transfers page of the history, so it is usually cached
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/transfers/?limit={}",
+        safe_address,
+        recent_recipients_scan_size()
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let transfers: Page<Transfer> = schema_drift::parse(&body)?;
+
+    let recipients =
+        collect_recent_recipients(&transfers.results, safe_address, recent_recipients_limit());
+    let addresses: Vec<String> = recipients
+        .iter()
+        .map(|(address, _)| address.to_string())
+        .collect();
+    let address_info_index = info_provider.address_info_index(&addresses).await;
+
+    Ok(recipients
+        .into_iter()
+        .map(|(address, execution_date)| RecentRecipient {
+            address: address_info_index
+                .get(&address)
+                .cloned()
+                .unwrap_or(AddressEx::address_only(&address)),
+            last_transfer_timestamp: execution_date.timestamp_millis(),
+        })
+        .collect())
+}
+
+/// Transfers are expected newest first, so the first occurrence of a recipient is its latest one
+pub fn collect_recent_recipients(
+    transfers: &[Transfer],
+    safe_address: &str,
+    limit: usize,
+) -> Vec<(String, DateTime<Utc>)> {
+    let mut seen = HashSet::new();
+    transfers
+        .iter()
+        .filter_map(|transfer| outgoing_recipient(transfer, safe_address))
+        .filter(|(to, _)| seen.insert(to.to_lowercase()))
+        .take(limit)
+        .collect()
+}
+
+fn outgoing_recipient(transfer: &Transfer, safe_address: &str) -> Option<(String, DateTime<Utc>)> {
+    let (from, to, execution_date) = match transfer {
+        Transfer::Erc721(transfer) => (&transfer.from, &transfer.to, transfer.execution_date),
+        Transfer::Erc20(transfer) => (&transfer.from, &transfer.to, transfer.execution_date),
+        Transfer::Ether(transfer) => (&transfer.from, &transfer.to, transfer.execution_date),
+        Transfer::Unknown => return None,
+    };
+    if from.eq_ignore_ascii_case(safe_address) && !to.eq_ignore_ascii_case(safe_address) {
+        Some((to.to_string(), execution_date))
+    } else {
+        None
+    }
+}
@@ -2,9 +2,8 @@ use crate::cache::cache_operations::RequestCached;
 use crate::common::models::backend::transactions::{MultisigTransaction, Transaction};
 use crate::common::models::backend::transfers::Transfer;
 use crate::common::models::page::{Page, SafeList};
-use crate::config::{
-    default_request_timeout, owners_for_safes_cache_duration, transaction_request_timeout,
-};
+use crate::config::{owners_for_safes_cache_duration, transaction_request_timeout};
+use crate::monitoring::schema_drift;
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
 use crate::routes::safes::models::{SafeLastChanges, SafeState};
 use crate::utils::context::RequestContext;
@@ -64,7 +63,7 @@ async fn get_last_collectible(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    let transaction: Page<Transfer> = serde_json::from_str(&body)?;
+    let transaction: Page<Transfer> = schema_drift::parse(&body)?;
 
     transaction
         .results
@@ -98,7 +97,7 @@ async fn get_last_queued_tx(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    let transaction: Page<MultisigTransaction> = serde_json::from_str(&body)?;
+    let transaction: Page<MultisigTransaction> = schema_drift::parse(&body)?;
 
     transaction
         .results
@@ -127,7 +126,7 @@ async fn get_last_history_tx(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    let transaction: Page<Transaction> = serde_json::from_str(&body)?;
+    let transaction: Page<Transaction> = schema_drift::parse(&body)?;
 
     transaction
         .results
@@ -155,10 +154,9 @@ pub async fn get_owners_for_safe(
 
     let url = core_uri!(info_provider, "/v1/owners/{}/safes", owner_address)?;
     let body = RequestCached::new_from_context(url, context)
-        .request_timeout(default_request_timeout())
         .cache_duration(owners_for_safes_cache_duration())
         .execute()
         .await?;
 
-    Ok(serde_json::from_str(&body)?)
+    Ok(schema_drift::parse(&body)?)
 }
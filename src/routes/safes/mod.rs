@@ -4,3 +4,6 @@ pub mod converters;
 pub mod handlers;
 pub mod models;
 pub mod routes;
+
+#[cfg(test)]
+mod tests;
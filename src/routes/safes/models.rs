@@ -1,5 +1,10 @@
 use crate::common::models::addresses::AddressEx;
 use crate::common::models::data_decoded::Operation;
+use crate::providers::info::TokenInfo;
+use crate::routes::balances::models::Balance;
+use crate::routes::transactions::models::summary::TransactionListItem;
+use crate::utils::safe_version::SafeCapability;
+use crate::utils::validation::{Validate, Validator};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -10,12 +15,42 @@ pub struct SafeInfoEx {
     pub nonce: u64,
     pub threshold: u64,
     pub owners: Vec<AddressEx>,
-    pub implementation: AddressEx,
+    pub implementation: ImplementationInfo,
     pub modules: Option<Vec<AddressEx>>,
-    pub fallback_handler: Option<AddressEx>,
-    pub guard: Option<AddressEx>,
+    /// Omitted if the Safe version doesn't support it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_handler: Option<Option<AddressEx>>,
+    /// Omitted if the Safe version doesn't support it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guard: Option<Option<AddressEx>>,
     pub version: Option<String>,
     pub implementation_version_state: ImplementationVersionState,
+    /// Empty if the Safe version is unknown
+    pub capabilities: Vec<SafeCapability>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<SafeInfoWarning>,
+}
+
+/// Master copy of the Safe, with the name and logo of the contract
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImplementationInfo {
+    #[serde(flatten)]
+    pub address: AddressEx,
+    pub version: Option<String>,
+    /// `+L2` variant of the version, emitting events for indexing on L2 chains
+    #[serde(rename = "isL2")]
+    pub is_l2: bool,
+}
+
+/// Settings of the Safe signers should review before signing
+#[derive(Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SafeInfoWarning {
+    /// The fallback handler is not a known contract
+    UnknownFallbackHandler,
+    /// The transaction guard is not a known contract
+    UnknownGuard,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -59,9 +94,199 @@ pub struct SafeTransactionEstimationRequest {
     pub operation: Operation,
 }
 
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentRecipient {
+    pub address: AddressEx,
+    pub last_transfer_timestamp: i64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeLabelRequest {
+    pub label: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeLabel {
+    pub chain_id: String,
+    pub address: String,
+    pub label: String,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SafeTransactionEstimation {
     pub latest_nonce: u64,
     pub safe_tx_gas: String,
 }
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAllowance {
+    pub token_address: String,
+    pub token_info: Option<TokenInfo>,
+    pub spender: AddressEx,
+    pub amount: String,
+    pub last_approval_timestamp: Option<i64>,
+}
+
+/// Safe that is not deployed yet, registered with its predicted address and setup
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterfactualSafeRequest {
+    pub address: String,
+    pub owners: Vec<String>,
+    pub threshold: u64,
+    pub salt_nonce: String,
+    pub factory_address: Option<String>,
+    pub master_copy: Option<String>,
+    pub fallback_handler: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterfactualSafe {
+    pub address: String,
+    pub chain_id: String,
+    pub owners: Vec<String>,
+    pub threshold: u64,
+    pub salt_nonce: String,
+    pub factory_address: Option<String>,
+    pub master_copy: Option<String>,
+    pub fallback_handler: Option<String>,
+    /// In ms
+    pub created_at: i64,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SafeDeploymentStatus {
+    PendingDeployment,
+}
+
+/// Safe info of a [CounterfactualSafe] until the transaction service indexed the Safe
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSafeInfo {
+    pub address: AddressEx,
+    pub chain_id: String,
+    pub nonce: u64,
+    pub threshold: u64,
+    pub owners: Vec<AddressEx>,
+    pub status: SafeDeploymentStatus,
+    pub salt_nonce: String,
+    pub factory_address: Option<AddressEx>,
+    pub master_copy: Option<AddressEx>,
+    pub fallback_handler: Option<AddressEx>,
+    pub created_at: i64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum SafeInfoResponse {
+    Deployed(SafeState),
+    PendingDeployment(PendingSafeInfo),
+}
+
+/// What the home screen of a Safe shows, see `/v1/chains/<chain_id>/safes/<safe_address>/dashboard`
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeDashboard {
+    pub safe: SafeInfoResponse,
+    /// Aggregated fiat balance of all the balances of the Safe
+    pub fiat_total: String,
+    /// Largest balances by fiat balance
+    pub balances: Vec<Balance>,
+    /// Transactions awaiting confirmation or execution
+    pub queued: u64,
+    /// Latest history transactions, without their date labels
+    pub history: Vec<TransactionListItem>,
+}
+
+impl Validate for CounterfactualSafeRequest {
+    fn validate(&self, validator: &mut Validator) {
+        validator.address("address", &self.address);
+        validate_setup(validator, &self.owners, self.threshold, &self.salt_nonce);
+        validator
+            .optional_address("factoryAddress", &self.factory_address)
+            .optional_address("masterCopy", &self.master_copy)
+            .optional_address("fallbackHandler", &self.fallback_handler);
+    }
+}
+
+/// <summary>Example body of SafeCreationRequest</summary>
+///
+/// ```json
+/// {
+///   "owners": ["0x1230B3d59858296A31053C1b8562Ecf89A2f888b"],
+///   "threshold": 1,
+///   "saltNonce": "1652283768542",
+///   "relay": true
+/// }
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeCreationRequest {
+    pub owners: Vec<String>,
+    pub threshold: u64,
+    pub salt_nonce: String,
+    /// Defaults to the canonical v1.3.0 deployments
+    pub factory_address: Option<String>,
+    pub master_copy: Option<String>,
+    pub fallback_handler: Option<String>,
+    /// Submits the deployment to the relay service instead of returning it for the client to
+    /// submit
+    #[serde(default)]
+    pub relay: bool,
+}
+
+impl Validate for SafeCreationRequest {
+    fn validate(&self, validator: &mut Validator) {
+        validate_setup(validator, &self.owners, self.threshold, &self.salt_nonce);
+        validator
+            .optional_address("factoryAddress", &self.factory_address)
+            .optional_address("masterCopy", &self.master_copy)
+            .optional_address("fallbackHandler", &self.fallback_handler);
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeCreationTransaction {
+    /// Proxy factory
+    pub to: String,
+    /// `createProxyWithNonce` call data
+    pub data: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeCreationResponse {
+    /// Predicted address of the Safe
+    pub safe_address: String,
+    pub transaction: SafeCreationTransaction,
+    /// Relay task of the deployment, set if it was relayed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+}
+
+fn validate_setup(validator: &mut Validator, owners: &[String], threshold: u64, salt_nonce: &str) {
+    validator
+        .check("owners", !owners.is_empty(), "must not be empty")
+        .range("threshold", threshold, 1, owners.len().max(1) as u64)
+        .uint("saltNonce", salt_nonce);
+    for owner in owners.iter() {
+        validator.address("owners", owner);
+    }
+    let mut unique_owners: Vec<String> = owners.iter().map(|owner| owner.to_lowercase()).collect();
+    unique_owners.sort();
+    unique_owners.dedup();
+    validator.check(
+        "owners",
+        unique_owners.len() == owners.len(),
+        "must not contain duplicates",
+    );
+}
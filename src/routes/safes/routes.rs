@@ -1,10 +1,21 @@
 use crate::cache::cache_operations::CacheResponse;
 use crate::config::owners_for_safes_cache_duration;
+use crate::monitoring::audit::{self, AuditOperation, Caller};
+use crate::routes::safes::handlers::allowances::get_allowances;
+use crate::routes::safes::handlers::counterfactual;
+use crate::routes::safes::handlers::creation;
+use crate::routes::safes::handlers::dashboard;
 use crate::routes::safes::handlers::estimations;
-use crate::routes::safes::handlers::safes::{get_owners_for_safe, get_safe_info_ex};
-use crate::routes::safes::models::SafeTransactionEstimationRequest;
+use crate::routes::safes::handlers::labels;
+use crate::routes::safes::handlers::recipients::get_recent_recipients;
+use crate::routes::safes::models::{
+    CounterfactualSafeRequest, SafeCreationRequest, SafeLabelRequest,
+    SafeTransactionEstimationRequest,
+};
 use crate::utils::context::RequestContext;
+use crate::utils::device::Device;
 use crate::utils::errors::ApiResult;
+use crate::utils::read_only;
 use rocket::response::content;
 use rocket::serde::json::Error;
 use rocket::serde::json::Json;
@@ -12,6 +23,8 @@ use rocket::serde::json::Json;
 /**
  * `/v1/chains/<chain_id>/safes/<safe_address>` <br />
  * Returns [SafeState](crate::models::handlers::safes::SafeState)
+ *
+ * Safes registered with `POST /v1/chains/<chain_id>/safes/counterfactual` are returned as [PendingSafeInfo](crate::routes::safes::models::PendingSafeInfo), with `status: PENDING_DEPLOYMENT`, until the transaction service indexed them
  */
 #[get("/v1/chains/<chain_id>/safes/<safe_address>")]
 pub async fn get_safe_info(
@@ -20,7 +33,37 @@ pub async fn get_safe_info(
     safe_address: String,
 ) -> ApiResult<content::Json<String>> {
     CacheResponse::new(&context)
-        .resp_generator(|| get_safe_info_ex(&context, &chain_id, &safe_address))
+        .resp_generator(|| {
+            counterfactual::get_safe_info_or_pending(&context, &chain_id, &safe_address)
+        })
+        .execute()
+        .await
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/dashboard?<fiat>` <br />
+ * Returns [SafeDashboard](crate::routes::safes::models::SafeDashboard)
+ *
+ * # Safe dashboard
+ *
+ * Everything the home screen of a Safe shows in one response, requested concurrently: the Safe info (as returned by `/v1/chains/<chain_id>/safes/<safe_address>`), the `DASHBOARD_BALANCES_SIZE` largest balances with the fiat total of all balances, the amount of queued transactions and the `DASHBOARD_HISTORY_SIZE` latest history transactions.
+ *
+ * Balances are requested with the defaults of the balances endpoint, untrusted tokens included and spam tokens excluded. Counterfactual Safes have no balances nor transactions yet.
+ *
+ * ## Query parameters
+ *
+ * - `<fiat>`: fiat code of the balances, `USD` by default
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/dashboard?<fiat>")]
+pub async fn get_safe_dashboard(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    fiat: Option<String>,
+) -> ApiResult<content::Json<String>> {
+    let fiat = fiat.unwrap_or_else(|| String::from("USD"));
+    CacheResponse::new(&context)
+        .resp_generator(|| dashboard::get_safe_dashboard(&context, &chain_id, &safe_address, &fiat))
         .execute()
         .await
 }
@@ -29,7 +72,7 @@ pub async fn get_safe_info(
  * `/v1/chains/<chain_id>/owners/<safe_address>/safes` <br/>
  * Returns [Vec] of [String]
  *
- * Returns a list of Safes for which the address is an owner
+ * Returns a list of Safes for which the address is an owner, followed by the counterfactual Safes it owns that are not indexed yet
  */
 #[get("/v1/chains/<chain_id>/owners/<owner_address>/safes")]
 pub async fn get_owners(
@@ -38,12 +81,50 @@ pub async fn get_owners(
     owner_address: String,
 ) -> ApiResult<content::Json<String>> {
     CacheResponse::new(&context)
-        .resp_generator(|| get_owners_for_safe(&context, &chain_id, &owner_address))
+        .resp_generator(|| {
+            counterfactual::get_owners_with_pending(&context, &chain_id, &owner_address)
+        })
         .duration(owners_for_safes_cache_duration())
         .execute()
         .await
 }
 
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/recent-recipients` <br/>
+ * Returns [Vec] of [RecentRecipient](crate::routes::safes::models::RecentRecipient)
+ *
+ * Returns the most recent unique recipients of outgoing transfers of the Safe, newest first
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/recent-recipients")]
+pub async fn get_safe_recent_recipients(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .resp_generator(|| get_recent_recipients(&context, &chain_id, &safe_address))
+        .execute()
+        .await
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/allowances` <br/>
+ * Returns [Vec] of [TokenAllowance](crate::routes::safes::models::TokenAllowance)
+ *
+ * Returns the token approvals granted by recent transactions of the Safe that still have a non zero allowance on chain, latest approval first
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/allowances")]
+pub async fn get_safe_allowances(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .resp_generator(|| get_allowances(&context, &chain_id, &safe_address))
+        .execute()
+        .await
+}
+
 /**
  * `/v1/chains/<chain_id>/safes/<safe_address>/multisig-transactions/estimations` <br />
  * Returns [SafeTransactionEstimation](crate::models::handlers::utils::SafeTransactionEstimation)
@@ -100,3 +181,173 @@ pub async fn post_safe_gas_estimation<'e>(
         .await?,
     )?))
 }
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/label` <br />
+ * Returns [Vec] of [SafeLabel](crate::routes::safes::models::SafeLabel), all labels of the device
+ *
+ * # Safe Label
+ *
+ * Stores a name for the Safe on behalf of a device, so it is shared by every installation that uses the same device credentials. Requests need the `Safe-Device-Uuid` and `Safe-Device-Token` headers; the token is bound to the uuid the first time it is used.
+ *
+ * An empty `label` removes the label of the Safe.
+ *
+ * ## Path
+ *
+ * `PUT /v1/chains/<chain_id>/safes/<safe_address>/label`
+ *
+ * Example request body:
+ *
+ * ```json
+ * {
+ *   "label": "Treasury"
+ * }
+ * ```
+ */
+#[put(
+    "/v1/chains/<chain_id>/safes/<safe_address>/label",
+    format = "application/json",
+    data = "<safe_label_request>"
+)]
+pub async fn put_safe_label<'e>(
+    context: RequestContext,
+    caller: Caller,
+    device: Device,
+    chain_id: String,
+    safe_address: String,
+    safe_label_request: Result<Json<SafeLabelRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    read_only::ensure_writable(&context).await?;
+    let safe_label_request = safe_label_request?.0;
+    let result = labels::set_safe_label(
+        &context,
+        &device,
+        &chain_id,
+        &safe_address,
+        &safe_label_request,
+    )
+    .await;
+    audit::record(
+        AuditOperation::SetSafeLabel,
+        &safe_address,
+        &caller,
+        audit::payload_hash(&safe_label_request),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/v1/safes/labels` <br />
+ * Returns [Vec] of [SafeLabel](crate::routes::safes::models::SafeLabel)
+ *
+ * Labels stored for the device across all chains, authenticated with the same headers as `PUT /v1/chains/<chain_id>/safes/<safe_address>/label`
+ */
+#[get("/v1/safes/labels")]
+pub async fn get_safe_labels(
+    context: RequestContext,
+    device: Device,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &labels::get_safe_labels(&context, &device).await?,
+    )?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/counterfactual` <br />
+ * Returns [CounterfactualSafe](crate::routes::safes::models::CounterfactualSafe)
+ *
+ * # Counterfactual Safe
+ *
+ * Registers a Safe that is not deployed yet with its predicted address and setup. Until the transaction service indexed the Safe, `GET /v1/chains/<chain_id>/safes/<safe_address>` returns it with `status: PENDING_DEPLOYMENT` and it is listed for its owners. Registering the same address again replaces the stored setup.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/safes/counterfactual`
+ *
+ * Example request body:
+ *
+ * ```json
+ * {
+ *   "address": "0x4B0D3Ad2C2bF6a1a5c9C39BfC2A5E5C7d5f79B3E",
+ *   "owners": ["0x1230B3d59858296A31053C1b8562Ecf89A2f888b"],
+ *   "threshold": 1,
+ *   "saltNonce": "1652283768542",
+ *   "factoryAddress": "0xa6B71E26C5e0845f74c812102Ca7114b6a896AB2",
+ *   "masterCopy": "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552",
+ *   "fallbackHandler": "0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4"
+ * }
+ * ```
+ */
+#[post(
+    "/v1/chains/<chain_id>/safes/counterfactual",
+    format = "application/json",
+    data = "<counterfactual_safe_request>"
+)]
+pub async fn post_counterfactual_safe<'e>(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    counterfactual_safe_request: Result<Json<CounterfactualSafeRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    read_only::ensure_writable(&context).await?;
+    let counterfactual_safe_request = counterfactual_safe_request?.0;
+    let result = counterfactual::register_counterfactual_safe(
+        &context,
+        &chain_id,
+        &counterfactual_safe_request,
+    )
+    .await;
+    audit::record(
+        AuditOperation::RegisterCounterfactualSafe,
+        &counterfactual_safe_request.address,
+        &caller,
+        audit::payload_hash(&counterfactual_safe_request),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/create` <br />
+ * Returns [SafeCreationResponse](crate::routes::safes::models::SafeCreationResponse)
+ *
+ * # Safe creation
+ *
+ * Builds the proxy factory transaction deploying a Safe with the given owners, threshold and salt nonce, and the address the Safe will be deployed at. Factory, master copy and fallback handler default to the canonical v1.3.0 deployments (the L2 master copy on L2 chains).
+ *
+ * Without `relay` the transaction is returned for the client to submit. With `"relay": true` it is submitted to the relayer configured with `RELAY_SERVICE_URI` instead, the response contains its `taskId` and the deployment can be followed with `GET /v1/chains/<chain_id>/relay/<task_id>`. Relayed deployments count against the relay quota of the predicted Safe, which is registered as counterfactual Safe until the transaction service indexed it.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/safes/create`
+ *
+ * The expected [crate::routes::safes::models::SafeCreationRequest] body for this request can be found in the sections of the models
+ */
+#[post(
+    "/v1/chains/<chain_id>/safes/create",
+    format = "application/json",
+    data = "<safe_creation_request>"
+)]
+pub async fn post_safe_creation<'e>(
+    context: RequestContext,
+    caller: Caller,
+    chain_id: String,
+    safe_creation_request: Result<Json<SafeCreationRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    read_only::ensure_writable(&context).await?;
+    let safe_creation_request = safe_creation_request?.0;
+    let result = creation::create_safe(&context, &chain_id, &safe_creation_request).await;
+    let target = result
+        .as_ref()
+        .map(|response| response.safe_address.to_string())
+        .unwrap_or_default();
+    audit::record(
+        AuditOperation::CreateSafe,
+        &target,
+        &caller,
+        audit::payload_hash(&safe_creation_request),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
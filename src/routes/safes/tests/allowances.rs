@@ -0,0 +1,139 @@
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::common::models::data_decoded::DataDecoded;
+use crate::routes::safes::handlers::allowances::{
+    allowance_call_data, collect_approvals, decode_allowance, Approval,
+};
+use chrono::{TimeZone, Utc};
+use ethabi::Uint;
+
+fn approve_transaction() -> MultisigTransaction {
+    serde_json::from_str(crate::tests::json::MULTISIG_TX_CUSTOM).unwrap()
+}
+
+#[test]
+fn collect_approvals_unique_newest_first() {
+    let mut older_transaction = approve_transaction();
+    older_transaction.execution_date = Some(Utc.ymd(2020, 6, 1).and_hms(12, 0, 0));
+
+    let actual = collect_approvals(&[approve_transaction(), older_transaction]);
+
+    let expected = vec![Approval {
+        token: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+        spender: "0xae9844F89D98c150F5e61bfC676D68b492155990".to_string(),
+        execution_date: Some(Utc.ymd(2020, 6, 22).and_hms(18, 0, 54)),
+    }];
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn collect_approvals_within_multi_send() {
+    let mut multi_send_transaction = approve_transaction();
+    multi_send_transaction.safe_transaction.to =
+        "0x8D29bE29923b68abfDD21e541b9374737B49cdAD".to_string();
+    multi_send_transaction.safe_transaction.data_decoded = Some(
+        serde_json::from_str::<DataDecoded>(
+            r#"{
+            "method": "multiSend",
+            "parameters": [
+                {
+                    "name": "transactions",
+                    "type": "bytes",
+                    "value": "0x00",
+                    "valueDecoded": [
+                        {
+                            "operation": 0,
+                            "to": "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88",
+                            "value": "0",
+                            "data": "0x",
+                            "dataDecoded": {
+                                "method": "approve",
+                                "parameters": [
+                                    {
+                                        "name": "spender",
+                                        "type": "address",
+                                        "value": "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23"
+                                    },
+                                    {
+                                        "name": "value",
+                                        "type": "uint256",
+                                        "value": "1000"
+                                    }
+                                ]
+                            }
+                        },
+                        {
+                            "operation": 0,
+                            "to": "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88",
+                            "value": "0",
+                            "data": "0x",
+                            "dataDecoded": {
+                                "method": "transfer",
+                                "parameters": [
+                                    {
+                                        "name": "to",
+                                        "type": "address",
+                                        "value": "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23"
+                                    },
+                                    {
+                                        "name": "value",
+                                        "type": "uint256",
+                                        "value": "1000"
+                                    }
+                                ]
+                            }
+                        }
+                    ]
+                }
+            ]
+        }"#,
+        )
+        .unwrap(),
+    );
+
+    let actual: Vec<(String, String)> =
+        collect_approvals(&[multi_send_transaction, approve_transaction()])
+            .into_iter()
+            .map(|approval| (approval.token, approval.spender))
+            .collect();
+
+    let expected = vec![
+        (
+            "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88".to_string(),
+            "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
+        ),
+        (
+            "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
+            "0xae9844F89D98c150F5e61bfC676D68b492155990".to_string(),
+        ),
+    ];
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn allowance_call_data_encodes_owner_and_spender() {
+    let actual = allowance_call_data(
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        "0xae9844F89D98c150F5e61bfC676D68b492155990",
+    )
+    .unwrap();
+
+    assert_eq!(
+        "0xdd62ed3e\
+        0000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b\
+        000000000000000000000000ae9844f89d98c150f5e61bfc676d68b492155990",
+        actual
+    );
+}
+
+#[test]
+fn decode_allowance_uint() {
+    let actual =
+        decode_allowance("0x0000000000000000000000000000000000000000000000000001c6bf52634000");
+
+    assert_eq!(Some(Uint::from(500000000000000u64)), actual);
+}
+
+#[test]
+fn decode_allowance_empty_result() {
+    assert_eq!(None, decode_allowance("0x"));
+}
@@ -0,0 +1,142 @@
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::page::SafeList;
+use crate::routes::safes::handlers::counterfactual::{
+    counterfactual_safe, pending_safe_info, with_pending_safes,
+};
+use crate::routes::safes::models::{
+    CounterfactualSafeRequest, PendingSafeInfo, SafeDeploymentStatus,
+};
+use crate::utils::validation::Validate;
+use serde_json::json;
+use std::collections::HashMap;
+
+const SAFE: &str = "0x4B0D3Ad2C2bF6a1a5c9C39BfC2A5E5C7d5f79B3E";
+const OWNER: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const OTHER_OWNER: &str = "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd";
+
+fn request(owners: Vec<&str>, threshold: u64) -> CounterfactualSafeRequest {
+    CounterfactualSafeRequest {
+        address: SAFE.to_string(),
+        owners: owners.into_iter().map(String::from).collect(),
+        threshold,
+        salt_nonce: String::from("1652283768542"),
+        factory_address: Some(String::from("0xa6B71E26C5e0845f74c812102Ca7114b6a896AB2")),
+        master_copy: None,
+        fallback_handler: None,
+    }
+}
+
+fn stored(requests: Vec<(&str, CounterfactualSafeRequest, i64)>) -> HashMap<String, String> {
+    requests
+        .into_iter()
+        .map(|(address, mut request, created_at)| {
+            request.address = address.to_string();
+            let safe = counterfactual_safe("4", &request, created_at);
+            (
+                address.to_lowercase(),
+                serde_json::to_string(&safe).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn counterfactual_safe_request_valid() {
+    assert!(request(vec![OWNER, OTHER_OWNER], 2).validated().is_ok());
+}
+
+#[test]
+fn counterfactual_safe_request_reports_invalid_setup() {
+    let error = request(vec![OWNER, &OWNER.to_lowercase(), "0x12"], 4)
+        .validated()
+        .unwrap_err();
+
+    assert_eq!(error.status, 422);
+    assert_eq!(
+        error.details.arguments.unwrap(),
+        vec![
+            String::from("threshold: must be between 1 and 3"),
+            String::from("owners: must be a 0x prefixed address"),
+            String::from("owners: must not contain duplicates"),
+        ]
+    );
+}
+
+#[test]
+fn counterfactual_safe_request_requires_owners() {
+    let arguments = request(vec![], 1)
+        .validated()
+        .unwrap_err()
+        .details
+        .arguments
+        .unwrap();
+
+    assert_eq!(arguments, vec![String::from("owners: must not be empty")]);
+}
+
+#[test]
+fn pending_safe_info_serializes_status() {
+    let safe = counterfactual_safe("4", &request(vec![OWNER], 1), 1652283768542);
+
+    let expected = PendingSafeInfo {
+        address: AddressEx::address_only(SAFE),
+        chain_id: String::from("4"),
+        nonce: 0,
+        threshold: 1,
+        owners: vec![AddressEx::address_only(OWNER)],
+        status: SafeDeploymentStatus::PendingDeployment,
+        salt_nonce: String::from("1652283768542"),
+        factory_address: Some(AddressEx::address_only(
+            "0xa6B71E26C5e0845f74c812102Ca7114b6a896AB2",
+        )),
+        master_copy: None,
+        fallback_handler: None,
+        created_at: 1652283768542,
+    };
+    let actual = pending_safe_info(&safe);
+
+    assert_eq!(actual, expected);
+    let json = serde_json::to_value(&actual).unwrap();
+    assert_eq!(json["status"], json!("PENDING_DEPLOYMENT"));
+    assert_eq!(json["saltNonce"], json!("1652283768542"));
+}
+
+#[test]
+fn with_pending_safes_appends_safes_of_owner() {
+    let other_safe = "0x9A5d4F3E5C4B8A1C5f11D7e1c2C3D4A5B6c7D8E9";
+    let indexed = SafeList {
+        safes: vec![String::from("0x8f7C1BCdD19D0E2Ed3d2a0e0E8b6E9d6F1A7e5C3")],
+    };
+    let stored = stored(vec![
+        (other_safe, request(vec![OWNER], 1), 2),
+        (SAFE, request(vec![OWNER], 1), 1),
+        (
+            "0x6C4b1CDa2b0A7a1E8b8E8F4E4C0d5D3A2f1e0d9C",
+            request(vec![OTHER_OWNER], 1),
+            3,
+        ),
+    ]);
+
+    let actual = with_pending_safes(indexed, &stored, &OWNER.to_lowercase());
+
+    assert_eq!(
+        actual.safes,
+        vec![
+            String::from("0x8f7C1BCdD19D0E2Ed3d2a0e0E8b6E9d6F1A7e5C3"),
+            String::from(SAFE),
+            String::from(other_safe),
+        ]
+    );
+}
+
+#[test]
+fn with_pending_safes_skips_indexed_safes() {
+    let indexed = SafeList {
+        safes: vec![SAFE.to_lowercase()],
+    };
+    let stored = stored(vec![(SAFE, request(vec![OWNER], 1), 1)]);
+
+    let actual = with_pending_safes(indexed, &stored, OWNER);
+
+    assert_eq!(actual.safes, vec![SAFE.to_lowercase()]);
+}
@@ -0,0 +1,221 @@
+use crate::routes::safes::handlers::creation::{
+    create_proxy_data, decode_creation_code, predicted_safe_address, safe_deployment, setup_data,
+    SafeDeployment, DEFAULT_FALLBACK_HANDLER, DEFAULT_MASTER_COPY, DEFAULT_MASTER_COPY_L2,
+    DEFAULT_PROXY_FACTORY,
+};
+use crate::routes::safes::models::{
+    SafeCreationRequest, SafeCreationResponse, SafeCreationTransaction,
+};
+use crate::utils::validation::Validate;
+use ethabi::Uint;
+use serde_json::json;
+
+const OWNER: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const SALT_NONCE: u64 = 1652283768542;
+const CREATION_CODE: &str = "0x608060405234801561001057600080fd5b50";
+
+fn request(owners: Vec<&str>, threshold: u64) -> SafeCreationRequest {
+    SafeCreationRequest {
+        owners: owners.into_iter().map(String::from).collect(),
+        threshold,
+        salt_nonce: SALT_NONCE.to_string(),
+        factory_address: None,
+        master_copy: None,
+        fallback_handler: None,
+        relay: false,
+    }
+}
+
+fn default_deployment() -> SafeDeployment {
+    safe_deployment(&request(vec![OWNER], 1), false)
+}
+
+fn expected_initializer() -> String {
+    [
+        "0xb63e800d",
+        "0000000000000000000000000000000000000000000000000000000000000100",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000140",
+        "000000000000000000000000f48f2b2d2a534e402487b3ee7c18c33aec0fe5e4",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    ]
+    .concat()
+}
+
+#[test]
+fn safe_creation_request_validation() {
+    assert!(request(vec![OWNER], 1).validated().is_ok());
+
+    let mut invalid = request(vec![OWNER, &OWNER.to_lowercase()], 3);
+    invalid.salt_nonce = String::from("-1");
+    invalid.master_copy = Some(String::from("0x12"));
+    let arguments = invalid.validated().unwrap_err().details.arguments.unwrap();
+
+    assert_eq!(
+        arguments,
+        vec![
+            String::from("threshold: must be between 1 and 2"),
+            String::from("saltNonce: must be an unsigned integer"),
+            String::from("owners: must not contain duplicates"),
+            String::from("masterCopy: must be a 0x prefixed address"),
+        ]
+    );
+}
+
+#[test]
+fn safe_creation_request_relay_defaults_to_false() {
+    let request: SafeCreationRequest = serde_json::from_value(json!({
+        "owners": [OWNER],
+        "threshold": 1,
+        "saltNonce": "0"
+    }))
+    .unwrap();
+
+    assert!(!request.relay);
+}
+
+#[test]
+fn safe_deployment_defaults_to_canonical_contracts() {
+    let l2_deployment = safe_deployment(&request(vec![OWNER], 1), true);
+
+    assert_eq!(
+        default_deployment(),
+        SafeDeployment {
+            factory_address: DEFAULT_PROXY_FACTORY.to_string(),
+            master_copy: DEFAULT_MASTER_COPY.to_string(),
+            fallback_handler: DEFAULT_FALLBACK_HANDLER.to_string(),
+        }
+    );
+    assert_eq!(l2_deployment.master_copy, DEFAULT_MASTER_COPY_L2);
+}
+
+#[test]
+fn safe_deployment_uses_requested_contracts() {
+    let mut request = request(vec![OWNER], 1);
+    request.factory_address = Some(String::from("0x12302fe9c02ff50939baaaaf415fc226c078613c"));
+    request.master_copy = Some(String::from("0x6851d6fdfafd08c0295c392436245e5bc78b0185"));
+
+    let actual = safe_deployment(&request, true);
+
+    assert_eq!(
+        actual,
+        SafeDeployment {
+            factory_address: String::from("0x12302fE9c02ff50939BaAaaf415fc226C078613C"),
+            master_copy: String::from("0x6851D6fDFAfD08c0295C392436245E5bc78B0185"),
+            fallback_handler: DEFAULT_FALLBACK_HANDLER.to_string(),
+        }
+    );
+}
+
+#[test]
+fn setup_data_without_modules_and_payment() {
+    let actual = setup_data(&[OWNER.to_string()], 1, &default_deployment()).unwrap();
+
+    assert_eq!(to_hex_string!(actual), expected_initializer());
+}
+
+#[test]
+fn create_proxy_data_encodes_master_copy_initializer_and_nonce() {
+    let initializer = setup_data(&[OWNER.to_string()], 1, &default_deployment()).unwrap();
+
+    let actual =
+        create_proxy_data(DEFAULT_MASTER_COPY, &initializer, &Uint::from(SALT_NONCE)).unwrap();
+
+    assert!(actual.starts_with(
+        &[
+            "0x1688f0b9",
+            "000000000000000000000000d9db270c1b5e3bd161e8c8503c55ceabee709552",
+            "0000000000000000000000000000000000000000000000000000000000000060",
+            "00000000000000000000000000000000000000000000000000000180b3c97ede",
+            "0000000000000000000000000000000000000000000000000000000000000164",
+        ]
+        .concat()
+    ));
+    assert!(actual.contains(expected_initializer().trim_start_matches("0x")));
+    assert_eq!(actual.len(), 2 + 2 * 516);
+}
+
+#[test]
+fn predicted_safe_address_create2() {
+    let initializer = setup_data(&[OWNER.to_string()], 1, &default_deployment()).unwrap();
+    let creation_code = decode_hex_str(CREATION_CODE);
+
+    let actual = predicted_safe_address(
+        DEFAULT_PROXY_FACTORY,
+        DEFAULT_MASTER_COPY,
+        &initializer,
+        &Uint::from(SALT_NONCE),
+        &creation_code,
+    )
+    .unwrap();
+    let other_nonce = predicted_safe_address(
+        DEFAULT_PROXY_FACTORY,
+        DEFAULT_MASTER_COPY,
+        &initializer,
+        &Uint::from(SALT_NONCE + 1),
+        &creation_code,
+    )
+    .unwrap();
+
+    assert_eq!(actual, "0xB99d51D3144B84c5765621A11c89CABcFCf6bf44");
+    assert_ne!(actual, other_nonce);
+}
+
+#[test]
+fn decode_creation_code_reads_bytes() {
+    let result = [
+        "0x",
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "0000000000000000000000000000000000000000000000000000000000000012",
+        "608060405234801561001057600080fd5b500000000000000000000000000000",
+    ]
+    .concat();
+    let empty = [
+        "0x",
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    ]
+    .concat();
+
+    assert_eq!(
+        decode_creation_code(&result),
+        Some(decode_hex_str(CREATION_CODE))
+    );
+    assert_eq!(decode_creation_code(&empty), None);
+    assert_eq!(decode_creation_code("0x"), None);
+}
+
+#[test]
+fn safe_creation_response_omits_task_id_of_unrelayed_deployments() {
+    let response = SafeCreationResponse {
+        safe_address: String::from("0xB99d51D3144B84c5765621A11c89CABcFCf6bf44"),
+        transaction: SafeCreationTransaction {
+            to: DEFAULT_PROXY_FACTORY.to_string(),
+            data: String::from("0x1688f0b9"),
+            value: String::from("0"),
+        },
+        task_id: None,
+    };
+
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({
+            "safeAddress": "0xB99d51D3144B84c5765621A11c89CABcFCf6bf44",
+            "transaction": {
+                "to": DEFAULT_PROXY_FACTORY,
+                "data": "0x1688f0b9",
+                "value": "0"
+            }
+        })
+    );
+}
+
+fn decode_hex_str(value: &str) -> Vec<u8> {
+    crate::utils::transactions::decode_hex(value).unwrap()
+}
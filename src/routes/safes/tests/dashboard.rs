@@ -0,0 +1,87 @@
+use crate::providers::info::{TokenInfo, TokenType};
+use crate::routes::balances::models::{Balance, Balances};
+use crate::routes::safes::handlers::counterfactual::pending_safe_info;
+use crate::routes::safes::handlers::dashboard::safe_dashboard;
+use crate::routes::safes::models::{CounterfactualSafe, SafeInfoResponse};
+use crate::routes::transactions::models::summary::{ConflictType, TransactionListItem};
+use crate::testing::builders::TransactionSummaryBuilder;
+
+const SAFE: &str = "0x4B0D3Ad2C2bF6a1a5c9C39BfC2A5E5C7d5f79B3E";
+
+fn safe() -> SafeInfoResponse {
+    SafeInfoResponse::PendingDeployment(pending_safe_info(&CounterfactualSafe {
+        address: SAFE.to_string(),
+        chain_id: String::from("4"),
+        owners: vec![String::from("0x1230B3d59858296A31053C1b8562Ecf89A2f888b")],
+        threshold: 1,
+        salt_nonce: String::from("1652283768542"),
+        factory_address: None,
+        master_copy: None,
+        fallback_handler: None,
+        created_at: 0,
+    }))
+}
+
+fn balance(symbol: &str, fiat_balance: &str) -> Balance {
+    Balance {
+        token_info: TokenInfo {
+            token_type: TokenType::Erc20,
+            address: String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+            decimals: 18,
+            symbol: String::from(symbol),
+            name: String::from(symbol),
+            logo_uri: None,
+        },
+        balance: String::from("1"),
+        fiat_balance: String::from(fiat_balance),
+        fiat_conversion: String::from(fiat_balance),
+        spam: false,
+    }
+}
+
+fn transaction(id: &str) -> TransactionListItem {
+    TransactionListItem::Transaction {
+        transaction: TransactionSummaryBuilder::new(id).build(),
+        conflict_type: ConflictType::None,
+        executability: None,
+    }
+}
+
+#[test]
+fn safe_dashboard_keeps_largest_balances_and_fiat_total() {
+    let balances = Balances {
+        fiat_total: String::from("60"),
+        items: vec![balance("A", "30"), balance("B", "20"), balance("C", "10")],
+    };
+
+    let actual = safe_dashboard(safe(), balances, 4, vec![], 2, 3);
+
+    assert_eq!(actual.fiat_total, "60");
+    assert_eq!(
+        actual.balances,
+        vec![balance("A", "30"), balance("B", "20")]
+    );
+    assert_eq!(actual.queued, 4);
+}
+
+#[test]
+fn safe_dashboard_keeps_latest_transactions_without_date_labels() {
+    let history = vec![
+        TransactionListItem::DateLabel { timestamp: 2 },
+        transaction("multisig_2"),
+        transaction("multisig_1"),
+        TransactionListItem::DateLabel { timestamp: 1 },
+        transaction("multisig_0"),
+    ];
+    let balances = Balances {
+        fiat_total: String::from("0"),
+        items: vec![],
+    };
+
+    let actual = safe_dashboard(safe(), balances, 0, history, 5, 2);
+
+    assert_eq!(
+        actual.history,
+        vec![transaction("multisig_2"), transaction("multisig_1")]
+    );
+}
@@ -0,0 +1,55 @@
+use crate::routes::safes::handlers::labels::update_labels;
+use crate::routes::safes::models::SafeLabel;
+
+fn safe_label(chain_id: &str, address: &str, label: &str) -> SafeLabel {
+    SafeLabel {
+        chain_id: chain_id.to_string(),
+        address: address.to_string(),
+        label: label.to_string(),
+    }
+}
+
+#[test]
+fn update_labels_replaces_label_of_same_safe_and_chain() {
+    let mut labels = vec![
+        safe_label(
+            "1",
+            "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+            "Treasury",
+        ),
+        safe_label("4", "0x1230B3d59858296A31053C1b8562Ecf89A2f888b", "Testing"),
+    ];
+
+    update_labels(
+        &mut labels,
+        "1",
+        "0x1230b3d59858296a31053c1b8562ecf89a2f888b",
+        "Payroll",
+    );
+
+    assert_eq!(
+        labels,
+        vec![
+            safe_label("4", "0x1230B3d59858296A31053C1b8562Ecf89A2f888b", "Testing"),
+            safe_label("1", "0x1230b3d59858296a31053c1b8562ecf89a2f888b", "Payroll"),
+        ]
+    );
+}
+
+#[test]
+fn update_labels_empty_label_removes() {
+    let mut labels = vec![safe_label(
+        "1",
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        "Treasury",
+    )];
+
+    update_labels(
+        &mut labels,
+        "1",
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        "",
+    );
+
+    assert!(labels.is_empty());
+}
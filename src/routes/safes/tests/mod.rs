@@ -0,0 +1,6 @@
+mod allowances;
+mod counterfactual;
+mod creation;
+mod dashboard;
+mod labels;
+mod recipients;
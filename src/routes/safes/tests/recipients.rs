@@ -0,0 +1,88 @@
+use crate::common::models::backend::transfers::Transfer;
+use crate::routes::safes::handlers::recipients::collect_recent_recipients;
+
+const SAFE_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+fn transfers() -> Vec<Transfer> {
+    serde_json::from_str(
+        r#"[
+        {
+            "type": "ETHER_TRANSFER",
+            "executionDate": "2021-10-05T12:00:00Z",
+            "blockNumber": 3,
+            "transactionHash": "0x3",
+            "to": "0xF353eBBa77e5E71c210599236686D51cA1F88b84",
+            "value": "1000",
+            "from": "0x1230b3d59858296a31053c1b8562ecf89a2f888b"
+        },
+        {
+            "type": "ERC20_TRANSFER",
+            "executionDate": "2021-10-04T12:00:00Z",
+            "blockNumber": 2,
+            "transactionHash": "0x2",
+            "to": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+            "value": "1000",
+            "tokenAddress": "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88",
+            "tokenInfo": null,
+            "from": "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23"
+        },
+        {
+            "type": "ERC721_TRANSFER",
+            "executionDate": "2021-10-03T12:00:00Z",
+            "blockNumber": 1,
+            "transactionHash": "0x1",
+            "to": "0xf353ebba77e5e71c210599236686d51ca1f88b84",
+            "tokenId": "1",
+            "tokenAddress": "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88",
+            "tokenInfo": null,
+            "from": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b"
+        },
+        {
+            "type": "ERC20_TRANSFER",
+            "executionDate": "2021-10-02T12:00:00Z",
+            "blockNumber": 0,
+            "transactionHash": "0x0",
+            "to": "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23",
+            "value": "1000",
+            "tokenAddress": "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88",
+            "tokenInfo": null,
+            "from": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b"
+        }
+    ]"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn collect_recent_recipients_outgoing_unique_newest_first() {
+    let actual: Vec<String> = collect_recent_recipients(&transfers(), SAFE_ADDRESS, 10)
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    assert_eq!(
+        vec![
+            "0xF353eBBa77e5E71c210599236686D51cA1F88b84".to_string(),
+            "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23".to_string(),
+        ],
+        actual
+    );
+}
+
+#[test]
+fn collect_recent_recipients_respects_limit() {
+    let actual = collect_recent_recipients(&transfers(), SAFE_ADDRESS, 1);
+
+    assert_eq!(1, actual.len());
+    assert_eq!(
+        "2021-10-05T12:00:00+00:00",
+        actual.get(0).unwrap().1.to_rfc3339()
+    );
+}
+
+#[test]
+fn collect_recent_recipients_empty() {
+    let actual = collect_recent_recipients(&[], SAFE_ADDRESS, 10);
+
+    assert!(actual.is_empty());
+}
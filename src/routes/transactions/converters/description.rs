@@ -0,0 +1,202 @@
+//! Human-readable summaries of transactions (e.g. "Send 100 USDC to 0xab…12"), generated from
+//! the transaction info so that every client presents the same text. Templates only use the data
+//! already present in the summary, transactions that can't be described have no description.
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::backend::chains::NativeCurrency;
+use crate::config::feature_flag_transaction_descriptions;
+use crate::providers::info::InfoProvider;
+use crate::routes::transactions::models::{
+    Custom, SettingsInfo, TransactionInfo, Transfer, TransferDirection, TransferInfo,
+};
+use bigdecimal::{num_bigint::BigInt, BigDecimal};
+use std::str::FromStr;
+
+/// `owner_count` is the number of owners of the Safe before the transaction is executed, used to
+/// show thresholds as `<threshold>/<owners>`. The native currency is only fetched for the
+/// transactions moving it.
+pub async fn transaction_description(
+    info_provider: &(impl InfoProvider + Sync),
+    tx_info: &TransactionInfo,
+    owner_count: Option<usize>,
+) -> Option<String> {
+    if !feature_flag_transaction_descriptions() {
+        return None;
+    }
+    let native_currency = if uses_native_currency(tx_info) {
+        info_provider
+            .chain_info()
+            .await
+            .ok()
+            .map(|chain_info| chain_info.native_currency)
+    } else {
+        None
+    };
+    describe(tx_info, native_currency.as_ref(), owner_count)
+}
+
+fn uses_native_currency(tx_info: &TransactionInfo) -> bool {
+    matches!(
+        tx_info,
+        TransactionInfo::Transfer(Transfer {
+            transfer_info: TransferInfo::NativeCoin(_),
+            ..
+        })
+    )
+}
+
+pub fn describe(
+    tx_info: &TransactionInfo,
+    native_currency: Option<&NativeCurrency>,
+    owner_count: Option<usize>,
+) -> Option<String> {
+    match tx_info {
+        TransactionInfo::Transfer(transfer) => describe_transfer(transfer, native_currency),
+        TransactionInfo::SettingsChange(settings_change) => match &settings_change.settings_info {
+            Some(settings_info) => Some(describe_settings(settings_info, owner_count)),
+            None => Some(format!("Call {}", settings_change.data_decoded.method)),
+        },
+        TransactionInfo::Custom(custom) => Some(describe_custom(custom)),
+        TransactionInfo::Creation(_) => Some(String::from("Create Safe")),
+        TransactionInfo::Unknown => None,
+    }
+}
+
+fn describe_transfer(
+    transfer: &Transfer,
+    native_currency: Option<&NativeCurrency>,
+) -> Option<String> {
+    let asset = match &transfer.transfer_info {
+        TransferInfo::Erc20(erc20) => {
+            let symbol = erc20
+                .token_symbol
+                .as_ref()
+                .or_else(|| erc20.token_name.as_ref())
+                .map(String::to_string)
+                .unwrap_or_else(|| short_address(&erc20.token_address));
+            match erc20.decimals {
+                Some(decimals) => format!("{} {}", format_amount(&erc20.value, decimals)?, symbol),
+                None => format!("{} {}", erc20.value, symbol),
+            }
+        }
+        TransferInfo::Erc721(erc721) => {
+            let collection = erc721
+                .token_symbol
+                .as_ref()
+                .or_else(|| erc721.token_name.as_ref())
+                .map(String::to_string)
+                .unwrap_or_else(|| short_address(&erc721.token_address));
+            format!("{} #{}", collection, erc721.token_id)
+        }
+        TransferInfo::NativeCoin(native_coin) => {
+            let native_currency = native_currency?;
+            format!(
+                "{} {}",
+                format_amount(&native_coin.value, native_currency.decimals)?,
+                native_currency.symbol
+            )
+        }
+    };
+    let description = match transfer.direction {
+        TransferDirection::Outgoing => {
+            format!("Send {} to {}", asset, display_address(&transfer.recipient))
+        }
+        TransferDirection::Incoming => {
+            format!(
+                "Receive {} from {}",
+                asset,
+                display_address(&transfer.sender)
+            )
+        }
+        TransferDirection::Unknown => format!(
+            "Transfer {} from {} to {}",
+            asset,
+            display_address(&transfer.sender),
+            display_address(&transfer.recipient)
+        ),
+    };
+    Some(description)
+}
+
+fn describe_settings(settings_info: &SettingsInfo, owner_count: Option<usize>) -> String {
+    match settings_info {
+        SettingsInfo::SetFallbackHandler { handler } => {
+            format!("Set fallback handler to {}", display_address(handler))
+        }
+        SettingsInfo::AddOwner { owner, threshold } => format!(
+            "Add owner {} and change threshold to {}",
+            display_address(owner),
+            display_threshold(*threshold, owner_count.map(|count| count + 1))
+        ),
+        SettingsInfo::RemoveOwner { owner, threshold } => format!(
+            "Remove owner {} and change threshold to {}",
+            display_address(owner),
+            display_threshold(*threshold, owner_count.map(|count| count.saturating_sub(1)))
+        ),
+        SettingsInfo::SwapOwner {
+            old_owner,
+            new_owner,
+        } => format!(
+            "Replace owner {} with {}",
+            display_address(old_owner),
+            display_address(new_owner)
+        ),
+        SettingsInfo::ChangeThreshold { threshold } => format!(
+            "Change threshold to {}",
+            display_threshold(*threshold, owner_count)
+        ),
+        SettingsInfo::ChangeImplementation { implementation } => format!(
+            "Change implementation to {}",
+            display_address(implementation)
+        ),
+        SettingsInfo::EnableModule { module } => {
+            format!("Enable module {}", display_address(module))
+        }
+        SettingsInfo::DisableModule { module } => {
+            format!("Disable module {}", display_address(module))
+        }
+    }
+}
+
+fn describe_custom(custom: &Custom) -> String {
+    if custom.is_cancellation {
+        return String::from("Reject transaction");
+    }
+    match (custom.action_count, &custom.method_name) {
+        (Some(1), _) => String::from("Execute 1 action"),
+        (Some(action_count), _) => format!("Execute {} actions", action_count),
+        (None, Some(method_name)) => {
+            format!("Call {} on {}", method_name, display_address(&custom.to))
+        }
+        (None, None) => format!("Interact with {}", display_address(&custom.to)),
+    }
+}
+
+fn display_threshold(threshold: u64, owner_count: Option<usize>) -> String {
+    match owner_count {
+        Some(owner_count) => format!("{}/{}", threshold, owner_count),
+        None => threshold.to_string(),
+    }
+}
+
+fn display_address(address: &AddressEx) -> String {
+    short_address(&address.value)
+}
+
+/// `0xabcd…1234` becomes `0xab…34`, values too short to be shortened are kept as they are
+pub fn short_address(address: &str) -> String {
+    if address.len() <= 8 || !address.is_ascii() {
+        return address.to_string();
+    }
+    format!("{}…{}", &address[..4], &address[address.len() - 2..])
+}
+
+/// `value` in the smallest unit of the token, without trailing zeros
+pub fn format_amount(value: &str, decimals: u64) -> Option<String> {
+    let value = BigInt::from_str(value).ok()?;
+    let amount = BigDecimal::new(value, decimals as i64).normalized();
+    // Normalizing whole amounts can switch to a negative scale, e.g. `1E+2`
+    if amount.as_bigint_and_exponent().1 < 0 {
+        return Some(amount.with_scale(0).to_string());
+    }
+    Some(amount.to_string())
+}
@@ -2,7 +2,7 @@ extern crate chrono;
 
 use crate::common::models::addresses::AddressEx;
 use crate::common::models::backend::transactions::{ModuleTransaction, MultisigTransaction};
-use crate::providers::ext::InfoProviderExt;
+use crate::providers::address_info::{address_ex_index, address_ex_or_default, AddressSource};
 use crate::providers::info::{InfoProvider, SafeInfo, TokenInfo};
 use crate::routes::transactions::converters::safe_app_info::safe_app_info_from;
 use crate::routes::transactions::models::details::{
@@ -22,6 +22,14 @@ impl MultisigTransaction {
             .safe_info(&self.safe_transaction.safe.to_string())
             .await?;
         let gas_token = info_provider.address_to_token_info(&self.gas_token).await;
+        let data_decoded = self.safe_transaction.data_decoded.as_ref();
+        let address_info_index = OptionFuture::from(data_decoded.map(|data_decoded| async move {
+            data_decoded.build_address_info_index(info_provider).await
+        }))
+        .await
+        .flatten();
+        let actions = data_decoded
+            .and_then(|data_decoded| data_decoded.multi_send_actions(address_info_index.as_ref()));
 
         Ok(TransactionDetails {
             tx_id: self.generate_id(),
@@ -34,16 +42,8 @@ impl MultisigTransaction {
                 hex_data: self.safe_transaction.data.to_owned(),
                 data_decoded: self.safe_transaction.data_decoded.clone(),
                 operation: self.safe_transaction.operation,
-                address_info_index: OptionFuture::from(
-                    self.safe_transaction
-                        .data_decoded
-                        .as_ref()
-                        .map(|data_decoded| async move {
-                            data_decoded.build_address_info_index(info_provider).await
-                        }),
-                )
-                .await
-                .flatten(),
+                address_info_index,
+                actions,
             }),
             tx_hash: self.transaction_hash.as_ref().map(|hash| hash.to_owned()),
             detailed_execution_info: Some(DetailedExecutionInfo::Multisig(
@@ -56,6 +56,7 @@ impl MultisigTransaction {
             )
             .await
             .flatten(),
+            logs: None,
         })
     }
 
@@ -113,6 +114,8 @@ impl MultisigTransaction {
                     .map(|rejection| AddressEx::address_only(rejection))
                     .collect()
             }),
+            execution_estimation: None,
+            execution_cost: None,
         }
     }
 }
@@ -123,9 +126,17 @@ impl ModuleTransaction {
         info_provider: &(impl InfoProvider + Sync),
     ) -> ApiResult<TransactionDetails> {
         let safe_transaction = &self.safe_transaction;
-        let module_info = info_provider
-            .address_ex_from_contracts_or_default(&self.module)
-            .await;
+        let index =
+            address_ex_index(info_provider, &[&self.module], AddressSource::Contracts).await;
+        let module_info = address_ex_or_default(&index, &self.module);
+        let data_decoded = safe_transaction.data_decoded.as_ref();
+        let address_info_index = OptionFuture::from(data_decoded.map(|data_decoded| async move {
+            data_decoded.build_address_info_index(info_provider).await
+        }))
+        .await
+        .flatten();
+        let actions = data_decoded
+            .and_then(|data_decoded| data_decoded.multi_send_actions(address_info_index.as_ref()));
         Ok(TransactionDetails {
             tx_id: self.generate_id(),
             executed_at: Some(self.execution_date.timestamp_millis()),
@@ -137,19 +148,15 @@ impl ModuleTransaction {
                 hex_data: safe_transaction.data.to_owned(),
                 data_decoded: safe_transaction.data_decoded.clone(),
                 operation: safe_transaction.operation,
-                address_info_index: OptionFuture::from(safe_transaction.data_decoded.as_ref().map(
-                    |data_decoded| async move {
-                        data_decoded.build_address_info_index(info_provider).await
-                    },
-                ))
-                .await
-                .flatten(),
+                address_info_index,
+                actions,
             }),
             tx_hash: Some(self.transaction_hash.to_owned()),
             detailed_execution_info: Some(DetailedExecutionInfo::Module(ModuleExecutionDetails {
                 address: module_info,
             })),
             safe_app_info: None,
+            logs: None,
         })
     }
 }
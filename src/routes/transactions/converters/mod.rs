@@ -1,5 +1,6 @@
 extern crate chrono;
 
+pub mod description;
 pub mod details;
 pub mod safe_app_info;
 pub mod summary;
@@ -8,7 +9,9 @@ pub mod transaction_id;
 #[cfg(test)]
 mod tests;
 
-use crate::common::converters::{get_address_ex_from_any_source, get_transfer_direction};
+use crate::common::converters::{
+    get_address_ex_from_any_source, get_transfer_direction, get_transfer_parties,
+};
 use crate::common::models::addresses::AddressEx;
 use crate::common::models::backend::transactions::{
     ModuleTransaction, MultisigTransaction, SafeTransaction,
@@ -103,9 +106,11 @@ impl SafeTransaction {
         let sender = get_from_param(&self.data_decoded, &self.safe);
         let recipient = get_to_param(&self.data_decoded, "0x0");
         let direction = get_transfer_direction(&self.safe, &sender, &recipient);
+        let (sender, recipient) =
+            get_transfer_parties(&self.safe, &sender, &recipient, info_provider).await;
         Transfer {
-            sender: get_address_ex_from_any_source(&self.safe, &sender, info_provider).await,
-            recipient: get_address_ex_from_any_source(&self.safe, &recipient, info_provider).await,
+            sender,
+            recipient,
             direction,
             transfer_info: TransferInfo::Erc20(Erc20Transfer {
                 token_address: token.address.to_owned(),
@@ -130,9 +135,11 @@ impl SafeTransaction {
         let sender = get_from_param(&self.data_decoded, &self.safe);
         let recipient = get_to_param(&self.data_decoded, "0x0");
         let direction = get_transfer_direction(&self.safe, &sender, &recipient);
+        let (sender, recipient) =
+            get_transfer_parties(&self.safe, &sender, &recipient, info_provider).await;
         Transfer {
-            sender: get_address_ex_from_any_source(&self.safe, &sender, info_provider).await,
-            recipient: get_address_ex_from_any_source(&self.safe, &recipient, info_provider).await,
+            sender,
+            recipient,
             direction,
             transfer_info: TransferInfo::Erc721(Erc721Transfer {
                 token_address: token.address.to_owned(),
@@ -298,7 +305,7 @@ impl ModuleTransaction {
     }
 }
 
-fn data_size(data: &Option<String>) -> usize {
+pub(crate) fn data_size(data: &Option<String>) -> usize {
     match data {
         Some(actual_data) => {
             let length = actual_data.len();
@@ -4,8 +4,9 @@ use crate::common::models::backend::transactions::{CreationTransaction, Transact
 use crate::common::models::backend::transactions::{
     EthereumTransaction, ModuleTransaction, MultisigTransaction,
 };
-use crate::providers::ext::InfoProviderExt;
+use crate::providers::address_info::{address_ex_index, address_ex_or_default, AddressSource};
 use crate::providers::info::InfoProvider;
+use crate::routes::transactions::converters::description::transaction_description;
 use crate::routes::transactions::converters::safe_app_info::safe_app_info_from;
 use crate::routes::transactions::models::summary::{
     ExecutionInfo, ModuleExecutionInfo, MultisigExecutionInfo, TransactionSummary,
@@ -50,6 +51,14 @@ impl MultisigTransaction {
         } else {
             None
         };
+        let tx_info = self.transaction_info(info_provider).await;
+        // The owners of the Safe are only known before the transaction changes them
+        let owner_count = if self.is_executed {
+            None
+        } else {
+            Some(safe_info.owners.len())
+        };
+        let description = transaction_description(info_provider, &tx_info, owner_count).await;
         Ok(vec![TransactionSummary {
             id: self.generate_id(),
             timestamp: self
@@ -63,7 +72,7 @@ impl MultisigTransaction {
                 confirmations_required: self.confirmation_required(safe_info.threshold),
                 missing_signers,
             })),
-            tx_info: self.transaction_info(info_provider).await,
+            tx_info,
             safe_app_info: OptionFuture::from(
                 self.origin
                     .as_ref()
@@ -71,6 +80,7 @@ impl MultisigTransaction {
             )
             .await
             .flatten(),
+            description,
         }])
     }
 }
@@ -86,13 +96,16 @@ impl EthereumTransaction {
                 let mut results = Vec::with_capacity(transfers.len());
 
                 for transfer in transfers {
+                    let tx_info = transfer.to_transfer(info_provider, safe_address).await;
+                    let description = transaction_description(info_provider, &tx_info, None).await;
                     let transaction_summary = TransactionSummary {
                         id: self.generate_id(safe_address, &hex_hash(transfer)),
                         timestamp: self.execution_date.timestamp_millis(),
                         tx_status: TransactionStatus::Success,
                         execution_info: None,
                         safe_app_info: None,
-                        tx_info: transfer.to_transfer(info_provider, safe_address).await,
+                        tx_info,
+                        description,
                     };
 
                     results.push(transaction_summary);
@@ -109,9 +122,11 @@ impl ModuleTransaction {
         &self,
         info_provider: &(impl InfoProvider + Sync),
     ) -> Vec<TransactionSummary> {
-        let module_info = info_provider
-            .address_ex_from_contracts_or_default(&self.module)
-            .await;
+        let index =
+            address_ex_index(info_provider, &[&self.module], AddressSource::Contracts).await;
+        let module_info = address_ex_or_default(&index, &self.module);
+        let tx_info = self.transaction_info(info_provider).await;
+        let description = transaction_description(info_provider, &tx_info, None).await;
         vec![TransactionSummary {
             id: self.generate_id(),
             timestamp: self.execution_date.timestamp_millis(),
@@ -120,7 +135,8 @@ impl ModuleTransaction {
                 address: module_info,
             })),
             safe_app_info: None,
-            tx_info: self.transaction_info(info_provider).await,
+            tx_info,
+            description,
         }]
     }
 }
@@ -131,24 +147,31 @@ impl CreationTransaction {
         safe_address: &str,
         info_provider: &(impl InfoProvider + Sync),
     ) -> TransactionSummary {
+        let mut lookups = vec![self.creator.as_str()];
+        lookups.extend(self.master_copy.as_deref());
+        lookups.extend(self.factory_address.as_deref());
+        let index = address_ex_index(info_provider, &lookups, AddressSource::Contracts).await;
+        let tx_info = TransactionInfo::Creation(Creation {
+            creator: address_ex_or_default(&index, &self.creator),
+            transaction_hash: self.transaction_hash.clone(),
+            implementation: self
+                .master_copy
+                .as_ref()
+                .map(|master_copy| address_ex_or_default(&index, master_copy)),
+            factory: self
+                .factory_address
+                .as_ref()
+                .map(|factory_address| address_ex_or_default(&index, factory_address)),
+        });
+        let description = transaction_description(info_provider, &tx_info, None).await;
         TransactionSummary {
             id: self.generate_id(safe_address),
             timestamp: self.created.timestamp_millis(),
             tx_status: TransactionStatus::Success,
-            tx_info: TransactionInfo::Creation(Creation {
-                creator: info_provider
-                    .address_ex_from_contracts_or_default(&self.creator)
-                    .await,
-                transaction_hash: self.transaction_hash.clone(),
-                implementation: info_provider
-                    .optional_address_ex_from_contracts(&self.master_copy)
-                    .await,
-                factory: info_provider
-                    .optional_address_ex_from_contracts(&self.factory_address)
-                    .await,
-            }),
+            tx_info,
             execution_info: None,
             safe_app_info: None,
+            description,
         }
     }
 }
@@ -0,0 +1,260 @@
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::backend::chains::NativeCurrency;
+use crate::common::models::data_decoded::DataDecoded;
+use crate::routes::transactions::converters::description::{
+    describe, format_amount, short_address,
+};
+use crate::routes::transactions::models::{
+    Creation, Custom, Erc20Transfer, Erc721Transfer, NativeCoinTransfer, SettingsChange,
+    SettingsInfo, TransactionInfo, Transfer, TransferDirection, TransferInfo,
+};
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const OTHER: &str = "0xab00000000000000000000000000000000000012";
+const OWNER: &str = "0xcd00000000000000000000000000000000000034";
+
+fn transfer(direction: TransferDirection, transfer_info: TransferInfo) -> TransactionInfo {
+    let (sender, recipient) = match direction {
+        TransferDirection::Incoming => (OTHER, SAFE),
+        _ => (SAFE, OTHER),
+    };
+    TransactionInfo::Transfer(Transfer {
+        sender: AddressEx::address_only(sender),
+        recipient: AddressEx::address_only(recipient),
+        direction,
+        transfer_info,
+    })
+}
+
+fn usdc_transfer(value: &str) -> TransferInfo {
+    TransferInfo::Erc20(Erc20Transfer {
+        token_address: String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+        token_name: Some(String::from("USD Coin")),
+        token_symbol: Some(String::from("USDC")),
+        logo_uri: None,
+        decimals: Some(6),
+        value: value.to_string(),
+    })
+}
+
+fn settings_change(settings_info: SettingsInfo) -> TransactionInfo {
+    TransactionInfo::SettingsChange(SettingsChange {
+        data_decoded: DataDecoded {
+            method: String::from("addOwnerWithThreshold"),
+            parameters: None,
+        },
+        settings_info: Some(settings_info),
+    })
+}
+
+fn custom(method_name: Option<&str>, action_count: Option<usize>) -> TransactionInfo {
+    TransactionInfo::Custom(Custom {
+        to: AddressEx::address_only(OTHER),
+        data_size: String::from("68"),
+        value: String::from("0"),
+        method_name: method_name.map(str::to_string),
+        action_count,
+        is_cancellation: false,
+    })
+}
+
+fn ether() -> NativeCurrency {
+    NativeCurrency {
+        name: String::from("Ether"),
+        symbol: String::from("ETH"),
+        decimals: 18,
+        logo_uri: String::from(""),
+    }
+}
+
+#[test]
+fn describe_outgoing_erc20_transfer() {
+    let tx_info = transfer(TransferDirection::Outgoing, usdc_transfer("100000000"));
+
+    assert_eq!(
+        Some(String::from("Send 100 USDC to 0xab…12")),
+        describe(&tx_info, None, None)
+    );
+}
+
+#[test]
+fn describe_incoming_erc20_transfer_with_fraction() {
+    let tx_info = transfer(TransferDirection::Incoming, usdc_transfer("1500000"));
+
+    assert_eq!(
+        Some(String::from("Receive 1.5 USDC from 0xab…12")),
+        describe(&tx_info, None, None)
+    );
+}
+
+#[test]
+fn describe_erc20_transfer_without_decimals_uses_raw_value() {
+    let tx_info = transfer(
+        TransferDirection::Outgoing,
+        TransferInfo::Erc20(Erc20Transfer {
+            token_address: String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+            token_name: None,
+            token_symbol: None,
+            logo_uri: None,
+            decimals: None,
+            value: String::from("42"),
+        }),
+    );
+
+    assert_eq!(
+        Some(String::from("Send 42 0xD9…02 to 0xab…12")),
+        describe(&tx_info, None, None)
+    );
+}
+
+#[test]
+fn describe_erc721_transfer() {
+    let tx_info = transfer(
+        TransferDirection::Outgoing,
+        TransferInfo::Erc721(Erc721Transfer {
+            token_address: String::from("0x16baF0dE678E52367adC69fD067E5eDd1D33e3bF"),
+            token_id: String::from("3"),
+            token_name: Some(String::from("CryptoKitties")),
+            token_symbol: Some(String::from("CK")),
+            logo_uri: None,
+        }),
+    );
+
+    assert_eq!(
+        Some(String::from("Send CK #3 to 0xab…12")),
+        describe(&tx_info, None, None)
+    );
+}
+
+#[test]
+fn describe_native_coin_transfer() {
+    let tx_info = transfer(
+        TransferDirection::Outgoing,
+        TransferInfo::NativeCoin(NativeCoinTransfer {
+            value: String::from("250000000000000000"),
+        }),
+    );
+
+    assert_eq!(
+        Some(String::from("Send 0.25 ETH to 0xab…12")),
+        describe(&tx_info, Some(&ether()), None)
+    );
+}
+
+#[test]
+fn describe_native_coin_transfer_without_native_currency() {
+    let tx_info = transfer(
+        TransferDirection::Outgoing,
+        TransferInfo::NativeCoin(NativeCoinTransfer {
+            value: String::from("250000000000000000"),
+        }),
+    );
+
+    assert_eq!(None, describe(&tx_info, None, None));
+}
+
+#[test]
+fn describe_add_owner_with_owner_count() {
+    let tx_info = settings_change(SettingsInfo::AddOwner {
+        owner: AddressEx::address_only(OWNER),
+        threshold: 2,
+    });
+
+    assert_eq!(
+        Some(String::from(
+            "Add owner 0xcd…34 and change threshold to 2/3"
+        )),
+        describe(&tx_info, None, Some(2))
+    );
+}
+
+#[test]
+fn describe_remove_owner_without_owner_count() {
+    let tx_info = settings_change(SettingsInfo::RemoveOwner {
+        owner: AddressEx::address_only(OWNER),
+        threshold: 1,
+    });
+
+    assert_eq!(
+        Some(String::from(
+            "Remove owner 0xcd…34 and change threshold to 1"
+        )),
+        describe(&tx_info, None, None)
+    );
+}
+
+#[test]
+fn describe_swap_owner() {
+    let tx_info = settings_change(SettingsInfo::SwapOwner {
+        old_owner: AddressEx::address_only(OWNER),
+        new_owner: AddressEx::address_only(OTHER),
+    });
+
+    assert_eq!(
+        Some(String::from("Replace owner 0xcd…34 with 0xab…12")),
+        describe(&tx_info, None, Some(3))
+    );
+}
+
+#[test]
+fn describe_settings_change_without_settings_info_uses_method() {
+    let tx_info = TransactionInfo::SettingsChange(SettingsChange {
+        data_decoded: DataDecoded {
+            method: String::from("setGuard"),
+            parameters: None,
+        },
+        settings_info: None,
+    });
+
+    assert_eq!(
+        Some(String::from("Call setGuard")),
+        describe(&tx_info, None, None)
+    );
+}
+
+#[test]
+fn describe_custom_transactions() {
+    assert_eq!(
+        Some(String::from("Call approve on 0xab…12")),
+        describe(&custom(Some("approve"), None), None, None)
+    );
+    assert_eq!(
+        Some(String::from("Execute 3 actions")),
+        describe(&custom(Some("multiSend"), Some(3)), None, None)
+    );
+    assert_eq!(
+        Some(String::from("Interact with 0xab…12")),
+        describe(&custom(None, None), None, None)
+    );
+}
+
+#[test]
+fn describe_creation_and_unknown() {
+    let creation = TransactionInfo::Creation(Creation {
+        creator: AddressEx::address_only(OWNER),
+        transaction_hash: String::from("0x00"),
+        implementation: None,
+        factory: None,
+    });
+
+    assert_eq!(
+        Some(String::from("Create Safe")),
+        describe(&creation, None, None)
+    );
+    assert_eq!(None, describe(&TransactionInfo::Unknown, None, None));
+}
+
+#[test]
+fn short_address_keeps_short_values() {
+    assert_eq!("0xab…12", short_address(OTHER));
+    assert_eq!("0x1234", short_address("0x1234"));
+}
+
+#[test]
+fn format_amount_strips_trailing_zeros() {
+    assert_eq!(Some(String::from("100")), format_amount("100000000", 6));
+    assert_eq!(Some(String::from("0.000001")), format_amount("1", 6));
+    assert_eq!(Some(String::from("0")), format_amount("0", 18));
+    assert_eq!(Some(String::from("12")), format_amount("12", 0));
+    assert_eq!(None, format_amount("not a number", 6));
+}
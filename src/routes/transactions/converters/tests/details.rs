@@ -72,7 +72,8 @@ async fn multisig_custom_transaction_to_transaction_details() {
             to: AddressEx::address_only("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
             value: Some(String::from("0")),
             operation: Operation::CALL,
-            address_info_index: None
+            address_info_index: None,
+            actions: None
         }),
         detailed_execution_info: Some(DetailedExecutionInfo::Multisig(
             MultisigExecutionDetails {
@@ -107,8 +108,11 @@ async fn multisig_custom_transaction_to_transaction_details() {
                 ],
                 rejectors: None,
                 gas_token_info: None,
+                execution_estimation: None,
+                execution_cost: None,
             })),
         safe_app_info: None,
+        logs: None,
     };
 
     let actual =
@@ -131,6 +135,7 @@ async fn module_transaction_to_transaction_details_module_info_success() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", address).to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
     mock_info_provider
@@ -160,17 +165,19 @@ async fn module_transaction_to_transaction_details_module_info_success() {
             to: AddressEx::address_only("0xaAEb2035FF394fdB2C879190f95e7676f1A9444B"),
             value: Some(String::from("0")),
             operation: Operation::CALL,
-            address_info_index: None
+            address_info_index: None,
+            actions: None
         }),
         detailed_execution_info: Some(DetailedExecutionInfo::Module(
             ModuleExecutionDetails {
                 address: AddressEx {
                     value: "0xfa559f0932b7B60d90B4af0b8813d4088465096b".to_string(),
                     name: Some("0xfa559f0932b7B60d90B4af0b8813d4088465096b_name".to_string()),
-                    logo_uri: None,
+                    logo_uri: None, risk_flags: vec![],
                 }
             })),
         safe_app_info: None,
+        logs: None,
     };
 
     let actual =
@@ -216,13 +223,15 @@ async fn module_transaction_to_transaction_details_success() {
             to: AddressEx::address_only("0xaAEb2035FF394fdB2C879190f95e7676f1A9444B"),
             value: Some(String::from("0")),
             operation: Operation::CALL,
-            address_info_index: None
+            address_info_index: None,
+            actions: None
         }),
         detailed_execution_info: Some(DetailedExecutionInfo::Module(
             ModuleExecutionDetails {
                 address: AddressEx::address_only("0xfa559f0932b7B60d90B4af0b8813d4088465096b")
             })),
         safe_app_info: None,
+        logs: None,
     };
 
     let actual =
@@ -268,13 +277,15 @@ async fn module_transaction_to_transaction_details_failed() {
             to: AddressEx::address_only("0xaAEb2035FF394fdB2C879190f95e7676f1A9444B"),
             value: Some(String::from("0")),
             operation: Operation::CALL,
-            address_info_index: None
+            address_info_index: None,
+            actions: None
         }),
         detailed_execution_info: Some(DetailedExecutionInfo::Module(
             ModuleExecutionDetails {
                 address: AddressEx::address_only("0xfa559f0932b7B60d90B4af0b8813d4088465096b")
             })),
         safe_app_info: None,
+        logs: None,
     };
 
     let actual =
@@ -313,6 +324,7 @@ async fn ethereum_tx_transfer_to_transaction_details() {
         tx_data: None,
         detailed_execution_info: None,
         safe_app_info: None,
+        logs: None,
     };
 
     let mut mock_info_provider = MockInfoProvider::new();
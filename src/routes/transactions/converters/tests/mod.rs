@@ -1,5 +1,6 @@
 pub(super) mod check_sender_or_receiver;
 mod data_size_calculation;
+mod description;
 mod details;
 mod is_cancellation;
 pub(super) mod map_status;
@@ -101,6 +101,7 @@ async fn module_tx_to_summary_transaction_success() {
             is_cancellation: false,
         }),
         safe_app_info: None,
+        description: None,
     }];
     assert_eq!(actual, expected);
 }
@@ -162,6 +163,7 @@ async fn module_tx_to_summary_transaction_failed() {
             is_cancellation: false,
         }),
         safe_app_info: None,
+        description: None,
     }];
     assert_eq!(actual, expected);
 }
@@ -182,6 +184,7 @@ async fn module_transaction_to_custom_summary_and_module_info() {
                 value: address.to_string(),
                 name: Some(format!("{}_name", address)),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
     mock_info_provider
@@ -211,9 +214,11 @@ async fn module_transaction_to_custom_summary_and_module_info() {
                 value: "0xfa559f0932b7B60d90B4af0b8813d4088465096b".to_string(),
                 name: Some("0xfa559f0932b7B60d90B4af0b8813d4088465096b_name".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             },
         })),
         safe_app_info: None,
+        description: None,
     };
 
     let actual =
@@ -309,6 +314,7 @@ async fn ethereum_tx_to_summary_transaction_with_transfers() {
             }),
             execution_info: None,
             safe_app_info: None,
+            description: None,
         },
         TransactionSummary {
             id: create_id!(
@@ -329,6 +335,7 @@ async fn ethereum_tx_to_summary_transaction_with_transfers() {
             }),
             execution_info: None,
             safe_app_info: None,
+            description: None,
         },
     ];
     assert_eq!(actual, expected);
@@ -339,7 +346,7 @@ async fn creation_transaction_to_summary_no_address_info_available() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider
         .expect_address_ex_from_contracts()
-        .times(3)
+        .times(2) // creator and factory are the same address
         .returning(move |_| bail!("No address info"));
 
     let created_date = Utc::now();
@@ -369,6 +376,7 @@ async fn creation_transaction_to_summary_no_address_info_available() {
         }),
         execution_info: None,
         safe_app_info: None,
+        description: None,
     };
 
     let actual = creation_tx
@@ -384,12 +392,13 @@ async fn creation_transaction_to_summary_address_info_available() {
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider
         .expect_address_ex_from_contracts()
-        .times(3)
+        .times(2) // creator and factory are the same address
         .returning(move |address| {
             Ok(AddressEx {
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -417,21 +426,25 @@ async fn creation_transaction_to_summary_address_info_available() {
                 value: creator,
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             },
             transaction_hash,
             implementation: Some(AddressEx {
                 value: master_copy,
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             }),
             factory: Some(AddressEx {
                 value: factory_address,
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             }),
         }),
         execution_info: None,
         safe_app_info: None,
+        description: None,
     };
 
     let actual = creation_tx
@@ -488,6 +501,7 @@ async fn multisig_transaction_to_erc20_transfer_summary() {
             missing_signers: None,
         })),
         safe_app_info: None,
+        description: None,
     };
 
     let actual =
@@ -544,6 +558,7 @@ async fn multisig_transaction_to_erc721_transfer_summary() {
             missing_signers: None,
         })),
         safe_app_info: None,
+        description: None,
     };
 
     let actual =
@@ -594,6 +609,7 @@ async fn multisig_transaction_to_ether_transfer_summary() {
             missing_signers: None,
         })),
         safe_app_info: None,
+        description: None,
     };
 
     let actual =
@@ -661,6 +677,7 @@ async fn multisig_transaction_to_settings_change_summary() {
             missing_signers: None,
         })),
         safe_app_info: None,
+        description: None,
     };
 
     let actual =
@@ -711,6 +728,7 @@ async fn multisig_transaction_to_custom_summary() {
             missing_signers: None,
         })),
         safe_app_info: None,
+        description: None,
     };
 
     let actual =
@@ -769,6 +787,7 @@ async fn multisig_transaction_with_missing_signers() {
             ]),
         })),
         safe_app_info: None,
+        description: None,
     };
 
     let actual =
@@ -821,6 +840,7 @@ async fn ethereum_transaction_with_inconsistent_token_types() {
         }),
         execution_info: None,
         safe_app_info: None,
+        description: None,
     };
 
     assert_eq!(1, actual.len());
@@ -885,6 +905,7 @@ async fn multisig_transaction_with_origin() {
             url: "https://apps.gnosis-safe.io/walletConnect".to_string(),
             logo_uri: "https://apps.gnosis-safe.io/walletConnect/walletConnect.jpg".to_string(),
         }),
+        description: None,
     };
 
     let actual =
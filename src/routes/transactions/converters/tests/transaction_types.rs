@@ -77,6 +77,7 @@ async fn transaction_data_size_and_value_greater_than_0_with_address_info() {
                 value: address.to_string(),
                 name: Some("".to_string()),
                 logo_uri: None,
+                risk_flags: vec![],
             })
         });
 
@@ -89,6 +90,7 @@ async fn transaction_data_size_and_value_greater_than_0_with_address_info() {
             value: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02".to_string(),
             name: Some("".to_string()),
             logo_uri: None,
+            risk_flags: vec![],
         },
         data_size: "68".to_string(),
         value: "100000000000000000".to_string(),
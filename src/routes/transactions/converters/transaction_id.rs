@@ -2,7 +2,11 @@ use crate::common::models::backend::transactions::{
     CreationTransaction, EthereumTransaction, ModuleTransaction, MultisigTransaction,
 };
 use crate::common::models::backend::transfers::Transfer;
+use crate::routes::transactions::models::{
+    TransactionIdParts, TransactionIdType, TransactionRawIds,
+};
 use crate::utils::hex_hash;
+use crate::utils::transaction_id::build_id;
 
 impl MultisigTransaction {
     pub fn generate_id(&self) -> String {
@@ -52,3 +56,68 @@ impl CreationTransaction {
         create_id!(super::super::models::ID_PREFIX_CREATION_TX, safe_address)
     }
 }
+
+impl TransactionRawIds {
+    pub fn new(chain_id: &str, id_parts: &TransactionIdParts) -> Self {
+        let id = build_id(id_parts);
+        let chain_id = chain_id.to_string();
+        match id_parts {
+            TransactionIdParts::Creation(safe_address) => TransactionRawIds {
+                id,
+                chain_id,
+                id_type: TransactionIdType::Creation,
+                safe_address: Some(safe_address.to_string()),
+                safe_tx_hash: None,
+                transaction_hash: None,
+                details_hash: None,
+            },
+            TransactionIdParts::Multisig {
+                safe_address,
+                safe_tx_hash,
+            } => TransactionRawIds {
+                id,
+                chain_id,
+                id_type: TransactionIdType::Multisig,
+                safe_address: Some(safe_address.to_string()),
+                safe_tx_hash: Some(safe_tx_hash.to_string()),
+                transaction_hash: None,
+                details_hash: None,
+            },
+            TransactionIdParts::Module {
+                safe_address,
+                transaction_hash,
+                details_hash,
+            } => TransactionRawIds {
+                id,
+                chain_id,
+                id_type: TransactionIdType::Module,
+                safe_address: Some(safe_address.to_string()),
+                safe_tx_hash: None,
+                transaction_hash: Some(transaction_hash.to_string()),
+                details_hash: Some(details_hash.to_string()),
+            },
+            TransactionIdParts::Ethereum {
+                safe_address,
+                transaction_hash,
+                details_hash,
+            } => TransactionRawIds {
+                id,
+                chain_id,
+                id_type: TransactionIdType::Ethereum,
+                safe_address: Some(safe_address.to_string()),
+                safe_tx_hash: None,
+                transaction_hash: Some(transaction_hash.to_string()),
+                details_hash: Some(details_hash.to_string()),
+            },
+            TransactionIdParts::TransactionHash(safe_tx_hash) => TransactionRawIds {
+                id,
+                chain_id,
+                id_type: TransactionIdType::SafeTxHash,
+                safe_address: None,
+                safe_tx_hash: Some(safe_tx_hash.to_string()),
+                transaction_hash: None,
+                details_hash: None,
+            },
+        }
+    }
+}
@@ -1,23 +1,42 @@
 extern crate reqwest;
 
-use crate::cache::cache_operations::RequestCached;
+use crate::cache::cache_operations::{CacheResponse, RequestCached};
 use crate::common::models::backend::transactions::{ModuleTransaction, MultisigTransaction};
 use crate::common::models::backend::transfers::Transfer;
 use crate::common::models::page::Page;
-use crate::config::transaction_request_timeout;
+use crate::config::{
+    feature_flag_decoded_logs, feature_flag_execution_cost, transaction_details_batch_size,
+    transaction_request_timeout,
+};
+use crate::monitoring::schema_drift;
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
-use crate::routes::transactions::models::details::TransactionDetails;
-use crate::routes::transactions::models::{TransactionIdParts, ID_SEPARATOR};
+use crate::providers::rpc::{EthCall, RpcProvider};
+use crate::routes::transactions::handlers::execution_cost::execution_cost;
+use crate::routes::transactions::handlers::expiry::ExpiryPolicy;
+use crate::routes::transactions::handlers::logs::transaction_logs;
+use crate::routes::transactions::models::details::{
+    DetailedExecutionInfo, ExecutionEstimation, TransactionDetails, TransactionDetailsResult,
+};
+use crate::routes::transactions::models::requests::TransactionDetailsRequest;
+use crate::routes::transactions::models::{
+    TransactionIdParts, TransactionRawIds, TransactionStatus,
+};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
 use crate::utils::hex_hash;
-use crate::utils::transactions::fetch_rejections;
-use log::debug;
+use crate::utils::transaction_id::parse_id;
+use crate::utils::transactions::{exec_transaction_data, fetch_rejections};
+use crate::utils::validation::{Validate, Validator};
+use chrono::Utc;
+use log::{debug, warn};
+use rocket::futures::future::join_all;
+use serde_json::value::RawValue;
 
 pub(super) async fn get_multisig_transaction_details(
     context: &RequestContext,
     chain_id: &str,
     safe_tx_hash: &str,
+    estimate_gas: bool,
 ) -> ApiResult<TransactionDetails> {
     let mut info_provider = DefaultInfoProvider::new(chain_id, context);
     let url = core_uri!(info_provider, "/v1/multisig-transactions/{}/", safe_tx_hash)?;
@@ -25,7 +44,7 @@ pub(super) async fn get_multisig_transaction_details(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    let multisig_tx: MultisigTransaction = serde_json::from_str(&body)?;
+    let multisig_tx: MultisigTransaction = schema_drift::parse(&body)?;
 
     let rejections = fetch_rejections(
         context,
@@ -35,13 +54,91 @@ pub(super) async fn get_multisig_transaction_details(
     )
     .await;
 
-    let details = multisig_tx
+    let mut details = multisig_tx
         .to_transaction_details(rejections, &mut info_provider)
         .await?;
 
+    let policy = ExpiryPolicy::from_config();
+    if policy.is_enabled() {
+        let safe_info = info_provider
+            .safe_info(&multisig_tx.safe_transaction.safe)
+            .await?;
+        if let Some(status) = policy.status(&multisig_tx, safe_info.nonce, Utc::now()) {
+            details.tx_status = status;
+        }
+    }
+
+    if estimate_gas && details.tx_status == TransactionStatus::AwaitingExecution {
+        if let Some(DetailedExecutionInfo::Multisig(ref mut execution_details)) =
+            details.detailed_execution_info
+        {
+            execution_details.execution_estimation =
+                estimate_execution(context, &info_provider, &multisig_tx).await;
+        }
+    }
+
+    if feature_flag_execution_cost() && multisig_tx.is_executed {
+        if let Some(DetailedExecutionInfo::Multisig(ref mut execution_details)) =
+            details.detailed_execution_info
+        {
+            execution_details.execution_cost =
+                execution_cost(context, &info_provider, &multisig_tx).await;
+        }
+    }
+
     Ok(details)
 }
 
+// Estimation is best effort: if the node can't be reached or fails for other reasons than a revert
+// we omit it instead of failing the details
+pub(super) async fn estimate_execution(
+    context: &RequestContext,
+    info_provider: &(impl InfoProvider + Sync),
+    multisig_tx: &MultisigTransaction,
+) -> Option<ExecutionEstimation> {
+    let chain_info = info_provider.chain_info().await.ok()?;
+    let call = EthCall {
+        from: multisig_tx
+            .confirmations
+            .as_ref()
+            .and_then(|confirmations| confirmations.first())
+            .map(|confirmation| confirmation.owner.to_string()),
+        to: multisig_tx.safe_transaction.safe.to_string(),
+        data: exec_transaction_data(multisig_tx).ok()?,
+        value: None,
+    };
+
+    match RpcProvider::new(context, &chain_info)
+        .estimate_gas(&call)
+        .await
+    {
+        Ok(Ok(gas)) => Some(ExecutionEstimation {
+            gas_estimate: Some(gas.to_string()),
+            will_revert: false,
+            revert_reason: None,
+        }),
+        Ok(Err(rpc_error)) if rpc_error.is_execution_reverted() => Some(ExecutionEstimation {
+            gas_estimate: None,
+            will_revert: true,
+            revert_reason: Some(rpc_error.message),
+        }),
+        Ok(Err(rpc_error)) => {
+            warn!(
+                "Execution estimation failed for {}: {:?}",
+                multisig_tx.safe_tx_hash, rpc_error
+            );
+            None
+        }
+        Err(error) => {
+            warn!(
+                "Execution estimation failed for {}: {:?}",
+                multisig_tx.safe_tx_hash, error
+            );
+            None
+        }
+    }
+}
+
 async fn get_ethereum_transaction_details(
     context: &RequestContext,
     chain_id: &str,
@@ -61,7 +158,7 @@ async fn get_ethereum_transaction_details(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    let transfers: Page<Transfer> = serde_json::from_str(&body)?;
+    let transfers: Page<Transfer> = schema_drift::parse(&body)?;
     let transfer = transfers
         .results
         .into_iter()
@@ -99,7 +196,7 @@ async fn get_module_transaction_details(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    let transactions: Page<ModuleTransaction> = serde_json::from_str(&body)?;
+    let transactions: Page<ModuleTransaction> = schema_drift::parse(&body)?;
     let transaction = transactions
         .results
         .into_iter()
@@ -116,10 +213,11 @@ pub async fn get_transactions_details(
     context: &RequestContext,
     chain_id: &str,
     details_id: &String,
+    estimate_gas: bool,
 ) -> ApiResult<TransactionDetails> {
     let id_parts = parse_id(details_id)?;
 
-    match id_parts {
+    let mut details = match id_parts {
         TransactionIdParts::Ethereum {
             safe_address,
             transaction_hash,
@@ -149,64 +247,77 @@ pub async fn get_transactions_details(
             .await
         }
         TransactionIdParts::Multisig { safe_tx_hash, .. } => {
-            get_multisig_transaction_details(context, chain_id, &safe_tx_hash).await
+            get_multisig_transaction_details(context, chain_id, &safe_tx_hash, estimate_gas).await
         }
         TransactionIdParts::TransactionHash(safe_tx_hash) => {
-            get_multisig_transaction_details(context, chain_id, &safe_tx_hash).await
+            get_multisig_transaction_details(context, chain_id, &safe_tx_hash, estimate_gas).await
         }
         _ => Err(client_error!(422, "Bad transaction id")),
+    }?;
+
+    if feature_flag_decoded_logs() && details.executed_at.is_some() {
+        if let Some(tx_hash) = details.tx_hash.as_ref() {
+            let info_provider = DefaultInfoProvider::new(chain_id, context);
+            details.logs = transaction_logs(context, &info_provider, tx_hash).await;
+        }
     }
+
+    Ok(details)
+}
+
+/// Failures of single transactions are returned as entries of the result, only an invalid
+/// request fails as a whole
+pub async fn get_multiple_transactions_details(
+    context: &RequestContext,
+    chain_id: &str,
+    request: &TransactionDetailsRequest,
+) -> ApiResult<Vec<TransactionDetailsResult>> {
+    let mut validator = Validator::default();
+    request.validate(&mut validator);
+    validator.check(
+        "transactionIds",
+        !request.transaction_ids.is_empty()
+            && request.transaction_ids.len() <= transaction_details_batch_size(),
+        "must contain between 1 and TRANSACTION_DETAILS_BATCH_SIZE ids",
+    );
+    validator.finish()?;
+
+    Ok(join_all(
+        request
+            .transaction_ids
+            .iter()
+            .map(|transaction_id| async move {
+                // Same entry as the one of the single transaction details endpoint
+                let details = CacheResponse::new(context)
+                    .key(format!(
+                        "/v1/chains/{}/transactions/{}",
+                        chain_id, transaction_id
+                    ))
+                    .resp_generator(|| {
+                        get_transactions_details(context, chain_id, transaction_id, false)
+                    })
+                    .execute()
+                    .await
+                    .and_then(|json| Ok(RawValue::from_string(json.0)?));
+                match details {
+                    Ok(details) => TransactionDetailsResult {
+                        transaction_id: transaction_id.to_string(),
+                        status: 200,
+                        details: Some(details),
+                        error: None,
+                    },
+                    Err(error) => TransactionDetailsResult {
+                        transaction_id: transaction_id.to_string(),
+                        status: error.status,
+                        details: None,
+                        error: Some(error.details),
+                    },
+                }
+            }),
+    )
+    .await)
 }
 
-pub(super) fn parse_id(details_id: &str) -> ApiResult<TransactionIdParts> {
-    let id_parts: Vec<&str> = details_id.split(ID_SEPARATOR).collect();
-    let tx_type = id_parts.get(0).ok_or(api_error!("Invalid id"))?;
-
-    Ok(match tx_type.to_owned() {
-        super::super::models::ID_PREFIX_MULTISIG_TX => TransactionIdParts::Multisig {
-            safe_address: id_parts
-                .get(1)
-                .ok_or(client_error!(422, "No safe address provided"))?
-                .to_string(),
-            safe_tx_hash: id_parts
-                .get(2)
-                .ok_or(client_error!(422, "No safe tx hash provided"))?
-                .to_string(),
-        },
-        super::super::models::ID_PREFIX_ETHEREUM_TX => TransactionIdParts::Ethereum {
-            safe_address: id_parts
-                .get(1)
-                .ok_or(client_error!(422, "No safe address"))?
-                .to_string(),
-            transaction_hash: id_parts
-                .get(2)
-                .ok_or(client_error!(422, "No ethereum tx hash"))?
-                .to_string(),
-            details_hash: id_parts
-                .get(3)
-                .ok_or(client_error!(422, "No ethereum tx details hash"))?
-                .to_string(),
-        },
-        super::super::models::ID_PREFIX_MODULE_TX => TransactionIdParts::Module {
-            safe_address: id_parts
-                .get(1)
-                .ok_or(client_error!(422, "No safe address"))?
-                .to_string(),
-            transaction_hash: id_parts
-                .get(2)
-                .ok_or(client_error!(422, "No module tx hash"))?
-                .to_string(),
-            details_hash: id_parts
-                .get(3)
-                .ok_or(client_error!(422, "No module tx details hash"))?
-                .to_string(),
-        },
-        super::super::models::ID_PREFIX_CREATION_TX => TransactionIdParts::Creation(
-            id_parts
-                .get(1)
-                .ok_or(client_error!(422, "No safe address provided"))?
-                .to_string(),
-        ),
-        &_ => TransactionIdParts::TransactionHash(tx_type.to_string()),
-    })
+pub fn get_transaction_raw_ids(chain_id: &str, details_id: &str) -> ApiResult<TransactionRawIds> {
+    Ok(TransactionRawIds::new(chain_id, &parse_id(details_id)?))
 }
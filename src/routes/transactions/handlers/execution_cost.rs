@@ -0,0 +1,125 @@
+//! Actual cost of executed multisig transactions, from the receipt of their ethereum transaction.
+//! Receipts are immutable, so they are cached without expiry.
+use crate::cache::Cache;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::providers::info::InfoProvider;
+use crate::providers::rpc::{RpcProvider, TransactionReceipt};
+use crate::routes::balances::handlers_v2::get_token_usd_rate;
+use crate::routes::balances::models::TokenPrice;
+use crate::routes::transactions::models::details::ExecutionCost;
+use crate::utils::context::RequestContext;
+use bigdecimal::{num_bigint::BigInt, BigDecimal};
+use log::warn;
+
+const RECEIPTS_KEY_BASE: &str = "rpc_receipts";
+const NATIVE_COIN_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+// Best effort like the execution estimation: the cost is omitted if it can't be determined
+pub(super) async fn execution_cost(
+    context: &RequestContext,
+    info_provider: &(impl InfoProvider + Sync),
+    multisig_tx: &MultisigTransaction,
+) -> Option<ExecutionCost> {
+    if !multisig_tx.is_executed {
+        return None;
+    }
+    let tx_hash = multisig_tx.transaction_hash.as_ref()?;
+    let receipt = transaction_receipt(context, info_provider, tx_hash, false).await?;
+    let chain_info = info_provider.chain_info().await.ok()?;
+    let native_coin_price =
+        get_token_usd_rate(context, NATIVE_COIN_ADDRESS.to_string(), info_provider)
+            .await
+            .ok();
+
+    cost_from_receipt(
+        &receipt,
+        multisig_tx.eth_gas_price.as_deref(),
+        chain_info.native_currency.decimals,
+        native_coin_price.as_ref(),
+    )
+}
+
+/// `fallback_gas_price` (in wei) is used if the node doesn't report the effective gas price
+pub fn cost_from_receipt(
+    receipt: &TransactionReceipt,
+    fallback_gas_price: Option<&str>,
+    native_coin_decimals: u64,
+    native_coin_price: Option<&TokenPrice>,
+) -> Option<ExecutionCost> {
+    let effective_gas_price = match receipt.effective_gas_price {
+        Some(effective_gas_price) => effective_gas_price,
+        None => fallback_gas_price?.parse().ok()?,
+    };
+    let native_cost = receipt.gas_used as u128 * effective_gas_price as u128;
+    let fiat_cost = native_coin_price.map(|price| {
+        (BigDecimal::new(BigInt::from(native_cost), native_coin_decimals as i64)
+            * &price.fiat_price)
+            .with_scale(5)
+            .to_string()
+    });
+
+    Some(ExecutionCost {
+        gas_used: receipt.gas_used.to_string(),
+        effective_gas_price: effective_gas_price.to_string(),
+        native_cost: native_cost.to_string(),
+        fiat_cost,
+        fiat_code: native_coin_price.map(|price| price.fiat_code.to_string()),
+    })
+}
+
+/// Receipts cached before logs were read are fetched again if `with_logs` is set
+pub(super) async fn transaction_receipt(
+    context: &RequestContext,
+    info_provider: &(impl InfoProvider + Sync),
+    tx_hash: &str,
+    with_logs: bool,
+) -> Option<TransactionReceipt> {
+    let cache = context.cache();
+    let receipts_key = receipts_key(info_provider.chain_id());
+    let tx_hash = tx_hash.to_lowercase();
+    if let Some(cached) = cached_receipt(cache.as_ref(), &receipts_key, &tx_hash).await {
+        if !with_logs || cached.logs.is_some() {
+            return Some(cached);
+        }
+    }
+
+    let chain_info = info_provider.chain_info().await.ok()?;
+    match RpcProvider::new(context, &chain_info)
+        .transaction_receipt(&tx_hash)
+        .await
+    {
+        Ok(Ok(receipt)) => {
+            if let Ok(serialized) = serde_json::to_string(&receipt) {
+                cache
+                    .insert_in_hash(&receipts_key, &tx_hash, &serialized)
+                    .await;
+            }
+            Some(receipt)
+        }
+        Ok(Err(rpc_error)) => {
+            warn!(
+                "Receipt of {} could not be fetched: {}",
+                tx_hash, rpc_error.message
+            );
+            None
+        }
+        Err(error) => {
+            warn!("Receipt of {} could not be fetched: {:?}", tx_hash, error);
+            None
+        }
+    }
+}
+
+async fn cached_receipt(
+    cache: &dyn Cache,
+    receipts_key: &str,
+    tx_hash: &str,
+) -> Option<TransactionReceipt> {
+    let cached = cache.get_from_hash(receipts_key, tx_hash).await?;
+    serde_json::from_str(&cached).ok()
+}
+
+// Outside of the "c_re" prefix, so invalidations don't drop the immutable receipts
+fn receipts_key(chain_id: &str) -> String {
+    format!("{}_{}", RECEIPTS_KEY_BASE, chain_id)
+}
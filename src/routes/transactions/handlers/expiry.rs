@@ -0,0 +1,153 @@
+//! Gateway side policy for queued transactions that will most likely never be executed.
+//! Transactions submitted more than `TX_QUEUED_EXPIRY_DAYS` ago are reported as `EXPIRED` and,
+//! with `FEATURE_FLAG_QUEUED_TX_OBSOLETE`, transactions the Safe nonce moved past as `OBSOLETE`.
+use crate::cache::cache_operations::{
+    Invalidate, InvalidationPattern, InvalidationScope, RequestCached,
+};
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::common::models::page::Page;
+use crate::config::{
+    feature_flag_queued_tx_obsolete, transaction_request_timeout, tx_queued_expiry_days,
+    tx_queued_summary_size,
+};
+use crate::monitoring::schema_drift;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::transactions::models::summary::TransactionListItem;
+use crate::routes::transactions::models::TransactionStatus;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiryPolicy {
+    /// 0 disables expiry
+    pub expiry_days: u64,
+    pub mark_obsolete: bool,
+}
+
+/// Safe tx hashes of the transactions whose cache entries were purged
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiredPurge {
+    pub purged: Vec<String>,
+}
+
+impl ExpiryPolicy {
+    pub fn from_config() -> Self {
+        ExpiryPolicy {
+            expiry_days: tx_queued_expiry_days() as u64,
+            mark_obsolete: feature_flag_queued_tx_obsolete(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.expiry_days > 0 || self.mark_obsolete
+    }
+
+    /// Status overriding the one derived from the transaction, `None` if the policy doesn't apply
+    pub fn status(
+        &self,
+        transaction: &MultisigTransaction,
+        safe_nonce: u64,
+        now: DateTime<Utc>,
+    ) -> Option<TransactionStatus> {
+        if transaction.is_executed {
+            return None;
+        }
+        if transaction.nonce < safe_nonce {
+            // Otherwise reported as cancelled
+            return self.mark_obsolete.then(|| TransactionStatus::Obsolete);
+        }
+        let expired = self.expiry_days > 0
+            && transaction.submission_date + Duration::days(self.expiry_days as i64) <= now;
+        expired.then(|| TransactionStatus::Expired)
+    }
+
+    /// Statuses of the transactions the policy applies to, by transaction id
+    pub fn statuses(
+        &self,
+        transactions: &[MultisigTransaction],
+        safe_nonce: u64,
+        now: DateTime<Utc>,
+    ) -> HashMap<String, TransactionStatus> {
+        transactions
+            .iter()
+            .filter_map(|transaction| {
+                self.status(transaction, safe_nonce, now)
+                    .map(|status| (transaction.generate_id(), status))
+            })
+            .collect()
+    }
+}
+
+pub(super) fn set_expiry_statuses(
+    items: &mut [TransactionListItem],
+    mut statuses: HashMap<String, TransactionStatus>,
+) {
+    for item in items.iter_mut() {
+        if let TransactionListItem::Transaction { transaction, .. } = item {
+            if let Some(status) = statuses.remove(&transaction.id) {
+                transaction.tx_status = status;
+            }
+        }
+    }
+}
+
+/// Drops the cached details of the Safe's non executed transactions that are expired or obsolete,
+/// along with its cached transaction lists. The oldest `TX_QUEUED_SUMMARY_SIZE` non executed
+/// transactions are checked.
+pub async fn purge_expired_transactions(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+) -> ApiResult<ExpiredPurge> {
+    let policy = ExpiryPolicy::from_config();
+    if !policy.is_enabled() {
+        return Err(client_error!(
+            503,
+            "Queued transaction expiry is not enabled"
+        ));
+    }
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_nonce = info_provider.safe_info(safe_address).await?.nonce;
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/multisig-transactions/?executed=false&limit={}&ordering=nonce,submissionDate",
+        safe_address,
+        tx_queued_summary_size()
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let transactions: Page<MultisigTransaction> = schema_drift::parse(&body)?;
+
+    let now = Utc::now();
+    let purged: Vec<String> = transactions
+        .results
+        .iter()
+        .filter(|transaction| policy.status(transaction, safe_nonce, now).is_some())
+        .map(|transaction| transaction.safe_tx_hash.to_string())
+        .collect();
+    for safe_tx_hash in &purged {
+        Invalidate::new(
+            InvalidationPattern::Any(InvalidationScope::Both, safe_tx_hash.to_string()),
+            context.cache(),
+        )
+        .source("QUEUE_PURGE")
+        .execute()
+        .await;
+    }
+    if !purged.is_empty() {
+        Invalidate::new(
+            InvalidationPattern::Transactions(InvalidationScope::Both, safe_address.to_string()),
+            context.cache(),
+        )
+        .source("QUEUE_PURGE")
+        .execute()
+        .await;
+    }
+    Ok(ExpiredPurge { purged })
+}
@@ -0,0 +1,107 @@
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, SafeInfo};
+use crate::routes::transactions::handlers::transfers::ZERO_ADDRESS;
+use crate::routes::transactions::models::requests::SafeTxHashVerificationRequest;
+use crate::routes::transactions::models::summary::{
+    HashMismatch, MismatchCause, SafeTxDomain, SafeTxHashVerification,
+};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::safe_version::parse_base_version;
+use crate::utils::transactions::{
+    safe_tx_hash_parts, use_legacy_domain_separator, SafeTransactionFields,
+};
+use crate::utils::validation::Validate;
+
+pub async fn verify_safe_tx_hash(
+    context: &RequestContext,
+    chain_id: &str,
+    request: &SafeTxHashVerificationRequest,
+) -> ApiResult<SafeTxHashVerification> {
+    request.validated()?;
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_info = info_provider.safe_info(&request.safe_address).await?;
+    safe_tx_hash_verification(chain_id, &safe_info, request)
+}
+
+/// Compares the submitted hashes to the ones recomputed for the domain of `safe_info`. For
+/// mismatches the likely cause is reported, so that signers know what to look at.
+pub fn safe_tx_hash_verification(
+    chain_id: &str,
+    safe_info: &SafeInfo,
+    request: &SafeTxHashVerificationRequest,
+) -> ApiResult<SafeTxHashVerification> {
+    let fields = SafeTransactionFields {
+        to: &request.to,
+        value: &request.value,
+        data: request.data.as_deref().unwrap_or("0x"),
+        operation: request.operation as u8,
+        safe_tx_gas: &request.safe_tx_gas,
+        base_gas: &request.base_gas,
+        gas_price: &request.gas_price,
+        gas_token: request.gas_token.as_deref().unwrap_or(ZERO_ADDRESS),
+        refund_receiver: request.refund_receiver.as_deref().unwrap_or(ZERO_ADDRESS),
+        nonce: request.nonce,
+    };
+    let version = safe_info
+        .version
+        .as_ref()
+        .and_then(|version| parse_base_version(version));
+    let is_legacy = use_legacy_domain_separator(version);
+    let parts = safe_tx_hash_parts(chain_id, &safe_info.address, is_legacy, &fields)?;
+    let safe_tx_hash = to_hex_string!(parts.safe_tx_hash);
+    let domain_hash = to_hex_string!(parts.domain_hash);
+    let message_hash = to_hex_string!(parts.message_hash);
+
+    let mut mismatches = vec![];
+    let mut compare = |field: &str, expected: &str, submitted: Option<&String>| {
+        let is_mismatch =
+            submitted.map_or(false, |submitted| !submitted.eq_ignore_ascii_case(expected));
+        if is_mismatch {
+            mismatches.push(HashMismatch {
+                field: field.to_string(),
+                expected: expected.to_string(),
+                submitted: submitted.unwrap().to_string(),
+            });
+        }
+        is_mismatch
+    };
+    let is_valid = !compare("safeTxHash", &safe_tx_hash, Some(&request.safe_tx_hash));
+    let is_domain_mismatch = compare("domainHash", &domain_hash, request.domain_hash.as_ref());
+    let is_message_mismatch = compare("messageHash", &message_hash, request.message_hash.as_ref());
+
+    let likely_cause = if is_valid {
+        None
+    } else if is_domain_mismatch {
+        Some(MismatchCause::Domain)
+    } else if request.domain_hash.is_some() || is_message_mismatch {
+        Some(MismatchCause::TransactionFields)
+    } else {
+        let other_version = safe_tx_hash_parts(chain_id, &safe_info.address, !is_legacy, &fields)?;
+        if request
+            .safe_tx_hash
+            .eq_ignore_ascii_case(&to_hex_string!(other_version.safe_tx_hash))
+        {
+            Some(MismatchCause::DomainVersion)
+        } else {
+            None
+        }
+    };
+
+    Ok(SafeTxHashVerification {
+        is_valid,
+        safe_tx_hash,
+        domain_hash,
+        message_hash,
+        domain: SafeTxDomain {
+            chain_id: if is_legacy {
+                None
+            } else {
+                Some(chain_id.to_string())
+            },
+            verifying_contract: safe_info.address.to_string(),
+            version: safe_info.version.to_owned(),
+        },
+        mismatches,
+        likely_cause,
+    })
+}
@@ -3,7 +3,8 @@ extern crate reqwest;
 use crate::cache::cache_operations::RequestCached;
 use crate::common::models::backend::transactions::{CreationTransaction, Transaction};
 use crate::common::models::page::{Page, PageMetadata};
-use crate::config::transaction_request_timeout;
+use crate::config::{transaction_request_timeout, tx_history_latency_budget};
+use crate::monitoring::schema_drift;
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
 use crate::routes::transactions::handlers::{build_absolute_uri, offset_page_meta};
 use crate::routes::transactions::models::summary::{
@@ -21,7 +22,8 @@ pub async fn get_history_transactions(
     cursor: &Option<String>,
     timezone_offset: &Option<String>,
 ) -> ApiResult<Page<TransactionListItem>> {
-    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    info_provider.latency_budget(tx_history_latency_budget());
     let request_timezone_offset = timezone_offset
         .as_ref()
         .and_then(|it| it.parse::<i32>().ok())
@@ -65,6 +67,7 @@ pub async fn get_history_transactions(
         service_txs_to_tx_list_items(service_txs, prev_page_timestamp, request_timezone_offset)?;
 
     Ok(Page {
+        count: backend_paged_txs.count,
         next: build_cursor(
             context,
             chain_id,
@@ -84,6 +87,7 @@ pub async fn get_history_transactions(
             -1, // Direction backwards
         ),
         results: tx_list_items,
+        incomplete: info_provider.is_incomplete().then(|| true),
     })
 }
 
@@ -148,7 +152,7 @@ async fn fetch_backend_paged_txs(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    Ok(serde_json::from_str::<Page<Transaction>>(&body)?)
+    Ok(schema_drift::parse::<Page<Transaction>>(&body)?)
 }
 
 pub(super) async fn backend_txs_to_summary_txs(
@@ -189,6 +193,7 @@ pub(super) fn service_txs_to_tx_list_items(
             tx_list_items.push(TransactionListItem::Transaction {
                 transaction: tx,
                 conflict_type: ConflictType::None,
+                executability: None,
             })
         });
     }
@@ -240,7 +245,7 @@ pub(super) async fn get_creation_transaction_summary(
         .execute()
         .await?;
 
-    let creation_transaction_dto: CreationTransaction = serde_json::from_str(&body)?;
+    let creation_transaction_dto: CreationTransaction = schema_drift::parse(&body)?;
     let transaction_summary = creation_transaction_dto
         .to_transaction_summary(safe, info_provider)
         .await;
@@ -0,0 +1,147 @@
+//! Event logs of executed transactions, from the receipt of their ethereum transaction. Events of
+//! the Safe (`ExecutionSuccess`, `ExecutionFailure`, `SafeReceived`) and ERC20 `Transfer`s are
+//! decoded, other events are returned with their raw topics and data.
+use crate::common::models::addresses::AddressEx;
+use crate::providers::info::InfoProvider;
+use crate::providers::rpc::ReceiptLog;
+use crate::routes::transactions::handlers::execution_cost::transaction_receipt;
+use crate::routes::transactions::models::details::{LogParameter, TransactionLog};
+use crate::utils::checksum::to_checksum_address;
+use crate::utils::context::RequestContext;
+use crate::utils::transactions::decode_hex;
+use ethabi::{ParamType, Token};
+use ethcontract_common::hash::keccak256;
+
+struct KnownEvent {
+    name: &'static str,
+    signature: &'static str,
+    /// Name, type and whether the parameter is indexed
+    parameters: &'static [(&'static str, &'static str, bool)],
+}
+
+const KNOWN_EVENTS: [KnownEvent; 4] = [
+    KnownEvent {
+        name: "ExecutionSuccess",
+        signature: "ExecutionSuccess(bytes32,uint256)",
+        parameters: &[("txHash", "bytes32", false), ("payment", "uint256", false)],
+    },
+    KnownEvent {
+        name: "ExecutionFailure",
+        signature: "ExecutionFailure(bytes32,uint256)",
+        parameters: &[("txHash", "bytes32", false), ("payment", "uint256", false)],
+    },
+    KnownEvent {
+        name: "SafeReceived",
+        signature: "SafeReceived(address,uint256)",
+        parameters: &[("sender", "address", true), ("value", "uint256", false)],
+    },
+    // ERC721 transfers share the signature, but have the token id as fourth topic
+    KnownEvent {
+        name: "Transfer",
+        signature: "Transfer(address,address,uint256)",
+        parameters: &[
+            ("from", "address", true),
+            ("to", "address", true),
+            ("value", "uint256", false),
+        ],
+    },
+];
+
+// Best effort like the execution cost: the logs are omitted if the receipt can't be fetched
+pub(super) async fn transaction_logs(
+    context: &RequestContext,
+    info_provider: &(impl InfoProvider + Sync),
+    tx_hash: &str,
+) -> Option<Vec<TransactionLog>> {
+    let receipt = transaction_receipt(context, info_provider, tx_hash, true).await?;
+    let mut logs = vec![];
+    for log in receipt.logs.unwrap_or_default().iter() {
+        let address = info_provider
+            .address_ex_from_contracts(&log.address)
+            .await
+            .unwrap_or_else(|_| AddressEx::address_only(&to_checksum_address(&log.address)));
+        logs.push(transaction_log(log, address));
+    }
+    Some(logs)
+}
+
+pub fn transaction_log(log: &ReceiptLog, address: AddressEx) -> TransactionLog {
+    match decode_log(log) {
+        Some((event, parameters)) => TransactionLog {
+            log_index: log.log_index,
+            address,
+            event: Some(event.to_string()),
+            parameters,
+            topics: None,
+            data: None,
+        },
+        None => TransactionLog {
+            log_index: log.log_index,
+            address,
+            event: None,
+            parameters: vec![],
+            topics: Some(log.topics.clone()),
+            data: Some(log.data.to_string()),
+        },
+    }
+}
+
+/// Name and parameters of a well-known event, `None` for other events
+pub fn decode_log(log: &ReceiptLog) -> Option<(&'static str, Vec<LogParameter>)> {
+    let topic = log.topics.first()?.to_lowercase();
+    let event = KNOWN_EVENTS.iter().find(|event| {
+        to_hex_string!(keccak256(event.signature.as_bytes())) == topic
+            && event
+                .parameters
+                .iter()
+                .filter(|(_, _, indexed)| *indexed)
+                .count()
+                == log.topics.len() - 1
+    })?;
+
+    let mut topics = log.topics[1..].iter();
+    let data_types: Vec<ParamType> = event
+        .parameters
+        .iter()
+        .filter(|(_, _, indexed)| !indexed)
+        .map(|(_, param_type, _)| param_type_of(param_type))
+        .collect();
+    let mut data = ethabi::decode(&data_types, &decode_hex(&log.data).ok()?)
+        .ok()?
+        .into_iter();
+
+    let mut parameters = Vec::with_capacity(event.parameters.len());
+    for (name, param_type, indexed) in event.parameters.iter() {
+        let token = if *indexed {
+            let topic = decode_hex(topics.next()?).ok()?;
+            ethabi::decode(&[param_type_of(param_type)], &topic)
+                .ok()?
+                .pop()?
+        } else {
+            data.next()?
+        };
+        parameters.push(LogParameter {
+            name: name.to_string(),
+            param_type: param_type.to_string(),
+            value: token_value(&token)?,
+        });
+    }
+    Some((event.name, parameters))
+}
+
+fn param_type_of(param_type: &str) -> ParamType {
+    match param_type {
+        "address" => ParamType::Address,
+        "bytes32" => ParamType::FixedBytes(32),
+        _ => ParamType::Uint(256),
+    }
+}
+
+fn token_value(token: &Token) -> Option<String> {
+    match token {
+        Token::Address(address) => Some(to_checksum_address(&format!("{:#x}", address))),
+        Token::FixedBytes(bytes) => Some(to_hex_string!(bytes)),
+        Token::Uint(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
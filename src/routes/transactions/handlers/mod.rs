@@ -4,9 +4,21 @@ use rocket::http::uri::Origin;
 use std::cmp::max;
 
 pub mod details;
+pub mod execution_cost;
+pub mod expiry;
+pub mod hash_verification;
 pub mod history;
+pub mod logs;
+pub mod nonce;
+pub mod notes;
+pub mod owners;
 pub mod proposal;
 pub mod queued;
+pub mod ready_callbacks;
+pub mod replacement;
+pub mod signatures;
+pub mod transfer_history;
+pub mod transfers;
 
 #[cfg(test)]
 mod tests;
@@ -19,6 +31,6 @@ pub(super) fn offset_page_meta(meta: &PageMetadata, offset: i64) -> String {
     .to_url_string()
 }
 
-pub(super) fn build_absolute_uri(context: &RequestContext, origin: Origin) -> String {
+pub(crate) fn build_absolute_uri(context: &RequestContext, origin: Origin) -> String {
     format!("{}{}", context.host, origin)
 }
@@ -0,0 +1,73 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::common::models::page::Page;
+use crate::config::transaction_request_timeout;
+use crate::monitoring::schema_drift;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::transactions::handlers::expiry::ExpiryPolicy;
+use crate::routes::transactions::models::summary::TransactionSummary;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use chrono::{DateTime, Utc};
+
+// Far more than the proposals a nonce ever receives, the transaction service paginates anyway
+const MAX_TRANSACTIONS_PER_NONCE: usize = 100;
+
+/// Every multisig transaction proposed for `nonce`, executed, queued or replaced, in the order
+/// they were submitted
+pub async fn get_transactions_by_nonce(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    nonce: u64,
+    trusted: bool,
+) -> ApiResult<Vec<TransactionSummary>> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/multisig-transactions/?nonce={}&ordering=submissionDate&trusted={}&limit={}",
+        safe_address,
+        nonce,
+        trusted,
+        MAX_TRANSACTIONS_PER_NONCE
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let transactions: Page<MultisigTransaction> = schema_drift::parse(&body)?;
+    let safe_nonce = info_provider.safe_info(safe_address).await?.nonce;
+
+    Ok(to_summaries(
+        &info_provider,
+        &transactions.results,
+        &ExpiryPolicy::from_config(),
+        safe_nonce,
+        Utc::now(),
+    )
+    .await)
+}
+
+/// Transactions that can't be converted are left out, like in the transaction lists
+pub(super) async fn to_summaries(
+    info_provider: &(impl InfoProvider + Sync),
+    transactions: &[MultisigTransaction],
+    expiry_policy: &ExpiryPolicy,
+    safe_nonce: u64,
+    now: DateTime<Utc>,
+) -> Vec<TransactionSummary> {
+    let mut summaries = vec![];
+    for transaction in transactions {
+        for mut summary in transaction
+            .to_transaction_summary(info_provider)
+            .await
+            .unwrap_or_default()
+        {
+            if let Some(status) = expiry_policy.status(transaction, safe_nonce, now) {
+                summary.tx_status = status;
+            }
+            summaries.push(summary);
+        }
+    }
+    summaries
+}
@@ -0,0 +1,140 @@
+use crate::cache::Cache;
+use crate::routes::transactions::models::details::TransactionNote;
+use crate::routes::transactions::models::requests::TransactionNoteRequest;
+use crate::routes::transactions::models::TransactionIdParts;
+use crate::utils::context::RequestContext;
+use crate::utils::device::Device;
+use crate::utils::errors::ApiResult;
+use crate::utils::transaction_id::parse_id;
+use chrono::Utc;
+use serde_json::Value;
+use std::sync::Arc;
+
+// Notes are stored without expiry, one entry per device like the Safe labels
+const TRANSACTION_NOTES_KEY: &str = "transaction_notes";
+pub const MAX_NOTE_LENGTH: usize = 500;
+pub const MAX_NOTES_PER_DEVICE: usize = 1000;
+
+pub async fn set_transaction_note(
+    context: &RequestContext,
+    device: &Device,
+    chain_id: &str,
+    details_id: &str,
+    request: &TransactionNoteRequest,
+) -> ApiResult<Option<TransactionNote>> {
+    let cache = context.cache();
+    device.authenticate(&cache).await?;
+    let note = request.note.trim();
+    if note.chars().count() > MAX_NOTE_LENGTH {
+        return Err(client_error!(422, "Note is too long"));
+    }
+
+    let transaction_id = note_transaction_id(details_id)?;
+    let mut notes = stored_notes(&cache, &device.uuid).await;
+    update_notes(
+        &mut notes,
+        chain_id,
+        &transaction_id,
+        note,
+        Utc::now().timestamp_millis(),
+    );
+    if notes.len() > MAX_NOTES_PER_DEVICE {
+        return Err(client_error!(
+            422,
+            "Too many transaction notes for this device"
+        ));
+    }
+    cache
+        .insert_in_hash(
+            TRANSACTION_NOTES_KEY,
+            &device.uuid,
+            &serde_json::to_string(&notes)?,
+        )
+        .await;
+    Ok(find_note(&notes, chain_id, &transaction_id).cloned())
+}
+
+pub async fn get_transaction_notes(
+    context: &RequestContext,
+    device: &Device,
+) -> ApiResult<Vec<TransactionNote>> {
+    let cache = context.cache();
+    device.authenticate(&cache).await?;
+    Ok(stored_notes(&cache, &device.uuid).await)
+}
+
+/// Adds the note of the device to the (cached) transaction details `body`
+pub async fn with_device_note(
+    context: &RequestContext,
+    device: &Device,
+    chain_id: &str,
+    details_id: &str,
+    body: String,
+) -> ApiResult<String> {
+    let cache = context.cache();
+    device.authenticate(&cache).await?;
+    let notes = stored_notes(&cache, &device.uuid).await;
+    let note = find_note(&notes, chain_id, &note_transaction_id(details_id)?);
+    Ok(merge_note(body, note))
+}
+
+pub fn merge_note(body: String, note: Option<&TransactionNote>) -> String {
+    let note = match note {
+        Some(note) => note,
+        None => return body,
+    };
+    match serde_json::from_str::<Value>(&body) {
+        Ok(Value::Object(mut details)) => {
+            details.insert("note".to_string(), Value::String(note.note.to_string()));
+            Value::Object(details).to_string()
+        }
+        _ => body,
+    }
+}
+
+/// Replaces the note of the transaction on that chain, an empty note removes it
+pub fn update_notes(
+    notes: &mut Vec<TransactionNote>,
+    chain_id: &str,
+    transaction_id: &str,
+    note: &str,
+    updated_at: i64,
+) {
+    notes.retain(|stored| stored.chain_id != chain_id || stored.transaction_id != transaction_id);
+    if !note.is_empty() {
+        notes.push(TransactionNote {
+            chain_id: chain_id.to_string(),
+            transaction_id: transaction_id.to_string(),
+            note: note.to_string(),
+            updated_at,
+        });
+    }
+}
+
+/// Multisig transactions can be requested with their id or their `safe_tx_hash`, both share
+/// the note
+pub fn note_transaction_id(details_id: &str) -> ApiResult<String> {
+    Ok(match parse_id(details_id)? {
+        TransactionIdParts::Multisig { safe_tx_hash, .. } => safe_tx_hash.to_lowercase(),
+        TransactionIdParts::TransactionHash(safe_tx_hash) => safe_tx_hash.to_lowercase(),
+        _ => details_id.to_lowercase(),
+    })
+}
+
+fn find_note<'n>(
+    notes: &'n [TransactionNote],
+    chain_id: &str,
+    transaction_id: &str,
+) -> Option<&'n TransactionNote> {
+    notes
+        .iter()
+        .find(|note| note.chain_id == chain_id && note.transaction_id == transaction_id)
+}
+
+async fn stored_notes(cache: &Arc<dyn Cache>, device_uuid: &str) -> Vec<TransactionNote> {
+    cache
+        .get_from_hash(TRANSACTION_NOTES_KEY, device_uuid)
+        .await
+        .and_then(|notes| serde_json::from_str(&notes).ok())
+        .unwrap_or_default()
+}
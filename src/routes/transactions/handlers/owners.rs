@@ -0,0 +1,134 @@
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, SafeInfo};
+use crate::routes::transactions::handlers::transfers::{build_call, next_nonce, ZERO_ADDRESS};
+use crate::routes::transactions::models::requests::{OwnerChange, OwnerChangeRequest};
+use crate::routes::transactions::models::summary::TransactionBuild;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::transactions::parse_address;
+use crate::utils::validation::Validate;
+use ethabi::Token;
+use ethcontract_common::hash::keccak256;
+
+pub const ADD_OWNER_SIGNATURE: &str = "addOwnerWithThreshold(address,uint256)";
+pub const REMOVE_OWNER_SIGNATURE: &str = "removeOwner(address,address,uint256)";
+pub const SWAP_OWNER_SIGNATURE: &str = "swapOwner(address,address,address)";
+// Head of the linked list of owners in the Safe contract, the `prevOwner` of the first owner
+const SENTINEL_OWNERS: &str = "0x0000000000000000000000000000000000000001";
+
+pub async fn build_owner_change(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    request: &OwnerChangeRequest,
+) -> ApiResult<TransactionBuild> {
+    request.validated()?;
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_info = info_provider.safe_info(safe_address).await?;
+    let nonce = next_nonce(request.nonce, &safe_info)?;
+    let data = owner_change_data(request, &safe_info)?;
+
+    // Owners are managed by calls of the Safe to itself
+    build_call(
+        chain_id,
+        &safe_info,
+        safe_info.address.to_string(),
+        String::from("0"),
+        data,
+        nonce,
+    )
+}
+
+/// Calldata of the owner management call of the Safe. Changes the contract would revert are
+/// rejected with a `422`.
+pub fn owner_change_data(request: &OwnerChangeRequest, safe_info: &SafeInfo) -> ApiResult<String> {
+    let owners = &safe_info.owners;
+    let owner_index = owner_index(owners, &request.owner);
+    let (signature, tokens) = match request.change {
+        OwnerChange::Add => {
+            check_new_owner(owners, &request.owner)?;
+            let threshold = request.threshold.unwrap_or(safe_info.threshold);
+            check_threshold(threshold, owners.len() + 1)?;
+            (
+                ADD_OWNER_SIGNATURE,
+                vec![
+                    Token::Address(parse_address(&request.owner)?),
+                    Token::Uint(threshold.into()),
+                ],
+            )
+        }
+        OwnerChange::Remove => {
+            let index = owner_index.ok_or(client_error!(422, "Not an owner of the Safe"))?;
+            if owners.len() == 1 {
+                return Err(client_error!(422, "The last owner can't be removed"));
+            }
+            let remaining = owners.len() - 1;
+            let threshold = request
+                .threshold
+                .unwrap_or_else(|| safe_info.threshold.min(remaining as u64));
+            check_threshold(threshold, remaining)?;
+            (
+                REMOVE_OWNER_SIGNATURE,
+                vec![
+                    Token::Address(parse_address(prev_owner(owners, index))?),
+                    Token::Address(parse_address(&request.owner)?),
+                    Token::Uint(threshold.into()),
+                ],
+            )
+        }
+        OwnerChange::Swap => {
+            let index = owner_index.ok_or(client_error!(422, "Not an owner of the Safe"))?;
+            let new_owner = request
+                .new_owner
+                .as_ref()
+                .ok_or(client_error!(422, "No new owner provided"))?;
+            check_new_owner(owners, new_owner)?;
+            (
+                SWAP_OWNER_SIGNATURE,
+                vec![
+                    Token::Address(parse_address(prev_owner(owners, index))?),
+                    Token::Address(parse_address(&request.owner)?),
+                    Token::Address(parse_address(new_owner)?),
+                ],
+            )
+        }
+    };
+
+    let mut encoded = keccak256(signature.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&tokens));
+    Ok(to_hex_string!(encoded))
+}
+
+/// Owner pointing to `owners[index]` in the linked list of the contract
+pub fn prev_owner(owners: &[String], index: usize) -> &str {
+    if index == 0 {
+        SENTINEL_OWNERS
+    } else {
+        &owners[index - 1]
+    }
+}
+
+fn owner_index(owners: &[String], owner: &str) -> Option<usize> {
+    owners
+        .iter()
+        .position(|candidate| candidate.eq_ignore_ascii_case(owner))
+}
+
+fn check_new_owner(owners: &[String], owner: &str) -> ApiResult<()> {
+    if owner.eq_ignore_ascii_case(ZERO_ADDRESS) || owner.eq_ignore_ascii_case(SENTINEL_OWNERS) {
+        return Err(client_error!(422, "Invalid owner address"));
+    }
+    if owner_index(owners, owner).is_some() {
+        return Err(client_error!(422, "Already an owner of the Safe"));
+    }
+    Ok(())
+}
+
+fn check_threshold(threshold: u64, owners: usize) -> ApiResult<()> {
+    if threshold == 0 || threshold > owners as u64 {
+        return Err(client_error!(
+            422,
+            "Threshold must be between 1 and the amount of owners"
+        ));
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::cache::cache_operations::{Invalidate, InvalidationPattern, InvalidationScope};
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::transactions::handlers::signatures::check_confirmation;
 use crate::routes::transactions::models::requests::MultisigTransactionRequest;
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
@@ -13,6 +14,7 @@ pub async fn submit_confirmation(
     signature: &str,
 ) -> ApiResult<()> {
     let info_provider = DefaultInfoProvider::new(chain_id, context);
+    check_confirmation(context, &info_provider, safe_tx_hash, signature).await?;
     let url = core_uri!(
         info_provider,
         "/v1/multisig-transactions/{}/confirmations/",
@@ -31,7 +33,9 @@ pub async fn submit_confirmation(
         InvalidationPattern::Any(InvalidationScope::Both, String::from(safe_tx_hash)),
         context.cache(),
     )
-    .execute();
+    .source("CONFIRMATION")
+    .execute()
+    .await;
     Ok(())
 }
 
@@ -60,7 +64,9 @@ pub async fn propose_transaction(
         InvalidationPattern::Any(InvalidationScope::Both, String::from(safe_address)),
         context.cache(),
     )
-    .execute();
+    .source("PROPOSAL")
+    .execute()
+    .await;
     Invalidate::new(
         InvalidationPattern::Any(
             InvalidationScope::Both,
@@ -68,6 +74,8 @@ pub async fn propose_transaction(
         ),
         context.cache(),
     )
-    .execute();
+    .source("PROPOSAL")
+    .execute()
+    .await;
     Ok(())
 }
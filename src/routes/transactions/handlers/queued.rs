@@ -1,14 +1,34 @@
 use crate::cache::cache_operations::RequestCached;
 use crate::common::models::backend::transactions::MultisigTransaction;
 use crate::common::models::page::{Page, PageMetadata};
-use crate::config::transaction_request_timeout;
-use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::config::{
+    transaction_request_timeout, tx_queued_latency_budget, tx_queued_poll_interval,
+    tx_queued_poll_max_wait, tx_queued_summary_size,
+};
+use crate::monitoring::schema_drift;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, SafeInfo};
+use crate::routes::transactions::handlers::details::estimate_execution;
+use crate::routes::transactions::handlers::expiry::{set_expiry_statuses, ExpiryPolicy};
+use crate::routes::transactions::handlers::ready_callbacks;
 use crate::routes::transactions::handlers::{build_absolute_uri, offset_page_meta};
-use crate::routes::transactions::models::summary::{ConflictType, Label, TransactionListItem};
+use crate::routes::transactions::models::details::ExecutionEstimation;
+use crate::routes::transactions::models::summary::{
+    ConflictType, Label, QueueDiff, QueueDiffItem, QueueSummary, TransactionListItem,
+};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
+use chrono::Utc;
+use ethcontract_common::hash::keccak256;
 use itertools::Itertools;
+use rocket::futures::future::join_all;
+use rocket::tokio::time::sleep;
+use std::cmp::min;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const QUEUED_SNAPSHOT_KEY_BASE: &str = "queued_snapshot";
+// Long enough for clients polling the queue, while keeping few snapshots per Safe around
+const QUEUED_SNAPSHOT_DURATION: usize = 10 * 60 * 1000;
 
 // use https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.peekable
 pub async fn get_queued_transactions(
@@ -18,8 +38,10 @@ pub async fn get_queued_transactions(
     cursor: &Option<String>,
     timezone_offset: &Option<String>,
     trusted: &Option<bool>,
+    check_executability: bool,
 ) -> ApiResult<Page<TransactionListItem>> {
     let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    info_provider.latency_budget(tx_queued_latency_budget());
 
     // Parse page meta (offset and limit)
     let page_meta = PageMetadata::from_cursor(cursor.as_ref().unwrap_or(&"".to_string()));
@@ -44,18 +66,42 @@ pub async fn get_queued_transactions(
         .request_timeout(transaction_request_timeout())
         .execute()
         .await?;
-    let mut backend_transactions: Page<MultisigTransaction> = serde_json::from_str(&body)?;
+    let mut backend_transactions: Page<MultisigTransaction> = schema_drift::parse(&body)?;
+    ready_callbacks::notify_ready(
+        context,
+        chain_id,
+        safe_address,
+        &backend_transactions.results,
+    )
+    .await;
 
     // We need to do this before we create the iterator
     // Nonce of the first item in the next page (-1 if not present)
     let edge_nonce = get_edge_nonce(&mut backend_transactions);
 
+    let executabilities = if check_executability {
+        estimate_executabilities(
+            context,
+            &info_provider,
+            safe_nonce,
+            &backend_transactions.results,
+        )
+        .await
+    } else {
+        HashMap::new()
+    };
+    let expiry_statuses = ExpiryPolicy::from_config().statuses(
+        &backend_transactions.results,
+        safe_nonce as u64,
+        Utc::now(),
+    );
+
     // Use an iterator to avoid shifting the result vector (would potentially trigger copies)
     let mut tx_iter = backend_transactions.results.into_iter();
     // Nonce of the last item in the previous page (-1 if not present)
     let previous_page_nonce = get_previous_page_nonce(&page_meta, &mut tx_iter);
 
-    let service_transactions = process_transactions(
+    let mut service_transactions = process_transactions(
         &mut info_provider,
         safe_nonce,
         &mut tx_iter,
@@ -63,8 +109,11 @@ pub async fn get_queued_transactions(
         edge_nonce,
     )
     .await;
+    set_executabilities(&mut service_transactions, executabilities);
+    set_expiry_statuses(&mut service_transactions, expiry_statuses);
 
     Ok(Page {
+        count: backend_transactions.count,
         next: build_cursor(
             context,
             &chain_id,
@@ -86,9 +135,55 @@ pub async fn get_queued_transactions(
             -1, // Direction backwards
         ),
         results: service_transactions,
+        incomplete: info_provider.is_incomplete().then(|| true),
     })
 }
 
+/// Simulated executions of the transactions that can be executed right away (nonce of the Safe and
+/// enough confirmations) by transaction id. Later nonces would always revert, as the signatures
+/// are checked against the current nonce.
+async fn estimate_executabilities(
+    context: &RequestContext,
+    info_provider: &(impl InfoProvider + Sync),
+    safe_nonce: i64,
+    transactions: &[MultisigTransaction],
+) -> HashMap<String, ExecutionEstimation> {
+    join_all(
+        transactions
+            .iter()
+            .filter(|transaction| {
+                let confirmations = transaction.confirmations.as_ref().map_or(0, Vec::len);
+                transaction.nonce as i64 == safe_nonce
+                    && confirmations as u64 >= transaction.confirmations_required.unwrap_or(1)
+            })
+            .map(|transaction| async move {
+                estimate_execution(context, info_provider, transaction)
+                    .await
+                    .map(|estimation| (transaction.generate_id(), estimation))
+            }),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub(super) fn set_executabilities(
+    items: &mut Vec<TransactionListItem>,
+    executabilities: HashMap<String, ExecutionEstimation>,
+) {
+    for item in items.iter_mut() {
+        if let TransactionListItem::Transaction {
+            transaction,
+            executability,
+            ..
+        } = item
+        {
+            *executability = executabilities.get(&transaction.id).cloned();
+        }
+    }
+}
+
 // Nonce of the first item in the next page (-1 if not present)
 pub(super) fn get_edge_nonce(backend_transactions: &mut Page<MultisigTransaction>) -> i64 {
     // If there is a next url we remove the last item for information on the next page
@@ -268,6 +363,289 @@ pub(super) async fn add_transaction_as_summary(
         items.push(TransactionListItem::Transaction {
             transaction: summary,
             conflict_type: tx_conflict_type,
+            executability: None,
         });
     }
 }
+
+/// Waits until the serialized queue differs from the one identified by `etag`.
+/// Returns the new etag and page, or `None` if nothing changed within the max wait time.
+///
+/// The page is only converted again once the `queued_fingerprint` changed, every other tick
+/// costs a single (usually cached) backend request.
+pub async fn poll_queued_transactions(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    etag: &Option<String>,
+    cursor: &Option<String>,
+    timezone_offset: &Option<String>,
+    trusted: &Option<bool>,
+) -> ApiResult<Option<(String, String)>> {
+    let deadline = Instant::now() + Duration::from_millis(tx_queued_poll_max_wait());
+    let poll_interval = Duration::from_millis(tx_queued_poll_interval());
+    // Clients may send the etag as received in the header, which is quoted
+    let known_etag = etag.as_ref().map(|it| it.trim_matches('"').to_string());
+    let mut known_fingerprint = None;
+    loop {
+        let fingerprint = queued_fingerprint(context, chain_id, safe_address, trusted).await?;
+        if known_fingerprint.as_ref() != Some(&fingerprint) {
+            // Safe infos are memoized per context, a fresh one sees the nonce after executions
+            let tick_context = RequestContext::new(
+                context.request_id.to_string(),
+                context.host.to_string(),
+                context.http_client(),
+                context.cache(),
+            );
+            // Simulations would change the etag on every poll
+            let page = get_queued_transactions(
+                &tick_context,
+                chain_id,
+                safe_address,
+                cursor,
+                timezone_offset,
+                trusted,
+                false,
+            )
+            .await?;
+            let body = serde_json::to_string(&page)?;
+            let current_etag = queued_etag(&body);
+            if known_etag.as_ref() != Some(&current_etag) {
+                return Ok(Some((current_etag, body)));
+            }
+            known_fingerprint = Some(fingerprint);
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(None);
+        }
+        sleep(min(remaining, poll_interval)).await;
+    }
+}
+
+/// Hash of the most recently modified multisig transaction of the Safe (and the amount of them),
+/// which changes whenever a transaction is proposed, confirmed, executed or deleted. Backend
+/// responses are cached until a hook invalidates them, so this is cheap while nothing changed.
+pub(super) async fn queued_fingerprint(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    trusted: &Option<bool>,
+) -> ApiResult<String> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/multisig-transactions/?ordering=-modified&trusted={}&limit=1",
+        safe_address,
+        trusted.unwrap_or(true)
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    Ok(queued_etag(&body))
+}
+
+pub(super) fn queued_etag(body: &str) -> String {
+    to_hex_string!(keccak256(body.as_bytes()))
+}
+
+/// Changes of the queue page since the page identified by `since_etag`. Every served page is
+/// stored as a snapshot (item keys and the hashes of their content) for `QUEUED_SNAPSHOT_DURATION`,
+/// so that the next request can be answered with the changed items only.
+pub async fn get_queued_diff(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    since_etag: &Option<String>,
+    cursor: &Option<String>,
+    timezone_offset: &Option<String>,
+    trusted: &Option<bool>,
+) -> ApiResult<QueueDiff> {
+    let page = get_queued_transactions(
+        context,
+        chain_id,
+        safe_address,
+        cursor,
+        timezone_offset,
+        trusted,
+        false,
+    )
+    .await?;
+    let etag = queued_etag(&serde_json::to_string(&page)?);
+    let cache = context.cache();
+    let known_snapshot = match since_etag.as_ref() {
+        Some(since_etag) => cache
+            .fetch(&queued_snapshot_key(
+                chain_id,
+                safe_address,
+                since_etag.trim_matches('"'),
+            ))
+            .await
+            .and_then(|snapshot| serde_json::from_str(&snapshot).ok()),
+        None => None,
+    };
+    let is_incomplete = page.incomplete.is_some();
+
+    let (diff, snapshot) = diff_queue(page, etag, known_snapshot.as_ref())?;
+    // Items cut short by the latency budget would show up as changed on the next request
+    if !is_incomplete {
+        cache
+            .create(
+                &queued_snapshot_key(chain_id, safe_address, &diff.etag),
+                &serde_json::to_string(&snapshot)?,
+                QUEUED_SNAPSHOT_DURATION,
+            )
+            .await;
+    }
+    Ok(diff)
+}
+
+fn queued_snapshot_key(chain_id: &str, safe_address: &str, etag: &str) -> String {
+    format!(
+        "{}_{}_{}_{}",
+        QUEUED_SNAPSHOT_KEY_BASE, chain_id, safe_address, etag
+    )
+}
+
+/// The diff of `page` against `known_snapshot` (everything is added without one), and the
+/// snapshot of `page`
+pub(super) fn diff_queue(
+    page: Page<TransactionListItem>,
+    etag: String,
+    known_snapshot: Option<&HashMap<String, String>>,
+) -> ApiResult<(QueueDiff, HashMap<String, String>)> {
+    let mut diff = QueueDiff {
+        etag,
+        reset: known_snapshot.is_none(),
+        count: page.count,
+        next: page.next,
+        previous: page.previous,
+        order: vec![],
+        added: vec![],
+        changed: vec![],
+        removed: vec![],
+    };
+    let mut snapshot = HashMap::with_capacity(page.results.len());
+    for item in page.results.into_iter() {
+        let mut key = queue_item_key(&item);
+        // A multisig transaction converted into several summaries shares its id
+        let mut duplicates = 1;
+        while snapshot.contains_key(&key) {
+            duplicates += 1;
+            key = format!("{}_{}", queue_item_key(&item), duplicates);
+        }
+        let content_hash = to_hex_string!(keccak256(serde_json::to_vec(&item)?));
+        match known_snapshot.and_then(|known_snapshot| known_snapshot.get(&key)) {
+            Some(known_hash) if *known_hash == content_hash => {}
+            Some(_) => diff.changed.push(QueueDiffItem {
+                key: key.clone(),
+                item,
+            }),
+            None => diff.added.push(QueueDiffItem {
+                key: key.clone(),
+                item,
+            }),
+        }
+        diff.order.push(key.clone());
+        snapshot.insert(key, content_hash);
+    }
+    if let Some(known_snapshot) = known_snapshot {
+        diff.removed = known_snapshot
+            .keys()
+            .filter(|key| !snapshot.contains_key(*key))
+            .cloned()
+            .sorted()
+            .collect();
+    }
+    Ok((diff, snapshot))
+}
+
+pub(super) fn queue_item_key(item: &TransactionListItem) -> String {
+    match item {
+        TransactionListItem::Transaction { transaction, .. } => transaction.id.to_string(),
+        TransactionListItem::DateLabel { timestamp } => format!("DATE_LABEL_{}", timestamp),
+        TransactionListItem::Label { label } => match label {
+            Label::Next => String::from("LABEL_NEXT"),
+            Label::Queued => String::from("LABEL_QUEUED"),
+        },
+        TransactionListItem::ConflictHeader { nonce } => format!("CONFLICT_HEADER_{}", nonce),
+    }
+}
+
+pub async fn get_queued_summary(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    signer: &Option<String>,
+    trusted: &Option<bool>,
+) -> ApiResult<QueueSummary> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_info = info_provider.safe_info(safe_address).await?;
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/multisig-transactions/?limit={}&nonce__gte={}&ordering=nonce,submissionDate&trusted={}",
+        safe_address,
+        tx_queued_summary_size(),
+        safe_info.nonce,
+        trusted.unwrap_or(true)
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let backend_transactions: Page<MultisigTransaction> = schema_drift::parse(&body)?;
+
+    let mut summary = queue_summary(&backend_transactions.results, &safe_info, signer.as_deref());
+    summary.incomplete = backend_transactions.next.map(|_| true);
+    Ok(summary)
+}
+
+pub(super) fn queue_summary(
+    transactions: &[MultisigTransaction],
+    safe_info: &SafeInfo,
+    signer: Option<&str>,
+) -> QueueSummary {
+    let mut nonce_counts: HashMap<u64, usize> = HashMap::new();
+    for transaction in transactions {
+        *nonce_counts.entry(transaction.nonce).or_insert(0) += 1;
+    }
+    let is_owner = |address: &str| {
+        safe_info
+            .owners
+            .iter()
+            .any(|owner| owner.eq_ignore_ascii_case(address))
+    };
+    let signer = signer.filter(|signer| is_owner(signer));
+
+    let mut summary = QueueSummary {
+        total: transactions.len() as u64,
+        awaiting_signature: signer.map(|_| 0),
+        awaiting_execution: 0,
+        conflicting: 0,
+        incomplete: None,
+    };
+    for transaction in transactions {
+        let confirmations = transaction.confirmations.as_deref().unwrap_or(&[]);
+        let required = transaction
+            .confirmations_required
+            .unwrap_or(safe_info.threshold);
+        if confirmations.len() as u64 >= required {
+            summary.awaiting_execution += 1;
+        } else if let Some(signer) = signer {
+            let has_signed = confirmations
+                .iter()
+                .any(|confirmation| confirmation.owner.eq_ignore_ascii_case(signer));
+            if !has_signed {
+                summary.awaiting_signature = summary.awaiting_signature.map(|count| count + 1);
+            }
+        }
+        if nonce_counts
+            .get(&transaction.nonce)
+            .map_or(false, |count| *count > 1)
+        {
+            summary.conflicting += 1;
+        }
+    }
+    summary
+}
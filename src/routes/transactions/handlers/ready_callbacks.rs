@@ -0,0 +1,239 @@
+//! Callbacks registered by integrators for their Safes, notified with a `READY_TO_EXECUTE` event
+//! once a queued transaction has all required confirmations, so that automation doesn't need to
+//! poll the queue. Transactions are checked whenever the queue is loaded from the transaction
+//! service and on `NEW_CONFIRMATION` hooks, each of them is notified once.
+use crate::cache::cache_operations::RequestCached;
+use crate::cache::Cache;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::config::{feature_flag_ready_callbacks, transaction_request_timeout};
+use crate::monitoring::schema_drift;
+use crate::providers::info::DefaultInfoProvider;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{HttpClient, Request};
+use crate::utils::outbound;
+use chrono::Utc;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Callbacks are stored without expiry, one entry per chain
+const READY_CALLBACKS_KEY: &str = "ready_callbacks";
+const READY_NOTIFIED_KEY_BASE: &str = "ready_notified";
+// Queued transactions are rarely left fully confirmed for longer
+const READY_NOTIFIED_DURATION: usize = 7 * 24 * 60 * 60 * 1000;
+const READY_EVENT_TYPE: &str = "READY_TO_EXECUTE";
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyCallback {
+    pub url: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub chain_id: String,
+    pub safe_address: String,
+    pub safe_tx_hash: String,
+    pub nonce: u64,
+    pub confirmations: usize,
+    pub confirmations_required: u64,
+    pub timestamp: i64,
+}
+
+/// Callback urls of a chain, by lowercase Safe address
+pub async fn chain_callbacks(cache: &dyn Cache, chain_id: &str) -> HashMap<String, String> {
+    cache
+        .get_from_hash(READY_CALLBACKS_KEY, chain_id)
+        .await
+        .and_then(|callbacks| serde_json::from_str(&callbacks).ok())
+        .unwrap_or_default()
+}
+
+pub async fn get_callback(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe_address: &str,
+) -> Option<ReadyCallback> {
+    chain_callbacks(cache, chain_id)
+        .await
+        .remove(&safe_address.to_lowercase())
+        .map(|url| ReadyCallback { url })
+}
+
+/// Replaces the callback of the Safe, `None` removes it
+pub async fn set_callback(
+    cache: &dyn Cache,
+    chain_id: &str,
+    safe_address: &str,
+    callback: Option<&ReadyCallback>,
+) -> ApiResult<Option<ReadyCallback>> {
+    if let Some(callback) = callback {
+        let is_http = Url::parse(&callback.url).map_or(false, |url| {
+            url.scheme() == "https" || url.scheme() == "http"
+        });
+        if !is_http {
+            return Err(client_error!(
+                422,
+                "Callback url is not a valid http(s) url"
+            ));
+        }
+    }
+    let mut callbacks = chain_callbacks(cache, chain_id).await;
+    match callback {
+        Some(callback) => callbacks.insert(safe_address.to_lowercase(), callback.url.to_string()),
+        None => callbacks.remove(&safe_address.to_lowercase()),
+    };
+    cache
+        .insert_in_hash(
+            READY_CALLBACKS_KEY,
+            chain_id,
+            &serde_json::to_string(&callbacks)?,
+        )
+        .await;
+    Ok(callback.cloned())
+}
+
+pub fn is_ready(transaction: &MultisigTransaction) -> bool {
+    let confirmations = transaction.confirmations.as_ref().map_or(0, Vec::len);
+    !transaction.is_executed
+        && transaction
+            .confirmations_required
+            .map_or(false, |required| {
+                required > 0 && confirmations as u64 >= required
+            })
+}
+
+pub fn ready_event(
+    chain_id: &str,
+    safe_address: &str,
+    transaction: &MultisigTransaction,
+    timestamp: i64,
+) -> Option<ReadyEvent> {
+    if !is_ready(transaction) {
+        return None;
+    }
+    Some(ReadyEvent {
+        event_type: String::from(READY_EVENT_TYPE),
+        chain_id: chain_id.to_string(),
+        safe_address: safe_address.to_string(),
+        safe_tx_hash: transaction.safe_tx_hash.to_string(),
+        nonce: transaction.nonce,
+        confirmations: transaction.confirmations.as_ref().map_or(0, Vec::len),
+        confirmations_required: transaction.confirmations_required.unwrap_or_default(),
+        timestamp,
+    })
+}
+
+/// Posts an event, in the background, for every fully confirmed transaction that was not notified
+/// before. Nothing is checked if no callback is registered for the Safe.
+pub async fn notify_ready(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    transactions: &[MultisigTransaction],
+) {
+    if !feature_flag_ready_callbacks() {
+        return;
+    }
+    let cache = context.cache();
+    let callback = match get_callback(cache.as_ref(), chain_id, safe_address).await {
+        Some(callback) => callback,
+        None => return,
+    };
+    let now = Utc::now().timestamp_millis();
+    for event in transactions
+        .iter()
+        .filter_map(|transaction| ready_event(chain_id, safe_address, transaction, now))
+    {
+        let notified_key = format!(
+            "{}_{}_{}",
+            READY_NOTIFIED_KEY_BASE, chain_id, event.safe_tx_hash
+        );
+        if cache.has_key(&notified_key).await {
+            continue;
+        }
+        cache
+            .create(&notified_key, "", READY_NOTIFIED_DURATION)
+            .await;
+
+        let http_client = context.http_client();
+        let url = callback.url.to_string();
+        rocket::tokio::spawn(async move {
+            if let Err(error) = send_event(http_client, &url, &event, RETRY_DELAY).await {
+                log::warn!(
+                    "{} event for {} could not be sent to {}: {}",
+                    READY_EVENT_TYPE,
+                    event.safe_tx_hash,
+                    url,
+                    error
+                );
+            }
+        });
+    }
+}
+
+/// Checks a single transaction, e.g. after a `NEW_CONFIRMATION` hook
+pub async fn check_transaction(
+    context: &RequestContext,
+    chain_id: &str,
+    safe_address: &str,
+    safe_tx_hash: &str,
+) -> ApiResult<()> {
+    if !feature_flag_ready_callbacks()
+        || get_callback(context.cache().as_ref(), chain_id, safe_address)
+            .await
+            .is_none()
+    {
+        return Ok(());
+    }
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let url = core_uri!(info_provider, "/v1/multisig-transactions/{}/", safe_tx_hash)?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let transaction: MultisigTransaction = schema_drift::parse(&body)?;
+    notify_ready(
+        context,
+        chain_id,
+        safe_address,
+        std::slice::from_ref(&transaction),
+    )
+    .await;
+    Ok(())
+}
+
+/// Tries up to `MAX_ATTEMPTS` times, doubling `retry_delay` after every failed attempt
+pub async fn send_event(
+    http_client: Arc<dyn HttpClient>,
+    url: &str,
+    event: &ReadyEvent,
+    retry_delay: Duration,
+) -> ApiResult<()> {
+    // Registered by an operator, like the hosts of the chain configs
+    outbound::allow_url(url);
+    let body = serde_json::to_string(event)?;
+    let mut delay = retry_delay;
+    let mut attempt = 1;
+    loop {
+        let mut request = Request::new(url.to_string());
+        request.body(Some(body.to_string()));
+        match http_client.post(request).await {
+            Ok(_) => return Ok(()),
+            Err(error) if attempt >= MAX_ATTEMPTS => return Err(error),
+            Err(_) => {
+                rocket::tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::common::models::page::Page;
+use crate::config::transaction_request_timeout;
+use crate::monitoring::schema_drift;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::transactions::models::requests::ReplacementPreviewRequest;
+use crate::routes::transactions::models::summary::ReplacementPreview;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+
+pub async fn get_replacement_preview(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    request: &ReplacementPreviewRequest,
+) -> ApiResult<ReplacementPreview> {
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_nonce = info_provider.safe_info(safe_address).await?.nonce;
+    if request.nonce < safe_nonce {
+        return Err(client_error!(422, "Nonce has already been used"));
+    }
+
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/multisig-transactions/?nonce={}&executed=false&ordering=submissionDate",
+        safe_address,
+        request.nonce
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let backend_transactions: Page<MultisigTransaction> = schema_drift::parse(&body)?;
+
+    let mut replaced_transactions = vec![];
+    for transaction in replaceable_transactions(backend_transactions.results, request.nonce) {
+        // Transactions that cannot be converted are still replaced, but cannot be displayed
+        if let Ok(summaries) = transaction.to_transaction_summary(&info_provider).await {
+            replaced_transactions.extend(summaries);
+        }
+    }
+
+    Ok(ReplacementPreview {
+        nonce: request.nonce,
+        replaced_transactions,
+    })
+}
+
+pub(super) fn replaceable_transactions(
+    transactions: Vec<MultisigTransaction>,
+    nonce: u64,
+) -> Vec<MultisigTransaction> {
+    transactions
+        .into_iter()
+        .filter(|transaction| transaction.nonce == nonce && !transaction.is_executed)
+        .collect()
+}
@@ -0,0 +1,254 @@
+//! Pre-check of submitted confirmations, as the transaction service rejects invalid signatures
+//! with messages clients can't act on. The signer is recovered from the signature (EIP-712,
+//! `eth_sign`, approved hashes and EIP-1271 contract signatures, verified via `eth_call` with the
+//! current or the legacy interface) and has
+//! to be an owner of the Safe that didn't confirm the transaction yet. Rejections carry one of the
+//! `*_CODE` error codes.
+use crate::cache::cache_operations::RequestCached;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::config::{feature_flag_confirmation_signature_check, transaction_request_timeout};
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, SafeInfo};
+use crate::providers::rpc::{EthCall, RpcProvider, RpcResult};
+use crate::routes::transactions::handlers::transfers::ZERO_ADDRESS;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::safe_version::parse_base_version;
+use crate::utils::signatures::{safe_signature, SafeSignature, SignatureFailure};
+use crate::utils::transactions::{
+    decode_hex, safe_tx_hash_parts, use_legacy_domain_separator, SafeTransactionFields,
+};
+use ethabi::Token;
+use ethcontract_common::hash::keccak256;
+
+pub const MALFORMED_SIGNATURE_CODE: u64 = 2001;
+pub const UNRECOVERABLE_SIGNATURE_CODE: u64 = 2002;
+pub const NOT_AN_OWNER_CODE: u64 = 2003;
+pub const ALREADY_CONFIRMED_CODE: u64 = 2004;
+pub const INVALID_CONTRACT_SIGNATURE_CODE: u64 = 2005;
+
+pub const IS_VALID_SIGNATURE_SIGNATURE: &str = "isValidSignature(bytes32,bytes)";
+pub const LEGACY_IS_VALID_SIGNATURE_SIGNATURE: &str = "isValidSignature(bytes,bytes)";
+// Returned by `isValidSignature(bytes32,bytes)` for valid signatures
+const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+// Returned by `isValidSignature(bytes,bytes)` for valid signatures
+const EIP1271_LEGACY_MAGIC_VALUE: [u8; 4] = [0x20, 0xc1, 0x3b, 0x0b];
+
+pub async fn check_confirmation(
+    context: &RequestContext,
+    info_provider: &DefaultInfoProvider<'_>,
+    safe_tx_hash: &str,
+    signature: &str,
+) -> ApiResult<()> {
+    if !feature_flag_confirmation_signature_check() {
+        return Ok(());
+    }
+    let hash = decode_hex(safe_tx_hash)
+        .ok()
+        .filter(|hash| hash.len() == 32)
+        .ok_or_else(|| client_error!(422, "Invalid safe_tx_hash"))?;
+    let signature = decode_hex(signature).map_err(|_| {
+        signature_error(
+            MALFORMED_SIGNATURE_CODE,
+            String::from("Signature is not hex encoded"),
+        )
+    })?;
+    let signature = safe_signature(&signature, &hash).map_err(failure_error)?;
+
+    let url = core_uri!(info_provider, "/v1/multisig-transactions/{}/", safe_tx_hash)?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let multisig_tx: MultisigTransaction = serde_json::from_str(&body)?;
+    let safe_info = info_provider
+        .safe_info(&multisig_tx.safe_transaction.safe)
+        .await?;
+    let confirmed_by: Vec<String> = multisig_tx
+        .confirmations
+        .iter()
+        .flatten()
+        .map(|confirmation| confirmation.owner.to_string())
+        .collect();
+    check_signer(&signature, &safe_info.owners, &confirmed_by)?;
+
+    if let SafeSignature::Contract { owner, signature } = &signature {
+        let chain_info = info_provider.chain_info().await?;
+        let rpc_provider = RpcProvider::new(context, &chain_info);
+        let preimage = safe_tx_hash_preimage(info_provider.chain_id, &safe_info, &multisig_tx)?;
+        check_contract_signature(&rpc_provider, owner, &hash, &preimage, signature).await?;
+    }
+    Ok(())
+}
+
+/// The signer has to be an owner that didn't confirm yet
+pub fn check_signer(
+    signature: &SafeSignature,
+    owners: &[String],
+    confirmed_by: &[String],
+) -> ApiResult<()> {
+    let signer = signature.owner();
+    if !owners
+        .iter()
+        .any(|owner| owner.eq_ignore_ascii_case(signer))
+    {
+        return Err(signature_error(
+            NOT_AN_OWNER_CODE,
+            format!("Signer {} is not an owner of the Safe", signer),
+        ));
+    }
+    if confirmed_by
+        .iter()
+        .any(|owner| owner.eq_ignore_ascii_case(signer))
+    {
+        return Err(ApiError::new_with_error_code(
+            409,
+            ALREADY_CONFIRMED_CODE,
+            format!("Owner {} already confirmed the transaction", signer),
+        ));
+    }
+    Ok(())
+}
+
+// Contract signatures are only rejected if the owner contract rejects them, they are forwarded
+// unchecked if the node can't be reached. Owners only implementing the legacy interface revert on
+// `isValidSignature(bytes32,bytes)` and are asked with the data of the hash instead.
+async fn check_contract_signature(
+    rpc_provider: &RpcProvider,
+    owner: &str,
+    safe_tx_hash: &[u8],
+    safe_tx_hash_preimage: &[u8],
+    signature: &[u8],
+) -> ApiResult<()> {
+    let data = is_valid_signature_data(safe_tx_hash, signature);
+    let is_valid = match call_owner(rpc_provider, owner, data).await {
+        Some(Ok(result)) => is_magic_value(&result),
+        Some(Err(_)) => {
+            let data = legacy_is_valid_signature_data(safe_tx_hash_preimage, signature);
+            match call_owner(rpc_provider, owner, data).await {
+                Some(Ok(result)) => is_legacy_magic_value(&result),
+                Some(Err(_)) => false,
+                None => true,
+            }
+        }
+        None => true,
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(signature_error(
+            INVALID_CONTRACT_SIGNATURE_CODE,
+            format!("Contract signature of {} is not valid", owner),
+        ))
+    }
+}
+
+// `None` if the node can't be reached
+async fn call_owner(
+    rpc_provider: &RpcProvider,
+    owner: &str,
+    data: String,
+) -> Option<RpcResult<String>> {
+    let call = EthCall {
+        from: None,
+        to: owner.to_string(),
+        data,
+        value: None,
+    };
+    match rpc_provider.call(&call).await {
+        Ok(result) => Some(result),
+        Err(error) => {
+            log::debug!(
+                "Contract signature of {} could not be verified: {}",
+                owner,
+                error
+            );
+            None
+        }
+    }
+}
+
+/// Data hashed to the `safeTxHash` (`0x1901`, domain hash and message hash), it is what Safes
+/// before 1.3.0 pass to `isValidSignature(bytes,bytes)` of their contract owners
+pub fn safe_tx_hash_preimage(
+    chain_id: &str,
+    safe_info: &SafeInfo,
+    multisig_tx: &MultisigTransaction,
+) -> ApiResult<Vec<u8>> {
+    let safe_transaction = &multisig_tx.safe_transaction;
+    let safe_tx_gas = multisig_tx.safe_tx_gas.unwrap_or(0).to_string();
+    let base_gas = multisig_tx.base_gas.unwrap_or(0).to_string();
+    let fields = SafeTransactionFields {
+        to: &safe_transaction.to,
+        value: safe_transaction.value.as_deref().unwrap_or("0"),
+        data: safe_transaction.data.as_deref().unwrap_or("0x"),
+        operation: safe_transaction.operation as u8,
+        safe_tx_gas: &safe_tx_gas,
+        base_gas: &base_gas,
+        gas_price: multisig_tx.gas_price.as_deref().unwrap_or("0"),
+        gas_token: multisig_tx.gas_token.as_deref().unwrap_or(ZERO_ADDRESS),
+        refund_receiver: multisig_tx
+            .refund_receiver
+            .as_deref()
+            .unwrap_or(ZERO_ADDRESS),
+        nonce: multisig_tx.nonce,
+    };
+    let version = safe_info
+        .version
+        .as_ref()
+        .and_then(|version| parse_base_version(version));
+    let parts = safe_tx_hash_parts(
+        chain_id,
+        &safe_info.address,
+        use_legacy_domain_separator(version),
+        &fields,
+    )?;
+    let mut preimage = vec![0x19, 0x01];
+    preimage.extend_from_slice(&parts.domain_hash);
+    preimage.extend_from_slice(&parts.message_hash);
+    Ok(preimage)
+}
+
+pub fn is_valid_signature_data(safe_tx_hash: &[u8], signature: &[u8]) -> String {
+    let mut encoded = keccak256(IS_VALID_SIGNATURE_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[
+        Token::FixedBytes(safe_tx_hash.to_vec()),
+        Token::Bytes(signature.to_vec()),
+    ]));
+    to_hex_string!(encoded)
+}
+
+pub fn legacy_is_valid_signature_data(data: &[u8], signature: &[u8]) -> String {
+    let mut encoded = keccak256(LEGACY_IS_VALID_SIGNATURE_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[
+        Token::Bytes(data.to_vec()),
+        Token::Bytes(signature.to_vec()),
+    ]));
+    to_hex_string!(encoded)
+}
+
+/// Whether the `bytes4` returned by `isValidSignature` is the EIP-1271 magic value
+pub fn is_magic_value(result: &str) -> bool {
+    decode_hex(result).map_or(false, |data| data.starts_with(&EIP1271_MAGIC_VALUE))
+}
+
+/// Whether the `bytes4` returned by `isValidSignature(bytes,bytes)` is the legacy magic value
+pub fn is_legacy_magic_value(result: &str) -> bool {
+    decode_hex(result).map_or(false, |data| data.starts_with(&EIP1271_LEGACY_MAGIC_VALUE))
+}
+
+fn failure_error(failure: SignatureFailure) -> ApiError {
+    match failure {
+        SignatureFailure::Malformed => signature_error(
+            MALFORMED_SIGNATURE_CODE,
+            String::from("Signature is not a valid Safe signature"),
+        ),
+        SignatureFailure::NotRecoverable => signature_error(
+            UNRECOVERABLE_SIGNATURE_CODE,
+            String::from("Signer could not be recovered from the signature"),
+        ),
+    }
+}
+
+fn signature_error(code: u64, message: String) -> ApiError {
+    ApiError::new_with_error_code(422, code, message)
+}
@@ -0,0 +1,174 @@
+use crate::cache::MockCache;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::providers::info::MockInfoProvider;
+use crate::routes::transactions::handlers::details::estimate_execution;
+use crate::routes::transactions::models::details::ExecutionEstimation;
+use crate::testing::builders::ChainInfoBuilder;
+use crate::testing::setup::setup_rocket_with_mock_cache;
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::{MockHttpClient, Response};
+use mockall::predicate::eq;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::json;
+
+const CACHED_ID: &str = "multisig_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621";
+const INCOMPLETE_ID: &str = "multisig_0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+async fn client(mock_cache: MockCache) -> Client {
+    Client::tracked(setup_rocket_with_mock_cache(
+        MockHttpClient::new(),
+        mock_cache,
+        routes![super::super::super::routes::post_transactions_details],
+    ))
+    .await
+    .expect("valid rocket instance")
+}
+
+async fn post_details(client: &Client, transaction_ids: &[&str]) -> (Status, serde_json::Value) {
+    let response = client
+        .post("/v1/chains/4/transactions/details")
+        .header(Header::new("Host", "test.gnosis.io"))
+        .header(ContentType::JSON)
+        .body(json!({ "transactionIds": transaction_ids }).to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    let body = serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    (status, body)
+}
+
+#[rocket::async_test]
+async fn post_transactions_details_shares_single_details_cache() {
+    let cached_key = format!("c_resp_/v1/chains/4/transactions/{}", CACHED_ID);
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .with(eq(cached_key.to_string()))
+        .times(1)
+        .return_const(Some(String::from("{\"txId\":\"cached\"}")));
+    mock_cache
+        .expect_ttl()
+        .with(eq(cached_key))
+        .return_const(Some(1000));
+
+    let client = client(mock_cache).await;
+    let (status, actual) = post_details(&client, &[CACHED_ID]).await;
+
+    assert_eq!(status, Status::Ok);
+    assert_eq!(
+        actual,
+        json!([{
+            "transactionId": CACHED_ID,
+            "status": 200,
+            "details": { "txId": "cached" }
+        }])
+    );
+}
+
+#[rocket::async_test]
+async fn post_transactions_details_failures_as_entries() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .with(eq(format!(
+            "c_resp_/v1/chains/4/transactions/{}",
+            INCOMPLETE_ID
+        )))
+        .times(1)
+        .return_const(None);
+
+    let client = client(mock_cache).await;
+    let (status, actual) = post_details(&client, &[INCOMPLETE_ID]).await;
+
+    assert_eq!(status, Status::Ok);
+    assert_eq!(
+        actual,
+        json!([{
+            "transactionId": INCOMPLETE_ID,
+            "status": 422,
+            "error": { "code": 1337, "message": "No safe tx hash provided" }
+        }])
+    );
+}
+
+#[rocket::async_test]
+async fn post_transactions_details_rejects_empty_request() {
+    let client = client(MockCache::new()).await;
+    let (status, actual) = post_details(&client, &[]).await;
+
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(
+        actual["arguments"],
+        json!(["transactionIds: must contain between 1 and TRANSACTION_DETAILS_BATCH_SIZE ids"])
+    );
+}
+
+async fn estimation(rpc_body: &'static str) -> Option<ExecutionEstimation> {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_post()
+        .times(1)
+        .return_once(move |_| {
+            Ok(Response {
+                status_code: 200,
+                body: rpc_body.to_string(),
+            })
+        });
+    let context = RequestContext::mock(
+        String::from("/v1/chains/4/transactions/multisig_0x1"),
+        String::from("localhost"),
+        mock_http_client,
+        MockCache::new(),
+    );
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_chain_info()
+        .times(1)
+        .return_once(|| Ok(ChainInfoBuilder::new("4").build()));
+    let multisig_tx: MultisigTransaction =
+        serde_json::from_str(crate::tests::json::MULTISIG_TX_AWAITING_EXECUTION).unwrap();
+
+    estimate_execution(&context, &mock_info_provider, &multisig_tx).await
+}
+
+#[rocket::async_test]
+async fn estimate_execution_with_gas_estimate() {
+    let actual = estimation(r#"{"jsonrpc":"2.0","id":1,"result":"0x15f90"}"#).await;
+
+    assert_eq!(
+        actual,
+        Some(ExecutionEstimation {
+            gas_estimate: Some(String::from("90000")),
+            will_revert: false,
+            revert_reason: None,
+        })
+    );
+}
+
+#[rocket::async_test]
+async fn estimate_execution_reverting() {
+    let actual = estimation(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":3,"message":"execution reverted: GS013","data":"0x08c379a0"}}"#,
+    )
+    .await;
+
+    assert_eq!(
+        actual,
+        Some(ExecutionEstimation {
+            gas_estimate: None,
+            will_revert: true,
+            revert_reason: Some(String::from("execution reverted: GS013")),
+        })
+    );
+}
+
+#[rocket::async_test]
+async fn estimate_execution_omitted_on_node_error() {
+    let actual = estimation(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"daily request count exceeded, request rate limited"}}"#,
+    )
+    .await;
+
+    assert_eq!(actual, None);
+}
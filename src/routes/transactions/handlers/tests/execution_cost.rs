@@ -0,0 +1,99 @@
+use crate::cache::MockCache;
+use crate::providers::rpc::{RpcProvider, TransactionReceipt};
+use crate::routes::balances::models::TokenPrice;
+use crate::routes::transactions::handlers::execution_cost::cost_from_receipt;
+use crate::routes::transactions::models::details::ExecutionCost;
+use crate::testing::builders::ChainInfoBuilder;
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::{MockHttpClient, Response};
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+fn eth_price(fiat_price: &str) -> TokenPrice {
+    TokenPrice {
+        address: String::from("0x0000000000000000000000000000000000000000"),
+        fiat_code: String::from("USD"),
+        fiat_price: BigDecimal::from_str(fiat_price).unwrap(),
+        timestamp: String::from("2021-11-25T09:43:43.000Z"),
+    }
+}
+
+#[test]
+fn execution_cost_in_native_coin_and_fiat() {
+    let receipt = TransactionReceipt {
+        gas_used: 100000,
+        effective_gas_price: Some(50000000000),
+        logs: None,
+    };
+
+    let actual = cost_from_receipt(&receipt, Some("1"), 18, Some(&eth_price("4000.5")));
+
+    assert_eq!(
+        actual,
+        Some(ExecutionCost {
+            gas_used: String::from("100000"),
+            effective_gas_price: String::from("50000000000"),
+            native_cost: String::from("5000000000000000"),
+            fiat_cost: Some(String::from("20.00250")),
+            fiat_code: Some(String::from("USD")),
+        })
+    );
+}
+
+#[test]
+fn execution_cost_without_effective_gas_price() {
+    let receipt = TransactionReceipt {
+        gas_used: 21000,
+        effective_gas_price: None,
+        logs: None,
+    };
+
+    let actual = cost_from_receipt(&receipt, Some("1000000000"), 18, None);
+
+    assert_eq!(
+        actual,
+        Some(ExecutionCost {
+            gas_used: String::from("21000"),
+            effective_gas_price: String::from("1000000000"),
+            native_cost: String::from("21000000000000"),
+            fiat_cost: None,
+            fiat_code: None,
+        })
+    );
+    assert_eq!(cost_from_receipt(&receipt, None, 18, None), None);
+}
+
+#[rocket::async_test]
+async fn transaction_receipt_from_rpc() {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_post().times(1).return_once(|_| {
+        Ok(Response {
+            status_code: 200,
+            body: String::from(
+                r#"{"jsonrpc":"2.0","id":1,"result":{"gasUsed":"0x186a0","effectiveGasPrice":"0xba43b7400","status":"0x1"}}"#,
+            ),
+        })
+    });
+    let context = RequestContext::mock(
+        String::from("/v1/chains/1/transactions/multisig_0x1_0x2"),
+        String::from("localhost"),
+        mock_http_client,
+        MockCache::new(),
+    );
+    let chain_info = ChainInfoBuilder::new("1").build();
+
+    let actual = RpcProvider::new(&context, &chain_info)
+        .transaction_receipt("0x1")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        actual,
+        TransactionReceipt {
+            gas_used: 100000,
+            effective_gas_price: Some(50000000000),
+            logs: Some(vec![]),
+        }
+    );
+}
@@ -0,0 +1,122 @@
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::routes::transactions::handlers::expiry::{set_expiry_statuses, ExpiryPolicy};
+use crate::routes::transactions::models::summary::{ConflictType, Label, TransactionListItem};
+use crate::routes::transactions::models::TransactionStatus;
+use crate::testing::builders::TransactionSummaryBuilder;
+use crate::tests::json::{MULTISIG_TX_AWAITING_CONFIRMATIONS, MULTISIG_TX_SETTINGS_CHANGE};
+use chrono::{DateTime, TimeZone, Utc};
+
+// Submitted 2020-07-24T09:50:56Z with nonce 147
+fn queued_transaction() -> MultisigTransaction {
+    serde_json::from_str(MULTISIG_TX_AWAITING_CONFIRMATIONS).unwrap()
+}
+
+fn policy(expiry_days: u64, mark_obsolete: bool) -> ExpiryPolicy {
+    ExpiryPolicy {
+        expiry_days,
+        mark_obsolete,
+    }
+}
+
+fn days_after_submission(days: i64) -> DateTime<Utc> {
+    Utc.ymd(2020, 7, 24).and_hms(9, 50, 56) + chrono::Duration::days(days)
+}
+
+#[test]
+fn expiry_policy_disabled() {
+    let policy = policy(0, false);
+
+    assert!(!policy.is_enabled());
+    assert_eq!(
+        policy.status(&queued_transaction(), 147, days_after_submission(1000)),
+        None
+    );
+    assert_eq!(
+        policy.status(&queued_transaction(), 148, days_after_submission(1000)),
+        None
+    );
+}
+
+#[test]
+fn expiry_policy_expires_old_transactions() {
+    let policy = policy(30, false);
+
+    assert!(policy.is_enabled());
+    assert_eq!(
+        policy.status(&queued_transaction(), 147, days_after_submission(29)),
+        None
+    );
+    assert_eq!(
+        policy.status(&queued_transaction(), 147, days_after_submission(30)),
+        Some(TransactionStatus::Expired)
+    );
+    // Below the Safe nonce transactions are cancelled, unless marked as obsolete
+    assert_eq!(
+        policy.status(&queued_transaction(), 148, days_after_submission(30)),
+        None
+    );
+}
+
+#[test]
+fn expiry_policy_marks_obsolete_transactions() {
+    let policy = policy(30, true);
+
+    assert_eq!(
+        policy.status(&queued_transaction(), 148, days_after_submission(1)),
+        Some(TransactionStatus::Obsolete)
+    );
+    assert_eq!(
+        policy.status(&queued_transaction(), 148, days_after_submission(30)),
+        Some(TransactionStatus::Obsolete)
+    );
+}
+
+#[test]
+fn expiry_policy_ignores_executed_transactions() {
+    let executed: MultisigTransaction = serde_json::from_str(MULTISIG_TX_SETTINGS_CHANGE).unwrap();
+
+    assert_eq!(
+        policy(1, true).status(&executed, executed.nonce + 1, Utc::now()),
+        None
+    );
+}
+
+#[test]
+fn expiry_statuses_by_transaction_id() {
+    let transactions = vec![queued_transaction()];
+
+    let actual = policy(30, false).statuses(&transactions, 147, days_after_submission(30));
+
+    assert_eq!(actual.len(), 1);
+    assert_eq!(
+        actual.get(&transactions[0].generate_id()),
+        Some(&TransactionStatus::Expired)
+    );
+}
+
+#[test]
+fn set_expiry_statuses_by_transaction_id() {
+    let transaction_item = |id: &str| TransactionListItem::Transaction {
+        transaction: TransactionSummaryBuilder::new(id).build(),
+        conflict_type: ConflictType::None,
+        executability: None,
+    };
+    let mut items = vec![
+        TransactionListItem::Label { label: Label::Next },
+        transaction_item("multisig_0x1_0x2"),
+        transaction_item("multisig_0x1_0x3"),
+    ];
+    let statuses = vec![(String::from("multisig_0x1_0x3"), TransactionStatus::Expired)]
+        .into_iter()
+        .collect();
+
+    set_expiry_statuses(&mut items, statuses);
+
+    let tx_status = |item: &TransactionListItem| match item {
+        TransactionListItem::Transaction { transaction, .. } => Some(&transaction.tx_status),
+        _ => None,
+    };
+    assert_eq!(tx_status(&items[0]), None);
+    assert_eq!(tx_status(&items[1]), Some(&TransactionStatus::Success));
+    assert_eq!(tx_status(&items[2]), Some(&TransactionStatus::Expired));
+}
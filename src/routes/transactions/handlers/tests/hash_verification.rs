@@ -0,0 +1,135 @@
+use crate::common::models::data_decoded::Operation;
+use crate::providers::info::SafeInfo;
+use crate::routes::transactions::handlers::hash_verification::safe_tx_hash_verification;
+use crate::routes::transactions::models::requests::SafeTxHashVerificationRequest;
+use crate::routes::transactions::models::summary::{MismatchCause, SafeTxDomain};
+use crate::testing::builders::SafeInfoBuilder;
+use crate::utils::validation::Validate;
+
+const SAFE_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+// Same transaction as crate::tests::json::MULTISIG_TX_CUSTOM, see utils::tests::transactions
+const LEGACY_SAFE_TX_HASH: &str =
+    "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621";
+const V130_SAFE_TX_HASH: &str =
+    "0x80ffa0f09842ff2294aef07141c9f89271260040e17a209e68fea9135dcd18c8";
+
+fn safe_info(version: &str) -> SafeInfo {
+    SafeInfoBuilder::new(SAFE_ADDRESS)
+        .version(Some(version))
+        .build()
+}
+
+fn request(safe_tx_hash: &str) -> SafeTxHashVerificationRequest {
+    SafeTxHashVerificationRequest {
+        safe_address: SAFE_ADDRESS.to_string(),
+        to: String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"),
+        value: String::from("0"),
+        data: Some(String::from("0x095ea7b3000000000000000000000000ae9844f89d98c150f5e61bfc676d68b4921559900000000000000000000000000000000000000000000000000001c6bf52634000")),
+        operation: Operation::CALL,
+        safe_tx_gas: String::from("43485"),
+        base_gas: String::from("0"),
+        gas_price: String::from("0"),
+        gas_token: None,
+        refund_receiver: None,
+        nonce: 84,
+        safe_tx_hash: safe_tx_hash.to_string(),
+        domain_hash: None,
+        message_hash: None,
+    }
+}
+
+#[test]
+fn safe_tx_hash_verification_valid() {
+    let actual =
+        safe_tx_hash_verification("4", &safe_info("1.3.0"), &request(V130_SAFE_TX_HASH)).unwrap();
+
+    assert!(actual.is_valid);
+    assert_eq!(actual.safe_tx_hash, V130_SAFE_TX_HASH);
+    assert!(actual.mismatches.is_empty());
+    assert_eq!(actual.likely_cause, None);
+    assert_eq!(
+        actual.domain,
+        SafeTxDomain {
+            chain_id: Some(String::from("4")),
+            verifying_contract: SAFE_ADDRESS.to_string(),
+            version: Some(String::from("1.3.0")),
+        }
+    );
+}
+
+#[test]
+fn safe_tx_hash_verification_legacy_domain_without_chain_id() {
+    let actual = safe_tx_hash_verification(
+        "4",
+        &safe_info("1.1.1"),
+        &request(&LEGACY_SAFE_TX_HASH.to_uppercase().replace("0X", "0x")),
+    )
+    .unwrap();
+
+    assert!(actual.is_valid);
+    assert_eq!(actual.safe_tx_hash, LEGACY_SAFE_TX_HASH);
+    assert_eq!(actual.domain.chain_id, None);
+}
+
+#[test]
+fn safe_tx_hash_verification_reports_other_domain_version() {
+    let actual =
+        safe_tx_hash_verification("4", &safe_info("1.3.0"), &request(LEGACY_SAFE_TX_HASH)).unwrap();
+
+    assert!(!actual.is_valid);
+    assert_eq!(actual.mismatches.len(), 1);
+    assert_eq!(actual.mismatches[0].field, "safeTxHash");
+    assert_eq!(actual.mismatches[0].expected, V130_SAFE_TX_HASH);
+    assert_eq!(actual.mismatches[0].submitted, LEGACY_SAFE_TX_HASH);
+    assert_eq!(actual.likely_cause, Some(MismatchCause::DomainVersion));
+}
+
+#[test]
+fn safe_tx_hash_verification_reports_domain_mismatch() {
+    let mut request = request(LEGACY_SAFE_TX_HASH);
+    request.domain_hash = Some(String::from(
+        "0x0000000000000000000000000000000000000000000000000000000000000001",
+    ));
+
+    let actual = safe_tx_hash_verification("4", &safe_info("1.3.0"), &request).unwrap();
+
+    assert!(!actual.is_valid);
+    let fields: Vec<&str> = actual
+        .mismatches
+        .iter()
+        .map(|mismatch| mismatch.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["safeTxHash", "domainHash"]);
+    assert_eq!(actual.likely_cause, Some(MismatchCause::Domain));
+}
+
+#[test]
+fn safe_tx_hash_verification_reports_transaction_fields_mismatch() {
+    let expected =
+        safe_tx_hash_verification("4", &safe_info("1.3.0"), &request(V130_SAFE_TX_HASH)).unwrap();
+    let mut request = request(LEGACY_SAFE_TX_HASH);
+    request.nonce = 85;
+    request.domain_hash = Some(expected.domain_hash.to_string());
+
+    let actual = safe_tx_hash_verification("4", &safe_info("1.3.0"), &request).unwrap();
+
+    assert!(!actual.is_valid);
+    assert_eq!(actual.mismatches.len(), 1);
+    assert_eq!(actual.likely_cause, Some(MismatchCause::TransactionFields));
+}
+
+#[test]
+fn safe_tx_hash_verification_request_validation() {
+    let mut request = request("0x1234");
+    request.to = String::from("0x1");
+
+    let arguments = request.validated().unwrap_err().details.arguments.unwrap();
+
+    assert_eq!(
+        arguments,
+        vec![
+            "to: must be a 0x prefixed address",
+            "safeTxHash: must be a 0x prefixed 32 bytes hash"
+        ]
+    );
+}
@@ -0,0 +1,138 @@
+use crate::common::models::addresses::AddressEx;
+use crate::providers::rpc::ReceiptLog;
+use crate::routes::transactions::handlers::logs::{decode_log, transaction_log};
+use crate::routes::transactions::models::details::{LogParameter, TransactionLog};
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const OWNER_TOPIC: &str = "0x000000000000000000000000f2cea96575d6b10f51d9af3b10e3e4e5738aa6bd";
+const SAFE_TOPIC: &str = "0x0000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b";
+const VALUE_DATA: &str = "0x00000000000000000000000000000000000000000000000000000000000003e8";
+
+fn log(topics: Vec<&str>, data: &str) -> ReceiptLog {
+    ReceiptLog {
+        log_index: 7,
+        address: SAFE.to_lowercase(),
+        topics: topics.into_iter().map(String::from).collect(),
+        data: data.to_string(),
+    }
+}
+
+fn parameter(name: &str, param_type: &str, value: &str) -> LogParameter {
+    LogParameter {
+        name: name.to_string(),
+        param_type: param_type.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn decode_log_execution_success() {
+    let log = log(
+        vec!["0x442e715f626346e8c54381002da614f62bee8d27386535b2521ec8540898556e"],
+        "0x0ebb2c317f55c96469e0ed2014f5833dc02a70b42f0ac52f4630938900caa6980000000000000000000000000000000000000000000000000000000000000000",
+    );
+
+    let actual = decode_log(&log);
+
+    assert_eq!(
+        actual,
+        Some((
+            "ExecutionSuccess",
+            vec![
+                parameter(
+                    "txHash",
+                    "bytes32",
+                    "0x0ebb2c317f55c96469e0ed2014f5833dc02a70b42f0ac52f4630938900caa698"
+                ),
+                parameter("payment", "uint256", "0"),
+            ]
+        ))
+    );
+}
+
+#[test]
+fn decode_log_safe_received() {
+    let log = log(
+        vec![
+            "0x3d0ce9bfc3ed7d6862dbb28b2dea94561fe714a1b4d019aa8af39730d1ad7c3d",
+            OWNER_TOPIC,
+        ],
+        VALUE_DATA,
+    );
+
+    let actual = decode_log(&log);
+
+    assert_eq!(
+        actual,
+        Some((
+            "SafeReceived",
+            vec![
+                parameter(
+                    "sender",
+                    "address",
+                    "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd"
+                ),
+                parameter("value", "uint256", "1000"),
+            ]
+        ))
+    );
+}
+
+#[test]
+fn decode_log_erc20_transfer() {
+    let log = log(vec![TRANSFER_TOPIC, SAFE_TOPIC, OWNER_TOPIC], VALUE_DATA);
+
+    let actual = decode_log(&log);
+
+    assert_eq!(
+        actual,
+        Some((
+            "Transfer",
+            vec![
+                parameter("from", "address", SAFE),
+                parameter(
+                    "to",
+                    "address",
+                    "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd"
+                ),
+                parameter("value", "uint256", "1000"),
+            ]
+        ))
+    );
+}
+
+#[test]
+fn decode_log_erc721_transfer_is_not_decoded() {
+    let token_id_topic = "0x0000000000000000000000000000000000000000000000000000000000000001";
+    let log = log(
+        vec![TRANSFER_TOPIC, SAFE_TOPIC, OWNER_TOPIC, token_id_topic],
+        "0x",
+    );
+
+    assert_eq!(decode_log(&log), None);
+}
+
+#[test]
+fn transaction_log_keeps_raw_log_of_unknown_events() {
+    let log = log(
+        vec!["0x141df868a6331af528e38c83b7aa03edc19be66e37ae67f9285bf4f8e3c6a1a8"],
+        VALUE_DATA,
+    );
+
+    let actual = transaction_log(&log, AddressEx::address_only(SAFE));
+
+    assert_eq!(
+        actual,
+        TransactionLog {
+            log_index: 7,
+            address: AddressEx::address_only(SAFE),
+            event: None,
+            parameters: vec![],
+            topics: Some(vec![String::from(
+                "0x141df868a6331af528e38c83b7aa03edc19be66e37ae67f9285bf4f8e3c6a1a8"
+            )]),
+            data: Some(String::from(VALUE_DATA)),
+        }
+    );
+}
@@ -1,3 +1,16 @@
+mod details;
+mod execution_cost;
+mod expiry;
+mod hash_verification;
+mod logs;
+mod nonce;
+mod notes;
+mod owners;
 mod parse_id;
+mod ready_callbacks;
+mod signatures;
 pub mod transactions_history;
 pub mod transactions_queued;
+pub mod transactions_replacement;
+mod transfer_history;
+mod transfers;
@@ -0,0 +1,78 @@
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::providers::info::MockInfoProvider;
+use crate::routes::transactions::handlers::expiry::ExpiryPolicy;
+use crate::routes::transactions::handlers::nonce::to_summaries;
+use crate::routes::transactions::models::TransactionStatus;
+use crate::testing::builders::SafeInfoBuilder;
+use crate::tests::json::{MULTISIG_TX_AWAITING_CONFIRMATIONS, MULTISIG_TX_AWAITING_EXECUTION};
+use chrono::{TimeZone, Utc};
+
+const SAFE_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+fn transactions() -> Vec<MultisigTransaction> {
+    vec![
+        serde_json::from_str(MULTISIG_TX_AWAITING_CONFIRMATIONS).unwrap(),
+        serde_json::from_str(MULTISIG_TX_AWAITING_EXECUTION).unwrap(),
+    ]
+}
+
+fn mock_info_provider(safe_nonce: u64) -> MockInfoProvider {
+    let safe_info = SafeInfoBuilder::new(SAFE_ADDRESS)
+        .nonce(safe_nonce)
+        .threshold(2)
+        .build();
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_safe_info()
+        .returning(move |_| Ok(safe_info.clone()));
+    mock_info_provider
+        .expect_token_info()
+        .returning(|_| bail!("No token info"));
+    mock_info_provider
+        .expect_address_ex_from_any_source()
+        .returning(|_| bail!("No address info"));
+    mock_info_provider
+}
+
+#[rocket::async_test]
+async fn replaced_transactions_of_the_nonce_are_cancelled() {
+    let policy = ExpiryPolicy {
+        expiry_days: 0,
+        mark_obsolete: false,
+    };
+
+    let actual = to_summaries(
+        &mock_info_provider(1000),
+        &transactions(),
+        &policy,
+        1000,
+        Utc.ymd(2021, 11, 25).and_hms(0, 0, 0),
+    )
+    .await;
+
+    assert_eq!(actual.len(), 2);
+    assert!(actual
+        .iter()
+        .all(|summary| summary.tx_status == TransactionStatus::Cancelled));
+}
+
+#[rocket::async_test]
+async fn replaced_transactions_of_the_nonce_with_expiry_policy() {
+    let policy = ExpiryPolicy {
+        expiry_days: 0,
+        mark_obsolete: true,
+    };
+
+    let actual = to_summaries(
+        &mock_info_provider(1000),
+        &transactions(),
+        &policy,
+        1000,
+        Utc.ymd(2021, 11, 25).and_hms(0, 0, 0),
+    )
+    .await;
+
+    assert!(actual
+        .iter()
+        .all(|summary| summary.tx_status == TransactionStatus::Obsolete));
+}
@@ -0,0 +1,79 @@
+use crate::routes::transactions::handlers::notes::{merge_note, note_transaction_id, update_notes};
+use crate::routes::transactions::models::details::TransactionNote;
+use serde_json::{json, Value};
+
+const SAFE_TX_HASH: &str = "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621";
+
+fn note(chain_id: &str, transaction_id: &str, note: &str) -> TransactionNote {
+    TransactionNote {
+        chain_id: chain_id.to_string(),
+        transaction_id: transaction_id.to_string(),
+        note: note.to_string(),
+        updated_at: 1000,
+    }
+}
+
+#[test]
+fn note_transaction_id_shared_by_multisig_id_and_safe_tx_hash() {
+    let multisig_id = format!(
+        "multisig_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_{}",
+        SAFE_TX_HASH.to_uppercase().replace("0X", "0x")
+    );
+
+    assert_eq!(note_transaction_id(&multisig_id).unwrap(), SAFE_TX_HASH);
+    assert_eq!(note_transaction_id(SAFE_TX_HASH).unwrap(), SAFE_TX_HASH);
+}
+
+#[test]
+fn note_transaction_id_of_other_transactions() {
+    let module_id = "module_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x3e4d_i1a2b";
+
+    assert_eq!(
+        note_transaction_id(module_id).unwrap(),
+        module_id.to_lowercase()
+    );
+}
+
+#[test]
+fn update_notes_replaces_note_of_transaction() {
+    let mut notes = vec![
+        note("4", SAFE_TX_HASH, "Payroll"),
+        note("1", SAFE_TX_HASH, "Mainnet payroll"),
+    ];
+
+    update_notes(&mut notes, "4", SAFE_TX_HASH, "Payroll March", 2000);
+
+    assert_eq!(
+        notes,
+        vec![
+            note("1", SAFE_TX_HASH, "Mainnet payroll"),
+            TransactionNote {
+                updated_at: 2000,
+                ..note("4", SAFE_TX_HASH, "Payroll March")
+            },
+        ]
+    );
+}
+
+#[test]
+fn update_notes_empty_note_removes_it() {
+    let mut notes = vec![note("4", SAFE_TX_HASH, "Payroll")];
+
+    update_notes(&mut notes, "4", SAFE_TX_HASH, "", 2000);
+
+    assert!(notes.is_empty());
+}
+
+#[test]
+fn merge_note_adds_note_to_details() {
+    let body = json!({"txId": "multisig_0x1230_0x65df", "txStatus": "SUCCESS"}).to_string();
+    let stored = note("4", SAFE_TX_HASH, "Payroll");
+
+    let actual: Value = serde_json::from_str(&merge_note(body.to_string(), Some(&stored))).unwrap();
+
+    assert_eq!(
+        actual,
+        json!({"txId": "multisig_0x1230_0x65df", "txStatus": "SUCCESS", "note": "Payroll"})
+    );
+    assert_eq!(merge_note(body.to_string(), None), body);
+}
@@ -0,0 +1,143 @@
+use crate::providers::info::SafeInfo;
+use crate::routes::transactions::handlers::owners::{owner_change_data, prev_owner};
+use crate::routes::transactions::models::requests::{OwnerChange, OwnerChangeRequest};
+use crate::testing::builders::SafeInfoBuilder;
+use crate::utils::validation::Validate;
+
+const OWNER_A: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const OWNER_B: &str = "0xF353eBBa77e5E71c210599236686D51cA1F88b84";
+const OWNER_C: &str = "0xBe8C10Dbf4c6148f9834C56C3331f8191f355552";
+
+fn safe_info() -> SafeInfo {
+    SafeInfoBuilder::new("0x4cb09344de5bCCD45F045c5Defa0E0452869FF0f")
+        .owners(&[OWNER_A, OWNER_B])
+        .threshold(2)
+        .build()
+}
+
+fn request(change: OwnerChange, owner: &str, new_owner: Option<&str>) -> OwnerChangeRequest {
+    OwnerChangeRequest {
+        change,
+        owner: owner.to_string(),
+        new_owner: new_owner.map(|new_owner| new_owner.to_string()),
+        threshold: None,
+        nonce: None,
+    }
+}
+
+#[test]
+fn prev_owner_of_first_owner_is_sentinel() {
+    let owners = vec![OWNER_A.to_string(), OWNER_B.to_string()];
+
+    assert_eq!(
+        prev_owner(&owners, 0),
+        "0x0000000000000000000000000000000000000001"
+    );
+    assert_eq!(prev_owner(&owners, 1), OWNER_A);
+}
+
+#[test]
+fn owner_change_data_add_keeps_threshold() {
+    let actual =
+        owner_change_data(&request(OwnerChange::Add, OWNER_C, None), &safe_info()).unwrap();
+
+    assert_eq!(
+        actual,
+        "0x0d582f13\
+        000000000000000000000000be8c10dbf4c6148f9834c56c3331f8191f355552\
+        0000000000000000000000000000000000000000000000000000000000000002"
+    );
+}
+
+#[test]
+fn owner_change_data_remove_lowers_threshold() {
+    let lowercase_owner = OWNER_B.to_lowercase();
+
+    let actual = owner_change_data(
+        &request(OwnerChange::Remove, &lowercase_owner, None),
+        &safe_info(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        actual,
+        "0xf8dc5dd9\
+        0000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b\
+        000000000000000000000000f353ebba77e5e71c210599236686d51ca1f88b84\
+        0000000000000000000000000000000000000000000000000000000000000001"
+    );
+}
+
+#[test]
+fn owner_change_data_swap_first_owner() {
+    let actual = owner_change_data(
+        &request(OwnerChange::Swap, OWNER_A, Some(OWNER_C)),
+        &safe_info(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        actual,
+        "0xe318b52b\
+        0000000000000000000000000000000000000000000000000000000000000001\
+        0000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b\
+        000000000000000000000000be8c10dbf4c6148f9834c56c3331f8191f355552"
+    );
+}
+
+#[test]
+fn owner_change_data_rejects_reverting_changes() {
+    let message = |request: OwnerChangeRequest, safe_info: SafeInfo| {
+        owner_change_data(&request, &safe_info)
+            .unwrap_err()
+            .details
+            .message
+            .unwrap()
+    };
+    let mut too_high_threshold = request(OwnerChange::Add, OWNER_C, None);
+    too_high_threshold.threshold = Some(4);
+    let single_owner = SafeInfoBuilder::new(OWNER_C).owners(&[OWNER_A]).build();
+
+    assert_eq!(
+        message(request(OwnerChange::Add, OWNER_B, None), safe_info()),
+        "Already an owner of the Safe"
+    );
+    assert_eq!(
+        message(too_high_threshold, safe_info()),
+        "Threshold must be between 1 and the amount of owners"
+    );
+    assert_eq!(
+        message(request(OwnerChange::Remove, OWNER_C, None), safe_info()),
+        "Not an owner of the Safe"
+    );
+    assert_eq!(
+        message(request(OwnerChange::Remove, OWNER_A, None), single_owner),
+        "The last owner can't be removed"
+    );
+    assert_eq!(
+        message(
+            request(
+                OwnerChange::Swap,
+                OWNER_A,
+                Some("0x0000000000000000000000000000000000000001")
+            ),
+            safe_info()
+        ),
+        "Invalid owner address"
+    );
+}
+
+#[test]
+fn owner_change_request_new_owner_for_swaps_only() {
+    let mut request = request(OwnerChange::Add, OWNER_C, Some(OWNER_B));
+    request.change = OwnerChange::Swap;
+    request.threshold = Some(1);
+
+    let arguments = request.validated().unwrap_err().details.arguments.unwrap();
+    assert_eq!(arguments, vec!["threshold: can't be changed by swaps"]);
+
+    request.change = OwnerChange::Add;
+    request.threshold = None;
+    let arguments = request.validated().unwrap_err().details.arguments.unwrap();
+    assert_eq!(arguments, vec!["newOwner: must be set for swaps only"]);
+}
@@ -1,5 +1,8 @@
-use crate::routes::transactions::handlers::details::parse_id;
-use crate::routes::transactions::models::TransactionIdParts;
+use crate::routes::transactions::models::{
+    TransactionIdParts, TransactionIdType, TransactionRawIds,
+};
+use crate::utils::transaction_id::{build_id, parse_id};
+use serde_json::json;
 
 #[test]
 fn multisig_details_id() {
@@ -75,3 +78,55 @@ fn failure_details_id() {
 
     parse_id(malformed_details_id).unwrap();
 }
+
+#[test]
+fn build_id_round_trips_parse_id() {
+    let ids = [
+        "multisig_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x8bde30060a1e4d8383efa9b666654b31771c93325f905088c91f58803b4433b5",
+        "ethereum_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x4071662b18fb425db9a516b8472b4f545decb4bb6f6873af098b123b544e3cf4_0xae2714c8d2239062",
+        "module_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x4071662b18fb425db9a516b8472b4f545decb4bb6f6873af098b123b544e3cf4_0xae2714c8d2239062",
+        "creation_0x83eC7B0506556a7749306D69681aDbDbd08f0769",
+        "0x8bde30060a1e4d8383efa9b666654b31771c93325f905088c91f58803b4433b5",
+    ];
+
+    for id in ids.iter() {
+        assert_eq!(*id, build_id(&parse_id(id).unwrap()));
+    }
+}
+
+#[test]
+fn raw_ids_module_transaction() {
+    let details_id = "module_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x4071662b18fb425db9a516b8472b4f545decb4bb6f6873af098b123b544e3cf4_0xae2714c8d2239062";
+
+    let actual = TransactionRawIds::new("4", &parse_id(details_id).unwrap());
+
+    assert_eq!(actual.id_type, TransactionIdType::Module);
+    assert_eq!(
+        serde_json::to_value(&actual).unwrap(),
+        json!({
+            "id": details_id,
+            "chainId": "4",
+            "idType": "MODULE",
+            "safeAddress": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+            "transactionHash": "0x4071662b18fb425db9a516b8472b4f545decb4bb6f6873af098b123b544e3cf4",
+            "detailsHash": "0xae2714c8d2239062"
+        })
+    );
+}
+
+#[test]
+fn raw_ids_safe_tx_hash() {
+    let details_id = "0x8bde30060a1e4d8383efa9b666654b31771c93325f905088c91f58803b4433b5";
+
+    let actual = TransactionRawIds::new("1", &parse_id(details_id).unwrap());
+
+    assert_eq!(
+        serde_json::to_value(&actual).unwrap(),
+        json!({
+            "id": details_id,
+            "chainId": "1",
+            "idType": "SAFE_TX_HASH",
+            "safeTxHash": details_id
+        })
+    );
+}
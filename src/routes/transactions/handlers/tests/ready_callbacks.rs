@@ -0,0 +1,147 @@
+use crate::cache::MockCache;
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::routes::transactions::handlers::ready_callbacks::{
+    ready_event, send_event, set_callback, ReadyCallback, ReadyEvent,
+};
+use crate::tests::json::MULTISIG_TX_AWAITING_CONFIRMATIONS;
+use crate::utils::http_client::{MockHttpClient, Response};
+use mockall::predicate::eq;
+use mockall::Sequence;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SAFE_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const SAFE_TX_HASH: &str = "0x6e631d27c638458329ba95cc17961e74b8146c46886545cd1984bb2bcf4eccd3";
+const CALLBACK_URL: &str = "https://bot.example.com/safe-events";
+
+// Nonce 147 with 1 out of 2 confirmations
+fn queued_transaction(confirmations_required: u64) -> MultisigTransaction {
+    let mut transaction: MultisigTransaction =
+        serde_json::from_str(MULTISIG_TX_AWAITING_CONFIRMATIONS).unwrap();
+    transaction.confirmations_required = Some(confirmations_required);
+    transaction
+}
+
+fn expected_event() -> ReadyEvent {
+    ReadyEvent {
+        event_type: String::from("READY_TO_EXECUTE"),
+        chain_id: String::from("4"),
+        safe_address: String::from(SAFE_ADDRESS),
+        safe_tx_hash: String::from(SAFE_TX_HASH),
+        nonce: 147,
+        confirmations: 1,
+        confirmations_required: 1,
+        timestamp: 1637000000000,
+    }
+}
+
+#[test]
+fn ready_event_for_fully_confirmed_transaction() {
+    let actual = ready_event("4", SAFE_ADDRESS, &queued_transaction(1), 1637000000000);
+
+    assert_eq!(actual, Some(expected_event()));
+}
+
+#[test]
+fn no_ready_event_while_awaiting_confirmations_or_executed() {
+    let mut executed = queued_transaction(1);
+    executed.is_executed = true;
+
+    assert_eq!(
+        ready_event("4", SAFE_ADDRESS, &queued_transaction(2), 1637000000000),
+        None
+    );
+    assert_eq!(
+        ready_event("4", SAFE_ADDRESS, &executed, 1637000000000),
+        None
+    );
+}
+
+#[rocket::async_test]
+async fn set_callback_stores_lowercase_safe_address() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("ready_callbacks"), eq("4"))
+        .times(1)
+        .return_const(None);
+    mock_cache
+        .expect_insert_in_hash()
+        .with(
+            eq("ready_callbacks"),
+            eq("4"),
+            eq(r#"{"0x1230b3d59858296a31053c1b8562ecf89a2f888b":"https://bot.example.com/safe-events"}"#),
+        )
+        .times(1)
+        .return_const(());
+    let callback = ReadyCallback {
+        url: String::from(CALLBACK_URL),
+    };
+
+    let actual = set_callback(&mock_cache, "4", SAFE_ADDRESS, Some(&callback))
+        .await
+        .unwrap();
+
+    assert_eq!(actual, Some(callback));
+}
+
+#[rocket::async_test]
+async fn set_callback_rejects_non_http_url() {
+    let callback = ReadyCallback {
+        url: String::from("file:///etc/passwd"),
+    };
+
+    let actual = set_callback(&MockCache::new(), "4", SAFE_ADDRESS, Some(&callback)).await;
+
+    assert_eq!(actual.unwrap_err().status, 422);
+}
+
+#[rocket::async_test]
+async fn send_event_retries_failed_attempts() {
+    let mut sequence = Sequence::new();
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_post()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_once(|_| Err(api_error!("Connection refused")));
+    mock_http_client
+        .expect_post()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_once(|_| {
+            Ok(Response {
+                status_code: 200,
+                body: String::new(),
+            })
+        });
+
+    let actual = send_event(
+        Arc::new(mock_http_client),
+        CALLBACK_URL,
+        &expected_event(),
+        Duration::from_millis(0),
+    )
+    .await;
+
+    assert!(actual.is_ok());
+}
+
+#[rocket::async_test]
+async fn send_event_gives_up_after_three_attempts() {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_post()
+        .times(3)
+        .returning(|_| Err(api_error!("Connection refused")));
+
+    let actual = send_event(
+        Arc::new(mock_http_client),
+        CALLBACK_URL,
+        &expected_event(),
+        Duration::from_millis(0),
+    )
+    .await;
+
+    assert!(actual.is_err());
+}
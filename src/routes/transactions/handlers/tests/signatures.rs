@@ -0,0 +1,122 @@
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::routes::transactions::handlers::signatures::{
+    check_signer, is_legacy_magic_value, is_magic_value, is_valid_signature_data,
+    legacy_is_valid_signature_data, safe_tx_hash_preimage, ALREADY_CONFIRMED_CODE,
+    NOT_AN_OWNER_CODE,
+};
+use crate::testing::builders::SafeInfoBuilder;
+use crate::utils::signatures::SafeSignature;
+use ethcontract_common::hash::keccak256;
+
+const OWNER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+const OTHER_OWNER: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+fn signature() -> SafeSignature {
+    SafeSignature::Ecdsa {
+        owner: String::from(OWNER),
+    }
+}
+
+fn owners() -> Vec<String> {
+    vec![String::from(OTHER_OWNER), OWNER.to_lowercase()]
+}
+
+#[test]
+fn check_signer_owner() {
+    assert_eq!(
+        check_signer(&signature(), &owners(), &[String::from(OTHER_OWNER)]),
+        Ok(())
+    );
+}
+
+#[test]
+fn check_signer_not_an_owner() {
+    let error = check_signer(&signature(), &[String::from(OTHER_OWNER)], &[]).unwrap_err();
+
+    assert_eq!(error.status, 422);
+    assert_eq!(error.details.code, NOT_AN_OWNER_CODE);
+    assert_eq!(
+        error.details.message,
+        Some(format!("Signer {} is not an owner of the Safe", OWNER))
+    );
+}
+
+#[test]
+fn check_signer_already_confirmed() {
+    let error = check_signer(&signature(), &owners(), &[String::from(OWNER)]).unwrap_err();
+
+    assert_eq!(error.status, 409);
+    assert_eq!(error.details.code, ALREADY_CONFIRMED_CODE);
+}
+
+#[test]
+fn is_valid_signature_data_encoding() {
+    let actual = is_valid_signature_data(&[0x11; 32], &[0xaa, 0xbb]);
+
+    assert_eq!(
+        actual,
+        format!(
+            "0x1626ba7e{}{:064x}{:064x}aabb{}",
+            "11".repeat(32),
+            64,
+            2,
+            "0".repeat(60)
+        )
+    );
+}
+
+#[test]
+fn is_magic_value_result() {
+    assert!(is_magic_value(
+        "0x1626ba7e00000000000000000000000000000000000000000000000000000000"
+    ));
+    assert!(!is_magic_value(
+        "0xffffffff00000000000000000000000000000000000000000000000000000000"
+    ));
+    assert!(!is_magic_value("0x"));
+}
+
+#[test]
+fn legacy_is_valid_signature_data_encoding() {
+    let actual = legacy_is_valid_signature_data(&[0x11; 2], &[0xaa, 0xbb]);
+
+    assert_eq!(
+        actual,
+        format!(
+            "0x20c13b0b{:064x}{:064x}{:064x}1111{}{:064x}aabb{}",
+            64,
+            128,
+            2,
+            "0".repeat(60),
+            2,
+            "0".repeat(60)
+        )
+    );
+}
+
+#[test]
+fn is_legacy_magic_value_result() {
+    assert!(is_legacy_magic_value(
+        "0x20c13b0b00000000000000000000000000000000000000000000000000000000"
+    ));
+    assert!(!is_legacy_magic_value(
+        "0x1626ba7e00000000000000000000000000000000000000000000000000000000"
+    ));
+    assert!(!is_legacy_magic_value("0x"));
+}
+
+#[test]
+fn safe_tx_hash_preimage_hashes_to_safe_tx_hash() {
+    let multisig_tx =
+        serde_json::from_str::<MultisigTransaction>(crate::tests::json::MULTISIG_TX_CUSTOM)
+            .unwrap();
+    let safe_info = SafeInfoBuilder::new(&multisig_tx.safe_transaction.safe)
+        .version(Some("1.1.1"))
+        .build();
+
+    let actual = safe_tx_hash_preimage("4", &safe_info, &multisig_tx).unwrap();
+
+    assert_eq!(actual.len(), 66);
+    assert_eq!(&actual[..2], &[0x19, 0x01]);
+    assert_eq!(to_hex_string!(keccak256(&actual)), multisig_tx.safe_tx_hash);
+}
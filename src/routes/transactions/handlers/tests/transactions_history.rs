@@ -50,9 +50,11 @@ fn adjust_page_meta_offset_greater_than_0() {
 #[rocket::async_test]
 async fn backend_txs_to_summary_txs_empty() {
     let backend_txs = Page {
+        count: None,
         next: None,
         previous: None,
         results: vec![],
+        incomplete: None,
     };
     let mut mock_info_provider = MockInfoProvider::new();
     mock_info_provider.expect_safe_info().times(0);
@@ -105,6 +107,7 @@ async fn backend_txs_to_summary_txs_with_values() {
                 address: AddressEx::address_only("0xCFbFaC74C26F8647cBDb8c5caf80BB5b32E43134"),
             })),
             safe_app_info: None,
+            description: None,
         },
         TransactionSummary {
             id: "module_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x1cf24abdb39bb7b156677a128e709cea55c6991b12708904d1f0f3664ad6646e_0x2e5157f6f782e36f".into(),
@@ -124,6 +127,7 @@ async fn backend_txs_to_summary_txs_with_values() {
                 address: AddressEx::address_only("0xCFbFaC74C26F8647cBDb8c5caf80BB5b32E43134"),
             })),
             safe_app_info: None,
+            description: None,
         },
         TransactionSummary {
             id: "module_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x3f12bb74cd91ef09d553f66e3623bceaf879ba3dcb325227b1fbf2455757891a_0x15a0e5a089475db".into(),
@@ -143,6 +147,7 @@ async fn backend_txs_to_summary_txs_with_values() {
                 address: AddressEx::address_only("0xCFbFaC74C26F8647cBDb8c5caf80BB5b32E43134"),
             })),
             safe_app_info: None,
+            description: None,
         },
         TransactionSummary {
             id: "ethereum_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x021d4d8cb68f3f772906b58f97b66c6ead228c252627c5b1aff4b496d4ff0c2d_0xfd0dbbc7700a140f".into(),
@@ -175,6 +180,7 @@ async fn backend_txs_to_summary_txs_with_values() {
             ),
             execution_info: None,
             safe_app_info: None,
+            description: None,
         },
         TransactionSummary {
             id: "ethereum_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x5f4b7555f8e977ae302ab4125de685ccfacf52ac70e6f0aa2939bcb347f9a732_0xb7ceaac0cd5a85c5".into(),
@@ -205,6 +211,7 @@ async fn backend_txs_to_summary_txs_with_values() {
             ),
             execution_info: None,
             safe_app_info: None,
+            description: None,
         },
         TransactionSummary {
             id: "ethereum_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0xaafed95936f9d71eb8d9612e83f3f93f9decf33f11bbb4aa79cae98966ffa7fe_0x11bd3d64559a0af7".into(),
@@ -237,6 +244,7 @@ async fn backend_txs_to_summary_txs_with_values() {
             ),
             execution_info: None,
             safe_app_info: None,
+            description: None,
         },
     ];
     let actual = backend_txs_to_summary_txs(
@@ -290,14 +298,17 @@ async fn service_txs_to_tx_list_items_last_timestamp_undefined() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::DateLabel {
             timestamp: 1606694400000,
@@ -305,14 +316,17 @@ async fn service_txs_to_tx_list_items_last_timestamp_undefined() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
     ];
 
@@ -352,14 +366,17 @@ async fn service_txs_to_tx_list_items_last_timestamp_defined_but_different() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::DateLabel {
             timestamp: 1606694400000,
@@ -367,14 +384,17 @@ async fn service_txs_to_tx_list_items_last_timestamp_defined_but_different() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
     ];
 
@@ -413,14 +433,17 @@ async fn service_txs_to_tx_list_items_last_timestamp_defined_and_same() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::DateLabel {
             timestamp: 1606694400000,
@@ -428,14 +451,17 @@ async fn service_txs_to_tx_list_items_last_timestamp_defined_and_same() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
     ];
 
@@ -475,14 +501,17 @@ async fn service_txs_to_tx_list_items_date_label_berlin_timezone() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::DateLabel {
             timestamp: 1606690800000,
@@ -490,14 +519,17 @@ async fn service_txs_to_tx_list_items_date_label_berlin_timezone() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
     ];
 
@@ -536,14 +568,17 @@ async fn service_txs_to_tx_list_items_date_label_melbourne_timezone() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::DateLabel {
             timestamp: 1606741200000, // 2020/12/01
@@ -551,10 +586,12 @@ async fn service_txs_to_tx_list_items_date_label_melbourne_timezone() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::DateLabel {
             timestamp: 1606654800000, // 2020/11/30
@@ -562,6 +599,7 @@ async fn service_txs_to_tx_list_items_date_label_melbourne_timezone() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
     ];
 
@@ -601,14 +639,17 @@ async fn service_txs_to_tx_list_items_date_label_buenos_aires_timezone() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::DateLabel {
             timestamp: 1606705200000,
@@ -616,14 +657,17 @@ async fn service_txs_to_tx_list_items_date_label_buenos_aires_timezone() {
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: service_txs_inter.next().unwrap(),
             conflict_type: ConflictType::None,
+            executability: None,
         },
     ];
 
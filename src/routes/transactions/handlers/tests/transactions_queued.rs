@@ -1,24 +1,31 @@
+use crate::cache::MockCache;
 use crate::common::models::addresses::AddressEx;
 use crate::common::models::backend::transactions::MultisigTransaction;
 use crate::common::models::page::{Page, PageMetadata};
 use crate::providers::info::*;
 use crate::routes::transactions::handlers::queued::{
-    adjust_page_meta, get_edge_nonce, get_previous_page_nonce, process_transactions,
+    adjust_page_meta, diff_queue, get_edge_nonce, get_previous_page_nonce, process_transactions,
+    queue_item_key, queue_summary, queued_etag, queued_fingerprint, set_executabilities,
 };
+use crate::routes::transactions::models::details::ExecutionEstimation;
 use crate::routes::transactions::models::summary::{
-    ConflictType, ExecutionInfo, Label, MultisigExecutionInfo, TransactionListItem,
-    TransactionSummary,
+    ConflictType, ExecutionInfo, Label, MultisigExecutionInfo, QueueDiffItem, QueueSummary,
+    TransactionListItem, TransactionSummary,
 };
 use crate::routes::transactions::models::TransferDirection::Outgoing;
 use crate::routes::transactions::models::{
     Erc20Transfer, TransactionInfo, TransactionStatus, Transfer, TransferInfo,
 };
+use crate::testing::builders::{SafeInfoBuilder, TransactionSummaryBuilder};
 use crate::tests::json::{
     BACKEND_QUEUED_TRANSACTION_LIST_PAGE_CONFLICT_393,
     BACKEND_QUEUED_TRANSACTION_LIST_PAGE_CONFLICT_394,
-    BACKEND_QUEUED_TRANSACTION_LIST_PAGE_NO_CONFLICTS, MULTISIG_TX_AWAITING_EXECUTION,
-    MULTISIG_TX_SETTINGS_CHANGE, TOKEN_BAT,
+    BACKEND_QUEUED_TRANSACTION_LIST_PAGE_NO_CONFLICTS, CHAIN_INFO_RINKEBY,
+    MULTISIG_TX_AWAITING_EXECUTION, MULTISIG_TX_SETTINGS_CHANGE, TOKEN_BAT,
 };
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::{MockHttpClient, Response};
+use mockall::predicate::eq;
 
 #[test]
 fn adjust_page_meta_offset_0() {
@@ -58,9 +65,11 @@ fn get_edge_nonce_with_next() {
     let expected = edge_tx.nonce as i64;
     let results = vec![get_multisig_tx(MULTISIG_TX_SETTINGS_CHANGE), edge_tx];
     let mut page: Page<MultisigTransaction> = Page {
+        count: None,
         results,
         previous: None,
         next: Some("some_url".to_string()),
+        incomplete: None,
     };
 
     let actual = get_edge_nonce(&mut page);
@@ -79,9 +88,11 @@ fn get_edge_nonce_without_next() {
         get_multisig_tx(MULTISIG_TX_AWAITING_EXECUTION),
     ];
     let mut page: Page<MultisigTransaction> = Page {
+        count: None,
         results,
         previous: None,
         next: None,
+        incomplete: None,
     };
 
     let actual = get_edge_nonce(&mut page);
@@ -254,10 +265,13 @@ async fn process_transactions_no_conflicts_everything_queued() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
 
             conflict_type: ConflictType::None,
 
+            executability: None,
+
         },
         TransactionListItem::Transaction {
             transaction: TransactionSummary {
@@ -287,8 +301,10 @@ async fn process_transactions_no_conflicts_everything_queued() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: TransactionSummary {
@@ -318,8 +334,10 @@ async fn process_transactions_no_conflicts_everything_queued() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::None,
+            executability: None,
         },
     ];
 
@@ -415,8 +433,10 @@ async fn process_transactions_conflicts_in_queued() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::None,
+            executability: None,
         },
         TransactionListItem::Label {
             label: Label::Queued,
@@ -452,8 +472,10 @@ async fn process_transactions_conflicts_in_queued() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::HasNext,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: TransactionSummary {
@@ -483,8 +505,10 @@ async fn process_transactions_conflicts_in_queued() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::End,
+            executability: None,
         }
     ];
 
@@ -584,8 +608,10 @@ async fn process_transactions_conflicts_in_next() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::HasNext,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: TransactionSummary {
@@ -615,8 +641,10 @@ async fn process_transactions_conflicts_in_next() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::End,
+            executability: None,
         },
         TransactionListItem::Label {
             label: Label::Queued,
@@ -649,8 +677,10 @@ async fn process_transactions_conflicts_in_next() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::None,
+            executability: None,
         }
     ];
 
@@ -744,8 +774,10 @@ async fn process_transactions_conflicts_in_queued_spanning_to_next_page() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::End,
+            executability: None,
         },
         TransactionListItem::ConflictHeader {
             nonce: 394
@@ -778,8 +810,10 @@ async fn process_transactions_conflicts_in_queued_spanning_to_next_page() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::HasNext,
+            executability: None,
         },
         TransactionListItem::Transaction {
             transaction: TransactionSummary {
@@ -809,8 +843,10 @@ async fn process_transactions_conflicts_in_queued_spanning_to_next_page() {
                     ])
                 })),
                 safe_app_info: None,
+                description: None,
             },
             conflict_type: ConflictType::HasNext,
+            executability: None,
         }
     ];
 
@@ -820,3 +856,341 @@ async fn process_transactions_conflicts_in_queued_spanning_to_next_page() {
 fn get_multisig_tx(source: &str) -> MultisigTransaction {
     serde_json::from_str::<MultisigTransaction>(source).unwrap()
 }
+
+#[test]
+fn queued_etag_changes_with_body() {
+    let etag = queued_etag(r#"{"next":null,"previous":null,"results":[]}"#);
+
+    assert_eq!(66, etag.len());
+    assert_eq!(
+        etag,
+        queued_etag(r#"{"next":null,"previous":null,"results":[]}"#)
+    );
+    assert_ne!(
+        etag,
+        queued_etag(r#"{"next":null,"previous":null,"results":[{"type":"LABEL","label":"NEXT"}]}"#)
+    );
+}
+
+#[test]
+fn queue_summary_counts_awaiting_signature_execution_and_conflicts() {
+    let transactions: Page<MultisigTransaction> =
+        serde_json::from_str(BACKEND_QUEUED_TRANSACTION_LIST_PAGE_CONFLICT_393).unwrap();
+    let safe_info = SafeInfoBuilder::new("0x1230B3d59858296A31053C1b8562Ecf89A2f888b")
+        .threshold(2)
+        .owners(&[
+            "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd",
+            "0x8bc9Ab35a2A8b20ad8c23410C61db69F2e5d8164",
+        ])
+        .build();
+
+    let expected = QueueSummary {
+        total: 3,
+        awaiting_signature: Some(3),
+        awaiting_execution: 0,
+        conflicting: 2,
+        incomplete: None,
+    };
+
+    // Checksum independent
+    let actual = queue_summary(
+        &transactions.results,
+        &safe_info,
+        Some("0x8bc9ab35a2a8b20ad8c23410c61db69f2e5d8164"),
+    );
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn queue_summary_signer_already_confirmed() {
+    let transactions: Page<MultisigTransaction> =
+        serde_json::from_str(BACKEND_QUEUED_TRANSACTION_LIST_PAGE_NO_CONFLICTS).unwrap();
+    let safe_info = SafeInfoBuilder::new("0x1230B3d59858296A31053C1b8562Ecf89A2f888b")
+        .threshold(2)
+        .owners(&[
+            "0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd",
+            "0x8bc9Ab35a2A8b20ad8c23410C61db69F2e5d8164",
+        ])
+        .build();
+
+    let actual = queue_summary(
+        &transactions.results,
+        &safe_info,
+        Some("0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd"),
+    );
+
+    assert_eq!(actual.awaiting_signature, Some(0));
+    assert_eq!(actual.conflicting, 0);
+}
+
+#[test]
+fn queue_summary_without_owner_signer() {
+    let transactions: Page<MultisigTransaction> =
+        serde_json::from_str(BACKEND_QUEUED_TRANSACTION_LIST_PAGE_NO_CONFLICTS).unwrap();
+    let safe_info = SafeInfoBuilder::new("0x1230B3d59858296A31053C1b8562Ecf89A2f888b")
+        .threshold(1)
+        .owners(&["0xF2CeA96575d6b10f51d9aF3b10e3e4E5738aa6bd"])
+        .build();
+
+    let expected = QueueSummary {
+        total: 3,
+        awaiting_signature: None,
+        awaiting_execution: 3,
+        conflicting: 0,
+        incomplete: None,
+    };
+
+    let actual = queue_summary(
+        &transactions.results,
+        &safe_info,
+        Some("0x8bc9Ab35a2A8b20ad8c23410C61db69F2e5d8164"),
+    );
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn set_executabilities_by_transaction_id() {
+    let estimation = ExecutionEstimation {
+        gas_estimate: None,
+        will_revert: true,
+        revert_reason: Some(String::from("execution reverted: GS013")),
+    };
+    let transaction_item = |id: &str| TransactionListItem::Transaction {
+        transaction: TransactionSummaryBuilder::new(id).build(),
+        conflict_type: ConflictType::None,
+        executability: None,
+    };
+    let mut items = vec![
+        TransactionListItem::Label { label: Label::Next },
+        transaction_item("multisig_0x1_0x2"),
+        transaction_item("multisig_0x1_0x3"),
+    ];
+    let executabilities = vec![(String::from("multisig_0x1_0x2"), estimation.clone())]
+        .into_iter()
+        .collect();
+
+    set_executabilities(&mut items, executabilities);
+
+    assert_eq!(
+        items,
+        vec![
+            TransactionListItem::Label { label: Label::Next },
+            TransactionListItem::Transaction {
+                transaction: TransactionSummaryBuilder::new("multisig_0x1_0x2").build(),
+                conflict_type: ConflictType::None,
+                executability: Some(estimation),
+            },
+            transaction_item("multisig_0x1_0x3"),
+        ]
+    );
+}
+
+fn queue_page(results: Vec<TransactionListItem>) -> Page<TransactionListItem> {
+    Page {
+        count: Some(2),
+        next: None,
+        previous: None,
+        results,
+        incomplete: None,
+    }
+}
+
+fn queued_item(id: &str, conflict_type: ConflictType) -> TransactionListItem {
+    TransactionListItem::Transaction {
+        transaction: TransactionSummaryBuilder::new(id).build(),
+        conflict_type,
+        executability: None,
+    }
+}
+
+#[test]
+fn queue_item_key_of_every_item() {
+    assert_eq!(
+        "multisig_0x1_0x2",
+        queue_item_key(&queued_item("multisig_0x1_0x2", ConflictType::None))
+    );
+    assert_eq!(
+        "LABEL_NEXT",
+        queue_item_key(&TransactionListItem::Label { label: Label::Next })
+    );
+    assert_eq!(
+        "LABEL_QUEUED",
+        queue_item_key(&TransactionListItem::Label {
+            label: Label::Queued
+        })
+    );
+    assert_eq!(
+        "CONFLICT_HEADER_7",
+        queue_item_key(&TransactionListItem::ConflictHeader { nonce: 7 })
+    );
+}
+
+#[test]
+fn diff_queue_without_snapshot_adds_everything() {
+    let page = queue_page(vec![
+        TransactionListItem::Label { label: Label::Next },
+        queued_item("multisig_0x1_0x2", ConflictType::None),
+    ]);
+
+    let (diff, snapshot) = diff_queue(page, String::from("0xetag"), None).unwrap();
+
+    assert!(diff.reset);
+    assert_eq!("0xetag", diff.etag);
+    assert_eq!(Some(2), diff.count);
+    assert_eq!(vec!["LABEL_NEXT", "multisig_0x1_0x2"], diff.order);
+    assert_eq!(
+        vec![
+            QueueDiffItem {
+                key: String::from("LABEL_NEXT"),
+                item: TransactionListItem::Label { label: Label::Next },
+            },
+            QueueDiffItem {
+                key: String::from("multisig_0x1_0x2"),
+                item: queued_item("multisig_0x1_0x2", ConflictType::None),
+            },
+        ],
+        diff.added
+    );
+    assert!(diff.changed.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(2, snapshot.len());
+}
+
+#[test]
+fn diff_queue_against_snapshot() {
+    let previous_page = queue_page(vec![
+        TransactionListItem::Label { label: Label::Next },
+        queued_item("multisig_0x1_0x2", ConflictType::None),
+        TransactionListItem::Label {
+            label: Label::Queued,
+        },
+        queued_item("multisig_0x1_0x3", ConflictType::None),
+    ]);
+    let (_, known_snapshot) = diff_queue(previous_page, String::from("0xprevious"), None).unwrap();
+    let page = queue_page(vec![
+        TransactionListItem::Label { label: Label::Next },
+        TransactionListItem::ConflictHeader { nonce: 1 },
+        queued_item("multisig_0x1_0x2", ConflictType::HasNext),
+        queued_item("multisig_0x1_0x4", ConflictType::End),
+    ]);
+
+    let (diff, _) = diff_queue(page, String::from("0xcurrent"), Some(&known_snapshot)).unwrap();
+
+    assert!(!diff.reset);
+    assert_eq!(
+        vec![
+            "LABEL_NEXT",
+            "CONFLICT_HEADER_1",
+            "multisig_0x1_0x2",
+            "multisig_0x1_0x4"
+        ],
+        diff.order
+    );
+    assert_eq!(
+        vec!["CONFLICT_HEADER_1", "multisig_0x1_0x4"],
+        diff.added
+            .iter()
+            .map(|diff_item| diff_item.key.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert_eq!(
+        vec![QueueDiffItem {
+            key: String::from("multisig_0x1_0x2"),
+            item: queued_item("multisig_0x1_0x2", ConflictType::HasNext),
+        }],
+        diff.changed
+    );
+    assert_eq!(vec!["LABEL_QUEUED", "multisig_0x1_0x3"], diff.removed);
+}
+
+#[test]
+fn diff_queue_of_unchanged_page_is_empty() {
+    let items = || {
+        vec![
+            TransactionListItem::Label { label: Label::Next },
+            queued_item("multisig_0x1_0x2", ConflictType::None),
+        ]
+    };
+    let (_, known_snapshot) =
+        diff_queue(queue_page(items()), String::from("0xetag"), None).unwrap();
+
+    let (diff, snapshot) = diff_queue(
+        queue_page(items()),
+        String::from("0xetag"),
+        Some(&known_snapshot),
+    )
+    .unwrap();
+
+    assert!(!diff.reset);
+    assert!(diff.added.is_empty());
+    assert!(diff.changed.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(known_snapshot, snapshot);
+}
+
+#[test]
+fn diff_queue_keys_summaries_sharing_their_id() {
+    let page = queue_page(vec![
+        queued_item("multisig_0x1_0x2", ConflictType::HasNext),
+        queued_item("multisig_0x1_0x2", ConflictType::End),
+    ]);
+
+    let (diff, _) = diff_queue(page, String::from("0xetag"), None).unwrap();
+
+    assert_eq!(vec!["multisig_0x1_0x2", "multisig_0x1_0x2_2"], diff.order);
+}
+
+#[rocket::async_test]
+async fn queued_fingerprint_requests_latest_modified_transaction_only() {
+    let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+    let backend_page = format!(
+        r#"{{"count":12,"next":"next","previous":null,"results":[{}]}}"#,
+        MULTISIG_TX_AWAITING_EXECUTION
+    );
+    let chain_key = format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"));
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key))
+        .return_const(Some(String::from(CHAIN_INFO_RINKEBY)));
+    mock_cache
+        .expect_fetch()
+        .withf(|key| key.contains("/multisig-transactions/"))
+        .return_const(None);
+    mock_cache.expect_create().return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    let response_body = backend_page.to_string();
+    mock_http_client
+        .expect_get()
+        .times(1)
+        .withf(move |request| {
+            request.url().ends_with(&format!(
+                "/api/v1/safes/{}/multisig-transactions/?ordering=-modified&trusted=true&limit=1",
+                safe_address
+            ))
+        })
+        .returning(move |_| {
+            Ok(Response {
+                status_code: 200,
+                body: response_body.to_string(),
+            })
+        });
+    let context = RequestContext::mock(
+        String::from("/poll"),
+        String::from("host"),
+        mock_http_client,
+        mock_cache,
+    );
+
+    let actual = queued_fingerprint(&context, "4", safe_address, &None)
+        .await
+        .unwrap();
+
+    assert_eq!(actual, queued_etag(&backend_page));
+}
@@ -0,0 +1,41 @@
+use crate::common::models::backend::transactions::MultisigTransaction;
+use crate::common::models::page::Page;
+use crate::routes::transactions::handlers::replacement::replaceable_transactions;
+use crate::tests::json::BACKEND_QUEUED_TRANSACTION_LIST_PAGE_CONFLICT_393;
+
+fn backend_transactions() -> Vec<MultisigTransaction> {
+    serde_json::from_str::<Page<MultisigTransaction>>(
+        BACKEND_QUEUED_TRANSACTION_LIST_PAGE_CONFLICT_393,
+    )
+    .unwrap()
+    .results
+}
+
+#[test]
+fn replaceable_transactions_same_nonce_only() {
+    let actual: Vec<String> = replaceable_transactions(backend_transactions(), 393)
+        .into_iter()
+        .map(|transaction| transaction.nonce.to_string())
+        .collect();
+
+    assert_eq!(vec!["393".to_string(), "393".to_string()], actual);
+}
+
+#[test]
+fn replaceable_transactions_skips_executed() {
+    let mut transactions = backend_transactions();
+    transactions
+        .iter_mut()
+        .for_each(|transaction| transaction.is_executed = true);
+
+    let actual = replaceable_transactions(transactions, 393);
+
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn replaceable_transactions_unknown_nonce() {
+    let actual = replaceable_transactions(backend_transactions(), 400);
+
+    assert!(actual.is_empty());
+}
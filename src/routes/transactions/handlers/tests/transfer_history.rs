@@ -0,0 +1,94 @@
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::backend::transfers::Transfer;
+use crate::providers::info::*;
+use crate::routes::transactions::handlers::transfer_history::{
+    transfer_filters, transfers_to_summaries,
+};
+use crate::routes::transactions::models::summary::TransactionSummary;
+use crate::routes::transactions::models::TransactionStatus::Success;
+use crate::routes::transactions::models::TransferDirection::Incoming;
+use crate::routes::transactions::models::{
+    NativeCoinTransfer, TransactionInfo, Transfer as ServiceTransfer, TransferInfo,
+};
+
+const SAFE: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+const SENDER: &str = "0xf2565317F3Ae8Ae9EA98E9Fe1e7FADC77F823cbD";
+
+#[test]
+fn transfer_filters_without_filters() {
+    assert_eq!(
+        String::from(""),
+        transfer_filters(SAFE, &None, &None).unwrap()
+    );
+}
+
+#[test]
+fn transfer_filters_with_token_and_direction() {
+    let token = Some(String::from("0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02"));
+
+    assert_eq!(
+        String::from(
+            "&token_address=0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02\
+            &to=0x1230B3d59858296A31053C1b8562Ecf89A2f888b"
+        ),
+        transfer_filters(SAFE, &token, &Some(String::from("incoming"))).unwrap()
+    );
+    assert_eq!(
+        String::from("&_from=0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
+        transfer_filters(SAFE, &None, &Some(String::from("outgoing"))).unwrap()
+    );
+}
+
+#[test]
+fn transfer_filters_invalid_direction() {
+    let error = transfer_filters(SAFE, &None, &Some(String::from("sideways"))).unwrap_err();
+
+    assert_eq!(422, error.status);
+}
+
+#[rocket::async_test]
+async fn transfers_to_summaries_skips_unknown_transfers() {
+    let transfers = serde_json::from_value::<Vec<Transfer>>(serde_json::json!([
+        {
+            "type": "ETHER_TRANSFER",
+            "executionDate": "2021-06-01T10:00:00Z",
+            "blockNumber": 8647821,
+            "transactionHash": "0x9fa01d0eb0cf2aa0c3e5fa1c6fb4e3a125faa83d2bd9504cb9e4d6c1f8d1ed31",
+            "to": SAFE,
+            "value": "1000000000000000000",
+            "from": SENDER
+        },
+        {
+            "type": "SOMETHING_ELSE"
+        }
+    ]))
+    .unwrap();
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_address_ex_from_any_source()
+        .times(1)
+        .returning(move |_| bail!("No address info"));
+
+    let actual = transfers_to_summaries(&transfers, &mock_info_provider, SAFE).await;
+
+    let expected = vec![TransactionSummary {
+        id: transfers[0].generate_id(
+            SAFE,
+            "0x9fa01d0eb0cf2aa0c3e5fa1c6fb4e3a125faa83d2bd9504cb9e4d6c1f8d1ed31",
+        ),
+        timestamp: 1622541600000,
+        tx_status: Success,
+        execution_info: None,
+        safe_app_info: None,
+        tx_info: TransactionInfo::Transfer(ServiceTransfer {
+            sender: AddressEx::address_only(SENDER),
+            recipient: AddressEx::address_only(SAFE),
+            direction: Incoming,
+            transfer_info: TransferInfo::NativeCoin(NativeCoinTransfer {
+                value: String::from("1000000000000000000"),
+            }),
+        }),
+        description: None,
+    }];
+    assert_eq!(expected, actual);
+}
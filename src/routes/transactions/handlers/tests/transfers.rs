@@ -0,0 +1,73 @@
+use crate::routes::transactions::handlers::transfers::{erc20_transfer_data, to_base_units};
+use crate::routes::transactions::models::requests::TransferBuildRequest;
+use crate::utils::validation::Validate;
+
+#[test]
+fn to_base_units_applies_decimals() {
+    assert_eq!(to_base_units("1.5", 18).unwrap(), "1500000000000000000");
+    assert_eq!(to_base_units("12", 6).unwrap(), "12000000");
+    assert_eq!(to_base_units("0.000001", 6).unwrap(), "1");
+    assert_eq!(to_base_units("3.10", 1).unwrap(), "31");
+    assert_eq!(to_base_units("7", 0).unwrap(), "7");
+}
+
+#[test]
+fn to_base_units_rejects_more_decimals_than_token() {
+    let error = to_base_units("0.0000001", 6).unwrap_err();
+
+    assert_eq!(error.status, 422);
+    assert_eq!(
+        error.details.message.unwrap(),
+        "Amount has more decimals than the token"
+    );
+}
+
+#[test]
+fn to_base_units_rejects_zero_and_overflow() {
+    assert_eq!(
+        to_base_units("0.0", 18)
+            .unwrap_err()
+            .details
+            .message
+            .unwrap(),
+        "Amount must be greater than 0"
+    );
+    assert_eq!(
+        to_base_units("1", 78).unwrap_err().details.message.unwrap(),
+        "Amount is not a valid uint256"
+    );
+}
+
+#[test]
+fn erc20_transfer_data_encodes_recipient_and_amount() {
+    let actual = erc20_transfer_data(
+        "0xF353eBBa77e5E71c210599236686D51cA1F88b84",
+        "500000000000000",
+    )
+    .unwrap();
+
+    assert_eq!(
+        actual,
+        "0xa9059cbb\
+        000000000000000000000000f353ebba77e5e71c210599236686d51ca1f88b84\
+        0000000000000000000000000000000000000000000000000001c6bf52634000"
+    );
+}
+
+#[test]
+fn transfer_build_request_reports_invalid_fields() {
+    let request = TransferBuildRequest {
+        recipient: String::from("0x1234"),
+        token_address: Some(String::from("0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88")),
+        amount: String::from("1.5e18"),
+        nonce: None,
+    };
+
+    let error = request.validated().unwrap_err();
+
+    assert_eq!(error.status, 422);
+    let arguments = error.details.arguments.unwrap();
+    assert_eq!(arguments.len(), 2);
+    assert!(arguments[0].starts_with("recipient: "));
+    assert_eq!(arguments[1], "amount: must be a decimal number");
+}
@@ -0,0 +1,127 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::common::models::backend::transfers::Transfer;
+use crate::common::models::page::{Page, PageMetadata};
+use crate::config::{transaction_request_timeout, tx_history_latency_budget};
+use crate::monitoring::schema_drift;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::transactions::converters::description::transaction_description;
+use crate::routes::transactions::handlers::{build_absolute_uri, offset_page_meta};
+use crate::routes::transactions::models::summary::TransactionSummary;
+use crate::routes::transactions::models::{TransactionInfo, TransactionStatus};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::{ApiError, ApiResult};
+
+/// Executed transfers of the Safe, newest first, optionally only the ones of `token` and/or in
+/// one `direction` (`incoming` or `outgoing`)
+pub async fn get_transfers(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    token: &Option<String>,
+    direction: &Option<String>,
+    cursor: &Option<String>,
+) -> ApiResult<Page<TransactionSummary>> {
+    let filters = transfer_filters(safe_address, token, direction)?;
+    let mut info_provider = DefaultInfoProvider::new(chain_id, context);
+    info_provider.latency_budget(tx_history_latency_budget());
+    let page_metadata = PageMetadata::from_cursor(cursor.as_ref().unwrap_or(&"".to_string()));
+
+    let url = core_uri!(
+        info_provider,
+        "/v1/safes/{}/transfers/?{}{}",
+        safe_address,
+        page_metadata.to_url_string(),
+        filters
+    )?;
+    let body = RequestCached::new_from_context(url, context)
+        .request_timeout(transaction_request_timeout())
+        .execute()
+        .await?;
+    let transfers: Page<Transfer> = schema_drift::parse(&body)?;
+
+    let results = transfers_to_summaries(&transfers.results, &info_provider, safe_address).await;
+    let build_cursor = |url: Option<String>, offset_direction: i64| {
+        url.map(|_| {
+            build_absolute_uri(
+                context,
+                uri!(crate::routes::transactions::routes::get_transfers(
+                    chain_id,
+                    safe_address,
+                    token.clone(),
+                    direction.clone(),
+                    Some(offset_page_meta(
+                        &page_metadata,
+                        offset_direction * (page_metadata.limit as i64)
+                    ))
+                )),
+            )
+        })
+    };
+    Ok(Page {
+        count: transfers.count,
+        next: build_cursor(transfers.next, 1), // Direction forward
+        previous: build_cursor(transfers.previous, -1), // Direction backwards
+        results,
+        incomplete: info_provider.is_incomplete().then(|| true),
+    })
+}
+
+/// Query parameters of the transaction service transfers endpoint, starting with `&`
+pub(super) fn transfer_filters(
+    safe_address: &str,
+    token: &Option<String>,
+    direction: &Option<String>,
+) -> ApiResult<String> {
+    let mut filters = String::new();
+    if let Some(token) = token {
+        filters.push_str(&format!("&token_address={}", token));
+    }
+    match direction.as_deref() {
+        None => {}
+        Some("incoming") => filters.push_str(&format!("&to={}", safe_address)),
+        Some("outgoing") => filters.push_str(&format!("&_from={}", safe_address)),
+        Some(direction) => {
+            return Err(ApiError::new_from_message_with_code(
+                422,
+                format!(
+                    "Invalid direction {:?}, expected \"incoming\" or \"outgoing\"",
+                    direction
+                ),
+            ))
+        }
+    }
+    Ok(filters)
+}
+
+/// Transfers of unknown types are left out
+pub(super) async fn transfers_to_summaries(
+    transfers: &[Transfer],
+    info_provider: &(impl InfoProvider + Sync),
+    safe_address: &str,
+) -> Vec<TransactionSummary> {
+    let mut results = Vec::with_capacity(transfers.len());
+    for transfer in transfers {
+        let (tx_hash, timestamp) = match (
+            transfer.get_transaction_hash(),
+            transfer.get_execution_time(),
+        ) {
+            (Some(tx_hash), Some(timestamp)) => (tx_hash, timestamp),
+            _ => continue,
+        };
+        let tx_info = transfer.to_transfer(info_provider, safe_address).await;
+        if let TransactionInfo::Unknown = tx_info {
+            continue;
+        }
+        let description = transaction_description(info_provider, &tx_info, None).await;
+        results.push(TransactionSummary {
+            id: transfer.generate_id(safe_address, &tx_hash),
+            timestamp,
+            tx_status: TransactionStatus::Success,
+            execution_info: None,
+            safe_app_info: None,
+            tx_info,
+            description,
+        });
+    }
+    results
+}
@@ -0,0 +1,138 @@
+use crate::common::models::data_decoded::Operation;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, SafeInfo, TokenType};
+use crate::routes::transactions::models::requests::TransferBuildRequest;
+use crate::routes::transactions::models::summary::TransactionBuild;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::safe_version::parse_base_version;
+use crate::utils::transactions::{parse_address, parse_uint, safe_tx_hash, SafeTransactionFields};
+use crate::utils::validation::Validate;
+use ethabi::Token;
+use ethcontract_common::hash::keccak256;
+
+pub const ERC20_TRANSFER_SIGNATURE: &str = "transfer(address,uint256)";
+pub(super) const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+// Any amount padded to more digits does not fit a uint256
+const MAX_UINT256_DIGITS: u64 = 78;
+
+pub async fn build_transfer(
+    context: &RequestContext,
+    chain_id: &String,
+    safe_address: &String,
+    request: &TransferBuildRequest,
+) -> ApiResult<TransactionBuild> {
+    request.validated()?;
+    let info_provider = DefaultInfoProvider::new(chain_id, context);
+    let safe_info = info_provider.safe_info(safe_address).await?;
+    let nonce = next_nonce(request.nonce, &safe_info)?;
+
+    let (to, value, data) = match request.token_address.as_ref() {
+        Some(token_address) => {
+            let token_info = info_provider.token_info(token_address).await?;
+            if token_info.token_type != TokenType::Erc20 {
+                return Err(client_error!(422, "Only ERC20 tokens can be transferred"));
+            }
+            let amount = to_base_units(&request.amount, token_info.decimals)?;
+            (
+                token_address.to_string(),
+                String::from("0"),
+                erc20_transfer_data(&request.recipient, &amount)?,
+            )
+        }
+        None => {
+            let decimals = info_provider.chain_info().await?.native_currency.decimals;
+            (
+                request.recipient.to_string(),
+                to_base_units(&request.amount, decimals)?,
+                String::from("0x"),
+            )
+        }
+    };
+
+    build_call(chain_id, &safe_info, to, value, data, nonce)
+}
+
+/// Defaults to the current nonce of the Safe, used nonces are rejected
+pub(super) fn next_nonce(nonce: Option<u64>, safe_info: &SafeInfo) -> ApiResult<u64> {
+    let nonce = nonce.unwrap_or(safe_info.nonce);
+    if nonce < safe_info.nonce {
+        return Err(client_error!(422, "Nonce has already been used"));
+    }
+    Ok(nonce)
+}
+
+/// Safe transaction calling `to` without any refund, with its `safeTxHash`
+pub(super) fn build_call(
+    chain_id: &str,
+    safe_info: &SafeInfo,
+    to: String,
+    value: String,
+    data: String,
+    nonce: u64,
+) -> ApiResult<TransactionBuild> {
+    let fields = SafeTransactionFields {
+        to: &to,
+        value: &value,
+        data: &data,
+        operation: Operation::CALL as u8,
+        safe_tx_gas: "0",
+        base_gas: "0",
+        gas_price: "0",
+        gas_token: ZERO_ADDRESS,
+        refund_receiver: ZERO_ADDRESS,
+        nonce,
+    };
+    let version = safe_info
+        .version
+        .as_ref()
+        .and_then(|version| parse_base_version(version));
+    let safe_tx_hash = safe_tx_hash(chain_id, &safe_info.address, version, &fields)?;
+
+    Ok(TransactionBuild {
+        to,
+        value,
+        data,
+        operation: Operation::CALL,
+        safe_tx_gas: String::from("0"),
+        base_gas: String::from("0"),
+        gas_price: String::from("0"),
+        gas_token: ZERO_ADDRESS.to_string(),
+        refund_receiver: ZERO_ADDRESS.to_string(),
+        nonce,
+        safe_tx_hash,
+    })
+}
+
+/// Converts a decimal `amount` in token units to base units without any rounding: amounts with
+/// more fractional digits than `decimals` are rejected, as are zero and overflowing amounts
+pub fn to_base_units(amount: &str, decimals: u64) -> ApiResult<String> {
+    let mut parts = amount.splitn(2, '.');
+    let integer = parts.next().unwrap_or("");
+    let fraction = parts.next().unwrap_or("").trim_end_matches('0');
+    if fraction.len() as u64 > decimals {
+        return Err(client_error!(
+            422,
+            "Amount has more decimals than the token"
+        ));
+    }
+    if decimals > MAX_UINT256_DIGITS {
+        return Err(client_error!(422, "Amount is not a valid uint256"));
+    }
+    let padding = "0".repeat(decimals as usize - fraction.len());
+    let base_units = format!("{}{}{}", integer, fraction, padding);
+    let base_units = parse_uint(Some(&base_units))
+        .map_err(|_| client_error!(422, "Amount is not a valid uint256"))?;
+    if base_units.is_zero() {
+        return Err(client_error!(422, "Amount must be greater than 0"));
+    }
+    Ok(base_units.to_string())
+}
+
+pub fn erc20_transfer_data(recipient: &str, amount: &str) -> ApiResult<String> {
+    let mut encoded = keccak256(ERC20_TRANSFER_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[
+        Token::Address(parse_address(recipient)?),
+        Token::Uint(parse_uint(Some(amount))?),
+    ]));
+    Ok(to_hex_string!(encoded))
+}
@@ -1,8 +1,11 @@
 use super::*;
 use crate::common::models::data_decoded::{DataDecoded, Operation};
+use crate::providers::address_info::AddressInfoIndex;
 use crate::providers::info::{SafeAppInfo, TokenInfo};
-use serde::Serialize;
-use std::collections::HashMap;
+use crate::utils::checksum::serialize_checksummed_keys;
+use crate::utils::errors::ErrorDetails;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 
 /// Top level object returned by the `/v1/transactions/<details_id>` endpoint
 ///
@@ -230,6 +233,9 @@ pub struct TransactionDetails {
     pub tx_hash: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub safe_app_info: Option<SafeAppInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    // Only present for executed transactions with `FEATURE_FLAG_DECODED_LOGS` enabled
+    pub logs: Option<Vec<TransactionLog>>,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -259,6 +265,58 @@ pub struct MultisigExecutionDetails {
     pub rejectors: Option<Vec<AddressEx>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gas_token_info: Option<TokenInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    // Only present when requested with `estimate_gas=true` for transactions awaiting execution
+    pub execution_estimation: Option<ExecutionEstimation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    // Only present for executed transactions with `FEATURE_FLAG_EXECUTION_COST` enabled
+    pub execution_cost: Option<ExecutionCost>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionEstimation {
+    pub gas_estimate: Option<String>,
+    pub will_revert: bool,
+    pub revert_reason: Option<String>,
+}
+
+/// Cost of the ethereum transaction executing the Safe transaction, from its receipt
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionCost {
+    pub gas_used: String,
+    /// In wei
+    pub effective_gas_price: String,
+    /// In wei of the native coin
+    pub native_cost: String,
+    pub fiat_cost: Option<String>,
+    pub fiat_code: Option<String>,
+}
+
+/// Event emitted by the ethereum transaction, with its parameters if it is a well-known event
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionLog {
+    pub log_index: u64,
+    pub address: AddressEx,
+    /// Name of the event, `None` if it was not decoded
+    pub event: Option<String>,
+    pub parameters: Vec<LogParameter>,
+    /// Raw log of events that were not decoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topics: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: String,
+    pub value: String,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -284,6 +342,47 @@ pub struct TransactionData {
     pub value: Option<String>,
     pub operation: Operation,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_checksummed_keys")]
     // Mapping with info for the addresses in data_decoded
-    pub address_info_index: Option<HashMap<String, AddressEx>>,
+    pub address_info_index: Option<AddressInfoIndex>,
+    /// Sub-transactions in execution order, when the data decodes to a MultiSend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<MultiSendAction>>,
+}
+
+/// Sub-transaction of a MultiSend
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSendAction {
+    pub to: AddressEx,
+    pub value: Option<String>,
+    pub hex_data: Option<String>,
+    pub data_decoded: Option<DataDecoded>,
+    pub operation: Operation,
+}
+
+/// Entry of the `/v1/chains/<chain_id>/transactions/details` endpoint, in the order of the
+/// requested ids. Exactly one of `details` and `error` is set.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDetailsResult {
+    pub transaction_id: String,
+    /// Status the single transaction details endpoint would have responded with
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Box<RawValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetails>,
+}
+
+/// Note of a device on a transaction, merged into the transaction details of that device
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionNote {
+    pub chain_id: String,
+    /// `safe_tx_hash` of multisig transactions, the transaction id otherwise
+    pub transaction_id: String,
+    pub note: String,
+    /// In ms
+    pub updated_at: i64,
 }
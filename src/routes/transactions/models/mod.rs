@@ -13,7 +13,7 @@ pub const ID_PREFIX_ETHEREUM_TX: &str = "ethereum";
 pub const ID_PREFIX_CREATION_TX: &str = "creation";
 
 #[derive(PartialEq, Debug)]
-pub(crate) enum TransactionIdParts {
+pub enum TransactionIdParts {
     Creation(String),
     Multisig {
         safe_address: String,
@@ -32,6 +32,32 @@ pub(crate) enum TransactionIdParts {
     TransactionHash(String),
 }
 
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionIdType {
+    Multisig,
+    Module,
+    Ethereum,
+    Creation,
+    SafeTxHash,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRawIds {
+    pub id: String,
+    pub chain_id: String,
+    pub id_type: TransactionIdType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details_hash: Option<String>,
+}
+
 #[derive(Serialize, Debug, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionStatus {
@@ -40,6 +66,9 @@ pub enum TransactionStatus {
     Cancelled,
     Failed,
     Success,
+    // Set by the queued transaction expiry policy
+    Expired,
+    Obsolete,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -1,4 +1,5 @@
 use crate::common::models::data_decoded::Operation;
+use crate::utils::validation::{Validate, Validator};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -7,6 +8,133 @@ pub struct ConfirmationRequest {
     pub signed_safe_tx_hash: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplacementPreviewRequest {
+    pub nonce: u64,
+}
+
+/// <summary>Example body of TransactionDetailsRequest</summary>
+///
+/// ```json
+/// {
+///   "transactionIds": [
+///     "multisig_0x1230B3d59858296A31053C1b8562Ecf89A2f888b_0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621",
+///     "0x83c1e32d4486e113ff8c10c2d93986c7122ee2ce6a6eaf1bd8753fee990da0d1"
+///   ]
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDetailsRequest {
+    pub transaction_ids: Vec<String>,
+}
+
+/// <summary>Example body of TransactionNoteRequest</summary>
+///
+/// ```json
+/// {
+///   "note": "Payroll March"
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionNoteRequest {
+    pub note: String,
+}
+
+/// <summary>Example body of TransferBuildRequest</summary>
+///
+/// ```json
+/// {
+///   "recipient": "0xF353eBBa77e5E71c210599236686D51cA1F88b84",
+///   "tokenAddress": "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88",
+///   "amount": "12.5"
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferBuildRequest {
+    pub recipient: String,
+    /// The native coin is transferred if not set
+    pub token_address: Option<String>,
+    /// In token units (e.g. `"1.5"` ETH), converted with the decimals of the token
+    pub amount: String,
+    /// Defaults to the current nonce of the Safe
+    pub nonce: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OwnerChange {
+    Add,
+    Remove,
+    Swap,
+}
+
+/// <summary>Example body of OwnerChangeRequest</summary>
+///
+/// ```json
+/// {
+///   "change": "SWAP",
+///   "owner": "0xF353eBBa77e5E71c210599236686D51cA1F88b84",
+///   "newOwner": "0xBe8C10Dbf4c6148f9834C56C3331f8191f355552"
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerChangeRequest {
+    pub change: OwnerChange,
+    /// Owner to add or remove, or the owner replaced by a swap
+    pub owner: String,
+    /// Only for swaps
+    pub new_owner: Option<String>,
+    /// Not for swaps. Defaults to the current threshold, lowered to the remaining amount of owners
+    /// when removing an owner
+    pub threshold: Option<u64>,
+    /// Defaults to the current nonce of the Safe
+    pub nonce: Option<u64>,
+}
+
+/// <summary>Example body of SafeTxHashVerificationRequest</summary>
+///
+/// ```json
+/// {
+///   "safeAddress": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+///   "to": "0xF353eBBa77e5E71c210599236686D51cA1F88b84",
+///   "value": "1000000000000000000",
+///   "data": null,
+///   "operation": 0,
+///   "safeTxGas": "0",
+///   "baseGas": "0",
+///   "gasPrice": "0",
+///   "nonce": 12,
+///   "safeTxHash": "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTxHashVerificationRequest {
+    pub safe_address: String,
+    pub to: String,
+    pub value: String,
+    pub data: Option<String>,
+    pub operation: Operation,
+    pub safe_tx_gas: String,
+    pub base_gas: String,
+    pub gas_price: String,
+    /// Defaults to the zero address
+    pub gas_token: Option<String>,
+    /// Defaults to the zero address
+    pub refund_receiver: Option<String>,
+    pub nonce: u64,
+    pub safe_tx_hash: String,
+    /// Domain hash shown by the hardware wallet, if any
+    pub domain_hash: Option<String>,
+    /// Message hash shown by the hardware wallet, if any
+    pub message_hash: Option<String>,
+}
+
 /// MultisigTransactionRequest
 ///
 /// <details>
@@ -53,3 +181,95 @@ pub struct MultisigTransactionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub origin: Option<String>,
 }
+
+impl Validate for ConfirmationRequest {
+    fn validate(&self, validator: &mut Validator) {
+        validator.signature("signedSafeTxHash", &self.signed_safe_tx_hash);
+    }
+}
+
+impl Validate for MultisigTransactionRequest {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .address("to", &self.to)
+            .uint("value", &self.value)
+            .hex_data("data", &self.data)
+            .uint("nonce", &self.nonce)
+            .uint("safeTxGas", &self.safe_tx_gas)
+            .uint("baseGas", &self.base_gas)
+            .uint("gasPrice", &self.gas_price)
+            .address("gasToken", &self.gas_token)
+            .optional_address("refundReceiver", &self.refund_receiver)
+            .hash("safeTxHash", &self.safe_tx_hash)
+            .address("sender", &self.sender)
+            .optional_signature("signature", &self.signature)
+            // Same limit as the transaction service
+            .optional_length("origin", &self.origin, 0, 200);
+    }
+}
+
+impl Validate for TransactionDetailsRequest {
+    fn validate(&self, validator: &mut Validator) {
+        validator.each(
+            "transactionIds",
+            &self.transaction_ids,
+            |validator, field, transaction_id| {
+                validator.length(field, transaction_id, 1, 500);
+            },
+        );
+    }
+}
+
+impl Validate for OwnerChangeRequest {
+    fn validate(&self, validator: &mut Validator) {
+        let is_swap = self.change == OwnerChange::Swap;
+        validator
+            .address("owner", &self.owner)
+            .optional_address("newOwner", &self.new_owner)
+            .check(
+                "newOwner",
+                self.new_owner.is_some() == is_swap,
+                "must be set for swaps only",
+            )
+            .check(
+                "threshold",
+                self.threshold.is_none() || !is_swap,
+                "can't be changed by swaps",
+            );
+    }
+}
+
+impl Validate for SafeTxHashVerificationRequest {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .address("safeAddress", &self.safe_address)
+            .address("to", &self.to)
+            .uint("value", &self.value)
+            .hex_data("data", &self.data)
+            .uint("safeTxGas", &self.safe_tx_gas)
+            .uint("baseGas", &self.base_gas)
+            .uint("gasPrice", &self.gas_price)
+            .optional_address("gasToken", &self.gas_token)
+            .optional_address("refundReceiver", &self.refund_receiver)
+            .hash("safeTxHash", &self.safe_tx_hash);
+        if let Some(domain_hash) = self.domain_hash.as_ref() {
+            validator.hash("domainHash", domain_hash);
+        }
+        if let Some(message_hash) = self.message_hash.as_ref() {
+            validator.hash("messageHash", message_hash);
+        }
+    }
+}
+
+impl Validate for TransferBuildRequest {
+    fn validate(&self, validator: &mut Validator) {
+        let mut parts = self.amount.splitn(2, '.');
+        let is_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        let is_decimal =
+            parts.next().map_or(false, is_digits) && parts.next().map_or(true, is_digits);
+        validator
+            .address("recipient", &self.recipient)
+            .optional_address("tokenAddress", &self.token_address)
+            .check("amount", is_decimal, "must be a decimal number");
+    }
+}
@@ -1,5 +1,7 @@
 use super::*;
+use crate::common::models::data_decoded::Operation;
 use crate::providers::info::SafeAppInfo;
+use crate::routes::transactions::models::details::ExecutionEstimation;
 use serde::Serialize;
 
 ///TransactionSummary - object returned for [TransactionListItem::Transaction]
@@ -9,8 +11,13 @@ use serde::Serialize;
 ///
 /// ```json
 /// {
+///   "count": 2,
 ///   "next": null,
 ///   "previous": null,
+///   "pageInfo": {
+///     "hasNext": false,
+///     "cursor": null
+///   },
 ///   "results": [
 ///     {
 ///       "type": "DATE_LABEL",
@@ -133,6 +140,58 @@ pub struct TransactionSummary {
     pub execution_info: Option<ExecutionInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub safe_app_info: Option<SafeAppInfo>,
+    /// See [description](crate::routes::transactions::converters::description)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplacementPreview {
+    pub nonce: u64,
+    // Queued transactions that become invalid once a transaction with this nonce is executed
+    pub replaced_transactions: Vec<TransactionSummary>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSummary {
+    pub total: u64,
+    // Only present if a signer was provided
+    pub awaiting_signature: Option<u64>,
+    pub awaiting_execution: u64,
+    // Transactions sharing their nonce with other queued transactions
+    pub conflicting: u64,
+    // The queue has more transactions than the ones counted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incomplete: Option<bool>,
+}
+
+/// Changes of a queue page relative to the snapshot identified by `since_etag`. Items are identified
+/// by their `key`: the transaction id, or the label / conflict header with its value.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueDiff {
+    /// Identifies this page, to be sent as `since_etag` with the next request
+    pub etag: String,
+    /// The snapshot was unknown (or expired), every item is `added` and previously known items are
+    /// to be dropped
+    pub reset: bool,
+    pub count: Option<u64>,
+    pub next: Option<String>,
+    pub previous: Option<String>,
+    /// Keys of every item of the page, in their order
+    pub order: Vec<String>,
+    pub added: Vec<QueueDiffItem>,
+    pub changed: Vec<QueueDiffItem>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueDiffItem {
+    pub key: String,
+    pub item: TransactionListItem,
 }
 
 #[derive(Serialize, Debug, PartialEq)]
@@ -166,6 +225,9 @@ pub enum TransactionListItem {
     Transaction {
         transaction: TransactionSummary,
         conflict_type: ConflictType,
+        /// Only set for executable queued transactions when requested
+        #[serde(skip_serializing_if = "Option::is_none")]
+        executability: Option<ExecutionEstimation>,
     },
     DateLabel {
         timestamp: i64,
@@ -190,3 +252,65 @@ pub enum ConflictType {
     HasNext,
     End,
 }
+
+/// Ready to sign Safe transaction, with every field covered by `safeTxHash`
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBuild {
+    pub to: String,
+    pub value: String,
+    pub data: String,
+    pub operation: Operation,
+    pub safe_tx_gas: String,
+    pub base_gas: String,
+    pub gas_price: String,
+    pub gas_token: String,
+    pub refund_receiver: String,
+    pub nonce: u64,
+    pub safe_tx_hash: String,
+}
+
+/// Recomputed `safeTxHash` of a Safe transaction, compared to the submitted hashes
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTxHashVerification {
+    pub is_valid: bool,
+    pub safe_tx_hash: String,
+    pub domain_hash: String,
+    pub message_hash: String,
+    pub domain: SafeTxDomain,
+    /// Submitted hashes that differ from the recomputed ones
+    pub mismatches: Vec<HashMismatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub likely_cause: Option<MismatchCause>,
+}
+
+/// EIP-712 domain of the Safe the hash is recomputed for
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTxDomain {
+    /// Not part of the domain of Safes older than 1.3.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    pub verifying_contract: String,
+    pub version: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HashMismatch {
+    pub field: String,
+    pub expected: String,
+    pub submitted: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MismatchCause {
+    /// The domain (chain or Safe address) differs
+    Domain,
+    /// The domain matches, so one of the transaction fields differs
+    TransactionFields,
+    /// The hash was computed with the domain separator of another Safe version (before/after 1.3.0)
+    DomainVersion,
+}
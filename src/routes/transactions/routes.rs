@@ -1,10 +1,23 @@
 use crate::cache::cache_operations::CacheResponse;
-use crate::routes::transactions::handlers::{details, history, proposal, queued};
+use crate::common::models::page::Page;
+use crate::config::{execution_estimation_cache_duration, request_cache_duration};
+use crate::monitoring::audit::{self, AuditOperation, Caller};
+use crate::routes::transactions::handlers::{
+    details, hash_verification, history, nonce, notes, owners, proposal, queued, replacement,
+    transfer_history, transfers,
+};
 use crate::routes::transactions::models::requests::{
-    ConfirmationRequest, MultisigTransactionRequest,
+    ConfirmationRequest, MultisigTransactionRequest, OwnerChangeRequest, ReplacementPreviewRequest,
+    SafeTxHashVerificationRequest, TransactionDetailsRequest, TransactionNoteRequest,
+    TransferBuildRequest,
 };
+use crate::routes::transactions::models::summary::{TransactionListItem, TransactionSummary};
 use crate::utils::context::RequestContext;
+use crate::utils::device::Device;
 use crate::utils::errors::ApiResult;
+use crate::utils::read_only;
+use crate::utils::validation::Validate;
+use rocket::http::Header;
 use rocket::response::content;
 use rocket::serde::json::Error;
 use rocket::serde::json::Json;
@@ -25,18 +38,160 @@ use rocket::serde::json::Json;
  *
  * ## Query paramets
  *
- * There aren't any query parameters that can be passed to this endpoint.
+ * - `estimate_gas`: when `true`, multisig transactions awaiting execution include an `executionEstimation` in their `detailedExecutionInfo`, with the gas estimation of `execTransaction` from the chain RPC and whether the execution would revert with the current signatures.
+ *
+ * ## Headers
+ *
+ * With the `Safe-Device-Uuid` and `Safe-Device-Token` headers, the response contains the `note` the device stored for the transaction with `PUT /v1/chains/<chain_id>/transactions/<transaction_id>/note`.
  */
-#[get("/v1/chains/<chain_id>/transactions/<details_id>")]
+#[get("/v1/chains/<chain_id>/transactions/<details_id>?<estimate_gas>")]
 pub async fn get_transactions(
     context: RequestContext,
+    device: Option<Device>,
     chain_id: String,
     details_id: String,
+    estimate_gas: Option<bool>,
 ) -> ApiResult<content::Json<String>> {
-    CacheResponse::new(&context)
-        .resp_generator(|| details::get_transactions_details(&context, &chain_id, &details_id))
+    let estimate_gas = estimate_gas.unwrap_or(false);
+    let duration = if estimate_gas {
+        execution_estimation_cache_duration()
+    } else {
+        request_cache_duration()
+    };
+    let details = CacheResponse::new(&context)
+        .duration(duration)
+        .resp_generator(|| {
+            details::get_transactions_details(&context, &chain_id, &details_id, estimate_gas)
+        })
         .execute()
-        .await
+        .await?;
+    // Notes are merged after the response cache, which is shared by all devices
+    match device {
+        Some(device) => Ok(content::Json(
+            notes::with_device_note(&context, &device, &chain_id, &details_id, details.0).await?,
+        )),
+        None => Ok(details),
+    }
+}
+
+/**
+ * `/v1/chains/<chain_id>/transactions/<transaction_id>/note` <br />
+ * Returns the stored [TransactionNote](crate::routes::transactions::models::details::TransactionNote), `null` once removed
+ *
+ * # Transaction Note
+ *
+ * Stores a free-text note (up to 500 characters) on a transaction on behalf of a device, returned as `note` by the transaction details for that device. Requests need the `Safe-Device-Uuid` and `Safe-Device-Token` headers, like the Safe labels. The note of a multisig transaction is shared by its id and its `safe_tx_hash`.
+ *
+ * An empty `note` removes the note of the transaction.
+ *
+ * ## Path
+ *
+ * `PUT /v1/chains/<chain_id>/transactions/<transaction_id>/note`
+ *
+ * The expected [crate::routes::transactions::models::requests::TransactionNoteRequest] body for this request can be found in the sections of the models
+ */
+#[put(
+    "/v1/chains/<chain_id>/transactions/<details_id>/note",
+    format = "application/json",
+    data = "<transaction_note_request>"
+)]
+pub async fn put_transaction_note<'e>(
+    context: RequestContext,
+    caller: Caller,
+    device: Device,
+    chain_id: String,
+    details_id: String,
+    transaction_note_request: Result<Json<TransactionNoteRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    read_only::ensure_writable(&context).await?;
+    let transaction_note_request = transaction_note_request?.0;
+    let result = notes::set_transaction_note(
+        &context,
+        &device,
+        &chain_id,
+        &details_id,
+        &transaction_note_request,
+    )
+    .await;
+    audit::record(
+        AuditOperation::SetTransactionNote,
+        &details_id,
+        &caller,
+        audit::payload_hash(&transaction_note_request),
+        &result,
+    );
+    Ok(content::Json(serde_json::to_string(&result?)?))
+}
+
+/**
+ * `/v1/transactions/notes` <br />
+ * Returns [Vec] of [TransactionNote](crate::routes::transactions::models::details::TransactionNote)
+ *
+ * Notes stored for the device across all chains, authenticated with the same headers as `PUT /v1/chains/<chain_id>/transactions/<transaction_id>/note`
+ */
+#[get("/v1/transactions/notes")]
+pub async fn get_transaction_notes(
+    context: RequestContext,
+    device: Device,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &notes::get_transaction_notes(&context, &device).await?,
+    )?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/transactions/details` <br />
+ * Returns a list of [TransactionDetailsResult](crate::routes::transactions::models::details::TransactionDetailsResult)
+ *
+ * # Multiple Transaction Details
+ *
+ * Returns the details of up to `TRANSACTION_DETAILS_BATCH_SIZE` transactions in one request, in the order of the requested ids. The transactions are fetched concurrently and share the cached entries of the single transaction details endpoint.
+ *
+ * Each entry has the `status` the single endpoint would have responded with, and either the `details` or the `error` for that transaction, so that one missing transaction doesn't fail the whole request.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/transactions/details`
+ *
+ * The expected [crate::routes::transactions::models::requests::TransactionDetailsRequest] body for this request can be found in the sections of the models
+ */
+#[post(
+    "/v1/chains/<chain_id>/transactions/details",
+    format = "application/json",
+    data = "<details_request>"
+)]
+pub async fn post_transactions_details<'e>(
+    context: RequestContext,
+    chain_id: String,
+    details_request: Result<Json<TransactionDetailsRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &details::get_multiple_transactions_details(&context, &chain_id, &details_request?.0)
+            .await?,
+    )?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/transactions/<transaction_id>/raw-ids` <br />
+ * Returns [TransactionRawIds](crate::routes::transactions::models::TransactionRawIds)
+ *
+ * # Transaction Raw Ids
+ *
+ * Decomposes a gateway transaction id into the identifiers used by the core services, so integrators don't need to reverse-engineer the id scheme. The id is `<type>_<safe_address>_<hashes...>` where `<type>` is one of `multisig`, `module`, `ethereum` or `creation`; any other value is treated as a `safe_tx_hash`.
+ *
+ * No requests are made to the core services, the id is not checked to exist.
+ *
+ * ## Path
+ *
+ * `GET /v1/chains/<chain_id>/transactions/<transaction_id>/raw-ids`
+ */
+#[get("/v1/chains/<chain_id>/transactions/<details_id>/raw-ids")]
+pub async fn get_transaction_raw_ids(
+    chain_id: String,
+    details_id: String,
+) -> ApiResult<content::Json<String>> {
+    let raw_ids = details::get_transaction_raw_ids(&chain_id, &details_id)?;
+    Ok(content::Json(serde_json::to_string(&raw_ids)?))
 }
 
 /**
@@ -47,6 +202,8 @@ pub async fn get_transactions(
  *
  * This endpoint provides a way for submitting confirmations for clients making use of the `safe_tx_hash` as part of the path, and the very same `safe_tx_hash` signed by an owner corresponding to the safe from which the transaction is being sent.
  *
+ * Before the confirmation is forwarded, the signer is recovered from the signature and has to be an owner of the Safe that did not confirm the transaction yet. Rejected signatures are answered with the error `code` of the problem: `2001` malformed signature, `2002` signer not recoverable, `2003` signer is not an owner, `2004` owner confirmed already (`409`), `2005` contract signature rejected by the owner contract.
+ *
  * If the confirmation is submitted successfully to the core services, then the local cache for that specific transaction is invalidated and the updated transaction details with the confirmation are returned in the request.
  *
  * ## Path
@@ -66,20 +223,34 @@ pub async fn get_transactions(
 )]
 pub async fn post_confirmation<'e>(
     context: RequestContext,
+    caller: Caller,
     chain_id: String,
     safe_tx_hash: String,
     tx_confirmation_request: Result<Json<ConfirmationRequest>, Error<'e>>,
 ) -> ApiResult<content::Json<String>> {
-    proposal::submit_confirmation(
+    read_only::ensure_writable(&context).await?;
+    let request: ConfirmationRequest = tx_confirmation_request?.0;
+    request.validated()?;
+    let result = proposal::submit_confirmation(
         &context,
         &chain_id,
         &safe_tx_hash,
-        &tx_confirmation_request?.0.signed_safe_tx_hash,
+        &request.signed_safe_tx_hash,
     )
-    .await?;
+    .await;
+    audit::record(
+        AuditOperation::ConfirmTransaction,
+        &safe_tx_hash,
+        &caller,
+        audit::payload_hash(&request),
+        &result,
+    );
+    result?;
 
     CacheResponse::new(&context)
-        .resp_generator(|| details::get_transactions_details(&context, &chain_id, &safe_tx_hash))
+        .resp_generator(|| {
+            details::get_transactions_details(&context, &chain_id, &safe_tx_hash, false)
+        })
         .execute()
         .await
 }
@@ -135,12 +306,57 @@ pub async fn get_transactions_history(
                 &timezone_offset,
             )
         })
+        .skip_cache_if(|page: &Page<TransactionListItem>| page.incomplete.is_some())
         .execute()
         .await
 }
 
 /**
- * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>&<timezone_offset>&<trusted>` <br />
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transfers?<token>&<direction>&<cursor>` <br />
+ * Returns a [Page](crate::models::commons::Page) of [TransactionSummary](crate::models::handlers::transactions::summary::TransactionSummary)
+ *
+ * # Transfers
+ *
+ * The executed transfers of the Safe, newest first, e.g. for the activity of a single asset. Every transfer is a
+ * `TransactionSummary` with a `Transfer` transaction info, the same as in the transaction history, without date labels.
+ *
+ * ## Path
+ *
+ * `GET /v1/chains/<chain_id>/safes/<safe_address>/transfers?<token>&<direction>&<cursor>`
+ *
+ * ## Query parameters
+ *
+ * - `<token>`: only the transfers of the token at this address
+ * - `<direction>`: `incoming` or `outgoing`, only the transfers received or sent by the Safe
+ * - `<cursor>` is the desired page of data to be loaded. Values for this parameter can be either `Page.next` or `Page.previous`.
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/transfers?<token>&<direction>&<cursor>")]
+pub async fn get_transfers(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    token: Option<String>,
+    direction: Option<String>,
+    cursor: Option<String>,
+) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .resp_generator(|| {
+            transfer_history::get_transfers(
+                &context,
+                &chain_id,
+                &safe_address,
+                &token,
+                &direction,
+                &cursor,
+            )
+        })
+        .skip_cache_if(|page: &Page<TransactionSummary>| page.incomplete.is_some())
+        .execute()
+        .await
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>&<timezone_offset>&<trusted>&<check_executability>` <br />
  * Returns a [Page](crate::models::commons::Page) of  [TransactionListItem](crate::models::handlers::transactions::summary::TransactionListItem)
  *
  * # Transactions Queued
@@ -155,7 +371,7 @@ pub async fn get_transactions_history(
  *
  * ## Path
  *
- * `GET /v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>&<timezone_offset>&<trusted>`
+ * `GET /v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>&<timezone_offset>&<trusted>&<check_executability>`
  *
  * The response is a list of [crate::models::handlers::transactions::summary::TransactionListItem], which is a polymorphic struct. Details follow in the models sections.
  *
@@ -165,8 +381,9 @@ pub async fn get_transactions_history(
  * - `<cursor>` is the desired page of data to be loaded. Values for this parameter can be either `Page.next` or `Page.previous`. **WARNING:** Don't fiddle with the values of these 2 fields.
  * - `<timezone_offset>`: Currently ignored by the gateway.
  * - `<trusted>`: forwarded directly to the core services. Only for debugging purposes clients **should not** send it (unless they know what they are doing).
+ * - `<check_executability>`: when `true`, transactions with the nonce of the Safe and enough confirmations include an `executability` with the gas estimation of `execTransaction` from the chain RPC and whether the execution would revert, so that signers can be warned. Transactions with later nonces are not simulated.
  */
-#[get("/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>&<timezone_offset>&<trusted>")]
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>&<timezone_offset>&<trusted>&<check_executability>")]
 pub async fn get_transactions_queued(
     context: RequestContext,
     chain_id: String,
@@ -174,8 +391,16 @@ pub async fn get_transactions_queued(
     cursor: Option<String>,
     timezone_offset: Option<String>,
     trusted: Option<bool>,
+    check_executability: Option<bool>,
 ) -> ApiResult<content::Json<String>> {
+    let check_executability = check_executability.unwrap_or(false);
+    let duration = if check_executability {
+        execution_estimation_cache_duration()
+    } else {
+        request_cache_duration()
+    };
     CacheResponse::new(&context)
+        .duration(duration)
         .resp_generator(|| {
             queued::get_queued_transactions(
                 &context,
@@ -184,12 +409,336 @@ pub async fn get_transactions_queued(
                 &cursor,
                 &timezone_offset,
                 &trusted,
+                check_executability,
+            )
+        })
+        .skip_cache_if(|page: &Page<TransactionListItem>| page.incomplete.is_some())
+        .execute()
+        .await
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/nonce/<nonce>?<trusted>` <br />
+ * Returns [Vec] of [TransactionSummary](crate::routes::transactions::models::summary::TransactionSummary)
+ *
+ * # Transactions by nonce
+ *
+ * Every multisig transaction proposed for the nonce, whether it was executed, is still queued or was replaced (`CANCELLED`), in the order they were submitted. The summaries have the same layout as in `/transactions/queued`.
+ *
+ * ## Path
+ *
+ * `GET /v1/chains/<chain_id>/safes/<safe_address>/transactions/nonce/<nonce>?<trusted>`
+ *
+ * ## Query parameters
+ *
+ * - `<trusted>`: same as for `/transactions/queued`, defaults to `true`.
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/transactions/nonce/<nonce>?<trusted>")]
+pub async fn get_transactions_by_nonce(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    nonce: u64,
+    trusted: Option<bool>,
+) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .resp_generator(|| {
+            nonce::get_transactions_by_nonce(
+                &context,
+                &chain_id,
+                &safe_address,
+                nonce,
+                trusted.unwrap_or(true),
             )
         })
         .execute()
         .await
 }
 
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/summary?<signer>&<trusted>` <br />
+ * Returns [QueueSummary](crate::routes::transactions::models::summary::QueueSummary)
+ *
+ * # Transactions Queued Summary
+ *
+ * Counters of the queued transactions, so that clients can show badges without loading the queue pages.
+ * Counts are computed from the first `TX_QUEUED_SUMMARY_SIZE` queued transactions, `incomplete` is set if there are more.
+ *
+ * ## Path
+ *
+ * `GET /v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/summary?<signer>&<trusted>`
+ *
+ * ## Query parameters
+ *
+ * - `<signer>`: owner address for which `awaitingSignature` is computed, `awaitingSignature` is `null` if it is not an owner.
+ * - `<trusted>`: same as for `/transactions/queued`.
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/summary?<signer>&<trusted>")]
+pub async fn get_transactions_queued_summary(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    signer: Option<String>,
+    trusted: Option<bool>,
+) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .resp_generator(|| {
+            queued::get_queued_summary(&context, &chain_id, &safe_address, &signer, &trusted)
+        })
+        .execute()
+        .await
+}
+
+#[derive(Responder)]
+pub enum QueuedPollResponse {
+    #[response(status = 200, content_type = "json")]
+    Changed(String, Header<'static>),
+    #[response(status = 304)]
+    NotModified(()),
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/poll?<etag>&<cursor>&<timezone_offset>&<trusted>` <br />
+ * Returns a [Page](crate::models::commons::Page) of [TransactionListItem](crate::models::handlers::transactions::summary::TransactionListItem)
+ *
+ * # Transactions Queued Long-Polling
+ *
+ * Alternative to polling `/transactions/queued` for clients that cannot use WebSockets. The connection is held until the
+ * queue differs from the one identified by `<etag>`, or until the max wait time (`TX_QUEUED_POLL_MAX_WAIT`) passes.
+ *
+ * ## Path
+ *
+ * `GET /v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/poll?<etag>&<cursor>&<timezone_offset>&<trusted>`
+ *
+ * ## Query parameters
+ *
+ * - `<etag>`: value of the `ETag` header of the previous response. If omitted the current queue is returned right away.
+ * - `<cursor>`, `<timezone_offset>` and `<trusted>`: same as for `/transactions/queued`.
+ *
+ * Returns `200` with the new page and `ETag` header if the queue changed, `304` otherwise.
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/poll?<etag>&<cursor>&<timezone_offset>&<trusted>")]
+pub async fn get_transactions_queued_poll(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    etag: Option<String>,
+    cursor: Option<String>,
+    timezone_offset: Option<String>,
+    trusted: Option<bool>,
+) -> ApiResult<QueuedPollResponse> {
+    let result = queued::poll_queued_transactions(
+        &context,
+        &chain_id,
+        &safe_address,
+        &etag,
+        &cursor,
+        &timezone_offset,
+        &trusted,
+    )
+    .await?;
+    Ok(match result {
+        Some((etag, body)) => {
+            QueuedPollResponse::Changed(body, Header::new("ETag", format!("\"{}\"", etag)))
+        }
+        None => QueuedPollResponse::NotModified(()),
+    })
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/diff?<since_etag>&<cursor>&<timezone_offset>&<trusted>` <br />
+ * Returns [QueueDiff](crate::routes::transactions::models::summary::QueueDiff)
+ *
+ * # Transactions Queued Diff
+ *
+ * For clients polling large queues: only the items of the page that were added, changed or removed since the page identified by
+ * `<since_etag>` are returned, along with the keys of every item in their order. Pages are identified by the `etag` of the
+ * previous response, which is known to the gateway for 10 minutes. Without `<since_etag>`, or if it is not known (anymore), the
+ * response has `reset` set and every item is added.
+ *
+ * ## Path
+ *
+ * `GET /v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/diff?<since_etag>&<cursor>&<timezone_offset>&<trusted>`
+ *
+ * ## Query parameters
+ *
+ * - `<since_etag>`: `etag` of the previous response
+ * - `<cursor>`, `<timezone_offset>` and `<trusted>`: same as for `/transactions/queued`.
+ */
+#[get("/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued/diff?<since_etag>&<cursor>&<timezone_offset>&<trusted>")]
+pub async fn get_transactions_queued_diff(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    since_etag: Option<String>,
+    cursor: Option<String>,
+    timezone_offset: Option<String>,
+    trusted: Option<bool>,
+) -> ApiResult<content::Json<String>> {
+    let diff = queued::get_queued_diff(
+        &context,
+        &chain_id,
+        &safe_address,
+        &since_etag,
+        &cursor,
+        &timezone_offset,
+        &trusted,
+    )
+    .await?;
+    Ok(content::Json(serde_json::to_string(&diff)?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/replacement-preview` <br />
+ * Returns [ReplacementPreview](crate::routes::transactions::models::summary::ReplacementPreview)
+ *
+ * # Replacement Preview
+ *
+ * Returns the queued transactions that would be replaced by executing a transaction with the given `nonce`,
+ * so that clients can warn about them before proposing a replacement.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/safes/<safe_address>/transactions/replacement-preview`
+ *
+ * Example request body:
+ *
+ * ```json
+ * {
+ *   "nonce": 393
+ * }
+ * ```
+ *
+ * A `nonce` lower than the current nonce of the Safe results in a `422`.
+ */
+#[post(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/replacement-preview",
+    format = "application/json",
+    data = "<replacement_preview_request>"
+)]
+pub async fn post_replacement_preview<'e>(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    replacement_preview_request: Result<Json<ReplacementPreviewRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &replacement::get_replacement_preview(
+            &context,
+            &chain_id,
+            &safe_address,
+            &replacement_preview_request?.0,
+        )
+        .await?,
+    )?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/transactions/build-transfer` <br />
+ * Returns [TransactionBuild](crate::routes::transactions::models::summary::TransactionBuild)
+ *
+ * # Build Transfer
+ *
+ * Encodes a transfer of the native coin (no `tokenAddress`) or of an ERC20 token, returning the fields of the Safe transaction and its `safeTxHash` to be signed and proposed.
+ * The `amount` is given in token units and converted with the decimals of the token.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/safes/<safe_address>/transactions/build-transfer`
+ *
+ * The expected [crate::routes::transactions::models::requests::TransferBuildRequest] body for this request can be found in the sections of the models
+ *
+ * Amounts with more decimals than the token, or a `nonce` lower than the current nonce of the Safe, result in a `422`.
+ */
+#[post(
+    "/v1/chains/<chain_id>/safes/<safe_address>/transactions/build-transfer",
+    format = "application/json",
+    data = "<transfer_build_request>"
+)]
+pub async fn post_build_transfer<'e>(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    transfer_build_request: Result<Json<TransferBuildRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &transfers::build_transfer(
+            &context,
+            &chain_id,
+            &safe_address,
+            &transfer_build_request?.0,
+        )
+        .await?,
+    )?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/safes/<safe_address>/settings/owners` <br />
+ * Returns [TransactionBuild](crate::routes::transactions::models::summary::TransactionBuild)
+ *
+ * # Build Owner Change
+ *
+ * Encodes a call of the Safe to itself adding (`addOwnerWithThreshold`), removing (`removeOwner`) or replacing (`swapOwner`) an owner, returning the fields of the Safe transaction and its `safeTxHash` to be signed and proposed.
+ * The `prevOwner` pointer the contract expects for removals and swaps is resolved from the owners of the Safe.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/safes/<safe_address>/settings/owners`
+ *
+ * The expected [crate::routes::transactions::models::requests::OwnerChangeRequest] body for this request can be found in the sections of the models
+ *
+ * Changes the contract would revert (e.g. adding an existing owner, removing the last owner or a threshold higher than the resulting amount of owners), or a `nonce` lower than the current nonce of the Safe, result in a `422`.
+ */
+#[post(
+    "/v1/chains/<chain_id>/safes/<safe_address>/settings/owners",
+    format = "application/json",
+    data = "<owner_change_request>"
+)]
+pub async fn post_owner_change<'e>(
+    context: RequestContext,
+    chain_id: String,
+    safe_address: String,
+    owner_change_request: Result<Json<OwnerChangeRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &owners::build_owner_change(&context, &chain_id, &safe_address, &owner_change_request?.0)
+            .await?,
+    )?))
+}
+
+/**
+ * `/v1/chains/<chain_id>/transactions/verify-hash` <br />
+ * Returns [SafeTxHashVerification](crate::routes::transactions::models::summary::SafeTxHashVerification)
+ *
+ * # Verify Safe Transaction Hash
+ *
+ * Recomputes the EIP-712 `safeTxHash` of the submitted transaction fields for the domain of the Safe (chain id, address and version) and compares it to the submitted `safeTxHash`, so that the hash shown by a hardware wallet can be checked before signing.
+ * If the `domainHash` and `messageHash` shown by the wallet are submitted as well, they are compared too.
+ *
+ * ## Path
+ *
+ * `POST /v1/chains/<chain_id>/transactions/verify-hash`
+ *
+ * The expected [crate::routes::transactions::models::requests::SafeTxHashVerificationRequest] body for this request can be found in the sections of the models
+ *
+ * A hash that doesn't match is not an error: the response has `isValid: false`, the mismatching hashes and, when it can be told, the `likelyCause` (`DOMAIN`, `TRANSACTION_FIELDS` or `DOMAIN_VERSION`).
+ */
+#[post(
+    "/v1/chains/<chain_id>/transactions/verify-hash",
+    format = "application/json",
+    data = "<verification_request>"
+)]
+pub async fn post_verify_safe_tx_hash<'e>(
+    context: RequestContext,
+    chain_id: String,
+    verification_request: Result<Json<SafeTxHashVerificationRequest>, Error<'e>>,
+) -> ApiResult<content::Json<String>> {
+    Ok(content::Json(serde_json::to_string(
+        &hash_verification::verify_safe_tx_hash(&context, &chain_id, &verification_request?.0)
+            .await?,
+    )?))
+}
+
 /**
  * `/v1/chains/<chain_id>/transactions/<safe_address>/propose` <br />
  * No return value
@@ -216,17 +765,28 @@ pub async fn get_transactions_queued(
 )]
 pub async fn post_transaction<'e>(
     context: RequestContext,
+    caller: Caller,
     chain_id: String,
     safe_address: String,
     multisig_transaction_request: Result<Json<MultisigTransactionRequest>, Error<'e>>,
 ) -> ApiResult<content::Json<String>> {
+    read_only::ensure_writable(&context).await?;
     let request: MultisigTransactionRequest = multisig_transaction_request?.0;
+    request.validated()?;
 
-    proposal::propose_transaction(&context, &chain_id, &safe_address, &request).await?;
+    let result = proposal::propose_transaction(&context, &chain_id, &safe_address, &request).await;
+    audit::record(
+        AuditOperation::ProposeTransaction,
+        &safe_address,
+        &caller,
+        audit::payload_hash(&request),
+        &result,
+    );
+    result?;
 
     let tx_details = CacheResponse::new(&context)
         .resp_generator(|| {
-            details::get_transactions_details(&context, &chain_id, &request.safe_tx_hash)
+            details::get_transactions_details(&context, &chain_id, &request.safe_tx_hash, false)
         })
         .execute()
         .await;
@@ -0,0 +1,206 @@
+use crate::common::models::backend::chains::{
+    BlockExplorerUriTemplate, ChainInfo, GasPrice, NativeCurrency, RpcAuthentication, RpcUri, Theme,
+};
+use crate::providers::info::{SafeAppInfo, SafeInfo};
+use crate::routes::transactions::models::summary::{ExecutionInfo, TransactionSummary};
+use crate::routes::transactions::models::{TransactionInfo, TransactionStatus};
+
+pub struct SafeInfoBuilder {
+    safe_info: SafeInfo,
+}
+
+impl SafeInfoBuilder {
+    pub fn new(address: &str) -> Self {
+        SafeInfoBuilder {
+            safe_info: SafeInfo {
+                address: address.to_string(),
+                nonce: 0,
+                threshold: 1,
+                owners: vec![String::from("0x1230B3d59858296A31053C1b8562Ecf89A2f888b")],
+                master_copy: String::from("0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552"),
+                modules: None,
+                fallback_handler: String::from("0xf48f2B2d2a534e402487b3ee7C18c33Aec0Fe5e4"),
+                guard: String::from("0x0000000000000000000000000000000000000000"),
+                version: Some(String::from("1.3.0")),
+            },
+        }
+    }
+
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.safe_info.nonce = nonce;
+        self
+    }
+
+    pub fn threshold(mut self, threshold: u64) -> Self {
+        self.safe_info.threshold = threshold;
+        self
+    }
+
+    pub fn owners(mut self, owners: &[&str]) -> Self {
+        self.safe_info.owners = owners.iter().map(|owner| owner.to_string()).collect();
+        self
+    }
+
+    pub fn master_copy(mut self, master_copy: &str) -> Self {
+        self.safe_info.master_copy = master_copy.to_string();
+        self
+    }
+
+    pub fn modules(mut self, modules: &[&str]) -> Self {
+        self.safe_info.modules = Some(modules.iter().map(|module| module.to_string()).collect());
+        self
+    }
+
+    pub fn fallback_handler(mut self, fallback_handler: &str) -> Self {
+        self.safe_info.fallback_handler = fallback_handler.to_string();
+        self
+    }
+
+    pub fn guard(mut self, guard: &str) -> Self {
+        self.safe_info.guard = guard.to_string();
+        self
+    }
+
+    pub fn version(mut self, version: Option<&str>) -> Self {
+        self.safe_info.version = version.map(|it| it.to_string());
+        self
+    }
+
+    pub fn build(self) -> SafeInfo {
+        self.safe_info
+    }
+}
+
+pub struct ChainInfoBuilder {
+    chain_info: ChainInfo,
+}
+
+impl ChainInfoBuilder {
+    pub fn new(chain_id: &str) -> Self {
+        ChainInfoBuilder {
+            chain_info: ChainInfo {
+                recommended_master_copy_version: String::from("1.3.0"),
+                transaction_service: String::from("https://safe-transaction.example.com"),
+                vpc_transaction_service: String::from("http://safe-transaction-web.default"),
+                transaction_service_fallback_uri: None,
+                vpc_transaction_service_fallback_uri: None,
+                chain_id: chain_id.to_string(),
+                chain_name: String::from("Testnet"),
+                short_name: String::from("test"),
+                l2: false,
+                description: String::from(""),
+                rpc_uri: RpcUri {
+                    authentication: RpcAuthentication::NoAuthentication,
+                    value: String::from("https://rpc.example.com"),
+                },
+                block_explorer_uri_template: BlockExplorerUriTemplate {
+                    address: String::from("https://explorer.example.com/address/{{address}}"),
+                    tx_hash: String::from("https://explorer.example.com/tx/{{txHash}}"),
+                    api: String::from(
+                        "https://api.explorer.example.com/api?module={{module}}&action={{action}}&address={{address}}&apiKey={{apiKey}}",
+                    ),
+                },
+                native_currency: NativeCurrency {
+                    name: String::from("Ether"),
+                    symbol: String::from("ETH"),
+                    decimals: 18,
+                    logo_uri: String::from("https://assets.example.com/eth.png"),
+                },
+                theme: Theme {
+                    text_color: String::from("#ffffff"),
+                    background_color: String::from("#000000"),
+                },
+                ens_registry_address: None,
+                gas_price: vec![],
+                disabled_wallets: vec![],
+                features: vec![],
+            },
+        }
+    }
+
+    pub fn transaction_service(mut self, transaction_service: &str) -> Self {
+        self.chain_info.transaction_service = transaction_service.to_string();
+        self
+    }
+
+    pub fn vpc_transaction_service(mut self, vpc_transaction_service: &str) -> Self {
+        self.chain_info.vpc_transaction_service = vpc_transaction_service.to_string();
+        self
+    }
+
+    pub fn rpc_uri(mut self, authentication: RpcAuthentication, value: &str) -> Self {
+        self.chain_info.rpc_uri = RpcUri {
+            authentication,
+            value: value.to_string(),
+        };
+        self
+    }
+
+    pub fn l2(mut self, l2: bool) -> Self {
+        self.chain_info.l2 = l2;
+        self
+    }
+
+    pub fn gas_price(mut self, gas_price: Vec<GasPrice>) -> Self {
+        self.chain_info.gas_price = gas_price;
+        self
+    }
+
+    pub fn features(mut self, features: &[&str]) -> Self {
+        self.chain_info.features = features.iter().map(|it| it.to_string()).collect();
+        self
+    }
+
+    pub fn build(self) -> ChainInfo {
+        self.chain_info
+    }
+}
+
+pub struct TransactionSummaryBuilder {
+    transaction_summary: TransactionSummary,
+}
+
+impl TransactionSummaryBuilder {
+    pub fn new(id: &str) -> Self {
+        TransactionSummaryBuilder {
+            transaction_summary: TransactionSummary {
+                id: id.to_string(),
+                timestamp: 0,
+                tx_status: TransactionStatus::Success,
+                tx_info: TransactionInfo::Unknown,
+                execution_info: None,
+                safe_app_info: None,
+                description: None,
+            },
+        }
+    }
+
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.transaction_summary.timestamp = timestamp;
+        self
+    }
+
+    pub fn tx_status(mut self, tx_status: TransactionStatus) -> Self {
+        self.transaction_summary.tx_status = tx_status;
+        self
+    }
+
+    pub fn tx_info(mut self, tx_info: TransactionInfo) -> Self {
+        self.transaction_summary.tx_info = tx_info;
+        self
+    }
+
+    pub fn execution_info(mut self, execution_info: ExecutionInfo) -> Self {
+        self.transaction_summary.execution_info = Some(execution_info);
+        self
+    }
+
+    pub fn safe_app_info(mut self, safe_app_info: SafeAppInfo) -> Self {
+        self.transaction_summary.safe_app_info = Some(safe_app_info);
+        self
+    }
+
+    pub fn build(self) -> TransactionSummary {
+        self.transaction_summary
+    }
+}
@@ -0,0 +1,11 @@
+//! Fixtures for integration tests of the gateway, compiled for unit tests and with the `testing`
+//! feature, so that deployments extending the gateway can test their additions without copying
+//! the internal test modules.
+// Not every fixture is used by the tests of this crate
+#![allow(dead_code)]
+
+pub mod builders;
+pub mod setup;
+
+#[cfg(test)]
+mod tests;
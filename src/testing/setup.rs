@@ -0,0 +1,71 @@
+use crate::cache::{create_cache, Cache, MockCache};
+use crate::gateway::GatewayBuilder;
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::{HttpClient, MockHttpClient, Response};
+use dotenv::dotenv;
+use rocket::{Build, Rocket, Route};
+use std::sync::Arc;
+
+/// Rocket instance with the given routes, backed by the configured cache and `mock_http_client`
+pub fn setup_rocket(mock_http_client: MockHttpClient, routes: Vec<Route>) -> Rocket<Build> {
+    dotenv().ok();
+
+    routes_only(
+        GatewayBuilder::new(
+            create_cache(),
+            Arc::new(mock_http_client) as Arc<dyn HttpClient>,
+        ),
+        routes,
+    )
+    .build()
+}
+
+pub fn setup_rocket_with_mock_cache(
+    mock_http_client: MockHttpClient,
+    mock_cache: MockCache,
+    routes: Vec<Route>,
+) -> Rocket<Build> {
+    dotenv().ok();
+
+    routes_only(
+        GatewayBuilder::new(
+            Arc::new(mock_cache) as Arc<dyn Cache>,
+            Arc::new(mock_http_client) as Arc<dyn HttpClient>,
+        ),
+        routes,
+    )
+    .build()
+}
+
+// Without fairings, so that tests only exercise the routes under test
+fn routes_only(builder: GatewayBuilder, routes: Vec<Route>) -> GatewayBuilder {
+    builder
+        .route_groups(&[])
+        .core_routes(false)
+        .fairings(false)
+        .routes(routes)
+}
+
+/// Expects a single `GET` to `url` answered with `status_code` and `body`.
+/// Non 2xx responses are returned as errors, like the `reqwest` implementation does.
+pub fn expect_get(mock_http_client: &mut MockHttpClient, url: &str, status_code: u16, body: &str) {
+    let url = url.to_string();
+    let response = Response {
+        body: body.to_string(),
+        status_code,
+    };
+    mock_http_client
+        .expect_get()
+        .times(1)
+        .withf(move |request| request.url() == url)
+        .return_once(move |_| {
+            if response.is_success() {
+                Ok(response)
+            } else {
+                Err(ApiError::from_backend_error(
+                    response.status_code,
+                    &response.body,
+                ))
+            }
+        });
+}
@@ -0,0 +1,61 @@
+use crate::common::models::backend::chains::RpcAuthentication;
+use crate::routes::transactions::models::TransactionStatus;
+use crate::testing::builders::{ChainInfoBuilder, SafeInfoBuilder, TransactionSummaryBuilder};
+
+#[test]
+fn safe_info_builder() {
+    let safe_info = SafeInfoBuilder::new("0x1230B3d59858296A31053C1b8562Ecf89A2f888b")
+        .nonce(5)
+        .threshold(2)
+        .owners(&[
+            "0xBEA2F9227230976d2813a2f8b922c22bE1DE1B23",
+            "0x37e9F140A9Df5DCBc783C6c220660a4E15CBFe72",
+        ])
+        .version(None)
+        .build();
+
+    assert_eq!(
+        safe_info.address,
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b"
+    );
+    assert_eq!(safe_info.nonce, 5);
+    assert_eq!(safe_info.threshold, 2);
+    assert_eq!(safe_info.owners.len(), 2);
+    assert_eq!(safe_info.version, None);
+}
+
+#[test]
+fn chain_info_builder() {
+    let chain_info = ChainInfoBuilder::new("321")
+        .transaction_service("https://safe-transaction.kcc.network")
+        .rpc_uri(RpcAuthentication::ApiKeyPath, "https://rpc.kcc.network/")
+        .l2(true)
+        .build();
+
+    assert_eq!(chain_info.chain_id, "321");
+    assert_eq!(
+        chain_info.transaction_service,
+        "https://safe-transaction.kcc.network"
+    );
+    assert_eq!(
+        chain_info.rpc_uri.authentication,
+        RpcAuthentication::ApiKeyPath
+    );
+    assert!(chain_info.l2);
+}
+
+#[test]
+fn transaction_summary_builder() {
+    let transaction_summary = TransactionSummaryBuilder::new("multisig_0x1_0x2")
+        .timestamp(1604531696000)
+        .tx_status(TransactionStatus::AwaitingExecution)
+        .build();
+
+    assert_eq!(transaction_summary.id, "multisig_0x1_0x2");
+    assert_eq!(transaction_summary.timestamp, 1604531696000);
+    assert_eq!(
+        transaction_summary.tx_status,
+        TransactionStatus::AwaitingExecution
+    );
+    assert!(transaction_summary.execution_info.is_none());
+}
@@ -0,0 +1,126 @@
+use crate::common::converters::get_transfer_parties;
+use crate::common::models::addresses::AddressEx;
+use crate::providers::address_info::{address_ex_index, address_ex_or_default, AddressSource};
+use crate::providers::ext::InfoProviderExt;
+use crate::providers::info::MockInfoProvider;
+use mockall::predicate::eq;
+
+const TOKEN: &str = "0xD81F7D71ed570D121A1Ef9e3Bc0fc2bd6192De46";
+const CONTRACT: &str = "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D";
+const UNKNOWN: &str = "0xF353eBBa77e5E71c210599236686D51cA1F88b84";
+
+fn address_ex(address: &str, name: &str) -> AddressEx {
+    AddressEx {
+        value: address.to_string(),
+        name: Some(name.to_string()),
+        logo_uri: None,
+        risk_flags: vec![],
+    }
+}
+
+#[rocket::async_test]
+async fn address_info_index_resolves_every_address_once() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_address_ex_from_any_source()
+        .with(eq(TOKEN))
+        .times(1)
+        .return_once(|_| Ok(address_ex(TOKEN, "BigAmount")));
+    mock_info_provider
+        .expect_address_ex_from_any_source()
+        .with(eq(CONTRACT))
+        .times(1)
+        .return_once(|_| Ok(address_ex(CONTRACT, "MultiSendCallOnly")));
+    mock_info_provider
+        .expect_address_ex_from_any_source()
+        .with(eq(UNKNOWN))
+        .times(1)
+        .return_once(|_| Err(api_error!("No contract info")));
+
+    let addresses: Vec<String> = vec![TOKEN, CONTRACT, TOKEN, UNKNOWN, CONTRACT, UNKNOWN]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let actual = mock_info_provider.address_info_index(&addresses).await;
+
+    assert_eq!(actual.len(), 2);
+    assert_eq!(actual.get(TOKEN), Some(&address_ex(TOKEN, "BigAmount")));
+    assert_eq!(
+        actual.get(CONTRACT),
+        Some(&address_ex(CONTRACT, "MultiSendCallOnly"))
+    );
+}
+
+#[rocket::async_test]
+async fn address_info_index_skips_values_that_are_not_addresses() {
+    // Any lookup would fail the test, as no expectation is set
+    let mock_info_provider = MockInfoProvider::new();
+
+    let addresses: Vec<String> = vec![
+        "0x0000000000000000000000000000000000000000",
+        "0x1234",
+        "1000000000000000000",
+        "",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+    let actual = mock_info_provider.address_info_index(&addresses).await;
+
+    assert!(actual.is_empty());
+}
+
+#[rocket::async_test]
+async fn address_ex_index_from_contracts_resolves_every_address_once() {
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_address_ex_from_any_source()
+        .times(0);
+    mock_info_provider
+        .expect_address_ex_from_contracts()
+        .with(eq(CONTRACT))
+        .times(1)
+        .return_once(|_| Ok(address_ex(CONTRACT, "MultiSendCallOnly")));
+    mock_info_provider
+        .expect_address_ex_from_contracts()
+        .with(eq(UNKNOWN))
+        .times(1)
+        .return_once(|_| Err(api_error!("No contract info")));
+
+    let index = address_ex_index(
+        &mock_info_provider,
+        &[CONTRACT, UNKNOWN, CONTRACT],
+        AddressSource::Contracts,
+    )
+    .await;
+
+    assert_eq!(
+        address_ex_or_default(&index, CONTRACT),
+        address_ex(CONTRACT, "MultiSendCallOnly")
+    );
+    assert_eq!(
+        address_ex_or_default(&index, UNKNOWN),
+        AddressEx::address_only(UNKNOWN)
+    );
+}
+
+#[rocket::async_test]
+async fn transfer_parties_are_resolved_in_one_batch() {
+    let safe = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_address_ex_from_any_source()
+        .with(eq(TOKEN))
+        .times(1)
+        .return_once(|_| Ok(address_ex(TOKEN, "BigAmount")));
+
+    let actual = get_transfer_parties(safe, TOKEN, safe, &mock_info_provider).await;
+
+    assert_eq!(
+        actual,
+        (
+            address_ex(TOKEN, "BigAmount"),
+            AddressEx::address_only(safe)
+        )
+    );
+}
@@ -0,0 +1,48 @@
+use crate::common::models::addresses::{AddressEx, RiskFlag};
+use crate::providers::address_risk::{combine_flags, merge_denylist, Denylist};
+use serde_json::json;
+
+const SCAM_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+#[test]
+fn denylists_are_merged_by_lowercase_address() {
+    let scams: Denylist = serde_json::from_value(json!({ SCAM_ADDRESS: ["KNOWN_SCAM"] })).unwrap();
+    let sanctions: Denylist =
+        serde_json::from_value(json!({ SCAM_ADDRESS.to_lowercase(): ["SANCTIONED"] })).unwrap();
+    let mut denylist = Denylist::new();
+
+    merge_denylist(&mut denylist, scams);
+    merge_denylist(&mut denylist, sanctions);
+
+    assert_eq!(
+        denylist.get(&SCAM_ADDRESS.to_lowercase()),
+        Some(&vec![RiskFlag::KnownScam, RiskFlag::Sanctioned])
+    );
+    assert_eq!(denylist.get(SCAM_ADDRESS), None);
+}
+
+#[test]
+fn combine_flags_drops_duplicates_and_unknown_flags() {
+    let reported: Vec<RiskFlag> =
+        serde_json::from_value(json!(["SANCTIONED", "RUG_PULL", "KNOWN_SCAM"])).unwrap();
+
+    let actual = combine_flags(vec![RiskFlag::KnownScam].into_iter().chain(reported));
+
+    assert_eq!(actual, vec![RiskFlag::KnownScam, RiskFlag::Sanctioned]);
+}
+
+#[test]
+fn address_ex_serializes_risk_flags_only_if_flagged() {
+    let mut address_ex = AddressEx::address_only(SCAM_ADDRESS);
+    assert_eq!(
+        serde_json::to_value(&address_ex).unwrap(),
+        json!({ "value": SCAM_ADDRESS })
+    );
+
+    address_ex.risk_flags = vec![RiskFlag::Phishing];
+
+    assert_eq!(
+        serde_json::to_value(&address_ex).unwrap(),
+        json!({ "value": SCAM_ADDRESS, "riskFlags": ["PHISHING"] })
+    );
+}
@@ -1,6 +1,7 @@
 use crate::common::models::backend::chains::{
     BlockExplorerUriTemplate, ChainInfo, GasPrice, NativeCurrency, RpcAuthentication, RpcUri, Theme,
 };
+use crate::providers::failover;
 use crate::providers::info::*;
 use crate::utils::errors::ApiResult;
 
@@ -15,6 +16,8 @@ async fn core_uri_success_with_params_prod() {
         transaction_service: "https://safe-transaction.mainnet.gnosis.io".to_string(),
         vpc_transaction_service: "http://mainnet-safe-transaction-web.safe.svc.cluster.local"
             .to_string(),
+        transaction_service_fallback_uri: None,
+        vpc_transaction_service_fallback_uri: None,
         chain_id: "1".to_string(),
         chain_name: "".to_string(),
         short_name: "eth".to_string(),
@@ -70,6 +73,8 @@ async fn core_uri_success_without_params_prod() {
         transaction_service: "https://safe-transaction.mainnet.gnosis.io".to_string(),
         vpc_transaction_service: "http://mainnet-safe-transaction-web.safe.svc.cluster.local"
             .to_string(),
+        transaction_service_fallback_uri: None,
+        vpc_transaction_service_fallback_uri: None,
         chain_id: "1".to_string(),
         chain_name: "".to_string(),
         short_name: "eth".to_string(),
@@ -125,6 +130,8 @@ async fn core_uri_success_with_params_local() {
         transaction_service: "https://safe-transaction.mainnet.gnosis.io".to_string(),
         vpc_transaction_service: "http://mainnet-safe-transaction-web.safe.svc.cluster.local"
             .to_string(),
+        transaction_service_fallback_uri: None,
+        vpc_transaction_service_fallback_uri: None,
         chain_id: "1".to_string(),
         chain_name: "".to_string(),
         short_name: "eth".to_string(),
@@ -180,6 +187,8 @@ async fn core_uri_success_without_params_local() {
         transaction_service: "https://safe-transaction.mainnet.gnosis.io".to_string(),
         vpc_transaction_service: "http://mainnet-safe-transaction-web.safe.svc.cluster.local"
             .to_string(),
+        transaction_service_fallback_uri: None,
+        vpc_transaction_service_fallback_uri: None,
         chain_id: "1".to_string(),
         chain_name: "".to_string(),
         short_name: "eth".to_string(),
@@ -232,3 +241,63 @@ async fn core_uri_error() {
     let url = core_uri!(mock_info_provider, "/nice/path");
     url.unwrap();
 }
+
+#[rocket::async_test]
+async fn core_uri_uses_fallback_while_primary_is_unhealthy() {
+    // Same hosts for both uris, so the result doesn't depend on VPC_TRANSACTION_SERVICE_URI
+    let mut chain_info = crate::testing::builders::ChainInfoBuilder::new("1")
+        .transaction_service("http://primary-failover-test.default")
+        .vpc_transaction_service("http://primary-failover-test.default")
+        .build();
+    chain_info.transaction_service_fallback_uri =
+        Some("http://secondary-failover-test.default".to_string());
+    chain_info.vpc_transaction_service_fallback_uri =
+        Some("http://secondary-failover-test.default".to_string());
+    failover::apply_fallbacks(&mut chain_info);
+    let mut mock_info_provider = MockInfoProvider::new();
+    mock_info_provider
+        .expect_chain_info()
+        .times(3)
+        .returning(move || Ok(chain_info.clone()));
+
+    let healthy_url = core_uri!(mock_info_provider, "/v1/safes/").unwrap();
+    failover::report_failure(&healthy_url);
+    let unhealthy_url = core_uri!(mock_info_provider, "/v1/safes/").unwrap();
+    failover::report_success(&healthy_url);
+    let recovered_url = core_uri!(mock_info_provider, "/v1/safes/").unwrap();
+
+    assert_eq!(
+        healthy_url,
+        "http://primary-failover-test.default/api/v1/safes/"
+    );
+    assert_eq!(
+        unhealthy_url,
+        "http://secondary-failover-test.default/api/v1/safes/"
+    );
+    assert_eq!(
+        recovered_url,
+        "http://primary-failover-test.default/api/v1/safes/"
+    );
+}
+
+#[test]
+fn fallback_url_only_for_registered_primaries() {
+    failover::register_fallback(
+        "https://primary-fallback-url-test.example.com",
+        "https://secondary-fallback-url-test.example.com",
+    );
+
+    assert_eq!(
+        failover::fallback_url(
+            "https://primary-fallback-url-test.example.com/api/v1/safes/0x1/?limit=10"
+        ),
+        Some(
+            "https://secondary-fallback-url-test.example.com/api/v1/safes/0x1/?limit=10"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        failover::fallback_url("https://unknown-fallback-url-test.example.com/api/v1/safes/"),
+        None
+    );
+}
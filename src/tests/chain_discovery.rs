@@ -0,0 +1,136 @@
+use crate::cache::MockCache;
+use crate::providers::info::{generate_unknown_chain_key, DefaultInfoProvider, InfoProvider};
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::{MockHttpClient, Response};
+use mockall::predicate::eq;
+use mockall::Sequence;
+
+fn chain_request_key(chain_id: &str) -> String {
+    format!("c_reqs_{}", config_uri!("/v1/chains/{}/", chain_id))
+}
+
+#[rocket::async_test]
+async fn chain_info_looks_up_cached_not_found_chain_again() {
+    let chain_key = chain_request_key("4");
+    let mut sequence = Sequence::new();
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(Some(String::from("404;Not found")));
+    mock_cache
+        .expect_has_key()
+        .with(eq(generate_unknown_chain_key("4")))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(false);
+    mock_cache
+        .expect_create()
+        .withf(|key, _, _| key == generate_unknown_chain_key("4"))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(());
+    mock_cache
+        .expect_invalidate()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(());
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(None);
+    let created_key = chain_key.to_string();
+    mock_cache
+        .expect_create()
+        .withf(move |key, _, _| key == created_key)
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(());
+    let last_known_good_key = format!("c_lkg_{}", config_uri!("/v1/chains/{}/", "4"));
+    mock_cache
+        .expect_create()
+        .withf(move |key, _, _| key == last_known_good_key)
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(());
+    mock_cache
+        .expect_invalidate_pattern()
+        .with(eq(format!("c_reqs*{}*", config_uri!("/v1/chains/?limit="))))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_const(0usize);
+    mock_cache
+        .expect_append_to_stream()
+        .withf(|stream, entry, _| stream == "invalidations" && entry.contains("CHAIN_DISCOVERY"))
+        .times(1)
+        .return_const(());
+
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_get().times(1).return_once(|_| {
+        Ok(Response {
+            status_code: 200,
+            body: String::from(crate::tests::json::CHAIN_INFO_RINKEBY),
+        })
+    });
+
+    let context = RequestContext::mock(
+        String::from("/v1/chains/4"),
+        String::from("localhost"),
+        mock_http_client,
+        mock_cache,
+    );
+    let info_provider = DefaultInfoProvider::new("4", &context);
+
+    let actual = info_provider.chain_info().await.unwrap();
+
+    assert_eq!(actual.chain_id, "4");
+}
+
+#[rocket::async_test]
+async fn chain_info_not_found_chain_not_looked_up_again_within_duration() {
+    let chain_key = chain_request_key("5");
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .return_const(Some(String::from("404;Not found")));
+    mock_cache
+        .expect_has_key()
+        .with(eq(generate_unknown_chain_key("5")))
+        .times(1)
+        .return_const(true);
+    mock_cache.expect_create().times(0);
+    mock_cache.expect_invalidate().times(0);
+
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_get().times(0);
+
+    let context = RequestContext::mock(
+        String::from("/v1/chains/5"),
+        String::from("localhost"),
+        mock_http_client,
+        mock_cache,
+    );
+    let info_provider = DefaultInfoProvider::new("5", &context);
+
+    let actual = info_provider.chain_info().await;
+
+    assert_eq!(actual.unwrap_err().status, 404);
+}
@@ -0,0 +1,47 @@
+use crate::common::models::backend::chains::ChainInfo;
+use crate::providers::chain_seed::{find_chain, merge_chains};
+use crate::tests::json::{CHAIN_INFO_POLYGON, CHAIN_INFO_RINKEBY};
+
+fn chain(json: &str, chain_name: &str) -> ChainInfo {
+    let mut chain_info = serde_json::from_str::<ChainInfo>(json).unwrap();
+    chain_info.chain_name = chain_name.to_string();
+    chain_info
+}
+
+#[test]
+fn find_chain_prefers_file_seed() {
+    let file = vec![chain(CHAIN_INFO_RINKEBY, "Rinkeby file")];
+    let bundled = vec![
+        chain(CHAIN_INFO_RINKEBY, "Rinkeby"),
+        chain(CHAIN_INFO_POLYGON, "Polygon"),
+    ];
+
+    let rinkeby = find_chain(Some(file.as_slice()), &bundled, "4").unwrap();
+    let polygon = find_chain(Some(file.as_slice()), &bundled, "137").unwrap();
+
+    assert_eq!(rinkeby.chain_name, "Rinkeby file");
+    assert_eq!(polygon.chain_name, "Polygon");
+    assert_eq!(find_chain(None, &bundled, "1"), None);
+}
+
+#[test]
+fn merge_chains_replaces_bundled_chains_of_file_seed() {
+    let file = vec![chain(CHAIN_INFO_RINKEBY, "Rinkeby file")];
+    let bundled = vec![
+        chain(CHAIN_INFO_RINKEBY, "Rinkeby"),
+        chain(CHAIN_INFO_POLYGON, "Polygon"),
+    ];
+
+    let actual: Vec<String> = merge_chains(Some(file.as_slice()), &bundled)
+        .into_iter()
+        .map(|chain| chain.chain_name)
+        .collect();
+
+    assert_eq!(actual, vec!["Rinkeby file", "Polygon"]);
+    assert!(merge_chains(None, &[]).is_empty());
+}
+
+#[test]
+fn bundled_chain_seed_is_valid() {
+    serde_json::from_str::<Vec<ChainInfo>>(include_str!("../providers/chain_seed.json")).unwrap();
+}
@@ -0,0 +1,185 @@
+use crate::cache::MockCache;
+use crate::providers::gas::{estimate_gas_price, fee_tiers, FeeTier, GasPriceEstimation};
+use crate::providers::rpc::FeeHistory;
+use crate::testing::builders::ChainInfoBuilder;
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::{MockHttpClient, Response};
+use mockall::Sequence;
+
+fn rpc_response(body: &str) -> Response {
+    Response {
+        status_code: 200,
+        body: body.to_string(),
+    }
+}
+
+fn context(mock_http_client: MockHttpClient) -> RequestContext {
+    RequestContext::mock(
+        String::from("/v1/chains/1/gas-price"),
+        String::from("localhost"),
+        mock_http_client,
+        MockCache::new(),
+    )
+}
+
+fn tier(max_fee_per_gas: &str, max_priority_fee_per_gas: &str) -> FeeTier {
+    FeeTier {
+        max_fee_per_gas: max_fee_per_gas.to_string(),
+        max_priority_fee_per_gas: max_priority_fee_per_gas.to_string(),
+    }
+}
+
+#[test]
+fn fee_tiers_from_median_priority_fees() {
+    let fee_history = FeeHistory {
+        base_fee_per_gas: vec![90, 95, 100],
+        reward: vec![vec![1, 5, 20], vec![3, 4, 10], vec![2, 6, 30]],
+    };
+
+    let actual = fee_tiers(&fee_history).unwrap();
+
+    assert_eq!(
+        actual,
+        GasPriceEstimation::Eip1559 {
+            base_fee_per_gas: String::from("100"),
+            slow: tier("202", "2"),
+            standard: tier("205", "5"),
+            fast: tier("220", "20"),
+        }
+    );
+}
+
+#[test]
+fn fee_tiers_never_decrease() {
+    let fee_history = FeeHistory {
+        base_fee_per_gas: vec![100],
+        reward: vec![vec![10, 5, 0]],
+    };
+
+    let actual = fee_tiers(&fee_history).unwrap();
+
+    assert_eq!(
+        actual,
+        GasPriceEstimation::Eip1559 {
+            base_fee_per_gas: String::from("100"),
+            slow: tier("210", "10"),
+            standard: tier("210", "10"),
+            fast: tier("210", "10"),
+        }
+    );
+}
+
+#[test]
+fn fee_tiers_without_base_fee() {
+    let fee_history = FeeHistory {
+        base_fee_per_gas: vec![],
+        reward: vec![],
+    };
+
+    assert!(fee_tiers(&fee_history).is_err());
+}
+
+#[rocket::async_test]
+async fn estimate_gas_price_eip1559_chain() {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_post().times(1).return_once(|_| {
+        Ok(rpc_response(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"oldestBlock":"0x1","baseFeePerGas":["0x5a","0x64"],"gasUsedRatio":[0.5],"reward":[["0x1","0x2","0x3"]]}}"#,
+        ))
+    });
+    let chain_info = ChainInfoBuilder::new("1").features(&["EIP1559"]).build();
+
+    let actual = estimate_gas_price(&context(mock_http_client), &chain_info)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        actual,
+        GasPriceEstimation::Eip1559 {
+            base_fee_per_gas: String::from("100"),
+            slow: tier("201", "1"),
+            standard: tier("202", "2"),
+            fast: tier("203", "3"),
+        }
+    );
+}
+
+#[rocket::async_test]
+async fn estimate_gas_price_legacy_chain() {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_post().times(1).return_once(|_| {
+        Ok(rpc_response(
+            r#"{"jsonrpc":"2.0","id":1,"result":"0x12a05f200"}"#,
+        ))
+    });
+    let chain_info = ChainInfoBuilder::new("1").build();
+
+    let actual = estimate_gas_price(&context(mock_http_client), &chain_info)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        actual,
+        GasPriceEstimation::Legacy {
+            gas_price: String::from("5000000000"),
+        }
+    );
+}
+
+#[rocket::async_test]
+async fn estimate_gas_price_falls_back_without_fee_history() {
+    let mut sequence = Sequence::new();
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_post()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_once(|_| {
+            Ok(rpc_response(
+                r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"the method eth_feeHistory does not exist"}}"#,
+            ))
+        });
+    mock_http_client
+        .expect_post()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .return_once(|_| {
+            Ok(rpc_response(
+                r#"{"jsonrpc":"2.0","id":1,"result":"0x3b9aca00"}"#,
+            ))
+        });
+    let chain_info = ChainInfoBuilder::new("1").features(&["EIP1559"]).build();
+
+    let actual = estimate_gas_price(&context(mock_http_client), &chain_info)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        actual,
+        GasPriceEstimation::Legacy {
+            gas_price: String::from("1000000000"),
+        }
+    );
+}
+
+#[test]
+fn gas_price_estimation_json() {
+    let estimation = GasPriceEstimation::Eip1559 {
+        base_fee_per_gas: String::from("100"),
+        slow: tier("201", "1"),
+        standard: tier("202", "2"),
+        fast: tier("203", "3"),
+    };
+
+    let actual = serde_json::to_value(&estimation).unwrap();
+
+    assert_eq!(actual["type"], "EIP1559");
+    assert_eq!(actual["standard"]["maxFeePerGas"], "202");
+    assert_eq!(
+        serde_json::to_value(&GasPriceEstimation::Legacy {
+            gas_price: String::from("1"),
+        })
+        .unwrap(),
+        serde_json::json!({ "type": "LEGACY", "gasPrice": "1" })
+    );
+}
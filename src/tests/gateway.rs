@@ -0,0 +1,78 @@
+use crate::cache::MockCache;
+use crate::gateway::GatewayBuilder;
+use crate::routes::RouteGroup;
+use crate::utils::http_client::MockHttpClient;
+use std::sync::Arc;
+
+fn builder() -> GatewayBuilder {
+    GatewayBuilder::new(Arc::new(MockCache::new()), Arc::new(MockHttpClient::new()))
+}
+
+fn route_names(builder: GatewayBuilder) -> Vec<String> {
+    builder
+        .build()
+        .routes()
+        .filter_map(|route| route.name.as_ref().map(|name| name.to_string()))
+        .collect()
+}
+
+#[test]
+fn gateway_serves_every_route_group_by_default() {
+    let actual = route_names(builder());
+
+    for route_group in RouteGroup::ALL.iter() {
+        for route in route_group.routes() {
+            assert!(actual.contains(&route.name.unwrap().to_string()));
+        }
+    }
+    assert!(actual.contains(&String::from("health")));
+}
+
+#[test]
+fn gateway_without_disabled_route_groups() {
+    let actual = route_names(
+        builder()
+            .disable(RouteGroup::Hooks)
+            .disable(RouteGroup::Admin),
+    );
+
+    assert!(actual.contains(&String::from("get_transactions_history")));
+    assert!(actual.contains(&String::from("get_safe_info")));
+    assert!(!actual.contains(&String::from("update")));
+    assert!(!actual.contains(&String::from("flush")));
+    assert!(!actual.contains(&String::from("get_chains_export")));
+    assert!(!actual.contains(&String::from("get_audit_entries")));
+}
+
+#[test]
+fn gateway_with_only_selected_route_groups() {
+    let mut actual = route_names(
+        builder()
+            .route_groups(&[RouteGroup::Balances])
+            .core_routes(false)
+            .enable(RouteGroup::Collectibles),
+    );
+
+    let mut expected: Vec<String> = RouteGroup::Balances
+        .routes()
+        .into_iter()
+        .chain(RouteGroup::Collectibles.routes())
+        .map(|route| route.name.unwrap().to_string())
+        .collect();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn route_group_names() {
+    assert_eq!(RouteGroup::from_name("hooks"), Some(RouteGroup::Hooks));
+    assert_eq!(RouteGroup::from_name("Admin"), Some(RouteGroup::Admin));
+    assert_eq!(RouteGroup::from_name("safes"), None);
+    for route_group in RouteGroup::ALL.iter() {
+        assert_eq!(
+            RouteGroup::from_name(route_group.name()),
+            Some(*route_group)
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::cache::MockCache;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::MockHttpClient;
+use mockall::predicate::eq;
+
+fn chain_request_key(chain_id: &str) -> String {
+    format!("c_reqs_{}", config_uri!("/v1/chains/{}/", chain_id))
+}
+
+fn cached_chain_info(mock_cache: &mut MockCache, chain_id: &str) {
+    let chain_key = chain_request_key(chain_id);
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key))
+        .times(1)
+        .return_const(Some(String::from(crate::tests::json::CHAIN_INFO_RINKEBY)));
+}
+
+#[rocket::async_test]
+async fn info_providers_of_one_request_share_lookups() {
+    let mut mock_cache = MockCache::new();
+    cached_chain_info(&mut mock_cache, "4");
+    let context = RequestContext::mock(
+        String::from("/v1/chains/4/transactions/details"),
+        String::from("host"),
+        MockHttpClient::new(),
+        mock_cache,
+    );
+
+    let first = DefaultInfoProvider::new("4", &context).chain_info().await;
+    let second = DefaultInfoProvider::new("4", &context).chain_info().await;
+
+    assert_eq!(first.unwrap().chain_id, "4");
+    assert_eq!(second.unwrap().chain_id, "4");
+}
+
+#[rocket::async_test]
+async fn info_memo_is_scoped_by_chain() {
+    let mut mock_cache = MockCache::new();
+    cached_chain_info(&mut mock_cache, "4");
+    cached_chain_info(&mut mock_cache, "137");
+    let context = RequestContext::mock(
+        String::from("/v1/notifications/register"),
+        String::from("host"),
+        MockHttpClient::new(),
+        mock_cache,
+    );
+
+    DefaultInfoProvider::new("4", &context)
+        .chain_info()
+        .await
+        .unwrap();
+    DefaultInfoProvider::new("137", &context)
+        .chain_info()
+        .await
+        .unwrap();
+}
+
+#[rocket::async_test]
+async fn info_memo_is_not_shared_across_requests() {
+    let mut mock_cache = MockCache::new();
+    let chain_key = chain_request_key("4");
+    mock_cache
+        .expect_has_key()
+        .with(eq(chain_key.to_string()))
+        .times(2)
+        .return_const(true);
+    mock_cache
+        .expect_fetch()
+        .with(eq(chain_key))
+        .times(2)
+        .return_const(Some(String::from(crate::tests::json::CHAIN_INFO_RINKEBY)));
+    let mock_cache = std::sync::Arc::new(mock_cache);
+    let context = |request_id: &str| {
+        RequestContext::new(
+            String::from(request_id),
+            String::from("host"),
+            std::sync::Arc::new(MockHttpClient::new()),
+            mock_cache.clone(),
+        )
+    };
+
+    let first_context = context("/v1/chains/4/safes/0x1");
+    let second_context = context("/v1/chains/4/safes/0x2");
+    DefaultInfoProvider::new("4", &first_context)
+        .chain_info()
+        .await
+        .unwrap();
+    DefaultInfoProvider::new("4", &second_context)
+        .chain_info()
+        .await
+        .unwrap();
+}
@@ -1,4 +1,22 @@
 #[cfg(test)]
+mod address_info;
+#[cfg(test)]
+mod address_risk;
+#[cfg(test)]
 mod backend_url;
 #[cfg(test)]
+mod chain_discovery;
+#[cfg(test)]
+mod chain_seed;
+#[cfg(test)]
+mod gas_price;
+#[cfg(test)]
+mod gateway;
+#[cfg(test)]
+mod info_memo;
+#[cfg(test)]
 pub mod json;
+#[cfg(test)]
+mod static_token_list;
+#[cfg(test)]
+mod token_overrides;
@@ -0,0 +1,159 @@
+use crate::cache::MockCache;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider, TokenInfo, TokenType};
+use crate::providers::token_list::{find_token, StaticTokenList};
+use crate::utils::context::RequestContext;
+use crate::utils::http_client::MockHttpClient;
+use mockall::predicate::eq;
+
+const USDC_ADDRESS: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+fn token(address: &str, symbol: &str) -> TokenInfo {
+    TokenInfo {
+        token_type: TokenType::Erc20,
+        address: address.to_string(),
+        decimals: 6,
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        logo_uri: None,
+    }
+}
+
+fn token_list(chain_id: &str, tokens: Vec<TokenInfo>) -> StaticTokenList {
+    vec![(chain_id.to_string(), tokens)].into_iter().collect()
+}
+
+#[test]
+fn find_token_prefers_configured_list() {
+    let configured = token_list("1", vec![token(USDC_ADDRESS, "USDC.configured")]);
+    let bundled = token_list("1", vec![token(USDC_ADDRESS, "USDC")]);
+
+    let actual = find_token(Some(&configured), &bundled, "1", USDC_ADDRESS).unwrap();
+
+    assert_eq!(actual.symbol, "USDC.configured");
+}
+
+#[test]
+fn find_token_falls_back_to_bundled_list() {
+    let configured = token_list("1", vec![token("0x1", "ONE")]);
+    let bundled = token_list("1", vec![token(USDC_ADDRESS, "USDC")]);
+
+    let actual = find_token(
+        Some(&configured),
+        &bundled,
+        "1",
+        &USDC_ADDRESS.to_lowercase(),
+    )
+    .unwrap();
+
+    assert_eq!(actual.symbol, "USDC");
+    assert_eq!(find_token(None, &bundled, "137", USDC_ADDRESS), None);
+}
+
+#[rocket::async_test]
+async fn token_info_from_bundled_list_while_token_service_errored() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq("dip_ti_1"))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("dip_ti_1"), eq(USDC_ADDRESS))
+        .times(1)
+        .return_const(None);
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("dip_ti_1"), eq("state"))
+        .times(1)
+        .return_const(Some(String::from("errored")));
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("token_overrides"), eq("1"))
+        .times(1)
+        .return_const(None);
+    let context = RequestContext::mock(
+        String::from("/v1/chains/1/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/balances/usd"),
+        String::from("localhost"),
+        MockHttpClient::new(),
+        mock_cache,
+    );
+    let info_provider = DefaultInfoProvider::new("1", &context);
+
+    let actual = info_provider.token_info(USDC_ADDRESS).await.unwrap();
+
+    assert_eq!(actual.symbol, "USDC");
+    assert_eq!(actual.decimals, 6);
+    assert!(context.data_freshness().is_stale());
+}
+
+#[rocket::async_test]
+async fn token_info_unknown_while_token_service_available() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq("dip_ti_1"))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("dip_ti_1"), eq(USDC_ADDRESS))
+        .times(1)
+        .return_const(None);
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("dip_ti_1"), eq("state"))
+        .times(1)
+        .return_const(Some(String::from("populated")));
+    let context = RequestContext::mock(
+        String::from("/v1/chains/1/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/balances/usd"),
+        String::from("localhost"),
+        MockHttpClient::new(),
+        mock_cache,
+    );
+    let info_provider = DefaultInfoProvider::new("1", &context);
+
+    assert!(info_provider.token_info(USDC_ADDRESS).await.is_err());
+    assert!(!context.data_freshness().is_stale());
+}
+
+#[rocket::async_test]
+async fn token_info_with_operator_override() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq("dip_ti_1"))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("dip_ti_1"), eq(USDC_ADDRESS))
+        .times(1)
+        .return_const(Some(
+            serde_json::to_string(&token(USDC_ADDRESS, "USDC")).unwrap(),
+        ));
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("token_overrides"), eq("1"))
+        .times(1)
+        .return_const(Some(String::from(
+            r#"{"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48":{"symbol":"USDC.e","logoUri":"https://logos.example/usdc.png"}}"#,
+        )));
+    let context = RequestContext::mock(
+        String::from("/v1/chains/1/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/balances/usd"),
+        String::from("localhost"),
+        MockHttpClient::new(),
+        mock_cache,
+    );
+    let info_provider = DefaultInfoProvider::new("1", &context);
+
+    let actual = info_provider.token_info(USDC_ADDRESS).await.unwrap();
+
+    assert_eq!(actual.symbol, "USDC.e");
+    assert_eq!(actual.name, "USDC");
+    assert_eq!(actual.decimals, 6);
+    assert_eq!(
+        actual.logo_uri.as_deref(),
+        Some("https://logos.example/usdc.png")
+    );
+}
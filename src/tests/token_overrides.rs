@@ -0,0 +1,114 @@
+use crate::cache::MockCache;
+use crate::providers::info::{TokenInfo, TokenType};
+use crate::providers::token_overrides::{set_override, TokenOverride, TokenOverrides};
+use crate::routes::balances::converters::with_token_override;
+use crate::routes::balances::models::Balance;
+use mockall::predicate::eq;
+
+const TOKEN_ADDRESS: &str = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+
+fn balance(symbol: &str, spam: bool) -> Balance {
+    Balance {
+        token_info: TokenInfo {
+            token_type: TokenType::Erc20,
+            address: TOKEN_ADDRESS.to_string(),
+            decimals: 18,
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            logo_uri: None,
+        },
+        balance: String::from("1000"),
+        fiat_balance: String::from("0"),
+        fiat_conversion: String::from("0"),
+        spam,
+    }
+}
+
+fn overrides(token_override: TokenOverride) -> TokenOverrides {
+    vec![(TOKEN_ADDRESS.to_lowercase(), token_override)]
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn balance_with_token_override() {
+    let token_overrides = overrides(TokenOverride {
+        symbol: Some(String::from("GNO")),
+        decimals: Some(6),
+        ..TokenOverride::default()
+    });
+
+    let actual = with_token_override(balance("visit gno.io", true), &token_overrides);
+
+    assert_eq!(actual.token_info.symbol, "GNO");
+    assert_eq!(actual.token_info.name, "visit gno.io");
+    assert_eq!(actual.token_info.decimals, 6);
+    // Still spam because of the name
+    assert!(actual.spam);
+}
+
+#[test]
+fn balance_with_spam_override() {
+    let token_overrides = overrides(TokenOverride {
+        spam: Some(true),
+        ..TokenOverride::default()
+    });
+
+    assert!(with_token_override(balance("GNO", false), &token_overrides).spam);
+    assert_eq!(
+        with_token_override(balance("GNO", false), &TokenOverrides::new()),
+        balance("GNO", false)
+    );
+}
+
+#[rocket::async_test]
+async fn set_override_stores_lowercase_address() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("token_overrides"), eq("1"))
+        .times(1)
+        .return_const(None);
+    mock_cache
+        .expect_insert_in_hash()
+        .with(
+            eq("token_overrides"),
+            eq("1"),
+            eq(r#"{"0x1230b3d59858296a31053c1b8562ecf89a2f888b":{"logoUri":"https://example.com/logo.png"}}"#),
+        )
+        .times(1)
+        .return_const(());
+    let token_override = TokenOverride {
+        logo_uri: Some(String::from("https://example.com/logo.png")),
+        ..TokenOverride::default()
+    };
+
+    let actual = set_override(&mock_cache, "1", TOKEN_ADDRESS, &token_override)
+        .await
+        .unwrap();
+
+    assert_eq!(actual, overrides(token_override));
+}
+
+#[rocket::async_test]
+async fn set_empty_override_removes_it() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("token_overrides"), eq("1"))
+        .times(1)
+        .return_const(Some(String::from(
+            r#"{"0x1230b3d59858296a31053c1b8562ecf89a2f888b":{"symbol":"GNO"}}"#,
+        )));
+    mock_cache
+        .expect_insert_in_hash()
+        .with(eq("token_overrides"), eq("1"), eq("{}"))
+        .times(1)
+        .return_const(());
+
+    let actual = set_override(&mock_cache, "1", TOKEN_ADDRESS, &TokenOverride::default())
+        .await
+        .unwrap();
+
+    assert!(actual.is_empty());
+}
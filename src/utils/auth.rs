@@ -0,0 +1,134 @@
+//! Named API keys of the admin, hook and diagnostics routes, each limited to the [Scope]s it is
+//! configured with via `ADMIN_API_KEYS` (or `ADMIN_API_KEYS_FILE`), so that e.g. the transaction
+//! service can deliver hooks without being able to flush caches or read the configuration.
+//! `WEBHOOK_TOKEN` is still accepted with every scope.
+use crate::config::{admin_api_keys, webhook_token};
+use crate::utils::errors::ApiResult;
+use lazy_static::lazy_static;
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, Request};
+use subtle::ConstantTimeEq;
+
+// Name of the key of `WEBHOOK_TOKEN`
+const WEBHOOK_TOKEN_KEY: &str = "webhook";
+/// Header carrying the token of the routes authorized with request guards, so that it doesn't end
+/// up in access logs like path segments do
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+lazy_static! {
+    static ref API_KEYS: Vec<ApiKey> = configured_keys();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    /// Cache invalidation hooks of the transaction and config services, and ready callbacks
+    Hooks,
+    /// Writes to the cache: flushes, imports, migrations, token overrides, read-only mode
+    CacheAdmin,
+    /// Metrics, stats and the configuration of the instance
+    Diagnostics,
+    /// Snapshots of the cache for other instances
+    Exports,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 4] = [
+        Scope::Hooks,
+        Scope::CacheAdmin,
+        Scope::Diagnostics,
+        Scope::Exports,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Scope::ALL
+            .iter()
+            .find(|scope| scope.name() == name)
+            .copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scope::Hooks => "hooks",
+            Scope::CacheAdmin => "cache-admin",
+            Scope::Diagnostics => "diagnostics",
+            Scope::Exports => "exports",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub name: String,
+    pub token: String,
+    pub scopes: Vec<Scope>,
+}
+
+/// `Ok` if `token` belongs to a key with `scope`
+pub fn authorize(token: &str, scope: Scope) -> ApiResult<()> {
+    check_key(&API_KEYS, token, scope).map(|_| ())
+}
+
+/// The key of `token`, answered like an unknown token if it lacks `scope`. Tokens are compared in
+/// constant time and against every key, so that response times don't tell how close a guess is.
+pub fn check_key<'k>(keys: &'k [ApiKey], token: &str, scope: Scope) -> ApiResult<&'k ApiKey> {
+    let matching_key = keys.iter().fold(None, |matching_key, key| {
+        if bool::from(key.token.as_bytes().ct_eq(token.as_bytes())) {
+            Some(key)
+        } else {
+            matching_key
+        }
+    });
+    match matching_key {
+        Some(key) if key.scopes.contains(&scope) => Ok(key),
+        Some(key) => {
+            log::warn!("API key {} lacks the {} scope", key.name, scope.name());
+            bail!("Invalid token")
+        }
+        None => bail!("Invalid token"),
+    }
+}
+
+/// Request guard of the routes needing a key with the [Scope::Diagnostics] scope, sent in the
+/// [API_KEY_HEADER]. Holds the name of the key.
+pub struct DiagnosticsKey(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for DiagnosticsKey {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match request
+            .headers()
+            .get_one(API_KEY_HEADER)
+            .map(|token| check_key(&API_KEYS, token, Scope::Diagnostics))
+        {
+            Some(Ok(key)) => request::Outcome::Success(DiagnosticsKey(key.name.clone())),
+            _ => request::Outcome::Failure((Status::Unauthorized, ())),
+        }
+    }
+}
+
+/// Unknown scopes are reported when the settings are validated and ignored here
+pub fn configured_keys() -> Vec<ApiKey> {
+    let mut keys: Vec<ApiKey> = admin_api_keys()
+        .into_iter()
+        .filter(|(_, key)| !key.token.is_empty())
+        .map(|(name, key)| ApiKey {
+            name,
+            token: key.token,
+            scopes: key
+                .scopes
+                .iter()
+                .filter_map(|scope| Scope::from_name(scope))
+                .collect(),
+        })
+        .collect();
+    if let Some(token) = webhook_token().filter(|token| !token.is_empty()) {
+        keys.push(ApiKey {
+            name: String::from(WEBHOOK_TOKEN_KEY),
+            token,
+            scopes: Scope::ALL.to_vec(),
+        });
+    }
+    keys
+}
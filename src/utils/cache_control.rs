@@ -0,0 +1,181 @@
+use crate::config::cache_debug_headers;
+use crate::utils::device::DEVICE_UUID_HEADER;
+use chrono::{Duration, Utc};
+use ethcontract_common::hash::keccak256;
+use lazy_static::lazy_static;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Header, Method, Status};
+use rocket::{Request, Response};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+lazy_static! {
+    static ref CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+    static ref CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+    static ref CACHE_STALE: AtomicU64 = AtomicU64::new(0);
+}
+
+/// Remaining time (in ms) the response of the current request stays cached, set while the
+/// response is served from (or stored in) the cache and read by the [CacheControl] fairing
+#[derive(Clone, Default, Debug)]
+pub struct ResponseTtl(Arc<Mutex<Option<usize>>>);
+
+impl ResponseTtl {
+    /// Keeps the shortest ttl when a response is built from several cached entries
+    pub fn set(&self, ttl: usize) {
+        let mut current = self.0.lock().unwrap();
+        *current = Some(current.map_or(ttl, |current| current.min(ttl)));
+    }
+
+    pub fn get(&self) -> Option<usize> {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// Set when (part of) the response of the current request is a last known good copy served
+/// because the upstream failed, read by the [CacheControl] fairing
+#[derive(Clone, Default, Debug)]
+pub struct DataFreshness(Arc<AtomicBool>);
+
+impl DataFreshness {
+    pub fn mark_stale(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CacheLookup {
+    Hit,
+    Miss,
+}
+
+/// Outcome of the response cache lookups of the current request, set by `CacheResponse` and read
+/// by the [CacheControl] fairing
+#[derive(Clone, Default, Debug)]
+pub struct CacheStatus(Arc<Mutex<Option<(CacheLookup, String)>>>);
+
+impl CacheStatus {
+    /// A miss on any of the lookups makes the whole response a miss
+    pub fn record(&self, lookup: CacheLookup, cache_key: &str) {
+        let mut current = self.0.lock().unwrap();
+        let replace = match *current {
+            None => true,
+            Some((current_lookup, _)) => {
+                current_lookup == CacheLookup::Hit && lookup == CacheLookup::Miss
+            }
+        };
+        if replace {
+            *current = Some((lookup, cache_key.to_string()));
+        }
+    }
+
+    pub fn get(&self) -> Option<CacheLookup> {
+        self.0.lock().unwrap().as_ref().map(|(lookup, _)| *lookup)
+    }
+
+    /// Hash of the key the status was recorded for
+    pub fn key_hash(&self) -> Option<String> {
+        self.0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(_, cache_key)| to_hex_string!(keccak256(cache_key.as_bytes())))
+    }
+}
+
+/// `X-Cache` of the responses served by this instance since it started
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheHitStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub stale: u64,
+    /// Share of hits, 0 without responses
+    pub hit_rate: f32,
+}
+
+pub fn hit_stats() -> CacheHitStats {
+    let hits = CACHE_HITS.load(Ordering::Relaxed);
+    let misses = CACHE_MISSES.load(Ordering::Relaxed);
+    let stale = CACHE_STALE.load(Ordering::Relaxed);
+    let total = hits + misses + stale;
+    CacheHitStats {
+        hits,
+        misses,
+        stale,
+        hit_rate: if total == 0 {
+            0.0
+        } else {
+            hits as f32 / total as f32
+        },
+    }
+}
+
+/// Adds `Cache-Control` and `Expires` headers to successful reads served through the cache,
+/// so that CDNs and HTTP caches in front of the gateway don't outlive the cached entry, and an
+/// `X-Cache: HIT|MISS|STALE` header telling where the response came from
+pub struct CacheControl();
+
+#[rocket::async_trait]
+impl Fairing for CacheControl {
+    fn info(&self) -> Info {
+        Info {
+            name: "Add cache headers to cached responses",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        // HEAD requests are answered by the GET routes, so they get the same headers
+        let is_read = request.method() == Method::Get || request.method() == Method::Head;
+        if !is_read {
+            return;
+        }
+        let is_stale = request.local_cache(DataFreshness::default).is_stale();
+        let cache_status = request.local_cache(CacheStatus::default);
+        let x_cache = match cache_status.get() {
+            _ if is_stale => Some(("STALE", &*CACHE_STALE)),
+            Some(CacheLookup::Hit) => Some(("HIT", &*CACHE_HITS)),
+            Some(CacheLookup::Miss) => Some(("MISS", &*CACHE_MISSES)),
+            None => None,
+        };
+        if let Some((x_cache, counter)) = x_cache {
+            counter.fetch_add(1, Ordering::Relaxed);
+            response.set_header(Header::new("X-Cache", x_cache));
+        }
+        if let Some(key_hash) = cache_status.key_hash().filter(|_| cache_debug_headers()) {
+            response.set_header(Header::new("X-Cache-Key", key_hash));
+        }
+        if response.status() != Status::Ok {
+            return;
+        }
+        // Stale copies are never stored in the response cache, so they get no cache headers
+        if is_stale {
+            response.set_header(Header::new("data_freshness", "stale"));
+            return;
+        }
+        if let Some(ttl) = request.local_cache(ResponseTtl::default).get() {
+            let max_age = ttl / 1000;
+            let expires = Utc::now() + Duration::seconds(max_age as i64);
+            // Responses of devices can contain their notes, shared caches must not store them
+            let scope = if request.headers().contains(DEVICE_UUID_HEADER) {
+                "private"
+            } else {
+                "public"
+            };
+            response.set_header(Header::new(
+                "Cache-Control",
+                format!("{}, max-age={}", scope, max_age),
+            ));
+            response.set_header(Header::new(
+                "Expires",
+                expires.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ));
+        }
+    }
+}
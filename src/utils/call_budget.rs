@@ -0,0 +1,156 @@
+//! Limits the upstream calls made while serving one incoming request, so that pathological
+//! payloads (e.g. a history page triggering hundreds of contract lookups) are aborted instead of
+//! flooding the transaction service.
+use crate::utils::errors::{ApiError, ApiResult, ErrorDetails};
+use crate::utils::http_client::{BinaryResponse, HttpClient, Request, Response};
+use crate::utils::trace_id;
+use lazy_static::lazy_static;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Status};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+// Distinguishes the budget error from 503s of upstream services, which trigger failovers
+const CALL_BUDGET_EXCEEDED_CODE: u64 = 1503;
+
+lazy_static! {
+    static ref CALL_BUDGET_EXCEEDED_COUNTS: Mutex<BTreeMap<String, u64>> =
+        Mutex::new(BTreeMap::new());
+}
+
+pub struct CallBudget {
+    /// Method and path template of the incoming request
+    route: String,
+    limit: usize,
+    calls: AtomicUsize,
+}
+
+impl CallBudget {
+    pub fn new(route: String, limit: usize) -> Self {
+        CallBudget {
+            route,
+            limit,
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.calls.load(Ordering::Relaxed) > self.limit
+    }
+
+    /// Fails with a 503 once more than `limit` calls were made. Every request exceeding its
+    /// budget is counted once for its route, see [exceeded_counts].
+    pub fn spend(&self) -> ApiResult<()> {
+        let calls = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+        if calls <= self.limit {
+            return Ok(());
+        }
+        if calls == self.limit + 1 {
+            log::warn!(
+                "Upstream call budget of {} calls exceeded by {}",
+                self.limit,
+                self.route
+            );
+            *CALL_BUDGET_EXCEEDED_COUNTS
+                .lock()
+                .unwrap()
+                .entry(self.route.to_string())
+                .or_insert(0) += 1;
+        }
+        Err(self.exceeded_error())
+    }
+
+    fn exceeded_error(&self) -> ApiError {
+        ApiError {
+            status: 503,
+            details: ErrorDetails {
+                code: CALL_BUDGET_EXCEEDED_CODE,
+                message: Some(format!(
+                    "Upstream call budget of {} calls exceeded",
+                    self.limit
+                )),
+                arguments: None,
+                debug: None,
+            },
+        }
+    }
+}
+
+pub fn is_exceeded_error(error: &ApiError) -> bool {
+    error.status == 503 && error.details.code == CALL_BUDGET_EXCEEDED_CODE
+}
+
+/// Requests of this instance that exceeded their upstream call budget, per route
+pub fn exceeded_counts() -> BTreeMap<String, u64> {
+    CALL_BUDGET_EXCEEDED_COUNTS.lock().unwrap().clone()
+}
+
+/// Spends the budget of the incoming request on every call of the wrapped client
+pub struct BudgetedHttpClient {
+    http_client: Arc<dyn HttpClient>,
+    budget: Arc<CallBudget>,
+}
+
+impl BudgetedHttpClient {
+    pub fn new(http_client: Arc<dyn HttpClient>, budget: Arc<CallBudget>) -> Self {
+        BudgetedHttpClient {
+            http_client,
+            budget,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl HttpClient for BudgetedHttpClient {
+    async fn get(&self, request: Request) -> ApiResult<Response> {
+        self.budget.spend()?;
+        self.http_client.get(request).await
+    }
+
+    async fn post(&self, request: Request) -> ApiResult<Response> {
+        self.budget.spend()?;
+        self.http_client.post(request).await
+    }
+
+    async fn delete(&self, request: Request) -> ApiResult<Response> {
+        self.budget.spend()?;
+        self.http_client.delete(request).await
+    }
+
+    async fn get_binary(&self, request: Request, max_size: usize) -> ApiResult<BinaryResponse> {
+        self.budget.spend()?;
+        self.http_client.get_binary(request, max_size).await
+    }
+}
+
+/// Answers requests that exceeded their budget with the 503, also when the handler recovered
+/// from the failed calls (e.g. by omitting token information), as the response is incomplete
+pub struct CallBudgetGuard();
+
+#[rocket::async_trait]
+impl Fairing for CallBudgetGuard {
+    fn info(&self) -> Info {
+        Info {
+            name: "CallBudgetGuard",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(
+        &self,
+        request: &'r rocket::Request<'_>,
+        response: &mut rocket::Response<'r>,
+    ) {
+        let call_budget = request.local_cache(|| None::<Arc<CallBudget>>);
+        let error = match call_budget {
+            Some(call_budget) if call_budget.is_exceeded() => call_budget.exceeded_error(),
+            _ => return,
+        };
+        let body = trace_id::error_body(request, &error.details).unwrap_or_default();
+        response.set_status(Status::ServiceUnavailable);
+        response.set_header(ContentType::JSON);
+        response.set_sized_body(body.len(), Cursor::new(body));
+    }
+}
@@ -0,0 +1,259 @@
+//! Capabilities declared by clients in the `X-Client-Capabilities` header, e.g.
+//! `max-page-size=10; tx-info=Transfer,SettingsChange,Custom,Creation`, so that new server
+//! features can roll out without breaking older app versions:
+//!
+//! - `max-page-size` caps the `limit` of the paginated routes, the cursor of the request is
+//!   rewritten before it is routed
+//! - `tx-info` lists the `txInfo` types the client can render, other types are served as `Custom`,
+//!   filled from the `txData` of the transaction where the response has it (transaction details)
+//!
+//! Both are applied around the handlers (like the serialization profiles), so cached responses
+//! stay the same for every client.
+use crate::common::models::page::PageMetadata;
+use crate::routes::transactions::converters::data_size;
+use crate::utils::chain_hosts::matches_template;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::uri::Origin;
+use rocket::http::{ContentType, Header};
+use rocket::{Data, Request, Response};
+use serde_json::{json, Map, Value};
+use std::io::Cursor;
+
+pub const CLIENT_CAPABILITIES_HEADER: &str = "X-Client-Capabilities";
+const CUSTOM_TX_INFO: &str = "Custom";
+// Page size of the paginated routes without a cursor, see [PageMetadata::from_cursor]
+const DEFAULT_PAGE_SIZE: u64 = 20;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ClientCapabilities {
+    pub max_page_size: Option<u64>,
+    /// `None` if all types are supported
+    pub tx_info_types: Option<Vec<String>>,
+}
+
+impl ClientCapabilities {
+    /// Unknown or malformed capabilities are ignored
+    pub fn from_header(header: &str) -> Self {
+        let mut capabilities = ClientCapabilities::default();
+        for capability in header.split(';') {
+            let mut parts = capability.splitn(2, '=');
+            let (name, value) = match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) => (name.trim().to_lowercase(), value.trim()),
+                _ => continue,
+            };
+            match name.as_str() {
+                "max-page-size" => {
+                    capabilities.max_page_size = value.parse().ok().filter(|size| *size > 0)
+                }
+                "tx-info" => {
+                    capabilities.tx_info_types = Some(
+                        value
+                            .split(',')
+                            .map(|tx_info_type| tx_info_type.trim().to_string())
+                            .filter(|tx_info_type| !tx_info_type.is_empty())
+                            .collect(),
+                    )
+                }
+                _ => {}
+            }
+        }
+        capabilities
+    }
+
+    pub fn from_request(request: &Request<'_>) -> Option<Self> {
+        request
+            .headers()
+            .get_one(CLIENT_CAPABILITIES_HEADER)
+            .map(ClientCapabilities::from_header)
+    }
+
+    /// `Custom` can always be rendered, it is what unknown types are converted to
+    pub fn supports_tx_info(&self, tx_info_type: &str) -> bool {
+        tx_info_type == CUSTOM_TX_INFO
+            || self.tx_info_types.as_ref().map_or(true, |tx_info_types| {
+                tx_info_types
+                    .iter()
+                    .any(|supported| supported == tx_info_type)
+            })
+    }
+}
+
+/// `query` with its page size capped to `max_page_size`, `None` if it doesn't need to change.
+/// `add_cursor` adds a cursor to queries without one (for routes where the default page size
+/// would exceed the cap).
+pub fn capped_query(query: Option<&str>, max_page_size: u64, add_cursor: bool) -> Option<String> {
+    let mut changed = false;
+    let mut has_cursor = false;
+    let mut segments: Vec<String> = query
+        .unwrap_or("")
+        .split('&')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let mut parts = segment.splitn(2, '=');
+            let name = parts.next().unwrap_or("");
+            let value = parts.next().unwrap_or("");
+            match name {
+                "cursor" => {
+                    has_cursor = true;
+                    let mut page_metadata = PageMetadata::from_cursor(&decode(value));
+                    if page_metadata.limit <= max_page_size {
+                        return segment.to_string();
+                    }
+                    changed = true;
+                    page_metadata.limit = max_page_size;
+                    format!("cursor={}", encode(&page_metadata.to_url_string()))
+                }
+                "limit" => match value.parse::<u64>() {
+                    Ok(limit) if limit > max_page_size => {
+                        changed = true;
+                        format!("limit={}", max_page_size)
+                    }
+                    _ => segment.to_string(),
+                },
+                _ => segment.to_string(),
+            }
+        })
+        .collect();
+    if !has_cursor && add_cursor && max_page_size < DEFAULT_PAGE_SIZE {
+        changed = true;
+        let page_metadata = PageMetadata {
+            offset: 0,
+            limit: max_page_size,
+        };
+        segments.push(format!("cursor={}", encode(&page_metadata.to_url_string())));
+    }
+    changed.then(|| segments.join("&"))
+}
+
+/// `txInfo` objects of types the client doesn't know are replaced by a `Custom` one. Its `to`,
+/// `dataSize`, `value` and `methodName` come from the `txData` next to the `txInfo` and are left
+/// out where there is none (e.g. in transaction lists), rather than made up.
+pub fn down_convert(value: Value, capabilities: &ClientCapabilities) -> Value {
+    match value {
+        Value::Object(mut map) => {
+            if let Some(tx_info) = map.remove("txInfo") {
+                let tx_info = down_convert_tx_info(tx_info, map.get("txData"), capabilities);
+                map.insert(String::from("txInfo"), tx_info);
+            }
+            Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, down_convert(value, capabilities)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|value| down_convert(value, capabilities))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn down_convert_tx_info(
+    tx_info: Value,
+    tx_data: Option<&Value>,
+    capabilities: &ClientCapabilities,
+) -> Value {
+    let tx_info_type = match tx_info.get("type").and_then(Value::as_str) {
+        Some(tx_info_type) => tx_info_type,
+        None => return tx_info,
+    };
+    if capabilities.supports_tx_info(tx_info_type) {
+        return tx_info;
+    }
+    let mut custom = json!({
+        "type": CUSTOM_TX_INFO,
+        "isCancellation": false,
+    });
+    if let Some(tx_data) = tx_data.filter(|tx_data| tx_data.get("to").is_some()) {
+        let hex_data = tx_data
+            .get("hexData")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let method_name = tx_data
+            .pointer("/dataDecoded/method")
+            .cloned()
+            .unwrap_or(Value::Null);
+        custom["to"] = tx_data["to"].clone();
+        custom["dataSize"] = Value::String(data_size(&hex_data).to_string());
+        custom["value"] = tx_data
+            .get("value")
+            .filter(|value| value.is_string())
+            .cloned()
+            .unwrap_or_else(|| Value::String(String::from("0")));
+        custom["methodName"] = method_name;
+    }
+    custom
+}
+
+fn decode(value: &str) -> String {
+    value
+        .replace("%3D", "=")
+        .replace("%3d", "=")
+        .replace("%26", "&")
+}
+
+fn encode(value: &str) -> String {
+    value.replace('=', "%3D").replace('&', "%26")
+}
+
+/// Applies the [ClientCapabilities] of the request
+pub struct ClientCapabilityNegotiation();
+
+#[rocket::async_trait]
+impl Fairing for ClientCapabilityNegotiation {
+    fn info(&self) -> Info {
+        Info {
+            name: "Apply the capabilities declared by the client",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        let max_page_size = match ClientCapabilities::from_request(request)
+            .and_then(|capabilities| capabilities.max_page_size)
+        {
+            Some(max_page_size) => max_page_size,
+            None => return,
+        };
+        let path = request.uri().path().to_string();
+        let is_paginated = request.rocket().routes().any(|route| {
+            let template = route.uri.to_string();
+            template.contains("<cursor>")
+                && !template.contains("<limit>")
+                && matches_template(&template, &path)
+        });
+        let query = request.uri().query().map(|query| query.to_string());
+        if let Some(query) = capped_query(query.as_deref(), max_page_size, is_paginated) {
+            if let Ok(uri) = Origin::parse_owned(format!("{}?{}", path, query)) {
+                request.set_uri(uri);
+            }
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if response.content_type() != Some(ContentType::JSON) {
+            return;
+        }
+        // The same uri is served with different bodies depending on the header
+        response.adjoin_header(Header::new("Vary", CLIENT_CAPABILITIES_HEADER));
+        let capabilities = match ClientCapabilities::from_request(request) {
+            Some(capabilities) if capabilities.tx_info_types.is_some() => capabilities,
+            _ => return,
+        };
+        let body = match response.body_mut().to_string().await {
+            Ok(body) => body,
+            Err(error) => {
+                log::error!("Could not read response body: {}", error);
+                return;
+            }
+        };
+        let body = match serde_json::from_str::<Value>(&body) {
+            Ok(value) => serde_json::to_string(&down_convert(value, &capabilities)).unwrap_or(body),
+            Err(_) => body,
+        };
+        response.set_sized_body(body.len(), Cursor::new(body));
+    }
+}
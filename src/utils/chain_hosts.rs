@@ -0,0 +1,114 @@
+//! Resolution of the chain from the `Host` header, so that white-label deployments can serve
+//! chain scoped domains (e.g. `polygon.gateway.example.com/v1/safes/<safe_address>`) from the
+//! same instance. Requests are rewritten to the regular `/v1/chains/<chain_id>/...` routes.
+use crate::config::chain_hosts;
+use lazy_static::lazy_static;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::uri::Origin;
+use rocket::{Data, Request};
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref CHAIN_HOSTS: HashMap<String, String> = chain_hosts();
+}
+
+pub struct ChainHosts();
+
+#[rocket::async_trait]
+impl Fairing for ChainHosts {
+    fn info(&self) -> Info {
+        Info {
+            name: "Resolve the chain from the request host",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        let chain_id = match request
+            .headers()
+            .get_one("Host")
+            .and_then(|host| chain_for_host(&CHAIN_HOSTS, host))
+        {
+            Some(chain_id) => chain_id,
+            None => return,
+        };
+        let templates: Vec<String> = request
+            .rocket()
+            .routes()
+            .map(|route| route.uri.to_string())
+            .collect();
+        let path = request.uri().path().to_string();
+        if let Some(resolved_path) = resolve_path(&templates, &path, chain_id) {
+            let uri = match request.uri().query() {
+                Some(query) => format!("{}?{}", resolved_path, query),
+                None => resolved_path,
+            };
+            if let Ok(uri) = Origin::parse_owned(uri) {
+                request.set_uri(uri);
+            }
+        }
+    }
+}
+
+/// Matched by `host`, ignoring the port
+pub fn chain_for_host<'c>(chain_hosts: &'c HashMap<String, String>, host: &str) -> Option<&'c str> {
+    let host = host.split(':').next()?.to_lowercase();
+    chain_hosts.get(&host).map(String::as_str)
+}
+
+/// `path` scoped to `chain_id`, if it doesn't match any route as it is but does once scoped
+pub fn resolve_path(templates: &[String], path: &str, chain_id: &str) -> Option<String> {
+    let matches_any = |path: &str| {
+        templates
+            .iter()
+            .any(|template| matches_template(template, path))
+    };
+    if matches_any(path) {
+        return None;
+    }
+    chain_scoped_path(path, chain_id).filter(|scoped_path| matches_any(scoped_path))
+}
+
+/// `/<version>/<rest>` as `/<version>/chains/<chain_id>/<rest>`
+pub fn chain_scoped_path(path: &str, chain_id: &str) -> Option<String> {
+    let mut segments = path.trim_start_matches('/').splitn(2, '/');
+    let version = segments.next().filter(|version| is_version(version))?;
+    let rest = segments.next().unwrap_or("");
+    if rest.starts_with("chains/") || rest == "chains" {
+        return None;
+    }
+    if rest.is_empty() {
+        Some(format!("/{}/chains/{}", version, chain_id))
+    } else {
+        Some(format!("/{}/chains/{}/{}", version, chain_id, rest))
+    }
+}
+
+/// Segment wise match of a route uri (e.g. `/v1/chains/<chain_id>/safes/<safe_address>?<trusted>`),
+/// `<param>` matching a single segment and `<param..>` the remaining ones
+pub fn matches_template(template: &str, path: &str) -> bool {
+    let template_path = template.split('?').next().unwrap_or("");
+    let mut template_segments = template_path
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let mut path_segments = path.split('/').filter(|segment| !segment.is_empty());
+    loop {
+        match (template_segments.next(), path_segments.next()) {
+            (Some(template_segment), _) if template_segment.ends_with("..>") => return true,
+            (Some(template_segment), Some(path_segment)) => {
+                let is_param = template_segment.starts_with('<') && template_segment.ends_with('>');
+                if !is_param && template_segment != path_segment {
+                    return false;
+                }
+            }
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+fn is_version(segment: &str) -> bool {
+    segment.len() > 1
+        && segment.starts_with('v')
+        && segment[1..].chars().all(|c| c.is_ascii_digit())
+}
@@ -0,0 +1,130 @@
+//! Loads the chains list and the info of every chain into the cache at liftoff, so that the first
+//! requests after a deploy don't wait for the config service. Chains are loaded concurrently, at
+//! most `CHAIN_WARM_UP_CONCURRENCY` at a time.
+use crate::cache::Cache;
+use crate::config::chain_warm_up_concurrency;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::chains::handlers::{chain_id_of, get_all_chains};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::HttpClient;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::futures::{stream, StreamExt};
+use rocket::{Orbit, Rocket};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct WarmedUpChains {
+    pub loaded: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+pub async fn warm_up_chains(
+    context: &RequestContext,
+    concurrency: usize,
+) -> ApiResult<WarmedUpChains> {
+    let chain_ids: Vec<String> = get_all_chains(context)
+        .await?
+        .iter()
+        .filter_map(|chain| chain_id_of(chain).map(String::from))
+        .collect();
+    Ok(
+        load_concurrently(chain_ids, concurrency, |chain_id| async move {
+            // A context per chain, the lookups of a context are memoized one at a time
+            let context = RequestContext::new(
+                context.request_id.to_string(),
+                context.host.to_string(),
+                context.http_client(),
+                context.cache(),
+            );
+            DefaultInfoProvider::new(&chain_id, &context)
+                .chain_info()
+                .await
+                .map(|_| ())
+        })
+        .await,
+    )
+}
+
+/// Runs `load` for every chain, at most `concurrency` at a time
+pub async fn load_concurrently<F, Fut>(
+    chain_ids: Vec<String>,
+    concurrency: usize,
+    load: F,
+) -> WarmedUpChains
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = ApiResult<()>>,
+{
+    let results: Vec<(String, ApiResult<()>)> = stream::iter(chain_ids)
+        .map(|chain_id| {
+            let loaded = load(chain_id.to_string());
+            async move { (chain_id, loaded.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut warmed_up = WarmedUpChains::default();
+    for (chain_id, result) in results {
+        match result {
+            Ok(_) => warmed_up.loaded.push(chain_id),
+            Err(error) => {
+                log::warn!("Chain {} could not be warmed up: {}", chain_id, error);
+                warmed_up.failed.push(chain_id);
+            }
+        }
+    }
+    warmed_up.loaded.sort();
+    warmed_up.failed.sort();
+    warmed_up
+}
+
+pub struct ChainWarmUp();
+
+#[rocket::async_trait]
+impl Fairing for ChainWarmUp {
+    fn info(&self) -> Info {
+        Info {
+            name: "ChainWarmUp",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let concurrency = chain_warm_up_concurrency();
+        if concurrency == 0 {
+            return;
+        }
+        let (cache, http_client) = match (
+            rocket.state::<Arc<dyn Cache>>(),
+            rocket.state::<Arc<dyn HttpClient>>(),
+        ) {
+            (Some(cache), Some(http_client)) => (cache.clone(), http_client.clone()),
+            _ => return,
+        };
+        let context = RequestContext::new(
+            String::from("chain_warm_up"),
+            String::from("localhost"),
+            http_client,
+            cache,
+        );
+        let started = Instant::now();
+        match warm_up_chains(&context, concurrency).await {
+            Ok(warm_up) if warm_up.failed.is_empty() => log::info!(
+                "Warmed up {} chains in {} ms",
+                warm_up.loaded.len(),
+                started.elapsed().as_millis()
+            ),
+            Ok(warm_up) => log::warn!(
+                "Warmed up {} chains in {} ms, failed: {}",
+                warm_up.loaded.len(),
+                started.elapsed().as_millis(),
+                warm_up.failed.join(", ")
+            ),
+            Err(error) => log::warn!("Chains list could not be warmed up: {}", error),
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! EIP-55 checksums for the addresses in json responses. Upstream services return addresses in
+//! mixed forms (lowercase, checksummed), so that clients always display the same form:
+//!
+//! - [AddressEx](crate::common::models::addresses::AddressEx) values, the keys of the
+//!   `addressInfoIndex` and the decoded parameters of `address` types are checksummed when they
+//!   are serialized, see [serialize_checksummed] and [serialize_checksummed_keys]
+//! - the [AddressChecksums] fairing rewrites the values of the other known address fields in a
+//!   single pass over the response body
+use crate::providers::address_info::AddressInfoIndex;
+use ethcontract_common::hash::keccak256;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::ContentType;
+use rocket::{Request, Response};
+use serde::Serializer;
+use std::io::Cursor;
+
+/// EIP-55 form of a `0x` prefixed, 20 bytes hex `address` (in any case)
+pub fn to_checksum_address(address: &str) -> String {
+    let hex = address.trim_start_matches("0x").to_lowercase();
+    let hash = keccak256(hex.as_bytes());
+    let checksummed: String = hex
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            let nibble = (hash[index / 2] >> (if index % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if c.is_ascii_alphabetic() && nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+/// `value` checksummed if it is an address, as it is otherwise
+pub fn checksummed(value: &str) -> String {
+    if is_address(value) {
+        to_checksum_address(value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Serializes an address field in its checksummed form
+pub fn serialize_checksummed<S>(value: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&checksummed(value))
+}
+
+/// Serializes an [AddressInfoIndex] with its keys checksummed, like the values they are looked up
+/// with
+pub fn serialize_checksummed_keys<S>(
+    index: &Option<AddressInfoIndex>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match index {
+        Some(index) => serializer.collect_map(
+            index
+                .iter()
+                .map(|(address, address_ex)| (checksummed(address), address_ex)),
+        ),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Fields holding an address, or a list of addresses. The generic `value` field isn't one of
+/// them, as it also holds e.g. `bytes20` parameters: [AddressEx] and decoded parameters are
+/// checksummed by their serializers.
+///
+/// [AddressEx]: crate::common::models::addresses::AddressEx
+const ADDRESS_FIELDS: &[&str] = &[
+    "address",
+    "owners",
+    "owner",
+    "sender",
+    "recipient",
+    "to",
+    "from",
+    "signer",
+    "signers",
+    "delegate",
+    "delegator",
+    "safe",
+    "executor",
+    "refundReceiver",
+    "gasToken",
+    "tokenAddress",
+    "masterCopy",
+    "implementation",
+    "fallbackHandler",
+    "guard",
+    "modules",
+    "factoryAddress",
+    "creator",
+];
+
+// Object or array of the body being read, with the field its values belong to: the last key of
+// an object, the field holding an array
+struct Frame {
+    object: bool,
+    field: Option<String>,
+}
+
+/// `body` with the values of the [ADDRESS_FIELDS] that are exactly an address checksummed.
+/// Keys and other values (hashes, calldata) are left as they are.
+pub fn checksum_addresses(body: &str) -> String {
+    let bytes = body.as_bytes();
+    let mut output = String::with_capacity(body.len());
+    let mut copied = 0;
+    let mut frames: Vec<Frame> = vec![];
+    let mut expecting_key = false;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => {
+                let end = string_end(bytes, index);
+                let content = &body[index + 1..end];
+                if expecting_key {
+                    if let Some(frame) = frames.last_mut() {
+                        frame.field = Some(content.to_string());
+                    }
+                    expecting_key = false;
+                } else if is_address(content) && is_address_field(frames.last()) {
+                    output.push_str(&body[copied..index + 1]);
+                    output.push_str(&to_checksum_address(content));
+                    copied = end;
+                }
+                index = end + 1;
+                continue;
+            }
+            b'{' => {
+                frames.push(Frame {
+                    object: true,
+                    field: None,
+                });
+                expecting_key = true;
+            }
+            b'[' => {
+                let field = frames.last().and_then(|frame| frame.field.clone());
+                frames.push(Frame {
+                    object: false,
+                    field,
+                });
+            }
+            b'}' | b']' => {
+                frames.pop();
+                expecting_key = false;
+            }
+            b',' => expecting_key = frames.last().map_or(false, |frame| frame.object),
+            _ => {}
+        }
+        index += 1;
+    }
+    output.push_str(&body[copied..]);
+    output
+}
+
+/// Index of the quote closing the string starting at `start`, the length of `bytes` if it is not
+/// closed
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'"' => return index,
+            _ => index += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn is_address(value: &str) -> bool {
+    value.len() == 42
+        && value.starts_with("0x")
+        && value[2..].bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn is_address_field(frame: Option<&Frame>) -> bool {
+    frame
+        .and_then(|frame| frame.field.as_deref())
+        .map_or(false, |field| ADDRESS_FIELDS.contains(&field))
+}
+
+pub struct AddressChecksums();
+
+#[rocket::async_trait]
+impl Fairing for AddressChecksums {
+    fn info(&self) -> Info {
+        Info {
+            name: "Checksum the addresses of json responses",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, _request: &'r Request<'_>, response: &mut Response<'r>) {
+        if response.content_type() != Some(ContentType::JSON) {
+            return;
+        }
+        let body = match response.body_mut().to_string().await {
+            Ok(body) => body,
+            Err(error) => {
+                log::error!("Could not read response body: {}", error);
+                return;
+            }
+        };
+        let body = checksum_addresses(&body);
+        response.set_sized_body(body.len(), Cursor::new(body));
+    }
+}
@@ -1,6 +1,12 @@
 use crate::cache::Cache;
-use crate::config::scheme;
+use crate::config::{scheme, upstream_call_budget};
+use crate::monitoring::upstream_latency;
+use crate::providers::info::InfoMemo;
+use crate::utils::cache_control::{CacheStatus, DataFreshness, ResponseTtl};
+use crate::utils::call_budget::{BudgetedHttpClient, CallBudget};
 use crate::utils::http_client::HttpClient;
+use crate::utils::response_budget;
+use crate::utils::trace_id::{TraceId, TracedHttpClient};
 use rocket::request::{self, FromRequest, Request};
 use std::sync::Arc;
 
@@ -9,6 +15,36 @@ pub struct RequestContext {
     pub host: String,
     http_client: Arc<dyn HttpClient>,
     cache: Arc<dyn Cache>,
+    response_ttl: ResponseTtl,
+    data_freshness: DataFreshness,
+    cache_status: CacheStatus,
+    info_memo: InfoMemo,
+    call_budget: Option<Arc<CallBudget>>,
+    response_budget: Option<usize>,
+}
+
+impl RequestContext {
+    /// For entry points that are not served by rocket (or run detached from the request),
+    /// where the request guard is not available
+    pub fn new(
+        request_id: String,
+        host: String,
+        http_client: Arc<dyn HttpClient>,
+        cache: Arc<dyn Cache>,
+    ) -> Self {
+        RequestContext {
+            request_id,
+            host,
+            http_client,
+            cache,
+            response_ttl: ResponseTtl::default(),
+            data_freshness: DataFreshness::default(),
+            cache_status: CacheStatus::default(),
+            info_memo: InfoMemo::default(),
+            call_budget: None,
+            response_budget: None,
+        }
+    }
 }
 
 impl RequestContext {
@@ -19,6 +55,33 @@ impl RequestContext {
     pub fn cache(&self) -> Arc<dyn Cache> {
         self.cache.clone()
     }
+
+    pub fn response_ttl(&self) -> ResponseTtl {
+        self.response_ttl.clone()
+    }
+
+    pub fn data_freshness(&self) -> DataFreshness {
+        self.data_freshness.clone()
+    }
+
+    pub fn cache_status(&self) -> CacheStatus {
+        self.cache_status.clone()
+    }
+
+    /// Lookups of the info providers created for this request
+    pub fn info_memo(&self) -> InfoMemo {
+        self.info_memo.clone()
+    }
+
+    /// Upstream calls left to this request, `None` without `UPSTREAM_CALL_BUDGET`
+    pub fn call_budget(&self) -> Option<Arc<CallBudget>> {
+        self.call_budget.clone()
+    }
+
+    /// Size budget (in bytes) of the response to this request, see [response_budget]
+    pub fn response_budget(&self) -> Option<usize> {
+        self.response_budget
+    }
 }
 
 #[cfg(test)]
@@ -34,8 +97,30 @@ impl RequestContext {
             host,
             http_client: Arc::new(mock_http_client),
             cache: Arc::new(mock_cache),
+            response_ttl: ResponseTtl::default(),
+            data_freshness: DataFreshness::default(),
+            cache_status: CacheStatus::default(),
+            info_memo: InfoMemo::default(),
+            call_budget: None,
+            response_budget: None,
         }
     }
+
+    /// Spends `call_budget` on every upstream call, as contexts of requests do with
+    /// `UPSTREAM_CALL_BUDGET`
+    pub fn with_call_budget(mut self, call_budget: Arc<CallBudget>) -> Self {
+        self.http_client = Arc::new(BudgetedHttpClient::new(
+            self.http_client,
+            call_budget.clone(),
+        ));
+        self.call_budget = Some(call_budget);
+        self
+    }
+
+    pub fn with_response_budget(mut self, response_budget: usize) -> Self {
+        self.response_budget = Some(response_budget);
+        self
+    }
 }
 
 #[rocket::async_trait]
@@ -59,13 +144,53 @@ impl<'r> FromRequest<'r> for RequestContext {
             .expect("Request Host must be available");
 
         let uri = request.uri().to_string();
+        let response_ttl = request.local_cache(ResponseTtl::default).clone();
+        let data_freshness = request.local_cache(DataFreshness::default).clone();
+        let cache_status = request.local_cache(CacheStatus::default).clone();
+        let info_memo = request.local_cache(InfoMemo::default).clone();
+        // Shared by every guard of the request, so that all of its calls spend the same budget
+        let call_budget = request
+            .local_cache(|| {
+                let limit = upstream_call_budget();
+                (limit > 0).then(|| {
+                    let route = request.route().map_or_else(
+                        || request.uri().path().to_string(),
+                        |route| format!("{} {}", request.method(), route.uri),
+                    );
+                    Arc::new(CallBudget::new(route, limit))
+                })
+            })
+            .clone();
+        let http_client = match upstream_latency::chain_of_path(&request.uri().path().to_string()) {
+            Some(chain_id) => upstream_latency::tracked(http_client, chain_id),
+            None => http_client,
+        };
+        let http_client: Arc<dyn HttpClient> = match call_budget.as_ref() {
+            Some(call_budget) => {
+                Arc::new(BudgetedHttpClient::new(http_client, call_budget.clone()))
+            }
+            None => http_client,
+        };
+        let http_client: Arc<dyn HttpClient> = match TraceId::of(request) {
+            Some(trace_id) => Arc::new(TracedHttpClient::new(http_client, trace_id)),
+            None => http_client,
+        };
         let host = format!("{}://{}", scheme(), host.to_string());
+        let response_budget = request
+            .route()
+            .and_then(|route| response_budget::route_budget(&route.uri.to_string()));
 
         return request::Outcome::Success(RequestContext {
             request_id: uri,
             host,
             cache,
             http_client,
+            response_ttl,
+            data_freshness,
+            cache_status,
+            info_memo,
+            call_budget,
+            response_budget,
         });
     }
 }
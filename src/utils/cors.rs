@@ -20,11 +20,11 @@ impl Fairing for CORS {
             response.set_header(Header::new("Access-Control-Allow-Origin", "*"));
             response.set_header(Header::new(
                 "Access-Control-Allow-Methods",
-                "POST, GET, OPTIONS",
+                "POST, GET, PUT, OPTIONS",
             ));
             response.set_header(Header::new(
                 "Access-Control-Allow-Headers",
-                "X-Requested-With, Content-Type, Authorization",
+                "X-Requested-With, Content-Type, Authorization, Safe-Device-Uuid, Safe-Device-Token, X-Serialization-Profile, X-Client-Capabilities",
             ));
             response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
         }
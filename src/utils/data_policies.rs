@@ -0,0 +1,193 @@
+//! Upper bound of the age of the data served by the read routes, so that integrators can reason
+//! about freshness. The bound of a route is what its response and the upstream responses it is
+//! built from can stay cached, the [DataMaxAge] fairing tightens it with the remaining ttl of the
+//! served response and sends it as `X-Data-Max-Age` (in seconds). Last known good copies served
+//! while the upstream fails can be older, up to `LAST_KNOWN_GOOD_CACHE_DURATION`.
+use crate::config::{
+    about_cache_duration, balances_cache_duration, chain_info_cache_duration,
+    chain_info_response_cache_duration, exchange_api_cache_duration, exchange_rates_cache_duration,
+    gas_price_cache_duration, last_known_good_cache_duration, owners_for_safes_cache_duration,
+    request_cache_duration, safe_info_cache_duration, token_price_cache_duration,
+};
+use crate::utils::cache_control::{DataFreshness, ResponseTtl};
+use lazy_static::lazy_static;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Header, Method, Status};
+use rocket::{Request, Response};
+use serde::Serialize;
+use std::cmp::max;
+
+lazy_static! {
+    static ref DATA_POLICIES: Vec<DataPolicy> = policies();
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DataPolicy {
+    /// Path of the route, without its query
+    pub route: &'static str,
+    /// In ms, how long the response is cached by the gateway
+    pub response_cache: usize,
+    /// In ms, the longest the upstream responses it is built from are cached
+    pub upstream_cache: usize,
+    /// Entries are dropped before they expire when the upstream reports changes via hooks
+    pub invalidated_by_hooks: bool,
+    /// In ms, `responseCache` + `upstreamCache`
+    pub max_age: usize,
+}
+
+impl DataPolicy {
+    pub fn new(
+        route: &'static str,
+        response_cache: usize,
+        upstream_cache: usize,
+        invalidated_by_hooks: bool,
+    ) -> Self {
+        DataPolicy {
+            route,
+            response_cache,
+            upstream_cache,
+            invalidated_by_hooks,
+            max_age: response_cache.saturating_add(upstream_cache),
+        }
+    }
+
+    /// Bound (in ms) for a response served with `remaining_ttl` left in the response cache, or
+    /// built from a last known good copy if `is_stale`
+    pub fn max_age_of(&self, remaining_ttl: Option<usize>, is_stale: bool) -> usize {
+        let cached_for = remaining_ttl.map_or(self.response_cache, |ttl| {
+            self.response_cache.saturating_sub(ttl)
+        });
+        let upstream_cache = if is_stale {
+            max(self.upstream_cache, last_known_good_cache_duration())
+        } else {
+            self.upstream_cache
+        };
+        cached_for.saturating_add(upstream_cache)
+    }
+}
+
+/// Policies of the read routes served from the cache
+pub fn policies() -> Vec<DataPolicy> {
+    let request_cache = request_cache_duration();
+    vec![
+        DataPolicy::new(
+            "/v1/chains",
+            chain_info_response_cache_duration(),
+            chain_info_cache_duration(),
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>",
+            chain_info_response_cache_duration(),
+            chain_info_cache_duration(),
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/about",
+            about_cache_duration(),
+            chain_info_cache_duration(),
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/gas-price",
+            gas_price_cache_duration(),
+            0,
+            false,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/exchange-rates",
+            exchange_rates_cache_duration(),
+            max(token_price_cache_duration(), exchange_api_cache_duration()),
+            false,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/owners/<owner_address>/safes",
+            owners_for_safes_cache_duration(),
+            owners_for_safes_cache_duration(),
+            false,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/safes/<safe_address>",
+            request_cache,
+            safe_info_cache_duration(),
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/safes/<safe_address>/balances/<fiat>",
+            balances_cache_duration(),
+            max(balances_cache_duration(), token_price_cache_duration()),
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/safes/<safe_address>/collectibles",
+            request_cache,
+            request_cache,
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/safes/<safe_address>/transactions/history",
+            request_cache,
+            request_cache,
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued",
+            request_cache,
+            request_cache,
+            true,
+        ),
+        DataPolicy::new(
+            "/v1/chains/<chain_id>/transactions/<details_id>",
+            request_cache,
+            request_cache,
+            true,
+        ),
+    ]
+}
+
+/// Policy of `route`, a route template with or without its query
+pub fn policy_for<'p>(policies: &'p [DataPolicy], route: &str) -> Option<&'p DataPolicy> {
+    let path = route.split('?').next().unwrap_or(route);
+    policies.iter().find(|policy| policy.route == path)
+}
+
+pub fn configured_policies() -> &'static [DataPolicy] {
+    &DATA_POLICIES
+}
+
+/// `X-Data-Max-Age` header value (in seconds, rounded up) of `max_age` (in ms)
+pub fn header_value(max_age: usize) -> String {
+    ((max_age.saturating_add(999)) / 1000).to_string()
+}
+
+/// Adds `X-Data-Max-Age` to successful reads of the routes with a [DataPolicy]
+pub struct DataMaxAge();
+
+#[rocket::async_trait]
+impl Fairing for DataMaxAge {
+    fn info(&self) -> Info {
+        Info {
+            name: "Add the maximum data age to read responses",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let is_read = request.method() == Method::Get || request.method() == Method::Head;
+        if !is_read || response.status() != Status::Ok {
+            return;
+        }
+        let route = match request.route() {
+            Some(route) => route.uri.to_string(),
+            None => return,
+        };
+        if let Some(policy) = policy_for(&DATA_POLICIES, &route) {
+            let max_age = policy.max_age_of(
+                request.local_cache(ResponseTtl::default).get(),
+                request.local_cache(DataFreshness::default).is_stale(),
+            );
+            response.set_header(Header::new("X-Data-Max-Age", header_value(max_age)));
+        }
+    }
+}
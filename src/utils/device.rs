@@ -0,0 +1,60 @@
+use crate::cache::Cache;
+use crate::utils::errors::ApiResult;
+use ethcontract_common::hash::keccak256;
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, Request};
+use std::sync::Arc;
+
+const DEVICE_TOKENS_KEY: &str = "device_tokens";
+pub const DEVICE_UUID_HEADER: &str = "Safe-Device-Uuid";
+pub const DEVICE_TOKEN_HEADER: &str = "Safe-Device-Token";
+
+/// Device identity sent by clients as `Safe-Device-Uuid` and `Safe-Device-Token` headers.
+/// The token is bound to the uuid the first time it is used, later requests for the same
+/// uuid have to present the same token.
+pub struct Device {
+    pub uuid: String,
+    token: String,
+}
+
+impl Device {
+    pub fn new(uuid: &str, token: &str) -> Self {
+        Device {
+            uuid: uuid.to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    /// Only a hash of the token is stored
+    pub async fn authenticate(&self, cache: &Arc<dyn Cache>) -> ApiResult<()> {
+        let token_hash = to_hex_string!(keccak256(self.token.as_bytes()));
+        match cache.get_from_hash(DEVICE_TOKENS_KEY, &self.uuid).await {
+            Some(stored_hash) if stored_hash == token_hash => Ok(()),
+            Some(_) => Err(client_error!(401, "Invalid device token")),
+            None => {
+                cache
+                    .insert_in_hash(DEVICE_TOKENS_KEY, &self.uuid, &token_hash)
+                    .await;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Device {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let headers = request.headers();
+        match (
+            headers.get_one(DEVICE_UUID_HEADER),
+            headers.get_one(DEVICE_TOKEN_HEADER),
+        ) {
+            (Some(uuid), Some(token)) if !uuid.is_empty() && !token.is_empty() => {
+                request::Outcome::Success(Device::new(uuid, token))
+            }
+            _ => request::Outcome::Failure((Status::Unauthorized, ())),
+        }
+    }
+}
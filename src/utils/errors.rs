@@ -1,5 +1,9 @@
 use crate::config::log_all_error_responses;
 use crate::utils::http_client::Response as HttpClientResponse;
+use crate::utils::payload_limits;
+use crate::utils::trace_id::{self, TraceId};
+use crate::utils::upstream_queue;
+use crate::utils::validation::INVALID_REQUEST_BODY;
 use reqwest::StatusCode;
 use rocket::http::{ContentType, Status};
 use rocket::request::Request;
@@ -84,6 +88,32 @@ impl ApiError {
         )
     }
 
+    /// For errors clients tell apart by their `code`
+    pub fn new_with_error_code(status_code: u16, code: u64, message: String) -> Self {
+        Self::new(
+            status_code,
+            ErrorDetails {
+                code,
+                message: Some(message),
+                arguments: None,
+                debug: None,
+            },
+        )
+    }
+
+    /// `422` listing every invalid field of a request body in the `arguments`
+    pub fn new_validation_error(errors: Vec<String>) -> Self {
+        Self::new(
+            422,
+            ErrorDetails {
+                code: 1337,
+                message: Some(String::from(INVALID_REQUEST_BODY)),
+                arguments: Some(errors),
+                debug: None,
+            },
+        )
+    }
+
     fn new(status_code: u16, message: ErrorDetails) -> Self {
         Self {
             status: status_code,
@@ -112,23 +142,28 @@ impl<'r> Responder<'r, 'static> for ApiError {
     fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
         if log_all_error_responses() || (self.status >= 500 && self.status < 600) {
             log::error!(
-                "ERR::{}::{}::{}",
+                "ERR::{}::{}::{}::{}",
                 self.status,
                 request.uri().to_string(),
-                self.details
+                self.details,
+                TraceId::of(request).unwrap_or_default()
             );
         }
-        let resp = serde_json::to_string(&self.details).unwrap_or(String::from(
+        let resp = trace_id::error_body(request, &self.details).unwrap_or(String::from(
             &self
                 .details
                 .message
                 .unwrap_or("No message error from backend".to_string()),
         ));
-        Response::build()
+        let mut response = Response::build();
+        response
             .sized_body(resp.len(), Cursor::new(resp))
             .header(ContentType::JSON)
-            .status(Status::from_code(self.status).unwrap_or(Status::new(self.status)))
-            .ok()
+            .status(Status::from_code(self.status).unwrap_or(Status::new(self.status)));
+        if upstream_queue::is_shed_error(&self) {
+            response.raw_header("Retry-After", upstream_queue::retry_after().to_string());
+        }
+        response.ok()
     }
 }
 
@@ -153,10 +188,17 @@ impl From<serde_json::error::Error> for ApiError {
 
 impl From<rocket::serde::json::Error<'_>> for ApiError {
     fn from(err: Error<'_>) -> Self {
-        let message = match err {
-            Error::Io(_) => String::from("Request deserialize IO error"),
-            Error::Parse(_request_json, json_error) => json_error.to_string(),
-        };
-        Self::new_from_message_with_code(422, message)
+        match err {
+            // Rocket cuts bodies off at the `json` limit with an unexpected EOF
+            Error::Io(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => {
+                payload_limits::request_body_too_large()
+            }
+            Error::Io(_) => {
+                Self::new_from_message_with_code(422, String::from("Request deserialize IO error"))
+            }
+            Error::Parse(_request_json, json_error) => {
+                Self::new_validation_error(vec![json_error.to_string()])
+            }
+        }
     }
 }
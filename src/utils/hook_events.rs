@@ -0,0 +1,463 @@
+//! Hooks of the transaction service published as normalized [HookEvent]s to a broker, so that
+//! other backend systems can follow the activity of Safes without polling the gateway. Publishing
+//! happens in the background after the caches were invalidated. The events of a request are sent
+//! as a single batch, the events the broker didn't take are sent again (up to
+//! `HOOK_EVENTS_MAX_ATTEMPTS` times, with a delay doubling from `HOOK_EVENTS_RETRY_INTERVAL`)
+//! without the ones it took. Delivery is at least once, consumers deduplicate by [HookEvent::id].
+//!
+//! - `kafka`: the records api (v2) of the Kafka REST proxy, with the http client of the gateway,
+//!   keyed by `<chain_id>:<address>`
+//! - `nats`: core NATS over a plain TCP connection per batch, to the subject
+//!   `<topic>.<chain_id>.<address>`. Publications are confirmed by the server (`verbose` mode).
+use crate::common::models::backend::hooks::Payload;
+use crate::config::{
+    default_request_timeout, hook_events_broker, hook_events_max_attempts,
+    hook_events_retry_interval, hook_events_topic, hook_events_uri,
+};
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{HttpClient, Request};
+use crate::utils::retry_queue::retry_delay;
+use chrono::Utc;
+use ethcontract_common::hash::keccak256;
+use lazy_static::lazy_static;
+use reqwest::Url;
+use rocket::tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use rocket::tokio::net::TcpStream;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const KAFKA: &str = "kafka";
+pub const NATS: &str = "nats";
+pub const BROKERS: [&str; 2] = [KAFKA, NATS];
+
+// Legacy hooks don't have a chain id
+const UNKNOWN_CHAIN: &str = "unknown";
+// Limit of Kafka for topic names
+const MAX_TOPIC_LENGTH: usize = 249;
+const MAX_EVENT_TYPE_LENGTH: usize = 64;
+const UNKNOWN_EVENT_TYPE: &str = "UNKNOWN";
+const KAFKA_JSON_CONTENT_TYPE: &str = "application/vnd.kafka.json.v2+json";
+const NATS_DEFAULT_PORT: u16 = 4222;
+const MISSING_CONFIRMATION: &str = "The broker didn't confirm the event";
+
+lazy_static! {
+    static ref PUBLISHED_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static ref RETRIED_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static ref FAILED_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static ref LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+}
+
+/// A hook as published, the same for every broker
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HookEvent {
+    /// Same for the same hook delivered twice, for consumers to deduplicate
+    pub id: String,
+    pub chain_id: Option<String>,
+    pub address: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Fields of the hook besides the address, chain and type
+    pub details: Value,
+    /// In ms
+    pub received_at: i64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishStats {
+    pub broker: Option<String>,
+    pub published_events: u64,
+    /// Events sent again, counted once per retry
+    pub retried_events: u64,
+    /// Events dropped after their last attempt
+    pub failed_events: u64,
+    pub last_error: Option<String>,
+}
+
+/// Events of a batch the broker didn't take, the others were published
+#[derive(Debug, PartialEq)]
+pub struct FailedEvents {
+    pub events: Vec<HookEvent>,
+    /// Error of the first failed event
+    pub error: String,
+}
+
+impl FailedEvents {
+    pub fn all(events: &[HookEvent], error: impl ToString) -> Self {
+        FailedEvents {
+            events: events.to_vec(),
+            error: error.to_string(),
+        }
+    }
+}
+
+/// Outcome of [deliver]
+#[derive(Debug, Default, PartialEq)]
+pub struct Delivery {
+    pub published: usize,
+    pub retried: usize,
+    pub failed: usize,
+}
+
+#[rocket::async_trait]
+pub trait EventPublisher: Send + Sync {
+    /// Publishes `events` as a single batch
+    async fn publish(&self, events: &[HookEvent]) -> Result<(), FailedEvents>;
+}
+
+pub fn normalize(payload: &Payload, received_at: i64) -> HookEvent {
+    let mut details = match serde_json::to_value(&payload.details) {
+        Ok(Value::Object(details)) => details,
+        _ => Map::new(),
+    };
+    let event_type = match details.remove("type") {
+        Some(Value::String(event_type)) if is_valid_event_type(&event_type) => event_type,
+        _ => String::from(UNKNOWN_EVENT_TYPE),
+    };
+    let id = to_hex_string!(keccak256(
+        serde_json::to_string(payload)
+            .unwrap_or_default()
+            .as_bytes()
+    ));
+    HookEvent {
+        id,
+        chain_id: payload.chain_id.clone(),
+        address: payload.address.to_string(),
+        event_type,
+        details: Value::Object(details),
+        received_at,
+    }
+}
+
+/// Publishes `payloads` in the background if a broker is configured
+pub fn publish(http_client: Arc<dyn HttpClient>, payloads: &[Payload]) {
+    let publisher = match configured_publisher(http_client) {
+        Some(publisher) => publisher,
+        None => return,
+    };
+    let received_at = Utc::now().timestamp_millis();
+    let events: Vec<HookEvent> = payloads
+        .iter()
+        .map(|payload| normalize(payload, received_at))
+        .collect();
+    rocket::tokio::spawn(async move {
+        let delivery = deliver(
+            publisher.as_ref(),
+            events,
+            hook_events_max_attempts(),
+            hook_events_retry_interval(),
+        )
+        .await;
+        record_delivery(&delivery);
+    });
+}
+
+/// Publishes `events`, sending the ones the broker didn't take again until they were tried
+/// `max_attempts` times, after a delay doubling from `retry_interval` ms
+pub async fn deliver(
+    publisher: &dyn EventPublisher,
+    events: Vec<HookEvent>,
+    max_attempts: usize,
+    retry_interval: u64,
+) -> Delivery {
+    let mut delivery = Delivery::default();
+    let mut pending = events;
+    for attempt in 1..=max_attempts {
+        let sent = pending.len();
+        let failed = match publisher.publish(&pending).await {
+            Ok(_) => {
+                delivery.published += sent;
+                pending = vec![];
+                break;
+            }
+            Err(failed) => failed,
+        };
+        delivery.published += sent.saturating_sub(failed.events.len());
+        log::warn!(
+            "Publishing {} of {} hook events failed (attempt {} of {}): {}",
+            failed.events.len(),
+            sent,
+            attempt,
+            max_attempts,
+            failed.error
+        );
+        *LAST_ERROR.lock().unwrap() = Some(failed.error);
+        pending = failed.events;
+        if attempt < max_attempts {
+            delivery.retried += pending.len();
+            let delay = retry_delay(retry_interval, attempt);
+            rocket::tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+    }
+    delivery.failed = pending.len();
+    delivery
+}
+
+/// Invalid settings are reported when they are validated, nothing is published with them
+pub fn configured_publisher(http_client: Arc<dyn HttpClient>) -> Option<Box<dyn EventPublisher>> {
+    let uri = hook_events_uri()?;
+    let topic = hook_events_topic();
+    if !is_valid_topic(&topic) {
+        return None;
+    }
+    match hook_events_broker()?.as_str() {
+        KAFKA => Some(Box::new(KafkaRestPublisher {
+            http_client,
+            uri,
+            topic,
+        })),
+        NATS if is_valid_subject_prefix(&topic) => Some(Box::new(NatsPublisher {
+            uri,
+            subject_prefix: topic,
+        })),
+        _ => None,
+    }
+}
+
+/// Kafka topic names are made of letters, digits, `.`, `_` and `-`, so they also can't change the
+/// path of the records api
+pub fn is_valid_topic(topic: &str) -> bool {
+    !topic.is_empty()
+        && topic.len() <= MAX_TOPIC_LENGTH
+        && topic != "."
+        && topic != ".."
+        && topic
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-')
+}
+
+/// NATS subjects are tokens separated by `.`, none of them empty
+pub fn is_valid_subject_prefix(topic: &str) -> bool {
+    topic.split('.').all(|token| !token.is_empty())
+}
+
+/// Types of the hooks, e.g. `NEW_CONFIRMATION`. Others are published as `UNKNOWN`.
+pub fn is_valid_event_type(event_type: &str) -> bool {
+    !event_type.is_empty()
+        && event_type.len() <= MAX_EVENT_TYPE_LENGTH
+        && event_type
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+pub fn record_delivery(delivery: &Delivery) {
+    PUBLISHED_EVENTS.fetch_add(delivery.published as u64, Ordering::Relaxed);
+    RETRIED_EVENTS.fetch_add(delivery.retried as u64, Ordering::Relaxed);
+    FAILED_EVENTS.fetch_add(delivery.failed as u64, Ordering::Relaxed);
+    if delivery.failed > 0 {
+        log::error!("Dropped {} hook events", delivery.failed);
+    }
+}
+
+/// Deliveries since the start of the instance
+pub fn stats() -> PublishStats {
+    PublishStats {
+        broker: hook_events_broker(),
+        published_events: PUBLISHED_EVENTS.load(Ordering::Relaxed),
+        retried_events: RETRIED_EVENTS.load(Ordering::Relaxed),
+        failed_events: FAILED_EVENTS.load(Ordering::Relaxed),
+        last_error: LAST_ERROR.lock().unwrap().clone(),
+    }
+}
+
+fn chain_of(event: &HookEvent) -> &str {
+    event.chain_id.as_deref().unwrap_or(UNKNOWN_CHAIN)
+}
+
+/// Splits `events` by the `results` of the broker, which are in the same order. Events without
+/// a result were not confirmed and count as failed.
+pub fn failed_events(
+    events: &[HookEvent],
+    mut results: impl Iterator<Item = Result<(), String>>,
+) -> Result<(), FailedEvents> {
+    let mut failed: Option<FailedEvents> = None;
+    for event in events {
+        let error = match results.next() {
+            Some(Ok(_)) => continue,
+            Some(Err(error)) => error,
+            None => String::from(MISSING_CONFIRMATION),
+        };
+        failed
+            .get_or_insert_with(|| FailedEvents {
+                events: vec![],
+                error,
+            })
+            .events
+            .push(event.clone());
+    }
+    match failed {
+        Some(failed) => Err(failed),
+        None => Ok(()),
+    }
+}
+
+pub struct KafkaRestPublisher {
+    pub http_client: Arc<dyn HttpClient>,
+    pub uri: String,
+    pub topic: String,
+}
+
+#[rocket::async_trait]
+impl EventPublisher for KafkaRestPublisher {
+    async fn publish(&self, events: &[HookEvent]) -> Result<(), FailedEvents> {
+        let url = format!("{}/topics/{}", self.uri.trim_end_matches('/'), self.topic);
+        let records = events
+            .iter()
+            .map(kafka_record)
+            .collect::<ApiResult<Vec<Value>>>()
+            .map_err(|error| FailedEvents::all(events, error))?;
+        let mut request = Request::new(url);
+        request
+            .content_type(KAFKA_JSON_CONTENT_TYPE)
+            .body(Some(json!({ "records": records }).to_string()));
+        let response = self
+            .http_client
+            .post(request)
+            .await
+            .map_err(|error| FailedEvents::all(events, error))?;
+        failed_events(events, kafka_results(&response.body).into_iter())
+    }
+}
+
+/// Keyed by Safe, so that the events of a Safe stay ordered within their partition
+pub fn kafka_record(event: &HookEvent) -> ApiResult<Value> {
+    Ok(json!({
+        "key": format!("{}:{}", chain_of(event), event.address),
+        "value": serde_json::to_value(event)?,
+    }))
+}
+
+/// Results of the records of a produce response, from its `offsets` (in the order of the records)
+pub fn kafka_results(body: &str) -> Vec<Result<(), String>> {
+    let response: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+    response["offsets"]
+        .as_array()
+        .map(|offsets| {
+            offsets
+                .iter()
+                .map(|offset| match offset.get("error_code") {
+                    None | Some(Value::Null) => Ok(()),
+                    Some(error_code) => Err(format!(
+                        "Error {}: {}",
+                        error_code,
+                        offset["error"].as_str().unwrap_or_default()
+                    )),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub struct NatsPublisher {
+    /// `nats://[<user>:<password>@]<host>[:<port>]`
+    pub uri: String,
+    pub subject_prefix: String,
+}
+
+#[rocket::async_trait]
+impl EventPublisher for NatsPublisher {
+    async fn publish(&self, events: &[HookEvent]) -> Result<(), FailedEvents> {
+        let timeout = Duration::from_millis(default_request_timeout());
+        let answers = match rocket::tokio::time::timeout(timeout, self.send(events)).await {
+            Ok(Ok(answers)) => answers,
+            Ok(Err(error)) => return Err(FailedEvents::all(events, error)),
+            Err(_) => {
+                return Err(FailedEvents::all(
+                    events,
+                    format!("No answer of the NATS server within {:?}", timeout),
+                ))
+            }
+        };
+        // The first answer is the one to CONNECT, e.g. an authorization violation
+        let mut answers = answers.into_iter();
+        if let Some(Err(error)) = answers.next() {
+            return Err(FailedEvents::all(events, error));
+        }
+        failed_events(events, answers)
+    }
+}
+
+impl NatsPublisher {
+    /// Answers of the server to CONNECT and to the PUB of every event, in order: `Ok` for `+OK`,
+    /// the message of `-ERR` otherwise. The server is done with the batch once it answered
+    /// the final PING.
+    async fn send(&self, events: &[HookEvent]) -> ApiResult<Vec<Result<(), String>>> {
+        let url = Url::parse(&self.uri)
+            .map_err(|error| api_error!("Invalid NATS uri {}: {}", self.uri, error))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| api_error!("No host in the NATS uri {}", self.uri))?;
+        let stream = TcpStream::connect((host, url.port().unwrap_or(NATS_DEFAULT_PORT)))
+            .await
+            .map_err(|error| api_error!("Could not connect to {}: {}", host, error))?;
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .await
+            .map_err(|error| api_error!("Could not read from {}: {}", host, error))?;
+        if !line.starts_with("INFO") {
+            bail!("Unexpected greeting of {}: {}", host, line.trim_end());
+        }
+        let mut commands = format!("CONNECT {}\r\n", nats_connect_options(&url));
+        for event in events {
+            let payload = serde_json::to_string(event)?;
+            commands.push_str(&format!(
+                "PUB {} {}\r\n{}\r\n",
+                nats_subject(&self.subject_prefix, event),
+                payload.len(),
+                payload
+            ));
+        }
+        commands.push_str("PING\r\n");
+        writer
+            .write_all(commands.as_bytes())
+            .await
+            .map_err(|error| api_error!("Could not write to {}: {}", host, error))?;
+        let mut answers = vec![];
+        loop {
+            line.clear();
+            let read = reader
+                .read_line(&mut line)
+                .await
+                .map_err(|error| api_error!("Could not read from {}: {}", host, error))?;
+            let answer = line.trim_end();
+            // The server closes the connection after most errors
+            if read == 0 || answer == "PONG" {
+                break;
+            }
+            if answer == "+OK" {
+                answers.push(Ok(()));
+            } else if let Some(error) = answer.strip_prefix("-ERR") {
+                answers.push(Err(error.trim().trim_matches('\'').to_string()));
+            }
+        }
+        Ok(answers)
+    }
+}
+
+/// `verbose`, so that the server confirms every PUB
+pub fn nats_connect_options(url: &Url) -> Value {
+    let mut options = json!({
+        "verbose": true,
+        "pedantic": false,
+        "name": "safe-client-gateway",
+        "lang": "rust",
+        "version": env!("CARGO_PKG_VERSION"),
+    });
+    if !url.username().is_empty() {
+        options["user"] = Value::String(url.username().to_string());
+        options["pass"] = Value::String(url.password().unwrap_or_default().to_string());
+    }
+    options
+}
+
+/// By chain and Safe, so that consumers can subscribe to e.g. `safe-events.4.*`
+pub fn nats_subject(subject_prefix: &str, event: &HookEvent) -> String {
+    format!("{}.{}.{}", subject_prefix, chain_of(event), event.address)
+}
@@ -1,14 +1,35 @@
-use crate::config::default_request_timeout;
+use crate::config::{
+    default_request_timeout, upstream_headers, upstream_max_response_size, upstream_tls,
+    UpstreamTls,
+};
 use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::outbound;
+use crate::utils::payload_limits;
+use crate::utils::shadowing;
+use crate::utils::trace_id::TRACE_ID_HEADER;
 use core::time::Duration;
+use lazy_static::lazy_static;
 use mockall::automock;
 use reqwest::header::CONTENT_TYPE;
+use reqwest::{Certificate, Client, Identity, RequestBuilder, Url};
+use std::collections::HashMap;
+use std::fs;
+
+pub type UpstreamHeaders = HashMap<String, HashMap<String, String>>;
+
+lazy_static! {
+    static ref UPSTREAM_HEADERS: UpstreamHeaders = upstream_headers();
+}
 
 #[derive(PartialEq, Debug)]
 pub struct Request {
     url: String,
     body: Option<String>,
-    timeout: Duration,
+    /// `None` for the `DEFAULT_REQUEST_TIMEOUT`
+    timeout: Option<Duration>,
+    trace_id: Option<String>,
+    /// `None` for `application/json`
+    content_type: Option<String>,
 }
 
 impl Request {
@@ -16,19 +37,51 @@ impl Request {
         Request {
             url,
             body: None,
-            timeout: Duration::from_millis(default_request_timeout()),
+            timeout: None,
+            trace_id: None,
+            content_type: None,
         }
     }
 
     pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
-        self.timeout = timeout;
+        self.timeout = Some(timeout);
         self
     }
 
+    /// Whether the timeout was set, instead of the `DEFAULT_REQUEST_TIMEOUT`
+    pub fn has_explicit_timeout(&self) -> bool {
+        self.timeout.is_some()
+    }
+
+    /// Host of the url, `None` for urls that can't be parsed
+    pub fn host(&self) -> Option<String> {
+        Url::parse(&self.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+    }
+
     pub fn body(&mut self, body: Option<String>) -> &mut Self {
         self.body = body;
         self
     }
+
+    /// Content type of the body of posts and deletes, for upstreams that don't take
+    /// `application/json`
+    pub fn content_type(&mut self, content_type: &str) -> &mut Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Forwarded in the `X-Trace-Id` header
+    pub fn trace_id(&mut self, trace_id: Option<String>) -> &mut Self {
+        self.trace_id = trace_id;
+        self
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -51,16 +104,33 @@ impl Response {
     /// Maps a [reqwest::Response] into a [ApiResult<Response>]
     /// If the response is a client error [400, 500[ or a server error [500, 600[ then
     /// an [ApiError] is returned as a failure. [Response] is returned otherwise.
+    /// Bodies exceeding the payload limits fail with a 502 before they are parsed.
     ///
     /// # Arguments
     ///
     /// * `reqwest_response`: The [reqwest::Response] to be mapped
+    /// * `url`: The url the response was requested from
     ///
     /// returns: Result<Response, ApiError>
     ///
-    async fn from(reqwest_response: reqwest::Response) -> ApiResult<Self> {
+    async fn from(mut reqwest_response: reqwest::Response, url: &str) -> ApiResult<Self> {
         let status_code = reqwest_response.status().as_u16();
-        let body: String = reqwest_response.text().await?;
+        let max_size = upstream_max_response_size();
+        if reqwest_response
+            .content_length()
+            .map_or(false, |length| length > max_size as u64)
+        {
+            return Err(payload_limits::response_too_large(url));
+        }
+        let mut bytes = vec![];
+        while let Some(chunk) = reqwest_response.chunk().await? {
+            if bytes.len() + chunk.len() > max_size {
+                return Err(payload_limits::response_too_large(url));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        let body = String::from_utf8_lossy(&bytes).into_owned();
+        payload_limits::check_response_depth(url, &body)?;
         let response = Response { body, status_code };
 
         if response.is_client_error() || response.is_server_error() {
@@ -71,46 +141,238 @@ impl Response {
     }
 }
 
+/// Raw body of a response that is not JSON (e.g. images)
+#[derive(PartialEq, Debug)]
+pub struct BinaryResponse {
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
 #[automock]
 #[rocket::async_trait]
 pub trait HttpClient: Send + Sync {
     async fn get(&self, request: Request) -> ApiResult<Response>;
     async fn post(&self, request: Request) -> ApiResult<Response>;
     async fn delete(&self, request: Request) -> ApiResult<Response>;
+    /// Fails without reading the rest of the body once it exceeds `max_size` bytes
+    async fn get_binary(&self, request: Request, max_size: usize) -> ApiResult<BinaryResponse>;
 }
 
 #[rocket::async_trait]
 impl HttpClient for reqwest::Client {
     async fn get(&self, request: Request) -> ApiResult<Response> {
-        let response = self
-            .get(&request.url)
-            .timeout(request.timeout)
-            .send()
-            .await?;
-        Response::from(response).await
+        let response =
+            with_upstream_headers(self.get(&request.url), &request.url, &request.trace_id)
+                .timeout(effective_timeout(request.timeout))
+                .send()
+                .await?;
+        Response::from(response, &request.url).await
     }
 
     async fn post(&self, request: Request) -> ApiResult<Response> {
+        let content_type = content_type_of(&request.content_type);
         let body = request.body.unwrap_or(String::from(""));
-        let response = self
-            .post(&request.url)
-            .header(CONTENT_TYPE, "application/json")
-            .body(body)
-            .timeout(request.timeout)
-            .send()
-            .await?;
-        Response::from(response).await
+        let response =
+            with_upstream_headers(self.post(&request.url), &request.url, &request.trace_id)
+                .header(CONTENT_TYPE, content_type)
+                .body(body)
+                .timeout(effective_timeout(request.timeout))
+                .send()
+                .await?;
+        Response::from(response, &request.url).await
     }
 
     async fn delete(&self, request: Request) -> ApiResult<Response> {
+        let content_type = content_type_of(&request.content_type);
         let body = request.body.unwrap_or(String::from(""));
-        let response = self
-            .delete(&request.url)
-            .header(CONTENT_TYPE, "application/json")
-            .body(body)
-            .timeout(request.timeout)
-            .send()
-            .await?;
-        Response::from(response).await
+        let response =
+            with_upstream_headers(self.delete(&request.url), &request.url, &request.trace_id)
+                .header(CONTENT_TYPE, content_type)
+                .body(body)
+                .timeout(effective_timeout(request.timeout))
+                .send()
+                .await?;
+        Response::from(response, &request.url).await
+    }
+
+    async fn get_binary(&self, request: Request, max_size: usize) -> ApiResult<BinaryResponse> {
+        let mut response =
+            with_upstream_headers(self.get(&request.url), &request.url, &request.trace_id)
+                .timeout(effective_timeout(request.timeout))
+                .send()
+                .await?;
+        let status = response.status();
+        if status.is_client_error() || status.is_server_error() {
+            let body = response.text().await?;
+            return Err(ApiError::new_from_message_with_code(status.as_u16(), body));
+        }
+        let too_large = || {
+            ApiError::new_from_message_with_code(
+                502,
+                format!("Response of {} exceeds {} bytes", &request.url, max_size),
+            )
+        };
+        if response
+            .content_length()
+            .map_or(false, |length| length > max_size as u64)
+        {
+            return Err(too_large());
+        }
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(String::from);
+        let mut body = vec![];
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > max_size {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(BinaryResponse { content_type, body })
+    }
+}
+
+/// Routes requests through a dedicated client for hosts with custom TLS settings
+/// (CA bundle, client certificate), all other hosts use the default client.
+/// Requests to hosts outside of the [outbound] safelist are rejected.
+pub struct UpstreamClient {
+    default_client: Client,
+    host_clients: HashMap<String, Client>,
+}
+
+impl UpstreamClient {
+    pub fn new(connect_timeout: Duration) -> Self {
+        let host_clients = upstream_tls()
+            .into_iter()
+            .map(|(host, tls)| {
+                let client = build_tls_client(connect_timeout, &tls).unwrap_or_else(|error| {
+                    panic!("Invalid TLS configuration for {}: {}", host, error)
+                });
+                (host, client)
+            })
+            .collect();
+        UpstreamClient {
+            default_client: Client::builder()
+                .connect_timeout(connect_timeout)
+                .build()
+                .unwrap(),
+            host_clients,
+        }
+    }
+
+    fn client_for(&self, url: &str) -> &Client {
+        for_url(&self.host_clients, url).unwrap_or(&self.default_client)
+    }
+}
+
+#[rocket::async_trait]
+impl HttpClient for UpstreamClient {
+    async fn get(&self, request: Request) -> ApiResult<Response> {
+        outbound::check_url(&request.url)?;
+        let shadow_request = shadowing::shadow_url(&request.url).map(|url| Request {
+            url,
+            body: None,
+            timeout: request.timeout,
+            trace_id: request.trace_id.clone(),
+            content_type: None,
+        });
+        let url = request.url.to_string();
+        let result = HttpClient::get(self.client_for(&url), request).await;
+        if let Some(shadow_request) = shadow_request {
+            // Mirrored in the background, so that the canary doesn't slow down the response
+            let client = self.client_for(&shadow_request.url).clone();
+            let status = shadowing::status_of(&result);
+            rocket::tokio::spawn(async move {
+                let shadow_url = shadow_request.url.to_string();
+                let shadow_result = HttpClient::get(&client, shadow_request).await;
+                shadowing::compare(
+                    &url,
+                    status,
+                    &shadow_url,
+                    shadowing::status_of(&shadow_result),
+                );
+            });
+        }
+        result
+    }
+
+    async fn post(&self, request: Request) -> ApiResult<Response> {
+        outbound::check_url(&request.url)?;
+        HttpClient::post(self.client_for(&request.url), request).await
+    }
+
+    async fn delete(&self, request: Request) -> ApiResult<Response> {
+        outbound::check_url(&request.url)?;
+        HttpClient::delete(self.client_for(&request.url), request).await
+    }
+
+    async fn get_binary(&self, request: Request, max_size: usize) -> ApiResult<BinaryResponse> {
+        outbound::check_url(&request.url)?;
+        HttpClient::get_binary(self.client_for(&request.url), request, max_size).await
+    }
+}
+
+fn content_type_of(content_type: &Option<String>) -> String {
+    content_type
+        .clone()
+        .unwrap_or_else(|| String::from("application/json"))
+}
+
+fn effective_timeout(timeout: Option<Duration>) -> Duration {
+    timeout.unwrap_or_else(|| Duration::from_millis(default_request_timeout()))
+}
+
+fn build_tls_client(connect_timeout: Duration, tls: &UpstreamTls) -> ApiResult<Client> {
+    let mut builder = Client::builder().connect_timeout(connect_timeout);
+    if let Some(ca_bundle) = tls.ca_bundle.as_ref() {
+        let pem = fs::read(ca_bundle)
+            .map_err(|error| api_error!("Could not read {}: {}", ca_bundle, error))?;
+        builder = builder.add_root_certificate(Certificate::from_pem(&pem)?);
     }
+    if let Some(client_identity) = tls.client_identity.as_ref() {
+        let der = fs::read(client_identity)
+            .map_err(|error| api_error!("Could not read {}: {}", client_identity, error))?;
+        builder = builder.identity(Identity::from_pkcs12_der(
+            &der,
+            &tls.client_identity_password,
+        )?);
+    }
+    Ok(builder.build()?)
+}
+
+fn with_upstream_headers(
+    request_builder: RequestBuilder,
+    url: &str,
+    trace_id: &Option<String>,
+) -> RequestBuilder {
+    let request_builder = match trace_id {
+        Some(trace_id) => request_builder.header(TRACE_ID_HEADER, trace_id.as_str()),
+        None => request_builder,
+    };
+    match headers_for_url(&UPSTREAM_HEADERS, url) {
+        Some(headers) => headers
+            .iter()
+            .fold(request_builder, |builder, (name, value)| {
+                builder.header(name.as_str(), value.as_str())
+            }),
+        None => request_builder,
+    }
+}
+
+pub(super) fn headers_for_url<'h>(
+    upstream_headers: &'h UpstreamHeaders,
+    url: &str,
+) -> Option<&'h HashMap<String, String>> {
+    for_url(upstream_headers, url)
+}
+
+/// Per host settings are matched by `host:port` first, then by `host`
+pub(super) fn for_url<'v, V>(per_host: &'v HashMap<String, V>, url: &str) -> Option<&'v V> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    url.port()
+        .and_then(|port| per_host.get(&format!("{}:{}", host, port)))
+        .or_else(|| per_host.get(host))
 }
@@ -0,0 +1,90 @@
+//! Fetches the token logos into the asset cache in the background whenever the token list of a
+//! chain was loaded, so that the first balances rendered after it don't send every client to the
+//! hosts of the logos. Logos are served from the cache by
+//! `/v1/chains/<chain_id>/assets/tokens/<token_address>`.
+//!
+//! At most `LOGO_PREFETCH_LIMIT` logos are prefetched per list, `LOGO_PREFETCH_CONCURRENCY` at a
+//! time. Logos that are cached already are skipped, failed fetches are only logged.
+use crate::cache::Cache;
+use crate::config::{logo_prefetch_concurrency, logo_prefetch_limit};
+use crate::routes::chains::handlers::{generate_asset_key, load_asset};
+use crate::utils::http_client::HttpClient;
+use rocket::futures::{stream, StreamExt};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PrefetchedLogos {
+    pub fetched: usize,
+    pub cached: usize,
+    pub failed: usize,
+}
+
+/// Prefetches `logo_uris` in the background, if enabled
+pub fn schedule(http_client: Arc<dyn HttpClient>, cache: Arc<dyn Cache>, logo_uris: Vec<String>) {
+    let concurrency = logo_prefetch_concurrency();
+    if concurrency == 0 || logo_uris.is_empty() {
+        return;
+    }
+    rocket::tokio::spawn(async move {
+        let prefetched = prefetch_logos(
+            http_client.as_ref(),
+            cache.as_ref(),
+            logo_uris,
+            logo_prefetch_limit(),
+            concurrency,
+        )
+        .await;
+        log::debug!("Token logos prefetched: {:?}", prefetched);
+    });
+}
+
+/// Distinct, non empty uris of `logo_uris`, at most `limit` of them in their original order
+pub fn logos_to_prefetch(logo_uris: Vec<String>, limit: usize) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    logo_uris
+        .into_iter()
+        .filter(|uri| !uri.is_empty() && seen.insert(uri.to_string()))
+        .take(limit)
+        .collect()
+}
+
+pub async fn prefetch_logos(
+    http_client: &dyn HttpClient,
+    cache: &dyn Cache,
+    logo_uris: Vec<String>,
+    limit: usize,
+    concurrency: usize,
+) -> PrefetchedLogos {
+    let mut prefetched = PrefetchedLogos::default();
+    let mut missing = vec![];
+    for uri in logos_to_prefetch(logo_uris, limit) {
+        if cache.has_key(&generate_asset_key(&uri)).await {
+            prefetched.cached += 1;
+        } else {
+            missing.push(uri);
+        }
+    }
+
+    let results: Vec<bool> = stream::iter(missing)
+        .map(|uri| async move {
+            match load_asset(http_client, cache, &uri).await {
+                Ok(_) => true,
+                Err(error) => {
+                    log::debug!("Token logo {} could not be prefetched: {}", uri, error);
+                    false
+                }
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+    for fetched in results {
+        if fetched {
+            prefetched.fetched += 1;
+        } else {
+            prefetched.failed += 1;
+        }
+    }
+    prefetched
+}
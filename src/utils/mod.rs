@@ -3,13 +3,39 @@ use crate::common::models::data_decoded::{DataDecoded, ParamValue, ValueDecodedT
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
+pub mod auth;
+pub mod cache_control;
+pub mod call_budget;
+pub mod capabilities;
+pub mod chain_hosts;
+pub mod chain_warm_up;
+pub mod checksum;
 pub mod context;
 pub mod cors;
+pub mod data_policies;
+pub mod device;
 pub mod errors;
+pub mod hook_events;
 pub mod http_client;
 pub mod json;
+pub mod logo_prefetch;
+pub mod outbound;
+pub mod payload_limits;
+pub mod read_only;
+pub mod response_budget;
+pub mod retry_queue;
+pub mod safe_version;
+pub mod serialization;
+pub mod shadowing;
+pub mod signatures;
+pub mod spam;
+pub mod trace_id;
+pub mod transaction_id;
 pub mod transactions;
+pub mod upstream_queue;
+pub mod upstream_versions;
 pub mod urls;
+pub mod validation;
 
 #[cfg(test)]
 mod tests;
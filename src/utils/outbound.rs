@@ -0,0 +1,96 @@
+//! Safelist of the hosts the gateway sends requests to, so that a compromised or malformed
+//! configuration can't make it request arbitrary (e.g. internal) urls.
+use crate::common::models::backend::chains::ChainInfo;
+use crate::config::{
+    address_reputation_uri, analytics_sink_uri, config_service_uri, exchange_api_base_uri,
+    hook_events_uri, nft_ipfs_gateway, outbound_allowed_hosts, outbound_url_validation,
+    relay_service_uri, shadow_upstreams, slo_alert_webhook_uri, static_token_list_uri,
+    transaction_service_fallback_uris, upstream_headers, upstream_tls,
+};
+use crate::utils::errors::ApiResult;
+use lazy_static::lazy_static;
+use reqwest::Url;
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+lazy_static! {
+    static ref ALLOWED_HOSTS: RwLock<HashSet<String>> = RwLock::new(configured_hosts());
+}
+
+/// Fails if `url` targets a host that is not in the safelist
+pub fn check_url(url: &str) -> ApiResult<()> {
+    if !outbound_url_validation() || is_allowed(&ALLOWED_HOSTS.read().unwrap(), url) {
+        Ok(())
+    } else {
+        log::warn!("Rejected outbound request to {}", url);
+        Err(api_error!("Outbound request to {} is not allowed", url))
+    }
+}
+
+/// Adds the host of `url` to the safelist
+pub fn allow_url(url: &str) {
+    if let Some(host) = host_of(url) {
+        let is_known = ALLOWED_HOSTS.read().unwrap().contains(&host);
+        if !is_known {
+            ALLOWED_HOSTS.write().unwrap().insert(host);
+        }
+    }
+}
+
+/// Adds the hosts of the chain's services provided by the config service to the safelist
+pub fn allow_chain(chain_info: &ChainInfo) {
+    for url in chain_urls(chain_info) {
+        allow_url(url);
+    }
+}
+
+pub fn chain_urls(chain_info: &ChainInfo) -> Vec<&str> {
+    let mut urls = vec![
+        chain_info.transaction_service.as_str(),
+        chain_info.vpc_transaction_service.as_str(),
+        chain_info.rpc_uri.value.as_str(),
+    ];
+    urls.extend(chain_info.transaction_service_fallback_uri.as_deref());
+    urls.extend(chain_info.vpc_transaction_service_fallback_uri.as_deref());
+    urls
+}
+
+pub fn is_allowed(allowed_hosts: &HashSet<String>, url: &str) -> bool {
+    host_of(url).map_or(false, |host| allowed_hosts.contains(&host))
+}
+
+fn host_of(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(|host| host.to_lowercase())
+}
+
+fn configured_hosts() -> HashSet<String> {
+    let mut urls = vec![config_service_uri(), exchange_api_base_uri()];
+    urls.extend(relay_service_uri());
+    urls.extend(analytics_sink_uri());
+    urls.extend(hook_events_uri());
+    urls.extend(slo_alert_webhook_uri());
+    urls.extend(static_token_list_uri());
+    urls.extend(address_reputation_uri());
+    urls.push(nft_ipfs_gateway());
+    urls.extend(
+        transaction_service_fallback_uris()
+            .into_iter()
+            .map(|(_, uri)| uri),
+    );
+    urls.extend(shadow_upstreams().into_iter().map(|(_, uri)| uri));
+
+    let mut hosts: HashSet<String> = urls.iter().filter_map(|url| host_of(url)).collect();
+    // Per host settings are keyed by `host` or `host:port`
+    let upstream_hosts = upstream_headers()
+        .into_iter()
+        .map(|(host, _)| host)
+        .chain(upstream_tls().into_iter().map(|(host, _)| host));
+    hosts.extend(
+        upstream_hosts.filter_map(|host| host.split(':').next().map(|host| host.to_lowercase())),
+    );
+    hosts.extend(outbound_allowed_hosts());
+    hosts
+}
@@ -0,0 +1,111 @@
+//! Bounds the payloads the gateway reads, so that pathological bodies can't exhaust its memory:
+//! client JSON bodies up to `MAX_REQUEST_BODY_SIZE` (413 otherwise), upstream responses up to
+//! `UPSTREAM_MAX_RESPONSE_SIZE` and nested up to `UPSTREAM_MAX_JSON_DEPTH` (502 otherwise). Both
+//! upstream limits are checked before the response is parsed. Rejections are counted and exposed
+//! via `/about/payload-limits/<token>`.
+use crate::config::{max_request_body_size, upstream_max_json_depth, upstream_max_response_size};
+use crate::utils::errors::{ApiError, ApiResult};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+lazy_static! {
+    static ref REJECTED_REQUEST_BODIES: AtomicU64 = AtomicU64::new(0);
+    static ref OVERSIZED_RESPONSES: AtomicU64 = AtomicU64::new(0);
+    static ref TOO_DEEP_RESPONSES: AtomicU64 = AtomicU64::new(0);
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadLimitStats {
+    pub max_request_body_size: usize,
+    pub upstream_max_response_size: usize,
+    pub upstream_max_json_depth: usize,
+    /// Rejections since the instance started
+    pub rejected_request_bodies: u64,
+    pub oversized_responses: u64,
+    pub too_deep_responses: u64,
+}
+
+pub fn stats() -> PayloadLimitStats {
+    PayloadLimitStats {
+        max_request_body_size: max_request_body_size(),
+        upstream_max_response_size: upstream_max_response_size(),
+        upstream_max_json_depth: upstream_max_json_depth(),
+        rejected_request_bodies: REJECTED_REQUEST_BODIES.load(Ordering::Relaxed),
+        oversized_responses: OVERSIZED_RESPONSES.load(Ordering::Relaxed),
+        too_deep_responses: TOO_DEEP_RESPONSES.load(Ordering::Relaxed),
+    }
+}
+
+/// For client bodies cut off at `MAX_REQUEST_BODY_SIZE`
+pub fn request_body_too_large() -> ApiError {
+    ApiError::new_from_message_with_code(413, reject_request_body())
+}
+
+/// Counts the rejection, returns the message for the client
+pub fn reject_request_body() -> String {
+    REJECTED_REQUEST_BODIES.fetch_add(1, Ordering::Relaxed);
+    format!("Request body exceeds {} bytes", max_request_body_size())
+}
+
+/// For upstream responses cut off at `UPSTREAM_MAX_RESPONSE_SIZE`
+pub fn response_too_large(url: &str) -> ApiError {
+    OVERSIZED_RESPONSES.fetch_add(1, Ordering::Relaxed);
+    log::warn!(
+        "Response of {} exceeds {} bytes",
+        url,
+        upstream_max_response_size()
+    );
+    ApiError::new_from_message_with_code(
+        502,
+        format!(
+            "Upstream response exceeds {} bytes",
+            upstream_max_response_size()
+        ),
+    )
+}
+
+/// Fails with a 502 when `body` nests arrays and objects deeper than `UPSTREAM_MAX_JSON_DEPTH`
+pub fn check_response_depth(url: &str, body: &str) -> ApiResult<()> {
+    let max_depth = upstream_max_json_depth();
+    if !exceeds_depth(body, max_depth) {
+        return Ok(());
+    }
+    TOO_DEEP_RESPONSES.fetch_add(1, Ordering::Relaxed);
+    log::warn!("Response of {} is nested deeper than {}", url, max_depth);
+    Err(ApiError::new_from_message_with_code(
+        502,
+        format!("Upstream response is nested deeper than {}", max_depth),
+    ))
+}
+
+/// Scans `body` without parsing it, brackets inside strings don't count
+pub fn exceeds_depth(body: &str, max_depth: usize) -> bool {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for byte in body.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                if depth > max_depth {
+                    return true;
+                }
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    false
+}
@@ -0,0 +1,64 @@
+//! Maintenance switch answering every write route (proposals, confirmations, delegates,
+//! notification registrations, labels, relays) with a 503 while reads keep being served, e.g.
+//! during maintenance windows of the transaction service. Starts from `READ_ONLY_MODE` and can
+//! be switched at runtime via `/admin/read-only/<token>`, which applies to every instance sharing
+//! the cache. Hooks keep invalidating the caches.
+use crate::cache::Cache;
+use crate::config::read_only_mode;
+use crate::utils::context::RequestContext;
+use crate::utils::errors::{ApiError, ApiResult};
+use serde::{Deserialize, Serialize};
+
+// Stored without expiry, so that the switch outlives cache flushes
+const READ_ONLY_KEY: &str = "read_only";
+const READ_ONLY_FIELD: &str = "mode";
+const DEFAULT_MESSAGE: &str = "The gateway is in read-only mode for maintenance, retry later";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyMode {
+    pub enabled: bool,
+    /// Returned to clients instead of the default maintenance message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The mode set via the admin route, `READ_ONLY_MODE` if it was never switched
+pub async fn current(cache: &dyn Cache) -> ReadOnlyMode {
+    cache
+        .get_from_hash(READ_ONLY_KEY, READ_ONLY_FIELD)
+        .await
+        .and_then(|mode| serde_json::from_str(&mode).ok())
+        .unwrap_or(ReadOnlyMode {
+            enabled: read_only_mode(),
+            message: None,
+        })
+}
+
+pub async fn set(cache: &dyn Cache, mode: &ReadOnlyMode) -> ApiResult<ReadOnlyMode> {
+    cache
+        .insert_in_hash(
+            READ_ONLY_KEY,
+            READ_ONLY_FIELD,
+            &serde_json::to_string(mode)?,
+        )
+        .await;
+    Ok(mode.clone())
+}
+
+/// Fails with a 503 while the gateway is read-only, called first by every write route
+pub async fn ensure_writable(context: &RequestContext) -> ApiResult<()> {
+    check_writable(context.cache().as_ref()).await
+}
+
+pub async fn check_writable(cache: &dyn Cache) -> ApiResult<()> {
+    let mode = current(cache).await;
+    if !mode.enabled {
+        return Ok(());
+    }
+    Err(ApiError::new_from_message_with_code(
+        503,
+        mode.message
+            .unwrap_or_else(|| String::from(DEFAULT_MESSAGE)),
+    ))
+}
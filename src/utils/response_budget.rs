@@ -0,0 +1,154 @@
+//! Response size budgets, so that low-bandwidth clients aren't sent multi-megabyte payloads. Json
+//! responses larger than the budget of their route (`RESPONSE_SIZE_BUDGETS`, or
+//! `RESPONSE_SIZE_BUDGET` for the other routes) are trimmed when the
+//! [CacheResponse](crate::cache::cache_operations::CacheResponse) builds them, dropping the heavy
+//! optional fields stage by stage until the response fits:
+//!
+//! 1. `logoUri`
+//! 2. `hexData` longer than `RESPONSE_HEX_DATA_LIMIT` characters, set to `null`
+//! 3. the `parameters` of `dataDecoded`, keeping the decoded `method`
+//!
+//! Trimmed responses are flagged with `"truncated": true`, clients request the full data from
+//! the routes of the single items. Responses that don't fit after the last stage are served
+//! trimmed. The trimmed response is what gets cached, so cache hits are served as they are.
+use crate::config::{response_hex_data_limit, response_size_budget, response_size_budgets};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use lazy_static::lazy_static;
+use rocket::response::content;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+pub const TRUNCATED_KEY: &str = "truncated";
+
+lazy_static! {
+    static ref ROUTE_BUDGETS: HashMap<String, usize> = response_size_budgets();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrimStage {
+    LogoUris,
+    HexData(usize),
+    DecodedParameters,
+}
+
+impl TrimStage {
+    pub fn stages(hex_data_limit: usize) -> [TrimStage; 3] {
+        [
+            TrimStage::LogoUris,
+            TrimStage::HexData(hex_data_limit),
+            TrimStage::DecodedParameters,
+        ]
+    }
+
+    pub fn apply(&self, value: &mut Value) {
+        visit_objects(value, &mut |object| match self {
+            TrimStage::LogoUris => {
+                object.remove("logoUri");
+            }
+            TrimStage::HexData(limit) => {
+                if let Some(hex_data) = object.get_mut("hexData") {
+                    if hex_data.as_str().map_or(false, |data| data.len() > *limit) {
+                        *hex_data = Value::Null;
+                    }
+                }
+            }
+            TrimStage::DecodedParameters => {
+                if let Some(Value::Object(data_decoded)) = object.get_mut("dataDecoded") {
+                    data_decoded.remove("parameters");
+                }
+            }
+        });
+    }
+}
+
+/// Budget (in bytes) of `route`, a route template with or without its query. `None` if the route
+/// has no budget.
+pub fn budget_for(budgets: &HashMap<String, usize>, default: usize, route: &str) -> Option<usize> {
+    let path = route.split('?').next().unwrap_or(route);
+    let budget = budgets.get(path).copied().unwrap_or(default);
+    if budget == 0 {
+        None
+    } else {
+        Some(budget)
+    }
+}
+
+/// Budget (in bytes) of the responses of `route`, a route template, `None` if it has no budget
+pub fn route_budget(route: &str) -> Option<usize> {
+    budget_for(&ROUTE_BUDGETS, response_size_budget(), route)
+}
+
+/// `response` serialized, trimmed to `budget` if it exceeds it. The trim stages are applied to
+/// the json value of `response` rather than to a parsed body, and only once it is too large.
+/// Responses that are not json objects are not trimmed.
+pub fn to_string_within_budget<R>(
+    response: &R,
+    budget: Option<usize>,
+    hex_data_limit: usize,
+) -> serde_json::Result<String>
+where
+    R: Serialize,
+{
+    let serialized = serde_json::to_string(response)?;
+    let budget = match budget {
+        Some(budget) if serialized.len() > budget => budget,
+        _ => return Ok(serialized),
+    };
+    let mut value = match serde_json::to_value(response)? {
+        value @ Value::Object(_) => value,
+        _ => return Ok(serialized),
+    };
+    if let Value::Object(object) = &mut value {
+        object.insert(TRUNCATED_KEY.to_string(), Value::Bool(true));
+    }
+    let mut trimmed = String::new();
+    for stage in TrimStage::stages(hex_data_limit).iter() {
+        stage.apply(&mut value);
+        trimmed = serde_json::to_string(&value)?;
+        if trimmed.len() <= budget {
+            break;
+        }
+    }
+    log::debug!(
+        "Response trimmed from {} to {} bytes",
+        serialized.len(),
+        trimmed.len()
+    );
+    Ok(trimmed)
+}
+
+/// `response` as the body of the request of `context`, trimmed to its budget, for the handlers
+/// that don't build their response with a
+/// [CacheResponse](crate::cache::cache_operations::CacheResponse)
+pub fn json_within_budget<R>(
+    context: &RequestContext,
+    response: &R,
+) -> ApiResult<content::Json<String>>
+where
+    R: Serialize,
+{
+    Ok(content::Json(to_string_within_budget(
+        response,
+        context.response_budget(),
+        response_hex_data_limit(),
+    )?))
+}
+
+fn visit_objects(value: &mut Value, visit: &mut dyn FnMut(&mut Map<String, Value>)) {
+    match value {
+        Value::Object(object) => {
+            visit(object);
+            for value in object.values_mut() {
+                visit_objects(value, visit);
+            }
+        }
+        Value::Array(values) => {
+            for value in values.iter_mut() {
+                visit_objects(value, visit);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,264 @@
+//! Writes forwarded to upstream services that fail with a server error, for the endpoints listed
+//! in `RETRY_QUEUE_ENDPOINTS`, are stored in the cache and answered with a 202 instead of
+//! failing, e.g. notification registrations submitted while the transaction service is down.
+//! The [RetryQueueWorker] of every instance retries them with an exponential backoff, an instance
+//! claims a write before sending it so that it is not sent twice.
+use crate::cache::Cache;
+use crate::config::{retry_queue_endpoints, retry_queue_interval, retry_queue_max_attempts};
+use crate::utils::errors::{ApiError, ApiResult};
+use crate::utils::http_client::{HttpClient, Request};
+use chrono::Utc;
+use ethcontract_common::hash::keccak256;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Orbit, Rocket};
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const NOTIFICATION_REGISTRATION: &str = "notification_registration";
+pub const NOTIFICATION_UNREGISTRATION: &str = "notification_unregistration";
+pub const ENDPOINTS: [&str; 2] = [NOTIFICATION_REGISTRATION, NOTIFICATION_UNREGISTRATION];
+
+const QUEUED_WRITE_KEY_BASE: &str = "retry_queue";
+const CLAIM_KEY_BASE: &str = "retry_claim";
+const CLAIM_FIELD: &str = "claims";
+// Writes left behind (e.g. after the endpoint was removed from RETRY_QUEUE_ENDPOINTS) expire
+const QUEUED_WRITE_DURATION: usize = 7 * 24 * 60 * 60 * 1000;
+// Caps the delay at 1024 times RETRY_QUEUE_INTERVAL
+const MAX_BACKOFF_EXPONENT: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WriteMethod {
+    Post,
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedWrite {
+    pub id: String,
+    pub endpoint: String,
+    pub method: WriteMethod,
+    pub url: String,
+    pub body: Option<String>,
+    /// Attempts made so far, including the one of the client request
+    pub attempts: usize,
+    /// In ms
+    pub next_attempt: i64,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RetryRound {
+    pub sent: usize,
+    pub rescheduled: usize,
+    pub dropped: usize,
+}
+
+pub fn is_enabled(endpoint: &str) -> bool {
+    retry_queue_endpoints()
+        .iter()
+        .any(|enabled| enabled == endpoint)
+}
+
+/// Client errors would fail the same way when retried
+pub fn is_retryable(error: &ApiError) -> bool {
+    error.status >= 500
+}
+
+/// `true` if the write failed with `error` was queued for a retry, i.e. `endpoint` is enabled and
+/// the error is retryable
+pub async fn queue_failed(
+    cache: &dyn Cache,
+    endpoint: &str,
+    method: WriteMethod,
+    url: &str,
+    body: Option<String>,
+    error: &ApiError,
+) -> bool {
+    if !is_enabled(endpoint) || !is_retryable(error) {
+        return false;
+    }
+    let write = queued_write(
+        endpoint,
+        method,
+        url,
+        body,
+        Utc::now().timestamp_millis(),
+        retry_queue_interval(),
+    );
+    log::warn!(
+        "Queued {} write to {} for a retry: {}",
+        endpoint,
+        url,
+        error
+    );
+    store(cache, &write).await;
+    true
+}
+
+/// Identical writes share their id, queuing the same write again replaces it
+pub fn queued_write(
+    endpoint: &str,
+    method: WriteMethod,
+    url: &str,
+    body: Option<String>,
+    now: i64,
+    interval: u64,
+) -> QueuedWrite {
+    let id = to_hex_string!(keccak256(
+        format!("{:?} {} {}", method, url, body.as_deref().unwrap_or("")).as_bytes()
+    ));
+    QueuedWrite {
+        id,
+        endpoint: endpoint.to_string(),
+        method,
+        url: url.to_string(),
+        body,
+        attempts: 1,
+        next_attempt: now + retry_delay(interval, 1) as i64,
+    }
+}
+
+/// Delay (in ms) after the `attempts`th failed attempt, doubling from `interval` on
+pub fn retry_delay(interval: u64, attempts: usize) -> u64 {
+    let exponent = min(attempts.saturating_sub(1), MAX_BACKOFF_EXPONENT);
+    interval.saturating_mul(1 << exponent)
+}
+
+/// Writes waiting for a retry, in no particular order
+pub async fn queued_writes(cache: &dyn Cache) -> Vec<QueuedWrite> {
+    let mut writes = vec![];
+    for key in cache.keys(&format!("{}_*", QUEUED_WRITE_KEY_BASE)).await {
+        if let Some(write) = cache.fetch(&key).await {
+            writes.extend(serde_json::from_str(&write).ok());
+        }
+    }
+    writes
+}
+
+/// Sends every write due at `now` once
+pub async fn retry_due(
+    cache: &dyn Cache,
+    http_client: &dyn HttpClient,
+    now: i64,
+    interval: u64,
+    max_attempts: usize,
+) -> RetryRound {
+    let mut round = RetryRound::default();
+    for mut write in queued_writes(cache).await {
+        if write.next_attempt > now || !claim(cache, &write.id, interval).await {
+            continue;
+        }
+        match send(http_client, &write).await {
+            Ok(_) => {
+                cache.invalidate(&key(&write.id)).await;
+                round.sent += 1;
+            }
+            Err(error) if is_retryable(&error) && write.attempts + 1 < max_attempts => {
+                write.attempts += 1;
+                write.next_attempt = now + retry_delay(interval, write.attempts) as i64;
+                store(cache, &write).await;
+                round.rescheduled += 1;
+            }
+            Err(error) => {
+                log::warn!(
+                    "Dropped queued {} write to {} after {} attempts: {}",
+                    write.endpoint,
+                    write.url,
+                    write.attempts + 1,
+                    error
+                );
+                cache.invalidate(&key(&write.id)).await;
+                round.dropped += 1;
+            }
+        }
+    }
+    round
+}
+
+async fn send(http_client: &dyn HttpClient, write: &QueuedWrite) -> ApiResult<()> {
+    let mut request = Request::new(write.url.to_string());
+    request.body(write.body.clone());
+    match write.method {
+        WriteMethod::Post => http_client.post(request).await?,
+        WriteMethod::Delete => http_client.delete(request).await?,
+    };
+    Ok(())
+}
+
+// Only the first instance incrementing the claim within the interval sends the write
+async fn claim(cache: &dyn Cache, id: &str, interval: u64) -> bool {
+    cache
+        .increment_in_hash(
+            &format!("{}_{}", CLAIM_KEY_BASE, id),
+            CLAIM_FIELD,
+            interval as usize,
+        )
+        .await
+        == 1
+}
+
+async fn store(cache: &dyn Cache, write: &QueuedWrite) {
+    match serde_json::to_string(write) {
+        Ok(value) => {
+            cache
+                .create(&key(&write.id), &value, QUEUED_WRITE_DURATION)
+                .await
+        }
+        Err(error) => log::error!("Could not queue {} write: {}", write.endpoint, error),
+    }
+}
+
+fn key(id: &str) -> String {
+    format!("{}_{}", QUEUED_WRITE_KEY_BASE, id)
+}
+
+/// Retries the queued writes every `RETRY_QUEUE_INTERVAL`, also those queued before a restart
+pub struct RetryQueueWorker();
+
+#[rocket::async_trait]
+impl Fairing for RetryQueueWorker {
+    fn info(&self) -> Info {
+        Info {
+            name: "RetryQueueWorker",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        if retry_queue_endpoints().is_empty() {
+            return;
+        }
+        let (cache, http_client) = match (
+            rocket.state::<Arc<dyn Cache>>(),
+            rocket.state::<Arc<dyn HttpClient>>(),
+        ) {
+            (Some(cache), Some(http_client)) => (cache.clone(), http_client.clone()),
+            _ => return,
+        };
+        rocket::tokio::spawn(async move {
+            loop {
+                let interval = retry_queue_interval();
+                rocket::tokio::time::sleep(Duration::from_millis(interval)).await;
+                let round = retry_due(
+                    cache.as_ref(),
+                    http_client.as_ref(),
+                    Utc::now().timestamp_millis(),
+                    interval,
+                    retry_queue_max_attempts(),
+                )
+                .await;
+                if round != RetryRound::default() {
+                    log::info!(
+                        "Retry queue: {} sent, {} rescheduled, {} dropped",
+                        round.sent,
+                        round.rescheduled,
+                        round.dropped
+                    );
+                }
+            }
+        });
+    }
+}
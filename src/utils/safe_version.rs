@@ -0,0 +1,96 @@
+//! Features of the Safe contracts that depend on the version of the deployed master copy
+use crate::providers::info::SAFE_V_1_3_0;
+use lazy_static::lazy_static;
+use semver::{BuildMetadata, Version};
+use serde::Serialize;
+
+// Build metadata of the master copies emitting events for indexing on L2 chains, e.g. `1.3.0+L2`
+const L2_BUILD_METADATA: &str = "L2";
+
+lazy_static! {
+    static ref SAFE_V_1_0_0: Version = Version::new(1, 0, 0);
+    static ref SAFE_V_1_1_0: Version = Version::new(1, 1, 0);
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SafeCapability {
+    Modules,
+    FallbackHandler,
+    /// Transaction guards, checking transactions before and after their execution
+    Guard,
+    /// `simulateAndRevert` of `StorageAccessible`
+    Simulate,
+    /// The chain id is part of the EIP-712 domain of the Safe transaction hash
+    ChainIdInDomain,
+}
+
+impl SafeCapability {
+    pub const ALL: [SafeCapability; 5] = [
+        SafeCapability::Modules,
+        SafeCapability::FallbackHandler,
+        SafeCapability::Guard,
+        SafeCapability::Simulate,
+        SafeCapability::ChainIdInDomain,
+    ];
+
+    fn min_version(&self) -> &'static Version {
+        match self {
+            SafeCapability::Modules => &SAFE_V_1_0_0,
+            SafeCapability::FallbackHandler => &SAFE_V_1_1_0,
+            SafeCapability::Guard | SafeCapability::Simulate | SafeCapability::ChainIdInDomain => {
+                &SAFE_V_1_3_0
+            }
+        }
+    }
+}
+
+/// `None` for Safes without a (valid semver) version, e.g. with an unsupported master copy
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafeVersion(Option<Version>);
+
+impl SafeVersion {
+    pub fn parse(version: Option<&String>) -> Self {
+        SafeVersion(version.and_then(|version| parse_base_version(version)))
+    }
+
+    /// Capability known to be supported, `false` for unknown versions
+    pub fn supports(&self, capability: SafeCapability) -> bool {
+        self.0
+            .as_ref()
+            .map_or(false, |version| version >= capability.min_version())
+    }
+
+    /// Whether a version dependent field is emitted: for unknown versions it is, as the
+    /// transaction service still reports it
+    pub fn emits(&self, capability: SafeCapability) -> bool {
+        self.0.is_none() || self.supports(capability)
+    }
+
+    pub fn capabilities(&self) -> Vec<SafeCapability> {
+        SafeCapability::ALL
+            .iter()
+            .copied()
+            .filter(|capability| self.supports(*capability))
+            .collect()
+    }
+}
+
+/// `version` without its build metadata, so that the `+L2` variants compare as the version they
+/// are a variant of (semver orders `1.3.0` before `1.3.0+L2`)
+pub fn parse_base_version(version: &str) -> Option<Version> {
+    let mut version = Version::parse(version).ok()?;
+    version.build = BuildMetadata::EMPTY;
+    Some(version)
+}
+
+/// Whether `version` is the one of an L2 master copy, e.g. `1.3.0+L2`
+pub fn is_l2_version(version: &str) -> bool {
+    Version::parse(version).map_or(false, |version| {
+        version
+            .build
+            .as_str()
+            .split('.')
+            .any(|identifier| identifier.eq_ignore_ascii_case(L2_BUILD_METADATA))
+    })
+}
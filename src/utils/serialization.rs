@@ -0,0 +1,119 @@
+//! Serialization profiles, so that integrators can consume the api with snake_case keys without
+//! mapping them client side. The profile is selected per request with the `serialization` query
+//! parameter or the `X-Serialization-Profile` header, e.g. `?serialization=snake_case`.
+//!
+//! Models (and cached responses) are always serialized as camelCase, the [SerializationProfiles]
+//! fairing rewrites the keys of the json body once it is built.
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Header};
+use rocket::{Request, Response};
+use serde_json::{Map, Value};
+use std::io::Cursor;
+
+pub const SERIALIZATION_PROFILE_HEADER: &str = "X-Serialization-Profile";
+const SERIALIZATION_PROFILE_PARAM: &str = "serialization";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SerializationProfile {
+    CamelCase,
+    SnakeCase,
+}
+
+impl SerializationProfile {
+    /// Unknown profiles fall back to camelCase, the profile every client understands
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "snake_case" => SerializationProfile::SnakeCase,
+            _ => SerializationProfile::CamelCase,
+        }
+    }
+
+    /// The query parameter takes precedence over the header
+    pub fn from_request(request: &Request<'_>) -> Self {
+        request
+            .query_value::<&str>(SERIALIZATION_PROFILE_PARAM)
+            .and_then(Result::ok)
+            .or_else(|| request.headers().get_one(SERIALIZATION_PROFILE_HEADER))
+            .map_or(
+                SerializationProfile::CamelCase,
+                SerializationProfile::from_name,
+            )
+    }
+
+    pub fn apply(&self, value: Value) -> Value {
+        match self {
+            SerializationProfile::CamelCase => value,
+            SerializationProfile::SnakeCase => to_snake_case_keys(value),
+        }
+    }
+}
+
+/// Renames the keys of every object in `value`. Keys that are not camelCase identifiers (e.g.
+/// addresses or chain ids used as keys) are kept as they are.
+pub fn to_snake_case_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (snake_case_key(key), to_snake_case_keys(value)))
+                .collect::<Map<String, Value>>(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(to_snake_case_keys).collect()),
+        value => value,
+    }
+}
+
+fn snake_case_key(key: String) -> String {
+    let is_identifier = key.starts_with(|c: char| c.is_ascii_lowercase())
+        && key.chars().all(|c| c.is_ascii_alphanumeric());
+    if !is_identifier {
+        return key;
+    }
+    let mut snake_case = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            snake_case.push('_');
+            snake_case.push(c.to_ascii_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+    snake_case
+}
+
+/// Applies the [SerializationProfile] of the request to json responses
+pub struct SerializationProfiles();
+
+#[rocket::async_trait]
+impl Fairing for SerializationProfiles {
+    fn info(&self) -> Info {
+        Info {
+            name: "Apply the serialization profile of the request",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if response.content_type() != Some(ContentType::JSON) {
+            return;
+        }
+        // The same uri is served with different bodies depending on the header
+        response.adjoin_header(Header::new("Vary", SERIALIZATION_PROFILE_HEADER));
+        let profile = SerializationProfile::from_request(request);
+        if profile == SerializationProfile::CamelCase {
+            return;
+        }
+        let body = match response.body_mut().to_string().await {
+            Ok(body) => body,
+            Err(error) => {
+                log::error!("Could not read response body: {}", error);
+                return;
+            }
+        };
+        // Bodies that are not json (despite the content type) are served as they are
+        let body = match serde_json::from_str::<Value>(&body) {
+            Ok(value) => serde_json::to_string(&profile.apply(value)).unwrap_or(body),
+            Err(_) => body,
+        };
+        response.set_sized_body(body.len(), Cursor::new(body));
+    }
+}
@@ -0,0 +1,66 @@
+//! Read requests to the upstreams of `SHADOW_UPSTREAMS` mirrored to a canary instance (e.g. the
+//! staging deployment of a new transaction service version), for the share `SHADOW_RATE` of the
+//! requests. Mirrored requests are sent in the background and their responses are discarded,
+//! statuses that differ from the ones of the upstream are logged.
+use crate::config::{shadow_rate, shadow_upstreams};
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::Response;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref SHADOW_UPSTREAMS: HashMap<String, String> = shadow_upstreams();
+    static ref SHADOW_RATE: f32 = shadow_rate();
+}
+
+/// Url `url` is mirrored to, if it is picked for shadowing
+pub fn shadow_url(url: &str) -> Option<String> {
+    if SHADOW_UPSTREAMS.is_empty() || rand::random::<f32>() >= *SHADOW_RATE {
+        return None;
+    }
+    mirrored_url(&SHADOW_UPSTREAMS, url)
+}
+
+/// `url` with the base uri of its upstream replaced by the one of the canary, the longest
+/// matching base uri wins
+pub fn mirrored_url(upstreams: &HashMap<String, String>, url: &str) -> Option<String> {
+    upstreams
+        .iter()
+        .filter_map(|(upstream, canary)| {
+            let upstream = upstream.trim_end_matches('/');
+            let path = url.strip_prefix(upstream)?;
+            // Only full path segments, `https://host` doesn't match `https://host2`
+            if path.is_empty() || path.starts_with('/') || path.starts_with('?') {
+                Some((
+                    upstream.len(),
+                    format!("{}{}", canary.trim_end_matches('/'), path),
+                ))
+            } else {
+                None
+            }
+        })
+        .max_by_key(|(length, _)| *length)
+        .map(|(_, url)| url)
+}
+
+pub fn status_of(result: &ApiResult<Response>) -> u16 {
+    match result {
+        Ok(response) => response.status_code,
+        Err(error) => error.status,
+    }
+}
+
+/// `true` if the statuses diverged, which is logged
+pub fn compare(url: &str, status: u16, shadow_url: &str, shadow_status: u16) -> bool {
+    if status == shadow_status {
+        return false;
+    }
+    log::warn!(
+        "Shadow request diverged: {} answered {}, {} answered {}",
+        url,
+        status,
+        shadow_url,
+        shadow_status
+    );
+    true
+}
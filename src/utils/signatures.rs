@@ -0,0 +1,118 @@
+//! Signatures as the Safe contracts check them: recovers the owner a confirmation is signed by,
+//! from the `r`, `s` and `v` of the signature (`v` selects the signature type, see
+//! `checkNSignatures` in the Safe contracts). The recovery is done with libsecp256k1.
+use crate::utils::checksum::to_checksum_address;
+use ethabi::Uint;
+use ethcontract_common::hash::keccak256;
+use lazy_static::lazy_static;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1, VerifyOnly};
+
+const ETH_SIGN_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n32";
+
+lazy_static! {
+    static ref SECP256K1: Secp256k1<VerifyOnly> = Secp256k1::verification_only();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SafeSignature {
+    /// EIP-712 signature of the `safeTxHash` (`v` 27 or 28)
+    Ecdsa { owner: String },
+    /// `eth_sign` of the `safeTxHash` (`v` 31 or 32)
+    EthSign { owner: String },
+    /// Hash approved on chain via `approveHash` (`v` 1), `r` is the owner
+    ApprovedHash { owner: String },
+    /// EIP-1271 signature (`v` 0), `r` is the owner contract and `s` the offset of its signature
+    Contract { owner: String, signature: Vec<u8> },
+}
+
+impl SafeSignature {
+    pub fn owner(&self) -> &str {
+        match self {
+            SafeSignature::Ecdsa { owner }
+            | SafeSignature::EthSign { owner }
+            | SafeSignature::ApprovedHash { owner }
+            | SafeSignature::Contract { owner, .. } => owner,
+        }
+    }
+}
+
+/// Why a signature has no owner
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureFailure {
+    /// Too short, unknown `v` or a contract signature pointing outside of the signature
+    Malformed,
+    /// `r` and `s` don't recover to a point of the curve
+    NotRecoverable,
+}
+
+/// Signature of the Safe transaction `safe_tx_hash` (32 bytes) and its owner
+pub fn safe_signature(
+    signature: &[u8],
+    safe_tx_hash: &[u8],
+) -> Result<SafeSignature, SignatureFailure> {
+    if signature.len() < 65 || safe_tx_hash.len() != 32 {
+        return Err(SignatureFailure::Malformed);
+    }
+    let s = Uint::from_big_endian(&signature[32..64]);
+    match signature[64] {
+        0 => Ok(SafeSignature::Contract {
+            owner: address_of_word(&signature[..32]),
+            signature: contract_signature(signature, &s).ok_or(SignatureFailure::Malformed)?,
+        }),
+        1 => Ok(SafeSignature::ApprovedHash {
+            owner: address_of_word(&signature[..32]),
+        }),
+        v @ 27..=28 => Ok(SafeSignature::Ecdsa {
+            owner: recover_address(safe_tx_hash, &signature[..64], v - 27)
+                .ok_or(SignatureFailure::NotRecoverable)?,
+        }),
+        v @ 31..=32 => {
+            let mut message = ETH_SIGN_PREFIX.to_vec();
+            message.extend_from_slice(safe_tx_hash);
+            Ok(SafeSignature::EthSign {
+                owner: recover_address(&keccak256(&message), &signature[..64], v - 31)
+                    .ok_or(SignatureFailure::NotRecoverable)?,
+            })
+        }
+        _ => Err(SignatureFailure::Malformed),
+    }
+}
+
+/// Checksummed address of the key that signed `hash`, `None` if `signature` (`r` and `s`) is not
+/// valid
+pub fn recover_address(hash: &[u8], signature: &[u8], recovery_id: u8) -> Option<String> {
+    if recovery_id > 1 {
+        return None;
+    }
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32).ok()?;
+    let signature = RecoverableSignature::from_compact(signature, recovery_id).ok()?;
+    let message = Message::from_slice(hash).ok()?;
+    let public_key = SECP256K1.recover(&message, &signature).ok()?;
+    // Uncompressed encoding without the 0x04 prefix
+    let encoded = public_key.serialize_uncompressed();
+    Some(to_checksum_address(&to_hex_string!(
+        keccak256(&encoded[1..])[12..]
+    )))
+}
+
+/// Dynamic part of a contract signature: the length at offset `s`, followed by the signature
+pub(crate) fn contract_signature(signature: &[u8], offset: &Uint) -> Option<Vec<u8>> {
+    if *offset > Uint::from(signature.len()) {
+        return None;
+    }
+    let offset = offset.low_u64() as usize;
+    if offset < 65 || offset + 32 > signature.len() {
+        return None;
+    }
+    let length = Uint::from_big_endian(&signature[offset..offset + 32]);
+    let start = offset + 32;
+    if length > Uint::from(signature.len() - start) {
+        return None;
+    }
+    Some(signature[start..start + length.low_u64() as usize].to_vec())
+}
+
+fn address_of_word(word: &[u8]) -> String {
+    to_checksum_address(&to_hex_string!(word[12..32]))
+}
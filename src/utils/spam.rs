@@ -0,0 +1,67 @@
+use crate::config::{spam_name_patterns, spam_token_denylist};
+use crate::providers::info::{TokenInfo, TokenType};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::Value;
+
+lazy_static! {
+    static ref SPAM_NAME_REGEX: Option<Regex> = spam_name_regex(&spam_name_patterns());
+}
+
+pub fn is_spam_token(address: &str, name: &str, symbol: &str) -> bool {
+    is_denylisted(address) || has_spam_pattern(name) || has_spam_pattern(symbol)
+}
+
+/// Native tokens are never spam, independent of their balance
+pub fn is_spam_balance(token_info: &TokenInfo) -> bool {
+    token_info.token_type != TokenType::NativeToken
+        && is_spam_token(&token_info.address, &token_info.name, &token_info.symbol)
+}
+
+/// Collectibles are forwarded as received, so they are classified on the raw json
+pub fn is_spam_collectible(collectible: &Value) -> bool {
+    let field = |key: &str| collectible.get(key).and_then(Value::as_str).unwrap_or("");
+    is_spam_token(field("address"), field("tokenName"), field("tokenSymbol"))
+        || has_spam_pattern(field("name"))
+}
+
+/// Case insensitive regex matching any of `patterns` as a whole word: patterns starting or ending
+/// with a letter or digit can't be part of a longer word there (`claim` doesn't match `Reclaim`),
+/// so that `.com` matches `scam.com` but not `Dot.Community`. `None` if there are no patterns.
+pub fn spam_name_regex(patterns: &[String]) -> Option<Regex> {
+    if patterns.is_empty() {
+        return None;
+    }
+    let alternatives: Vec<String> = patterns
+        .iter()
+        .map(|pattern| {
+            let is_word_char = |it: Option<char>| it.map_or(false, char::is_alphanumeric);
+            format!(
+                "{}{}{}",
+                if is_word_char(pattern.chars().next()) {
+                    r"\b"
+                } else {
+                    ""
+                },
+                regex::escape(pattern),
+                if is_word_char(pattern.chars().last()) {
+                    r"\b"
+                } else {
+                    ""
+                },
+            )
+        })
+        .collect();
+    Regex::new(&format!("(?i){}", alternatives.join("|"))).ok()
+}
+
+fn is_denylisted(address: &str) -> bool {
+    let address = address.to_lowercase();
+    spam_token_denylist().iter().any(|it| it == &address)
+}
+
+fn has_spam_pattern(value: &str) -> bool {
+    SPAM_NAME_REGEX
+        .as_ref()
+        .map_or(false, |regex| regex.is_match(value))
+}
@@ -0,0 +1,55 @@
+use crate::utils::auth::{check_key, ApiKey, Scope};
+
+fn keys() -> Vec<ApiKey> {
+    vec![
+        ApiKey {
+            name: String::from("tx-service"),
+            token: String::from("hooks_token"),
+            scopes: vec![Scope::Hooks],
+        },
+        ApiKey {
+            name: String::from("ops"),
+            token: String::from("ops_token"),
+            scopes: vec![Scope::CacheAdmin, Scope::Diagnostics],
+        },
+    ]
+}
+
+#[test]
+fn scope_names_round_trip() {
+    for scope in Scope::ALL.iter() {
+        assert_eq!(Some(*scope), Scope::from_name(scope.name()));
+    }
+    assert_eq!(Some(Scope::CacheAdmin), Scope::from_name("cache-admin"));
+    assert_eq!(None, Scope::from_name("cache_admin"));
+}
+
+#[test]
+fn check_key_accepts_token_with_scope() {
+    let keys = keys();
+
+    let key = check_key(&keys, "ops_token", Scope::Diagnostics).unwrap();
+
+    assert_eq!("ops", key.name);
+    assert!(check_key(&keys, "hooks_token", Scope::Hooks).is_ok());
+}
+
+#[test]
+fn check_key_rejects_token_without_scope() {
+    let keys = keys();
+
+    let error = check_key(&keys, "hooks_token", Scope::CacheAdmin).unwrap_err();
+
+    assert_eq!(Some(String::from("Invalid token")), error.details.message);
+    assert!(check_key(&keys, "ops_token", Scope::Exports).is_err());
+}
+
+#[test]
+fn check_key_rejects_unknown_token() {
+    let keys = keys();
+
+    let error = check_key(&keys, "some_token", Scope::Hooks).unwrap_err();
+
+    assert_eq!(Some(String::from("Invalid token")), error.details.message);
+    assert!(check_key(&[], "", Scope::Hooks).is_err());
+}
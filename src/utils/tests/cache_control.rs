@@ -0,0 +1,215 @@
+use crate::cache::cache_operations::CacheResponse;
+use crate::cache::MockCache;
+use crate::testing::setup::setup_rocket_with_mock_cache;
+use crate::utils::cache_control::{
+    CacheControl, CacheLookup, CacheStatus, DataFreshness, ResponseTtl,
+};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::MockHttpClient;
+use mockall::predicate::eq;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::response::content;
+
+const MASTER_COPIES_URI: &str = "/v1/chains/4/about/master-copies";
+const MASTER_COPIES_CACHE_KEY: &str = "c_resp_/v1/chains/4/about/master-copies";
+
+fn cached_master_copies(times: usize) -> MockCache {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(times)
+        .with(eq(MASTER_COPIES_CACHE_KEY))
+        .return_const(Some(String::from("[]")));
+    mock_cache
+        .expect_ttl()
+        .times(times)
+        .with(eq(MASTER_COPIES_CACHE_KEY))
+        .return_const(Some(30500));
+    mock_cache
+}
+
+#[get("/stale")]
+fn stale_response(context: RequestContext) -> &'static str {
+    context.response_ttl().set(30000);
+    context.data_freshness().mark_stale();
+    "[]"
+}
+
+#[get("/uncached")]
+async fn uncached_response(context: RequestContext) -> ApiResult<content::Json<String>> {
+    CacheResponse::new(&context)
+        .resp_generator(|| async { Ok(vec![String::from("fresh")]) })
+        .execute()
+        .await
+}
+
+async fn client(mock_cache: MockCache) -> Client {
+    let rocket = setup_rocket_with_mock_cache(
+        MockHttpClient::new(),
+        mock_cache,
+        routes![crate::routes::about::routes::get_master_copies],
+    )
+    .attach(CacheControl());
+    Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance")
+}
+
+#[rocket::async_test]
+async fn cached_response_has_cache_headers() {
+    let client = client(cached_master_copies(1)).await;
+
+    let response = {
+        let mut request = client.get(MASTER_COPIES_URI);
+        request.add_header(Header::new("Host", "test.gnosis.io"));
+        request.dispatch().await
+    };
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one("Cache-Control"),
+        Some("public, max-age=30")
+    );
+    assert!(response.headers().get_one("Expires").is_some());
+    assert_eq!(response.headers().get_one("X-Cache"), Some("HIT"));
+    assert!(response.headers().get_one("X-Cache-Key").is_none());
+    assert_eq!(response.into_string().await.unwrap(), "[]");
+}
+
+#[rocket::async_test]
+async fn device_response_is_private() {
+    let client = client(cached_master_copies(1)).await;
+
+    let response = {
+        let mut request = client.get(MASTER_COPIES_URI);
+        request.add_header(Header::new("Host", "test.gnosis.io"));
+        request.add_header(Header::new("Safe-Device-Uuid", "device"));
+        request.dispatch().await
+    };
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one("Cache-Control"),
+        Some("private, max-age=30")
+    );
+}
+
+#[rocket::async_test]
+async fn head_request_has_cache_headers_without_body() {
+    let client = client(cached_master_copies(1)).await;
+
+    let response = {
+        let mut request = client.head(MASTER_COPIES_URI);
+        request.add_header(Header::new("Host", "test.gnosis.io"));
+        request.dispatch().await
+    };
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one("Cache-Control"),
+        Some("public, max-age=30")
+    );
+    assert!(response.into_string().await.unwrap_or_default().is_empty());
+}
+
+#[test]
+fn response_ttl_keeps_shortest() {
+    let response_ttl = ResponseTtl::default();
+    assert_eq!(response_ttl.get(), None);
+
+    response_ttl.set(60000);
+    response_ttl.clone().set(15000);
+    response_ttl.set(30000);
+
+    assert_eq!(response_ttl.get(), Some(15000));
+}
+
+#[rocket::async_test]
+async fn stale_response_has_freshness_header_without_cache_headers() {
+    let rocket = setup_rocket_with_mock_cache(
+        MockHttpClient::new(),
+        MockCache::new(),
+        routes![stale_response],
+    )
+    .attach(CacheControl());
+    let client = Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance");
+
+    let response = {
+        let mut request = client.get("/stale");
+        request.add_header(Header::new("Host", "test.gnosis.io"));
+        request.dispatch().await
+    };
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("data_freshness"), Some("stale"));
+    assert_eq!(response.headers().get_one("X-Cache"), Some("STALE"));
+    assert!(response.headers().get_one("Cache-Control").is_none());
+}
+
+#[test]
+fn data_freshness_shared_between_clones() {
+    let data_freshness = DataFreshness::default();
+    assert!(!data_freshness.is_stale());
+
+    data_freshness.clone().mark_stale();
+
+    assert!(data_freshness.is_stale());
+}
+
+#[rocket::async_test]
+async fn generated_response_is_a_cache_miss() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_resp_/uncached"))
+        .return_const(None);
+    mock_cache
+        .expect_create()
+        .times(1)
+        .withf(|key, value, _| key == "c_resp_/uncached" && value == "[\"fresh\"]")
+        .return_const(());
+    let rocket = setup_rocket_with_mock_cache(
+        MockHttpClient::new(),
+        mock_cache,
+        routes![uncached_response],
+    )
+    .attach(CacheControl());
+    let client = Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance");
+
+    let response = {
+        let mut request = client.get("/uncached");
+        request.add_header(Header::new("Host", "test.gnosis.io"));
+        request.dispatch().await
+    };
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("X-Cache"), Some("MISS"));
+}
+
+#[test]
+fn cache_status_keeps_misses() {
+    let cache_status = CacheStatus::default();
+    assert_eq!(cache_status.get(), None);
+    assert_eq!(cache_status.key_hash(), None);
+
+    cache_status.record(CacheLookup::Hit, "c_resp_first");
+    assert_eq!(cache_status.get(), Some(CacheLookup::Hit));
+
+    cache_status
+        .clone()
+        .record(CacheLookup::Miss, "c_resp_second");
+    cache_status.record(CacheLookup::Hit, "c_resp_third");
+
+    assert_eq!(cache_status.get(), Some(CacheLookup::Miss));
+    let second = CacheStatus::default();
+    second.record(CacheLookup::Miss, "c_resp_second");
+    assert_eq!(cache_status.key_hash(), second.key_hash());
+    assert_eq!(cache_status.key_hash().unwrap().len(), 64);
+}
@@ -0,0 +1,170 @@
+use crate::cache::cache_operations::RequestCached;
+use crate::cache::{Cache, MockCache};
+use crate::routes::safes::handlers::safes::get_safe_info_ex;
+use crate::tests::json::{CHAIN_INFO_RINKEBY, SAFE_WITH_MODULES};
+use crate::utils::call_budget::{
+    exceeded_counts, is_exceeded_error, BudgetedHttpClient, CallBudget,
+};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::{HttpClient, MockHttpClient, Request, Response};
+use mockall::predicate::eq;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn ok_response() -> Response {
+    Response {
+        status_code: 200,
+        body: String::from("{}"),
+    }
+}
+
+#[test]
+fn call_budget_refuses_calls_over_limit() {
+    let call_budget = CallBudget::new(String::from("GET /test/refuses"), 2);
+
+    assert!(call_budget.spend().is_ok());
+    assert!(call_budget.spend().is_ok());
+    assert!(!call_budget.is_exceeded());
+
+    let error = call_budget.spend().unwrap_err();
+
+    assert!(call_budget.is_exceeded());
+    assert!(is_exceeded_error(&error));
+    assert_eq!(error.status, 503);
+    assert_eq!(
+        error.details.message,
+        Some(String::from("Upstream call budget of 2 calls exceeded"))
+    );
+}
+
+#[test]
+fn call_budget_counts_exceeding_requests_once() {
+    let route = "GET /test/counts";
+    for _ in 0..2 {
+        let call_budget = CallBudget::new(String::from(route), 1);
+        for _ in 0..3 {
+            let _ = call_budget.spend();
+        }
+    }
+
+    assert_eq!(exceeded_counts().get(route), Some(&2));
+}
+
+#[test]
+fn upstream_errors_are_not_budget_errors() {
+    let error = ApiError::new_from_message_with_code(503, String::from("Service unavailable"));
+
+    assert!(!is_exceeded_error(&error));
+}
+
+#[rocket::async_test]
+async fn budgeted_http_client_stops_calling_upstream() {
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_get()
+        .times(2)
+        .returning(|_| Ok(ok_response()));
+    let call_budget = Arc::new(CallBudget::new(String::from("GET /test/client"), 2));
+    let http_client = BudgetedHttpClient::new(Arc::new(mock_http_client), call_budget.clone());
+
+    for _ in 0..2 {
+        let response = http_client
+            .get(Request::new(String::from("https://example.com")))
+            .await;
+        assert_eq!(response.unwrap(), ok_response());
+    }
+    let error = http_client
+        .get(Request::new(String::from("https://example.com")))
+        .await
+        .unwrap_err();
+
+    assert!(is_exceeded_error(&error));
+}
+
+#[rocket::async_test]
+async fn request_cached_does_not_cache_budget_errors() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .with(eq("c_reqs_https://example.com/chains/6"))
+        .return_const(None);
+    mock_cache.expect_create().times(0);
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+    let call_budget = Arc::new(CallBudget::new(String::from("GET /test/cached"), 0));
+    let client: Arc<dyn HttpClient> = Arc::new(BudgetedHttpClient::new(
+        Arc::new(MockHttpClient::new()),
+        call_budget,
+    ));
+
+    let actual = RequestCached::new(
+        String::from("https://example.com/chains/6"),
+        &client,
+        &cache,
+    )
+    .cache_all_errors()
+    .execute()
+    .await;
+
+    assert!(is_exceeded_error(&actual.unwrap_err()));
+}
+
+#[rocket::async_test]
+async fn handler_exceeding_call_budget_stops_calling_upstream() {
+    let safe_address = "0x1230B3d59858296A31053C1b8562Ecf89A2f888b";
+    let chain_key = format!("c_reqs_{}", config_uri!("/v1/chains/{}/", "4"));
+    let has_chain_key = chain_key.to_string();
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .returning(move |key| key == has_chain_key);
+    mock_cache.expect_fetch().returning(move |key| {
+        if key == chain_key {
+            Some(String::from(CHAIN_INFO_RINKEBY))
+        } else {
+            None
+        }
+    });
+    mock_cache.expect_create().return_const(());
+    mock_cache.expect_insert_in_hash().return_const(());
+    mock_cache.expect_get_from_hash().returning(|_, _| None);
+    mock_cache.expect_increment_in_hash().return_const(1usize);
+    mock_cache.expect_fetch_hash().returning(|_| HashMap::new());
+    mock_cache.expect_ttl().returning(|_| None);
+    mock_cache.expect_expire_entity().return_const(());
+    mock_cache.expect_invalidate_pattern().return_const(0usize);
+    mock_cache.expect_append_to_stream().return_const(());
+    // Only the safe info fits in the budget, the master copies, contract lookups and tags of
+    // the safe are refused
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_get()
+        .times(1)
+        .withf(move |request| {
+            request
+                .url()
+                .ends_with(&format!("/v1/safes/{}/", safe_address))
+        })
+        .returning(|_| {
+            Ok(Response {
+                status_code: 200,
+                body: String::from(SAFE_WITH_MODULES),
+            })
+        });
+    let call_budget = Arc::new(CallBudget::new(String::from("GET /test/handler"), 1));
+    let context = RequestContext::mock(
+        String::from("/v1/chains/4/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b"),
+        String::from("host"),
+        mock_http_client,
+        mock_cache,
+    )
+    .with_call_budget(call_budget.clone());
+
+    let actual = get_safe_info_ex(&context, &String::from("4"), &String::from(safe_address)).await;
+
+    // The handler does without the refused calls, the guard answers the request with a 503
+    assert_eq!(actual.unwrap().safe_config.nonce, 180);
+    assert!(call_budget.is_exceeded());
+    assert_eq!(exceeded_counts().get("GET /test/handler"), Some(&1));
+}
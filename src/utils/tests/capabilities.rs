@@ -0,0 +1,195 @@
+use crate::utils::capabilities::{
+    capped_query, down_convert, ClientCapabilities, ClientCapabilityNegotiation,
+    CLIENT_CAPABILITIES_HEADER,
+};
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::response::content;
+use serde_json::json;
+
+#[get("/history?<cursor>")]
+fn history(cursor: Option<String>) -> content::Json<String> {
+    content::Json(
+        json!({
+            "cursor": cursor,
+            "results": [{"transaction": {"txInfo": {"type": "SwapOrder", "value": "5"}}}]
+        })
+        .to_string(),
+    )
+}
+
+async fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![history])
+        .attach(ClientCapabilityNegotiation());
+    Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance")
+}
+
+#[test]
+fn client_capabilities_from_header() {
+    assert_eq!(
+        ClientCapabilities {
+            max_page_size: Some(10),
+            tx_info_types: Some(vec![String::from("Transfer"), String::from("Creation")]),
+        },
+        ClientCapabilities::from_header("max-page-size=10; tx-info=Transfer, Creation")
+    );
+    assert_eq!(
+        ClientCapabilities::default(),
+        ClientCapabilities::from_header("max-page-size=0; unknown=1; broken")
+    );
+}
+
+#[test]
+fn supports_tx_info_always_includes_custom() {
+    let capabilities = ClientCapabilities::from_header("tx-info=Transfer");
+
+    assert!(capabilities.supports_tx_info("Transfer"));
+    assert!(capabilities.supports_tx_info("Custom"));
+    assert!(!capabilities.supports_tx_info("SettingsChange"));
+    assert!(ClientCapabilities::default().supports_tx_info("SettingsChange"));
+}
+
+#[test]
+fn capped_query_caps_cursor_and_limit() {
+    assert_eq!(
+        Some(String::from(
+            "cursor=limit%3D10%26offset%3D40&trusted=false"
+        )),
+        capped_query(
+            Some("cursor=limit%3D50%26offset%3D40&trusted=false"),
+            10,
+            true
+        )
+    );
+    assert_eq!(
+        Some(String::from("limit=10")),
+        capped_query(Some("limit=100"), 10, false)
+    );
+    assert_eq!(
+        None,
+        capped_query(Some("cursor=limit%3D5%26offset%3D0"), 10, true)
+    );
+}
+
+#[test]
+fn capped_query_adds_cursor_below_default_page_size() {
+    assert_eq!(
+        Some(String::from("cursor=limit%3D5%26offset%3D0")),
+        capped_query(None, 5, true)
+    );
+    assert_eq!(None, capped_query(None, 5, false));
+    assert_eq!(None, capped_query(None, 50, true));
+}
+
+#[test]
+fn down_convert_replaces_unknown_tx_info() {
+    let capabilities = ClientCapabilities::from_header("tx-info=Transfer,SettingsChange");
+    let value = json!({
+        "results": [
+            {"transaction": {"txInfo": {"type": "Transfer", "direction": "INCOMING"}}},
+            {"transaction": {"txInfo": {"type": "Creation", "creator": {"value": "0x1"}}}},
+            {"transaction": {"txInfo": {"type": "SwapOrder", "recipient": {"value": "0x2"}, "value": "7"}}}
+        ]
+    });
+
+    assert_eq!(
+        json!({
+            "results": [
+                {"transaction": {"txInfo": {"type": "Transfer", "direction": "INCOMING"}}},
+                {"transaction": {"txInfo": {"type": "Custom", "isCancellation": false}}},
+                {"transaction": {"txInfo": {"type": "Custom", "isCancellation": false}}}
+            ]
+        }),
+        down_convert(value, &capabilities)
+    );
+}
+
+#[test]
+fn down_convert_fills_custom_tx_info_from_tx_data() {
+    let capabilities = ClientCapabilities::from_header("tx-info=Transfer");
+    let value = json!({
+        "txId": "multisig_0x1_0x2",
+        "txInfo": {"type": "SwapOrder", "recipient": {"value": "0x2"}, "value": "7"},
+        "txData": {
+            "hexData": "0x095ea7b30000",
+            "dataDecoded": {"method": "approve", "parameters": []},
+            "to": {"value": "0x3", "name": "Token"},
+            "value": "0",
+            "operation": 0
+        }
+    });
+
+    assert_eq!(
+        json!({
+            "type": "Custom",
+            "to": {"value": "0x3", "name": "Token"},
+            "dataSize": "6",
+            "value": "0",
+            "methodName": "approve",
+            "isCancellation": false
+        }),
+        down_convert(value, &capabilities)["txInfo"]
+    );
+}
+
+#[test]
+fn down_convert_without_decoded_data() {
+    let capabilities = ClientCapabilities::from_header("tx-info=Transfer");
+    let value = json!({
+        "txInfo": {"type": "SwapOrder"},
+        "txData": {"hexData": null, "dataDecoded": null, "to": {"value": "0x3"}, "value": null, "operation": 0}
+    });
+
+    assert_eq!(
+        json!({
+            "type": "Custom",
+            "to": {"value": "0x3"},
+            "dataSize": "0",
+            "value": "0",
+            "methodName": null,
+            "isCancellation": false
+        }),
+        down_convert(value, &capabilities)["txInfo"]
+    );
+}
+
+#[rocket::async_test]
+async fn negotiation_caps_page_size_and_down_converts() {
+    let client = client().await;
+
+    let response = client
+        .get("/history")
+        .header(Header::new(
+            CLIENT_CAPABILITIES_HEADER,
+            "max-page-size=5; tx-info=Transfer",
+        ))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(json!("limit=5&offset=0"), body["cursor"]);
+    assert_eq!(
+        json!("Custom"),
+        body["results"][0]["transaction"]["txInfo"]["type"]
+    );
+}
+
+#[rocket::async_test]
+async fn negotiation_without_header_serves_response_as_is() {
+    let client = client().await;
+
+    let response = client.get("/history").dispatch().await;
+
+    let body: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert_eq!(json!(null), body["cursor"]);
+    assert_eq!(
+        json!("SwapOrder"),
+        body["results"][0]["transaction"]["txInfo"]["type"]
+    );
+}
@@ -0,0 +1,109 @@
+use crate::utils::chain_hosts::{
+    chain_for_host, chain_scoped_path, matches_template, resolve_path,
+};
+use std::collections::HashMap;
+
+fn templates() -> Vec<String> {
+    vec![
+        "/v1/chains/<chain_id>",
+        "/v1/chains?<limit>",
+        "/v1/chains/<chain_id>/safes/<safe_address>",
+        "/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>&<timezone_offset>&<trusted>",
+        "/v1/safes/labels",
+        "/v1/balances/supported-fiat-codes",
+        "/about",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+#[test]
+fn chain_for_host_ignores_port_and_case() {
+    let mut chain_hosts = HashMap::new();
+    chain_hosts.insert(
+        String::from("polygon.gateway.example.com"),
+        String::from("137"),
+    );
+
+    assert_eq!(
+        chain_for_host(&chain_hosts, "polygon.gateway.example.com"),
+        Some("137")
+    );
+    assert_eq!(
+        chain_for_host(&chain_hosts, "Polygon.Gateway.Example.com:8000"),
+        Some("137")
+    );
+    assert_eq!(chain_for_host(&chain_hosts, "gateway.example.com"), None);
+}
+
+#[test]
+fn matches_template_segments() {
+    assert!(matches_template(
+        "/v1/chains/<chain_id>/safes/<safe_address>",
+        "/v1/chains/137/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b"
+    ));
+    assert!(matches_template(
+        "/v1/chains/<chain_id>/safes/<safe_address>/transactions/queued?<cursor>",
+        "/v1/chains/137/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/transactions/queued"
+    ));
+    assert!(matches_template("/static/<path..>", "/static/css/main.css"));
+    assert!(!matches_template(
+        "/v1/chains/<chain_id>/safes/<safe_address>",
+        "/v1/chains/137/safes"
+    ));
+    assert!(!matches_template(
+        "/v1/safes/labels",
+        "/v1/safes/0x1230B3d5"
+    ));
+}
+
+#[test]
+fn chain_scoped_path_adds_chain_after_version() {
+    assert_eq!(
+        chain_scoped_path("/v1/safes/0x1230B3d5", "137"),
+        Some(String::from("/v1/chains/137/safes/0x1230B3d5"))
+    );
+    assert_eq!(
+        chain_scoped_path("/v1", "137"),
+        Some(String::from("/v1/chains/137"))
+    );
+    assert_eq!(
+        chain_scoped_path("/v1/chains/4/safes/0x1230B3d5", "137"),
+        None
+    );
+    assert_eq!(chain_scoped_path("/about", "137"), None);
+}
+
+#[test]
+fn resolve_path_only_rewrites_unmatched_chain_routes() {
+    let templates = templates();
+
+    assert_eq!(
+        resolve_path(
+            &templates,
+            "/v1/safes/0x1230B3d5/transactions/queued",
+            "137"
+        ),
+        Some(String::from(
+            "/v1/chains/137/safes/0x1230B3d5/transactions/queued"
+        ))
+    );
+    assert_eq!(
+        resolve_path(&templates, "/v1", "137"),
+        Some(String::from("/v1/chains/137"))
+    );
+    // Chain independent routes and explicit chains are served as they are
+    assert_eq!(resolve_path(&templates, "/v1/safes/labels", "137"), None);
+    assert_eq!(
+        resolve_path(&templates, "/v1/balances/supported-fiat-codes", "137"),
+        None
+    );
+    assert_eq!(
+        resolve_path(&templates, "/v1/chains/4/safes/0x1230B3d5", "137"),
+        None
+    );
+    assert_eq!(resolve_path(&templates, "/about", "137"), None);
+    // Not a route once scoped either
+    assert_eq!(resolve_path(&templates, "/v1/unknown", "137"), None);
+}
@@ -0,0 +1,49 @@
+use crate::utils::chain_warm_up::{load_concurrently, WarmedUpChains};
+use rocket::tokio::time::{sleep, Duration};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn chain_ids(chain_ids: &[&str]) -> Vec<String> {
+    chain_ids.iter().map(|it| it.to_string()).collect()
+}
+
+#[rocket::async_test]
+async fn load_concurrently_reports_failed_chains() {
+    let actual = load_concurrently(chain_ids(&["4", "1", "137"]), 2, |chain_id| async move {
+        if chain_id == "137" {
+            Err(api_error!("Chain 137 timed out"))
+        } else {
+            Ok(())
+        }
+    })
+    .await;
+
+    assert_eq!(
+        actual,
+        WarmedUpChains {
+            loaded: chain_ids(&["1", "4"]),
+            failed: chain_ids(&["137"]),
+        }
+    );
+}
+
+#[rocket::async_test]
+async fn load_concurrently_bounds_parallelism() {
+    let running = &AtomicUsize::new(0);
+    let max_running = &AtomicUsize::new(0);
+
+    let actual = load_concurrently(
+        chain_ids(&["1", "4", "5", "100", "137", "246"]),
+        2,
+        move |_| async move {
+            let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(now_running, Ordering::SeqCst);
+            sleep(Duration::from_millis(10)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        },
+    )
+    .await;
+
+    assert_eq!(actual.loaded.len(), 6);
+    assert_eq!(max_running.load(Ordering::SeqCst), 2);
+}
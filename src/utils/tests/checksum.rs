@@ -0,0 +1,147 @@
+use crate::common::models::addresses::AddressEx;
+use crate::common::models::data_decoded::{Operation, ParamValue, Parameter};
+use crate::routes::transactions::models::details::TransactionData;
+use crate::utils::checksum::{checksum_addresses, to_checksum_address, AddressChecksums};
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::response::content;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[get("/owners")]
+fn owners() -> content::Json<&'static str> {
+    content::Json(r#"{"owners": ["0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"]}"#)
+}
+
+#[get("/plain")]
+fn plain() -> &'static str {
+    "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+}
+
+#[test]
+fn to_checksum_address_eip55_vectors() {
+    assert_eq!(
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        to_checksum_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")
+    );
+    assert_eq!(
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        to_checksum_address("0xFB6916095CA1DF60BB79CE92CE3EA74C37C5D359")
+    );
+    assert_eq!(
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        to_checksum_address("0xdbf03b407c01e7cd3cbea99509d93f8dddc8c6fb")
+    );
+    assert_eq!(
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        to_checksum_address("0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb")
+    );
+}
+
+#[test]
+fn checksum_addresses_rewrites_address_fields() {
+    let body = r#"{"to": "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359", "owners": ["0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", "0xdbf03b407c01e7cd3cbea99509d93f8dddc8c6fb"], "txInfo": {"sender": "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359"}}"#;
+
+    let expected = r#"{"to": "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359", "owners": ["0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB"], "txInfo": {"sender": "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"}}"#;
+    assert_eq!(expected, checksum_addresses(body));
+}
+
+#[test]
+fn checksum_addresses_leaves_keys_and_other_fields_as_they_are() {
+    let body = r#"{"addressInfoIndex": {"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed": {"name": "Owner"}}, "salt": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", "id": ["0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"], "value": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"}"#;
+
+    assert_eq!(body, checksum_addresses(body));
+}
+
+#[test]
+fn address_ex_serializes_checksummed() {
+    let address_ex = AddressEx::address_only("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+
+    assert_eq!(
+        json!({"value": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"}),
+        serde_json::to_value(&address_ex).unwrap()
+    );
+}
+
+#[test]
+fn parameters_serialize_only_address_values_checksummed() {
+    let parameter = |param_type: &str, value: ParamValue| Parameter {
+        name: String::from("param"),
+        param_type: param_type.to_string(),
+        value,
+        value_decoded: None,
+    };
+    let address = || ParamValue::SingleValue("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".into());
+    let parameters = vec![
+        parameter("address", address()),
+        parameter("address[]", ParamValue::ArrayValue(vec![address()])),
+        parameter("bytes20", address()),
+    ];
+
+    assert_eq!(
+        json!([
+            {"name": "param", "type": "address", "value": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"},
+            {"name": "param", "type": "address[]", "value": ["0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"]},
+            {"name": "param", "type": "bytes20", "value": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"}
+        ]),
+        serde_json::to_value(&parameters).unwrap()
+    );
+}
+
+#[test]
+fn address_info_index_serializes_checksummed_keys() {
+    let address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+    let mut address_info_index = HashMap::new();
+    address_info_index.insert(address.to_string(), AddressEx::address_only(address));
+    let tx_data = TransactionData {
+        hex_data: None,
+        data_decoded: None,
+        to: AddressEx::zero(),
+        value: None,
+        operation: Operation::CALL,
+        address_info_index: Some(address_info_index),
+        actions: None,
+    };
+
+    assert_eq!(
+        json!({"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed": {"value": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"}}),
+        serde_json::to_value(&tx_data).unwrap()["addressInfoIndex"]
+    );
+}
+
+#[test]
+fn checksum_addresses_leaves_other_strings_as_they_are() {
+    let body = r#"{"safeTxHash": "0x1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b", "hexData": "0xa9059cbb0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed", "note": "sent \"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\"", "value": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaeg"}"#;
+
+    assert_eq!(body, checksum_addresses(body));
+}
+
+#[test]
+fn checksum_addresses_of_non_json_body() {
+    assert_eq!("", checksum_addresses(""));
+    assert_eq!("Bad Gateway", checksum_addresses("Bad Gateway"));
+    assert_eq!("\"0x12", checksum_addresses("\"0x12"));
+}
+
+#[rocket::async_test]
+async fn address_checksums_only_rewrites_json_responses() {
+    let rocket = rocket::build()
+        .mount("/", routes![owners, plain])
+        .attach(AddressChecksums());
+    let client = Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance");
+
+    let response = client.get("/owners").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        r#"{"owners": ["0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"]}"#,
+        response.into_string().await.unwrap()
+    );
+
+    let response = client.get("/plain").dispatch().await;
+    assert_eq!(
+        "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+        response.into_string().await.unwrap()
+    );
+}
@@ -0,0 +1,106 @@
+use crate::cache::MockCache;
+use crate::testing::setup::setup_rocket_with_mock_cache;
+use crate::utils::context::RequestContext;
+use crate::utils::data_policies::{header_value, policy_for, DataMaxAge, DataPolicy};
+use crate::utils::http_client::MockHttpClient;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+
+#[get("/v1/chains/<chain_id>/gas-price")]
+fn gas_price(context: RequestContext, chain_id: String) -> String {
+    context.response_ttl().set(4000);
+    chain_id
+}
+
+#[get("/v1/chains/<chain_id>/gas-price/uncached")]
+fn uncached(chain_id: String) -> String {
+    chain_id
+}
+
+#[test]
+fn data_policy_max_age_adds_up_caches() {
+    let policy = DataPolicy::new(
+        "/v1/chains/<chain_id>/safes/<safe_address>",
+        60000,
+        30000,
+        true,
+    );
+
+    assert_eq!(policy.max_age, 90000);
+}
+
+#[test]
+fn data_policy_max_age_of_uses_remaining_ttl() {
+    let policy = DataPolicy::new(
+        "/v1/chains/<chain_id>/safes/<safe_address>",
+        60000,
+        30000,
+        true,
+    );
+
+    assert_eq!(policy.max_age_of(Some(45000), false), 45000);
+    assert_eq!(policy.max_age_of(Some(60000), false), 30000);
+    assert_eq!(policy.max_age_of(None, false), 90000);
+}
+
+#[test]
+fn policy_for_ignores_query() {
+    let policies = vec![
+        DataPolicy::new("/v1/chains", 1, 60000, true),
+        DataPolicy::new("/v1/chains/<chain_id>/exchange-rates", 60000, 1, false),
+    ];
+
+    assert_eq!(
+        policy_for(
+            &policies,
+            "/v1/chains/<chain_id>/exchange-rates?<currencies>"
+        ),
+        Some(&policies[1])
+    );
+    assert_eq!(
+        policy_for(&policies, "/v1/chains?<limit>"),
+        Some(&policies[0])
+    );
+    assert_eq!(
+        policy_for(&policies, "/v1/chains/<chain_id>/gas-price"),
+        None
+    );
+}
+
+#[test]
+fn header_value_rounds_up_to_seconds() {
+    assert_eq!(header_value(0), "0");
+    assert_eq!(header_value(1), "1");
+    assert_eq!(header_value(60000), "60");
+    assert_eq!(header_value(60001), "61");
+}
+
+#[rocket::async_test]
+async fn data_max_age_header_of_policy_routes() {
+    let rocket = setup_rocket_with_mock_cache(
+        MockHttpClient::new(),
+        MockCache::new(),
+        routes![gas_price, uncached],
+    )
+    .attach(DataMaxAge());
+    let client = Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance");
+
+    let response = {
+        let mut request = client.get("/v1/chains/4/gas-price");
+        request.add_header(Header::new("Host", "test.gnosis.io"));
+        request.dispatch().await
+    };
+    assert_eq!(response.status(), Status::Ok);
+    // GAS_PRICE_CACHE_DURATION of 10s, of which 4s are left
+    assert_eq!(response.headers().get_one("X-Data-Max-Age"), Some("6"));
+
+    let response = {
+        let mut request = client.get("/v1/chains/4/gas-price/uncached");
+        request.add_header(Header::new("Host", "test.gnosis.io"));
+        request.dispatch().await
+    };
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.headers().get_one("X-Data-Max-Age").is_none());
+}
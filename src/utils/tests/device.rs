@@ -0,0 +1,57 @@
+use crate::cache::{Cache, MockCache};
+use crate::utils::device::Device;
+use mockall::predicate::eq;
+use std::sync::Arc;
+
+// keccak256("secret")
+const SECRET_HASH: &str = "0x65462b0520ef7d3df61b9992ed3bea0c56ead753be7c8b3614e0ce01e4cac41b";
+
+#[rocket::async_test]
+async fn authenticate_binds_token_on_first_use() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .times(1)
+        .with(eq("device_tokens"), eq("device-1"))
+        .return_const(None);
+    mock_cache
+        .expect_insert_in_hash()
+        .times(1)
+        .with(eq("device_tokens"), eq("device-1"), eq(SECRET_HASH))
+        .return_const(());
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+
+    let actual = Device::new("device-1", "secret").authenticate(&cache).await;
+
+    assert!(actual.is_ok());
+}
+
+#[rocket::async_test]
+async fn authenticate_known_device() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .times(1)
+        .return_const(Some(SECRET_HASH.to_string()));
+    mock_cache.expect_insert_in_hash().times(0);
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+
+    let actual = Device::new("device-1", "secret").authenticate(&cache).await;
+
+    assert!(actual.is_ok());
+}
+
+#[rocket::async_test]
+async fn authenticate_wrong_token() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .times(1)
+        .return_const(Some(SECRET_HASH.to_string()));
+    mock_cache.expect_insert_in_hash().times(0);
+    let cache: Arc<dyn Cache> = Arc::new(mock_cache);
+
+    let actual = Device::new("device-1", "other").authenticate(&cache).await;
+
+    assert_eq!(actual.unwrap_err().status, 401);
+}
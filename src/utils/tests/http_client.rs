@@ -0,0 +1,106 @@
+use crate::utils::http_client::{for_url, headers_for_url, Request, UpstreamHeaders};
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn build_upstream_headers() -> UpstreamHeaders {
+    let mut upstream_headers = HashMap::new();
+    let mut host_headers = HashMap::new();
+    host_headers.insert(String::from("Authorization"), String::from("Basic host"));
+    upstream_headers.insert(String::from("safe-transaction.example.com"), host_headers);
+    let mut port_headers = HashMap::new();
+    port_headers.insert(String::from("Authorization"), String::from("Basic port"));
+    upstream_headers.insert(
+        String::from("safe-transaction.example.com:8000"),
+        port_headers,
+    );
+    upstream_headers
+}
+
+#[test]
+fn headers_for_url_matches_host() {
+    let upstream_headers = build_upstream_headers();
+
+    let actual = headers_for_url(
+        &upstream_headers,
+        "https://safe-transaction.example.com/api/v1/safes/",
+    )
+    .unwrap();
+
+    assert_eq!(actual.get("Authorization").unwrap(), "Basic host");
+}
+
+#[test]
+fn headers_for_url_prefers_host_and_port() {
+    let upstream_headers = build_upstream_headers();
+
+    let actual = headers_for_url(
+        &upstream_headers,
+        "http://safe-transaction.example.com:8000/api/v1/safes/",
+    )
+    .unwrap();
+
+    assert_eq!(actual.get("Authorization").unwrap(), "Basic port");
+}
+
+#[test]
+fn headers_for_url_unknown_host() {
+    let upstream_headers = build_upstream_headers();
+
+    let actual = headers_for_url(&upstream_headers, "https://safe-config.example.com/api/");
+
+    assert!(actual.is_none());
+}
+
+#[test]
+fn headers_for_url_invalid_url() {
+    let upstream_headers = build_upstream_headers();
+
+    let actual = headers_for_url(&upstream_headers, "not a url");
+
+    assert!(actual.is_none());
+}
+
+#[test]
+fn for_url_prefers_port_over_host() {
+    let mut per_host = HashMap::new();
+    per_host.insert(String::from("safe-transaction.internal"), "host");
+    per_host.insert(String::from("safe-transaction.internal:8443"), "port");
+
+    assert_eq!(
+        Some(&"port"),
+        for_url(
+            &per_host,
+            "https://safe-transaction.internal:8443/api/v1/about/"
+        )
+    );
+    assert_eq!(
+        Some(&"host"),
+        for_url(&per_host, "https://safe-transaction.internal/api/v1/about/")
+    );
+    assert_eq!(
+        None,
+        for_url(&per_host, "https://safe-config.internal/api/v1/chains/")
+    );
+}
+
+#[test]
+fn request_timeout_is_explicit_once_set() {
+    let mut request = Request::new(String::from("https://safe-transaction.example.com/api/"));
+    assert!(!request.has_explicit_timeout());
+
+    request.timeout(Duration::from_millis(1000));
+
+    assert!(request.has_explicit_timeout());
+}
+
+#[test]
+fn request_host() {
+    let request = Request::new(String::from(
+        "https://safe-transaction.example.com:8000/api/v1/about/",
+    ));
+    assert_eq!(
+        request.host(),
+        Some(String::from("safe-transaction.example.com"))
+    );
+    assert_eq!(Request::new(String::from("not a url")).host(), None);
+}
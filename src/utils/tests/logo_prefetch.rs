@@ -0,0 +1,96 @@
+use crate::cache::MockCache;
+use crate::routes::chains::handlers::generate_asset_key;
+use crate::utils::http_client::{BinaryResponse, MockHttpClient};
+use crate::utils::logo_prefetch::{logos_to_prefetch, prefetch_logos, PrefetchedLogos};
+use mockall::predicate::eq;
+
+const CACHED_LOGO: &str = "https://gnosis-safe-token-logos.s3.amazonaws.com/0xcached.png";
+const MISSING_LOGO: &str = "https://gnosis-safe-token-logos.s3.amazonaws.com/0xmissing.png";
+const BROKEN_LOGO: &str = "https://gnosis-safe-token-logos.s3.amazonaws.com/0xbroken.png";
+
+#[test]
+fn logos_to_prefetch_distinct_within_limit() {
+    let logo_uris = vec![
+        String::from(CACHED_LOGO),
+        String::from(""),
+        String::from(CACHED_LOGO),
+        String::from(MISSING_LOGO),
+        String::from(BROKEN_LOGO),
+    ];
+
+    assert_eq!(
+        logos_to_prefetch(logo_uris, 2),
+        vec![String::from(CACHED_LOGO), String::from(MISSING_LOGO)]
+    );
+}
+
+#[rocket::async_test]
+async fn prefetch_logos_fetches_missing_logos() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_has_key()
+        .with(eq(generate_asset_key(CACHED_LOGO)))
+        .times(1)
+        .return_const(true);
+    mock_cache
+        .expect_has_key()
+        .with(eq(generate_asset_key(MISSING_LOGO)))
+        .times(1)
+        .return_const(false);
+    mock_cache
+        .expect_has_key()
+        .with(eq(generate_asset_key(BROKEN_LOGO)))
+        .times(1)
+        .return_const(false);
+    mock_cache.expect_fetch().times(2).return_const(None);
+    mock_cache
+        .expect_create()
+        .withf(|key, value, _| {
+            key == generate_asset_key(MISSING_LOGO) && value == "image/png;0x89504e47"
+        })
+        .times(1)
+        .return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_get_binary()
+        .withf(|request, _| request.url() == MISSING_LOGO)
+        .times(1)
+        .return_once(|_, _| {
+            Ok(BinaryResponse {
+                content_type: Some(String::from("image/png")),
+                body: vec![0x89, 0x50, 0x4e, 0x47],
+            })
+        });
+    mock_http_client
+        .expect_get_binary()
+        .withf(|request, _| request.url() == BROKEN_LOGO)
+        .times(1)
+        .return_once(|_, _| {
+            Ok(BinaryResponse {
+                content_type: Some(String::from("text/html")),
+                body: b"<html></html>".to_vec(),
+            })
+        });
+
+    let actual = prefetch_logos(
+        &mock_http_client,
+        &mock_cache,
+        vec![
+            String::from(CACHED_LOGO),
+            String::from(MISSING_LOGO),
+            String::from(BROKEN_LOGO),
+        ],
+        500,
+        2,
+    )
+    .await;
+
+    assert_eq!(
+        actual,
+        PrefetchedLogos {
+            fetched: 1,
+            cached: 1,
+            failed: 1,
+        }
+    );
+}
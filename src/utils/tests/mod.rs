@@ -1,7 +1,33 @@
+mod auth;
+mod cache_control;
+mod call_budget;
+mod capabilities;
+mod chain_hosts;
+mod chain_warm_up;
+mod checksum;
 mod data_decoded_utils;
+mod data_policies;
+mod device;
 mod errors;
+mod hook_events;
+mod http_client;
 mod json;
+mod logo_prefetch;
 mod macros;
 mod method_names;
+mod outbound;
+mod payload_limits;
+mod read_only;
+mod response_budget;
+mod retry_queue;
+mod safe_version;
+mod serialization;
+mod shadowing;
+mod signatures;
+mod spam;
+mod trace_id;
 mod transactions;
+mod upstream_queue;
+mod upstream_versions;
 mod urls;
+mod validation;
@@ -0,0 +1,62 @@
+use crate::testing::builders::ChainInfoBuilder;
+use crate::utils::outbound::{chain_urls, is_allowed};
+use std::collections::HashSet;
+
+fn allowed_hosts() -> HashSet<String> {
+    vec!["safe-transaction.example.com", "rpc.example.com"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn is_allowed_matches_host_only() {
+    let allowed_hosts = allowed_hosts();
+
+    assert!(is_allowed(
+        &allowed_hosts,
+        "https://safe-transaction.example.com/api/v1/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/"
+    ));
+    assert!(is_allowed(
+        &allowed_hosts,
+        "http://SAFE-TRANSACTION.example.com:8000/api/v1/about/"
+    ));
+    assert!(is_allowed(&allowed_hosts, "https://rpc.example.com"));
+}
+
+#[test]
+fn is_allowed_rejects_unknown_hosts() {
+    let allowed_hosts = allowed_hosts();
+
+    assert!(!is_allowed(
+        &allowed_hosts,
+        "http://169.254.169.254/latest/meta-data/"
+    ));
+    assert!(!is_allowed(&allowed_hosts, "http://localhost:6379"));
+    assert!(!is_allowed(
+        &allowed_hosts,
+        "https://safe-transaction.example.com.attacker.io/api/v1/about/"
+    ));
+    assert!(!is_allowed(
+        &allowed_hosts,
+        "https://attacker.io/?safe-transaction.example.com"
+    ));
+    assert!(!is_allowed(&allowed_hosts, "not a url"));
+}
+
+#[test]
+fn chain_urls_include_services_rpc_and_fallbacks() {
+    let mut chain_info = ChainInfoBuilder::new("4").build();
+    chain_info.transaction_service_fallback_uri = Some(String::from(
+        "https://safe-transaction-secondary.example.com",
+    ));
+
+    let expected = vec![
+        "https://safe-transaction.example.com",
+        "http://safe-transaction-web.default",
+        "https://rpc.example.com",
+        "https://safe-transaction-secondary.example.com",
+    ];
+
+    assert_eq!(chain_urls(&chain_info), expected);
+}
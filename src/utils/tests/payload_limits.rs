@@ -0,0 +1,76 @@
+use crate::utils::errors::ApiError;
+use crate::utils::payload_limits::{
+    check_response_depth, exceeds_depth, request_body_too_large, stats,
+};
+use rocket::serde::json::Error;
+use std::io;
+
+#[test]
+fn exceeds_depth_counts_nested_arrays_and_objects() {
+    let body = r#"{"results": [{"dataDecoded": {"parameters": []}}]}"#;
+
+    assert!(!exceeds_depth(body, 5));
+    assert!(exceeds_depth(body, 4));
+}
+
+#[test]
+fn exceeds_depth_ignores_brackets_in_strings() {
+    let body = r#"{"method": "[[[{{{", "escaped": "\"[[[", "value": []}"#;
+
+    assert!(!exceeds_depth(body, 2));
+}
+
+#[test]
+fn exceeds_depth_of_non_json_body() {
+    assert!(!exceeds_depth("Bad Gateway", 1));
+    assert!(!exceeds_depth("", 1));
+}
+
+#[test]
+fn check_response_depth_rejects_deep_responses_with_502() {
+    let deep_body = format!("{}{}", "[".repeat(1000), "]".repeat(1000));
+    let rejected_before = stats().too_deep_responses;
+
+    let error = check_response_depth("https://safe-transaction.rinkeby.gnosis.io/", &deep_body)
+        .unwrap_err();
+
+    assert_eq!(502, error.status);
+    assert_eq!(
+        Some(String::from("Upstream response is nested deeper than 64")),
+        error.details.message
+    );
+    assert!(stats().too_deep_responses > rejected_before);
+    assert!(check_response_depth("https://safe-transaction.rinkeby.gnosis.io/", "[[]]").is_ok());
+}
+
+#[test]
+fn request_body_too_large_is_413_and_counted() {
+    let rejected_before = stats().rejected_request_bodies;
+
+    let error = request_body_too_large();
+
+    assert_eq!(413, error.status);
+    assert_eq!(
+        Some(String::from("Request body exceeds 1048576 bytes")),
+        error.details.message
+    );
+    assert!(stats().rejected_request_bodies > rejected_before);
+}
+
+#[test]
+fn json_body_cut_off_at_limit_maps_to_413() {
+    let error: ApiError = Error::Io(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "data limit exceeded",
+    ))
+    .into();
+
+    assert_eq!(413, error.status);
+}
+
+#[test]
+fn json_io_error_maps_to_422() {
+    let error: ApiError = Error::Io(io::Error::new(io::ErrorKind::Other, "broken pipe")).into();
+
+    assert_eq!(422, error.status);
+}
@@ -0,0 +1,76 @@
+use crate::cache::MockCache;
+use crate::utils::read_only::{check_writable, current, set, ReadOnlyMode};
+use mockall::predicate::eq;
+
+fn stored_mode(mode: Option<&str>) -> MockCache {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_get_from_hash()
+        .with(eq("read_only"), eq("mode"))
+        .times(1)
+        .return_const(mode.map(String::from));
+    mock_cache
+}
+
+#[rocket::async_test]
+async fn writable_without_stored_mode() {
+    let mock_cache = stored_mode(None);
+
+    assert!(check_writable(&mock_cache).await.is_ok());
+}
+
+#[rocket::async_test]
+async fn read_only_with_default_message() {
+    let mock_cache = stored_mode(Some(r#"{"enabled":true}"#));
+
+    let error = check_writable(&mock_cache).await.unwrap_err();
+
+    assert_eq!(error.status, 503);
+    assert_eq!(
+        error.details.message,
+        Some(String::from(
+            "The gateway is in read-only mode for maintenance, retry later"
+        ))
+    );
+}
+
+#[rocket::async_test]
+async fn read_only_with_custom_message() {
+    let mock_cache = stored_mode(Some(r#"{"enabled":true,"message":"Back at 14:00 UTC"}"#));
+
+    let error = check_writable(&mock_cache).await.unwrap_err();
+
+    assert_eq!(
+        error.details.message,
+        Some(String::from("Back at 14:00 UTC"))
+    );
+}
+
+#[rocket::async_test]
+async fn stored_mode_overrides_config() {
+    let mock_cache = stored_mode(Some(r#"{"enabled":false}"#));
+
+    assert_eq!(
+        current(&mock_cache).await,
+        ReadOnlyMode {
+            enabled: false,
+            message: None,
+        }
+    );
+}
+
+#[rocket::async_test]
+async fn set_stores_mode() {
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_insert_in_hash()
+        .with(eq("read_only"), eq("mode"), eq(r#"{"enabled":true}"#))
+        .times(1)
+        .return_const(());
+    let mode = ReadOnlyMode {
+        enabled: true,
+        message: None,
+    };
+
+    assert_eq!(set(&mock_cache, &mode).await.unwrap(), mode);
+}
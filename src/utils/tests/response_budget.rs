@@ -0,0 +1,124 @@
+use crate::utils::response_budget::{budget_for, to_string_within_budget, TrimStage};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn history_page() -> Value {
+    json!({
+        "next": null,
+        "results": [{
+            "type": "TRANSACTION",
+            "transaction": {
+                "txInfo": {
+                    "type": "Custom",
+                    "to": {"value": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "logoUri": "https://tokens.example/usdc.png"},
+                    "hexData": format!("0x{}", "ab".repeat(512)),
+                    "dataDecoded": {
+                        "method": "multiSend",
+                        "parameters": [{"name": "transactions", "type": "bytes", "value": "0x00"}]
+                    }
+                }
+            }
+        }]
+    })
+}
+
+fn tx_info(value: &Value) -> &Value {
+    &value["results"][0]["transaction"]["txInfo"]
+}
+
+#[test]
+fn budget_for_route() {
+    let mut budgets = HashMap::new();
+    budgets.insert(
+        String::from("/v1/chains/<chain_id>/safes/<safe_address>/transactions/history"),
+        1000,
+    );
+
+    assert_eq!(
+        budget_for(
+            &budgets,
+            0,
+            "/v1/chains/<chain_id>/safes/<safe_address>/transactions/history?<cursor>"
+        ),
+        Some(1000)
+    );
+    assert_eq!(budget_for(&budgets, 0, "/v1/chains/<chain_id>"), None);
+    assert_eq!(
+        budget_for(&budgets, 5000, "/v1/chains/<chain_id>"),
+        Some(5000)
+    );
+}
+
+#[test]
+fn trim_stage_hex_data_keeps_short_data() {
+    let mut value = json!({"hexData": "0x1234", "nested": [{"hexData": "0x123456"}]});
+
+    TrimStage::HexData(6).apply(&mut value);
+
+    assert_eq!(
+        value,
+        json!({"hexData": "0x1234", "nested": [{"hexData": null}]})
+    );
+}
+
+#[test]
+fn to_string_within_budget_without_budget() {
+    let expected = history_page().to_string();
+
+    assert_eq!(
+        to_string_within_budget(&history_page(), None, 16).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn to_string_within_budget_within_budget() {
+    let expected = history_page().to_string();
+
+    assert_eq!(
+        to_string_within_budget(&history_page(), Some(expected.len()), 16).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn to_string_within_budget_drops_logo_uris_first() {
+    let budget = history_page().to_string().len() - 1;
+
+    let trimmed = to_string_within_budget(&history_page(), Some(budget), 16).unwrap();
+    let actual: Value = serde_json::from_str(&trimmed).unwrap();
+
+    assert_eq!(actual["truncated"], json!(true));
+    assert_eq!(tx_info(&actual)["to"].get("logoUri"), None);
+    assert_eq!(
+        tx_info(&actual)["hexData"],
+        tx_info(&history_page())["hexData"]
+    );
+    assert_eq!(
+        tx_info(&actual)["dataDecoded"],
+        tx_info(&history_page())["dataDecoded"]
+    );
+}
+
+#[test]
+fn to_string_within_budget_drops_every_stage() {
+    let trimmed = to_string_within_budget(&history_page(), Some(100), 16).unwrap();
+    let actual: Value = serde_json::from_str(&trimmed).unwrap();
+
+    assert_eq!(actual["truncated"], json!(true));
+    assert_eq!(tx_info(&actual)["hexData"], Value::Null);
+    assert_eq!(
+        tx_info(&actual)["dataDecoded"],
+        json!({"method": "multiSend"})
+    );
+}
+
+#[test]
+fn to_string_within_budget_skips_arrays() {
+    let response = json!([{"logoUri": "https://tokens.example/usdc.png"}]);
+
+    assert_eq!(
+        to_string_within_budget(&response, Some(10), 16).unwrap(),
+        response.to_string()
+    );
+}
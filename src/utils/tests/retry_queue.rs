@@ -0,0 +1,240 @@
+use crate::cache::MockCache;
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::{MockHttpClient, Response};
+use crate::utils::retry_queue::{
+    is_retryable, queued_write, retry_delay, retry_due, QueuedWrite, RetryRound, WriteMethod,
+    NOTIFICATION_REGISTRATION,
+};
+use mockall::predicate::eq;
+
+const URL: &str = "https://safe-transaction.rinkeby.gnosis.io/api/v1/notifications/devices/";
+const INTERVAL: u64 = 1000;
+
+fn write(attempts: usize, next_attempt: i64) -> QueuedWrite {
+    QueuedWrite {
+        attempts,
+        next_attempt,
+        ..queued_write(
+            NOTIFICATION_REGISTRATION,
+            WriteMethod::Post,
+            URL,
+            Some(String::from("{\"uuid\":\"5c1d\"}")),
+            0,
+            INTERVAL,
+        )
+    }
+}
+
+fn mock_cache(write: &QueuedWrite) -> MockCache {
+    let key = format!("retry_queue_{}", write.id);
+    let value = serde_json::to_string(write).unwrap();
+    let mut mock_cache = MockCache::new();
+    mock_cache
+        .expect_keys()
+        .with(eq("retry_queue_*"))
+        .times(1)
+        .return_once(move |_| vec![key]);
+    mock_cache
+        .expect_fetch()
+        .times(1)
+        .return_once(move |_| Some(value));
+    mock_cache
+}
+
+fn expect_claim(mock_cache: &mut MockCache, write: &QueuedWrite) {
+    mock_cache
+        .expect_increment_in_hash()
+        .with(
+            eq(format!("retry_claim_{}", write.id)),
+            eq("claims"),
+            eq(INTERVAL as usize),
+        )
+        .times(1)
+        .return_const(1usize);
+}
+
+#[test]
+fn retry_delay_doubles_up_to_cap() {
+    assert_eq!(1000, retry_delay(1000, 1));
+    assert_eq!(2000, retry_delay(1000, 2));
+    assert_eq!(8000, retry_delay(1000, 4));
+    assert_eq!(1024000, retry_delay(1000, 11));
+    assert_eq!(1024000, retry_delay(1000, 50));
+    assert_eq!(1000, retry_delay(1000, 0));
+}
+
+#[test]
+fn queued_write_of_same_payload_has_same_id() {
+    let actual = queued_write(
+        NOTIFICATION_REGISTRATION,
+        WriteMethod::Post,
+        URL,
+        Some(String::from("{}")),
+        5000,
+        INTERVAL,
+    );
+    let same = queued_write(
+        NOTIFICATION_REGISTRATION,
+        WriteMethod::Post,
+        URL,
+        Some(String::from("{}")),
+        9000,
+        INTERVAL,
+    );
+    let other = queued_write(
+        NOTIFICATION_REGISTRATION,
+        WriteMethod::Delete,
+        URL,
+        None,
+        5000,
+        INTERVAL,
+    );
+
+    assert_eq!(1, actual.attempts);
+    assert_eq!(6000, actual.next_attempt);
+    assert_eq!(actual.id, same.id);
+    assert_ne!(actual.id, other.id);
+}
+
+#[test]
+fn server_errors_are_retryable() {
+    assert!(is_retryable(&ApiError::new_from_message_with_code(
+        503,
+        String::from("Service unavailable")
+    )));
+    assert!(!is_retryable(&ApiError::new_from_message_with_code(
+        400,
+        String::from("Invalid signature")
+    )));
+}
+
+#[rocket::async_test]
+async fn retry_due_sends_and_removes_write() {
+    let write = write(1, 1000);
+    let mut mock_cache = mock_cache(&write);
+    expect_claim(&mut mock_cache, &write);
+    mock_cache
+        .expect_invalidate()
+        .with(eq(format!("retry_queue_{}", write.id)))
+        .times(1)
+        .return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_post()
+        .times(1)
+        .withf(|request| request.url() == URL)
+        .return_once(|_| {
+            Ok(Response {
+                body: String::new(),
+                status_code: 201,
+            })
+        });
+
+    let actual = retry_due(&mock_cache, &mock_http_client, 2000, INTERVAL, 8).await;
+
+    assert_eq!(
+        RetryRound {
+            sent: 1,
+            rescheduled: 0,
+            dropped: 0,
+        },
+        actual
+    );
+}
+
+#[rocket::async_test]
+async fn retry_due_reschedules_write_failing_with_server_error() {
+    let write = write(2, 1000);
+    let mut mock_cache = mock_cache(&write);
+    expect_claim(&mut mock_cache, &write);
+    let rescheduled = serde_json::to_string(&QueuedWrite {
+        attempts: 3,
+        next_attempt: 2000 + 4000,
+        ..write.clone()
+    })
+    .unwrap();
+    mock_cache
+        .expect_create()
+        .with(
+            eq(format!("retry_queue_{}", write.id)),
+            eq(rescheduled),
+            eq(7 * 24 * 60 * 60 * 1000),
+        )
+        .times(1)
+        .return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_post().times(1).return_once(|_| {
+        Err(ApiError::new_from_message_with_code(
+            502,
+            String::from("Bad gateway"),
+        ))
+    });
+
+    let actual = retry_due(&mock_cache, &mock_http_client, 2000, INTERVAL, 8).await;
+
+    assert_eq!(1, actual.rescheduled);
+}
+
+#[rocket::async_test]
+async fn retry_due_drops_write_after_last_attempt() {
+    let write = write(7, 1000);
+    let mut mock_cache = mock_cache(&write);
+    expect_claim(&mut mock_cache, &write);
+    mock_cache.expect_invalidate().times(1).return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_post().times(1).return_once(|_| {
+        Err(ApiError::new_from_message_with_code(
+            502,
+            String::from("Bad gateway"),
+        ))
+    });
+
+    let actual = retry_due(&mock_cache, &mock_http_client, 2000, INTERVAL, 8).await;
+
+    assert_eq!(1, actual.dropped);
+}
+
+#[rocket::async_test]
+async fn retry_due_drops_write_failing_with_client_error() {
+    let write = write(1, 1000);
+    let mut mock_cache = mock_cache(&write);
+    expect_claim(&mut mock_cache, &write);
+    mock_cache.expect_invalidate().times(1).return_const(());
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client.expect_post().times(1).return_once(|_| {
+        Err(ApiError::new_from_message_with_code(
+            422,
+            String::from("Invalid signature"),
+        ))
+    });
+
+    let actual = retry_due(&mock_cache, &mock_http_client, 2000, INTERVAL, 8).await;
+
+    assert_eq!(1, actual.dropped);
+}
+
+#[rocket::async_test]
+async fn retry_due_skips_writes_not_due_yet() {
+    let write = write(1, 5000);
+    let mock_cache = mock_cache(&write);
+    let mock_http_client = MockHttpClient::new();
+
+    let actual = retry_due(&mock_cache, &mock_http_client, 2000, INTERVAL, 8).await;
+
+    assert_eq!(RetryRound::default(), actual);
+}
+
+#[rocket::async_test]
+async fn retry_due_skips_writes_claimed_by_other_instance() {
+    let write = write(1, 1000);
+    let mut mock_cache = mock_cache(&write);
+    mock_cache
+        .expect_increment_in_hash()
+        .times(1)
+        .return_const(2usize);
+    let mock_http_client = MockHttpClient::new();
+
+    let actual = retry_due(&mock_cache, &mock_http_client, 2000, INTERVAL, 8).await;
+
+    assert_eq!(RetryRound::default(), actual);
+}
@@ -0,0 +1,85 @@
+use crate::utils::safe_version::{is_l2_version, parse_base_version, SafeCapability, SafeVersion};
+use semver::Version;
+
+fn version(version: &str) -> SafeVersion {
+    SafeVersion::parse(Some(&version.to_string()))
+}
+
+#[test]
+fn capabilities_by_safe_version() {
+    assert_eq!(
+        version("1.0.0").capabilities(),
+        vec![SafeCapability::Modules]
+    );
+    assert_eq!(
+        version("1.1.1").capabilities(),
+        vec![SafeCapability::Modules, SafeCapability::FallbackHandler]
+    );
+    assert_eq!(
+        version("1.3.0").capabilities(),
+        SafeCapability::ALL.to_vec()
+    );
+    assert_eq!(
+        version("1.3.0+L2").capabilities(),
+        SafeCapability::ALL.to_vec()
+    );
+}
+
+#[test]
+fn unknown_safe_version_emits_every_field() {
+    let unknown = SafeVersion::parse(None);
+    let invalid = version("not a version");
+
+    for safe_version in [unknown, invalid].iter() {
+        assert_eq!(safe_version.capabilities(), vec![]);
+        assert!(!safe_version.supports(SafeCapability::Guard));
+        assert!(safe_version.emits(SafeCapability::Guard));
+    }
+}
+
+#[test]
+fn version_dependent_fields_emitted_if_supported() {
+    assert!(version("1.1.1").emits(SafeCapability::FallbackHandler));
+    assert!(!version("1.1.1").emits(SafeCapability::Guard));
+    assert!(!version("1.0.0").emits(SafeCapability::FallbackHandler));
+    assert!(version("1.3.0").emits(SafeCapability::Guard));
+}
+
+#[test]
+fn safe_capability_json() {
+    assert_eq!(
+        serde_json::to_value(&SafeCapability::ALL).unwrap(),
+        serde_json::json!([
+            "MODULES",
+            "FALLBACK_HANDLER",
+            "GUARD",
+            "SIMULATE",
+            "CHAIN_ID_IN_DOMAIN"
+        ])
+    );
+}
+
+#[test]
+fn l2_variants_have_the_capabilities_of_their_version() {
+    assert_eq!(
+        version("1.3.0+L2").capabilities(),
+        version("1.3.0").capabilities()
+    );
+    assert!(version("1.3.0+L2").supports(SafeCapability::Guard));
+}
+
+#[test]
+fn parse_base_version_drops_build_metadata() {
+    assert_eq!(parse_base_version("1.3.0+L2"), Some(Version::new(1, 3, 0)));
+    assert_eq!(parse_base_version("1.1.1"), Some(Version::new(1, 1, 1)));
+    assert_eq!(parse_base_version("not a version"), None);
+}
+
+#[test]
+fn is_l2_version_checks_build_metadata() {
+    assert!(is_l2_version("1.3.0+L2"));
+    assert!(is_l2_version("1.3.0+l2"));
+    assert!(!is_l2_version("1.3.0"));
+    assert!(!is_l2_version("1.3.0+L20"));
+    assert!(!is_l2_version("not a version"));
+}
@@ -0,0 +1,131 @@
+use crate::utils::serialization::{
+    to_snake_case_keys, SerializationProfile, SerializationProfiles, SERIALIZATION_PROFILE_HEADER,
+};
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::response::content;
+use serde_json::json;
+
+#[get("/safe")]
+fn safe_info() -> content::Json<&'static str> {
+    content::Json(
+        r#"{"chainId":"4","fallbackHandler":{"value":"0x1"},"modules":[{"logoUri":null}]}"#,
+    )
+}
+
+#[get("/plain")]
+fn plain() -> &'static str {
+    "chainId"
+}
+
+async fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![safe_info, plain])
+        .attach(SerializationProfiles());
+    Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance")
+}
+
+#[test]
+fn to_snake_case_keys_renames_nested_keys() {
+    let value = json!({
+        "txStatus": "SUCCESS",
+        "executionInfo": { "confirmationsRequired": 1, "missingSigners": [{ "logoUri": null }] },
+        "nonce": 1
+    });
+
+    assert_eq!(
+        to_snake_case_keys(value),
+        json!({
+            "tx_status": "SUCCESS",
+            "execution_info": { "confirmations_required": 1, "missing_signers": [{ "logo_uri": null }] },
+            "nonce": 1
+        })
+    );
+}
+
+#[test]
+fn to_snake_case_keys_keeps_data_keys_and_values() {
+    let value = json!({
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b": "someValue",
+        "4": { "TokenType": "ERC20" },
+        "chain_id": "4"
+    });
+
+    assert_eq!(to_snake_case_keys(value.clone()), value);
+}
+
+#[test]
+fn serialization_profile_from_name() {
+    assert_eq!(
+        SerializationProfile::from_name("SNAKE_CASE"),
+        SerializationProfile::SnakeCase
+    );
+    assert_eq!(
+        SerializationProfile::from_name("kebab-case"),
+        SerializationProfile::CamelCase
+    );
+}
+
+#[rocket::async_test]
+async fn camel_case_by_default() {
+    let client = client().await;
+
+    let response = client.get("/safe").dispatch().await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one("Vary"),
+        Some(SERIALIZATION_PROFILE_HEADER)
+    );
+    assert_eq!(
+        response.into_string().await.unwrap(),
+        r#"{"chainId":"4","fallbackHandler":{"value":"0x1"},"modules":[{"logoUri":null}]}"#
+    );
+}
+
+#[rocket::async_test]
+async fn snake_case_from_query() {
+    let client = client().await;
+
+    let response = client
+        .get("/safe?serialization=snake_case")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.into_string().await.unwrap()).unwrap(),
+        json!({ "chain_id": "4", "fallback_handler": { "value": "0x1" }, "modules": [{ "logo_uri": null }] })
+    );
+}
+
+#[rocket::async_test]
+async fn snake_case_from_header() {
+    let client = client().await;
+
+    let response = {
+        let mut request = client.get("/safe");
+        request.add_header(Header::new(SERIALIZATION_PROFILE_HEADER, "snake_case"));
+        request.dispatch().await
+    };
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.into_string().await.unwrap()).unwrap(),
+        json!({ "chain_id": "4", "fallback_handler": { "value": "0x1" }, "modules": [{ "logo_uri": null }] })
+    );
+}
+
+#[rocket::async_test]
+async fn non_json_responses_are_kept() {
+    let client = client().await;
+
+    let response = client
+        .get("/plain?serialization=snake_case")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.headers().get_one("Vary"), None);
+    assert_eq!(response.into_string().await.unwrap(), "chainId");
+}
@@ -0,0 +1,91 @@
+use crate::utils::errors::ApiError;
+use crate::utils::http_client::Response;
+use crate::utils::shadowing::{compare, mirrored_url, status_of};
+use std::collections::HashMap;
+
+fn upstreams() -> HashMap<String, String> {
+    let mut upstreams = HashMap::new();
+    upstreams.insert(
+        String::from("https://safe-transaction.rinkeby.gnosis.io/"),
+        String::from("https://safe-transaction.rinkeby.staging.gnosisdev.com"),
+    );
+    upstreams.insert(
+        String::from("https://safe-transaction.rinkeby.gnosis.io/api/v2"),
+        String::from("https://canary.gnosisdev.com/api/v2"),
+    );
+    upstreams
+}
+
+#[test]
+fn mirrored_url_replaces_base_uri() {
+    let actual = mirrored_url(
+        &upstreams(),
+        "https://safe-transaction.rinkeby.gnosis.io/api/v1/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/?limit=1",
+    );
+
+    assert_eq!(
+        actual,
+        Some(String::from("https://safe-transaction.rinkeby.staging.gnosisdev.com/api/v1/safes/0x1230B3d59858296A31053C1b8562Ecf89A2f888b/?limit=1"))
+    );
+}
+
+#[test]
+fn mirrored_url_longest_base_uri_wins() {
+    let actual = mirrored_url(
+        &upstreams(),
+        "https://safe-transaction.rinkeby.gnosis.io/api/v2/safes/",
+    );
+
+    assert_eq!(
+        actual,
+        Some(String::from("https://canary.gnosisdev.com/api/v2/safes/"))
+    );
+}
+
+#[test]
+fn mirrored_url_other_hosts_are_not_mirrored() {
+    let upstreams = upstreams();
+
+    assert_eq!(
+        mirrored_url(
+            &upstreams,
+            "https://safe-transaction.rinkeby.gnosis.io.example.com/api/v1/safes/"
+        ),
+        None
+    );
+    assert_eq!(
+        mirrored_url(&upstreams, "https://safe-config.gnosis.io/api/v1/chains/"),
+        None
+    );
+}
+
+#[test]
+fn status_of_responses_and_errors() {
+    let response = Ok(Response {
+        body: String::from("{}"),
+        status_code: 200,
+    });
+    let error = Err(ApiError::new_from_message_with_code(
+        404,
+        String::from("Not found"),
+    ));
+
+    assert_eq!(status_of(&response), 200);
+    assert_eq!(status_of(&error), 404);
+}
+
+#[test]
+fn compare_reports_diverging_statuses() {
+    assert!(!compare(
+        "https://upstream/api",
+        200,
+        "https://canary/api",
+        200
+    ));
+    assert!(compare(
+        "https://upstream/api",
+        200,
+        "https://canary/api",
+        500
+    ));
+}
@@ -0,0 +1,97 @@
+use crate::utils::signatures::{safe_signature, SafeSignature, SignatureFailure};
+use crate::utils::transactions::decode_hex;
+
+const SAFE_TX_HASH: &str = "0xb15e7ef6cc6ac0bc9ae3c0a505aa065c2b43503e8d2f1c7af96b1dab33af3d79";
+// Signed with the private key 0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318
+const OWNER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+fn signature_of(signature: &str) -> Result<SafeSignature, SignatureFailure> {
+    safe_signature(
+        &decode_hex(signature).unwrap(),
+        &decode_hex(SAFE_TX_HASH).unwrap(),
+    )
+}
+
+#[test]
+fn safe_signature_ecdsa() {
+    let actual = signature_of("0x9377c312145a5afb911bf9e8c067bcf6094c533603687850df502b61290bbf5e84f31f90276dd2aa7573c6b6167f84978e285c71bf722da53f37170f24f82e9f1c");
+
+    assert_eq!(
+        actual,
+        Ok(SafeSignature::Ecdsa {
+            owner: String::from(OWNER)
+        })
+    );
+}
+
+#[test]
+fn safe_signature_eth_sign() {
+    let actual = signature_of("0xd08b48e31486ab4fb674edc647e1ddf56b554aa8f67c990ed0578c18a6b051fb90c837dcaa97e40362b8285ead4b40bf5fd5228c0ad4a666fd748bd860fc674f1f");
+
+    assert_eq!(
+        actual,
+        Ok(SafeSignature::EthSign {
+            owner: String::from(OWNER)
+        })
+    );
+}
+
+#[test]
+fn safe_signature_ecdsa_with_other_hash_recovers_other_signer() {
+    let actual = safe_signature(
+        &decode_hex("0x9377c312145a5afb911bf9e8c067bcf6094c533603687850df502b61290bbf5e84f31f90276dd2aa7573c6b6167f84978e285c71bf722da53f37170f24f82e9f1c").unwrap(),
+        &[0u8; 32],
+    )
+    .unwrap();
+
+    assert_ne!(actual.owner(), OWNER);
+}
+
+#[test]
+fn safe_signature_approved_hash() {
+    let actual = signature_of("0x0000000000000000000000002c7536e3605d9c16a7a3d7b1898e529396a65c23000000000000000000000000000000000000000000000000000000000000000001");
+
+    assert_eq!(
+        actual,
+        Ok(SafeSignature::ApprovedHash {
+            owner: String::from(OWNER)
+        })
+    );
+}
+
+#[test]
+fn safe_signature_contract() {
+    let actual = signature_of("0x0000000000000000000000002c7536e3605d9c16a7a3d7b1898e529396a65c230000000000000000000000000000000000000000000000000000000000000041000000000000000000000000000000000000000000000000000000000000000003aabbcc");
+
+    assert_eq!(
+        actual,
+        Ok(SafeSignature::Contract {
+            owner: String::from(OWNER),
+            signature: vec![0xaa, 0xbb, 0xcc],
+        })
+    );
+}
+
+#[test]
+fn safe_signature_contract_out_of_bounds() {
+    let actual = signature_of("0x0000000000000000000000002c7536e3605d9c16a7a3d7b1898e529396a65c230000000000000000000000000000000000000000000000000000000000000041000000000000000000000000000000000000000000000000000000000000000004aabbcc");
+
+    assert_eq!(actual, Err(SignatureFailure::Malformed));
+}
+
+#[test]
+fn safe_signature_malformed() {
+    assert_eq!(signature_of("0x1234"), Err(SignatureFailure::Malformed));
+    assert_eq!(
+        signature_of("0x9377c312145a5afb911bf9e8c067bcf6094c533603687850df502b61290bbf5e84f31f90276dd2aa7573c6b6167f84978e285c71bf722da53f37170f24f82e9f05"),
+        Err(SignatureFailure::Malformed)
+    );
+}
+
+#[test]
+fn safe_signature_not_recoverable() {
+    // s = 0
+    let actual = signature_of("0x9377c312145a5afb911bf9e8c067bcf6094c533603687850df502b61290bbf5e00000000000000000000000000000000000000000000000000000000000000001b");
+
+    assert_eq!(actual, Err(SignatureFailure::NotRecoverable));
+}
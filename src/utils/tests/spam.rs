@@ -0,0 +1,116 @@
+use crate::providers::info::{TokenInfo, TokenType};
+use crate::utils::spam::{is_spam_balance, is_spam_collectible, is_spam_token, spam_name_regex};
+use serde_json::json;
+
+fn token_info(token_type: TokenType, name: &str, symbol: &str) -> TokenInfo {
+    TokenInfo {
+        token_type,
+        address: "0xD6801a1DfFCd0a410336Ef88DeF4320D6DF1883e".to_string(),
+        decimals: 18,
+        symbol: symbol.to_string(),
+        name: name.to_string(),
+        logo_uri: None,
+    }
+}
+
+#[test]
+fn is_spam_token_name_patterns() {
+    let address = "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88";
+
+    assert!(is_spam_token(address, "Visit www.free-tokens.io", "FREE"));
+    assert!(is_spam_token(address, "Uniswap", "Claim rewards"));
+    assert!(!is_spam_token(address, "Compound Ether 📈", "cETH"));
+}
+
+#[test]
+fn is_spam_token_names_containing_patterns_in_words() {
+    let address = "0x63704B63Ac04f3a173Dfe677C7e3D330c347CD88";
+
+    assert!(!is_spam_token(address, "Reclaim Protocol", "RECLAIM"));
+    assert!(!is_spam_token(address, "Visitors DAO", "VISITOR"));
+    assert!(!is_spam_token(address, "Dot.Community", "DOTC"));
+    assert!(!is_spam_token(address, "Airdropped Bond", "ABOND"));
+    assert!(!is_spam_token(address, "Voucherify", "VCHR"));
+}
+
+#[test]
+fn spam_name_regex_whole_words() {
+    let regex = spam_name_regex(&[String::from("claim"), String::from(".com")]).unwrap();
+
+    assert!(regex.is_match("CLAIM your tokens"));
+    assert!(regex.is_match("rewards-claim"));
+    assert!(regex.is_match("free-tokens.com"));
+    assert!(!regex.is_match("Claimable"));
+    assert!(!regex.is_match("Reclaim"));
+    assert!(!regex.is_match("Compound"));
+    assert!(!regex.is_match("dot.company"));
+}
+
+#[test]
+fn spam_name_regex_escapes_patterns() {
+    let regex = spam_name_regex(&[String::from("www.")]).unwrap();
+
+    assert!(regex.is_match("www.scam.xyz"));
+    assert!(!regex.is_match("wwwx"));
+}
+
+#[test]
+fn spam_name_regex_without_patterns() {
+    assert!(spam_name_regex(&[]).is_none());
+}
+
+#[test]
+fn is_spam_token_denylist() {
+    std::env::set_var(
+        "SPAM_TOKEN_DENYLIST",
+        "0x1111111111111111111111111111111111111111, 0x2222222222222222222222222222222222222222",
+    );
+
+    assert!(is_spam_token(
+        "0x2222222222222222222222222222222222222222",
+        "Wrapped Ether",
+        "WETH"
+    ));
+    assert!(!is_spam_token(
+        "0x3333333333333333333333333333333333333333",
+        "Wrapped Ether",
+        "WETH"
+    ));
+
+    std::env::remove_var("SPAM_TOKEN_DENYLIST");
+}
+
+#[test]
+fn is_spam_balance_by_token() {
+    let spam = token_info(TokenType::Erc20, "Visit claim-rewards.xyz", "CLAIM");
+    let legit = token_info(TokenType::Erc20, "Compound Ether", "cETH");
+
+    assert!(is_spam_balance(&spam));
+    assert!(!is_spam_balance(&legit));
+}
+
+#[test]
+fn is_spam_balance_never_native_token() {
+    let token = token_info(TokenType::NativeToken, "Visit claim-rewards.xyz", "ETH");
+
+    assert!(!is_spam_balance(&token));
+}
+
+#[test]
+fn is_spam_collectible_raw_json() {
+    let spam = json!({
+        "address": "0xD753e03c05533F85bA9695C139771b1E9698a53C",
+        "tokenName": "Main",
+        "tokenSymbol": "JOSE",
+        "name": "Claim your prize at https://scam.xyz"
+    });
+    let legit = json!({
+        "address": "0xD753e03c05533F85bA9695C139771b1E9698a53C",
+        "tokenName": "Main",
+        "tokenSymbol": "JOSE",
+        "name": "Chiken dinner"
+    });
+
+    assert!(is_spam_collectible(&spam));
+    assert!(!is_spam_collectible(&legit));
+}
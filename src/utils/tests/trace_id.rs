@@ -0,0 +1,105 @@
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{HttpClient, MockHttpClient, Request, Response};
+use crate::utils::trace_id::{is_valid, new_trace_id, TraceIds, TracedHttpClient};
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use std::sync::Arc;
+
+#[get("/trace-id/error")]
+fn failing_route() -> ApiResult<String> {
+    Err(client_error!(422, "Invalid request"))
+}
+
+async fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![failing_route])
+        .attach(TraceIds());
+    Client::tracked(rocket)
+        .await
+        .expect("valid rocket instance")
+}
+
+#[test]
+fn trace_id_validation() {
+    assert!(is_valid("0123456789abcdef0123456789abcdef"));
+    assert!(is_valid("lb-7f3c_2"));
+    assert!(!is_valid(""));
+    assert!(!is_valid("id with spaces"));
+    assert!(!is_valid(&"a".repeat(65)));
+    assert!(is_valid(&new_trace_id()));
+    assert_eq!(new_trace_id().len(), 32);
+}
+
+#[rocket::async_test]
+async fn trace_id_in_header_and_error_body() {
+    let client = client().await;
+
+    let response = client.get("/trace-id/error").dispatch().await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let trace_id = response
+        .headers()
+        .get_one("X-Trace-Id")
+        .expect("trace id header")
+        .to_string();
+    let body: serde_json::Value =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    assert!(is_valid(&trace_id));
+    assert_eq!(body["traceId"], serde_json::Value::String(trace_id));
+    assert_eq!(body["message"], "Invalid request");
+}
+
+#[rocket::async_test]
+async fn trace_id_of_client_is_kept() {
+    let client = client().await;
+
+    let response = client
+        .get("/trace-id/error")
+        .header(Header::new("X-Trace-Id", "client-trace-1"))
+        .dispatch()
+        .await;
+
+    assert_eq!(
+        response.headers().get_one("X-Trace-Id"),
+        Some("client-trace-1")
+    );
+}
+
+#[rocket::async_test]
+async fn invalid_trace_id_of_client_is_replaced() {
+    let client = client().await;
+
+    let response = client
+        .get("/trace-id/error")
+        .header(Header::new("X-Trace-Id", "not a trace id"))
+        .dispatch()
+        .await;
+
+    let trace_id = response.headers().get_one("X-Trace-Id").unwrap();
+    assert_ne!(trace_id, "not a trace id");
+    assert!(is_valid(trace_id));
+}
+
+#[rocket::async_test]
+async fn traced_http_client_forwards_trace_id() {
+    let mut expected = Request::new(String::from("https://example.com"));
+    expected.trace_id(Some(String::from("trace-1")));
+    let mut mock_http_client = MockHttpClient::new();
+    mock_http_client
+        .expect_get()
+        .times(1)
+        .withf(move |request| request == &expected)
+        .return_once(|_| {
+            Ok(Response {
+                status_code: 200,
+                body: String::from("{}"),
+            })
+        });
+    let traced = TracedHttpClient::new(Arc::new(mock_http_client), String::from("trace-1"));
+
+    let actual = traced
+        .get(Request::new(String::from("https://example.com")))
+        .await;
+
+    assert!(actual.is_ok());
+}
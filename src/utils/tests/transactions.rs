@@ -1,6 +1,9 @@
+use crate::common::models::backend::transactions::{Confirmation, MultisigTransaction};
 use crate::utils::transactions::{
-    cancellation_parts_hash, domain_hash_v100, domain_hash_v130, hash, use_legacy_domain_separator,
+    cancellation_parts_hash, collected_signatures, domain_hash_v100, domain_hash_v130,
+    exec_transaction_data, hash, safe_tx_hash, use_legacy_domain_separator, SafeTransactionFields,
 };
+use chrono::Utc;
 use ethabi::Address;
 use ethcontract_common::hash::keccak256;
 use semver::Version;
@@ -99,3 +102,142 @@ fn use_legacy_domain_separator_legacy() {
 
     assert_eq!(true, use_legacy_domain_separator(version));
 }
+
+#[test]
+fn exec_transaction_data_with_signatures() {
+    let multisig_tx = serde_json::from_str::<MultisigTransaction>(
+        crate::tests::json::MULTISIG_TX_AWAITING_EXECUTION,
+    )
+    .unwrap();
+
+    let actual = exec_transaction_data(&multisig_tx).unwrap();
+
+    assert_eq!(EXPECTED_EXEC_TRANSACTION_DATA, actual);
+}
+
+#[test]
+fn exec_transaction_data_from_confirmations() {
+    let mut multisig_tx = serde_json::from_str::<MultisigTransaction>(
+        crate::tests::json::MULTISIG_TX_AWAITING_EXECUTION,
+    )
+    .unwrap();
+    multisig_tx.signatures = None;
+    multisig_tx.confirmations.as_mut().unwrap().reverse();
+
+    let actual = exec_transaction_data(&multisig_tx).unwrap();
+
+    assert_eq!(EXPECTED_EXEC_TRANSACTION_DATA, actual);
+}
+
+fn confirmation(owner: &str, signature_type: &str, signature: String) -> Confirmation {
+    Confirmation {
+        owner: String::from(owner),
+        submission_date: Utc::now(),
+        transaction_hash: None,
+        signature_type: String::from(signature_type),
+        signature: Some(signature),
+    }
+}
+
+#[test]
+fn collected_signatures_appends_dynamic_parts_of_contract_signatures() {
+    let mut multisig_tx = serde_json::from_str::<MultisigTransaction>(
+        crate::tests::json::MULTISIG_TX_AWAITING_EXECUTION,
+    )
+    .unwrap();
+    multisig_tx.signatures = None;
+    let contract_signature = format!(
+        "0x{:0>64}{:064x}00{:064x}deadbeef",
+        "1111111111111111111111111111111111111111", 65, 4
+    );
+    let ecdsa_signature = format!("0x{}{}1f", "22".repeat(32), "33".repeat(32));
+    multisig_tx.confirmations = Some(vec![
+        confirmation(
+            "0x2222222222222222222222222222222222222222",
+            "ETH_SIGN",
+            ecdsa_signature,
+        ),
+        confirmation(
+            "0x1111111111111111111111111111111111111111",
+            "CONTRACT_SIGNATURE",
+            contract_signature,
+        ),
+    ]);
+
+    let actual = collected_signatures(&multisig_tx).unwrap();
+
+    let expected = format!(
+        "0x{:0>64}{:064x}00{}{}1f{:064x}deadbeef",
+        "1111111111111111111111111111111111111111",
+        130,
+        "22".repeat(32),
+        "33".repeat(32),
+        4
+    );
+    assert_eq!(to_hex_string!(actual), expected);
+}
+
+#[test]
+fn collected_signatures_invalid_contract_signature() {
+    let mut multisig_tx = serde_json::from_str::<MultisigTransaction>(
+        crate::tests::json::MULTISIG_TX_AWAITING_EXECUTION,
+    )
+    .unwrap();
+    multisig_tx.signatures = None;
+    // `s` points past the end of the signature
+    let signature = format!(
+        "0x{:0>64}{:064x}00",
+        "1111111111111111111111111111111111111111", 96
+    );
+    multisig_tx.confirmations = Some(vec![confirmation(
+        "0x1111111111111111111111111111111111111111",
+        "CONTRACT_SIGNATURE",
+        signature,
+    )]);
+
+    assert!(collected_signatures(&multisig_tx).is_err());
+}
+
+const EXPECTED_EXEC_TRANSACTION_DATA: &str = "0x6a7612020000000000000000000000001230b3d59858296a31053c1b8562ecf89a2f888b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000596100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000160000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000827744eff991bea8be03f31604da4cc38974fa8f447ed9f0a4b8ac00e0385e915d59cf901b260ff262be1ac876b779520f254c2cc85538017de4a51161832757451b000000000000000000000000f2cea96575d6b10f51d9af3b10e3e4e5738aa6bd000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000";
+
+#[test]
+fn safe_tx_hash_matches_transaction_service() {
+    let zero_address = "0x0000000000000000000000000000000000000000";
+    let fields = SafeTransactionFields {
+        to: "0xD9BA894E0097f8cC2BBc9D24D308b98e36dc6D02",
+        value: "0",
+        data: "0x095ea7b3000000000000000000000000ae9844f89d98c150f5e61bfc676d68b4921559900000000000000000000000000000000000000000000000000001c6bf52634000",
+        operation: 0,
+        safe_tx_gas: "43485",
+        base_gas: "0",
+        gas_price: "0",
+        gas_token: zero_address,
+        refund_receiver: zero_address,
+        nonce: 84,
+    };
+
+    let legacy = safe_tx_hash(
+        "4",
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        Some(Version::new(1, 1, 1)),
+        &fields,
+    )
+    .unwrap();
+    let v130 = safe_tx_hash(
+        "4",
+        "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+        Some(Version::new(1, 3, 0)),
+        &fields,
+    )
+    .unwrap();
+
+    // Same transaction as crate::tests::json::MULTISIG_TX_CUSTOM
+    assert_eq!(
+        legacy,
+        "0x65df8a1e5a40703d9c67d5df6f9b552d3830faf0507c3d7350ba3764d3a68621"
+    );
+    assert_eq!(
+        v130,
+        "0x80ffa0f09842ff2294aef07141c9f89271260040e17a209e68fea9135dcd18c8"
+    );
+}
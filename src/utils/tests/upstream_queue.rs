@@ -0,0 +1,92 @@
+use crate::utils::errors::ApiError;
+use crate::utils::upstream_queue::{is_shed_error, UpstreamQueue, UpstreamQueueStats};
+use rocket::tokio::sync::oneshot;
+use rocket::tokio::task::{yield_now, JoinHandle};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Takes a slot of `queue` until the returned sender is used
+async fn hold_slot(queue: &Arc<UpstreamQueue>) -> (oneshot::Sender<()>, JoinHandle<()>) {
+    let (release, released) = oneshot::channel::<()>();
+    let queue_clone = queue.clone();
+    let holder = rocket::tokio::spawn(async move {
+        queue_clone
+            .run(async move {
+                released.await.ok();
+                Ok::<(), ApiError>(())
+            })
+            .await
+            .unwrap();
+    });
+    while queue.stats().in_flight == 0 {
+        yield_now().await;
+    }
+    (release, holder)
+}
+
+#[rocket::async_test]
+async fn upstream_queue_runs_calls_with_free_slots() {
+    let queue = UpstreamQueue::new(2, Duration::from_millis(10));
+
+    let result = queue.run(async { Ok::<u64, ApiError>(1) }).await;
+
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(
+        queue.stats(),
+        UpstreamQueueStats {
+            max_concurrency: 2,
+            in_flight: 0,
+            queued: 0,
+            shed: 0,
+        }
+    );
+}
+
+#[rocket::async_test]
+async fn upstream_queue_sheds_calls_waiting_too_long() {
+    let queue = Arc::new(UpstreamQueue::new(1, Duration::from_millis(10)));
+    let (release, holder) = hold_slot(&queue).await;
+
+    let error = queue
+        .run(async { Ok::<(), ApiError>(()) })
+        .await
+        .unwrap_err();
+
+    assert!(is_shed_error(&error));
+    assert_eq!(error.status, 503);
+    assert_eq!(queue.stats().shed, 1);
+    assert_eq!(queue.stats().queued, 0);
+
+    release.send(()).unwrap();
+    holder.await.unwrap();
+    assert_eq!(queue.stats().in_flight, 0);
+    assert!(queue.run(async { Ok::<(), ApiError>(()) }).await.is_ok());
+}
+
+#[rocket::async_test]
+async fn upstream_queue_runs_queued_calls_once_a_slot_is_free() {
+    let queue = Arc::new(UpstreamQueue::new(1, Duration::from_secs(5)));
+    let (release, holder) = hold_slot(&queue).await;
+
+    let queue_clone = queue.clone();
+    let waiting =
+        rocket::tokio::spawn(
+            async move { queue_clone.run(async { Ok::<u64, ApiError>(2) }).await },
+        );
+    while queue.stats().queued == 0 {
+        yield_now().await;
+    }
+    release.send(()).unwrap();
+
+    assert_eq!(waiting.await.unwrap().unwrap(), 2);
+    holder.await.unwrap();
+    assert_eq!(queue.stats().queued, 0);
+    assert_eq!(queue.stats().shed, 0);
+}
+
+#[test]
+fn upstream_errors_are_not_shed_errors() {
+    let error = ApiError::new_from_message_with_code(503, String::from("Service unavailable"));
+
+    assert!(!is_shed_error(&error));
+}
@@ -0,0 +1,63 @@
+use crate::utils::safe_version::parse_base_version;
+use crate::utils::upstream_versions::{check_version, version_of, Compatibility, VersionRange};
+
+const SUPPORTED: &[VersionRange] = &[VersionRange {
+    min: "3.0.0",
+    below: "5.0.0",
+}];
+
+#[test]
+fn version_range_contains() {
+    let range = SUPPORTED[0];
+
+    assert!(range.contains(&parse_base_version("3.0.0").unwrap()));
+    assert!(range.contains(&parse_base_version("4.9.1").unwrap()));
+    assert!(!range.contains(&parse_base_version("2.9.9").unwrap()));
+    assert!(!range.contains(&parse_base_version("5.0.0").unwrap()));
+}
+
+#[test]
+fn check_version_compatible() {
+    assert_eq!(
+        check_version(Some("3.4.2"), SUPPORTED),
+        (Compatibility::Compatible, None)
+    );
+    assert_eq!(
+        check_version(Some("v4.1.0"), SUPPORTED),
+        (Compatibility::Compatible, None)
+    );
+}
+
+#[test]
+fn check_version_incompatible() {
+    assert_eq!(
+        check_version(Some("5.0.1"), SUPPORTED),
+        (
+            Compatibility::Incompatible,
+            Some(String::from(
+                "Version 5.0.1 is not supported, supported: >=3.0.0, <5.0.0"
+            ))
+        )
+    );
+}
+
+#[test]
+fn check_version_unknown() {
+    assert_eq!(
+        check_version(Some("latest"), SUPPORTED),
+        (
+            Compatibility::Unknown,
+            Some(String::from("Version latest is not a semantic version"))
+        )
+    );
+    assert_eq!(check_version(None, SUPPORTED).0, Compatibility::Unknown);
+}
+
+#[test]
+fn version_of_about_response() {
+    let body = r#"{"name": "Safe Transaction Service", "version": "3.4.2", "api_version": "v1"}"#;
+
+    assert_eq!(version_of(body), Some(String::from("3.4.2")));
+    assert_eq!(version_of(r#"{"name": "Safe Transaction Service"}"#), None);
+    assert_eq!(version_of("<html></html>"), None);
+}
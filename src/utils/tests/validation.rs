@@ -0,0 +1,131 @@
+use crate::routes::notifications::models::NotificationRegistrationRequest;
+use crate::routes::transactions::models::requests::MultisigTransactionRequest;
+use crate::utils::errors::{ApiError, ErrorDetails};
+use crate::utils::validation::{Validate, Validator, INVALID_REQUEST_BODY};
+
+const MULTISIG_TRANSACTION_REQUEST: &str = r#"{
+  "to": "0xBe8C10Dbf4c6148f9834C56C3331f8191f355552",
+  "value": "0",
+  "data": "0x",
+  "nonce": "39",
+  "operation": 0,
+  "safeTxGas": "0",
+  "baseGas": "0",
+  "gasPrice": "0",
+  "gasToken": "0x0000000000000000000000000000000000000000",
+  "refundReceiver": "0x0000000000000000000000000000000000000000",
+  "safeTxHash": "0x4b574e7c729db54b427dd17a6b2ae3481221642a9d61c52a53f77500d98ddc1d",
+  "sender": "0x1230B3d59858296A31053C1b8562Ecf89A2f888b",
+  "signature": "0x4b574e7c729db54b427dd17a6b2ae3481221642a9d61c52a53f77500d98ddc1d739c39dfb117619fb09a20e3f5070d018e62c37f89fb622ae10b56a6be9af5c11b",
+  "origin": "{\"url\": \"https://apps.gnosis-safe.io/tx-builder\", \"name\": \"Transaction Builder\"}"
+}"#;
+
+#[test]
+fn valid_multisig_transaction_request() {
+    let request =
+        serde_json::from_str::<MultisigTransactionRequest>(MULTISIG_TRANSACTION_REQUEST).unwrap();
+
+    assert_eq!(request.validated(), Ok(()));
+}
+
+#[test]
+fn invalid_multisig_transaction_request_lists_every_field() {
+    let mut request =
+        serde_json::from_str::<MultisigTransactionRequest>(MULTISIG_TRANSACTION_REQUEST).unwrap();
+    request.to = String::from("0xBe8C10Dbf4c6148f9834C56C3331f819");
+    request.value = String::from("-1");
+    request.data = Some(String::from("0x123"));
+    request.safe_tx_hash =
+        String::from("4b574e7c729db54b427dd17a6b2ae3481221642a9d61c52a53f77500d98ddc1d");
+    request.signature = Some(String::from("0x"));
+    request.origin = Some("a".repeat(201));
+
+    let expected = ApiError::new_validation_error(vec![
+        String::from("to: must be a 0x prefixed address"),
+        String::from("value: must be an unsigned integer"),
+        String::from("data: must be 0x prefixed hex data"),
+        String::from("safeTxHash: must be a 0x prefixed 32 bytes hash"),
+        String::from("signature: must be 0x prefixed 65 bytes signatures"),
+        String::from("origin: must be between 0 and 200 characters"),
+    ]);
+
+    assert_eq!(request.validated(), Err(expected));
+}
+
+#[test]
+fn invalid_notification_registration_reports_nested_fields() {
+    let request = serde_json::from_str::<NotificationRegistrationRequest>(
+        r#"{
+          "uuid": "c50750df-700c-4b17-98ca-b95a5c27ca18",
+          "cloudMessagingToken": "",
+          "bundle": "io.gnosis.safe.debug",
+          "version": "2.13.0",
+          "deviceType": "ANDROID",
+          "buildNumber": "703",
+          "timestamp": "1618906387",
+          "safeRegistrations": [
+            {
+              "chainId": "1",
+              "safes": ["0x00E17aA063fbDB3BFdEfc2c3b2c13173d2711a35"],
+              "signatures": ["0x4b574e7c729db54b427dd17a6b2ae3481221642a9d61c52a53f77500d98ddc1d739c39dfb117619fb09a20e3f5070d018e62c37f89fb622ae10b56a6be9af5c11b"]
+            },
+            {
+              "chainId": "rinkeby",
+              "safes": [
+                "0x00E17aA063fbDB3BFdEfc2c3b2c13173d2711a35",
+                "0x00e17Aa063FbDB3bFdEfC2c3b2C13173D2711A3"
+              ],
+              "signatures": []
+            }
+          ]
+        }"#,
+    )
+    .unwrap();
+
+    let mut validator = Validator::default();
+    request.validate(&mut validator);
+
+    assert_eq!(
+        validator.errors(),
+        &[
+            String::from("cloudMessagingToken: must be between 1 and 200 characters"),
+            String::from("safeRegistrations[1].chainId: must be an unsigned integer"),
+            String::from("safeRegistrations[1].safes[1]: must be a 0x prefixed address"),
+        ]
+    );
+}
+
+#[test]
+fn validation_error_details() {
+    let actual =
+        ApiError::new_validation_error(vec![String::from("to: must be a 0x prefixed address")]);
+
+    let expected = ApiError {
+        status: 422,
+        details: ErrorDetails {
+            code: 1337,
+            message: Some(String::from(INVALID_REQUEST_BODY)),
+            arguments: Some(vec![String::from("to: must be a 0x prefixed address")]),
+            debug: None,
+        },
+    };
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn validator_range() {
+    let mut validator = Validator::default();
+    validator
+        .range("limit", 0, 1, 20)
+        .range("offset", 5, 0, 10)
+        .range("nonce", 21, 1, 20);
+
+    assert_eq!(
+        validator.errors(),
+        &[
+            String::from("limit: must be between 1 and 20"),
+            String::from("nonce: must be between 1 and 20"),
+        ]
+    );
+}
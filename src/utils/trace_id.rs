@@ -0,0 +1,116 @@
+//! Correlation id of every request, returned in the `X-Trace-Id` response header, added to error
+//! bodies and logs and forwarded to upstream services, so that reported issues can be traced.
+//! Valid ids sent by clients (or proxies in front of the gateway) are kept.
+use crate::utils::errors::{ApiResult, ErrorDetails};
+use crate::utils::http_client::{BinaryResponse, HttpClient, Request, Response};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::Data;
+use std::sync::Arc;
+
+pub const TRACE_ID_HEADER: &str = "X-Trace-Id";
+const MAX_TRACE_ID_LENGTH: usize = 64;
+
+/// `None` if the request was not seen by the [TraceIds] fairing
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceId(pub Option<String>);
+
+impl TraceId {
+    pub fn of(request: &rocket::Request<'_>) -> Option<String> {
+        request.local_cache(|| TraceId(None)).0.clone()
+    }
+}
+
+pub fn is_valid(trace_id: &str) -> bool {
+    !trace_id.is_empty()
+        && trace_id.len() <= MAX_TRACE_ID_LENGTH
+        && trace_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn new_trace_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// JSON body of an error response, with the trace id of the request if there is one
+pub fn error_body(request: &rocket::Request<'_>, details: &ErrorDetails) -> Option<String> {
+    let mut body = serde_json::to_value(details).ok()?;
+    if let (Some(trace_id), Some(fields)) = (TraceId::of(request), body.as_object_mut()) {
+        fields.insert(String::from("traceId"), serde_json::Value::String(trace_id));
+    }
+    serde_json::to_string(&body).ok()
+}
+
+pub struct TraceIds();
+
+#[rocket::async_trait]
+impl Fairing for TraceIds {
+    fn info(&self) -> Info {
+        Info {
+            name: "TraceIds",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut rocket::Request<'_>, _data: &mut Data<'_>) {
+        let trace_id = request
+            .headers()
+            .get_one(TRACE_ID_HEADER)
+            .filter(|trace_id| is_valid(trace_id))
+            .map(String::from)
+            .unwrap_or_else(new_trace_id);
+        request.local_cache(|| TraceId(Some(trace_id)));
+    }
+
+    async fn on_response<'r>(
+        &self,
+        request: &'r rocket::Request<'_>,
+        response: &mut rocket::Response<'r>,
+    ) {
+        if let Some(trace_id) = TraceId::of(request) {
+            response.set_header(Header::new(TRACE_ID_HEADER, trace_id));
+        }
+    }
+}
+
+/// Forwards the trace id of the incoming request on every call of the wrapped client
+pub struct TracedHttpClient {
+    http_client: Arc<dyn HttpClient>,
+    trace_id: String,
+}
+
+impl TracedHttpClient {
+    pub fn new(http_client: Arc<dyn HttpClient>, trace_id: String) -> Self {
+        TracedHttpClient {
+            http_client,
+            trace_id,
+        }
+    }
+
+    fn traced(&self, mut request: Request) -> Request {
+        request.trace_id(Some(self.trace_id.to_string()));
+        request
+    }
+}
+
+#[rocket::async_trait]
+impl HttpClient for TracedHttpClient {
+    async fn get(&self, request: Request) -> ApiResult<Response> {
+        self.http_client.get(self.traced(request)).await
+    }
+
+    async fn post(&self, request: Request) -> ApiResult<Response> {
+        self.http_client.post(self.traced(request)).await
+    }
+
+    async fn delete(&self, request: Request) -> ApiResult<Response> {
+        self.http_client.delete(self.traced(request)).await
+    }
+
+    async fn get_binary(&self, request: Request, max_size: usize) -> ApiResult<BinaryResponse> {
+        self.http_client
+            .get_binary(self.traced(request), max_size)
+            .await
+    }
+}
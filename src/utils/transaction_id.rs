@@ -0,0 +1,94 @@
+use crate::routes::transactions::models::{
+    TransactionIdParts, ID_PREFIX_CREATION_TX, ID_PREFIX_ETHEREUM_TX, ID_PREFIX_MODULE_TX,
+    ID_PREFIX_MULTISIG_TX, ID_SEPARATOR,
+};
+use crate::utils::errors::ApiResult;
+
+/// Builds the gateway transaction id, `<type>_<safe_address>_<upstream ids...>`
+pub fn build_id(id_parts: &TransactionIdParts) -> String {
+    match id_parts {
+        TransactionIdParts::Creation(safe_address) => {
+            create_id!(ID_PREFIX_CREATION_TX, safe_address)
+        }
+        TransactionIdParts::Multisig {
+            safe_address,
+            safe_tx_hash,
+        } => create_id!(ID_PREFIX_MULTISIG_TX, safe_address, safe_tx_hash),
+        TransactionIdParts::Module {
+            safe_address,
+            transaction_hash,
+            details_hash,
+        } => create_id!(
+            ID_PREFIX_MODULE_TX,
+            safe_address,
+            transaction_hash,
+            details_hash
+        ),
+        TransactionIdParts::Ethereum {
+            safe_address,
+            transaction_hash,
+            details_hash,
+        } => create_id!(
+            ID_PREFIX_ETHEREUM_TX,
+            safe_address,
+            transaction_hash,
+            details_hash
+        ),
+        TransactionIdParts::TransactionHash(safe_tx_hash) => safe_tx_hash.to_string(),
+    }
+}
+
+/// Splits a gateway transaction id into the identifiers of the core services.
+/// Anything without a known prefix is treated as a `safe_tx_hash`.
+pub fn parse_id(details_id: &str) -> ApiResult<TransactionIdParts> {
+    let id_parts: Vec<&str> = details_id.split(ID_SEPARATOR).collect();
+    let tx_type = id_parts.get(0).ok_or(api_error!("Invalid id"))?;
+
+    Ok(match tx_type.to_owned() {
+        ID_PREFIX_MULTISIG_TX => TransactionIdParts::Multisig {
+            safe_address: id_parts
+                .get(1)
+                .ok_or(client_error!(422, "No safe address provided"))?
+                .to_string(),
+            safe_tx_hash: id_parts
+                .get(2)
+                .ok_or(client_error!(422, "No safe tx hash provided"))?
+                .to_string(),
+        },
+        ID_PREFIX_ETHEREUM_TX => TransactionIdParts::Ethereum {
+            safe_address: id_parts
+                .get(1)
+                .ok_or(client_error!(422, "No safe address"))?
+                .to_string(),
+            transaction_hash: id_parts
+                .get(2)
+                .ok_or(client_error!(422, "No ethereum tx hash"))?
+                .to_string(),
+            details_hash: id_parts
+                .get(3)
+                .ok_or(client_error!(422, "No ethereum tx details hash"))?
+                .to_string(),
+        },
+        ID_PREFIX_MODULE_TX => TransactionIdParts::Module {
+            safe_address: id_parts
+                .get(1)
+                .ok_or(client_error!(422, "No safe address"))?
+                .to_string(),
+            transaction_hash: id_parts
+                .get(2)
+                .ok_or(client_error!(422, "No module tx hash"))?
+                .to_string(),
+            details_hash: id_parts
+                .get(3)
+                .ok_or(client_error!(422, "No module tx details hash"))?
+                .to_string(),
+        },
+        ID_PREFIX_CREATION_TX => TransactionIdParts::Creation(
+            id_parts
+                .get(1)
+                .ok_or(client_error!(422, "No safe address provided"))?
+                .to_string(),
+        ),
+        &_ => TransactionIdParts::TransactionHash(tx_type.to_string()),
+    })
+}
@@ -5,6 +5,8 @@ use crate::providers::info::SAFE_V_1_3_0;
 use crate::providers::info::{DefaultInfoProvider, InfoProvider};
 use crate::utils::context::RequestContext;
 use crate::utils::errors::ApiResult;
+use crate::utils::safe_version::parse_base_version;
+use crate::utils::signatures::contract_signature;
 use ethabi::ethereum_types::H256;
 use ethabi::{Address, Uint};
 use ethcontract_common::hash::keccak256;
@@ -20,6 +22,9 @@ pub const SAFE_TX_TYPEHASH: &'static str =
 pub const ERC191_BYTE: &'static str = "19";
 pub const ERC191_VERSION: &'static str = "01";
 
+pub const EXEC_TRANSACTION_SIGNATURE: &'static str =
+    "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)";
+
 pub async fn fetch_rejections(
     context: &RequestContext,
     chain_id: &str,
@@ -32,8 +37,12 @@ pub async fn fetch_rejections(
         .await
         .ok()
         .as_ref()
-        .and_then(|safe_info| safe_info.version.as_ref().map(|it| Version::parse(it).ok()))
-        .flatten();
+        .and_then(|safe_info| {
+            safe_info
+                .version
+                .as_ref()
+                .and_then(|it| parse_base_version(it))
+        });
     let is_legacy = use_legacy_domain_separator(version);
     let safe_address: Address =
         serde_json::from_value(serde_json::value::Value::String(safe_address.to_string())).unwrap();
@@ -60,12 +69,16 @@ pub async fn fetch_rejections(
 }
 
 pub(super) fn hash(safe_address: Address, nonce: u64, domain_hash: [u8; 32]) -> [u8; 32] {
+    erc191_hash(domain_hash, cancellation_parts_hash(&safe_address, nonce))
+}
+
+fn erc191_hash(domain_hash: [u8; 32], struct_hash: [u8; 32]) -> [u8; 32] {
     let erc_191_byte = u8::from_str_radix(ERC191_BYTE, 16).unwrap();
     let erc_191_version = u8::from_str_radix(ERC191_VERSION, 16).unwrap();
 
     let mut encoded = ethabi::encode(&[
         ethabi::Token::Uint(Uint::from(domain_hash)),
-        ethabi::Token::Uint(Uint::from(cancellation_parts_hash(&safe_address, nonce))),
+        ethabi::Token::Uint(Uint::from(struct_hash)),
     ]);
 
     encoded.insert(0, erc_191_version);
@@ -122,7 +135,80 @@ pub(super) fn cancellation_parts_hash(safe_address: &Address, nonce: u64) -> [u8
     keccak256(encoded_parts)
 }
 
-pub(super) fn use_legacy_domain_separator(version: Option<Version>) -> bool {
+/// Fields of a Safe transaction covered by its `safeTxHash`, uints as decimal strings
+pub struct SafeTransactionFields<'f> {
+    pub to: &'f str,
+    pub value: &'f str,
+    pub data: &'f str,
+    pub operation: u8,
+    pub safe_tx_gas: &'f str,
+    pub base_gas: &'f str,
+    pub gas_price: &'f str,
+    pub gas_token: &'f str,
+    pub refund_receiver: &'f str,
+    pub nonce: u64,
+}
+
+/// Hash signed by the owners, the domain separator depends on the `version` of the Safe
+pub fn safe_tx_hash(
+    chain_id: &str,
+    safe_address: &str,
+    version: Option<Version>,
+    fields: &SafeTransactionFields,
+) -> ApiResult<String> {
+    let parts = safe_tx_hash_parts(
+        chain_id,
+        safe_address,
+        use_legacy_domain_separator(version),
+        fields,
+    )?;
+    Ok(to_hex_string!(parts.safe_tx_hash))
+}
+
+/// `safeTxHash` and the hashes it is built from, the ones shown by hardware wallets
+pub struct SafeTxHashParts {
+    pub domain_hash: [u8; 32],
+    pub message_hash: [u8; 32],
+    pub safe_tx_hash: [u8; 32],
+}
+
+pub fn safe_tx_hash_parts(
+    chain_id: &str,
+    safe_address: &str,
+    legacy_domain_separator: bool,
+    fields: &SafeTransactionFields,
+) -> ApiResult<SafeTxHashParts> {
+    let safe_address = parse_address(safe_address)?;
+    let domain_hash = if legacy_domain_separator {
+        domain_hash_v100(&safe_address)
+    } else {
+        domain_hash_v130(chain_id, &safe_address)
+    };
+    let safe_type_hash: H256 =
+        serde_json::from_value(serde_json::Value::String(SAFE_TX_TYPEHASH.into())).unwrap();
+
+    let message_hash = keccak256(ethabi::encode(&[
+        ethabi::Token::Uint(Uint::from(safe_type_hash.0)),
+        ethabi::Token::Address(parse_address(fields.to)?),
+        ethabi::Token::Uint(parse_uint(Some(fields.value))?),
+        ethabi::Token::Uint(Uint::from(keccak256(decode_hex(fields.data)?))),
+        ethabi::Token::Uint(Uint::from(fields.operation)),
+        ethabi::Token::Uint(parse_uint(Some(fields.safe_tx_gas))?),
+        ethabi::Token::Uint(parse_uint(Some(fields.base_gas))?),
+        ethabi::Token::Uint(parse_uint(Some(fields.gas_price))?),
+        ethabi::Token::Address(parse_address(fields.gas_token)?),
+        ethabi::Token::Address(parse_address(fields.refund_receiver)?),
+        ethabi::Token::Uint(Uint::from(fields.nonce)),
+    ]));
+
+    Ok(SafeTxHashParts {
+        domain_hash,
+        message_hash,
+        safe_tx_hash: erc191_hash(domain_hash, message_hash),
+    })
+}
+
+pub(crate) fn use_legacy_domain_separator(version: Option<Version>) -> bool {
     if let Some(version) = version.as_ref() {
         version < &SAFE_V_1_3_0
     } else {
@@ -130,6 +216,117 @@ pub(super) fn use_legacy_domain_separator(version: Option<Version>) -> bool {
     }
 }
 
+/// Encodes the `execTransaction` call data for a multisig transaction.
+/// For non executed transactions the collected confirmations are used as signatures,
+/// sorted by owner address as expected by the Safe contract.
+pub fn exec_transaction_data(multisig_tx: &MultisigTransaction) -> ApiResult<String> {
+    let safe_transaction = &multisig_tx.safe_transaction;
+    let zero_address = String::from("0x0000000000000000000000000000000000000000");
+
+    let mut encoded = keccak256(EXEC_TRANSACTION_SIGNATURE.as_bytes())[..4].to_vec();
+    encoded.extend(ethabi::encode(&[
+        ethabi::Token::Address(parse_address(&safe_transaction.to)?),
+        ethabi::Token::Uint(parse_uint(safe_transaction.value.as_deref())?),
+        ethabi::Token::Bytes(decode_hex(
+            safe_transaction.data.as_deref().unwrap_or("0x"),
+        )?),
+        ethabi::Token::Uint(Uint::from(safe_transaction.operation as u8)),
+        ethabi::Token::Uint(Uint::from(multisig_tx.safe_tx_gas.unwrap_or(0))),
+        ethabi::Token::Uint(Uint::from(multisig_tx.base_gas.unwrap_or(0))),
+        ethabi::Token::Uint(parse_uint(multisig_tx.gas_price.as_deref())?),
+        ethabi::Token::Address(parse_address(
+            multisig_tx.gas_token.as_ref().unwrap_or(&zero_address),
+        )?),
+        ethabi::Token::Address(parse_address(
+            multisig_tx
+                .refund_receiver
+                .as_ref()
+                .unwrap_or(&zero_address),
+        )?),
+        ethabi::Token::Bytes(collected_signatures(multisig_tx)?),
+    ]));
+
+    Ok(to_hex_string!(encoded))
+}
+
+/// Signatures of the confirmations as `checkNSignatures` reads them: the static parts (`r`, `s`
+/// and `v`) sorted by owner, followed by the dynamic parts of contract signatures (`v` 0), whose
+/// `s` is rewritten to the offset of their dynamic part
+pub(super) fn collected_signatures(multisig_tx: &MultisigTransaction) -> ApiResult<Vec<u8>> {
+    if let Some(signatures) = multisig_tx.signatures.as_ref() {
+        return decode_hex(signatures);
+    }
+    let mut confirmations: Vec<_> = multisig_tx
+        .confirmations
+        .as_ref()
+        .map(|confirmations| confirmations.iter().collect())
+        .unwrap_or_default();
+    confirmations.sort_by_key(|confirmation| confirmation.owner.to_lowercase());
+
+    let mut static_parts: Vec<Vec<u8>> = vec![];
+    let mut dynamic_parts: Vec<Option<Vec<u8>>> = vec![];
+    for confirmation in confirmations {
+        if let Some(signature) = confirmation.signature.as_ref() {
+            let signature = decode_hex(signature)?;
+            if signature.len() < 65 {
+                bail!("Invalid signature of {}", confirmation.owner);
+            }
+            let dynamic_part = if signature[64] == 0 {
+                let offset = Uint::from_big_endian(&signature[32..64]);
+                Some(contract_signature(&signature, &offset).ok_or_else(|| {
+                    api_error!("Invalid contract signature of {}", confirmation.owner)
+                })?)
+            } else {
+                None
+            };
+            static_parts.push(signature[..65].to_vec());
+            dynamic_parts.push(dynamic_part);
+        }
+    }
+
+    let mut offset = 65 * static_parts.len();
+    let mut signatures = vec![];
+    let mut dynamic_data = vec![];
+    for (mut static_part, dynamic_part) in static_parts.into_iter().zip(dynamic_parts) {
+        if let Some(dynamic_part) = dynamic_part {
+            Uint::from(offset).to_big_endian(&mut static_part[32..64]);
+            let mut length = [0u8; 32];
+            Uint::from(dynamic_part.len()).to_big_endian(&mut length);
+            offset += length.len() + dynamic_part.len();
+            dynamic_data.extend_from_slice(&length);
+            dynamic_data.extend(dynamic_part);
+        }
+        signatures.extend(static_part);
+    }
+    signatures.extend(dynamic_data);
+    Ok(signatures)
+}
+
+pub(crate) fn parse_address(address: &str) -> ApiResult<Address> {
+    Ok(serde_json::from_value(serde_json::Value::String(
+        address.to_string(),
+    ))?)
+}
+
+pub(crate) fn parse_uint(value: Option<&str>) -> ApiResult<Uint> {
+    Uint::from_dec_str(value.unwrap_or("0"))
+        .map_err(|_| api_error!("Invalid uint value: {:?}", value))
+}
+
+pub(crate) fn decode_hex(value: &str) -> ApiResult<Vec<u8>> {
+    let hex = value.trim_start_matches("0x");
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        bail!("Invalid hex value: {}", value);
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| {
+            u8::from_str_radix(&hex[index..index + 2], 16)
+                .map_err(|_| api_error!("Invalid hex value: {}", value))
+        })
+        .collect()
+}
+
 // We silently fail if the cancellation transaction is not found
 async fn fetch_cancellation_tx(
     context: &RequestContext,
@@ -0,0 +1,167 @@
+//! Bounds the upstream calls in flight on this instance with `UPSTREAM_MAX_CONCURRENCY`, so that
+//! traffic spikes don't pile up requests to the upstream services. Calls exceeding the limit wait
+//! for a slot up to `UPSTREAM_QUEUE_TIMEOUT` and are shed with a 503 (and a `Retry-After`) after
+//! that.
+use crate::config::{upstream_max_concurrency, upstream_queue_timeout};
+use crate::utils::errors::{ApiError, ApiResult, ErrorDetails};
+use crate::utils::http_client::{BinaryResponse, HttpClient, Request, Response};
+use lazy_static::lazy_static;
+use rocket::tokio::sync::Semaphore;
+use rocket::tokio::time::timeout;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Not a failure of the upstream services, like the exceeded call budget
+const UPSTREAM_QUEUE_FULL_CODE: u64 = 1513;
+
+lazy_static! {
+    static ref UPSTREAM_QUEUE: Arc<UpstreamQueue> = Arc::new(UpstreamQueue::new(
+        upstream_max_concurrency(),
+        Duration::from_millis(upstream_queue_timeout()),
+    ));
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamQueueStats {
+    pub max_concurrency: usize,
+    pub in_flight: usize,
+    /// Calls waiting for a slot
+    pub queued: usize,
+    /// Calls that didn't get a slot in time, since the instance started
+    pub shed: u64,
+}
+
+pub struct UpstreamQueue {
+    max_concurrency: usize,
+    max_wait: Duration,
+    slots: Semaphore,
+    queued: AtomicUsize,
+    shed: AtomicU64,
+}
+
+/// Counts a call as queued for as long as it waits, also when the waiting call is dropped
+struct Queued<'a>(&'a AtomicUsize);
+
+impl<'a> Queued<'a> {
+    fn enter(queued: &'a AtomicUsize) -> Self {
+        queued.fetch_add(1, Ordering::Relaxed);
+        Queued(queued)
+    }
+}
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl UpstreamQueue {
+    pub fn new(max_concurrency: usize, max_wait: Duration) -> Self {
+        UpstreamQueue {
+            max_concurrency,
+            max_wait,
+            slots: Semaphore::new(max_concurrency),
+            queued: AtomicUsize::new(0),
+            shed: AtomicU64::new(0),
+        }
+    }
+
+    /// Runs `call` once a slot is free
+    pub async fn run<T>(&self, call: impl Future<Output = ApiResult<T>>) -> ApiResult<T> {
+        let _slot = match self.slots.try_acquire() {
+            Ok(slot) => slot,
+            Err(_) => {
+                let _queued = Queued::enter(&self.queued);
+                match timeout(self.max_wait, self.slots.acquire()).await {
+                    Ok(Ok(slot)) => slot,
+                    _ => {
+                        self.shed.fetch_add(1, Ordering::Relaxed);
+                        return Err(shed_error());
+                    }
+                }
+            }
+        };
+        call.await
+    }
+
+    pub fn stats(&self) -> UpstreamQueueStats {
+        UpstreamQueueStats {
+            max_concurrency: self.max_concurrency,
+            in_flight: self.max_concurrency - self.slots.available_permits(),
+            queued: self.queued.load(Ordering::Relaxed),
+            shed: self.shed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn shed_error() -> ApiError {
+    ApiError {
+        status: 503,
+        details: ErrorDetails {
+            code: UPSTREAM_QUEUE_FULL_CODE,
+            message: Some(String::from(
+                "Too many upstream calls in flight, retry later",
+            )),
+            arguments: None,
+            debug: None,
+        },
+    }
+}
+
+pub fn is_shed_error(error: &ApiError) -> bool {
+    error.status == 503 && error.details.code == UPSTREAM_QUEUE_FULL_CODE
+}
+
+/// Seconds clients should wait before retrying a shed request, at least 1
+pub fn retry_after() -> u64 {
+    ((upstream_queue_timeout() + 999) / 1000).max(1)
+}
+
+/// State of the queue of this instance
+pub fn stats() -> UpstreamQueueStats {
+    UPSTREAM_QUEUE.stats()
+}
+
+/// `http_client` behind the queue of this instance, unchanged without `UPSTREAM_MAX_CONCURRENCY`
+pub fn queued(http_client: Arc<dyn HttpClient>) -> Arc<dyn HttpClient> {
+    if upstream_max_concurrency() == 0 {
+        return http_client;
+    }
+    Arc::new(QueuedHttpClient::new(http_client, UPSTREAM_QUEUE.clone()))
+}
+
+pub struct QueuedHttpClient {
+    http_client: Arc<dyn HttpClient>,
+    queue: Arc<UpstreamQueue>,
+}
+
+impl QueuedHttpClient {
+    pub fn new(http_client: Arc<dyn HttpClient>, queue: Arc<UpstreamQueue>) -> Self {
+        QueuedHttpClient { http_client, queue }
+    }
+}
+
+#[rocket::async_trait]
+impl HttpClient for QueuedHttpClient {
+    async fn get(&self, request: Request) -> ApiResult<Response> {
+        self.queue.run(self.http_client.get(request)).await
+    }
+
+    async fn post(&self, request: Request) -> ApiResult<Response> {
+        self.queue.run(self.http_client.post(request)).await
+    }
+
+    async fn delete(&self, request: Request) -> ApiResult<Response> {
+        self.queue.run(self.http_client.delete(request)).await
+    }
+
+    async fn get_binary(&self, request: Request, max_size: usize) -> ApiResult<BinaryResponse> {
+        self.queue
+            .run(self.http_client.get_binary(request, max_size))
+            .await
+    }
+}
@@ -0,0 +1,257 @@
+//! Checks the version of the transaction service of every chain against the versions the gateway
+//! supports, at liftoff and every `UPSTREAM_VERSION_CHECK_INTERVAL`, so that operators learn about
+//! breaking upstream deploys before users do. Incompatible versions are logged as warnings, the
+//! result of the last check of this instance is served by `/about/chains-status`.
+use crate::cache::Cache;
+use crate::config::upstream_version_check_interval;
+use crate::providers::info::{DefaultInfoProvider, InfoProvider};
+use crate::routes::chains::handlers::{chain_id_of, get_all_chains};
+use crate::utils::context::RequestContext;
+use crate::utils::errors::ApiResult;
+use crate::utils::http_client::{HttpClient, Request};
+use crate::utils::safe_version::parse_base_version;
+use chrono::Utc;
+use lazy_static::lazy_static;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::futures::{stream, StreamExt};
+use rocket::{Orbit, Rocket};
+use semver::Version;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+// Chains checked at the same time
+const CHECK_CONCURRENCY: usize = 5;
+
+/// Transaction service releases the gateway is known to work with
+pub const SUPPORTED_TRANSACTION_SERVICE_VERSIONS: &[VersionRange] = &[VersionRange {
+    min: "3.0.0",
+    below: "5.0.0",
+}];
+
+lazy_static! {
+    static ref CHAINS_STATUS: RwLock<Vec<ChainStatus>> = RwLock::new(vec![]);
+}
+
+/// Versions from `min` (inclusive) to `below` (exclusive)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VersionRange {
+    pub min: &'static str,
+    pub below: &'static str,
+}
+
+impl VersionRange {
+    pub fn contains(&self, version: &Version) -> bool {
+        match (Version::parse(self.min), Version::parse(self.below)) {
+            (Ok(min), Ok(below)) => *version >= min && *version < below,
+            _ => false,
+        }
+    }
+
+    pub fn to_requirement(&self) -> String {
+        format!(">={}, <{}", self.min, self.below)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Compatibility {
+    Compatible,
+    Incompatible,
+    /// The version could not be read
+    Unknown,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStatus {
+    pub chain_id: String,
+    pub transaction_service: Option<String>,
+    pub version: Option<String>,
+    pub compatibility: Compatibility,
+    pub warning: Option<String>,
+    /// In ms
+    pub checked_at: i64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainsStatus {
+    pub supported_versions: Vec<String>,
+    pub chains: Vec<ChainStatus>,
+}
+
+/// Result of the last check, empty until the first check finished
+pub fn chains_status() -> ChainsStatus {
+    ChainsStatus {
+        supported_versions: SUPPORTED_TRANSACTION_SERVICE_VERSIONS
+            .iter()
+            .map(VersionRange::to_requirement)
+            .collect(),
+        chains: CHAINS_STATUS.read().unwrap().clone(),
+    }
+}
+
+/// Compatibility of a transaction service reporting `version`, with the warning for operators
+pub fn check_version(
+    version: Option<&str>,
+    supported: &[VersionRange],
+) -> (Compatibility, Option<String>) {
+    let version = match version {
+        Some(version) => version,
+        None => {
+            return (
+                Compatibility::Unknown,
+                Some(String::from(
+                    "The transaction service did not report its version",
+                )),
+            )
+        }
+    };
+    match parse_base_version(version.trim_start_matches('v')) {
+        Some(parsed) if supported.iter().any(|range| range.contains(&parsed)) => {
+            (Compatibility::Compatible, None)
+        }
+        Some(_) => (
+            Compatibility::Incompatible,
+            Some(format!(
+                "Version {} is not supported, supported: {}",
+                version,
+                supported
+                    .iter()
+                    .map(VersionRange::to_requirement)
+                    .collect::<Vec<String>>()
+                    .join(" or ")
+            )),
+        ),
+        None => (
+            Compatibility::Unknown,
+            Some(format!("Version {} is not a semantic version", version)),
+        ),
+    }
+}
+
+/// Checks the transaction service of every chain and stores the result
+pub async fn check_chains(context: &RequestContext) -> ApiResult<Vec<ChainStatus>> {
+    let chain_ids: Vec<String> = get_all_chains(context)
+        .await?
+        .iter()
+        .filter_map(|chain| chain_id_of(chain).map(String::from))
+        .collect();
+    let mut statuses: Vec<ChainStatus> = stream::iter(chain_ids)
+        .map(|chain_id| check_chain(context, chain_id))
+        .buffer_unordered(CHECK_CONCURRENCY)
+        .collect()
+        .await;
+    statuses.sort_by(|left, right| left.chain_id.cmp(&right.chain_id));
+
+    for status in statuses.iter() {
+        if status.compatibility != Compatibility::Compatible {
+            log::warn!(
+                "Transaction service of chain {} ({}): {}",
+                status.chain_id,
+                status.transaction_service.as_deref().unwrap_or("unknown"),
+                status.warning.as_deref().unwrap_or("")
+            );
+        }
+    }
+    *CHAINS_STATUS.write().unwrap() = statuses.clone();
+    Ok(statuses)
+}
+
+async fn check_chain(context: &RequestContext, chain_id: String) -> ChainStatus {
+    // A context per chain, the lookups of a context are memoized one at a time
+    let context = RequestContext::new(
+        context.request_id.to_string(),
+        context.host.to_string(),
+        context.http_client(),
+        context.cache(),
+    );
+    let info_provider = DefaultInfoProvider::new(&chain_id, &context);
+    let transaction_service = info_provider
+        .chain_info()
+        .await
+        .ok()
+        .map(|chain_info| chain_info.transaction_service);
+    let (version, (compatibility, warning)) =
+        match transaction_service_version(&context, &info_provider).await {
+            Ok(version) => (
+                version.clone(),
+                check_version(version.as_deref(), SUPPORTED_TRANSACTION_SERVICE_VERSIONS),
+            ),
+            Err(error) => (
+                None,
+                (
+                    Compatibility::Unknown,
+                    Some(format!("The version could not be requested: {}", error)),
+                ),
+            ),
+        };
+    ChainStatus {
+        chain_id,
+        transaction_service,
+        version,
+        compatibility,
+        warning,
+        checked_at: Utc::now().timestamp_millis(),
+    }
+}
+
+async fn transaction_service_version(
+    context: &RequestContext,
+    info_provider: &DefaultInfoProvider<'_>,
+) -> ApiResult<Option<String>> {
+    let url = core_uri!(info_provider, "/v1/about/")?;
+    let response = context.http_client().get(Request::new(url)).await?;
+    Ok(version_of(&response.body))
+}
+
+/// `version` of the `/about/` response of the transaction service
+pub fn version_of(body: &str) -> Option<String> {
+    serde_json::from_str::<Value>(body)
+        .ok()?
+        .get("version")?
+        .as_str()
+        .map(String::from)
+}
+
+pub struct UpstreamVersionCheck();
+
+#[rocket::async_trait]
+impl Fairing for UpstreamVersionCheck {
+    fn info(&self) -> Info {
+        Info {
+            name: "UpstreamVersionCheck",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let interval = upstream_version_check_interval();
+        if interval == 0 {
+            return;
+        }
+        let (cache, http_client) = match (
+            rocket.state::<Arc<dyn Cache>>(),
+            rocket.state::<Arc<dyn HttpClient>>(),
+        ) {
+            (Some(cache), Some(http_client)) => (cache.clone(), http_client.clone()),
+            _ => return,
+        };
+        rocket::tokio::spawn(async move {
+            loop {
+                let context = RequestContext::new(
+                    String::from("upstream_version_check"),
+                    String::from("localhost"),
+                    http_client.clone(),
+                    cache.clone(),
+                );
+                if let Err(error) = check_chains(&context).await {
+                    log::warn!("Upstream versions could not be checked: {}", error);
+                }
+                rocket::tokio::time::sleep(Duration::from_millis(interval)).await;
+            }
+        });
+    }
+}
@@ -0,0 +1,182 @@
+//! Declarative validation of request bodies. Every invalid field is reported at once, in the
+//! `arguments` of a `422` error, so clients can fix their payload in a single round trip.
+use crate::utils::errors::{ApiError, ApiResult};
+
+pub const INVALID_REQUEST_BODY: &str = "Invalid request body";
+const ADDRESS_LENGTH: usize = 40;
+const HASH_LENGTH: usize = 64;
+const SIGNATURE_LENGTH: usize = 130;
+
+pub trait Validate {
+    fn validate(&self, validator: &mut Validator);
+
+    fn validated(&self) -> ApiResult<()> {
+        let mut validator = Validator::default();
+        self.validate(&mut validator);
+        validator.finish()
+    }
+}
+
+/// Collects the errors of an object, as `"<field>: <reason>"`
+#[derive(Default, Debug)]
+pub struct Validator {
+    errors: Vec<String>,
+}
+
+impl Validator {
+    /// Checksummed or not, `0x` prefixed 20 bytes
+    pub fn address(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(
+            field,
+            hex_digits(value).map_or(false, |digits| digits.len() == ADDRESS_LENGTH),
+            "must be a 0x prefixed address",
+        )
+    }
+
+    pub fn optional_address(&mut self, field: &str, value: &Option<String>) -> &mut Self {
+        match value {
+            Some(value) => self.address(field, value),
+            None => self,
+        }
+    }
+
+    pub fn hash(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(
+            field,
+            hex_digits(value).map_or(false, |digits| digits.len() == HASH_LENGTH),
+            "must be a 0x prefixed 32 bytes hash",
+        )
+    }
+
+    /// One or more concatenated 65 bytes signatures
+    pub fn signature(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(
+            field,
+            hex_digits(value).map_or(false, |digits| {
+                !digits.is_empty() && digits.len() % SIGNATURE_LENGTH == 0
+            }),
+            "must be 0x prefixed 65 bytes signatures",
+        )
+    }
+
+    pub fn optional_signature(&mut self, field: &str, value: &Option<String>) -> &mut Self {
+        match value {
+            Some(value) => self.signature(field, value),
+            None => self,
+        }
+    }
+
+    /// `0x` prefixed bytes, `0x` being empty data
+    pub fn hex_data(&mut self, field: &str, value: &Option<String>) -> &mut Self {
+        match value {
+            Some(value) => self.check(
+                field,
+                hex_digits(value).map_or(false, |digits| digits.len() % 2 == 0),
+                "must be 0x prefixed hex data",
+            ),
+            None => self,
+        }
+    }
+
+    /// Unsigned integer as a decimal string, as used for token amounts and gas values
+    pub fn uint(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(
+            field,
+            !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()),
+            "must be an unsigned integer",
+        )
+    }
+
+    pub fn optional_uint(&mut self, field: &str, value: &Option<String>) -> &mut Self {
+        match value {
+            Some(value) => self.uint(field, value),
+            None => self,
+        }
+    }
+
+    pub fn range(&mut self, field: &str, value: u64, min: u64, max: u64) -> &mut Self {
+        if value < min || value > max {
+            self.error(field, &format!("must be between {} and {}", min, max));
+        }
+        self
+    }
+
+    /// Length in characters
+    pub fn length(&mut self, field: &str, value: &str, min: usize, max: usize) -> &mut Self {
+        let length = value.chars().count();
+        if length < min || length > max {
+            self.error(
+                field,
+                &format!("must be between {} and {} characters", min, max),
+            );
+        }
+        self
+    }
+
+    pub fn optional_length(
+        &mut self,
+        field: &str,
+        value: &Option<String>,
+        min: usize,
+        max: usize,
+    ) -> &mut Self {
+        match value {
+            Some(value) => self.length(field, value, min, max),
+            None => self,
+        }
+    }
+
+    /// Validates `value` with its fields prefixed by `field`, e.g. `safeRegistrations[0].chainId`
+    pub fn nested(&mut self, field: &str, value: &impl Validate) -> &mut Self {
+        let mut nested = Validator::default();
+        value.validate(&mut nested);
+        self.errors.extend(
+            nested
+                .errors
+                .into_iter()
+                .map(|error| format!("{}.{}", field, error)),
+        );
+        self
+    }
+
+    pub fn each<T>(
+        &mut self,
+        field: &str,
+        values: &[T],
+        validate: impl Fn(&mut Self, &str, &T),
+    ) -> &mut Self {
+        for (index, value) in values.iter().enumerate() {
+            validate(self, &format!("{}[{}]", field, index), value);
+        }
+        self
+    }
+
+    pub fn check(&mut self, field: &str, is_valid: bool, reason: &str) -> &mut Self {
+        if !is_valid {
+            self.error(field, reason);
+        }
+        self
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    pub fn finish(self) -> ApiResult<()> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::new_validation_error(self.errors))
+        }
+    }
+
+    fn error(&mut self, field: &str, reason: &str) {
+        self.errors.push(format!("{}: {}", field, reason));
+    }
+}
+
+fn hex_digits(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("0x")
+        .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+}